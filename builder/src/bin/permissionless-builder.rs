@@ -1,10 +1,10 @@
 use std::{num::NonZeroUsize, path::PathBuf, time::Duration};
 
-use builder::non_permissioned::{build_instance_state, BuilderConfig};
+use builder::non_permissioned::{build_instance_state, BuilderConfig, MempoolLogConfig};
 use clap::Parser;
 use espresso_types::{eth_signature_key::EthKeyPair, parse_duration, SequencerVersions};
 use futures::future::pending;
-use hotshot::traits::ValidatedState;
+use hotshot_builder_core_refactored::selector::TransactionSelectionPolicy;
 use hotshot_types::{
     data::ViewNumber,
     traits::node_implementation::{ConsensusTime, Versions},
@@ -97,6 +97,37 @@ struct NonPermissionedBuilderOptions {
     )]
     tx_status_cache_size: usize,
 
+    /// Policy deciding which of the queued transactions go into a block.
+    ///
+    /// One of `fifo`, `round-robin` (take turns between namespaces) or
+    /// `quota:<bytes>` (limit the bytes each namespace may take up in a block).
+    #[clap(
+        long,
+        env = "ESPRESSO_BUILDER_TRANSACTION_SELECTION_POLICY",
+        default_value = "fifo"
+    )]
+    transaction_selection_policy: TransactionSelectionPolicy,
+
+    /// Path of a write-ahead log persisting transactions submitted to the builder's
+    /// private mempool, so that they survive restarts.
+    ///
+    /// Disabled if not set.
+    #[clap(
+        long,
+        env = "ESPRESSO_BUILDER_MEMPOOL_LOG",
+        requires = "mempool_log_query_url"
+    )]
+    mempool_log: Option<PathBuf>,
+
+    /// URL of a query service used to skip transactions recovered from the mempool log
+    /// that were sequenced while the builder was down.
+    #[clap(
+        long,
+        env = "ESPRESSO_BUILDER_MEMPOOL_LOG_QUERY_URL",
+        requires = "mempool_log"
+    )]
+    mempool_log_query_url: Option<Url>,
+
     /// Path to TOML file containing genesis state.
     #[clap(long, name = "GENESIS_FILE", env = "ESPRESSO_BUILDER_GENESIS_FILE")]
     genesis_file: PathBuf,
@@ -180,21 +211,18 @@ async fn run<V: Versions>(
     let base_fee = genesis.max_base_fee();
     tracing::info!(?base_fee, "base_fee");
 
-    let validated_state = ValidatedState::genesis(&instance_state).0;
-
     let api_response_timeout_duration = opt.max_api_timeout_duration;
 
     // make the txn timeout as 1/4 of the api_response_timeout_duration
     let txn_timeout_duration = api_response_timeout_duration / 4;
 
-    let _builder_config = BuilderConfig::init::<V>(
+    let _builder_config = BuilderConfig::init(
         builder_key_pair,
         bootstrapped_view,
         opt.tx_channel_capacity,
         opt.event_channel_capacity,
         opt.node_count,
-        instance_state.clone(),
-        validated_state,
+        instance_state,
         opt.hotshot_event_streaming_url,
         builder_server_url,
         api_response_timeout_duration,
@@ -202,6 +230,13 @@ async fn run<V: Versions>(
         txn_timeout_duration,
         base_fee,
         opt.tx_status_cache_size,
+        opt.transaction_selection_policy,
+        opt.mempool_log
+            .zip(opt.mempool_log_query_url)
+            .map(|(path, query_service_url)| MempoolLogConfig {
+                path,
+                query_service_url,
+            }),
     )
     .await?;
 
@@ -1,43 +1,5 @@
-use espresso_types::SeqTypes;
-use hotshot_builder_api::v0_1::builder::{
-    Error as BuilderApiError, Options as HotshotBuilderApiOptions,
-};
-use hotshot_builder_core::service::ProxyGlobalState;
-use sequencer::SequencerApiVersion;
-use tide_disco::{App, Url};
-use tokio::spawn;
-use vbs::version::{StaticVersion, StaticVersionType};
-
 pub mod non_permissioned;
 
-// It runs the api service for the builder
-pub fn run_builder_api_service(url: Url, source: ProxyGlobalState<SeqTypes>) {
-    // it is to serve hotshot
-    let builder_api = hotshot_builder_api::v0_1::builder::define_api::<
-        ProxyGlobalState<SeqTypes>,
-        SeqTypes,
-    >(&HotshotBuilderApiOptions::default())
-    .expect("Failed to construct the builder APIs");
-
-    // it enables external clients to submit txn to the builder's private mempool
-    let private_mempool_api = hotshot_builder_api::v0_1::builder::submit_api::<
-        ProxyGlobalState<SeqTypes>,
-        SeqTypes,
-        StaticVersion<0, 1>,
-    >(&HotshotBuilderApiOptions::default())
-    .expect("Failed to construct the builder API for private mempool txns");
-
-    let mut app: App<ProxyGlobalState<SeqTypes>, BuilderApiError> = App::with_state(source);
-
-    app.register_module("block_info", builder_api)
-        .expect("Failed to register the builder API");
-
-    app.register_module("txn_submit", private_mempool_api)
-        .expect("Failed to register the private mempool API");
-
-    spawn(app.serve(url, SequencerApiVersion::instance()));
-}
-
 #[cfg(test)]
 pub mod testing {
     use std::{
@@ -54,7 +16,7 @@ pub mod testing {
     use committable::Committable;
     use espresso_types::{
         traits::SequencerPersistence, v0_99::ChainConfig, Event, FeeAccount, NamespaceId,
-        NodeState, PrivKey, PubKey, Transaction, ValidatedState,
+        NodeState, PrivKey, PubKey, SeqTypes, Transaction,
     };
     use futures::stream::{Stream, StreamExt};
     use hotshot::{
@@ -85,9 +47,9 @@ pub mod testing {
     };
     use sequencer::{context::Consensus, network, SequencerApiVersion};
     use surf_disco::Client;
-    use vbs::version::StaticVersion;
+    use tide_disco::{App, Url};
+    use vbs::version::{StaticVersion, StaticVersionType};
 
-    use super::*;
     use crate::non_permissioned::BuilderConfig;
 
     #[derive(Clone)]
@@ -358,14 +320,13 @@ pub mod testing {
 
             let node_count = NonZeroUsize::new(num_nodes).unwrap();
 
-            let builder_config = BuilderConfig::init::<V>(
+            let builder_config = BuilderConfig::init(
                 key_pair,
                 bootstrapped_view,
                 tx_channel_capacity,
                 event_channel_capacity,
                 node_count,
                 NodeState::default().with_current_version(V::Base::VERSION),
-                ValidatedState::default(),
                 hotshot_events_streaming_api_url,
                 hotshot_builder_api_url,
                 Duration::from_millis(2000),
@@ -373,6 +334,8 @@ pub mod testing {
                 Duration::from_millis(500),
                 ChainConfig::default().base_fee,
                 819200,
+                Default::default(),
+                None,
             )
             .await
             .unwrap();
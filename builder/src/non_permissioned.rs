@@ -1,43 +1,44 @@
-use std::{collections::VecDeque, num::NonZeroUsize, sync::Arc, time::Duration};
+use std::{num::NonZeroUsize, path::PathBuf, sync::Arc, time::Duration};
 
 use anyhow::Context;
-use async_broadcast::broadcast;
 use async_lock::RwLock;
+use client::SequencerClient;
 use espresso_types::{
     eth_signature_key::EthKeyPair, v0_1::NoStorage, v0_99::ChainConfig, EpochCommittees, FeeAmount,
-    NodeState, Payload, SeqTypes, ValidatedState,
+    NodeState, SeqTypes, Transaction,
 };
-use hotshot::traits::BlockPayload;
-use hotshot_builder_core::{
-    builder_state::{BuilderState, MessageType},
-    service::{
-        run_non_permissioned_standalone_builder_service, GlobalState, ProxyGlobalState,
-        ReceivedTransaction,
-    },
+use hotshot_builder_core_refactored::{
+    selector::TransactionSelectionPolicy,
+    service::{BuilderConfig as ServiceConfig, GlobalState},
 };
 use hotshot_types::{
-    data::{fake_commitment, vid_commitment, ViewNumber},
+    data::ViewNumber,
     epoch_membership::EpochMembershipCoordinator,
-    traits::{
-        block_contents::GENESIS_VID_NUM_STORAGE_NODES, metrics::NoMetrics,
-        node_implementation::Versions, EncodeBytes,
-    },
+    traits::{metrics::NoMetrics, node_implementation::Versions},
 };
-use marketplace_builder_shared::{block::ParentBlockReferences, utils::EventServiceStream};
+use marketplace_builder_shared::{coordinator::mempool_log::MempoolLog, utils::EventServiceStream};
 use sequencer::{catchup::StatePeers, L1Params, SequencerApiVersion};
 use tide_disco::Url;
 use tokio::spawn;
 use vbs::version::StaticVersionType;
 
-use crate::run_builder_api_service;
-
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct BuilderConfig {
-    pub global_state: Arc<RwLock<GlobalState<SeqTypes>>>,
+    pub global_state: Arc<GlobalState<SeqTypes>>,
     pub hotshot_events_api_url: Url,
     pub hotshot_builder_apis_url: Url,
 }
 
+/// Configuration of the write-ahead log persisting the builder's private mempool across restarts
+#[derive(Clone, Debug)]
+pub struct MempoolLogConfig {
+    /// File the log is kept in
+    pub path: PathBuf,
+    /// Query service used to find out which of the recovered transactions
+    /// were sequenced while the builder was down
+    pub query_service_url: Url,
+}
+
 pub fn build_instance_state<V: Versions>(
     chain_config: ChainConfig,
     l1_params: L1Params,
@@ -76,16 +77,20 @@ pub fn build_instance_state<V: Versions>(
     )
 }
 
+/// Namespace of a transaction, for namespace-aware transaction selection policies
+fn transaction_namespace(tx: &Transaction) -> u64 {
+    tx.namespace().into()
+}
+
 impl BuilderConfig {
     #[allow(clippy::too_many_arguments)]
-    pub async fn init<V: Versions>(
+    pub async fn init(
         builder_key_pair: EthKeyPair,
         bootstrapped_view: ViewNumber,
         tx_channel_capacity: NonZeroUsize,
         event_channel_capacity: NonZeroUsize,
         node_count: NonZeroUsize,
         instance_state: NodeState,
-        validated_state: ValidatedState,
         hotshot_events_api_url: Url,
         hotshot_builder_apis_url: Url,
         max_api_timeout_duration: Duration,
@@ -93,6 +98,8 @@ impl BuilderConfig {
         maximize_txns_count_timeout_duration: Duration,
         base_fee: FeeAmount,
         tx_status_cache_size: usize,
+        transaction_selection_policy: TransactionSelectionPolicy,
+        mempool_log: Option<MempoolLogConfig>,
     ) -> anyhow::Result<Self> {
         tracing::info!(
             address = %builder_key_pair.fee_account(),
@@ -102,120 +109,82 @@ impl BuilderConfig {
             ?max_api_timeout_duration,
             ?instance_state.chain_config.max_block_size,
             ?maximize_txns_count_timeout_duration,
+            %transaction_selection_policy,
+            ?mempool_log,
             "initializing builder",
         );
 
-        // tx channel
-        let (mut tx_sender, tx_receiver) =
-            broadcast::<Arc<ReceivedTransaction<SeqTypes>>>(tx_channel_capacity.get());
-        tx_sender.set_overflow(true);
-
-        // da channel
-        let (da_sender, da_receiver) =
-            broadcast::<MessageType<SeqTypes>>(event_channel_capacity.get());
-
-        // qc channel
-        let (qc_sender, qc_receiver) =
-            broadcast::<MessageType<SeqTypes>>(event_channel_capacity.get());
-
-        // decide channel
-        let (decide_sender, decide_receiver) =
-            broadcast::<MessageType<SeqTypes>>(event_channel_capacity.get());
-
-        // builder api request channel
-        let (req_sender, req_receiver) =
-            broadcast::<MessageType<SeqTypes>>(event_channel_capacity.get());
-
-        let (genesis_payload, genesis_ns_table) =
-            Payload::from_transactions([], &validated_state, &instance_state)
-                .await
-                .expect("genesis payload construction failed");
-
-        let builder_commitment = genesis_payload.builder_commitment(&genesis_ns_table);
+        let mempool_log = mempool_log
+            .map(|config| {
+                let log = MempoolLog::open(&config.path)
+                    .with_context(|| format!("opening mempool log {}", config.path.display()))?;
+                anyhow::Ok((Arc::new(log), config.query_service_url))
+            })
+            .transpose()?;
 
-        let vid_commitment = {
-            let payload_bytes = genesis_payload.encode();
-            vid_commitment::<V>(
-                &payload_bytes,
-                &genesis_ns_table.encode(),
-                GENESIS_VID_NUM_STORAGE_NODES,
-                V::Base::VERSION,
-            )
-        };
+        let protocol_max_block_size = instance_state.chain_config.max_block_size.into();
 
         // create the global state
-        let global_state: GlobalState<SeqTypes> = GlobalState::<SeqTypes>::new(
-            req_sender,
-            tx_sender.clone(),
-            vid_commitment,
-            bootstrapped_view,
-            bootstrapped_view,
-            max_block_size_increment_period,
-            instance_state.chain_config.max_block_size.into(),
-            node_count.into(),
-            tx_status_cache_size,
-        );
-
-        let global_state = Arc::new(RwLock::new(global_state));
-        let global_state_clone = global_state.clone();
-
-        let builder_state = BuilderState::<SeqTypes, V>::new(
-            ParentBlockReferences {
-                view_number: bootstrapped_view,
-                vid_commitment,
-                leaf_commit: fake_commitment(),
-                builder_commitment,
-                tx_count: 0,
-                last_nonempty_view: None,
+        let global_state = GlobalState::new(
+            ServiceConfig {
+                builder_keys: (builder_key_pair.fee_account(), builder_key_pair),
+                max_api_waiting_time: max_api_timeout_duration,
+                max_block_size_increment_period,
+                maximize_txn_capture_timeout: maximize_txns_count_timeout_duration,
+                txn_garbage_collect_duration: Duration::from_secs(60),
+                txn_channel_capacity: tx_channel_capacity.get(),
+                tx_status_cache_capacity: tx_status_cache_size,
+                base_fee: base_fee
+                    .as_u64()
+                    .context("the base fee exceeds the maximum amount that a builder can pay (defined by u64::MAX)")?,
+                transaction_selector: transaction_selection_policy
+                    .selector(transaction_namespace),
+                mempool_log: mempool_log.as_ref().map(|(log, _)| Arc::clone(log)),
             },
-            decide_receiver,
-            da_receiver,
-            qc_receiver,
-            req_receiver,
-            tx_receiver,
-            VecDeque::new() /* tx_queue */,
-            global_state_clone,
-            maximize_txns_count_timeout_duration,
-            base_fee
-                .as_u64()
-                .context("the base fee exceeds the maximum amount that a builder can pay (defined by u64::MAX)")?,
-            Arc::new(instance_state),
-            Duration::from_secs(60),
-            Arc::new(validated_state),
+            instance_state,
+            protocol_max_block_size,
+            node_count.get(),
         );
 
-        // spawn the builder event loop
-        spawn(async move {
-            builder_state.event_loop();
-        });
-
-        // create the proxy global state it will server the builder apis
-        let proxy_global_state = ProxyGlobalState::new(
-            global_state.clone(),
-            (builder_key_pair.fee_account(), builder_key_pair),
-            max_api_timeout_duration,
-        );
+        if let Some((_, query_service_url)) = mempool_log {
+            let client = &SequencerClient::new(query_service_url);
+            let replayed = global_state
+                .replay_mempool_log(|commit| async move {
+                    match client.get_transaction_by_hash(commit).await {
+                        Ok(tx) => Some(tx.block_height()),
+                        Err(err) => {
+                            tracing::debug!(%commit, %err, "recovered transaction not sequenced");
+                            None
+                        },
+                    }
+                })
+                .await;
+            tracing::info!(replayed, "replayed transactions from mempool log");
+        }
 
         // start the hotshot api service
-        run_builder_api_service(hotshot_builder_apis_url.clone(), proxy_global_state);
+        let app = Arc::clone(&global_state)
+            .into_app()
+            .context("failed to construct the builder API")?;
+        spawn(app.serve(
+            hotshot_builder_apis_url.clone(),
+            SequencerApiVersion::instance(),
+        ));
 
         // spawn the builder service
         let events_url = hotshot_events_api_url.clone();
-        let global_state_clone = global_state.clone();
         tracing::info!("Running permissionless builder against hotshot events API at {events_url}",);
 
         let event_stream =
             EventServiceStream::<SeqTypes, SequencerApiVersion>::connect(events_url).await?;
 
+        let global_state_clone = Arc::clone(&global_state);
         spawn(async move {
-            let res = run_non_permissioned_standalone_builder_service::<_, SequencerApiVersion, _>(
-                da_sender,
-                qc_sender,
-                decide_sender,
-                event_stream,
-                global_state_clone,
-            )
-            .await;
+            let res = global_state_clone
+                .start_event_loop(event_stream)
+                .await
+                .context("builder event loop panicked")
+                .and_then(|res| res);
             tracing::error!(?res, "builder service exited");
             if res.is_err() {
                 panic!("Builder should restart.");
@@ -0,0 +1,297 @@
+//! Client for the `availability` API.
+
+use anyhow::Context;
+use committable::Commitment;
+use espresso_types::{
+    light_client::NamespaceInclusionProof, Header, NamespaceBlockQueryData, NamespaceId,
+    NamespaceProofQueryData, SeqTypes, Transaction,
+};
+use futures::{stream::BoxStream, try_join};
+use hotshot_query_service::{
+    availability::{
+        BlockQueryData, BlockSummaryQueryData, LeafQueryData, Limits, PayloadQueryData,
+        StateCertQueryData, TransactionQueryData, VidCommonQueryData,
+    },
+    types::HeightIndexed,
+};
+
+use crate::SequencerClient;
+
+impl SequencerClient {
+    /// Get the leaf at `height`.
+    pub async fn get_leaf(&self, height: u64) -> anyhow::Result<LeafQueryData<SeqTypes>> {
+        self.get(&format!("availability/leaf/{height}")).await
+    }
+
+    /// Get the leaves with heights in the range `[from, until)`.
+    pub async fn get_leaf_range(
+        &self,
+        from: u64,
+        until: u64,
+    ) -> anyhow::Result<Vec<LeafQueryData<SeqTypes>>> {
+        self.get(&format!("availability/leaf/{from}/{until}")).await
+    }
+
+    /// Get the header at `height`.
+    pub async fn get_header(&self, height: u64) -> anyhow::Result<Header> {
+        self.get(&format!("availability/header/{height}")).await
+    }
+
+    /// Get the header with the given block hash.
+    pub async fn get_header_by_hash(&self, hash: Commitment<Header>) -> anyhow::Result<Header> {
+        self.get(&format!("availability/header/hash/{hash}")).await
+    }
+
+    /// Get the headers with heights in the range `[from, until)`.
+    pub async fn get_header_range(&self, from: u64, until: u64) -> anyhow::Result<Vec<Header>> {
+        self.get(&format!("availability/header/{from}/{until}"))
+            .await
+    }
+
+    /// Get the block at `height`.
+    pub async fn get_block(&self, height: u64) -> anyhow::Result<BlockQueryData<SeqTypes>> {
+        self.get(&format!("availability/block/{height}")).await
+    }
+
+    /// Get the block with the given hash.
+    pub async fn get_block_by_hash(
+        &self,
+        hash: Commitment<Header>,
+    ) -> anyhow::Result<BlockQueryData<SeqTypes>> {
+        self.get(&format!("availability/block/hash/{hash}")).await
+    }
+
+    /// Get the blocks with heights in the range `[from, until)`.
+    pub async fn get_block_range(
+        &self,
+        from: u64,
+        until: u64,
+    ) -> anyhow::Result<Vec<BlockQueryData<SeqTypes>>> {
+        self.get(&format!("availability/block/{from}/{until}"))
+            .await
+    }
+
+    /// Get the payload of the block at `height`.
+    pub async fn get_payload(&self, height: u64) -> anyhow::Result<PayloadQueryData<SeqTypes>> {
+        self.get(&format!("availability/payload/{height}")).await
+    }
+
+    /// Get the payloads of the blocks with heights in the range `[from, until)`.
+    pub async fn get_payload_range(
+        &self,
+        from: u64,
+        until: u64,
+    ) -> anyhow::Result<Vec<PayloadQueryData<SeqTypes>>> {
+        self.get(&format!("availability/payload/{from}/{until}"))
+            .await
+    }
+
+    /// Get the VID common data for the block at `height`.
+    pub async fn get_vid_common(
+        &self,
+        height: u64,
+    ) -> anyhow::Result<VidCommonQueryData<SeqTypes>> {
+        self.get(&format!("availability/vid/common/{height}")).await
+    }
+
+    /// Get the transaction at position `index` in the block at `height`.
+    pub async fn get_transaction(
+        &self,
+        height: u64,
+        index: u64,
+    ) -> anyhow::Result<TransactionQueryData<SeqTypes>> {
+        self.get(&format!("availability/transaction/{height}/{index}"))
+            .await
+    }
+
+    /// Get the transaction with the given hash.
+    pub async fn get_transaction_by_hash(
+        &self,
+        hash: Commitment<Transaction>,
+    ) -> anyhow::Result<TransactionQueryData<SeqTypes>> {
+        self.get(&format!("availability/transaction/hash/{hash}"))
+            .await
+    }
+
+    /// Get a summary of the block at `height`.
+    pub async fn get_block_summary(
+        &self,
+        height: u64,
+    ) -> anyhow::Result<BlockSummaryQueryData<SeqTypes>> {
+        self.get(&format!("availability/block/summary/{height}"))
+            .await
+    }
+
+    /// Get summaries of the blocks with heights in the range `[from, until)`.
+    pub async fn get_block_summaries(
+        &self,
+        from: u64,
+        until: u64,
+    ) -> anyhow::Result<Vec<BlockSummaryQueryData<SeqTypes>>> {
+        self.get(&format!("availability/block/summaries/{from}/{until}"))
+            .await
+    }
+
+    /// Get the light client state update certificate for `epoch`.
+    pub async fn get_state_cert(&self, epoch: u64) -> anyhow::Result<StateCertQueryData<SeqTypes>> {
+        self.get(&format!("availability/state-cert/{epoch}")).await
+    }
+
+    /// Get the heights of blocks in the range `[from, until)` whose payloads were deleted by
+    /// namespace retention rules.
+    ///
+    /// Requests for the payloads of these blocks, and for the blocks and transactions which depend
+    /// on them, will not succeed.
+    pub async fn get_pruned_payloads(&self, from: u64, until: u64) -> anyhow::Result<Vec<u64>> {
+        self.get(&format!("availability/block/pruned/{from}/{until}"))
+            .await
+    }
+
+    /// Get the maximum range sizes supported by the availability API.
+    pub async fn get_availability_limits(&self) -> anyhow::Result<Limits> {
+        self.get("availability/limits").await
+    }
+
+    /// Get the transactions in namespace `ns_id` of the block at `height`, with a proof.
+    ///
+    /// The proof is not checked. Use [`get_verified_namespace`](Self::get_verified_namespace) to
+    /// check it against the block header.
+    pub async fn get_namespace(
+        &self,
+        height: u64,
+        ns_id: NamespaceId,
+    ) -> anyhow::Result<NamespaceProofQueryData> {
+        self.get(&format!("availability/block/{height}/namespace/{ns_id}"))
+            .await
+    }
+
+    /// Get the transactions in namespace `ns_id` for each block in the range `[from, until)`.
+    pub async fn get_namespace_range(
+        &self,
+        from: u64,
+        until: u64,
+        ns_id: NamespaceId,
+    ) -> anyhow::Result<Vec<NamespaceBlockQueryData>> {
+        self.get(&format!(
+            "availability/block/{from}/{until}/namespace/{ns_id}"
+        ))
+        .await
+    }
+
+    /// Get the transactions in namespace `ns_id` of the block at `height`, checking the proof.
+    ///
+    /// The proof is checked against the header and VID common data served by the same API, so this
+    /// protects against bugs and data corruption but not against a malicious server. Clients which
+    /// need the latter should check the header against a trusted source, such as the light client
+    /// contract.
+    pub async fn get_verified_namespace(
+        &self,
+        height: u64,
+        ns_id: NamespaceId,
+    ) -> anyhow::Result<NamespaceBlockQueryData> {
+        let (header, common, ns) = try_join!(
+            self.get_header(height),
+            self.get_vid_common(height),
+            self.get_namespace(height, ns_id),
+        )?;
+        ns.verify(&header, common.common(), ns_id)
+            .with_context(|| format!("verifying namespace {ns_id} at height {height}"))?;
+        Ok(NamespaceBlockQueryData::new(header, ns))
+    }
+
+    /// Get everything needed to prove the contents of namespace `ns_id` in the block at `height` to
+    /// a light client whose finalized block height is `finalized_height`.
+    ///
+    /// The result can be checked against the finalized state of the light client contract using
+    /// [`NamespaceInclusionProof::verify`].
+    pub async fn get_namespace_inclusion_proof(
+        &self,
+        finalized_height: u64,
+        height: u64,
+        ns_id: NamespaceId,
+    ) -> anyhow::Result<NamespaceInclusionProof> {
+        let (finalized, block_proof, header, common, ns) = try_join!(
+            self.get_header(finalized_height),
+            self.get_block_path(finalized_height, height),
+            self.get_header(height),
+            self.get_vid_common(height),
+            self.get_namespace(height, ns_id),
+        )?;
+        Ok(NamespaceInclusionProof {
+            block_merkle_root: finalized.block_merkle_tree_root(),
+            block_proof,
+            header,
+            vid_common: common.common().clone(),
+            ns_proof: ns.proof,
+        })
+    }
+
+    /// Stream leaves starting from `height`.
+    ///
+    /// The stream reconnects automatically if the connection is dropped.
+    pub fn stream_leaves(&self, height: u64) -> BoxStream<'static, LeafQueryData<SeqTypes>> {
+        self.reconnecting_stream(
+            "availability/stream/leaves".into(),
+            height,
+            HeightIndexed::height,
+        )
+    }
+
+    /// Stream headers starting from `height`.
+    ///
+    /// The stream reconnects automatically if the connection is dropped.
+    pub fn stream_headers(&self, height: u64) -> BoxStream<'static, Header> {
+        self.reconnecting_stream("availability/stream/headers".into(), height, Header::height)
+    }
+
+    /// Stream blocks starting from `height`.
+    ///
+    /// The stream reconnects automatically if the connection is dropped.
+    pub fn stream_blocks(&self, height: u64) -> BoxStream<'static, BlockQueryData<SeqTypes>> {
+        self.reconnecting_stream(
+            "availability/stream/blocks".into(),
+            height,
+            HeightIndexed::height,
+        )
+    }
+
+    /// Stream block payloads starting from `height`.
+    ///
+    /// The stream reconnects automatically if the connection is dropped.
+    pub fn stream_payloads(&self, height: u64) -> BoxStream<'static, PayloadQueryData<SeqTypes>> {
+        self.reconnecting_stream(
+            "availability/stream/payloads".into(),
+            height,
+            HeightIndexed::height,
+        )
+    }
+
+    /// Stream VID common data starting from `height`.
+    ///
+    /// The stream reconnects automatically if the connection is dropped.
+    pub fn stream_vid_common(
+        &self,
+        height: u64,
+    ) -> BoxStream<'static, VidCommonQueryData<SeqTypes>> {
+        self.reconnecting_stream(
+            "availability/stream/vid/common".into(),
+            height,
+            HeightIndexed::height,
+        )
+    }
+
+    /// Stream the transactions in namespace `ns_id` of each block, starting from `height`.
+    ///
+    /// The stream reconnects automatically if the connection is dropped.
+    pub fn stream_namespace(
+        &self,
+        height: u64,
+        ns_id: NamespaceId,
+    ) -> BoxStream<'static, NamespaceBlockQueryData> {
+        self.reconnecting_stream(
+            format!("availability/stream/namespace/{ns_id}"),
+            height,
+            |block| block.header.height(),
+        )
+    }
+}
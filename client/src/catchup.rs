@@ -0,0 +1,89 @@
+//! Client for the `catchup` API.
+
+use alloy::primitives::Address;
+use committable::Commitment;
+use espresso_types::{
+    v0_1::{RewardAccount, RewardAccountQueryData, RewardMerkleTree},
+    v0_99::ChainConfig,
+    AccountQueryData, BlockMerkleTree, FeeAccount, FeeMerkleTree, Leaf2,
+};
+use jf_merkle_tree::MerkleTreeScheme;
+
+use crate::SequencerClient;
+
+/// The path to the most recently appended leaf of the block Merkle tree.
+pub type BlocksFrontier = <BlockMerkleTree as MerkleTreeScheme>::MembershipProof;
+
+impl SequencerClient {
+    /// Get the balance of a fee account, with a proof, in the state at `height` and `view`.
+    pub async fn get_catchup_account(
+        &self,
+        height: u64,
+        view: u64,
+        address: Address,
+    ) -> anyhow::Result<AccountQueryData> {
+        self.get(&format!("catchup/{height}/{view}/account/{address:#x}"))
+            .await
+    }
+
+    /// Get a sparse fee Merkle tree containing `accounts`, in the state at `height` and `view`.
+    pub async fn get_catchup_accounts(
+        &self,
+        height: u64,
+        view: u64,
+        accounts: &[FeeAccount],
+    ) -> anyhow::Result<FeeMerkleTree> {
+        self.post_query(&format!("catchup/{height}/{view}/accounts"), &accounts)
+            .await
+    }
+
+    /// Get the balance of a reward account, with a proof, in the state at `height` and `view`.
+    pub async fn get_catchup_reward_account(
+        &self,
+        height: u64,
+        view: u64,
+        address: Address,
+    ) -> anyhow::Result<RewardAccountQueryData> {
+        self.get(&format!(
+            "catchup/{height}/{view}/reward-account/{address:#x}"
+        ))
+        .await
+    }
+
+    /// Get a sparse reward Merkle tree containing `accounts`, in the state at `height` and `view`.
+    pub async fn get_catchup_reward_accounts(
+        &self,
+        height: u64,
+        view: u64,
+        accounts: &[RewardAccount],
+    ) -> anyhow::Result<RewardMerkleTree> {
+        self.post_query(
+            &format!("catchup/{height}/{view}/reward-accounts"),
+            &accounts,
+        )
+        .await
+    }
+
+    /// Get the block Merkle tree frontier in the state at `height` and `view`.
+    pub async fn get_blocks_frontier(
+        &self,
+        height: u64,
+        view: u64,
+    ) -> anyhow::Result<BlocksFrontier> {
+        self.get(&format!("catchup/{height}/{view}/blocks")).await
+    }
+
+    /// Get the chain config with the given commitment.
+    pub async fn get_chain_config(
+        &self,
+        commitment: Commitment<ChainConfig>,
+    ) -> anyhow::Result<ChainConfig> {
+        self.get(&format!("catchup/chain-config/{commitment}"))
+            .await
+    }
+
+    /// Get a chain of leaves proving that the block at `height` was decided.
+    pub async fn get_leaf_chain(&self, height: u64) -> anyhow::Result<Vec<Leaf2>> {
+        self.get(&format!("catchup/{height}/leafchain")).await
+    }
+}
@@ -0,0 +1,19 @@
+//! Client for the `evidence` API.
+
+use espresso_types::SeqTypes;
+use hotshot_types::equivocation::EquivocationEvidence;
+
+use crate::SequencerClient;
+
+impl SequencerClient {
+    /// Get the evidence of equivocation the node recorded for views `from` (inclusive) to `to`
+    /// (exclusive).
+    pub async fn get_equivocation_evidence(
+        &self,
+        from: u64,
+        to: u64,
+    ) -> anyhow::Result<Vec<EquivocationEvidence<SeqTypes>>> {
+        self.get(&format!("evidence/equivocation/{from}/{to}"))
+            .await
+    }
+}
@@ -0,0 +1,180 @@
+//! Client for the `explorer` API.
+
+use alloy::primitives::Address;
+use committable::Commitment;
+use espresso_types::{
+    v0_3::{DelegatorDetailResponse, ValidatorDetailResponse, ValidatorsResponse},
+    Header, NamespaceId, PubKey, SeqTypes, Transaction,
+};
+use hotshot_query_service::explorer::{
+    BlockDetailResponse, BlockSummaryResponse, ExplorerSummaryResponse, NamespaceDetailResponse,
+    NamespaceOrder, NamespaceSummariesResponse, SearchResultResponse, TransactionDetailResponse,
+    TransactionSummariesResponse,
+};
+
+use crate::SequencerClient;
+
+impl SequencerClient {
+    /// Get the explorer view of the block at `height`.
+    pub async fn get_explorer_block(
+        &self,
+        height: u64,
+    ) -> anyhow::Result<BlockDetailResponse<SeqTypes>> {
+        self.get(&format!("explorer/block/{height}")).await
+    }
+
+    /// Get the explorer view of the block with the given hash.
+    pub async fn get_explorer_block_by_hash(
+        &self,
+        hash: Commitment<Header>,
+    ) -> anyhow::Result<BlockDetailResponse<SeqTypes>> {
+        self.get(&format!("explorer/block/hash/{hash}")).await
+    }
+
+    /// Get summaries of the latest `limit` blocks.
+    pub async fn get_explorer_latest_blocks(
+        &self,
+        limit: u64,
+    ) -> anyhow::Result<BlockSummaryResponse<SeqTypes>> {
+        self.get(&format!("explorer/blocks/latest/{limit}")).await
+    }
+
+    /// Get summaries of `limit` blocks, counting down from the block at `from`.
+    pub async fn get_explorer_blocks(
+        &self,
+        from: u64,
+        limit: u64,
+    ) -> anyhow::Result<BlockSummaryResponse<SeqTypes>> {
+        self.get(&format!("explorer/blocks/{from}/{limit}")).await
+    }
+
+    /// Get the explorer view of the transaction at position `offset` in the block at `height`.
+    pub async fn get_explorer_transaction(
+        &self,
+        height: u64,
+        offset: u64,
+    ) -> anyhow::Result<TransactionDetailResponse<SeqTypes>> {
+        self.get(&format!("explorer/transaction/{height}/{offset}"))
+            .await
+    }
+
+    /// Get the explorer view of the transaction with the given hash.
+    pub async fn get_explorer_transaction_by_hash(
+        &self,
+        hash: Commitment<Transaction>,
+    ) -> anyhow::Result<TransactionDetailResponse<SeqTypes>> {
+        self.get(&format!("explorer/transaction/hash/{hash}")).await
+    }
+
+    /// Get summaries of the latest `limit` transactions.
+    pub async fn get_explorer_latest_transactions(
+        &self,
+        limit: u64,
+    ) -> anyhow::Result<TransactionSummariesResponse<SeqTypes>> {
+        self.get(&format!("explorer/transactions/latest/{limit}"))
+            .await
+    }
+
+    /// Get summaries of `limit` transactions, counting down from position `offset` in the block at
+    /// `height`.
+    pub async fn get_explorer_transactions(
+        &self,
+        height: u64,
+        offset: u64,
+        limit: u64,
+    ) -> anyhow::Result<TransactionSummariesResponse<SeqTypes>> {
+        self.get(&format!(
+            "explorer/transactions/from/{height}/{offset}/{limit}"
+        ))
+        .await
+    }
+
+    /// Get summary statistics about the chain.
+    pub async fn get_explorer_summary(&self) -> anyhow::Result<ExplorerSummaryResponse<SeqTypes>> {
+        self.get("explorer/explorer-summary").await
+    }
+
+    /// Search for blocks and transactions matching `query`.
+    ///
+    /// `query` must be a TaggedBase64 string, such as a block or transaction hash.
+    pub async fn explorer_search(
+        &self,
+        query: &str,
+    ) -> anyhow::Result<SearchResultResponse<SeqTypes>> {
+        self.get(&format!("explorer/search/{query}")).await
+    }
+
+    /// Get up to `limit` of the most active namespaces over the last `window` seconds, ranked by
+    /// `order`.
+    pub async fn get_explorer_namespaces(
+        &self,
+        window: u64,
+        limit: u64,
+        order: NamespaceOrder,
+    ) -> anyhow::Result<NamespaceSummariesResponse> {
+        let order = match order {
+            NamespaceOrder::Transactions => "transactions",
+            NamespaceOrder::Bytes => "bytes",
+        };
+        self.get(&format!("explorer/namespaces/{window}/{limit}/by/{order}"))
+            .await
+    }
+
+    /// Get the activity of namespace `ns_id` over the last `window` seconds, split into `buckets`
+    /// intervals of equal length.
+    pub async fn get_explorer_namespace(
+        &self,
+        ns_id: NamespaceId,
+        window: u64,
+        buckets: u64,
+    ) -> anyhow::Result<NamespaceDetailResponse> {
+        self.get(&format!("explorer/namespace/{ns_id}/{window}/{buckets}"))
+            .await
+    }
+
+    /// Get the validators of `epoch`, or of the current epoch if `epoch` is `None`.
+    pub async fn get_explorer_validators(
+        &self,
+        epoch: Option<u64>,
+    ) -> anyhow::Result<ValidatorsResponse> {
+        match epoch {
+            Some(epoch) => self.get(&format!("explorer/validators/{epoch}")).await,
+            None => self.get("explorer/validators/current").await,
+        }
+    }
+
+    /// Get the stake table entry and activity of the validator with stake table key `validator`
+    /// in `epoch`, or in the current epoch if `epoch` is `None`.
+    pub async fn get_explorer_validator(
+        &self,
+        validator: PubKey,
+        epoch: Option<u64>,
+    ) -> anyhow::Result<ValidatorDetailResponse> {
+        match epoch {
+            Some(epoch) => {
+                self.get(&format!("explorer/validator/{validator}/epoch/{epoch}"))
+                    .await
+            },
+            None => self.get(&format!("explorer/validator/{validator}")).await,
+        }
+    }
+
+    /// Get the stake delegated by `delegator` to each validator in `epoch`, or in the current
+    /// epoch if `epoch` is `None`.
+    pub async fn get_explorer_delegator(
+        &self,
+        delegator: Address,
+        epoch: Option<u64>,
+    ) -> anyhow::Result<DelegatorDetailResponse> {
+        match epoch {
+            Some(epoch) => {
+                self.get(&format!("explorer/delegator/{delegator:#x}/epoch/{epoch}"))
+                    .await
+            },
+            None => {
+                self.get(&format!("explorer/delegator/{delegator:#x}"))
+                    .await
+            },
+        }
+    }
+}
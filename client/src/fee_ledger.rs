@@ -0,0 +1,39 @@
+//! Client for the `fee-ledger` API.
+
+use alloy::primitives::Address;
+use espresso_types::v0_1::{FeeLedgerPage, FeeStatement};
+
+use crate::SequencerClient;
+
+impl SequencerClient {
+    /// Get the changes to the fee balance of `address` made by blocks `from` (inclusive) to `to`
+    /// (exclusive).
+    pub async fn get_fee_statement(
+        &self,
+        address: Address,
+        from: u64,
+        to: u64,
+    ) -> anyhow::Result<FeeStatement> {
+        self.get(&format!("fee-ledger/statement/{address:#x}/{from}/{to}"))
+            .await
+    }
+
+    /// Get up to `limit` changes to the fee balance of `address`, newest first.
+    ///
+    /// If `before` is given, only entries older than the entry at that `(height, index)` are
+    /// returned. Pass the [`next`](FeeLedgerPage::next) field of a page to get the following page.
+    pub async fn get_fee_history(
+        &self,
+        address: Address,
+        limit: u64,
+        before: Option<(u64, u64)>,
+    ) -> anyhow::Result<FeeLedgerPage> {
+        let path = match before {
+            Some((height, index)) => {
+                format!("fee-ledger/history/{address:#x}/limit/{limit}/before/{height}/{index}")
+            },
+            None => format!("fee-ledger/history/{address:#x}/limit/{limit}"),
+        };
+        self.get(&path).await
+    }
+}
@@ -1,47 +1,126 @@
-use std::time::Duration;
+//! A typed client for the Espresso sequencer API.
+//!
+//! [`SequencerClient`] wraps the HTTP API served by a sequencer node, including the query service
+//! modules (`availability`, `node`, `explorer`, `status` and the merklized state modules) and the
+//! sequencer-specific modules (`submit`, `catchup`, `config`, `state-signature`,
+//! `reward-history`, `fee-ledger` and `evidence`). The separate node metrics service, which serves
+//! validator uptime and leaderboards, is not covered.
+//!
+//! Requests are made against a list of URLs. A request which fails because a node could not be
+//! reached, timed out or returned a server error is retried against the next URL; other errors,
+//! such as a rejected request, are returned immediately. Requests which change state on the node,
+//! like submitting transactions, are only retried if enabled with
+//! [`SequencerClient::with_post_failover`]. Streams transparently reconnect when a connection is
+//! dropped.
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use alloy::primitives::Address;
-use anyhow::Context;
+use anyhow::{ensure, Context};
 use espresso_types::{FeeAccount, FeeAmount, FeeMerkleTree, Header};
-use futures::{stream::BoxStream, StreamExt};
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
 use jf_merkle_tree::{
     prelude::{MerkleProof, Sha3Node},
     MerkleTreeScheme,
 };
+use serde::{de::DeserializeOwned, Serialize};
 use surf_disco::{
     error::ClientError,
     socket::{Connection, Unsupported},
-    Url,
+    Error as _, Url,
 };
 use tokio::time::sleep;
 use vbs::version::StaticVersion;
 
+mod availability;
+mod catchup;
+mod evidence;
+mod explorer;
+mod fee_ledger;
+mod node;
+mod rewards;
+mod state;
+mod status;
+mod submit;
+
+pub use catchup::BlocksFrontier;
+
 pub type SequencerApiVersion = StaticVersion<0, 1>;
 
+type Client = surf_disco::Client<ClientError, SequencerApiVersion>;
+
+/// How long to wait before trying to reconnect a dropped stream, if all URLs are unavailable.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
 #[derive(Clone, Debug)]
-pub struct SequencerClient(surf_disco::Client<ClientError, SequencerApiVersion>);
+pub struct SequencerClient {
+    clients: Arc<Vec<(Url, Client)>>,
+    /// Index of the URL which most recently served a request successfully, which will be tried
+    /// first for the next request.
+    preferred: Arc<AtomicUsize>,
+    /// Whether POST requests, which may not be idempotent, are retried against other URLs.
+    post_failover: bool,
+}
 
 pub type FeeMerkleProof = MerkleProof<FeeAmount, FeeAccount, Sha3Node, { FeeMerkleTree::ARITY }>;
 
 impl SequencerClient {
     pub fn new(provider: Url) -> Self {
-        Self(surf_disco::Client::new(provider))
+        Self {
+            clients: Arc::new(vec![(provider.clone(), Client::new(provider))]),
+            preferred: Default::default(),
+            post_failover: false,
+        }
+    }
+
+    /// Create a client which fails over between several sequencer nodes.
+    ///
+    /// Requests are sent to one URL at a time. If a request fails with a connection, timeout or
+    /// server error, it is retried against the next URL, until all URLs have been tried. Only
+    /// requests which do not change state on the node are retried, unless
+    /// [`with_post_failover`](Self::with_post_failover) is set.
+    pub fn with_failover(providers: impl IntoIterator<Item = Url>) -> anyhow::Result<Self> {
+        let clients = providers
+            .into_iter()
+            .map(|url| (url.clone(), Client::new(url)))
+            .collect::<Vec<_>>();
+        ensure!(!clients.is_empty(), "at least one URL is required");
+        Ok(Self {
+            clients: Arc::new(clients),
+            preferred: Default::default(),
+            post_failover: false,
+        })
+    }
+
+    /// Also retry POST requests, such as transaction submissions, against other URLs.
+    ///
+    /// A request which timed out or failed with a server error may still have been processed, so
+    /// with this set the same transactions may be submitted to more than one node.
+    pub fn with_post_failover(mut self) -> Self {
+        self.post_failover = true;
+        self
     }
 
     /// GET Block Height from the node
     pub async fn get_height(&self) -> anyhow::Result<u64> {
-        self.0
-            .get::<u64>("node/block-height")
-            .send()
+        self.get::<u64>("node/block-height")
             .await
             .context("getting Espresso block height")
     }
 
     /// Get the Number of Transactions
     pub async fn get_transaction_count(&self) -> anyhow::Result<u64> {
-        self.0
-            .get::<u64>("node/transactions/count")
-            .send()
+        self.get::<u64>("node/transactions/count")
             .await
             .context("getting Espresso transaction count")
     }
@@ -51,12 +130,9 @@ impl SequencerClient {
         &self,
         height: u64,
     ) -> anyhow::Result<BoxStream<'static, Result<Header, ClientError>>> {
-        self.0
-            .socket(&format!("availability/stream/headers/{height}"))
-            .subscribe::<Header>()
+        self.subscribe::<Header>(&format!("availability/stream/headers/{height}"))
             .await
             .context("subscribing to Espresso headers")
-            .map(|s| s.boxed())
     }
 
     /// Subscribe to a stream of Block Headers
@@ -64,9 +140,8 @@ impl SequencerClient {
         &self,
         height: u64,
     ) -> anyhow::Result<Connection<Header, Unsupported, ClientError, SequencerApiVersion>> {
-        self.0
-            .socket(&format!("availability/stream/blocks/{height}"))
-            .subscribe()
+        let path = format!("availability/stream/blocks/{height}");
+        self.request(true, |client| client.socket(&path).subscribe())
             .await
             .context("subscribing to Espresso Blocks")
     }
@@ -96,9 +171,7 @@ impl SequencerClient {
         let proof = loop {
             tracing::debug!(%address, block, retry, "fetching Espresso balance");
             match self
-                .0
                 .get::<FeeMerkleProof>(&format!("fee-state/{block}/{address:#x}"))
-                .send()
                 .await
             {
                 Ok(proof) => break proof,
@@ -120,10 +193,156 @@ impl SequencerClient {
         let balance = proof.elem().copied().unwrap_or(0.into());
         Ok(balance)
     }
+
+    /// Make a request, failing over to the next URL each time the request fails transiently.
+    ///
+    /// Starts with the URL which most recently succeeded, and returns the error from the last URL
+    /// tried if all of them fail. Errors which are not transient, or any error if `failover` is
+    /// not set, are returned without trying other URLs.
+    async fn request<T, F, Fut>(&self, failover: bool, f: F) -> Result<T, ClientError>
+    where
+        F: Fn(&Client) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let start = self.preferred.load(Ordering::Relaxed) % self.clients.len();
+        let mut index = start;
+        loop {
+            let (url, client) = &self.clients[index];
+            match f(client).await {
+                Ok(res) => {
+                    self.preferred.store(index, Ordering::Relaxed);
+                    return Ok(res);
+                },
+                Err(err) => {
+                    if !failover || !is_transient(&err) {
+                        return Err(err);
+                    }
+                    index = (index + 1) % self.clients.len();
+                    if index == start {
+                        return Err(err);
+                    }
+                    tracing::warn!(%url, "request failed, trying next URL: {err}");
+                },
+            }
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> anyhow::Result<T> {
+        self.request(true, |client| client.get::<T>(path).send())
+            .await
+            .with_context(|| format!("GET {path}"))
+    }
+
+    async fn post<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> anyhow::Result<T> {
+        self.send_post(path, body, self.post_failover).await
+    }
+
+    /// Send a POST request which only reads state, and so can always be retried.
+    async fn post_query<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> anyhow::Result<T> {
+        self.send_post(path, body, true).await
+    }
+
+    async fn send_post<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+        failover: bool,
+    ) -> anyhow::Result<T> {
+        self.request(failover, |client| {
+            let req = client.post::<T>(path).body_json(body);
+            async move { req?.send().await }
+        })
+        .await
+        .with_context(|| format!("POST {path}"))
+    }
+
+    async fn subscribe<T: DeserializeOwned + Send + 'static>(
+        &self,
+        path: &str,
+    ) -> anyhow::Result<BoxStream<'static, Result<T, ClientError>>> {
+        self.request(true, |client| {
+            let req = client.socket(path);
+            async move { Ok(req.subscribe::<T>().await?.boxed()) }
+        })
+        .await
+        .with_context(|| format!("subscribing to {path}"))
+    }
+
+    /// Subscribe to a stream of objects indexed by block height, such as `stream/headers/:height`.
+    ///
+    /// The stream never ends. If the connection is dropped, it reconnects (failing over to other URLs
+    /// if necessary) and resumes from just after the last object received. `path` is the route up to
+    /// but not including the height, and `height` gets the block height of an object.
+    fn reconnecting_stream<T>(
+        &self,
+        path: String,
+        from: u64,
+        height: fn(&T) -> u64,
+    ) -> BoxStream<'static, T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let client = self.clone();
+        let conn: Option<BoxStream<'static, Result<T, ClientError>>> = None;
+        stream::unfold((conn, from), move |(mut conn, mut next)| {
+            let client = client.clone();
+            let path = path.clone();
+            async move {
+                loop {
+                    let mut stream = match conn.take() {
+                        Some(stream) => stream,
+                        None => match client.subscribe(&format!("{path}/{next}")).await {
+                            Ok(stream) => stream,
+                            Err(err) => {
+                                tracing::warn!(
+                                    next,
+                                    "failed to connect stream, will retry: {err:#}"
+                                );
+                                sleep(RECONNECT_DELAY).await;
+                                continue;
+                            },
+                        },
+                    };
+                    match stream.next().await {
+                        Some(Ok(obj)) => {
+                            next = height(&obj) + 1;
+                            return Some((obj, (Some(stream), next)));
+                        },
+                        Some(Err(err)) => {
+                            tracing::warn!(next, "stream failed, reconnecting: {err:#}");
+                        },
+                        None => {
+                            tracing::warn!(next, "stream closed, reconnecting");
+                        },
+                    }
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+/// Whether a request failing with `err` might succeed against another node.
+///
+/// Connection failures are reported as server errors, so this covers unreachable nodes, timeouts
+/// and server errors, but not requests the node rejected.
+fn is_transient(err: &ClientError) -> bool {
+    let status = u16::from(err.status());
+    status == 408 || (500..600).contains(&status)
 }
 
 #[cfg(test)]
 mod tests {
+    use surf_disco::StatusCode;
+
     use super::*;
     // Regression test for a bug where the block number underflowed. This test would panic
     // on the previous implementation, as long as overflow checks are enabled.
@@ -138,4 +357,19 @@ mod tests {
             0.into()
         )
     }
+
+    #[test]
+    fn test_failover_requires_url() {
+        SequencerClient::with_failover([]).unwrap_err();
+    }
+
+    #[test]
+    fn test_transient_errors() {
+        let err = |status| ClientError::catch_all(status, "error".into());
+        assert!(is_transient(&err(StatusCode::INTERNAL_SERVER_ERROR)));
+        assert!(is_transient(&err(StatusCode::NOT_IMPLEMENTED)));
+        assert!(!is_transient(&err(StatusCode::BAD_REQUEST)));
+        assert!(!is_transient(&err(StatusCode::NOT_FOUND)));
+        assert!(!is_transient(&err(StatusCode::FORBIDDEN)));
+    }
 }
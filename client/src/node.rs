@@ -0,0 +1,79 @@
+//! Client for the `node` API.
+
+use espresso_types::{Header, PubKey, SeqTypes};
+use hotshot_query_service::node::{Limits, SyncStatus, TimeWindowQueryData};
+use hotshot_types::{data::VidShare, PeerConfig};
+
+use crate::SequencerClient;
+
+impl SequencerClient {
+    /// Get the number of transactions in the blocks with heights in the range `[from, to]`.
+    pub async fn count_transactions(&self, from: u64, to: u64) -> anyhow::Result<u64> {
+        self.get(&format!("node/transactions/count/{from}/{to}"))
+            .await
+    }
+
+    /// Get the total size in bytes of all payloads the node knows about.
+    pub async fn get_total_payload_size(&self) -> anyhow::Result<u64> {
+        self.get("node/payloads/total-size").await
+    }
+
+    /// Get the total size in bytes of the payloads of blocks in the range `[from, to]`.
+    pub async fn get_payload_size(&self, from: u64, to: u64) -> anyhow::Result<u64> {
+        self.get(&format!("node/payloads/size/{from}/{to}")).await
+    }
+
+    /// Get this node's VID share for the block at `height`.
+    pub async fn get_vid_share(&self, height: u64) -> anyhow::Result<VidShare> {
+        self.get(&format!("node/vid/share/{height}")).await
+    }
+
+    /// Get the sync status of the node's database.
+    pub async fn get_sync_status(&self) -> anyhow::Result<SyncStatus> {
+        self.get("node/sync-status").await
+    }
+
+    /// Get the headers with timestamps in the window `[start, end)`.
+    pub async fn get_header_window(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> anyhow::Result<TimeWindowQueryData<Header>> {
+        self.get(&format!("node/header/window/{start}/{end}")).await
+    }
+
+    /// Get the headers from `height` up to (but not including) timestamp `end`.
+    pub async fn get_header_window_from(
+        &self,
+        height: u64,
+        end: u64,
+    ) -> anyhow::Result<TimeWindowQueryData<Header>> {
+        self.get(&format!("node/header/window/from/{height}/{end}"))
+            .await
+    }
+
+    /// Get the maximum window sizes supported by the node API.
+    pub async fn get_node_limits(&self) -> anyhow::Result<Limits> {
+        self.get("node/limits").await
+    }
+
+    /// Get the stake table for the current epoch.
+    pub async fn get_current_stake_table(&self) -> anyhow::Result<Vec<PeerConfig<SeqTypes>>> {
+        self.get("node/stake-table/current").await
+    }
+
+    /// Get the stake table for `epoch`.
+    pub async fn get_stake_table(&self, epoch: u64) -> anyhow::Result<Vec<PeerConfig<SeqTypes>>> {
+        self.get(&format!("node/stake-table/{epoch}")).await
+    }
+
+    /// Get the leader of `view`.
+    ///
+    /// Once epochs are enabled, the `epoch` which the view belongs to must be given.
+    pub async fn get_leader(&self, view: u64, epoch: Option<u64>) -> anyhow::Result<PubKey> {
+        match epoch {
+            Some(epoch) => self.get(&format!("node/leader/{view}/{epoch}")).await,
+            None => self.get(&format!("node/leader/{view}")).await,
+        }
+    }
+}
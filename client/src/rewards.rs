@@ -0,0 +1,44 @@
+//! Client for the `reward-history` API.
+
+use alloy::primitives::Address;
+use espresso_types::v0_1::{EpochRewards, RewardAmount, RewardDistribution, RewardTotals};
+
+use crate::SequencerClient;
+
+impl SequencerClient {
+    /// Get the rewards paid out in the block at `height`.
+    pub async fn get_block_rewards(&self, height: u64) -> anyhow::Result<RewardDistribution> {
+        self.get(&format!("reward-history/block/{height}")).await
+    }
+
+    /// Get the rewards paid out by `validator` over the course of `epoch`.
+    pub async fn get_epoch_rewards(
+        &self,
+        epoch: u64,
+        validator: Address,
+    ) -> anyhow::Result<EpochRewards> {
+        self.get(&format!(
+            "reward-history/epoch/{epoch}/validator/{validator:#x}"
+        ))
+        .await
+    }
+
+    /// Get the reward earned by `delegator` from stake delegated to `validator` in `epoch`.
+    pub async fn get_delegator_epoch_reward(
+        &self,
+        epoch: u64,
+        validator: Address,
+        delegator: Address,
+    ) -> anyhow::Result<RewardAmount> {
+        self.get(&format!(
+            "reward-history/epoch/{epoch}/validator/{validator:#x}/delegator/{delegator:#x}"
+        ))
+        .await
+    }
+
+    /// Get the cumulative rewards earned by `address` over all indexed epochs.
+    pub async fn get_reward_totals(&self, address: Address) -> anyhow::Result<RewardTotals> {
+        self.get(&format!("reward-history/totals/{address:#x}"))
+            .await
+    }
+}
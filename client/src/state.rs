@@ -0,0 +1,151 @@
+//! Client for the merklized state APIs (`fee-state`, `reward-state` and `block-state`).
+
+use alloy::primitives::{Address, U256};
+use anyhow::{ensure, Context};
+use espresso_types::{
+    light_client::FeeWithdrawalProof,
+    v0_1::{RewardAccountProof, RewardAmount, RewardMerkleProof, RewardMerkleTree},
+    BlockMerkleTree, FeeAccount, FeeAccountProof, FeeAmount, FeeMerkleProof,
+};
+use futures::try_join;
+use jf_merkle_tree::MerkleTreeScheme;
+
+use crate::SequencerClient;
+
+type RawRewardMerkleProof = <RewardMerkleTree as MerkleTreeScheme>::MembershipProof;
+
+impl SequencerClient {
+    /// Get the Merkle path for a fee account in the state after the block at `height`.
+    ///
+    /// If the account does not exist, the path is a proof of non-membership.
+    pub async fn get_fee_path(
+        &self,
+        height: u64,
+        address: Address,
+    ) -> anyhow::Result<crate::FeeMerkleProof> {
+        self.get(&format!("fee-state/{height}/{address:#x}")).await
+    }
+
+    /// Get the balance of a fee account in the state after the block at `height`, with a proof.
+    ///
+    /// The proof is checked against the fee state root in the header at `height`.
+    pub async fn get_verified_fee_balance(
+        &self,
+        height: u64,
+        address: Address,
+    ) -> anyhow::Result<(U256, FeeAccountProof)> {
+        let (header, path) =
+            try_join!(self.get_header(height), self.get_fee_path(height, address))?;
+        let proof = FeeAccountProof {
+            account: address,
+            proof: if path.elem().is_some() {
+                FeeMerkleProof::Presence(path)
+            } else {
+                FeeMerkleProof::Absence(path)
+            },
+        };
+        let balance = proof
+            .verify(&header.fee_merkle_tree_root())
+            .with_context(|| format!("verifying fee balance of {address} at height {height}"))?;
+        Ok((balance, proof))
+    }
+
+    /// Get a proof of the total amount withdrawn from `account`, for a light client whose finalized
+    /// block height is `finalized_height`.
+    ///
+    /// The withdrawn amount is proven as of the last block finalized by the light client, and the
+    /// result can be checked against the light client state using [`FeeWithdrawalProof::verify`].
+    pub async fn get_fee_withdrawal_proof(
+        &self,
+        finalized_height: u64,
+        account: FeeAccount,
+    ) -> anyhow::Result<FeeWithdrawalProof> {
+        ensure!(
+            finalized_height > 0,
+            "light client has not finalized any blocks"
+        );
+        let height = finalized_height - 1;
+        let (finalized, block_proof, header, (_, escrow_proof)) = try_join!(
+            self.get_header(finalized_height),
+            self.get_block_path(finalized_height, height),
+            self.get_header(height),
+            self.get_verified_fee_balance(height, account.withdrawal_escrow().address()),
+        )?;
+        Ok(FeeWithdrawalProof {
+            block_merkle_root: finalized.block_merkle_tree_root(),
+            block_proof,
+            header,
+            escrow_proof,
+        })
+    }
+
+    /// Get the balance of a fee account in the latest state, if the account exists.
+    pub async fn get_latest_fee_balance(
+        &self,
+        address: Address,
+    ) -> anyhow::Result<Option<FeeAmount>> {
+        self.get(&format!("fee-state/fee-balance/latest/{address:#x}"))
+            .await
+    }
+
+    /// Get the Merkle path for a reward account in the state after the block at `height`.
+    ///
+    /// If the account does not exist, the path is a proof of non-membership.
+    pub async fn get_reward_path(
+        &self,
+        height: u64,
+        address: Address,
+    ) -> anyhow::Result<RawRewardMerkleProof> {
+        self.get(&format!("reward-state/{height}/{address:#x}"))
+            .await
+    }
+
+    /// Get the balance of a reward account in the state after the block at `height`, with a proof.
+    ///
+    /// The proof is checked against the reward state root in the header at `height`.
+    pub async fn get_verified_reward_balance(
+        &self,
+        height: u64,
+        address: Address,
+    ) -> anyhow::Result<(U256, RewardAccountProof)> {
+        let (header, path) = try_join!(
+            self.get_header(height),
+            self.get_reward_path(height, address)
+        )?;
+        let proof = RewardAccountProof {
+            account: address,
+            proof: if path.elem().is_some() {
+                RewardMerkleProof::Presence(path)
+            } else {
+                RewardMerkleProof::Absence(path)
+            },
+        };
+        let balance = proof
+            .verify(&header.reward_merkle_tree_root())
+            .with_context(|| format!("verifying reward balance of {address} at height {height}"))?;
+        Ok((balance, proof))
+    }
+
+    /// Get the balance of a reward account in the latest state, if the account exists.
+    pub async fn get_latest_reward_balance(
+        &self,
+        address: Address,
+    ) -> anyhow::Result<Option<RewardAmount>> {
+        self.get(&format!("reward-state/reward-balance/latest/{address:#x}"))
+            .await
+    }
+
+    /// Get a proof that the block at `block` is in the block Merkle tree as of `height`.
+    pub async fn get_block_path(
+        &self,
+        height: u64,
+        block: u64,
+    ) -> anyhow::Result<<BlockMerkleTree as MerkleTreeScheme>::MembershipProof> {
+        self.get(&format!("block-state/{height}/{block}")).await
+    }
+
+    /// Get the latest block height for which merklized state is available.
+    pub async fn get_state_height(&self) -> anyhow::Result<u64> {
+        self.get("block-state/block-height").await
+    }
+}
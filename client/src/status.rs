@@ -0,0 +1,36 @@
+//! Client for the `status`, `config` and `state-signature` APIs.
+
+use espresso_types::config::PublicNetworkConfig;
+use hotshot_types::light_client::StateSignatureRequestBody;
+
+use crate::SequencerClient;
+
+impl SequencerClient {
+    /// Get the fraction of views which successfully decided a block, as seen by this node.
+    pub async fn get_success_rate(&self) -> anyhow::Result<f64> {
+        self.get("status/success-rate").await
+    }
+
+    /// Get the number of seconds since this node last saw a decide.
+    pub async fn get_time_since_last_decide(&self) -> anyhow::Result<u64> {
+        self.get("status/time-since-last-decide").await
+    }
+
+    /// Get the public parts of the node's network configuration.
+    pub async fn get_network_config(&self) -> anyhow::Result<PublicNetworkConfig> {
+        self.get("config/hotshot").await
+    }
+
+    /// Get the public environment variables which the node was started with.
+    pub async fn get_env(&self) -> anyhow::Result<Vec<String>> {
+        self.get("config/env").await
+    }
+
+    /// Get the node's signature on the light client state as of the block at `height`.
+    pub async fn get_state_signature(
+        &self,
+        height: u64,
+    ) -> anyhow::Result<StateSignatureRequestBody> {
+        self.get(&format!("state-signature/block/{height}")).await
+    }
+}
@@ -0,0 +1,66 @@
+//! Client for the `submit` API.
+
+use anyhow::{ensure, Context};
+use committable::Commitment;
+use espresso_types::{
+    submit::{SubmitResult, TransactionStatus},
+    Transaction,
+};
+use futures::stream::{BoxStream, TryStreamExt};
+use surf_disco::error::ClientError;
+
+use crate::SequencerClient;
+
+impl SequencerClient {
+    /// Submit a transaction, returning its hash.
+    pub async fn submit(&self, tx: &Transaction) -> anyhow::Result<Commitment<Transaction>> {
+        self.post("submit/submit", tx).await
+    }
+
+    /// Submit a batch of transactions.
+    ///
+    /// The result contains one entry for each transaction, in order, indicating whether that
+    /// transaction was accepted.
+    pub async fn submit_batch(&self, txs: &[Transaction]) -> anyhow::Result<Vec<SubmitResult>> {
+        self.post("submit/batch", &txs).await
+    }
+
+    /// Get the status of a transaction submitted to or seen by the node.
+    pub async fn get_transaction_status(
+        &self,
+        hash: Commitment<Transaction>,
+    ) -> anyhow::Result<TransactionStatus> {
+        self.get(&format!("submit/status/{hash}")).await
+    }
+
+    /// Stream status updates for a transaction, until it is decided.
+    ///
+    /// The stream does not end if the transaction is dropped, since it may still be decided.
+    pub async fn subscribe_transaction_status(
+        &self,
+        hash: Commitment<Transaction>,
+    ) -> anyhow::Result<BoxStream<'static, Result<TransactionStatus, ClientError>>> {
+        self.subscribe(&format!("submit/status/stream/{hash}"))
+            .await
+    }
+
+    /// Submit a transaction and wait for it to be decided or dropped.
+    pub async fn submit_and_wait(&self, tx: &Transaction) -> anyhow::Result<TransactionStatus> {
+        let hash = self.submit(tx).await?;
+        let mut statuses = self.subscribe_transaction_status(hash).await?;
+        let mut last = None;
+        while let Some(status) = statuses.try_next().await? {
+            tracing::debug!(%hash, ?status, "transaction status update");
+            last = Some(status);
+            if status == TransactionStatus::Dropped {
+                break;
+            }
+        }
+        let status = last.with_context(|| format!("status stream for {hash} was empty"))?;
+        ensure!(
+            status.is_final() || status == TransactionStatus::Dropped,
+            "status stream for {hash} ended without a final status"
+        );
+        Ok(status)
+    }
+}
@@ -6,6 +6,7 @@
 pub mod r#erc1967proxy;
 pub mod r#esptoken;
 pub mod r#feecontract;
+pub mod r#feewithdrawalverifier;
 pub mod r#iplonkverifier;
 pub mod r#lightclient;
 pub mod r#lightclientarbitrum;
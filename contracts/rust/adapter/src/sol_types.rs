@@ -26,6 +26,9 @@ pub use crate::bindings::{
     erc1967proxy::ERC1967Proxy,
     esptoken::EspToken,
     feecontract::FeeContract::{self, Deposit},
+    feewithdrawalverifier::FeeWithdrawalVerifier::{
+        self, Attestation as FeeWithdrawalAttestationSol,
+    },
     lightclient::{
         IPlonkVerifier::{PlonkProof as PlonkProofSol, VerifyingKey as VerifyingKeySol},
         LightClient::{
@@ -72,6 +75,32 @@ sol! {
 
 }
 
+sol! {
+    /// withdrawal functions and events in src/FeeContract.sol, which are not yet in the generated
+    /// `FeeContract` bindings
+    #[sol(rpc)]
+    interface FeeContractWithdrawals {
+        event WithdrawalRequested(address indexed user, uint256 amount);
+        event WithdrawalClaimed(address indexed user, address recipient, uint256 amount);
+
+        function requestWithdrawal(uint256 amount) external;
+        function claimWithdrawal(address recipient, uint256 withdrawn, bytes calldata proof) external;
+        function claimed(address user) external view returns (uint256);
+        function withdrawalVerifier() external view returns (address);
+    }
+}
+
+sol! {
+    /// validator selection policy function and event in src/StakeTable.sol, which are not yet in
+    /// the generated `StakeTable` bindings
+    #[sol(rpc)]
+    interface StakeTableSelectionPolicy {
+        event ValidatorSelectionPolicyUpdated(uint64 maxValidators, uint64 minStakeRatio, uint256 minSelfStake);
+
+        function updateValidatorSelectionPolicy(uint64 maxValidators, uint64 minStakeRatio, uint256 minSelfStake) external;
+    }
+}
+
 // Due to <https://github.com/foundry-rs/foundry/issues/10153> the rust bindings contain duplicate types for our solidity types.
 // In order to avoid writing a lot of boilerplate code we use transmute to convert between these duplicated types.
 // Since all the types we transmute between are generated by foundry from the same underlying solidity type
@@ -7,6 +7,7 @@ import { OwnableUpgradeable } from
 import { Initializable } from "@openzeppelin/contracts-upgradeable/proxy/utils/Initializable.sol";
 import { UUPSUpgradeable } from
     "@openzeppelin/contracts-upgradeable/proxy/utils/UUPSUpgradeable.sol";
+import { IFeeWithdrawalVerifier } from "./interfaces/IFeeWithdrawalVerifier.sol";
 
 contract FeeContract is Initializable, OwnableUpgradeable, UUPSUpgradeable {
     // === Events ===
@@ -15,6 +16,15 @@ contract FeeContract is Initializable, OwnableUpgradeable, UUPSUpgradeable {
     event Deposit(address indexed user, uint256 amount);
     event Log(string func, uint256 gas);
 
+    /// @notice Notify a request to withdraw from the Espresso fee account of `user`
+    event WithdrawalRequested(address indexed user, uint256 amount);
+
+    /// @notice Notify a payout of funds withdrawn from Espresso
+    event WithdrawalClaimed(address indexed user, address recipient, uint256 amount);
+
+    /// @notice Notify a change of the contract used to verify withdrawal proofs
+    event WithdrawalVerifierUpdated(address verifier);
+
     /// @notice upgrade event when the proxy updates the implementation it's pointing to
     event Upgrade(address implementation);
     // === Constants ===
@@ -34,10 +44,22 @@ contract FeeContract is Initializable, OwnableUpgradeable, UUPSUpgradeable {
     error DepositTooSmall();
     error FunctionDoesNotExist();
     error NoFunctionCalled();
+    error InvalidWithdrawalAmount();
+    error WithdrawalsNotEnabled();
+    error InvalidWithdrawalProof();
+    error NothingToClaim();
+    error WithdrawalTransferFailed();
 
     /// @notice store user balances in a mapping
     mapping(address user => uint256 amount) public balances;
 
+    /// @notice the contract which verifies proofs of amounts withdrawn on Espresso
+    /// @dev withdrawals cannot be claimed until this is set
+    IFeeWithdrawalVerifier public withdrawalVerifier;
+
+    /// @notice the total amount paid out to each user from their Espresso withdrawals
+    mapping(address user => uint256 amount) public claimed;
+
     /// @notice since the constructor initializes storage on this contract we disable it
     /// @dev storage is on the proxy contract since it calls this contract via delegatecall
     /// @custom:oz-upgrades-unsafe-allow constructor
@@ -81,6 +103,49 @@ contract FeeContract is Initializable, OwnableUpgradeable, UUPSUpgradeable {
         emit Deposit(user, msg.value);
     }
 
+    /// @notice Request a withdrawal of `amount` from the caller's Espresso fee account
+    /// @dev Espresso moves the amount into the withdrawal escrow of the account once this request
+    /// is finalized on the L1. Requests for more than the account balance are ignored.
+    function requestWithdrawal(uint256 amount) public {
+        if (amount == 0) {
+            revert InvalidWithdrawalAmount();
+        }
+        emit WithdrawalRequested(msg.sender, amount);
+    }
+
+    /// @notice Pay out everything withdrawn by the caller on Espresso which has not been claimed
+    /// @param recipient The L1 account to pay
+    /// @param withdrawn The total amount ever withdrawn by the caller on Espresso
+    /// @param proof A proof of `withdrawn` for the withdrawal verifier
+    function claimWithdrawal(address recipient, uint256 withdrawn, bytes calldata proof) public {
+        if (address(withdrawalVerifier) == address(0)) {
+            revert WithdrawalsNotEnabled();
+        }
+        if (recipient == address(0)) {
+            revert InvalidUserAddress();
+        }
+        if (!withdrawalVerifier.verifyWithdrawn(msg.sender, withdrawn, proof)) {
+            revert InvalidWithdrawalProof();
+        }
+        if (withdrawn <= claimed[msg.sender]) {
+            revert NothingToClaim();
+        }
+
+        uint256 amount = withdrawn - claimed[msg.sender];
+        claimed[msg.sender] = withdrawn;
+        (bool success,) = recipient.call{ value: amount }("");
+        if (!success) {
+            revert WithdrawalTransferFailed();
+        }
+        emit WithdrawalClaimed(msg.sender, recipient, amount);
+    }
+
+    /// @notice Set the contract which verifies withdrawal proofs
+    function setWithdrawalVerifier(address verifier) public onlyOwner {
+        withdrawalVerifier = IFeeWithdrawalVerifier(verifier);
+        emit WithdrawalVerifierUpdated(verifier);
+    }
+
     /// @notice only the owner can authorize an upgrade
     function _authorizeUpgrade(address newImplementation) internal override onlyOwner {
         emit Upgrade(newImplementation);
@@ -92,6 +157,6 @@ contract FeeContract is Initializable, OwnableUpgradeable, UUPSUpgradeable {
         pure
         returns (uint8 majorVersion, uint8 minorVersion, uint8 patchVersion)
     {
-        return (1, 0, 0);
+        return (1, 1, 0);
     }
 }
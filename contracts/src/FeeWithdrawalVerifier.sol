@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: Unlicensed
+
+pragma solidity ^0.8.0;
+
+import { Ownable } from "@openzeppelin/contracts/access/Ownable.sol";
+import { ECDSA } from "@openzeppelin/contracts/utils/cryptography/ECDSA.sol";
+import { MessageHashUtils } from "@openzeppelin/contracts/utils/cryptography/MessageHashUtils.sol";
+import { BN254 } from "bn254/BN254.sol";
+
+import { IFeeWithdrawalVerifier } from "./interfaces/IFeeWithdrawalVerifier.sol";
+import { LightClient } from "./LightClient.sol";
+
+/// @title Verifies Espresso fee withdrawals against the light client contract
+/// @notice The Merkle proofs of a withdrawal escrow balance are checked off-chain by an attester,
+/// because the light client commits to the block Merkle root with a Rescue hash which is not
+/// available on the L1. The attester signs the escrow balance together with the light client state
+/// it was proven against, and this contract checks that signature and that the state was in fact
+/// finalized by the light client contract.
+/// @dev The attester is set by the owner, who is expected to also own the fee contract and can thus
+/// already move its funds through an upgrade. The attester does not add to that trust.
+contract FeeWithdrawalVerifier is IFeeWithdrawalVerifier, Ownable {
+    /// @notice A signed statement of the total amount withdrawn from a fee account
+    /// @param blockHeight The block height of the light client state the amount was proven against
+    /// @param blockCommRoot The block commitment root of that light client state
+    /// @param signature The attester's signature over the `attestationDigest` of the withdrawal
+    struct Attestation {
+        uint64 blockHeight;
+        uint256 blockCommRoot;
+        bytes signature;
+    }
+
+    // === Events ===
+
+    /// @notice Notify a change of the account which attests to withdrawals
+    event AttesterUpdated(address attester);
+
+    // === Errors ===
+
+    error InvalidAttesterAddress();
+    error InvalidLightClientAddress();
+
+    // === Storage ===
+
+    /// @notice the light client contract whose finalized states withdrawals are proven against
+    LightClient public immutable lightClient;
+
+    /// @notice the account which attests to withdrawals
+    address public attester;
+
+    constructor(address _lightClient, address _attester, address owner) Ownable(owner) {
+        if (_lightClient == address(0)) {
+            revert InvalidLightClientAddress();
+        }
+        lightClient = LightClient(_lightClient);
+        _setAttester(_attester);
+    }
+
+    /// @notice Check an ABI-encoded `Attestation` of `withdrawn` for `user`
+    /// @dev The attested light client state must be the current finalized state, or a state in the
+    /// retained history of the light client contract, so that a claim does not fail just because
+    /// the light client was updated while it was pending.
+    function verifyWithdrawn(address user, uint256 withdrawn, bytes calldata proof)
+        external
+        view
+        returns (bool)
+    {
+        Attestation memory attestation = abi.decode(proof, (Attestation));
+        if (!isFinalized(attestation.blockHeight, attestation.blockCommRoot)) {
+            return false;
+        }
+
+        bytes32 digest = MessageHashUtils.toEthSignedMessageHash(
+            attestationDigest(user, withdrawn, attestation.blockHeight, attestation.blockCommRoot)
+        );
+        (address signer, ECDSA.RecoverError err,) = ECDSA.tryRecover(digest, attestation.signature);
+        return err == ECDSA.RecoverError.NoError && signer == attester;
+    }
+
+    /// @notice The message signed by the attester to attest that `withdrawn` is the total amount
+    /// withdrawn by `user` as of the light client state with `blockHeight` and `blockCommRoot`
+    /// @dev the digest is bound to this chain and contract so attestations cannot be replayed
+    /// against another deployment
+    function attestationDigest(
+        address user,
+        uint256 withdrawn,
+        uint64 blockHeight,
+        uint256 blockCommRoot
+    ) public view returns (bytes32) {
+        return keccak256(
+            abi.encode(block.chainid, address(this), user, withdrawn, blockHeight, blockCommRoot)
+        );
+    }
+
+    /// @notice Whether the light client contract has finalized the state with `blockHeight` and
+    /// `blockCommRoot`, either as its current state or one in its retained history
+    function isFinalized(uint64 blockHeight, uint256 blockCommRoot) public view returns (bool) {
+        (, uint64 finalizedHeight, BN254.ScalarField finalizedRoot) = lightClient.finalizedState();
+        if (blockHeight == finalizedHeight) {
+            return BN254.ScalarField.unwrap(finalizedRoot) == blockCommRoot;
+        }
+        if (blockHeight == 0 || blockHeight > finalizedHeight) {
+            return false;
+        }
+
+        // The history lookup returns the first state with a height greater than its argument.
+        try lightClient.getHotShotCommitment(blockHeight - 1) returns (
+            BN254.ScalarField root, uint64 height
+        ) {
+            return height == blockHeight && BN254.ScalarField.unwrap(root) == blockCommRoot;
+        } catch {
+            return false;
+        }
+    }
+
+    /// @notice Set the account which attests to withdrawals
+    function setAttester(address _attester) public onlyOwner {
+        _setAttester(_attester);
+    }
+
+    function _setAttester(address _attester) internal {
+        if (_attester == address(0)) {
+            revert InvalidAttesterAddress();
+        }
+        attester = _attester;
+        emit AttesterUpdated(_attester);
+    }
+}
@@ -102,6 +102,14 @@ contract StakeTable is Initializable, InitializedAt, OwnableUpgradeable, UUPSUpg
     /// table are `Undelegated` and `ValidatorExit`.
     event Withdrawal(address indexed account, uint256 amount);
 
+    /// @notice The owner updates the rules for selecting the active validators of an epoch.
+    ///
+    /// @notice The confirmation layer selects the active validators according to the most recent
+    /// policy. Until this event is emitted, the confirmation layer uses its default policy.
+    event ValidatorSelectionPolicyUpdated(
+        uint64 maxValidators, uint64 minStakeRatio, uint256 minSelfStake
+    );
+
     // === Errors ===
 
     /// A user tries to register a validator with the same address
@@ -505,4 +513,18 @@ contract StakeTable is Initializable, InitializedAt, OwnableUpgradeable, UUPSUpg
 
         emit ConsensusKeysUpdated(validator, newBlsVK, newSchnorrVK);
     }
+
+    /// @notice Update the rules for selecting the active validators of an epoch
+    /// @dev This function can only be called by the owner
+    /// @param maxValidators The maximum number of active validators
+    /// @param minStakeRatio Validators with less than `1 / minStakeRatio` of the stake of the
+    ///        largest validator are not selected. Zero means there is no minimum stake.
+    /// @param minSelfStake The minimum amount a validator must delegate to itself to be selected
+    function updateValidatorSelectionPolicy(
+        uint64 maxValidators,
+        uint64 minStakeRatio,
+        uint256 minSelfStake
+    ) external virtual onlyOwner {
+        emit ValidatorSelectionPolicyUpdated(maxValidators, minStakeRatio, minSelfStake);
+    }
 }
@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: Unlicensed
+
+pragma solidity ^0.8.0;
+
+/// @title Verifies the total amount withdrawn from an Espresso fee account.
+/// @notice Withdrawals on Espresso move funds from a fee account into a withdrawal escrow account,
+/// whose balance is the total amount ever withdrawn. A verifier checks a proof of that balance
+/// against the state finalized by the light client contract.
+interface IFeeWithdrawalVerifier {
+    /// @notice Check that `withdrawn` is the total amount withdrawn from the Espresso fee account of
+    /// `user` as of some block finalized by the light client contract.
+    /// @param user The L1 address of the Espresso fee account
+    /// @param withdrawn The total amount withdrawn from the account
+    /// @param proof The proof of the withdrawal escrow balance
+    /// @return True if the proof is valid
+    function verifyWithdrawn(address user, uint256 withdrawn, bytes calldata proof)
+        external
+        view
+        returns (bool);
+}
@@ -15,6 +15,7 @@ import { UUPSUpgradeable } from
 // Target contract
 import { FeeContract } from "../src/FeeContract.sol";
 import { DeployFeeContractScript } from "./script/Fee.s.sol";
+import { FeeWithdrawalVerifierMock } from "./mocks/FeeWithdrawalVerifierMock.sol";
 
 /// @title FeeContract Test
 contract FeeContractUpgradabilityTest is Test {
@@ -197,6 +198,81 @@ contract FeeContractUpgradabilityTest is Test {
 
         assertEq(balance, 0);
     }
+
+    // test that withdrawal requests are emitted for Espresso to pick up
+    function test_requestWithdrawal() public {
+        address user = makeAddr("user");
+
+        vm.expectEmit(true, false, false, true);
+        emit FeeContract.WithdrawalRequested(user, 1 ether);
+        vm.prank(user);
+        feeContractProxy.requestWithdrawal(1 ether);
+
+        vm.expectRevert(FeeContract.InvalidWithdrawalAmount.selector);
+        vm.prank(user);
+        feeContractProxy.requestWithdrawal(0);
+    }
+
+    // test that only the owner can set the withdrawal verifier
+    function test_setWithdrawalVerifierWithWrongAdmin() public {
+        address otherUser = makeAddr("otherUser");
+        vm.prank(otherUser);
+        vm.expectRevert(
+            abi.encodeWithSelector(
+                OwnableUpgradeable.OwnableUnauthorizedAccount.selector, otherUser
+            )
+        );
+        feeContractProxy.setWithdrawalVerifier(address(1));
+    }
+
+    // test that withdrawals cannot be claimed before a verifier is set
+    function test_claimWithdrawalNotEnabled() public {
+        vm.expectRevert(FeeContract.WithdrawalsNotEnabled.selector);
+        feeContractProxy.claimWithdrawal(makeAddr("recipient"), 1, "");
+    }
+
+    // test claiming withdrawals pays out only what has not been claimed before
+    function test_claimWithdrawal() public {
+        address user = makeAddr("user");
+        address recipient = makeAddr("recipient");
+        FeeWithdrawalVerifierMock verifier = new FeeWithdrawalVerifierMock();
+
+        vm.prank(admin);
+        vm.expectEmit(false, false, false, true);
+        emit FeeContract.WithdrawalVerifierUpdated(address(verifier));
+        feeContractProxy.setWithdrawalVerifier(address(verifier));
+
+        // fund the contract
+        vm.deal(user, 1 ether);
+        vm.prank(user);
+        feeContractProxy.deposit{ value: 1 ether }(user);
+
+        // a proof of a different amount is rejected
+        verifier.setWithdrawn(user, 0.4 ether);
+        vm.prank(user);
+        vm.expectRevert(FeeContract.InvalidWithdrawalProof.selector);
+        feeContractProxy.claimWithdrawal(recipient, 0.5 ether, "");
+
+        vm.expectEmit(true, false, false, true);
+        emit FeeContract.WithdrawalClaimed(user, recipient, 0.4 ether);
+        vm.prank(user);
+        feeContractProxy.claimWithdrawal(recipient, 0.4 ether, "");
+        assertEq(recipient.balance, 0.4 ether);
+        assertEq(feeContractProxy.claimed(user), 0.4 ether);
+
+        // the same total cannot be claimed twice
+        vm.prank(user);
+        vm.expectRevert(FeeContract.NothingToClaim.selector);
+        feeContractProxy.claimWithdrawal(recipient, 0.4 ether, "");
+
+        // a later withdrawal pays out only the difference
+        verifier.setWithdrawn(user, 0.5 ether);
+        vm.prank(user);
+        feeContractProxy.claimWithdrawal(recipient, 0.5 ether, "");
+        assertEq(recipient.balance, 0.5 ether);
+        assertEq(feeContractProxy.claimed(user), 0.5 ether);
+        assertEq(address(feeContractProxy).balance, 0.5 ether);
+    }
 }
 
 contract FeeContractV2Test is Initializable, OwnableUpgradeable, UUPSUpgradeable {
@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: Unlicensed
+
+/* solhint-disable contract-name-camelcase, func-name-mixedcase, one-contract-per-file */
+
+pragma solidity ^0.8.0;
+
+// Libraries
+import { Test } from "forge-std/Test.sol";
+import { Ownable } from "@openzeppelin/contracts/access/Ownable.sol";
+import { MessageHashUtils } from "@openzeppelin/contracts/utils/cryptography/MessageHashUtils.sol";
+import { BN254 } from "bn254/BN254.sol";
+
+// Target contract
+import { FeeContract } from "../src/FeeContract.sol";
+import { FeeWithdrawalVerifier } from "../src/FeeWithdrawalVerifier.sol";
+import { LightClient as LC } from "../src/LightClient.sol";
+import { LightClientMock } from "./mocks/LightClientMock.sol";
+import { DeployFeeContractScript } from "./script/Fee.s.sol";
+
+/// @title FeeWithdrawalVerifier Test
+contract FeeWithdrawalVerifierTest is Test {
+    LightClientMock public lightClient;
+    FeeWithdrawalVerifier public verifier;
+    address public owner;
+    address public attester;
+    uint256 public attesterKey;
+    address public user;
+
+    uint64 internal constant HEIGHT = 10;
+    uint256 internal constant ROOT = 42;
+
+    function setUp() public {
+        owner = makeAddr("owner");
+        user = makeAddr("user");
+        (attester, attesterKey) = makeAddrAndKey("attester");
+
+        lightClient = new LightClientMock();
+        lightClient.setFinalizedState(
+            LC.LightClientState({
+                viewNum: 20,
+                blockHeight: HEIGHT,
+                blockCommRoot: BN254.ScalarField.wrap(ROOT)
+            })
+        );
+        verifier = new FeeWithdrawalVerifier(address(lightClient), attester, owner);
+    }
+
+    function attest(uint256 key, address account, uint256 withdrawn, uint64 height, uint256 root)
+        internal
+        view
+        returns (bytes memory)
+    {
+        bytes32 digest = MessageHashUtils.toEthSignedMessageHash(
+            verifier.attestationDigest(account, withdrawn, height, root)
+        );
+        (uint8 v, bytes32 r, bytes32 s) = vm.sign(key, digest);
+        return abi.encode(
+            FeeWithdrawalVerifier.Attestation({
+                blockHeight: height,
+                blockCommRoot: root,
+                signature: abi.encodePacked(r, s, v)
+            })
+        );
+    }
+
+    // test that an attestation against the finalized state is accepted for exactly what it attests
+    function test_verifyWithdrawn() public {
+        bytes memory proof = attest(attesterKey, user, 1 ether, HEIGHT, ROOT);
+        assertTrue(verifier.verifyWithdrawn(user, 1 ether, proof));
+
+        // different amount
+        assertFalse(verifier.verifyWithdrawn(user, 2 ether, proof));
+        // different user
+        assertFalse(verifier.verifyWithdrawn(makeAddr("other"), 1 ether, proof));
+    }
+
+    // test that only the attester's signature is accepted
+    function test_verifyWithdrawnWrongSigner() public {
+        (, uint256 otherKey) = makeAddrAndKey("other");
+        bytes memory proof = attest(otherKey, user, 1 ether, HEIGHT, ROOT);
+        assertFalse(verifier.verifyWithdrawn(user, 1 ether, proof));
+
+        // malformed signature
+        bytes memory malformed = abi.encode(
+            FeeWithdrawalVerifier.Attestation({
+                blockHeight: HEIGHT,
+                blockCommRoot: ROOT,
+                signature: hex"1234"
+            })
+        );
+        assertFalse(verifier.verifyWithdrawn(user, 1 ether, malformed));
+    }
+
+    // test that the attested state must have been finalized by the light client
+    function test_verifyWithdrawnNotFinalized() public {
+        // wrong root for the finalized height
+        assertFalse(
+            verifier.verifyWithdrawn(user, 1 ether, attest(attesterKey, user, 1 ether, HEIGHT, 7))
+        );
+        // beyond the finalized height
+        assertFalse(
+            verifier.verifyWithdrawn(
+                user, 1 ether, attest(attesterKey, user, 1 ether, HEIGHT + 1, ROOT)
+            )
+        );
+        // an earlier height which is not in the history
+        assertFalse(
+            verifier.verifyWithdrawn(
+                user, 1 ether, attest(attesterKey, user, 1 ether, HEIGHT - 1, ROOT)
+            )
+        );
+    }
+
+    // test that attestations against states in the light client history remain valid
+    function test_verifyWithdrawnHistory() public {
+        LC.StateHistoryCommitment[] memory history = new LC.StateHistoryCommitment[](2);
+        history[0] = LC.StateHistoryCommitment({
+            l1BlockHeight: 1,
+            l1BlockTimestamp: 1,
+            hotShotBlockHeight: 5,
+            hotShotBlockCommRoot: BN254.ScalarField.wrap(17)
+        });
+        history[1] = LC.StateHistoryCommitment({
+            l1BlockHeight: 2,
+            l1BlockTimestamp: 2,
+            hotShotBlockHeight: HEIGHT,
+            hotShotBlockCommRoot: BN254.ScalarField.wrap(ROOT)
+        });
+        lightClient.setStateHistory(history);
+
+        assertTrue(
+            verifier.verifyWithdrawn(user, 1 ether, attest(attesterKey, user, 1 ether, 5, 17))
+        );
+        // wrong root for the historical height
+        assertFalse(
+            verifier.verifyWithdrawn(user, 1 ether, attest(attesterKey, user, 1 ether, 5, 18))
+        );
+        // a height between historical states
+        assertFalse(
+            verifier.verifyWithdrawn(user, 1 ether, attest(attesterKey, user, 1 ether, 4, 17))
+        );
+    }
+
+    // test that only the owner can change the attester, and old attestations stop being accepted
+    function test_setAttester() public {
+        bytes memory proof = attest(attesterKey, user, 1 ether, HEIGHT, ROOT);
+        (address newAttester, uint256 newKey) = makeAddrAndKey("newAttester");
+
+        vm.prank(user);
+        vm.expectRevert(abi.encodeWithSelector(Ownable.OwnableUnauthorizedAccount.selector, user));
+        verifier.setAttester(newAttester);
+
+        vm.prank(owner);
+        vm.expectRevert(FeeWithdrawalVerifier.InvalidAttesterAddress.selector);
+        verifier.setAttester(address(0));
+
+        vm.prank(owner);
+        vm.expectEmit(false, false, false, true);
+        emit FeeWithdrawalVerifier.AttesterUpdated(newAttester);
+        verifier.setAttester(newAttester);
+
+        assertFalse(verifier.verifyWithdrawn(user, 1 ether, proof));
+        assertTrue(
+            verifier.verifyWithdrawn(user, 1 ether, attest(newKey, user, 1 ether, HEIGHT, ROOT))
+        );
+    }
+
+    // test claiming from the fee contract with this verifier
+    function test_claimWithdrawal() public {
+        DeployFeeContractScript deployer = new DeployFeeContractScript();
+        (address payable proxy, address admin) = deployer.run();
+        FeeContract feeContract = FeeContract(proxy);
+        vm.prank(admin);
+        feeContract.setWithdrawalVerifier(address(verifier));
+
+        vm.deal(user, 1 ether);
+        vm.prank(user);
+        feeContract.deposit{ value: 1 ether }(user);
+
+        address recipient = makeAddr("recipient");
+        bytes memory proof = attest(attesterKey, user, 0.4 ether, HEIGHT, ROOT);
+
+        // someone else cannot claim with the user's attestation
+        vm.prank(recipient);
+        vm.expectRevert(FeeContract.InvalidWithdrawalProof.selector);
+        feeContract.claimWithdrawal(recipient, 0.4 ether, proof);
+
+        vm.prank(user);
+        feeContract.claimWithdrawal(recipient, 0.4 ether, proof);
+        assertEq(recipient.balance, 0.4 ether);
+        assertEq(feeContract.claimed(user), 0.4 ether);
+    }
+}
@@ -629,6 +629,21 @@ contract StakeTable_register_Test is LightClientCommonTest {
 
         vm.stopPrank();
     }
+
+    function test_UpdateValidatorSelectionPolicy_Succeeds() public {
+        vm.prank(admin);
+        vm.expectEmit(false, false, false, true, address(stakeTable));
+        emit S.ValidatorSelectionPolicyUpdated(50, 10, 1 ether);
+        stakeTable.updateValidatorSelectionPolicy(50, 10, 1 ether);
+    }
+
+    function test_RevertWhen_UpdateValidatorSelectionPolicyNotOwner() public {
+        vm.prank(validator);
+        vm.expectRevert(
+            abi.encodeWithSelector(OwnableUpgradeable.OwnableUnauthorizedAccount.selector, validator)
+        );
+        stakeTable.updateValidatorSelectionPolicy(50, 10, 1 ether);
+    }
 }
 
 contract StakeTableV2Test is S {
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: Unlicensed
+
+pragma solidity ^0.8.0;
+
+import { IFeeWithdrawalVerifier } from "../../src/interfaces/IFeeWithdrawalVerifier.sol";
+
+/// @notice Withdrawal verifier which accepts any amount set by the test.
+contract FeeWithdrawalVerifierMock is IFeeWithdrawalVerifier {
+    mapping(address user => uint256 withdrawn) public withdrawn;
+
+    function setWithdrawn(address user, uint256 amount) public {
+        withdrawn[user] = amount;
+    }
+
+    function verifyWithdrawn(address user, uint256 amount, bytes calldata)
+        external
+        view
+        returns (bool)
+    {
+        return withdrawn[user] == amount;
+    }
+}
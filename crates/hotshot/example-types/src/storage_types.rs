@@ -14,11 +14,12 @@ use async_lock::RwLock;
 use async_trait::async_trait;
 use hotshot_types::{
     data::{
-        vid_disperse::{ADVZDisperseShare, VidDisperseShare2},
+        vid_disperse::{ADVZDisperseShare, VidDisperseShare2, VidEncodingEvidence},
         DaProposal, DaProposal2, QuorumProposal, QuorumProposal2, QuorumProposalWrapper,
         VidCommitment,
     },
     drb::DrbResult,
+    equivocation::EquivocationEvidence,
     event::HotShotAction,
     message::{convert_proposal, Proposal},
     simple_certificate::{
@@ -47,6 +48,8 @@ type VidShares2<TYPES> = BTreeMap<
 pub struct TestStorageState<TYPES: NodeType> {
     vids: VidShares<TYPES>,
     vid2: VidShares2<TYPES>,
+    vid_encoding_evidence: BTreeMap<TYPES::View, VidEncodingEvidence<TYPES>>,
+    equivocation_evidence: Vec<EquivocationEvidence<TYPES>>,
     das: HashMap<TYPES::View, Proposal<TYPES, DaProposal<TYPES>>>,
     da2s: HashMap<TYPES::View, Proposal<TYPES, DaProposal2<TYPES>>>,
     proposals: BTreeMap<TYPES::View, Proposal<TYPES, QuorumProposal<TYPES>>>,
@@ -68,6 +71,8 @@ impl<TYPES: NodeType> Default for TestStorageState<TYPES> {
         Self {
             vids: BTreeMap::new(),
             vid2: BTreeMap::new(),
+            vid_encoding_evidence: BTreeMap::new(),
+            equivocation_evidence: Vec::new(),
             das: HashMap::new(),
             da2s: HashMap::new(),
             proposals: BTreeMap::new(),
@@ -145,6 +150,16 @@ impl<TYPES: NodeType> TestStorage<TYPES> {
         self.inner.read().await.vid2.clone()
     }
 
+    pub async fn vid_encoding_evidence_cloned(
+        &self,
+    ) -> BTreeMap<TYPES::View, VidEncodingEvidence<TYPES>> {
+        self.inner.read().await.vid_encoding_evidence.clone()
+    }
+
+    pub async fn equivocation_evidence_cloned(&self) -> Vec<EquivocationEvidence<TYPES>> {
+        self.inner.read().await.equivocation_evidence.clone()
+    }
+
     pub async fn state_cert_cloned(&self) -> Option<LightClientStateUpdateCertificate<TYPES>> {
         self.inner
             .read()
@@ -189,6 +204,36 @@ impl<TYPES: NodeType> Storage<TYPES> for TestStorage<TYPES> {
         Ok(())
     }
 
+    async fn append_vid_encoding_evidence(
+        &self,
+        evidence: &VidEncodingEvidence<TYPES>,
+    ) -> Result<()> {
+        if self.should_return_err {
+            bail!("Failed to append VID encoding evidence to storage");
+        }
+        Self::run_delay_settings_from_config(&self.delay_config).await;
+        let mut inner = self.inner.write().await;
+        inner
+            .vid_encoding_evidence
+            .insert(evidence.view_number, evidence.clone());
+        Ok(())
+    }
+
+    async fn append_equivocation_evidence(
+        &self,
+        evidence: &EquivocationEvidence<TYPES>,
+    ) -> Result<()> {
+        if self.should_return_err {
+            bail!("Failed to append equivocation evidence to storage");
+        }
+        Self::run_delay_settings_from_config(&self.delay_config).await;
+        let mut inner = self.inner.write().await;
+        if !inner.equivocation_evidence.contains(evidence) {
+            inner.equivocation_evidence.push(evidence.clone());
+        }
+        Ok(())
+    }
+
     async fn append_da(
         &self,
         proposal: &Proposal<TYPES, DaProposal<TYPES>>,
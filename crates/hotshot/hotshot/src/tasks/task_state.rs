@@ -5,7 +5,7 @@
 // along with the HotShot repository. If not, see <https://mit-license.org/>.
 
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     sync::{atomic::AtomicBool, Arc},
     time::Instant,
 };
@@ -13,10 +13,17 @@ use std::{
 use async_trait::async_trait;
 use chrono::Utc;
 use hotshot_task_impls::{
-    builder::BuilderClient, consensus::ConsensusTaskState, da::DaTaskState,
-    quorum_proposal::QuorumProposalTaskState, quorum_proposal_recv::QuorumProposalRecvTaskState,
-    quorum_vote::QuorumVoteTaskState, request::NetworkRequestState, rewind::RewindTaskState,
-    transactions::TransactionTaskState, upgrade::UpgradeTaskState, vid::VidTaskState,
+    builder::BuilderClient,
+    consensus::ConsensusTaskState,
+    da::DaTaskState,
+    quorum_proposal::QuorumProposalTaskState,
+    quorum_proposal_recv::QuorumProposalRecvTaskState,
+    quorum_vote::QuorumVoteTaskState,
+    request::NetworkRequestState,
+    rewind::{EventRecorder, RecorderConfig, RewindTaskState},
+    transactions::TransactionTaskState,
+    upgrade::UpgradeTaskState,
+    vid::VidTaskState,
     view_sync::ViewSyncTaskState,
 };
 use hotshot_types::{
@@ -132,6 +139,10 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> CreateTaskState
             id: handle.hotshot.id,
             upgrade_lock: handle.hotshot.upgrade_lock.clone(),
             epoch_height: handle.epoch_height,
+            output_event_stream: handle.hotshot.external_event_stream.0.clone(),
+            storage: Arc::clone(&handle.storage),
+            gossiped_vid_shares: BTreeMap::new(),
+            vid_encoding_evidence_views: BTreeSet::new(),
         }
     }
 }
@@ -154,6 +165,8 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> CreateTaskState
             id: handle.hotshot.id,
             storage: Arc::clone(&handle.storage),
             upgrade_lock: handle.hotshot.upgrade_lock.clone(),
+            da_payload_commitments: BTreeMap::new(),
+            own_vid_shares: BTreeMap::new(),
         }
     }
 }
@@ -241,6 +254,7 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> CreateTaskState
             instance_state: handle.hotshot.instance_state(),
             latest_voted_view: handle.cur_view().await,
             vote_dependencies: BTreeMap::new(),
+            vid_encoding_evidence_views: BTreeSet::new(),
             network: Arc::clone(&handle.hotshot.network),
             membership: handle.hotshot.membership_coordinator.clone(),
             output_event_stream: handle.hotshot.external_event_stream.0.clone(),
@@ -300,6 +314,7 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> CreateTaskState
             output_event_stream: handle.hotshot.external_event_stream.0.clone(),
             storage: Arc::clone(&handle.storage),
             spawned_tasks: BTreeMap::new(),
+            first_proposals: BTreeMap::new(),
             id: handle.hotshot.id,
             upgrade_lock: handle.hotshot.upgrade_lock.clone(),
             epoch_height: handle.hotshot.config.epoch_height,
@@ -347,7 +362,7 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> CreateTaskState
 {
     async fn create_from(handle: &SystemContextHandle<TYPES, I, V>) -> Self {
         Self {
-            events: Vec::new(),
+            recorder: EventRecorder::new(handle.hotshot.id, RecorderConfig::from_env()),
             id: handle.hotshot.id,
         }
     }
@@ -9,10 +9,14 @@ use std::{sync::Arc, time::Duration};
 use async_broadcast::{Receiver, Sender};
 use chrono::Utc;
 use hotshot_types::{
+    equivocation::EquivocationEvidence,
     event::{Event, EventType},
     simple_certificate::EpochRootQuorumCertificate,
     simple_vote::{EpochRootQuorumVote, HasEpoch, QuorumVote2, TimeoutData2, TimeoutVote2},
-    traits::node_implementation::{ConsensusTime, NodeImplementation, NodeType},
+    traits::{
+        node_implementation::{ConsensusTime, NodeImplementation, NodeType},
+        storage::Storage,
+    },
     utils::{is_epoch_root, is_epoch_transition, is_last_block, EpochTransitionIndicator},
     vote::{HasViewNumber, Vote},
 };
@@ -570,3 +574,49 @@ pub(crate) async fn handle_timeout<TYPES: NodeType, I: NodeImplementation<TYPES>
 
     Ok(())
 }
+
+/// Handle an `EquivocationDetected` event by verifying the evidence, persisting it and reporting
+/// it to the application.
+#[instrument(skip_all)]
+pub(crate) async fn handle_equivocation_detected<
+    TYPES: NodeType,
+    I: NodeImplementation<TYPES>,
+    V: Versions,
+>(
+    evidence: &EquivocationEvidence<TYPES>,
+    task_state: &mut ConsensusTaskState<TYPES, I, V>,
+) -> Result<()> {
+    evidence
+        .verify(&task_state.upgrade_lock)
+        .await
+        .context(warn!("Received invalid equivocation evidence"))?;
+
+    task_state
+        .storage
+        .write()
+        .await
+        .append_equivocation_evidence(evidence)
+        .await
+        .wrap()
+        .context(error!("Failed to store equivocation evidence"))?;
+
+    let view_number = evidence.view_number();
+    tracing::warn!(
+        "Node {} equivocated on {} in view {view_number}",
+        evidence.offender(),
+        evidence.kind()
+    );
+
+    broadcast_event(
+        Event {
+            view_number,
+            event: EventType::Equivocation {
+                evidence: Arc::new(evidence.clone()),
+            },
+        },
+        &task_state.output_event_stream,
+    )
+    .await;
+
+    Ok(())
+}
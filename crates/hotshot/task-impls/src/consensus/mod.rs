@@ -31,7 +31,8 @@ use tokio::task::JoinHandle;
 use tracing::instrument;
 
 use self::handlers::{
-    handle_quorum_vote_recv, handle_timeout, handle_timeout_vote_recv, handle_view_change,
+    handle_equivocation_detected, handle_quorum_vote_recv, handle_timeout,
+    handle_timeout_vote_recv, handle_view_change,
 };
 use crate::{
     events::HotShotEvent,
@@ -147,6 +148,11 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> ConsensusTaskSt
                     tracing::debug!("Failed to handle TimeoutVoteRecv event; error = {e}");
                 }
             },
+            HotShotEvent::EquivocationDetected(ref evidence) => {
+                if let Err(e) = handle_equivocation_detected(evidence, self).await {
+                    tracing::debug!("Failed to handle EquivocationDetected event; error = {e}");
+                }
+            },
             HotShotEvent::SetFirstEpoch(view, epoch) => {
                 self.first_epoch = Some((*view, *epoch));
             },
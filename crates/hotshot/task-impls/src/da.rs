@@ -4,7 +4,7 @@
 // You should have received a copy of the MIT License
 // along with the HotShot repository. If not, see <https://mit-license.org/>.
 
-use std::{marker::PhantomData, sync::Arc};
+use std::{collections::BTreeMap, marker::PhantomData, sync::Arc};
 
 use async_broadcast::{Receiver, Sender};
 use async_lock::RwLock;
@@ -12,7 +12,11 @@ use async_trait::async_trait;
 use hotshot_task::task::TaskState;
 use hotshot_types::{
     consensus::{Consensus, OuterConsensus, PayloadWithMetadata},
-    data::{vid_commitment, vid_disperse::vid_total_weight, DaProposal2, PackedBundle},
+    data::{
+        vid_commitment,
+        vid_disperse::{vid_total_weight, VidDisperseShare2},
+        DaProposal2, PackedBundle, VidCommitment, VidDisperseShare,
+    },
     epoch_membership::EpochMembershipCoordinator,
     event::{Event, EventType},
     message::{Proposal, UpgradeLock},
@@ -20,7 +24,7 @@ use hotshot_types::{
     simple_vote::{DaData2, DaVote2},
     traits::{
         network::ConnectedNetwork,
-        node_implementation::{NodeImplementation, NodeType, Versions},
+        node_implementation::{ConsensusTime, NodeImplementation, NodeType, Versions},
         signature_key::SignatureKey,
         storage::Storage,
         BlockPayload, EncodeBytes,
@@ -78,6 +82,12 @@ pub struct DaTaskState<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Version
 
     /// Lock for a decided upgrade
     pub upgrade_lock: UpgradeLock<TYPES, V>,
+
+    /// VID commitments computed from the DA proposals we voted on, by view
+    pub da_payload_commitments: BTreeMap<TYPES::View, VidCommitment>,
+
+    /// Our own leader-signed VID shares, by view, to check against the DA proposals we voted on
+    pub own_vid_shares: BTreeMap<TYPES::View, Proposal<TYPES, VidDisperseShare2<TYPES>>>,
 }
 
 impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> DaTaskState<TYPES, I, V> {
@@ -250,6 +260,11 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> DaTaskState<TYP
                 tracing::debug!("Sending vote to the DA leader {:?}", vote.view_number());
 
                 broadcast_event(Arc::new(HotShotEvent::DaVoteSend(vote)), &event_stream).await;
+
+                self.da_payload_commitments
+                    .insert(view_number, payload_commitment);
+                self.check_own_vid_share(view_number, &event_stream).await;
+
                 let mut consensus_writer = self.consensus.write().await;
 
                 // Ensure this view is in the view map for garbage collection.
@@ -329,6 +344,42 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> DaTaskState<TYP
                     });
                 }
             },
+            HotShotEvent::VidShareRecv(_, share) => {
+                // Only the shares the leader dispersed to us for the current epoch can be checked
+                // against the DA proposals we voted on.
+                let VidDisperseShare::V1(data) = &share.data else {
+                    return Ok(());
+                };
+                let view = data.view_number;
+                if data.recipient_key != self.public_key
+                    || data.epoch != data.target_epoch
+                    || self.cur_view > view + 1
+                {
+                    return Ok(());
+                }
+
+                let view_leader_key = self
+                    .membership_coordinator
+                    .membership_for_epoch(data.epoch)
+                    .await
+                    .context(warn!("No stake table for epoch {:?}", data.epoch))?
+                    .leader(view)
+                    .await?;
+                ensure!(
+                    view_leader_key.validate(&share.signature, data.payload_commitment.as_ref()),
+                    debug!("VID share for view {view} was not signed by the leader")
+                );
+
+                self.own_vid_shares.insert(
+                    view,
+                    Proposal {
+                        data: data.clone(),
+                        signature: share.signature.clone(),
+                        _pd: PhantomData,
+                    },
+                );
+                self.check_own_vid_share(view, &event_stream).await;
+            },
             HotShotEvent::DaVoteRecv(ref vote) => {
                 tracing::debug!("DA vote recv, Main Task {:?}", vote.view_number());
                 // Check if we are the leader and the vote is from the sender.
@@ -377,6 +428,11 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> DaTaskState<TYP
                     tracing::info!("View changed by more than 1 going to view {view:?}");
                 }
                 self.cur_view = view;
+
+                // Keep the data of the previous view, we still accept its DA proposal.
+                let oldest_view = TYPES::View::new(view.saturating_sub(1));
+                self.da_payload_commitments = self.da_payload_commitments.split_off(&oldest_view);
+                self.own_vid_shares = self.own_vid_shares.split_off(&oldest_view);
             },
             HotShotEvent::BlockRecv(packed_bundle) => {
                 let PackedBundle::<TYPES> {
@@ -459,6 +515,38 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> DaTaskState<TYP
         }
         Ok(())
     }
+
+    /// Gossip our VID share if the leader dispersed a payload commitment that doesn't match the DA
+    /// proposal we voted on for the same view, so that a proof of incorrect encoding can be
+    /// gathered by the VID task.
+    async fn check_own_vid_share(
+        &mut self,
+        view: TYPES::View,
+        event_stream: &Sender<Arc<HotShotEvent<TYPES>>>,
+    ) {
+        let Some(payload_commitment) = self.da_payload_commitments.get(&view) else {
+            return;
+        };
+        // Each share only needs to be checked once.
+        let Some(share) = self.own_vid_shares.remove(&view) else {
+            return;
+        };
+        if *payload_commitment == VidCommitment::from(share.data.payload_commitment) {
+            return;
+        }
+        tracing::warn!(
+            "The leader dispersed VID commitment {} for view {view}, but the DA proposal commits to {payload_commitment}",
+            share.data.payload_commitment
+        );
+        broadcast_event(
+            Arc::new(HotShotEvent::VidShareGossipSend(
+                share,
+                self.public_key.clone(),
+            )),
+            event_stream,
+        )
+        .await;
+    }
 }
 
 #[async_trait]
@@ -11,9 +11,11 @@ use either::Either;
 use hotshot_task::task::TaskEvent;
 use hotshot_types::{
     data::{
+        vid_disperse::{VidDisperseShare2, VidEncodingEvidence},
         DaProposal2, Leaf2, PackedBundle, QuorumProposal2, QuorumProposalWrapper, UpgradeProposal,
         VidCommitment, VidDisperse, VidDisperseShare,
     },
+    equivocation::EquivocationEvidence,
     message::Proposal,
     request_response::ProposalRequestPayload,
     simple_certificate::{
@@ -32,6 +34,7 @@ use hotshot_types::{
     utils::BuilderCommitment,
     vote::HasViewNumber,
 };
+use serde::{Deserialize, Serialize};
 use vec1::Vec1;
 
 use crate::view_sync::ViewSyncPhase;
@@ -65,7 +68,8 @@ impl<TYPES: NodeType> Eq for ProposalMissing<TYPES> {}
 pub struct HotShotTaskCompleted;
 
 /// All of the possible events that can be passed between Sequencing `HotShot` tasks
-#[derive(Eq, PartialEq, Debug, Clone)]
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(bound(deserialize = "", serialize = ""))]
 #[allow(clippy::large_enum_variant)]
 pub enum HotShotEvent<TYPES: NodeType> {
     /// Shutdown the task
@@ -212,6 +216,27 @@ pub enum HotShotEvent<TYPES: NodeType> {
     ),
     /// VID share data is validated.
     VidShareValidated(Proposal<TYPES, VidDisperseShare<TYPES>>),
+    /// Gossip a leader-signed VID share to gather a proof of incorrect encoding; emitted by the DA
+    /// task when the leader's dispersal doesn't match its DA proposal, and by the VID task
+    VidShareGossipSend(
+        Proposal<TYPES, VidDisperseShare2<TYPES>>,
+        TYPES::SignatureKey,
+    ),
+    /// A gossiped VID share has been received from the network; handled by the VID task
+    VidShareGossipRecv(
+        Proposal<TYPES, VidDisperseShare2<TYPES>>,
+        TYPES::SignatureKey,
+    ),
+    /// Send evidence that the leader dispersed an incorrectly encoded block; emitted by the VID task
+    VidEncodingEvidenceSend(VidEncodingEvidence<TYPES>, TYPES::SignatureKey),
+    /// Evidence of an incorrectly encoded block has been received from the network; handled by the VID task
+    VidEncodingEvidenceRecv(VidEncodingEvidence<TYPES>, TYPES::SignatureKey),
+    /// Evidence of an incorrectly encoded block has been gathered or validated; we must not vote
+    /// for the view
+    VidEncodingEvidenceValidated(VidEncodingEvidence<TYPES>),
+    /// A node signed two conflicting proposals or votes for the same view; emitted by the quorum
+    /// proposal recv task and the vote collectors, handled by the consensus task
+    EquivocationDetected(EquivocationEvidence<TYPES>),
     /// Upgrade proposal has been received from the network
     UpgradeProposalRecv(Proposal<TYPES, UpgradeProposal<TYPES>>, TYPES::SignatureKey),
     /// Upgrade proposal has been sent to the network
@@ -363,6 +388,12 @@ impl<TYPES: NodeType> HotShotEvent<TYPES> {
             HotShotEvent::VidShareRecv(_, proposal) | HotShotEvent::VidShareValidated(proposal) => {
                 Some(proposal.data.view_number())
             },
+            HotShotEvent::VidShareGossipSend(proposal, _)
+            | HotShotEvent::VidShareGossipRecv(proposal, _) => Some(proposal.data.view_number()),
+            HotShotEvent::VidEncodingEvidenceSend(evidence, _)
+            | HotShotEvent::VidEncodingEvidenceRecv(evidence, _)
+            | HotShotEvent::VidEncodingEvidenceValidated(evidence) => Some(evidence.view_number()),
+            HotShotEvent::EquivocationDetected(evidence) => Some(evidence.view_number()),
             HotShotEvent::UpgradeProposalRecv(proposal, _)
             | HotShotEvent::UpgradeProposalSend(proposal, _) => Some(proposal.data.view_number()),
             HotShotEvent::UpgradeVoteRecv(vote) | HotShotEvent::UpgradeVoteSend(vote) => {
@@ -619,6 +650,38 @@ impl<TYPES: NodeType> Display for HotShotEvent<TYPES> {
                 "VIDShareValidated(view_number={:?})",
                 proposal.data.view_number()
             ),
+            HotShotEvent::VidShareGossipSend(proposal, _) => write!(
+                f,
+                "VidShareGossipSend(view_number={:?})",
+                proposal.data.view_number()
+            ),
+            HotShotEvent::VidShareGossipRecv(proposal, _) => write!(
+                f,
+                "VidShareGossipRecv(view_number={:?})",
+                proposal.data.view_number()
+            ),
+            HotShotEvent::VidEncodingEvidenceSend(evidence, _) => write!(
+                f,
+                "VidEncodingEvidenceSend(view_number={:?})",
+                evidence.view_number()
+            ),
+            HotShotEvent::VidEncodingEvidenceRecv(evidence, _) => write!(
+                f,
+                "VidEncodingEvidenceRecv(view_number={:?})",
+                evidence.view_number()
+            ),
+            HotShotEvent::VidEncodingEvidenceValidated(evidence) => write!(
+                f,
+                "VidEncodingEvidenceValidated(view_number={:?})",
+                evidence.view_number()
+            ),
+            HotShotEvent::EquivocationDetected(evidence) => write!(
+                f,
+                "EquivocationDetected(view_number={:?}, offender={}, kind={})",
+                evidence.view_number(),
+                evidence.offender(),
+                evidence.kind()
+            ),
             HotShotEvent::UpgradeProposalRecv(proposal, _) => write!(
                 f,
                 "UpgradeProposalRecv(view_number={:?})",
@@ -416,6 +416,28 @@ impl<TYPES: NodeType, V: Versions> NetworkMessageTaskState<TYPES, V> {
                             }
                             HotShotEvent::VidShareRecv(sender, convert_proposal(proposal))
                         },
+                        DaConsensusMessage::VidShareGossip(proposal) => {
+                            if !self
+                                .upgrade_lock
+                                .epochs_enabled(proposal.data.view_number())
+                                .await
+                            {
+                                tracing::warn!("received DaConsensusMessage::VidShareGossip for view {} but epochs are not enabled for that view", proposal.data.view_number());
+                                return;
+                            }
+                            HotShotEvent::VidShareGossipRecv(proposal, sender)
+                        },
+                        DaConsensusMessage::VidEncodingEvidence(evidence) => {
+                            if !self
+                                .upgrade_lock
+                                .epochs_enabled(evidence.view_number())
+                                .await
+                            {
+                                tracing::warn!("received DaConsensusMessage::VidEncodingEvidence for view {} but epochs are not enabled for that view", evidence.view_number());
+                                return;
+                            }
+                            HotShotEvent::VidEncodingEvidenceRecv(evidence, sender)
+                        },
                     },
                 };
                 broadcast_event(Arc::new(event), &self.internal_event_stream).await;
@@ -866,6 +888,20 @@ impl<
                 self.handle_vid_disperse_proposal(proposal, &sender).await;
                 None
             },
+            HotShotEvent::VidShareGossipSend(proposal, sender) => Some((
+                sender,
+                MessageKind::<TYPES>::from_consensus_message(SequencingMessage::Da(
+                    DaConsensusMessage::VidShareGossip(proposal),
+                )),
+                TransmitType::Broadcast,
+            )),
+            HotShotEvent::VidEncodingEvidenceSend(evidence, sender) => Some((
+                sender,
+                MessageKind::<TYPES>::from_consensus_message(SequencingMessage::Da(
+                    DaConsensusMessage::VidEncodingEvidence(evidence),
+                )),
+                TransmitType::Broadcast,
+            )),
             HotShotEvent::DaProposalSend(proposal, sender) => {
                 *maybe_action = Some(HotShotAction::DaPropose);
 
@@ -6,7 +6,10 @@
 
 #![allow(dead_code)]
 
-use std::sync::Arc;
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    sync::Arc,
+};
 
 use async_broadcast::{broadcast, Receiver, Sender};
 use async_lock::{RwLock, RwLockUpgradableReadGuard};
@@ -14,7 +17,8 @@ use committable::Committable;
 use hotshot_types::{
     consensus::OuterConsensus,
     data::{Leaf2, QuorumProposal, QuorumProposalWrapper},
-    epoch_membership::EpochMembershipCoordinator,
+    epoch_membership::{EpochMembership, EpochMembershipCoordinator},
+    equivocation::EquivocationEvidence,
     message::Proposal,
     simple_certificate::{QuorumCertificate, QuorumCertificate2},
     simple_vote::HasEpoch,
@@ -427,3 +431,45 @@ pub(crate) async fn handle_quorum_proposal_recv<
 
     Ok(())
 }
+
+/// Compare a proposal with the first validly signed proposal we saw for its view, and report the
+/// leader if they are for different leaves.
+///
+/// # Errors
+/// If the proposal is not signed by the leader of its view.
+pub(crate) async fn check_proposal_equivocation<TYPES: NodeType>(
+    proposal: &Proposal<TYPES, QuorumProposalWrapper<TYPES>>,
+    first_proposals: &mut BTreeMap<TYPES::View, Proposal<TYPES, QuorumProposalWrapper<TYPES>>>,
+    membership: &EpochMembership<TYPES>,
+    event_sender: &Sender<Arc<HotShotEvent<TYPES>>>,
+) -> Result<()> {
+    let view_number = proposal.data.view_number();
+    if first_proposals.get(&view_number).is_some_and(|first| {
+        Leaf2::from_quorum_proposal(&first.data).commit()
+            == Leaf2::from_quorum_proposal(&proposal.data).commit()
+    }) {
+        return Ok(());
+    }
+
+    proposal.validate_signature(membership).await?;
+
+    match first_proposals.entry(view_number) {
+        Entry::Vacant(entry) => {
+            entry.insert(proposal.clone());
+        },
+        Entry::Occupied(entry) => {
+            let evidence = EquivocationEvidence::QuorumProposal {
+                leader: membership.leader(view_number).await?,
+                first: entry.get().clone(),
+                second: proposal.clone(),
+            };
+            broadcast_event(
+                Arc::new(HotShotEvent::EquivocationDetected(evidence)),
+                event_sender,
+            )
+            .await;
+        },
+    }
+
+    Ok(())
+}
@@ -16,10 +16,10 @@ use futures::future::{err, join_all};
 use hotshot_task::task::{Task, TaskState};
 use hotshot_types::{
     consensus::{Consensus, OuterConsensus},
-    data::{EpochNumber, Leaf, ViewChangeEvidence2},
+    data::{EpochNumber, Leaf, QuorumProposalWrapper, ViewChangeEvidence2},
     epoch_membership::{self, EpochMembership, EpochMembershipCoordinator},
     event::Event,
-    message::UpgradeLock,
+    message::{Proposal, UpgradeLock},
     simple_certificate::UpgradeCertificate,
     simple_vote::HasEpoch,
     traits::{
@@ -35,7 +35,7 @@ use tokio::task::JoinHandle;
 use tracing::{debug, error, info, instrument, warn};
 use vbs::version::Version;
 
-use self::handlers::handle_quorum_proposal_recv;
+use self::handlers::{check_proposal_equivocation, handle_quorum_proposal_recv};
 use crate::{
     events::{HotShotEvent, ProposalMissing},
     helpers::{broadcast_event, fetch_proposal, parent_leaf_and_state},
@@ -77,6 +77,10 @@ pub struct QuorumProposalRecvTaskState<TYPES: NodeType, I: NodeImplementation<TY
     /// they are stale
     pub spawned_tasks: BTreeMap<TYPES::View, Vec<JoinHandle<()>>>,
 
+    /// The first validly signed proposal received for each recent view, to detect a leader
+    /// proposing twice
+    pub first_proposals: BTreeMap<TYPES::View, Proposal<TYPES, QuorumProposalWrapper<TYPES>>>,
+
     /// The node's id
     pub id: u64,
 
@@ -167,6 +171,16 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions>
                     tracing::warn!("No Stake table for epoch = {proposal_epoch:?}");
                     return;
                 };
+                if let Err(e) = check_proposal_equivocation(
+                    proposal,
+                    &mut self.first_proposals,
+                    &epoch_membership,
+                    &event_sender,
+                )
+                .await
+                {
+                    tracing::debug!(?e, "Failed to check the proposal for equivocation");
+                }
                 let validation_info = ValidationInfo::<TYPES, I, V> {
                     id: self.id,
                     public_key: self.public_key.clone(),
@@ -205,6 +219,7 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions>
                 // to enter view V + 1.
                 let oldest_view_to_keep = TYPES::View::new(view.saturating_sub(1));
                 self.cancel_tasks(oldest_view_to_keep);
+                self.first_proposals = self.first_proposals.split_off(&oldest_view_to_keep);
             },
             _ => {},
         }
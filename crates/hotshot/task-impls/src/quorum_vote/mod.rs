@@ -4,7 +4,11 @@
 // You should have received a copy of the MIT License
 // along with the HotShot repository. If not, see <https://mit-license.org/>.
 
-use std::{collections::BTreeMap, sync::Arc, time::Instant};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    time::Instant,
+};
 
 use async_broadcast::{InactiveReceiver, Receiver, Sender};
 use async_lock::RwLock;
@@ -323,6 +327,9 @@ pub struct QuorumVoteTaskState<TYPES: NodeType, I: NodeImplementation<TYPES>, V:
     /// Table for the in-progress dependency tasks.
     pub vote_dependencies: BTreeMap<TYPES::View, JoinHandle<()>>,
 
+    /// Views whose leader was proven to have dispersed an incorrectly encoded block, which we won't vote for.
+    pub vid_encoding_evidence_views: BTreeSet<TYPES::View>,
+
     /// The underlying network
     pub network: Arc<I::Network>,
 
@@ -416,7 +423,9 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> QuorumVoteTaskS
             "Attempting to make dependency task for view {view_number:?} and event {event:?}"
         );
 
-        if self.vote_dependencies.contains_key(&view_number) {
+        if self.vote_dependencies.contains_key(&view_number)
+            || self.vid_encoding_evidence_views.contains(&view_number)
+        {
             return;
         }
 
@@ -643,6 +652,14 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> QuorumVoteTaskS
                     Arc::clone(&event),
                 );
             },
+            HotShotEvent::VidEncodingEvidenceValidated(evidence) => {
+                let view = evidence.view_number;
+                tracing::warn!("Not voting for view {view}, the leader's VID dispersal is invalid");
+                self.vid_encoding_evidence_views.insert(view);
+                if let Some(dependency) = self.vote_dependencies.remove(&view) {
+                    dependency.abort();
+                }
+            },
             HotShotEvent::Timeout(view, ..) => {
                 let view = TYPES::View::new(view.saturating_sub(1));
                 // cancel old tasks
@@ -663,6 +680,8 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> QuorumVoteTaskS
                     task.abort();
                 }
                 self.vote_dependencies = current_tasks;
+                self.vid_encoding_evidence_views =
+                    self.vid_encoding_evidence_views.split_off(&view);
             },
             _ => {},
         }
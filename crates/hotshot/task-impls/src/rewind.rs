@@ -4,30 +4,405 @@
 // You should have received a copy of the MIT License
 // along with the HotShot repository. If not, see <https://mit-license.org/>.
 
-use std::{fs::OpenOptions, io::Write, sync::Arc};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use async_broadcast::{Receiver, Sender};
 use async_trait::async_trait;
+use chrono::Utc;
 use hotshot_task::task::TaskState;
 use hotshot_types::traits::node_implementation::NodeType;
-use hotshot_utils::anytrace::Result;
+use hotshot_utils::anytrace::*;
+use serde::{Deserialize, Serialize};
 
 use crate::events::HotShotEvent;
 
+/// Bytes at the start of every recording file, identifying the format and its version.
+const RECORDING_MAGIC: [u8; 8] = *b"HSREWND\x01";
+
+/// Capacity of the buffer in front of the current recording file.
+const WRITE_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Where and how much to record.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecorderConfig {
+    /// Directory in which the recording files are written.
+    pub dir: PathBuf,
+
+    /// Size in bytes after which the current recording file is closed and a new one started.
+    pub max_file_size: u64,
+
+    /// Number of recording files to keep. Older files are deleted when a new one is started.
+    pub max_files: usize,
+}
+
+impl Default for RecorderConfig {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("."),
+            max_file_size: 64 * 1024 * 1024,
+            max_files: 8,
+        }
+    }
+}
+
+impl RecorderConfig {
+    /// Read the configuration from the `HOTSHOT_REWIND_DIR`, `HOTSHOT_REWIND_MAX_FILE_SIZE` and
+    /// `HOTSHOT_REWIND_MAX_FILES` environment variables, using the defaults for any which are not
+    /// set or cannot be parsed.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            dir: std::env::var("HOTSHOT_REWIND_DIR")
+                .map(PathBuf::from)
+                .unwrap_or(default.dir),
+            max_file_size: std::env::var("HOTSHOT_REWIND_MAX_FILE_SIZE")
+                .ok()
+                .and_then(|size| size.parse().ok())
+                .unwrap_or(default.max_file_size),
+            max_files: std::env::var("HOTSHOT_REWIND_MAX_FILES")
+                .ok()
+                .and_then(|files| files.parse().ok())
+                .unwrap_or(default.max_files),
+        }
+    }
+}
+
+/// An event as received by a node, with its position in the node's event stream.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "", serialize = ""))]
+pub struct RecordedEvent<TYPES: NodeType> {
+    /// The number of events the node received before this one.
+    pub sequence: u64,
+
+    /// When the event was received, in milliseconds since the Unix epoch.
+    pub timestamp: i64,
+
+    /// The event.
+    pub event: HotShotEvent<TYPES>,
+}
+
+/// A borrowed [`RecordedEvent`], which serializes identically, so that events need not be cloned to
+/// be recorded.
+#[derive(Serialize)]
+#[serde(bound(serialize = ""))]
+struct RecordedEventRef<'a, TYPES: NodeType> {
+    /// See [`RecordedEvent::sequence`].
+    sequence: u64,
+    /// See [`RecordedEvent::timestamp`].
+    timestamp: i64,
+    /// See [`RecordedEvent::event`].
+    event: &'a HotShotEvent<TYPES>,
+}
+
+/// Writes events to a rotating set of binary recording files.
+///
+/// Each file starts with [`RECORDING_MAGIC`], followed by a sequence of frames, each of which is a
+/// little endian `u32` length followed by a bincode serialized [`RecordedEvent`]. Files are named
+/// `rewind_{id}.{index}.bin`, where `index` increases with each rotation, so that a recording can
+/// be read back in order by sorting its files by index.
+pub struct EventRecorder<TYPES: NodeType> {
+    /// Where and how much to record.
+    config: RecorderConfig,
+
+    /// The id of the node being recorded.
+    id: u64,
+
+    /// The file currently being written, if one has been opened.
+    writer: Option<BufWriter<File>>,
+
+    /// The index of the file currently being written.
+    file_index: u64,
+
+    /// The number of bytes written to the current file.
+    file_size: u64,
+
+    /// The sequence number of the next event.
+    sequence: u64,
+
+    /// Phantom data for the node type.
+    _pd: PhantomData<TYPES>,
+}
+
+impl<TYPES: NodeType> EventRecorder<TYPES> {
+    /// Create a recorder for node `id`. No file is created until the first event is recorded.
+    #[must_use]
+    pub fn new(id: u64, config: RecorderConfig) -> Self {
+        Self {
+            config,
+            id,
+            writer: None,
+            file_index: 0,
+            file_size: 0,
+            sequence: 0,
+            _pd: PhantomData,
+        }
+    }
+
+    /// The number of events recorded so far.
+    #[must_use]
+    pub fn events_recorded(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Append `event` to the recording, starting a new file first if the current one is full.
+    ///
+    /// # Errors
+    /// If the event cannot be serialized or written. The event is skipped, but later events are
+    /// still recorded.
+    pub fn record(&mut self, event: &HotShotEvent<TYPES>) -> Result<()> {
+        let sequence = self.sequence;
+        self.sequence += 1;
+
+        let bytes = bincode::serialize(&RecordedEventRef {
+            sequence,
+            timestamp: Utc::now().timestamp_millis(),
+            event,
+        })
+        .wrap()
+        .context(error!("Failed to serialize event {sequence}"))?;
+        let len = u32::try_from(bytes.len())
+            .wrap()
+            .context(error!("Event {sequence} is too large to record"))?;
+
+        if self.writer.is_none() || self.file_size >= self.config.max_file_size {
+            self.rotate()?;
+        }
+        let writer = self
+            .writer
+            .as_mut()
+            .context(error!("No recording file to write to"))?;
+        writer
+            .write_all(&len.to_le_bytes())
+            .and_then(|()| writer.write_all(&bytes))
+            .wrap()
+            .context(error!("Failed to write event {sequence}"))?;
+        self.file_size += 4 + u64::from(len);
+
+        Ok(())
+    }
+
+    /// Flush all recorded events to disk.
+    ///
+    /// # Errors
+    /// If the current file cannot be flushed.
+    pub fn flush(&mut self) -> Result<()> {
+        if let Some(writer) = self.writer.as_mut() {
+            writer
+                .flush()
+                .wrap()
+                .context(error!("Failed to flush recording"))?;
+        }
+        Ok(())
+    }
+
+    /// Close the current file, if any, start the next one and delete the files which no longer fit
+    /// within `max_files`.
+    fn rotate(&mut self) -> Result<()> {
+        if self.writer.is_some() {
+            self.flush()?;
+            self.file_index += 1;
+        } else {
+            // Continue after any recording left by a previous run of this node, rather than
+            // overwriting it.
+            self.file_index = recording_files(&self.config.dir, self.id)?
+                .last()
+                .map_or(0, |(index, _)| index + 1);
+        }
+
+        fs::create_dir_all(&self.config.dir)
+            .wrap()
+            .context(error!("Failed to create recording directory"))?;
+        let path = recording_file_path(&self.config.dir, self.id, self.file_index);
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .wrap()
+            .context(error!("Failed to create recording file {}", path.display()))?;
+        let mut writer = BufWriter::with_capacity(WRITE_BUFFER_SIZE, file);
+        writer
+            .write_all(&RECORDING_MAGIC)
+            .wrap()
+            .context(error!("Failed to write recording file {}", path.display()))?;
+        self.writer = Some(writer);
+        self.file_size = RECORDING_MAGIC.len() as u64;
+
+        let files = recording_files(&self.config.dir, self.id)?;
+        let excess = files.len().saturating_sub(self.config.max_files.max(1));
+        for (_, path) in files.into_iter().take(excess) {
+            if let Err(e) = fs::remove_file(&path) {
+                tracing::warn!(
+                    "Failed to remove old recording file {}: {e}",
+                    path.display()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The path of the recording file with the given index for node `id`.
+fn recording_file_path(dir: &Path, id: u64, index: u64) -> PathBuf {
+    dir.join(format!("rewind_{id}.{index}.bin"))
+}
+
+/// The recording files for node `id` in `dir`, with their indices, oldest first.
+///
+/// # Errors
+/// If `dir` exists but cannot be read.
+pub fn recording_files(dir: &Path, id: u64) -> Result<Vec<(u64, PathBuf)>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => {
+            return Err(e)
+                .wrap()
+                .context(error!("Failed to read recording directory"))
+        },
+    };
+
+    let prefix = format!("rewind_{id}.");
+    let mut files = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let index = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix(&prefix)?
+                .strip_suffix(".bin")?
+                .parse()
+                .ok()?;
+            Some((index, path))
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+/// Read all the events recorded for node `id` in `dir`, in the order they were received.
+///
+/// If the node was restarted while recording to the same directory, the events of each run follow
+/// each other, and the sequence numbers start again from zero at the start of each run. A
+/// truncated frame at the end of a file, as left by a node which crashed while recording, ends
+/// that file without an error.
+///
+/// # Errors
+/// If a recording file cannot be read or is not a recording.
+pub fn read_recording<TYPES: NodeType>(dir: &Path, id: u64) -> Result<Vec<RecordedEvent<TYPES>>> {
+    let mut events = vec![];
+    for (_, path) in recording_files(dir, id)? {
+        read_recording_file(&path, &mut events)?;
+    }
+    Ok(events)
+}
+
+/// Read the events in the recording file at `path` into `events`.
+fn read_recording_file<TYPES: NodeType>(
+    path: &Path,
+    events: &mut Vec<RecordedEvent<TYPES>>,
+) -> Result<()> {
+    let file = File::open(path)
+        .wrap()
+        .context(error!("Failed to open recording file {}", path.display()))?;
+    let file_size = file
+        .metadata()
+        .wrap()
+        .context(error!("Failed to read recording file {}", path.display()))?
+        .len();
+    let mut reader = BufReader::new(file);
+
+    let mut magic = [0u8; RECORDING_MAGIC.len()];
+    reader
+        .read_exact(&mut magic)
+        .wrap()
+        .context(error!("Failed to read recording file {}", path.display()))?;
+    ensure!(
+        magic == RECORDING_MAGIC,
+        error!("{} is not a recording file", path.display())
+    );
+    let mut offset = RECORDING_MAGIC.len() as u64;
+
+    loop {
+        let mut len = [0u8; 4];
+        match reader.read_exact(&mut len) {
+            Ok(()) => {},
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => {
+                return Err(e)
+                    .wrap()
+                    .context(error!("Failed to read recording file {}", path.display()))
+            },
+        }
+
+        offset += len.len() as u64;
+
+        // Don't trust the length of a frame beyond the size of the file, so a corrupt length can't
+        // make us allocate more than the file could hold.
+        let frame_size = u64::from(u32::from_le_bytes(len));
+        if frame_size > file_size.saturating_sub(offset) {
+            tracing::warn!(
+                "Recording file {} ends with a partial event",
+                path.display()
+            );
+            return Ok(());
+        }
+        offset += frame_size;
+
+        let mut bytes = vec![0u8; frame_size as usize];
+        match reader.read_exact(&mut bytes) {
+            Ok(()) => {},
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+                tracing::warn!(
+                    "Recording file {} ends with a partial event",
+                    path.display()
+                );
+                return Ok(());
+            },
+            Err(e) => {
+                return Err(e)
+                    .wrap()
+                    .context(error!("Failed to read recording file {}", path.display()))
+            },
+        }
+
+        events.push(
+            bincode::deserialize(&bytes)
+                .wrap()
+                .context(error!("Failed to parse event in {}", path.display()))?,
+        );
+    }
+}
+
 /// The task state for the `Rewind` task is used to capture all events received
 /// by a particular node, in the order they've been received.
+///
+/// Events are streamed to disk by an [`EventRecorder`] as they arrive, so memory use does not grow
+/// with the length of the run. The recording can be read back with [`read_recording`] and replayed
+/// into the task state machines.
 pub struct RewindTaskState<TYPES: NodeType> {
-    /// All events received by this node since the beginning of time.
-    pub events: Vec<Arc<HotShotEvent<TYPES>>>,
+    /// The recorder all events are written to.
+    pub recorder: EventRecorder<TYPES>,
 
     /// The id of this node
     pub id: u64,
 }
 
 impl<TYPES: NodeType> RewindTaskState<TYPES> {
-    /// Handles all events, storing them to the private state
+    /// Handles all events, writing them to the recording
     pub fn handle(&mut self, event: &Arc<HotShotEvent<TYPES>>) {
-        self.events.push(Arc::clone(event));
+        // We do not want to die here, so we log and move on capturing as many events as we can.
+        if let Err(e) = self.recorder.record(event) {
+            tracing::error!("Failed to record event {event}; error = {e}");
+        }
     }
 }
 
@@ -46,28 +421,16 @@ impl<TYPES: NodeType> TaskState for RewindTaskState<TYPES> {
     }
 
     fn cancel_subtasks(&mut self) {
-        tracing::info!("Node ID {} Recording {} events", self.id, self.events.len());
-        let filename = format!("rewind_{}.log", self.id);
-        let mut file = match OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(&filename)
-        {
-            Ok(file) => file,
-            Err(e) => {
-                tracing::error!("Failed to write file {filename}; error = {e}");
-                return;
-            },
-        };
-
-        for (event_number, event) in self.events.iter().enumerate() {
-            // We do not want to die here, so we log and move on capturing as many events as we can.
-            if let Err(e) = writeln!(file, "{event_number}: {event}") {
-                tracing::error!(
-                    "Failed to write event number {event_number} and event {event}; error = {e}"
-                );
-            }
+        tracing::info!(
+            "Node ID {} Recorded {} events",
+            self.id,
+            self.recorder.events_recorded()
+        );
+        if let Err(e) = self.recorder.flush() {
+            tracing::error!(
+                "Failed to flush recording for node {}; error = {e}",
+                self.id
+            );
         }
     }
 }
@@ -4,33 +4,54 @@
 // You should have received a copy of the MIT License
 // along with the HotShot repository. If not, see <https://mit-license.org/>.
 
-use std::{marker::PhantomData, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    marker::PhantomData,
+    sync::Arc,
+};
 
 use async_broadcast::{Receiver, Sender};
+use async_lock::RwLock;
 use async_trait::async_trait;
 use hotshot_task::task::TaskState;
 use hotshot_types::{
     consensus::{OuterConsensus, PayloadWithMetadata},
-    data::{PackedBundle, VidDisperse, VidDisperseShare},
+    data::{
+        vid_disperse::{vid_total_weight, VidDisperseShare2, VidEncodingEvidence},
+        PackedBundle, VidDisperse, VidDisperseShare,
+    },
     epoch_membership::EpochMembershipCoordinator,
+    event::{Event, EventType},
     message::{Proposal, UpgradeLock},
     simple_vote::HasEpoch,
     traits::{
         block_contents::BlockHeader,
-        node_implementation::{NodeImplementation, NodeType, Versions},
+        node_implementation::{ConsensusTime, NodeImplementation, NodeType, Versions},
         signature_key::SignatureKey,
+        storage::Storage,
         BlockPayload,
     },
     utils::{is_epoch_transition, option_epoch_from_block_number},
+    vid::avidm::{init_avidm_param, AvidMCommitment},
 };
-use hotshot_utils::anytrace::Result;
-use tracing::{debug, error, info, instrument};
+use hotshot_utils::anytrace::*;
+use tokio::task::spawn_blocking;
+use tracing::instrument;
 
 use crate::{
     events::{HotShotEvent, HotShotTaskCompleted},
     helpers::broadcast_event,
 };
 
+/// Gossiped VID shares, by view, payload commitment and recipient.
+pub type GossipedVidShares<TYPES> = BTreeMap<
+    <TYPES as NodeType>::View,
+    HashMap<
+        AvidMCommitment,
+        BTreeMap<<TYPES as NodeType>::SignatureKey, Proposal<TYPES, VidDisperseShare2<TYPES>>>,
+    >,
+>;
+
 /// Tracks state of a VID task
 pub struct VidTaskState<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> {
     /// View number this view is executing in.
@@ -62,6 +83,18 @@ pub struct VidTaskState<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versio
 
     /// Number of blocks in an epoch, zero means there are no epochs
     pub epoch_height: u64,
+
+    /// Output events to application
+    pub output_event_stream: async_broadcast::Sender<Event<TYPES>>,
+
+    /// This node's storage ref
+    pub storage: Arc<RwLock<I::Storage>>,
+
+    /// Leader-signed VID shares gossiped to gather a proof of incorrect encoding
+    pub gossiped_vid_shares: GossipedVidShares<TYPES>,
+
+    /// Views for which we already have evidence that the leader dispersed an incorrectly encoded block
+    pub vid_encoding_evidence_views: BTreeSet<TYPES::View>,
 }
 
 impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> VidTaskState<TYPES, I, V> {
@@ -152,10 +185,12 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> VidTaskState<TY
                     &self.private_key,
                     vid_disperse.payload_commitment_ref(),
                 ) else {
-                    error!("VID: failed to sign dispersal payload");
+                    tracing::error!("VID: failed to sign dispersal payload");
                     return None;
                 };
-                debug!("publishing VID disperse for view {view_number} and epoch {epoch:?}");
+                tracing::debug!(
+                    "publishing VID disperse for view {view_number} and epoch {epoch:?}"
+                );
                 broadcast_event(
                     Arc::new(HotShotEvent::VidDisperseSend(
                         Proposal {
@@ -181,10 +216,16 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> VidTaskState<TY
                 }
 
                 if *view - *self.cur_view > 1 {
-                    info!("View changed by more than 1 going to view {view:?}");
+                    tracing::info!("View changed by more than 1 going to view {view:?}");
                 }
                 self.cur_view = view;
 
+                // Keep the shares and evidence of the previous view, which might still be in progress.
+                let oldest_view = TYPES::View::new(view.saturating_sub(1));
+                self.gossiped_vid_shares = self.gossiped_vid_shares.split_off(&oldest_view);
+                self.vid_encoding_evidence_views =
+                    self.vid_encoding_evidence_views.split_off(&oldest_view);
+
                 return None;
             },
 
@@ -233,10 +274,10 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> VidTaskState<TY
                     &self.private_key,
                     next_epoch_vid_disperse.payload_commitment().as_ref(),
                 ) else {
-                    error!("VID: failed to sign dispersal payload for the next epoch");
+                    tracing::error!("VID: failed to sign dispersal payload for the next epoch");
                     return None;
                 };
-                debug!(
+                tracing::debug!(
                     "publishing VID disperse for view {proposal_view_number} and epoch {target_epoch:?}"
                 );
                 broadcast_event(
@@ -252,6 +293,28 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> VidTaskState<TY
                 )
                 .await;
             },
+            HotShotEvent::VidShareValidated(share) => {
+                if let Err(e) = self.handle_vid_share_validated(share, &event_stream).await {
+                    tracing::debug!("{e}");
+                }
+            },
+            HotShotEvent::VidShareGossipSend(share, sender)
+            | HotShotEvent::VidShareGossipRecv(share, sender) => {
+                if let Err(e) = self
+                    .handle_vid_share_gossip(share, sender, &event_stream)
+                    .await
+                {
+                    tracing::debug!("{e}");
+                }
+            },
+            HotShotEvent::VidEncodingEvidenceRecv(evidence, _) => {
+                if let Err(e) = self
+                    .handle_vid_encoding_evidence(evidence, &event_stream)
+                    .await
+                {
+                    tracing::debug!("{e}");
+                }
+            },
             HotShotEvent::Shutdown => {
                 return Some(HotShotTaskCompleted);
             },
@@ -259,6 +322,285 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES>, V: Versions> VidTaskState<TY
         }
         None
     }
+
+    /// Pool a gossiped VID share, and try to build a proof of incorrect encoding out of the pooled
+    /// shares. Gossip from a DA member means the leader's dispersal doesn't match its DA proposal,
+    /// in which case we contribute our own share as well.
+    async fn handle_vid_share_gossip(
+        &mut self,
+        share: &Proposal<TYPES, VidDisperseShare2<TYPES>>,
+        sender: &TYPES::SignatureKey,
+        event_stream: &Sender<Arc<HotShotEvent<TYPES>>>,
+    ) -> Result<()> {
+        let view = share.data.view_number;
+        ensure!(
+            self.cur_view <= view + 1,
+            debug!("Throwing away gossiped VID share for view {view}, which is too old")
+        );
+        ensure!(
+            !self.vid_encoding_evidence_views.contains(&view),
+            debug!("We already have VID encoding evidence for view {view}")
+        );
+
+        let membership = self
+            .membership_coordinator
+            .membership_for_epoch(share.data.epoch)
+            .await
+            .context(warn!("No stake table for epoch {:?}", share.data.epoch))?;
+        let leader = membership.leader(view).await?;
+        ensure!(
+            leader.validate(&share.signature, share.data.payload_commitment.as_ref()),
+            warn!("Gossiped VID share for view {view} was not signed by the leader")
+        );
+        let total_weight = vid_total_weight::<TYPES>(
+            self.membership_coordinator
+                .membership_for_epoch(share.data.target_epoch)
+                .await?
+                .stake_table()
+                .await,
+            share.data.target_epoch,
+        );
+        ensure!(
+            share.data.verify_share(total_weight).is_ok(),
+            warn!("Failed to verify gossiped VID share for view {view}")
+        );
+
+        let payload_commitment = share.data.payload_commitment;
+        self.gossiped_vid_shares
+            .entry(view)
+            .or_default()
+            .entry(payload_commitment)
+            .or_default()
+            .insert(share.data.recipient_key.clone(), share.clone());
+
+        if *sender != self.public_key
+            && membership.da_committee_members(view).await.contains(sender)
+        {
+            self.gossip_own_vid_share(view, payload_commitment, &leader, event_stream)
+                .await;
+        }
+
+        self.try_build_vid_encoding_evidence(
+            view,
+            payload_commitment,
+            leader,
+            total_weight,
+            event_stream,
+        )
+        .await
+    }
+
+    /// Contribute our own share if it arrives after others started gossiping theirs.
+    async fn handle_vid_share_validated(
+        &mut self,
+        share: &Proposal<TYPES, VidDisperseShare<TYPES>>,
+        event_stream: &Sender<Arc<HotShotEvent<TYPES>>>,
+    ) -> Result<()> {
+        let VidDisperseShare::V1(data) = &share.data else {
+            return Ok(());
+        };
+        let view = data.view_number;
+        if !self
+            .gossiped_vid_shares
+            .get(&view)
+            .is_some_and(|shares| shares.contains_key(&data.payload_commitment))
+        {
+            return Ok(());
+        }
+        let leader = self
+            .membership_coordinator
+            .membership_for_epoch(data.epoch)
+            .await
+            .context(warn!("No stake table for epoch {:?}", data.epoch))?
+            .leader(view)
+            .await?;
+        self.gossip_own_vid_share(view, data.payload_commitment, &leader, event_stream)
+            .await;
+        Ok(())
+    }
+
+    /// Gossip our own VID share for `view`, if we have one signed by the leader for
+    /// `payload_commitment` and haven't gossiped it yet.
+    async fn gossip_own_vid_share(
+        &self,
+        view: TYPES::View,
+        payload_commitment: AvidMCommitment,
+        leader: &TYPES::SignatureKey,
+        event_stream: &Sender<Arc<HotShotEvent<TYPES>>>,
+    ) {
+        if self
+            .gossiped_vid_shares
+            .get(&view)
+            .and_then(|shares| shares.get(&payload_commitment))
+            .is_some_and(|shares| shares.contains_key(&self.public_key))
+        {
+            return;
+        }
+        let Some(share) = self
+            .consensus
+            .read()
+            .await
+            .vid_shares()
+            .get(&view)
+            .and_then(|shares| shares.get(&self.public_key))
+            .cloned()
+        else {
+            return;
+        };
+        let VidDisperseShare::V1(data) = share.data else {
+            return;
+        };
+        if data.payload_commitment != payload_commitment
+            || !leader.validate(&share.signature, payload_commitment.as_ref())
+        {
+            return;
+        }
+        broadcast_event(
+            Arc::new(HotShotEvent::VidShareGossipSend(
+                Proposal {
+                    data,
+                    signature: share.signature,
+                    _pd: PhantomData,
+                },
+                self.public_key.clone(),
+            )),
+            event_stream,
+        )
+        .await;
+    }
+
+    /// Build the evidence against the leader once the pooled shares for `payload_commitment`
+    /// reach the recovery threshold.
+    async fn try_build_vid_encoding_evidence(
+        &mut self,
+        view: TYPES::View,
+        payload_commitment: AvidMCommitment,
+        leader: TYPES::SignatureKey,
+        total_weight: usize,
+        event_stream: &Sender<Arc<HotShotEvent<TYPES>>>,
+    ) -> Result<()> {
+        let shares: Vec<_> = self
+            .gossiped_vid_shares
+            .get(&view)
+            .and_then(|shares| shares.get(&payload_commitment))
+            .map(|shares| shares.values().cloned().collect())
+            .unwrap_or_default();
+        let weight: usize = shares.iter().map(|share| share.data.weight()).sum();
+        let recovery_threshold = init_avidm_param(total_weight)?.recovery_threshold;
+        ensure!(
+            weight >= recovery_threshold,
+            debug!("Gathered VID weight {weight} of {recovery_threshold} for view {view}")
+        );
+
+        // Whatever the outcome, these shares are all we need to decide on the encoding.
+        self.vid_encoding_evidence_views.insert(view);
+        self.gossiped_vid_shares.remove(&view);
+
+        let evidence =
+            spawn_blocking(move || VidEncodingEvidence::from_shares(leader, &shares, total_weight))
+                .await
+                .wrap()
+                .context(error!("Join error"))?
+                .context(info!(
+                    "Could not build VID encoding evidence for view {view}"
+                ))?;
+
+        broadcast_event(
+            Arc::new(HotShotEvent::VidEncodingEvidenceSend(
+                evidence.clone(),
+                self.public_key.clone(),
+            )),
+            event_stream,
+        )
+        .await;
+        self.record_vid_encoding_evidence(evidence, event_stream)
+            .await
+    }
+
+    /// Validate evidence received from the network.
+    async fn handle_vid_encoding_evidence(
+        &mut self,
+        evidence: &VidEncodingEvidence<TYPES>,
+        event_stream: &Sender<Arc<HotShotEvent<TYPES>>>,
+    ) -> Result<()> {
+        let view = evidence.view_number;
+        ensure!(
+            self.cur_view <= view + 1,
+            debug!("Throwing away VID encoding evidence for view {view}, which is too old")
+        );
+        ensure!(
+            !self.vid_encoding_evidence_views.contains(&view),
+            debug!("We already have VID encoding evidence for view {view}")
+        );
+
+        let leader = self
+            .membership_coordinator
+            .membership_for_epoch(evidence.epoch)
+            .await
+            .context(warn!("No stake table for epoch {:?}", evidence.epoch))?
+            .leader(view)
+            .await?;
+        ensure!(
+            evidence.leader == leader,
+            warn!("VID encoding evidence for view {view} doesn't incriminate the leader")
+        );
+        let total_weight = vid_total_weight::<TYPES>(
+            self.membership_coordinator
+                .membership_for_epoch(evidence.target_epoch)
+                .await?
+                .stake_table()
+                .await,
+            evidence.target_epoch,
+        );
+        let evidence_clone = evidence.clone();
+        spawn_blocking(move || evidence_clone.verify(total_weight))
+            .await
+            .wrap()
+            .context(error!("Join error"))?
+            .context(warn!("Invalid VID encoding evidence for view {view}"))?;
+
+        self.vid_encoding_evidence_views.insert(view);
+        self.gossiped_vid_shares.remove(&view);
+        self.record_vid_encoding_evidence(evidence.clone(), event_stream)
+            .await
+    }
+
+    /// Stop voting for the view of the evidence, then persist it and notify the application.
+    async fn record_vid_encoding_evidence(
+        &self,
+        evidence: VidEncodingEvidence<TYPES>,
+        event_stream: &Sender<Arc<HotShotEvent<TYPES>>>,
+    ) -> Result<()> {
+        let view = evidence.view_number;
+        tracing::warn!(
+            "Leader {} dispersed an incorrectly encoded block in view {view}",
+            evidence.leader
+        );
+        broadcast_event(
+            Arc::new(HotShotEvent::VidEncodingEvidenceValidated(evidence.clone())),
+            event_stream,
+        )
+        .await;
+
+        self.storage
+            .write()
+            .await
+            .append_vid_encoding_evidence(&evidence)
+            .await
+            .wrap()
+            .context(error!("Failed to append VID encoding evidence to storage"))?;
+        broadcast_event(
+            Event {
+                view_number: view,
+                event: EventType::VidEncodingEvidence {
+                    evidence: Arc::new(evidence),
+                },
+            },
+            &self.output_event_stream,
+        )
+        .await;
+        Ok(())
+    }
 }
 
 #[async_trait]
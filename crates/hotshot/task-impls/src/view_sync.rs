@@ -34,6 +34,7 @@ use hotshot_types::{
     StakeTableEntries,
 };
 use hotshot_utils::anytrace::*;
+use serde::{Deserialize, Serialize};
 use tokio::{spawn, task::JoinHandle, time::sleep};
 use tracing::instrument;
 
@@ -44,7 +45,7 @@ use crate::{
         create_vote_accumulator, AccumulatorInfo, HandleVoteEvent, VoteCollectionTaskState,
     },
 };
-#[derive(PartialEq, PartialOrd, Clone, Debug, Eq, Hash)]
+#[derive(PartialEq, PartialOrd, Clone, Debug, Eq, Hash, Serialize, Deserialize)]
 /// Phases of view sync
 pub enum ViewSyncPhase {
     /// No phase; before the protocol has begun
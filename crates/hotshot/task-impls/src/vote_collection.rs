@@ -5,7 +5,7 @@
 // along with the HotShot repository. If not, see <https://mit-license.org/>.
 
 use std::{
-    collections::{btree_map::Entry, BTreeMap, HashMap},
+    collections::{btree_map::Entry, hash_map, BTreeMap, HashMap},
     fmt::Debug,
     future::Future,
     marker::PhantomData,
@@ -17,6 +17,7 @@ use async_trait::async_trait;
 use either::Either::{Left, Right};
 use hotshot_types::{
     epoch_membership::EpochMembership,
+    equivocation::EquivocationEvidence,
     message::UpgradeLock,
     simple_certificate::{
         DaCertificate2, EpochRootQuorumCertificate, NextEpochQuorumCertificate2, QuorumCertificate,
@@ -57,6 +58,9 @@ pub struct VoteCollectionTaskState<
     /// accumulator handles aggregating the votes
     pub accumulator: Option<VoteAccumulator<TYPES, VOTE, CERT, V>>,
 
+    /// The first vote received from each stake table member, to detect equivocation
+    pub first_votes: HashMap<TYPES::SignatureKey, VOTE>,
+
     /// The view which we are collecting votes for
     pub view: TYPES::View,
 
@@ -85,11 +89,20 @@ pub trait AggregatableVote<
 
     /// return the Hotshot event for the completion of this CERT
     fn make_cert_event(certificate: CERT, key: &TYPES::SignatureKey) -> HotShotEvent<TYPES>;
+
+    /// return the evidence that the signer of two conflicting votes equivocated, if votes of this
+    /// kind can be used as evidence
+    fn make_equivocation_evidence(
+        _first: VOTE,
+        _second: VOTE,
+    ) -> Option<EquivocationEvidence<TYPES>> {
+        None
+    }
 }
 
 impl<
         TYPES: NodeType,
-        VOTE: Vote<TYPES> + AggregatableVote<TYPES, VOTE, CERT>,
+        VOTE: Vote<TYPES> + AggregatableVote<TYPES, VOTE, CERT> + Clone,
         CERT: Certificate<TYPES, VOTE::Commitment, Voteable = VOTE::Commitment> + Clone + Debug,
         V: Versions,
     > VoteCollectionTaskState<TYPES, VOTE, CERT, V>
@@ -127,7 +140,15 @@ impl<
             "No accumulator to handle vote with. This shouldn't happen."
         ))?;
 
-        match accumulator.accumulate(vote, self.membership.clone()).await {
+        let key = vote.signing_key();
+        let num_votes = accumulator.num_votes_by(&key);
+        let cert = accumulator.accumulate(vote, self.membership.clone()).await;
+        // The accumulator only keeps validly signed votes of stake table members
+        if accumulator.num_votes_by(&key) > num_votes {
+            self.check_equivocation(key, vote, event_stream).await;
+        }
+
+        match cert {
             None => Ok(None),
             Some(cert) => {
                 tracing::debug!("Certificate Formed! {cert:?}");
@@ -143,6 +164,37 @@ impl<
             },
         }
     }
+
+    /// Compare a vote accepted by the accumulator with the first vote of its signer in this view,
+    /// and report the signer if they conflict.
+    ///
+    /// This is only done until the certificate for the view is formed.
+    async fn check_equivocation(
+        &mut self,
+        key: TYPES::SignatureKey,
+        vote: &VOTE,
+        event_stream: &Sender<Arc<HotShotEvent<TYPES>>>,
+    ) {
+        match self.first_votes.entry(key) {
+            hash_map::Entry::Occupied(first)
+                if first.get().data_commitment() != vote.data_commitment() =>
+            {
+                if let Some(evidence) =
+                    VOTE::make_equivocation_evidence(first.get().clone(), vote.clone())
+                {
+                    broadcast_event(
+                        Arc::new(HotShotEvent::EquivocationDetected(evidence)),
+                        event_stream,
+                    )
+                    .await;
+                }
+            },
+            hash_map::Entry::Occupied(_) => {},
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert(vote.clone());
+            },
+        }
+    }
 }
 
 /// Trait for types which will handle a vote event.
@@ -222,6 +274,7 @@ where
         membership: info.membership.clone(),
         public_key: info.public_key.clone(),
         accumulator: Some(new_accumulator),
+        first_votes: HashMap::new(),
         view: info.view,
         id: info.id,
         transition_indicator,
@@ -366,6 +419,12 @@ impl<TYPES: NodeType> AggregatableVote<TYPES, QuorumVote2<TYPES>, QuorumCertific
     ) -> HotShotEvent<TYPES> {
         HotShotEvent::Qc2Formed(Left(certificate))
     }
+    fn make_equivocation_evidence(
+        first: QuorumVote2<TYPES>,
+        second: QuorumVote2<TYPES>,
+    ) -> Option<EquivocationEvidence<TYPES>> {
+        Some(EquivocationEvidence::QuorumVote { first, second })
+    }
 }
 
 impl<TYPES: NodeType>
@@ -416,6 +475,12 @@ impl<TYPES: NodeType> AggregatableVote<TYPES, DaVote2<TYPES>, DaCertificate2<TYP
     ) -> HotShotEvent<TYPES> {
         HotShotEvent::DacSend(certificate, key.clone())
     }
+    fn make_equivocation_evidence(
+        first: DaVote2<TYPES>,
+        second: DaVote2<TYPES>,
+    ) -> Option<EquivocationEvidence<TYPES>> {
+        Some(EquivocationEvidence::DaVote { first, second })
+    }
 }
 
 impl<TYPES: NodeType> AggregatableVote<TYPES, TimeoutVote2<TYPES>, TimeoutCertificate2<TYPES>>
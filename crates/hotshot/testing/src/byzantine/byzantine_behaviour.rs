@@ -1,5 +1,6 @@
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
+    marker::PhantomData,
     sync::Arc,
 };
 
@@ -19,10 +20,17 @@ use hotshot_task_impls::{
 };
 use hotshot_types::{
     consensus::{Consensus, OuterConsensus},
-    data::QuorumProposalWrapper,
+    data::{
+        ns_table::parse_ns_table, vid_disperse::AvidMDisperse, QuorumProposalWrapper, VidDisperse,
+    },
     message::{Proposal, UpgradeLock},
     simple_vote::QuorumVote2,
-    traits::node_implementation::{ConsensusTime, NodeImplementation, NodeType, Versions},
+    traits::{
+        block_contents::EncodeBytes,
+        node_implementation::{ConsensusTime, NodeImplementation, NodeType, Versions},
+        BlockPayload,
+    },
+    vid::avidm::AvidMScheme,
 };
 
 #[derive(Debug)]
@@ -430,3 +438,149 @@ impl<TYPES: NodeType, I: NodeImplementation<TYPES> + std::fmt::Debug, V: Version
         vec![event.clone()]
     }
 }
+
+#[derive(Debug)]
+/// An `EventTransformerState` that follows every quorum vote with a conflicting vote for the same
+/// view, for the leaf the node voted for in its previous vote
+pub struct EquivocatingVoter<TYPES: NodeType> {
+    /// The last honest vote the node sent
+    pub last_vote: Option<QuorumVote2<TYPES>>,
+}
+
+#[async_trait]
+impl<TYPES: NodeType, I: NodeImplementation<TYPES> + std::fmt::Debug, V: Versions>
+    EventTransformerState<TYPES, I, V> for EquivocatingVoter<TYPES>
+{
+    async fn recv_handler(&mut self, event: &HotShotEvent<TYPES>) -> Vec<HotShotEvent<TYPES>> {
+        vec![event.clone()]
+    }
+
+    async fn send_handler(
+        &mut self,
+        event: &HotShotEvent<TYPES>,
+        public_key: &TYPES::SignatureKey,
+        private_key: &<TYPES::SignatureKey as SignatureKey>::PrivateKey,
+        upgrade_lock: &UpgradeLock<TYPES, V>,
+        _consensus: Arc<RwLock<Consensus<TYPES>>>,
+    ) -> Vec<HotShotEvent<TYPES>> {
+        let HotShotEvent::QuorumVoteSend(vote) = event else {
+            return vec![event.clone()];
+        };
+        let Some(last_vote) = self.last_vote.replace(vote.clone()) else {
+            return vec![event.clone()];
+        };
+        if last_vote.data.leaf_commit == vote.data.leaf_commit {
+            return vec![event.clone()];
+        }
+
+        let mut data = vote.data.clone();
+        data.leaf_commit = last_vote.data.leaf_commit;
+        let conflicting_vote = QuorumVote2::<TYPES>::create_signed_vote(
+            data,
+            vote.view_number,
+            public_key,
+            private_key,
+            upgrade_lock,
+        )
+        .await
+        .context("Failed to sign vote")
+        .unwrap();
+        tracing::debug!(
+            "Sending conflicting quorum vote for view {}",
+            vote.view_number
+        );
+
+        vec![
+            event.clone(),
+            HotShotEvent::QuorumVoteSend(conflicting_vote),
+        ]
+    }
+}
+
+#[derive(Debug)]
+/// An `EventTransformerState` that replaces the AvidM dispersals sent by the leader with incorrectly
+/// encoded ones, signed by the leader
+pub struct MaliciousVidDisperser<TYPES: NodeType> {
+    /// Shared state of all view numbers we sent an incorrectly encoded dispersal at
+    pub malicious_dispersal_view_numbers: Arc<RwLock<HashSet<TYPES::View>>>,
+}
+
+impl<TYPES: NodeType> MaliciousVidDisperser<TYPES> {
+    /// Re-encode the payload of `disperse` incorrectly for the same recipients and weights
+    async fn disperse_incorrectly(
+        &self,
+        disperse: &AvidMDisperse<TYPES>,
+        private_key: &<TYPES::SignatureKey as SignatureKey>::PrivateKey,
+        consensus: &RwLock<Consensus<TYPES>>,
+    ) -> Option<Proposal<TYPES, VidDisperse<TYPES>>> {
+        let payload = consensus
+            .read()
+            .await
+            .saved_payloads()
+            .get(&disperse.view_number)
+            .cloned()?;
+        let txns = payload.payload.encode();
+        let ns_table = parse_ns_table(txns.len(), &payload.metadata.encode());
+        let weights = disperse
+            .shares
+            .values()
+            .map(|share| u32::try_from(share.weight()).ok())
+            .collect::<Option<Vec<_>>>()?;
+        let (payload_commitment, shares) =
+            AvidMScheme::ns_disperse_incorrectly(&disperse.common, &weights, &txns, ns_table)
+                .ok()?;
+        let signature = TYPES::SignatureKey::sign(private_key, payload_commitment.as_ref()).ok()?;
+
+        self.malicious_dispersal_view_numbers
+            .write()
+            .await
+            .insert(disperse.view_number);
+
+        Some(Proposal {
+            data: VidDisperse::V1(AvidMDisperse {
+                payload_commitment,
+                shares: disperse.shares.keys().cloned().zip(shares).collect(),
+                ..disperse.clone()
+            }),
+            signature,
+            _pd: PhantomData,
+        })
+    }
+}
+
+#[async_trait]
+impl<TYPES: NodeType, I: NodeImplementation<TYPES> + std::fmt::Debug, V: Versions>
+    EventTransformerState<TYPES, I, V> for MaliciousVidDisperser<TYPES>
+{
+    async fn recv_handler(&mut self, event: &HotShotEvent<TYPES>) -> Vec<HotShotEvent<TYPES>> {
+        vec![event.clone()]
+    }
+
+    async fn send_handler(
+        &mut self,
+        event: &HotShotEvent<TYPES>,
+        _public_key: &TYPES::SignatureKey,
+        private_key: &<TYPES::SignatureKey as SignatureKey>::PrivateKey,
+        _upgrade_lock: &UpgradeLock<TYPES, V>,
+        consensus: Arc<RwLock<Consensus<TYPES>>>,
+    ) -> Vec<HotShotEvent<TYPES>> {
+        if let HotShotEvent::VidDisperseSend(proposal, sender) = event {
+            if let VidDisperse::V1(disperse) = &proposal.data {
+                if let Some(malicious_proposal) = self
+                    .disperse_incorrectly(disperse, private_key, &consensus)
+                    .await
+                {
+                    tracing::debug!(
+                        "Sending incorrectly encoded VID disperse for view {}",
+                        disperse.view_number
+                    );
+                    return vec![HotShotEvent::VidDisperseSend(
+                        malicious_proposal,
+                        sender.clone(),
+                    )];
+                }
+            }
+        }
+        vec![event.clone()]
+    }
+}
@@ -0,0 +1,327 @@
+// Copyright (c) 2021-2024 Espresso Systems (espressosys.com)
+// This file is part of the HotShot repository.
+
+// You should have received a copy of the MIT License
+// along with the HotShot repository. If not, see <https://mit-license.org/>.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    sync::Arc,
+};
+
+use anyhow::{bail, ensure, Result};
+use async_lock::RwLock;
+use async_trait::async_trait;
+use hotshot::{
+    traits::TestableNodeImplementation,
+    types::{Event, EventType},
+};
+use hotshot_example_types::storage_types::TestStorage;
+use hotshot_types::{
+    equivocation::EquivocationEvidence,
+    traits::node_implementation::{NodeType, Versions},
+    vote::HasViewNumber,
+};
+
+use crate::{
+    test_runner::Node,
+    test_task::{AnyTestTaskState, TestResult, TestTaskState, TestTaskStateSeed},
+};
+
+/// Description of a task checking that the honest nodes caught a malicious VID disperser
+pub struct VidEncodingEvidenceTaskDescription<TYPES: NodeType> {
+    /// The id of the node dispersing incorrectly encoded blocks
+    pub byzantine_node_id: u64,
+    /// The views in which the byzantine node dispersed an incorrectly encoded block, shared with
+    /// its [`MaliciousVidDisperser`](crate::byzantine::byzantine_behaviour::MaliciousVidDisperser)
+    pub malicious_dispersal_view_numbers: Arc<RwLock<HashSet<TYPES::View>>>,
+}
+
+#[async_trait]
+impl<TYPES, I, V> TestTaskStateSeed<TYPES, I, V> for VidEncodingEvidenceTaskDescription<TYPES>
+where
+    TYPES: NodeType,
+    I: TestableNodeImplementation<TYPES, Storage = TestStorage<TYPES>>,
+    V: Versions,
+{
+    async fn into_state(
+        self: Box<Self>,
+        handles: Arc<RwLock<Vec<Node<TYPES, I, V>>>>,
+    ) -> AnyTestTaskState<TYPES> {
+        let byzantine_key = handles
+            .read()
+            .await
+            .iter()
+            .find(|node| node.node_id == self.byzantine_node_id)
+            .map(|node| node.handle.public_key());
+        Box::new(VidEncodingEvidenceTask {
+            handles,
+            byzantine_key,
+            malicious_dispersal_view_numbers: self.malicious_dispersal_view_numbers,
+            reported_views: BTreeMap::new(),
+            errors: Vec::new(),
+        })
+    }
+}
+
+/// Task collecting the VID encoding evidence reported by every node
+pub struct VidEncodingEvidenceTask<TYPES, I, V>
+where
+    TYPES: NodeType,
+    I: TestableNodeImplementation<TYPES>,
+    V: Versions,
+{
+    /// Handles to all the nodes, to inspect their storage at the end of the test
+    handles: Arc<RwLock<Vec<Node<TYPES, I, V>>>>,
+    /// The key of the node dispersing incorrectly encoded blocks
+    byzantine_key: Option<TYPES::SignatureKey>,
+    /// The views in which the byzantine node dispersed an incorrectly encoded block
+    malicious_dispersal_view_numbers: Arc<RwLock<HashSet<TYPES::View>>>,
+    /// The views for which each node reported evidence, by node id
+    reported_views: BTreeMap<usize, BTreeSet<TYPES::View>>,
+    /// Evidence which should not have been reported
+    errors: Vec<String>,
+}
+
+impl<TYPES, I, V> VidEncodingEvidenceTask<TYPES, I, V>
+where
+    TYPES: NodeType,
+    I: TestableNodeImplementation<TYPES, Storage = TestStorage<TYPES>>,
+    V: Versions,
+{
+    /// Check the reported and stored evidence against the malicious dispersals
+    async fn check_evidence(&self) -> Result<()> {
+        ensure!(
+            !self.reported_views.is_empty(),
+            "No node reported VID encoding evidence"
+        );
+        if let Some(error) = self.errors.first() {
+            bail!("{error} ({} invalid reports in total)", self.errors.len());
+        }
+
+        let malicious_views = self.malicious_dispersal_view_numbers.read().await;
+        for (node_id, views) in &self.reported_views {
+            if let Some(view) = views.iter().find(|view| !malicious_views.contains(view)) {
+                bail!("Node {node_id} reported VID encoding evidence for honest view {view}");
+            }
+        }
+
+        for node in self.handles.read().await.iter() {
+            let node_id = usize::try_from(node.node_id)?;
+            let stored = node
+                .handle
+                .storage()
+                .read()
+                .await
+                .vid_encoding_evidence_cloned()
+                .await;
+            if let Some(evidence) = stored
+                .values()
+                .find(|evidence| Some(&evidence.leader) != self.byzantine_key.as_ref())
+            {
+                bail!(
+                    "Node {node_id} stored VID encoding evidence against an honest leader in view \
+                     {}",
+                    evidence.view_number
+                );
+            }
+            if let Some(view) = self
+                .reported_views
+                .get(&node_id)
+                .into_iter()
+                .flatten()
+                .find(|view| !stored.contains_key(view))
+            {
+                bail!(
+                    "Node {node_id} reported VID encoding evidence for view {view} without \
+                     storing it"
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<TYPES, I, V> TestTaskState for VidEncodingEvidenceTask<TYPES, I, V>
+where
+    TYPES: NodeType,
+    I: TestableNodeImplementation<TYPES, Storage = TestStorage<TYPES>>,
+    V: Versions,
+{
+    type Event = Event<TYPES>;
+    type Error = anyhow::Error;
+
+    async fn handle_event(&mut self, (event, node_id): (Self::Event, usize)) -> Result<()> {
+        if let EventType::VidEncodingEvidence { evidence } = event.event {
+            if Some(&evidence.leader) != self.byzantine_key.as_ref() {
+                self.errors.push(format!(
+                    "Node {node_id} reported VID encoding evidence against an honest leader in \
+                     view {}",
+                    evidence.view_number
+                ));
+            }
+            self.reported_views
+                .entry(node_id)
+                .or_default()
+                .insert(evidence.view_number);
+        }
+        Ok(())
+    }
+
+    async fn check(&self) -> TestResult {
+        match self.check_evidence().await {
+            Ok(()) => TestResult::Pass,
+            Err(e) => TestResult::Fail(Box::new(e)),
+        }
+    }
+}
+
+/// Description of a task checking that the honest nodes caught an equivocating node
+pub struct EquivocationEvidenceTaskDescription {
+    /// The id of the equivocating node
+    pub byzantine_node_id: u64,
+    /// The kind of messages the node equivocates on, as in [`EquivocationEvidence::kind`]
+    pub kind: &'static str,
+}
+
+#[async_trait]
+impl<TYPES, I, V> TestTaskStateSeed<TYPES, I, V> for EquivocationEvidenceTaskDescription
+where
+    TYPES: NodeType,
+    I: TestableNodeImplementation<TYPES, Storage = TestStorage<TYPES>>,
+    V: Versions,
+{
+    async fn into_state(
+        self: Box<Self>,
+        handles: Arc<RwLock<Vec<Node<TYPES, I, V>>>>,
+    ) -> AnyTestTaskState<TYPES> {
+        let byzantine_key = handles
+            .read()
+            .await
+            .iter()
+            .find(|node| node.node_id == self.byzantine_node_id)
+            .map(|node| node.handle.public_key());
+        Box::new(EquivocationEvidenceTask {
+            handles,
+            byzantine_key,
+            kind: self.kind,
+            reported: BTreeMap::new(),
+            errors: Vec::new(),
+        })
+    }
+}
+
+/// Task collecting the equivocation evidence reported by every node
+pub struct EquivocationEvidenceTask<TYPES, I, V>
+where
+    TYPES: NodeType,
+    I: TestableNodeImplementation<TYPES>,
+    V: Versions,
+{
+    /// Handles to all the nodes, to inspect their storage at the end of the test
+    handles: Arc<RwLock<Vec<Node<TYPES, I, V>>>>,
+    /// The key of the equivocating node
+    byzantine_key: Option<TYPES::SignatureKey>,
+    /// The kind of messages the node equivocates on
+    kind: &'static str,
+    /// The evidence reported by each node, by node id
+    reported: BTreeMap<usize, Vec<EquivocationEvidence<TYPES>>>,
+    /// Evidence which should not have been reported
+    errors: Vec<String>,
+}
+
+impl<TYPES, I, V> EquivocationEvidenceTask<TYPES, I, V>
+where
+    TYPES: NodeType,
+    I: TestableNodeImplementation<TYPES, Storage = TestStorage<TYPES>>,
+    V: Versions,
+{
+    /// Check the reported and stored evidence against the equivocating node
+    async fn check_evidence(&self) -> Result<()> {
+        ensure!(
+            !self.reported.is_empty(),
+            "No node reported equivocation evidence"
+        );
+        if let Some(error) = self.errors.first() {
+            bail!("{error} ({} invalid reports in total)", self.errors.len());
+        }
+
+        for node in self.handles.read().await.iter() {
+            let node_id = usize::try_from(node.node_id)?;
+            let stored = node
+                .handle
+                .storage()
+                .read()
+                .await
+                .equivocation_evidence_cloned()
+                .await;
+            if let Some(evidence) = stored
+                .iter()
+                .find(|evidence| Some(&evidence.offender()) != self.byzantine_key.as_ref())
+            {
+                bail!(
+                    "Node {node_id} stored equivocation evidence against an honest node in view {}",
+                    evidence.view_number()
+                );
+            }
+            if let Some(evidence) = self
+                .reported
+                .get(&node_id)
+                .into_iter()
+                .flatten()
+                .find(|evidence| !stored.contains(evidence))
+            {
+                bail!(
+                    "Node {node_id} reported equivocation evidence for view {} without storing it",
+                    evidence.view_number()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<TYPES, I, V> TestTaskState for EquivocationEvidenceTask<TYPES, I, V>
+where
+    TYPES: NodeType,
+    I: TestableNodeImplementation<TYPES, Storage = TestStorage<TYPES>>,
+    V: Versions,
+{
+    type Event = Event<TYPES>;
+    type Error = anyhow::Error;
+
+    async fn handle_event(&mut self, (event, node_id): (Self::Event, usize)) -> Result<()> {
+        if let EventType::Equivocation { evidence } = event.event {
+            if Some(&evidence.offender()) != self.byzantine_key.as_ref() {
+                self.errors.push(format!(
+                    "Node {node_id} reported equivocation evidence against an honest node in view \
+                     {}",
+                    evidence.view_number()
+                ));
+            } else if evidence.kind() != self.kind {
+                self.errors.push(format!(
+                    "Node {node_id} reported equivocation on {} instead of {} in view {}",
+                    evidence.kind(),
+                    self.kind,
+                    evidence.view_number()
+                ));
+            }
+            self.reported
+                .entry(node_id)
+                .or_default()
+                .push((*evidence).clone());
+        }
+        Ok(())
+    }
+
+    async fn check(&self) -> TestResult {
+        match self.check_evidence().await {
+            Ok(()) => TestResult::Pass,
+            Err(e) => TestResult::Fail(Box::new(e)),
+        }
+    }
+}
@@ -1,2 +1,5 @@
 /// Byzantine definitions and implementations of different behaviours
 pub mod byzantine_behaviour;
+
+/// Test tasks checking the evidence the honest nodes gathered against byzantine ones
+pub mod evidence_task;
@@ -53,3 +53,6 @@ pub mod view_generator;
 
 /// byzantine framework for tests
 pub mod byzantine;
+
+/// replay of recorded events for tests
+pub mod replay;
@@ -0,0 +1,121 @@
+// Copyright (c) 2021-2024 Espresso Systems (espressosys.com)
+// This file is part of the HotShot repository.
+
+// You should have received a copy of the MIT License
+// along with the HotShot repository. If not, see <https://mit-license.org/>.
+
+use std::{path::Path, sync::Arc};
+
+use async_broadcast::broadcast;
+use hotshot_task::task::TaskState;
+use hotshot_task_impls::{
+    events::HotShotEvent,
+    rewind::{read_recording, RecordedEvent},
+};
+use hotshot_types::traits::node_implementation::NodeType;
+use tokio::time::timeout;
+
+use crate::{
+    predicates::PredicateResult,
+    script::{
+        panic_extra_output_in_script, panic_missing_output_in_script,
+        validate_output_or_panic_in_script, validate_task_state_or_panic_in_script, TaskScript,
+    },
+};
+
+/// Load the events recorded by the rewind task of node `id` in `dir`.
+///
+/// # Panics
+/// Panics if the recording cannot be read.
+pub fn load_recording<TYPES: NodeType>(dir: &Path, id: u64) -> Vec<RecordedEvent<TYPES>> {
+    read_recording(dir, id)
+        .unwrap_or_else(|e| panic!("Failed to read recording of node {id} in {dir:?}: {e}"))
+}
+
+/// Replay a recording into the task state of `script`, checking the task's outputs against the
+/// single stage of expectations in the script.
+///
+/// The recorded events are fed to the task one at a time, in the order they were recorded, and the
+/// outputs of each are collected before the next event is fed. Since a recording already contains
+/// the events the task emitted itself, the outputs are only checked, and never fed back into the
+/// task. As in the `run_test!` scripts, outputs must satisfy the output predicates in order, and
+/// the task state predicates are checked once the whole recording has been replayed.
+///
+/// # Panics
+/// Panics if the script does not have exactly one stage of expectations, or if the outputs or the
+/// final task state do not satisfy them.
+pub async fn replay_script<TYPES, S>(
+    recording: Vec<RecordedEvent<TYPES>>,
+    script: &mut TaskScript<TYPES, S>,
+    script_name: &str,
+) where
+    TYPES: NodeType,
+    S: TaskState<Event = HotShotEvent<TYPES>>,
+{
+    assert!(
+        script.expectations.len() == 1,
+        "A replay of {script_name} must have exactly one stage of expectations"
+    );
+
+    let (to_task, mut from_test) = broadcast(1024);
+    let (to_test, mut from_task) = broadcast(1024);
+    let mut output_index = 0;
+
+    for RecordedEvent {
+        sequence, event, ..
+    } in recording
+    {
+        // A recording ends with the shutdown of the node, which would cancel the task we are
+        // replaying into before we can check it.
+        if matches!(event, HotShotEvent::Shutdown) {
+            break;
+        }
+        tracing::debug!("Replaying event {sequence}: {event:?}");
+
+        let event = Arc::new(event);
+        to_task
+            .broadcast(Arc::clone(&event))
+            .await
+            .expect("Failed to broadcast input message");
+        let _ = script
+            .state
+            .handle_event(event, &to_test, &from_test)
+            .await
+            .inspect_err(|e| tracing::info!("{e}"));
+        while from_test.try_recv().is_ok() {}
+
+        while let Ok(Ok(received_output)) = timeout(script.timeout, from_task.recv_direct()).await {
+            tracing::debug!("Replay received: {:?}", received_output);
+
+            let output_asserts = &script.expectations[0].output_asserts;
+            if output_index >= output_asserts.len() {
+                panic_extra_output_in_script(0, script_name.to_string(), &received_output);
+            }
+
+            let result = validate_output_or_panic_in_script(
+                0,
+                script_name.to_string(),
+                &received_output,
+                &*output_asserts[output_index],
+            )
+            .await;
+            if result == PredicateResult::Pass {
+                output_index += 1;
+            }
+        }
+    }
+
+    let expectations = &script.expectations[0];
+    if let Some(assert) = expectations.output_asserts.get(output_index) {
+        panic_missing_output_in_script(0, script_name.to_string(), assert);
+    }
+    for assert in &expectations.task_state_asserts {
+        validate_task_state_or_panic_in_script(
+            0,
+            script_name.to_string(),
+            &script.state,
+            &**assert,
+        )
+        .await;
+    }
+}
@@ -2,15 +2,21 @@ use std::{collections::HashSet, rc::Rc, sync::Arc, time::Duration};
 
 use async_lock::RwLock;
 use hotshot_example_types::{
-    node_types::{Libp2pImpl, MarketplaceTestVersions, MemoryImpl, PushCdnImpl, TestVersions},
+    node_types::{
+        EpochsTestVersions, Libp2pImpl, MarketplaceTestVersions, MemoryImpl, PushCdnImpl,
+        TestVersions,
+    },
     state_types::TestTypes,
 };
 use hotshot_macros::cross_tests;
 use hotshot_testing::{
     block_builder::SimpleBuilderImplementation,
-    byzantine::byzantine_behaviour::{
-        BadProposalViewDos, DishonestDa, DishonestLeader, DishonestVoter, DishonestVoting,
-        DoubleProposeVote,
+    byzantine::{
+        byzantine_behaviour::{
+            BadProposalViewDos, DishonestDa, DishonestLeader, DishonestVoter, DishonestVoting,
+            DoubleProposeVote, EquivocatingVoter, MaliciousVidDisperser,
+        },
+        evidence_task::{EquivocationEvidenceTaskDescription, VidEncodingEvidenceTaskDescription},
     },
     completion_task::{CompletionTaskDescription, TimeBasedCompletionTaskDescription},
     test_builder::{Behaviour, TestDescription},
@@ -121,6 +127,97 @@ cross_tests!(
     },
 );
 
+// Test where node 2 disperses incorrectly encoded VID shares whenever it is the leader, which the
+// other nodes prove and refuse to vote for
+#[cfg(test)]
+#[tokio::test(flavor = "multi_thread")]
+async fn malicious_vid_disperser() {
+    hotshot::helpers::initialize_logging();
+
+    let malicious_dispersal_view_numbers = Arc::new(RwLock::new(HashSet::new()));
+    let behaviour = {
+        let malicious_dispersal_view_numbers = Arc::clone(&malicious_dispersal_view_numbers);
+        Rc::new(move |node_id| {
+            let malicious_vid_disperser = MaliciousVidDisperser {
+                malicious_dispersal_view_numbers: Arc::clone(&malicious_dispersal_view_numbers),
+            };
+            match node_id {
+                2 => Behaviour::Byzantine(Box::new(malicious_vid_disperser)),
+                _ => Behaviour::Standard,
+            }
+        })
+    };
+
+    let mut metadata: TestDescription<TestTypes, MemoryImpl, EpochsTestVersions> =
+        TestDescription {
+            // allow more time to pass in CI
+            completion_task_description: CompletionTaskDescription::TimeBasedCompletionTaskBuilder(
+                TimeBasedCompletionTaskDescription {
+                    duration: Duration::from_secs(60),
+                },
+            ),
+            behaviour,
+            ..TestDescription::default()
+        }
+        .set_num_nodes(10, 10);
+
+    metadata.overall_safety_properties.num_successful_views = 10;
+    // node 2 leads every 10th view, the next leader might time out as well
+    metadata.overall_safety_properties.possible_view_failures = (0..100)
+        .filter(|view| view % 10 == 2 || view % 10 == 3)
+        .collect();
+    metadata.overall_safety_properties.decide_timeout = Duration::from_secs(20);
+
+    metadata
+        .gen_launcher_with_tasks(vec![Box::new(VidEncodingEvidenceTaskDescription {
+            byzantine_node_id: 2,
+            malicious_dispersal_view_numbers,
+        })])
+        .launch()
+        .run_test::<SimpleBuilderImplementation>()
+        .await;
+}
+
+// Test where node 2 follows every quorum vote with a conflicting vote, which the leaders record as
+// equivocation evidence while still forming certificates from the honest votes
+#[cfg(test)]
+#[tokio::test(flavor = "multi_thread")]
+async fn equivocating_voter() {
+    hotshot::helpers::initialize_logging();
+
+    let behaviour = Rc::new(|node_id| {
+        let equivocating_voter = EquivocatingVoter { last_vote: None };
+        match node_id {
+            2 => Behaviour::Byzantine(Box::new(equivocating_voter)),
+            _ => Behaviour::Standard,
+        }
+    });
+
+    let mut metadata: TestDescription<TestTypes, MemoryImpl, EpochsTestVersions> =
+        TestDescription {
+            // allow more time to pass in CI
+            completion_task_description: CompletionTaskDescription::TimeBasedCompletionTaskBuilder(
+                TimeBasedCompletionTaskDescription {
+                    duration: Duration::from_secs(60),
+                },
+            ),
+            behaviour,
+            ..TestDescription::default()
+        }
+        .set_num_nodes(10, 10);
+
+    metadata.overall_safety_properties.num_successful_views = 10;
+
+    metadata
+        .gen_launcher_with_tasks(vec![Box::new(EquivocationEvidenceTaskDescription {
+            byzantine_node_id: 2,
+            kind: "quorum_vote",
+        })])
+        .launch()
+        .run_test::<SimpleBuilderImplementation>()
+        .await;
+}
+
 cross_tests!(
     TestName: dishonest_da,
     Impls: [MemoryImpl, Libp2pImpl, PushCdnImpl],
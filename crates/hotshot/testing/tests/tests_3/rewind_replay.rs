@@ -0,0 +1,141 @@
+// Copyright (c) 2021-2024 Espresso Systems (espressosys.com)
+// This file is part of the HotShot repository.
+
+// You should have received a copy of the MIT License
+// along with the HotShot repository. If not, see <https://mit-license.org/>.
+
+use std::{fs::OpenOptions, io::Write, path::Path, sync::Arc, time::Duration};
+
+use futures::StreamExt;
+use hotshot::tasks::task_state::CreateTaskState;
+use hotshot_example_types::{
+    node_types::{MemoryImpl, TestTypes, TestVersions},
+    state_types::TestValidatedState,
+};
+use hotshot_task_impls::{
+    events::HotShotEvent::{self, *},
+    quorum_proposal_recv::QuorumProposalRecvTaskState,
+    rewind::{read_recording, recording_files, EventRecorder, RecorderConfig},
+};
+use hotshot_testing::{
+    helpers::build_system_handle,
+    predicates::event::exact,
+    replay::{load_recording, replay_script},
+    script::{Expectations, TaskScript},
+    view_generator::TestViewGenerator,
+};
+use hotshot_types::{
+    data::{Leaf2, ViewNumber},
+    traits::node_implementation::ConsensusTime,
+};
+use tempfile::TempDir;
+
+/// Record `events` for node `id` into `dir`, rotating files after every event.
+fn record(
+    dir: &Path,
+    id: u64,
+    events: &[HotShotEvent<TestTypes>],
+    max_files: usize,
+) -> RecorderConfig {
+    let config = RecorderConfig {
+        dir: dir.to_path_buf(),
+        max_file_size: 1,
+        max_files,
+    };
+    let mut recorder = EventRecorder::<TestTypes>::new(id, config.clone());
+    for event in events {
+        recorder.record(event).unwrap();
+    }
+    recorder.flush().unwrap();
+    assert_eq!(recorder.events_recorded(), events.len() as u64);
+
+    config
+}
+
+#[cfg(test)]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rewind_recording_replays_quorum_proposal_recv() {
+    hotshot::helpers::initialize_logging();
+
+    let (handle, _, _, node_key_map) =
+        build_system_handle::<TestTypes, MemoryImpl, TestVersions>(2).await;
+    let membership = handle.hotshot.membership_coordinator.clone();
+    let consensus = handle.hotshot.consensus();
+    let mut consensus_writer = consensus.write().await;
+
+    let mut generator = TestViewGenerator::<TestVersions>::generate(membership, node_key_map);
+    let mut proposals = Vec::new();
+    let mut leaders = Vec::new();
+    let mut leaves = Vec::new();
+    for view in (&mut generator).take(2).collect::<Vec<_>>().await {
+        proposals.push(view.quorum_proposal.clone());
+        leaders.push(view.leader_public_key);
+        leaves.push(view.leaf.clone());
+
+        consensus_writer
+            .update_leaf(
+                Leaf2::from_quorum_proposal(&view.quorum_proposal.data),
+                Arc::new(TestValidatedState::default()),
+                None,
+            )
+            .unwrap();
+    }
+    drop(consensus_writer);
+
+    let events = vec![
+        ViewChange(ViewNumber::new(1), None),
+        QuorumProposalRecv(proposals[1].clone(), leaders[1]),
+        Shutdown,
+    ];
+
+    // Every event lands in its own file, and the recording reads back in order.
+    let tmp = TempDir::new().unwrap();
+    let config = record(tmp.path(), 2, &events, 8);
+    assert_eq!(recording_files(&config.dir, 2).unwrap().len(), events.len());
+    let recording = load_recording::<TestTypes>(&config.dir, 2);
+    assert_eq!(
+        recording.iter().map(|r| r.sequence).collect::<Vec<_>>(),
+        (0..events.len() as u64).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        recording
+            .iter()
+            .map(|r| r.event.clone())
+            .collect::<Vec<_>>(),
+        events
+    );
+
+    // With a limit on the number of files, only the most recent events are kept.
+    let pruned_tmp = TempDir::new().unwrap();
+    let pruned = record(pruned_tmp.path(), 2, &events, 1);
+    assert_eq!(recording_files(&pruned.dir, 2).unwrap().len(), 1);
+    let tail = read_recording::<TestTypes>(&pruned.dir, 2).unwrap();
+    assert_eq!(tail.len(), 1);
+    assert_eq!(tail[0].sequence, events.len() as u64 - 1);
+
+    // A corrupt frame length at the end of a file is read as a partial event, without allocating
+    // the claimed length.
+    let (_, last_file) = recording_files(&pruned.dir, 2).unwrap().pop().unwrap();
+    let mut file = OpenOptions::new().append(true).open(last_file).unwrap();
+    file.write_all(&u32::MAX.to_le_bytes()).unwrap();
+    file.write_all(&[0; 8]).unwrap();
+    drop(file);
+    assert_eq!(read_recording::<TestTypes>(&pruned.dir, 2).unwrap(), tail);
+
+    let state =
+        QuorumProposalRecvTaskState::<TestTypes, MemoryImpl, TestVersions>::create_from(&handle)
+            .await;
+    let mut script = TaskScript {
+        timeout: Duration::from_millis(35),
+        state,
+        expectations: vec![Expectations::from_outputs(vec![
+            exact(QuorumProposalPreliminarilyValidated(proposals[1].clone())),
+            exact(QuorumProposalValidated(
+                proposals[1].clone(),
+                leaves[0].clone(),
+            )),
+            exact(ViewChange(ViewNumber::new(2), None)),
+        ])],
+    };
+    replay_script(recording, &mut script, "QuorumProposalRecvTaskState").await;
+}
@@ -1930,7 +1930,8 @@ pub mod null_block {
 }
 
 /// A packed bundle constructed from a sequence of bundles.
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(bound(deserialize = "", serialize = ""))]
 pub struct PackedBundle<TYPES: NodeType> {
     /// The combined transactions as bytes.
     pub encoded_transactions: Arc<[u8]>,
@@ -28,7 +28,10 @@ use crate::{
     },
     vid::{
         advz::{advz_scheme, ADVZCommitment, ADVZCommon, ADVZScheme, ADVZShare},
-        avidm::{init_avidm_param, AvidMCommitment, AvidMCommon, AvidMScheme, AvidMShare},
+        avidm::{
+            init_avidm_param, AvidMCommitment, AvidMCommon, AvidMMalEncodingProof, AvidMScheme,
+            AvidMShare,
+        },
     },
     vote::HasViewNumber,
     PeerConfig,
@@ -37,7 +40,8 @@ use crate::{
 impl_has_epoch!(
     ADVZDisperse<TYPES>,
     AvidMDisperse<TYPES>,
-    VidDisperseShare2<TYPES>
+    VidDisperseShare2<TYPES>,
+    VidEncodingEvidence<TYPES>
 );
 
 /// ADVZ dispersal data
@@ -546,6 +550,11 @@ impl<TYPES: NodeType> VidDisperseShare2<TYPES> {
         self.share.payload_byte_len() as u32
     }
 
+    /// Returns the VID weight carried by this share.
+    pub fn weight(&self) -> usize {
+        self.share.weight()
+    }
+
     /// Split a VID share proposal into a proposal for each recipient.
     pub fn to_vid_share_proposals(
         vid_disperse: AvidMDisperse<TYPES>,
@@ -580,3 +589,103 @@ impl<TYPES: NodeType> VidDisperseShare2<TYPES> {
             .unwrap_or(Err(()))
     }
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+/// Evidence that a leader dispersed an incorrectly encoded block.
+///
+/// The leader signs the payload commitment of every VID share it sends, so a proof of
+/// incorrect encoding against that commitment, together with the leader's signature,
+/// attributes the malicious dispersal to the leader.
+pub struct VidEncodingEvidence<TYPES: NodeType> {
+    /// The view number of the malicious dispersal
+    pub view_number: TYPES::View,
+    /// The epoch number for which the VID data belongs to
+    pub epoch: Option<TYPES::Epoch>,
+    /// The epoch number to which the recipients of the VID shares belong to
+    pub target_epoch: Option<TYPES::Epoch>,
+    /// The public key of the leader who dispersed the shares
+    pub leader: TYPES::SignatureKey,
+    /// The incorrectly encoded payload commitment
+    pub payload_commitment: AvidMCommitment,
+    /// The leader's signature over the payload commitment
+    pub signature: <TYPES::SignatureKey as SignatureKey>::PureAssembledSignatureType,
+    /// The proof of incorrect encoding against the payload commitment
+    pub proof: AvidMMalEncodingProof,
+}
+
+impl<TYPES: NodeType> HasViewNumber<TYPES> for VidEncodingEvidence<TYPES> {
+    fn view_number(&self) -> TYPES::View {
+        self.view_number
+    }
+}
+
+impl<TYPES: NodeType> VidEncodingEvidence<TYPES> {
+    /// Build the evidence from the leader-signed VID shares of a view.
+    ///
+    /// `total_weight` is the VID total weight of the target epoch of the shares.
+    ///
+    /// # Errors
+    /// Returns an error if the shares are inconsistent, not signed by `leader`, or if no proof
+    /// of incorrect encoding can be generated from them, e.g. because the encoding is correct.
+    pub fn from_shares(
+        leader: TYPES::SignatureKey,
+        shares: &[Proposal<TYPES, VidDisperseShare2<TYPES>>],
+        total_weight: usize,
+    ) -> Result<Self> {
+        let first = shares.first().context(warn!("No VID shares given"))?;
+        for share in shares {
+            ensure!(
+                share.data.view_number == first.data.view_number
+                    && share.data.payload_commitment == first.data.payload_commitment,
+                warn!("VID shares are not for the same dispersal")
+            );
+            ensure!(
+                leader.validate(&share.signature, share.data.payload_commitment.as_ref()),
+                warn!("VID share is not signed by the leader")
+            );
+        }
+        let avidm_param = init_avidm_param(total_weight)?;
+        let avidm_shares: Vec<_> = shares
+            .iter()
+            .map(|share| share.data.share.clone())
+            .collect();
+        let proof = AvidMScheme::proof_of_incorrect_encoding(
+            &avidm_param,
+            &first.data.payload_commitment,
+            &avidm_shares,
+        )
+        .map_err(|err| warn!("Failed to generate proof of incorrect encoding: {err}"))?;
+
+        Ok(Self {
+            view_number: first.data.view_number,
+            epoch: first.data.epoch,
+            target_epoch: first.data.target_epoch,
+            leader,
+            payload_commitment: first.data.payload_commitment,
+            signature: first.signature.clone(),
+            proof,
+        })
+    }
+
+    /// Verify the leader's signature and the proof of incorrect encoding.
+    ///
+    /// `total_weight` is the VID total weight of `target_epoch`.
+    ///
+    /// # Errors
+    /// Returns an error if the evidence is invalid.
+    pub fn verify(&self, total_weight: usize) -> Result<()> {
+        ensure!(
+            self.leader
+                .validate(&self.signature, self.payload_commitment.as_ref()),
+            warn!("Invalid leader signature on the VID encoding evidence")
+        );
+        let avidm_param = init_avidm_param(total_weight)?;
+        ensure!(
+            self.proof
+                .verify(&avidm_param, &self.payload_commitment)
+                .is_ok_and(|result| result.is_ok()),
+            warn!("Invalid proof of incorrect encoding")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright (c) 2021-2024 Espresso Systems (espressosys.com)
+// This file is part of the HotShot repository.
+
+// You should have received a copy of the MIT License
+// along with the HotShot repository. If not, see <https://mit-license.org/>.
+
+//! Evidence that a node equivocated, i.e. signed two conflicting messages for the same view.
+
+use committable::Committable;
+use hotshot_utils::anytrace::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::{Leaf2, QuorumProposalWrapper},
+    message::{Proposal, UpgradeLock},
+    simple_vote::{DaVote2, QuorumVote2, SimpleVote, VersionedVoteData, Voteable},
+    traits::{
+        node_implementation::{NodeType, Versions},
+        signature_key::SignatureKey,
+    },
+    vote::{HasViewNumber, Vote},
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(bound(deserialize = "", serialize = ""))]
+/// Two conflicting messages signed by the same key for the same view.
+///
+/// Each variant carries both signed messages in full, so the evidence can be checked by anyone
+/// who knows the protocol versions, without trusting the node which recorded it.
+pub enum EquivocationEvidence<TYPES: NodeType> {
+    /// The leader signed two quorum proposals for different leaves
+    QuorumProposal {
+        /// The leader who signed both proposals
+        leader: TYPES::SignatureKey,
+        /// The first proposal seen
+        first: Proposal<TYPES, QuorumProposalWrapper<TYPES>>,
+        /// The conflicting proposal
+        second: Proposal<TYPES, QuorumProposalWrapper<TYPES>>,
+    },
+    /// A node cast two quorum votes for different leaves
+    QuorumVote {
+        /// The first vote seen
+        first: QuorumVote2<TYPES>,
+        /// The conflicting vote
+        second: QuorumVote2<TYPES>,
+    },
+    /// A DA committee member cast two DA votes for different payloads
+    DaVote {
+        /// The first vote seen
+        first: DaVote2<TYPES>,
+        /// The conflicting vote
+        second: DaVote2<TYPES>,
+    },
+}
+
+impl<TYPES: NodeType> HasViewNumber<TYPES> for EquivocationEvidence<TYPES> {
+    fn view_number(&self) -> TYPES::View {
+        match self {
+            Self::QuorumProposal { first, .. } => first.data.view_number(),
+            Self::QuorumVote { first, .. } => first.view_number(),
+            Self::DaVote { first, .. } => first.view_number(),
+        }
+    }
+}
+
+impl<TYPES: NodeType> EquivocationEvidence<TYPES> {
+    /// The key which signed both messages.
+    pub fn offender(&self) -> TYPES::SignatureKey {
+        match self {
+            Self::QuorumProposal { leader, .. } => leader.clone(),
+            Self::QuorumVote { first, .. } => first.signing_key(),
+            Self::DaVote { first, .. } => first.signing_key(),
+        }
+    }
+
+    /// A short name for the kind of messages the offender equivocated on.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::QuorumProposal { .. } => "quorum_proposal",
+            Self::QuorumVote { .. } => "quorum_vote",
+            Self::DaVote { .. } => "da_vote",
+        }
+    }
+
+    /// Check that both messages are for the same view, conflict, and are signed by the offender.
+    ///
+    /// This does not check that the offender was entitled to sign either message, e.g. that it
+    /// was the leader of the view.
+    ///
+    /// # Errors
+    /// Returns an error if the evidence is invalid.
+    pub async fn verify<V: Versions>(&self, upgrade_lock: &UpgradeLock<TYPES, V>) -> Result<()> {
+        match self {
+            Self::QuorumProposal {
+                leader,
+                first,
+                second,
+            } => {
+                ensure!(
+                    first.data.view_number() == second.data.view_number(),
+                    warn!("Proposals are for different views")
+                );
+                let first_commit = Leaf2::from_quorum_proposal(&first.data).commit();
+                let second_commit = Leaf2::from_quorum_proposal(&second.data).commit();
+                ensure!(
+                    first_commit != second_commit,
+                    warn!("Proposals are for the same leaf")
+                );
+                ensure!(
+                    leader.validate(&first.signature, first_commit.as_ref())
+                        && leader.validate(&second.signature, second_commit.as_ref()),
+                    warn!("Proposal signature is invalid")
+                );
+                Ok(())
+            },
+            Self::QuorumVote { first, second } => {
+                verify_conflicting_votes(first, second, upgrade_lock).await
+            },
+            Self::DaVote { first, second } => {
+                verify_conflicting_votes(first, second, upgrade_lock).await
+            },
+        }
+    }
+}
+
+/// Check that two votes are signed by the same key for the same view, but for different data.
+async fn verify_conflicting_votes<TYPES: NodeType, DATA: Voteable<TYPES> + 'static, V: Versions>(
+    first: &SimpleVote<TYPES, DATA>,
+    second: &SimpleVote<TYPES, DATA>,
+    upgrade_lock: &UpgradeLock<TYPES, V>,
+) -> Result<()> {
+    ensure!(
+        first.view_number() == second.view_number(),
+        warn!("Votes are for different views")
+    );
+    ensure!(
+        first.signing_key() == second.signing_key(),
+        warn!("Votes are signed by different keys")
+    );
+    ensure!(
+        first.data_commitment() != second.data_commitment(),
+        warn!("Votes are for the same data")
+    );
+    for vote in [first, second] {
+        let commit = VersionedVoteData::new(vote.data.clone(), vote.view_number(), upgrade_lock)
+            .await?
+            .commit();
+        ensure!(
+            vote.signing_key()
+                .validate(&vote.signature(), commit.as_ref()),
+            warn!("Vote signature is invalid")
+        );
+    }
+    Ok(())
+}
@@ -11,7 +11,11 @@ use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    data::{DaProposal2, Leaf2, QuorumProposalWrapper, UpgradeProposal, VidDisperseShare},
+    data::{
+        vid_disperse::VidEncodingEvidence, DaProposal2, Leaf2, QuorumProposalWrapper,
+        UpgradeProposal, VidDisperseShare,
+    },
+    equivocation::EquivocationEvidence,
     error::HotShotError,
     message::Proposal,
     simple_certificate::{LightClientStateUpdateCertificate, QuorumCertificate2},
@@ -176,6 +180,18 @@ pub enum EventType<TYPES: NodeType> {
         /// Public key of the leader submitting the proposal
         sender: TYPES::SignatureKey,
     },
+    /// Evidence was gathered or received that the leader of a view
+    /// dispersed an incorrectly encoded block
+    VidEncodingEvidence {
+        /// The evidence against the leader
+        evidence: Arc<VidEncodingEvidence<TYPES>>,
+    },
+
+    /// A node was caught signing two conflicting proposals or votes for the same view
+    Equivocation {
+        /// The evidence against the node
+        evidence: Arc<EquivocationEvidence<TYPES>>,
+    },
 
     /// A message destined for external listeners was received
     ExternalMessageReceived {
@@ -27,6 +27,7 @@ pub mod data;
 pub mod drb;
 /// Epoch Membership wrappers
 pub mod epoch_membership;
+pub mod equivocation;
 pub mod error;
 pub mod event;
 /// Holds the configuration file specification for a HotShot node.
@@ -26,7 +26,7 @@ use vbs::{
 
 use crate::{
     data::{
-        vid_disperse::{ADVZDisperseShare, VidDisperseShare2},
+        vid_disperse::{ADVZDisperseShare, VidDisperseShare2, VidEncodingEvidence},
         DaProposal, DaProposal2, Leaf, Leaf2, QuorumProposal, QuorumProposal2,
         QuorumProposalWrapper, UpgradeProposal,
     },
@@ -309,6 +309,12 @@ pub enum DaConsensusMessage<TYPES: NodeType> {
     ///
     /// Like [`DaProposal`]. Use `Msg` suffix to distinguish from `VidDisperse`.
     VidDisperseMsg2(Proposal<TYPES, VidDisperseShare2<TYPES>>),
+
+    /// A leader-signed VID share, gossiped to gather a proof of incorrect encoding.
+    VidShareGossip(Proposal<TYPES, VidDisperseShare2<TYPES>>),
+
+    /// Evidence that a leader dispersed an incorrectly encoded block.
+    VidEncodingEvidence(VidEncodingEvidence<TYPES>),
 }
 
 /// Messages for sequencing consensus.
@@ -406,6 +412,8 @@ impl<TYPES: NodeType> SequencingMessage<TYPES> {
                     DaConsensusMessage::DaVote2(vote_message) => vote_message.view_number(),
                     DaConsensusMessage::DaCertificate2(cert) => cert.view_number,
                     DaConsensusMessage::VidDisperseMsg2(disperse) => disperse.data.view_number(),
+                    DaConsensusMessage::VidShareGossip(share) => share.data.view_number(),
+                    DaConsensusMessage::VidEncodingEvidence(evidence) => evidence.view_number(),
                 }
             },
         }
@@ -479,6 +487,8 @@ impl<TYPES: NodeType> SequencingMessage<TYPES> {
                     },
                     DaConsensusMessage::DaVote2(vote_message) => vote_message.epoch(),
                     DaConsensusMessage::DaCertificate2(cert) => cert.epoch(),
+                    DaConsensusMessage::VidShareGossip(share) => share.data.epoch(),
+                    DaConsensusMessage::VidEncodingEvidence(evidence) => evidence.epoch(),
                 }
             },
         }
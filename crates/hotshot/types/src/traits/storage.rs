@@ -15,11 +15,12 @@ use async_trait::async_trait;
 use super::node_implementation::NodeType;
 use crate::{
     data::{
-        vid_disperse::{ADVZDisperseShare, VidDisperseShare2},
+        vid_disperse::{ADVZDisperseShare, VidDisperseShare2, VidEncodingEvidence},
         DaProposal, DaProposal2, QuorumProposal, QuorumProposal2, QuorumProposalWrapper,
         VidCommitment, VidDisperseShare,
     },
     drb::DrbResult,
+    equivocation::EquivocationEvidence,
     event::HotShotAction,
     message::{convert_proposal, Proposal},
     simple_certificate::{
@@ -62,6 +63,16 @@ pub trait Storage<TYPES: NodeType>: Send + Sync + Clone {
             },
         }
     }
+    /// Record evidence that a leader dispersed an incorrectly encoded block.
+    async fn append_vid_encoding_evidence(
+        &self,
+        evidence: &VidEncodingEvidence<TYPES>,
+    ) -> Result<()>;
+    /// Record evidence that a node signed conflicting proposals or votes.
+    async fn append_equivocation_evidence(
+        &self,
+        evidence: &EquivocationEvidence<TYPES>,
+    ) -> Result<()>;
     /// Add a proposal to the stored DA proposals.
     async fn append_da(
         &self,
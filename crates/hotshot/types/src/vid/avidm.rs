@@ -13,6 +13,7 @@ pub type AvidMParam = vid::avid_m::namespaced::NsAvidMParam;
 pub type AvidMCommitment = vid::avid_m::namespaced::NsAvidMCommit;
 pub type AvidMShare = vid::avid_m::namespaced::NsAvidMShare;
 pub type AvidMCommon = AvidMParam;
+pub type AvidMMalEncodingProof = vid::avid_m::proofs::NsMalEncodingProof;
 
 pub fn init_avidm_param(total_weight: usize) -> Result<AvidMParam> {
     let recovery_threshold = total_weight.div_ceil(3);
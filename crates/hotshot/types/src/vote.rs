@@ -237,6 +237,17 @@ impl<
         }
         None
     }
+
+    /// The number of distinct votes by `key` accumulated so far.
+    ///
+    /// Only validly signed votes of stake table members are accumulated, so more than one means
+    /// the signer voted for conflicting data.
+    pub fn num_votes_by(&self, key: &TYPES::SignatureKey) -> usize {
+        self.vote_outcomes
+            .values()
+            .filter(|(_, votes)| votes.contains_key(key))
+            .count()
+    }
 }
 
 /// Mapping of commitments to vote tokens by key.
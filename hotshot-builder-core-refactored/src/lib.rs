@@ -9,6 +9,7 @@
 
 pub mod block_size_limits;
 pub mod block_store;
+pub mod selector;
 pub mod service;
 
 // tracking the testing
@@ -0,0 +1,269 @@
+//! Policies deciding which of the queued transactions go into a block
+//! and in which order.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{Debug, Display},
+    str::FromStr,
+    sync::Arc,
+};
+
+use hotshot_types::traits::node_implementation::NodeType;
+use marketplace_builder_shared::{block::ReceivedTransaction, state::TransactionQueue};
+
+/// Function extracting the namespace a transaction belongs to,
+/// used by namespace-aware selectors
+pub type NamespaceFn<Types> = fn(&<Types as NodeType>::Transaction) -> u64;
+
+/// Policy the builder uses to assemble a block out of its transaction queue.
+pub trait TransactionSelector<Types: NodeType>: Debug + Send + Sync {
+    /// Select transactions from `queue` (which is in arrival order) to include in a
+    /// block of at most `max_block_size` bytes, in the order they should appear in the block.
+    ///
+    /// The first selected transaction may exceed `max_block_size` on its own, otherwise
+    /// a single transaction bigger than the target block size would stay stuck in queue
+    /// and we'd build empty blocks forever.
+    fn select(
+        &self,
+        queue: &TransactionQueue<Types>,
+        max_block_size: u64,
+    ) -> Vec<Arc<ReceivedTransaction<Types>>>;
+}
+
+/// Tracks the space left in the block being assembled
+#[derive(Debug, Clone, Copy)]
+struct BlockSpace {
+    used: u64,
+    max: u64,
+}
+
+impl BlockSpace {
+    fn new(max: u64) -> Self {
+        Self { used: 0, max }
+    }
+
+    /// Reserve space for a transaction of `size` bytes, returning
+    /// `false` if it doesn't fit. The first transaction always fits.
+    fn reserve(&mut self, size: u64) -> bool {
+        if self.used != 0 && self.used + size >= self.max {
+            return false;
+        }
+        self.used += size;
+        true
+    }
+}
+
+/// Includes transactions in arrival order until the block is full.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FifoSelector;
+
+impl<Types: NodeType> TransactionSelector<Types> for FifoSelector {
+    fn select(
+        &self,
+        queue: &TransactionQueue<Types>,
+        max_block_size: u64,
+    ) -> Vec<Arc<ReceivedTransaction<Types>>> {
+        let mut space = BlockSpace::new(max_block_size);
+        queue
+            .iter()
+            .map_while(|tx| space.reserve(tx.min_block_size).then(|| Arc::clone(tx)))
+            .collect()
+    }
+}
+
+/// Takes one transaction from each namespace in turn, so that a single
+/// namespace submitting many transactions can't starve the others.
+///
+/// Namespaces take turns in order of their oldest queued transaction,
+/// and transactions within a namespace keep their arrival order.
+pub struct NamespaceRoundRobinSelector<Types: NodeType> {
+    namespace: NamespaceFn<Types>,
+}
+
+impl<Types: NodeType> NamespaceRoundRobinSelector<Types> {
+    pub fn new(namespace: NamespaceFn<Types>) -> Self {
+        Self { namespace }
+    }
+}
+
+impl<Types: NodeType> Debug for NamespaceRoundRobinSelector<Types> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NamespaceRoundRobinSelector").finish()
+    }
+}
+
+impl<Types: NodeType> TransactionSelector<Types> for NamespaceRoundRobinSelector<Types> {
+    fn select(
+        &self,
+        queue: &TransactionQueue<Types>,
+        max_block_size: u64,
+    ) -> Vec<Arc<ReceivedTransaction<Types>>> {
+        let mut order = Vec::new();
+        let mut by_namespace: HashMap<u64, VecDeque<&Arc<ReceivedTransaction<Types>>>> =
+            HashMap::new();
+        for tx in queue.iter() {
+            let namespace = (self.namespace)(&tx.transaction);
+            by_namespace
+                .entry(namespace)
+                .or_insert_with(|| {
+                    order.push(namespace);
+                    VecDeque::new()
+                })
+                .push_back(tx);
+        }
+
+        let mut space = BlockSpace::new(max_block_size);
+        let mut selected = Vec::new();
+        let mut rotation = order.into_iter().collect::<VecDeque<_>>();
+        while let Some(namespace) = rotation.pop_front() {
+            let txns = by_namespace
+                .get_mut(&namespace)
+                .expect("every namespace in rotation has a queue");
+            let Some(tx) = txns.pop_front() else {
+                continue;
+            };
+            // A namespace whose next transaction doesn't fit is out of the rotation,
+            // skipping it instead would reorder its transactions
+            if !space.reserve(tx.min_block_size) {
+                continue;
+            }
+            selected.push(Arc::clone(tx));
+            if !txns.is_empty() {
+                rotation.push_back(namespace);
+            }
+        }
+
+        selected
+    }
+}
+
+/// Includes transactions in arrival order, but caps the number of bytes
+/// each namespace may take up in a single block.
+///
+/// As with the block size, the first transaction of a namespace is admitted
+/// even if it alone exceeds the quota, so that it can't get stuck in queue.
+pub struct NamespaceQuotaSelector<Types: NodeType> {
+    namespace: NamespaceFn<Types>,
+    max_namespace_bytes: u64,
+}
+
+impl<Types: NodeType> NamespaceQuotaSelector<Types> {
+    pub fn new(namespace: NamespaceFn<Types>, max_namespace_bytes: u64) -> Self {
+        Self {
+            namespace,
+            max_namespace_bytes,
+        }
+    }
+}
+
+impl<Types: NodeType> Debug for NamespaceQuotaSelector<Types> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NamespaceQuotaSelector")
+            .field("max_namespace_bytes", &self.max_namespace_bytes)
+            .finish()
+    }
+}
+
+impl<Types: NodeType> TransactionSelector<Types> for NamespaceQuotaSelector<Types> {
+    fn select(
+        &self,
+        queue: &TransactionQueue<Types>,
+        max_block_size: u64,
+    ) -> Vec<Arc<ReceivedTransaction<Types>>> {
+        let mut space = BlockSpace::new(max_block_size);
+        let mut quotas: HashMap<u64, BlockSpace> = HashMap::new();
+        // Namespaces that had a transaction rejected. We don't consider any
+        // further transactions from them to preserve their arrival order.
+        let mut exhausted = HashSet::new();
+        let mut selected = Vec::new();
+
+        for tx in queue.iter() {
+            let namespace = (self.namespace)(&tx.transaction);
+            if exhausted.contains(&namespace) {
+                continue;
+            }
+
+            // Block space only admits transactions strictly below its maximum,
+            // while the quota is inclusive
+            let quota = quotas
+                .entry(namespace)
+                .or_insert_with(|| BlockSpace::new(self.max_namespace_bytes.saturating_add(1)));
+            let mut reserved_quota = *quota;
+            if !reserved_quota.reserve(tx.min_block_size) || !space.reserve(tx.min_block_size) {
+                exhausted.insert(namespace);
+                continue;
+            }
+            *quota = reserved_quota;
+            selected.push(Arc::clone(tx));
+        }
+
+        selected
+    }
+}
+
+/// Transaction selection policy, as chosen in the builder's configuration.
+///
+/// Parses from `fifo`, `round-robin` or `quota:<bytes>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransactionSelectionPolicy {
+    /// See [`FifoSelector`]
+    #[default]
+    Fifo,
+    /// See [`NamespaceRoundRobinSelector`]
+    NamespaceRoundRobin,
+    /// See [`NamespaceQuotaSelector`]
+    NamespaceQuota { max_namespace_bytes: u64 },
+}
+
+impl TransactionSelectionPolicy {
+    /// Create the selector implementing this policy, using `namespace`
+    /// to attribute transactions to namespaces.
+    pub fn selector<Types: NodeType>(
+        self,
+        namespace: NamespaceFn<Types>,
+    ) -> Arc<dyn TransactionSelector<Types>> {
+        match self {
+            Self::Fifo => Arc::new(FifoSelector),
+            Self::NamespaceRoundRobin => Arc::new(NamespaceRoundRobinSelector::new(namespace)),
+            Self::NamespaceQuota {
+                max_namespace_bytes,
+            } => Arc::new(NamespaceQuotaSelector::new(namespace, max_namespace_bytes)),
+        }
+    }
+}
+
+impl Display for TransactionSelectionPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fifo => write!(f, "fifo"),
+            Self::NamespaceRoundRobin => write!(f, "round-robin"),
+            Self::NamespaceQuota {
+                max_namespace_bytes,
+            } => write!(f, "quota:{max_namespace_bytes}"),
+        }
+    }
+}
+
+impl FromStr for TransactionSelectionPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fifo" => Ok(Self::Fifo),
+            "round-robin" => Ok(Self::NamespaceRoundRobin),
+            _ => {
+                let Some(max_namespace_bytes) = s.strip_prefix("quota:") else {
+                    return Err(format!(
+                        "unknown transaction selection policy {s}, expected one of fifo, round-robin or quota:<bytes>"
+                    ));
+                };
+                let max_namespace_bytes = max_namespace_bytes.parse().map_err(|err| {
+                    format!("invalid namespace quota {max_namespace_bytes}: {err}")
+                })?;
+                Ok(Self::NamespaceQuota {
+                    max_namespace_bytes,
+                })
+            },
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     fmt::Display,
+    future::Future,
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
@@ -40,7 +41,7 @@ use hotshot_types::{
 };
 use marketplace_builder_shared::{
     block::{BlockId, BuilderStateId, ReceivedTransaction, TransactionSource},
-    coordinator::{BuilderStateCoordinator, BuilderStateLookup},
+    coordinator::{mempool_log::MempoolLog, BuilderStateCoordinator, BuilderStateLookup},
     error::Error,
     state::BuilderState,
     utils::BuilderKeys,
@@ -58,6 +59,7 @@ use vbs::version::StaticVersion;
 use crate::{
     block_size_limits::BlockSizeLimits,
     block_store::{BlockInfo, BlockStore},
+    selector::TransactionSelector,
 };
 
 /// Proportion of overall allotted time to wait for optimal builder state
@@ -107,12 +109,19 @@ pub struct BuilderConfig<Types: NodeType> {
     pub tx_status_cache_capacity: usize,
     /// Base fee; the sequencing fee for a block is calculated as block size × base fee
     pub base_fee: u64,
+    /// Policy deciding which queued transactions are included in a block
+    pub transaction_selector: Arc<dyn TransactionSelector<Types>>,
+    /// Write-ahead log persisting privately submitted transactions across restarts.
+    /// Recovered transactions are enqueued by [`GlobalState::replay_mempool_log`].
+    pub mempool_log: Option<Arc<MempoolLog<Types>>>,
 }
 
 #[cfg(test)]
 impl<Types: NodeType> BuilderConfig<Types> {
     pub(crate) fn test() -> Self {
         use marketplace_builder_shared::testing::constants::*;
+
+        use crate::selector::FifoSelector;
         Self {
             builder_keys:
                 <Types::BuilderSignatureKey as BuilderSignatureKey>::generated_from_seed_indexed(
@@ -125,6 +134,8 @@ impl<Types: NodeType> BuilderConfig<Types> {
             txn_channel_capacity: TEST_CHANNEL_BUFFER_SIZE,
             tx_status_cache_capacity: TEST_TX_STATUS_CACHE_CAPACITY,
             base_fee: TEST_BASE_FEE,
+            transaction_selector: Arc::new(FifoSelector),
+            mempool_log: None,
         }
     }
 }
@@ -148,6 +159,8 @@ pub struct GlobalState<Types: NodeType> {
     pub(crate) maximize_txn_capture_timeout: Duration,
     /// See [`BuilderConfig::base_fee`]
     pub(crate) base_fee: u64,
+    /// See [`BuilderConfig::transaction_selector`]
+    pub(crate) transaction_selector: Arc<dyn TransactionSelector<Types>>,
 }
 
 impl<Types: NodeType> GlobalState<Types>
@@ -163,12 +176,17 @@ where
         protocol_max_block_size: u64,
         num_nodes: usize,
     ) -> Arc<Self> {
+        let mut coordinator = BuilderStateCoordinator::new(
+            config.txn_channel_capacity,
+            config.txn_garbage_collect_duration,
+            config.tx_status_cache_capacity,
+        );
+        if let Some(mempool_log) = config.mempool_log {
+            coordinator = coordinator.with_mempool_log(mempool_log);
+        }
+
         Arc::new(Self {
-            coordinator: Arc::new(BuilderStateCoordinator::new(
-                config.txn_channel_capacity,
-                config.txn_garbage_collect_duration,
-                config.tx_status_cache_capacity,
-            )),
+            coordinator: Arc::new(coordinator),
             block_store: RwLock::new(BlockStore::new()),
             block_size_limits: BlockSizeLimits::new(
                 protocol_max_block_size,
@@ -180,9 +198,21 @@ where
             maximize_txn_capture_timeout: config.maximize_txn_capture_timeout,
             instance_state,
             base_fee: config.base_fee,
+            transaction_selector: config.transaction_selector,
         })
     }
 
+    /// Enqueue transactions recovered from the configured mempool log, skipping the ones
+    /// `sequenced_at` reports as already included in a block.
+    /// Should be called once on startup, see [`BuilderStateCoordinator::replay_mempool_log`].
+    pub async fn replay_mempool_log<F, Fut>(&self, sequenced_at: F) -> usize
+    where
+        F: Fn(Commitment<Types::Transaction>) -> Fut,
+        Fut: Future<Output = Option<u64>>,
+    {
+        self.coordinator.replay_mempool_log(sequenced_at).await
+    }
+
     /// Spawns an event loop handling HotShot events from the provided stream.
     /// Returns a handle for the spawned task.
     pub fn start_event_loop(
@@ -344,27 +374,11 @@ where
                 // Don't build an empty block
                 return Ok(None);
             }
-            txn_queue
-                .iter()
-                .scan(0, |total_size, tx| {
-                    let prev_size = *total_size;
-                    *total_size += tx.min_block_size;
-                    // We will include one transaction over our target block length
-                    // if it's the first transaction in queue, otherwise we'd have a possible failure
-                    // state where a single transaction larger than target block state is stuck in
-                    // queue and we just build empty blocks forever
-                    if *total_size >= max_block_size && prev_size != 0 {
-                        None
-                    } else {
-                        // Note: we're going to map from ReceivedTransaction to
-                        // Transaction it contains later, so we can just clone
-                        // the Arc here to reduce the time we hold the lock
-                        Some(Arc::clone(tx))
-                    }
-                })
-                .collect::<Vec<_>>()
+            self.transaction_selector.select(&txn_queue, max_block_size)
         };
 
+        let first_selected = transactions_to_include.first().map(|tx| tx.commit);
+
         let (payload, metadata) =
             match <Types::BlockPayload as BlockPayload<Types>>::from_transactions(
                 transactions_to_include
@@ -394,10 +408,16 @@ where
         // the sequencer indirectly, by observing that we passed some transactions
         // to `<Types::BlockPayload as BlockPayload<Types>>::from_transactions`, but
         // it returned an empty block.
-        // Thus we deduce that the first transaction in our queue is too big to *ever*
+        // Thus we deduce that the first transaction we selected is too big to *ever*
         // be included, because it alone goes over sequencer's block size limit.
         if truncated {
-            builder.txn_queue.write().await.pop_front();
+            if let Some(commit) = first_selected {
+                builder
+                    .txn_queue
+                    .write()
+                    .await
+                    .prune(std::iter::once(&commit));
+            }
             if !should_prioritize_finalization {
                 return Ok(None);
             }
@@ -36,6 +36,7 @@ mod basic;
 mod block_size;
 mod finalization;
 mod integration;
+mod selection;
 
 const MOCK_LEADER_KEYS: LazyCell<BuilderKeys<TestTypes>> =
     LazyCell::new(|| BLSPubKey::generated_from_seed_indexed([0; 32], 0));
@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use async_broadcast::broadcast;
+use hotshot_example_types::{
+    block_types::TestTransaction, node_types::TestTypes, state_types::TestInstanceState,
+};
+use hotshot_types::{
+    data::{VidCommitment, ViewNumber},
+    traits::node_implementation::ConsensusTime,
+};
+use marketplace_builder_shared::{
+    block::{BuilderStateId, ReceivedTransaction, TransactionSource},
+    state::TransactionQueue,
+    testing::constants::{TEST_NUM_NODES_IN_VID_COMPUTATION, TEST_PROTOCOL_MAX_BLOCK_SIZE},
+};
+use tracing_test::traced_test;
+
+use crate::{
+    selector::{
+        FifoSelector, NamespaceQuotaSelector, NamespaceRoundRobinSelector,
+        TransactionSelectionPolicy, TransactionSelector,
+    },
+    service::{BuilderConfig, GlobalState},
+    testing::TestServiceWrapper,
+};
+
+/// Test transactions are attributed to the namespace in their first byte
+fn namespace(tx: &TestTransaction) -> u64 {
+    tx.bytes()[0] as u64
+}
+
+/// Transaction of `len` bytes in namespace `ns`, `id` distinguishes
+/// otherwise identical transactions
+fn tx(ns: u8, id: u8, len: usize) -> TestTransaction {
+    let mut bytes = vec![0u8; len.max(2)];
+    bytes[0] = ns;
+    bytes[1] = id;
+    TestTransaction::new(bytes)
+}
+
+fn queue(transactions: &[TestTransaction]) -> TransactionQueue<TestTypes> {
+    let mut queue = TransactionQueue::new();
+    for tx in transactions {
+        queue.insert(Arc::new(ReceivedTransaction::new(
+            tx.clone(),
+            TransactionSource::Public,
+        )));
+    }
+    queue
+}
+
+fn select(
+    selector: &dyn TransactionSelector<TestTypes>,
+    transactions: &[TestTransaction],
+    max_block_size: u64,
+) -> Vec<TestTransaction> {
+    selector
+        .select(&queue(transactions), max_block_size)
+        .into_iter()
+        .map(|tx| tx.transaction.clone())
+        .collect()
+}
+
+#[test]
+fn fifo_selection() {
+    let transactions = [tx(0, 0, 10), tx(1, 0, 10), tx(0, 1, 10), tx(2, 0, 10)];
+
+    // Everything fits
+    assert_eq!(select(&FifoSelector, &transactions, 100), transactions);
+    // Stops at the first transaction that doesn't fit
+    assert_eq!(select(&FifoSelector, &transactions, 25), transactions[..2]);
+    // The first transaction is included even if it is too big on its own
+    assert_eq!(select(&FifoSelector, &transactions, 5), transactions[..1]);
+}
+
+#[test]
+fn round_robin_selection() {
+    let selector = NamespaceRoundRobinSelector::<TestTypes>::new(namespace);
+    // Namespace 0 floods the queue before anyone else gets a transaction in
+    let transactions = [
+        tx(0, 0, 10),
+        tx(0, 1, 10),
+        tx(0, 2, 10),
+        tx(0, 3, 10),
+        tx(1, 0, 10),
+        tx(2, 0, 10),
+        tx(1, 1, 10),
+    ];
+
+    assert_eq!(
+        select(&selector, &transactions, 100),
+        vec![
+            tx(0, 0, 10),
+            tx(1, 0, 10),
+            tx(2, 0, 10),
+            tx(0, 1, 10),
+            tx(1, 1, 10),
+            tx(0, 2, 10),
+            tx(0, 3, 10),
+        ]
+    );
+
+    // With room for only four transactions every namespace still gets in
+    assert_eq!(
+        select(&selector, &transactions, 45),
+        vec![tx(0, 0, 10), tx(1, 0, 10), tx(2, 0, 10), tx(0, 1, 10)]
+    );
+}
+
+#[test]
+fn round_robin_selection_skips_namespaces_that_dont_fit() {
+    let selector = NamespaceRoundRobinSelector::<TestTypes>::new(namespace);
+    let transactions = [tx(0, 0, 10), tx(1, 0, 50), tx(1, 1, 5), tx(2, 0, 10)];
+
+    // Namespace 1's big transaction doesn't fit, which takes it out of the
+    // rotation without letting its later transaction jump the queue
+    assert_eq!(
+        select(&selector, &transactions, 40),
+        vec![tx(0, 0, 10), tx(2, 0, 10)]
+    );
+}
+
+#[test]
+fn quota_selection() {
+    let selector = NamespaceQuotaSelector::<TestTypes>::new(namespace, 20);
+    let transactions = [
+        tx(0, 0, 10),
+        tx(0, 1, 10),
+        tx(0, 2, 10),
+        tx(1, 0, 30),
+        tx(1, 1, 10),
+        tx(2, 0, 10),
+    ];
+
+    // Namespace 0 is capped at its quota. Namespace 1's first transaction is
+    // over quota on its own, so it gets in, but nothing after it does.
+    assert_eq!(
+        select(&selector, &transactions, 1000),
+        vec![tx(0, 0, 10), tx(0, 1, 10), tx(1, 0, 30), tx(2, 0, 10)]
+    );
+
+    // Block size limits still apply
+    assert_eq!(
+        select(&selector, &transactions, 25),
+        vec![tx(0, 0, 10), tx(0, 1, 10)]
+    );
+}
+
+#[test]
+fn selection_policy_parsing() {
+    for policy in [
+        TransactionSelectionPolicy::Fifo,
+        TransactionSelectionPolicy::NamespaceRoundRobin,
+        TransactionSelectionPolicy::NamespaceQuota {
+            max_namespace_bytes: 1024,
+        },
+    ] {
+        assert_eq!(policy.to_string().parse(), Ok(policy));
+    }
+
+    assert_eq!(
+        "quota:4096".parse(),
+        Ok(TransactionSelectionPolicy::NamespaceQuota {
+            max_namespace_bytes: 4096
+        })
+    );
+    "quota:lots"
+        .parse::<TransactionSelectionPolicy>()
+        .unwrap_err();
+    "lifo".parse::<TransactionSelectionPolicy>().unwrap_err();
+}
+
+/// Checks that the builder assembles blocks using the configured policy
+#[tokio::test]
+#[traced_test]
+async fn builder_uses_configured_selector() {
+    let mut cfg = BuilderConfig::test();
+    cfg.transaction_selector =
+        TransactionSelectionPolicy::NamespaceRoundRobin.selector::<TestTypes>(namespace);
+    let global_state = GlobalState::new(
+        cfg,
+        TestInstanceState::default(),
+        TEST_PROTOCOL_MAX_BLOCK_SIZE,
+        TEST_NUM_NODES_IN_VID_COMPUTATION,
+    );
+    let (event_stream_sender, event_stream) = broadcast(1024);
+    let test_service =
+        TestServiceWrapper::new(Arc::clone(&global_state), event_stream_sender).await;
+    Arc::clone(&global_state).start_event_loop(event_stream);
+
+    test_service
+        .submit_transactions_private(vec![tx(0, 0, 10), tx(0, 1, 10), tx(1, 0, 10)])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_service
+            .get_transactions(&BuilderStateId {
+                parent_view: ViewNumber::genesis(),
+                parent_commitment: VidCommitment::default(),
+            })
+            .await,
+        vec![tx(0, 0, 10), tx(1, 0, 10), tx(0, 1, 10)]
+    );
+}
@@ -107,6 +107,10 @@ impl<Types: NodeType> EventFilterSet<Types> {
             EventType::DaProposal { .. } => filter.contains(&EventFilter::DaProposal),
             EventType::QuorumProposal { .. } => filter.contains(&EventFilter::QuorumProposal),
             EventType::UpgradeProposal { .. } => filter.contains(&EventFilter::UpgradeProposal),
+            EventType::VidEncodingEvidence { .. } => {
+                filter.contains(&EventFilter::VidEncodingEvidence)
+            },
+            EventType::Equivocation { .. } => filter.contains(&EventFilter::Equivocation),
             _ => false,
         }
     }
@@ -125,6 +129,8 @@ pub enum EventFilter<Types: NodeType> {
     DaProposal,
     QuorumProposal,
     UpgradeProposal,
+    VidEncodingEvidence,
+    Equivocation,
     Pd(PhantomData<Types>),
 }
 
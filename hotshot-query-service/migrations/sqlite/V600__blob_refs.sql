@@ -0,0 +1,8 @@
+-- Payloads and VID common data may be stored in a separate blob store. In that
+-- case, the inline data column holds an empty value and the blob column holds
+-- the content address of the data in the blob store.
+ALTER TABLE payload
+    ADD COLUMN data_blob TEXT;
+
+ALTER TABLE vid2
+    ADD COLUMN common_blob TEXT;
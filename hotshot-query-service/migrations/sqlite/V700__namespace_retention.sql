@@ -0,0 +1,26 @@
+-- The namespace of each transaction, for applications which divide blocks into namespaces. This is
+-- used to apply per-namespace retention rules when pruning.
+ALTER TABLE transactions
+    ADD COLUMN ns_id BIGINT;
+CREATE INDEX transactions_ns_id_idx ON transactions (ns_id, block_height);
+
+-- The namespaces present in each block.
+CREATE TABLE block_namespace
+(
+    height BIGINT NOT NULL REFERENCES header (height) ON DELETE CASCADE,
+    ns_id  BIGINT NOT NULL,
+    PRIMARY KEY (ns_id, height)
+);
+CREATE INDEX block_namespace_height_idx ON block_namespace (height);
+
+-- The height of the last block pruned by the retention rule for each namespace.
+CREATE TABLE namespace_pruned_height
+(
+    ns_id       BIGINT PRIMARY KEY,
+    last_height BIGINT NOT NULL
+);
+
+-- Payloads which were deleted because every namespace in the block was pruned by a retention rule.
+-- These are deliberate gaps, which should not be reported as missing or fetched again.
+ALTER TABLE payload
+    ADD COLUMN retention_pruned BOOLEAN NOT NULL DEFAULT false;
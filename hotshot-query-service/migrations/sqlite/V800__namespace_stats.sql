@@ -0,0 +1,14 @@
+-- Per-block statistics for each namespace, aggregated when the block is indexed so that namespace
+-- analytics don't need to scan payloads.
+ALTER TABLE block_namespace
+    ADD COLUMN num_transactions BIGINT NOT NULL DEFAULT 0;
+-- The total size of the namespace's transactions, or NULL if the payload does not report
+-- transaction sizes.
+ALTER TABLE block_namespace
+    ADD COLUMN size BIGINT;
+
+-- Backfill transaction counts for blocks indexed before this migration. Sizes can't be recovered
+-- without decoding payloads, so they are left unknown.
+UPDATE block_namespace AS b
+    SET num_transactions =
+        (SELECT count(*) FROM transactions AS t WHERE t.block_height = b.height AND t.ns_id = b.ns_id);
@@ -676,6 +676,18 @@ where
         }
         .boxed()
     })?
+    .at("get_pruned_payloads", move |req, state| {
+        async move {
+            let from: usize = req.integer_param("from")?;
+            let until: usize = req.integer_param("until")?;
+            enforce_range_limit(from, until, small_object_range_limit)?;
+
+            Ok(state
+                .read(|state| state.get_pruned_payloads(from..until).boxed())
+                .await?)
+        }
+        .boxed()
+    })?
     .at("get_limits", move |_req, _state| {
         async move {
             Ok(Limits {
@@ -701,7 +713,8 @@ where
     Ok(api)
 }
 
-fn enforce_range_limit(from: usize, until: usize, limit: usize) -> Result<(), Error> {
+/// Fail with [`Error::RangeLimit`] if the range `[from, until)` contains more than `limit` objects.
+pub fn enforce_range_limit(from: usize, until: usize, limit: usize) -> Result<(), Error> {
     if until.saturating_sub(from) > limit {
         return Err(Error::RangeLimit { from, until, limit });
     }
@@ -36,7 +36,7 @@ use super::{
     },
     StateCertQueryData,
 };
-use crate::{types::HeightIndexed, Header, Payload};
+use crate::{types::HeightIndexed, Header, Payload, QueryResult};
 
 #[derive(Derivative, From, Display)]
 #[derivative(Ord = "feature_allow_slow_enum")]
@@ -236,6 +236,14 @@ where
 
     async fn get_state_cert(&self, epoch: u64) -> Fetch<StateCertQueryData<Types>>;
 
+    /// Get the heights in `range` of blocks whose payloads were deleted by namespace retention
+    /// rules.
+    ///
+    /// These payloads will not be fetched, so requests for them never succeed.
+    async fn get_pruned_payloads<R>(&self, range: R) -> QueryResult<Vec<u64>>
+    where
+        R: RangeBounds<usize> + Send + 'static;
+
     async fn subscribe_blocks(&self, from: usize) -> BoxStream<'static, BlockQueryData<Types>> {
         self.get_block_range(from..)
             .await
@@ -193,6 +193,32 @@ pub trait QueryablePayload<Types: NodeType>: traits::BlockPayload<Types> {
     ) -> Option<(Self::Transaction, Self::InclusionProof)> {
         self.transaction_with_proof(meta, &self.by_hash(meta, hash)?)
     }
+
+    /// Get the namespace of the transaction with a given index.
+    ///
+    /// Applications which divide blocks into namespaces (e.g. one per rollup) can implement this to
+    /// enable per-namespace features such as namespace retention rules when pruning. The default
+    /// implementation returns [`None`], meaning that transactions do not belong to any namespace.
+    fn transaction_namespace(
+        &self,
+        _meta: &Self::Metadata,
+        _index: &Self::TransactionIndex,
+    ) -> Option<u64> {
+        None
+    }
+
+    /// Get the size in bytes of the transaction with a given index.
+    ///
+    /// This is used alongside [`transaction_namespace`](Self::transaction_namespace) to compute
+    /// per-namespace statistics. The default implementation returns [`None`], in which case only
+    /// the number of transactions in each namespace is tracked.
+    fn transaction_size(
+        &self,
+        _meta: &Self::Metadata,
+        _index: &Self::TransactionIndex,
+    ) -> Option<u64> {
+        None
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
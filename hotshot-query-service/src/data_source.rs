@@ -868,6 +868,7 @@ pub mod node_tests {
                 missing_vid_shares: 1,
                 missing_leaves: 0,
                 pruned_height: None,
+                pruned_payloads: 0,
             }
         );
 
@@ -882,6 +883,7 @@ pub mod node_tests {
                 missing_vid_shares: 3,
                 missing_leaves: 1,
                 pruned_height: None,
+                pruned_payloads: 0,
             }
         );
 
@@ -899,6 +901,7 @@ pub mod node_tests {
                 missing_vid_shares: 3,
                 missing_leaves: 1,
                 pruned_height: None,
+                pruned_payloads: 0,
             }
         );
 
@@ -939,6 +942,7 @@ pub mod node_tests {
             missing_vid_common: 0,
             missing_vid_shares: expected_missing,
             pruned_height: None,
+            pruned_payloads: 0,
         };
         assert_eq!(ds.sync_status().await.unwrap(), expected_sync_status);
 
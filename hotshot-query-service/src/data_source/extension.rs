@@ -306,6 +306,12 @@ where
     async fn get_state_cert(&self, epoch: u64) -> Fetch<StateCertQueryData<Types>> {
         self.data_source.get_state_cert(epoch).await
     }
+    async fn get_pruned_payloads<R>(&self, range: R) -> QueryResult<Vec<u64>>
+    where
+        R: RangeBounds<usize> + Send + 'static,
+    {
+        self.data_source.get_pruned_payloads(range).await
+    }
 }
 
 impl<D, U, Types> UpdateAvailabilityData<Types> for ExtensibleDataSource<D, U>
@@ -500,6 +506,24 @@ where
     > {
         self.data_source.get_search_results(query).await
     }
+
+    async fn get_namespace_summaries(
+        &self,
+        request: explorer::query_data::GetNamespaceSummariesRequest,
+    ) -> Result<
+        Vec<explorer::query_data::NamespaceSummary>,
+        explorer::query_data::GetNamespaceSummariesError,
+    > {
+        self.data_source.get_namespace_summaries(request).await
+    }
+
+    async fn get_namespace_detail(
+        &self,
+        request: explorer::query_data::GetNamespaceDetailRequest,
+    ) -> Result<explorer::query_data::NamespaceDetail, explorer::query_data::GetNamespaceDetailError>
+    {
+        self.data_source.get_namespace_detail(request).await
+    }
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -803,6 +803,16 @@ where
     async fn get_state_cert(&self, epoch: u64) -> Fetch<StateCertQueryData<Types>> {
         self.fetcher.get(StateCertRequest::from(epoch)).await
     }
+
+    async fn get_pruned_payloads<R>(&self, range: R) -> QueryResult<Vec<u64>>
+    where
+        R: RangeBounds<usize> + Send + 'static,
+    {
+        let mut tx = self.read().await.map_err(|err| QueryError::Error {
+            message: err.to_string(),
+        })?;
+        tx.get_pruned_payloads(range).await
+    }
 }
 
 impl<Types, S, P> UpdateAvailabilityData<Types> for FetchingDataSource<Types, S, P>
@@ -1979,6 +1989,30 @@ where
         })?;
         tx.get_search_results(query).await
     }
+
+    async fn get_namespace_summaries(
+        &self,
+        request: explorer::query_data::GetNamespaceSummariesRequest,
+    ) -> Result<
+        Vec<explorer::query_data::NamespaceSummary>,
+        explorer::query_data::GetNamespaceSummariesError,
+    > {
+        let mut tx = self.read().await.map_err(|err| QueryError::Error {
+            message: err.to_string(),
+        })?;
+        tx.get_namespace_summaries(request).await
+    }
+
+    async fn get_namespace_detail(
+        &self,
+        request: explorer::query_data::GetNamespaceDetailRequest,
+    ) -> Result<explorer::query_data::NamespaceDetail, explorer::query_data::GetNamespaceDetailError>
+    {
+        let mut tx = self.read().await.map_err(|err| QueryError::Error {
+            message: err.to_string(),
+        })?;
+        tx.get_namespace_detail(request).await
+    }
 }
 
 /// A provider which can be used as a fetcher by the availability service.
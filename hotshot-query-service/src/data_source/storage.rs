@@ -75,9 +75,11 @@ use crate::{
         query_data::{
             BlockDetail, BlockIdentifier, BlockSummary, ExplorerSummary, GetBlockDetailError,
             GetBlockSummariesError, GetBlockSummariesRequest, GetExplorerSummaryError,
-            GetSearchResultsError, GetTransactionDetailError, GetTransactionSummariesError,
-            GetTransactionSummariesRequest, SearchResult, TransactionDetailResponse,
-            TransactionIdentifier, TransactionSummary,
+            GetNamespaceDetailError, GetNamespaceDetailRequest, GetNamespaceSummariesError,
+            GetNamespaceSummariesRequest, GetSearchResultsError, GetTransactionDetailError,
+            GetTransactionSummariesError, GetTransactionSummariesRequest, NamespaceDetail,
+            NamespaceSummary, SearchResult, TransactionDetailResponse, TransactionIdentifier,
+            TransactionSummary,
         },
         traits::{ExplorerHeader, ExplorerTransaction},
     },
@@ -86,6 +88,7 @@ use crate::{
     Header, Payload, QueryResult, Transaction,
 };
 
+pub mod blob;
 pub mod fail_storage;
 pub mod fs;
 mod ledger_log;
@@ -197,6 +200,18 @@ where
     async fn first_available_leaf(&mut self, from: u64) -> QueryResult<LeafQueryData<Types>>;
 
     async fn get_state_cert(&mut self, epoch: u64) -> QueryResult<StateCertQueryData<Types>>;
+
+    /// Get the heights in `range` of blocks whose payloads were deleted by namespace retention
+    /// rules.
+    ///
+    /// The default implementation is for storage which does not support namespace retention rules,
+    /// and so never has such gaps.
+    async fn get_pruned_payloads<R>(&mut self, _range: R) -> QueryResult<Vec<u64>>
+    where
+        R: RangeBounds<usize> + Send + 'static,
+    {
+        Ok(vec![])
+    }
 }
 
 pub trait UpdateAvailabilityStorage<Types>
@@ -339,6 +354,21 @@ where
         &mut self,
         query: TaggedBase64,
     ) -> Result<SearchResult<Types>, GetSearchResultsError>;
+
+    /// `get_namespace_summaries` is a method that retrieves the most active
+    /// namespaces over a window of time, as specified by the given
+    /// [GetNamespaceSummariesRequest].
+    async fn get_namespace_summaries(
+        &mut self,
+        request: GetNamespaceSummariesRequest,
+    ) -> Result<Vec<NamespaceSummary>, GetNamespaceSummariesError>;
+
+    /// `get_namespace_detail` is a method that retrieves the history of a
+    /// single namespace, as specified by the given [GetNamespaceDetailRequest].
+    async fn get_namespace_detail(
+        &mut self,
+        request: GetNamespaceDetailRequest,
+    ) -> Result<NamespaceDetail, GetNamespaceDetailError>;
 }
 
 /// This trait defines methods that a data source should implement
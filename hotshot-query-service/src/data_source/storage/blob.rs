@@ -0,0 +1,327 @@
+// Copyright (c) 2022 Espresso Systems (espressosys.com)
+// This file is part of the HotShot Query Service library.
+//
+// This program is free software: you can redistribute it and/or modify it under the terms of the GNU
+// General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+// This program is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without
+// even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+// You should have received a copy of the GNU General Public License along with this program. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Content-addressed storage for large binary objects.
+//!
+//! Block payloads, VID data and DA proposals make up most of the storage used by an archival node.
+//! A [`BlobStore`] lets these objects live outside the relational database, which then only needs
+//! to store a reference to each object: its [`BlobKey`]. Because blobs are addressed by the hash of
+//! their contents, identical objects (such as the many empty payloads in a quiet chain) are only
+//! stored once, and data loaded from an untrusted store can be checked against its key.
+//!
+//! Two implementations are provided:
+//! * [`FileSystemBlobStore`] keeps blobs in a local directory
+//! * [`ObjectStoreBlobStore`] keeps blobs in any store implementing [`ObjectStore`], a minimal
+//!   S3-compatible interface. [`MemoryObjectStore`] is a local stand-in for testing.
+//!
+//! Blobs are never deleted, even when the rows referencing them are pruned, since a content-addressed
+//! blob may be shared by many rows. The blob layer is therefore best suited to archival nodes.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Display, Formatter},
+    fs,
+    path::PathBuf,
+    str::FromStr,
+};
+
+use anyhow::{bail, ensure, Context};
+use async_lock::RwLock;
+use async_trait::async_trait;
+
+/// The content address of a blob.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BlobKey(blake3::Hash);
+
+impl BlobKey {
+    /// The key of a blob with contents `data`.
+    pub fn of(data: &[u8]) -> Self {
+        Self(blake3::hash(data))
+    }
+}
+
+impl Display for BlobKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.to_hex())
+    }
+}
+
+impl FromStr for BlobKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(
+            blake3::Hash::from_hex(s).context(format!("malformed blob key {s}"))?,
+        ))
+    }
+}
+
+/// A content-addressed store for large binary objects.
+#[async_trait]
+pub trait BlobStore: Debug + Send + Sync {
+    /// Store a blob, returning its key.
+    ///
+    /// Storing a blob which is already present is a no-op.
+    async fn put(&self, data: &[u8]) -> anyhow::Result<BlobKey>;
+
+    /// Load the blob with the given key.
+    ///
+    /// Fails if the blob is not present. Implementations need not check that the returned data
+    /// matches `key`; callers which do not trust the store should use [`resolve`].
+    async fn get(&self, key: &BlobKey) -> anyhow::Result<Vec<u8>>;
+}
+
+/// A [`BlobStore`] backed by a directory on the local file system.
+///
+/// Each blob is stored in its own file, named after its key and sharded into subdirectories by the
+/// first byte of the key, to avoid creating very large directories.
+#[derive(Clone, Debug)]
+pub struct FileSystemBlobStore {
+    root: PathBuf,
+}
+
+impl FileSystemBlobStore {
+    /// Open a blob store in the directory `root`, creating it if necessary.
+    pub fn new(root: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let root = root.into();
+        fs::create_dir_all(&root).context(format!("creating blob directory {}", root.display()))?;
+        Ok(Self { root })
+    }
+
+    fn path(&self, key: &BlobKey) -> PathBuf {
+        let name = key.to_string();
+        self.root.join(&name[..2]).join(name)
+    }
+}
+
+#[async_trait]
+impl BlobStore for FileSystemBlobStore {
+    async fn put(&self, data: &[u8]) -> anyhow::Result<BlobKey> {
+        let key = BlobKey::of(data);
+        let path = self.path(&key);
+        if path.exists() {
+            return Ok(key);
+        }
+        let dir = path.parent().context("blob path has no parent")?;
+        fs::create_dir_all(dir).context(format!("creating blob directory {}", dir.display()))?;
+
+        // Write to a temporary file and then rename it, so that a blob file, if it exists, always
+        // has the complete contents.
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, data).context(format!("writing blob {}", tmp.display()))?;
+        fs::rename(&tmp, &path).context(format!("writing blob {}", path.display()))?;
+        Ok(key)
+    }
+
+    async fn get(&self, key: &BlobKey) -> anyhow::Result<Vec<u8>> {
+        let path = self.path(key);
+        fs::read(&path).context(format!("reading blob {}", path.display()))
+    }
+}
+
+/// A minimal interface to an S3-compatible object store.
+///
+/// This is the subset of the S3 API needed to store blobs: a flat namespace of keys within a single
+/// bucket, each naming an opaque byte string. Implementations can wrap an S3 client or any other
+/// service with compatible semantics.
+#[async_trait]
+pub trait ObjectStore: Debug + Send + Sync {
+    /// Create or overwrite the object `key`.
+    async fn put_object(&self, key: &str, body: Vec<u8>) -> anyhow::Result<()>;
+
+    /// Get the contents of the object `key`, or [`None`] if there is no such object.
+    async fn get_object(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Check whether the object `key` exists.
+    async fn head_object(&self, key: &str) -> anyhow::Result<bool>;
+}
+
+/// A [`BlobStore`] backed by an S3-compatible [`ObjectStore`].
+#[derive(Clone, Debug)]
+pub struct ObjectStoreBlobStore<S> {
+    store: S,
+    prefix: String,
+}
+
+impl<S: ObjectStore> ObjectStoreBlobStore<S> {
+    /// Store blobs in `store`, under object keys beginning with `prefix`.
+    pub fn new(store: S, prefix: impl Into<String>) -> Self {
+        Self {
+            store,
+            prefix: prefix.into(),
+        }
+    }
+
+    fn object_key(&self, key: &BlobKey) -> String {
+        format!("{}{key}", self.prefix)
+    }
+}
+
+#[async_trait]
+impl<S: ObjectStore> BlobStore for ObjectStoreBlobStore<S> {
+    async fn put(&self, data: &[u8]) -> anyhow::Result<BlobKey> {
+        let key = BlobKey::of(data);
+        let object_key = self.object_key(&key);
+        if !self.store.head_object(&object_key).await? {
+            self.store.put_object(&object_key, data.to_vec()).await?;
+        }
+        Ok(key)
+    }
+
+    async fn get(&self, key: &BlobKey) -> anyhow::Result<Vec<u8>> {
+        let object_key = self.object_key(key);
+        self.store
+            .get_object(&object_key)
+            .await?
+            .context(format!("missing object {object_key}"))
+    }
+}
+
+/// An in-memory [`ObjectStore`], standing in for a remote object store in tests.
+#[derive(Debug, Default)]
+pub struct MemoryObjectStore {
+    objects: RwLock<HashMap<String, Vec<u8>>>,
+}
+
+#[async_trait]
+impl ObjectStore for MemoryObjectStore {
+    async fn put_object(&self, key: &str, body: Vec<u8>) -> anyhow::Result<()> {
+        self.objects.write().await.insert(key.to_string(), body);
+        Ok(())
+    }
+
+    async fn get_object(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.objects.read().await.get(key).cloned())
+    }
+
+    async fn head_object(&self, key: &str) -> anyhow::Result<bool> {
+        Ok(self.objects.read().await.contains_key(key))
+    }
+}
+
+/// Prepare `data` to be stored in a database row which may refer to a blob.
+///
+/// Such rows have an inline data column and a nullable blob key column. If a blob store is
+/// configured, `data` is moved to the store, and the row gets an empty inline value and the blob
+/// key. Otherwise, `data` is stored inline and the blob key is [`None`]. Data stored this way can be
+/// loaded with [`resolve`].
+pub async fn offload(
+    store: Option<&dyn BlobStore>,
+    data: Vec<u8>,
+) -> anyhow::Result<(Vec<u8>, Option<String>)> {
+    match store {
+        Some(store) => {
+            let key = store.put(&data).await?;
+            Ok((vec![], Some(key.to_string())))
+        },
+        None => Ok((data, None)),
+    }
+}
+
+/// Load data stored with [`offload`], given the contents of the inline and blob key columns.
+///
+/// Data loaded from the blob store is checked against its key.
+pub async fn resolve(
+    store: Option<&dyn BlobStore>,
+    inline: Vec<u8>,
+    key: Option<String>,
+) -> anyhow::Result<Vec<u8>> {
+    let Some(key) = key else {
+        return Ok(inline);
+    };
+    let Some(store) = store else {
+        bail!("data is stored in blob {key}, but no blob store is configured");
+    };
+    let key = key.parse()?;
+    let data = store.get(&key).await?;
+    ensure!(BlobKey::of(&data) == key, "blob {key} is corrupt");
+    Ok(data)
+}
+
+#[cfg(test)]
+mod test {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    async fn test_blob_store(store: &dyn BlobStore) {
+        let key = store.put(b"hello").await.unwrap();
+        assert_eq!(key, BlobKey::of(b"hello"));
+        assert_eq!(store.get(&key).await.unwrap(), b"hello");
+
+        // Storing the same blob again is idempotent.
+        assert_eq!(store.put(b"hello").await.unwrap(), key);
+        assert_eq!(store.get(&key).await.unwrap(), b"hello");
+
+        // Empty blobs are allowed.
+        let empty = store.put(&[]).await.unwrap();
+        assert_eq!(store.get(&empty).await.unwrap(), Vec::<u8>::new());
+
+        // Missing blobs are an error.
+        store.get(&BlobKey::of(b"missing")).await.unwrap_err();
+
+        // Round trip through the row helpers.
+        let (inline, blob) = offload(Some(store), b"row data".to_vec()).await.unwrap();
+        assert!(inline.is_empty());
+        assert_eq!(
+            resolve(Some(store), inline, blob).await.unwrap(),
+            b"row data"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_file_system_blob_store() {
+        let dir = TempDir::new().unwrap();
+        let store = FileSystemBlobStore::new(dir.path().join("blobs")).unwrap();
+        test_blob_store(&store).await;
+
+        // Blobs persist across instances.
+        let key = BlobKey::of(b"hello");
+        let store = FileSystemBlobStore::new(dir.path().join("blobs")).unwrap();
+        assert_eq!(store.get(&key).await.unwrap(), b"hello");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_object_store_blob_store() {
+        let store = ObjectStoreBlobStore::new(MemoryObjectStore::default(), "blobs/");
+        test_blob_store(&store).await;
+
+        // Objects are stored under the prefix.
+        let key = BlobKey::of(b"hello");
+        assert!(store
+            .store
+            .head_object(&format!("blobs/{key}"))
+            .await
+            .unwrap());
+
+        // Corrupt data in the object store is detected.
+        store
+            .store
+            .put_object(&format!("blobs/{key}"), b"goodbye".to_vec())
+            .await
+            .unwrap();
+        resolve(Some(&store), vec![], Some(key.to_string()))
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_resolve_inline() {
+        let (inline, blob) = offload(None, b"row data".to_vec()).await.unwrap();
+        assert_eq!(blob, None);
+        assert_eq!(resolve(None, inline, blob).await.unwrap(), b"row data");
+
+        // A blob reference cannot be resolved without a blob store.
+        let key = BlobKey::of(b"row data").to_string();
+        resolve(None, vec![], Some(key)).await.unwrap_err();
+    }
+}
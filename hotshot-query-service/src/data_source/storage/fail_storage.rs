@@ -64,6 +64,7 @@ pub enum FailableAction {
     GetTransaction,
     FirstAvailableLeaf,
     GetStateCert,
+    GetPrunedPayloads,
 
     /// Target any action for failure.
     Any,
@@ -473,6 +474,15 @@ where
         self.maybe_fail_read(FailableAction::GetStateCert).await?;
         self.inner.get_state_cert(epoch).await
     }
+
+    async fn get_pruned_payloads<R>(&mut self, range: R) -> QueryResult<Vec<u64>>
+    where
+        R: RangeBounds<usize> + Send + 'static,
+    {
+        self.maybe_fail_read(FailableAction::GetPrunedPayloads)
+            .await?;
+        self.inner.get_pruned_payloads(range).await
+    }
 }
 
 impl<Types, T> UpdateAvailabilityStorage<Types> for Transaction<T>
@@ -794,6 +794,7 @@ where
             missing_vid_common: missing_vid,
             missing_vid_shares: missing_vid + null_vid_shares,
             pruned_height: None,
+            pruned_payloads: 0,
         })
     }
 
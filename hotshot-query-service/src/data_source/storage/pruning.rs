@@ -10,7 +10,7 @@
 // You should have received a copy of the GNU General Public License along with this program. If not,
 // see <https://www.gnu.org/licenses/>.
 
-use std::{fmt::Debug, time::Duration};
+use std::{collections::BTreeMap, fmt::Debug, time::Duration};
 
 use anyhow::bail;
 use async_trait::async_trait;
@@ -24,6 +24,7 @@ pub struct PrunerCfg {
     max_usage: u16,
     interval: Duration,
     state_tables: Vec<String>,
+    namespace_retention: BTreeMap<u64, Option<Duration>>,
 }
 
 #[async_trait]
@@ -114,6 +115,14 @@ impl PrunerCfg {
         self
     }
 
+    /// Add a retention rule for namespace `ns_id`.
+    ///
+    /// `None` means the namespace is kept forever.
+    pub fn with_namespace_retention(mut self, ns_id: u64, retention: Option<Duration>) -> Self {
+        self.namespace_retention.insert(ns_id, retention);
+        self
+    }
+
     /// Disk space threshold (in bytes).
     ///
     /// If the disk usage exceeds this threshold, pruning of data starts from
@@ -161,6 +170,20 @@ impl PrunerCfg {
     pub fn state_tables(&self) -> Vec<String> {
         self.state_tables.clone()
     }
+
+    /// Retention rules for individual namespaces
+    ///
+    /// Each rule gives the period for which payload data and transaction indexes belonging to a
+    /// namespace are retained, or `None` if they are retained forever. Headers and leaves are not
+    /// affected by these rules, and a block payload is only deleted once every namespace in the
+    /// block has been pruned, so payloads containing namespaces without a rule are kept until the
+    /// whole block is pruned.
+    ///
+    /// Rules also limit the global retention policy: blocks are never pruned while they contain
+    /// data which a namespace rule says to retain.
+    pub fn namespace_retention(&self) -> &BTreeMap<u64, Option<Duration>> {
+        &self.namespace_retention
+    }
 }
 
 impl Default for PrunerCfg {
@@ -178,6 +201,7 @@ impl Default for PrunerCfg {
             // 1.5 hour
             interval: Duration::from_secs(5400),
             state_tables: Vec::new(),
+            namespace_retention: BTreeMap::new(),
         }
     }
 }
@@ -11,7 +11,7 @@
 // see <https://www.gnu.org/licenses/>.
 
 #![cfg(feature = "sql-data-source")]
-use std::{cmp::min, fmt::Debug, str::FromStr, time::Duration};
+use std::{cmp::min, collections::BTreeMap, fmt::Debug, str::FromStr, sync::Arc, time::Duration};
 
 use anyhow::Context;
 use async_trait::async_trait;
@@ -41,7 +41,10 @@ use sqlx::{
 
 use crate::{
     data_source::{
-        storage::pruning::{PruneStorage, PrunerCfg, PrunerConfig},
+        storage::{
+            blob::BlobStore,
+            pruning::{PruneStorage, PrunerCfg, PrunerConfig},
+        },
         update::Transaction as _,
         VersionedDataSource,
     },
@@ -227,6 +230,7 @@ pub struct Config {
     pruner_cfg: Option<PrunerCfg>,
     archive: bool,
     pool: Option<Pool<Db>>,
+    blob_store: Option<Arc<dyn BlobStore>>,
 }
 
 #[cfg(not(feature = "embedded-db"))]
@@ -265,6 +269,7 @@ impl From<SqliteConnectOptions> for Config {
             pruner_cfg: None,
             archive: false,
             pool: None,
+            blob_store: None,
         }
     }
 }
@@ -282,6 +287,7 @@ impl From<PgConnectOptions> for Config {
             pruner_cfg: None,
             archive: false,
             pool: None,
+            blob_store: None,
         }
     }
 }
@@ -431,6 +437,17 @@ impl Config {
         self
     }
 
+    /// Store block payloads and VID common data in a separate blob store.
+    ///
+    /// The database will store only a reference to each of these objects. Objects which were
+    /// already stored in the database remain there and can still be loaded. Once this option has
+    /// been used, it must continue to be used with the same blob store, or objects referenced by the
+    /// database will not be loadable.
+    pub fn blob_store(mut self, store: Arc<dyn BlobStore>) -> Self {
+        self.blob_store = Some(store);
+        self
+    }
+
     /// Set the maximum idle time of a connection.
     ///
     /// Any connection which has been open and unused longer than this duration will be
@@ -488,6 +505,7 @@ pub struct SqlStorage {
     metrics: PrometheusMetrics,
     pool_metrics: PoolMetrics,
     pruner_cfg: Option<PrunerCfg>,
+    blob_store: Option<Arc<dyn BlobStore>>,
 }
 
 #[derive(Debug, Default)]
@@ -495,18 +513,26 @@ pub struct Pruner {
     pruned_height: Option<u64>,
     target_height: Option<u64>,
     minimum_retention_height: Option<u64>,
+    namespace_target_heights: Option<BTreeMap<u64, u64>>,
 }
 
 impl SqlStorage {
     pub fn pool(&self) -> Pool<Db> {
         self.pool.clone()
     }
+
+    /// The blob store used for large objects, if any.
+    pub fn blob_store(&self) -> Option<Arc<dyn BlobStore>> {
+        self.blob_store.clone()
+    }
+
     /// Connect to a remote database.
     pub async fn connect(mut config: Config) -> Result<Self, Error> {
         let metrics = PrometheusMetrics::default();
         let pool_metrics = PoolMetrics::new(&*metrics.subgroup("sql".into()));
         let pool = config.pool_opt.clone();
         let pruner_cfg = config.pruner_cfg;
+        let blob_store = config.blob_store.clone();
 
         // re-use the same pool if present and return early
         if let Some(pool) = config.pool {
@@ -515,6 +541,7 @@ impl SqlStorage {
                 pool_metrics,
                 pool,
                 pruner_cfg,
+                blob_store,
             });
         }
 
@@ -603,6 +630,7 @@ impl SqlStorage {
             pool_metrics,
             metrics,
             pruner_cfg,
+            blob_store,
         })
     }
 }
@@ -663,6 +691,109 @@ impl SqlStorage {
         };
         Ok(Some(height as u64))
     }
+
+    /// Prune a batch of data according to the namespace retention rules in `cfg`.
+    ///
+    /// Returns the height pruned to, or [`None`] if every namespace is already pruned up to its
+    /// target height for this pruner run.
+    async fn prune_namespaces(
+        &self,
+        cfg: &PrunerCfg,
+        pruner: &mut Pruner,
+    ) -> anyhow::Result<Option<u64>> {
+        if cfg.namespace_retention().is_empty() {
+            return Ok(None);
+        }
+
+        // Compute the target height for each namespace once per pruner run, just like the target
+        // height for the global retention policy.
+        let targets = match &pruner.namespace_target_heights {
+            Some(targets) => targets.clone(),
+            None => {
+                let mut targets = BTreeMap::new();
+                for (ns_id, retention) in cfg.namespace_retention() {
+                    let Some(retention) = retention else {
+                        continue;
+                    };
+                    let target = self
+                        .get_height_by_timestamp(
+                            Utc::now().timestamp() - (retention.as_secs()) as i64,
+                        )
+                        .await?;
+                    if let Some(target) = target {
+                        targets.insert(*ns_id, target);
+                    }
+                }
+                pruner.namespace_target_heights = Some(targets.clone());
+                targets
+            },
+        };
+        let Some(minimum_height) = self.get_minimum_height().await? else {
+            return Ok(None);
+        };
+
+        let pruned_heights = self.read().await?.load_namespace_pruned_heights().await?;
+        for (ns_id, target) in targets {
+            let from = match pruned_heights.get(&ns_id) {
+                Some(height) => height + 1,
+                None => minimum_height,
+            };
+            if from > target {
+                continue;
+            }
+            let to = min(from + cfg.batch_size() - 1, target);
+
+            let mut tx = self.write().await?;
+            tx.prune_namespace(ns_id, from, to).await?;
+            tx.commit().await.map_err(|e| QueryError::Error {
+                message: format!("failed to commit {e}"),
+            })?;
+            tracing::info!(ns_id, from, to, "pruned namespace");
+            return Ok(Some(to));
+        }
+
+        Ok(None)
+    }
+
+    /// Limit the height up to which the global retention policy may prune whole blocks.
+    ///
+    /// Blocks containing namespace data which has not been pruned by its namespace retention rule
+    /// must be kept, so the result is below the first such block.
+    async fn limit_by_namespace_retention(
+        &self,
+        cfg: &PrunerCfg,
+        height: Option<u64>,
+    ) -> anyhow::Result<Option<u64>> {
+        let Some(mut height) = height else {
+            return Ok(None);
+        };
+        if cfg.namespace_retention().is_empty() {
+            return Ok(Some(height));
+        }
+
+        let mut tx = self.read().await?;
+        let pruned_heights = tx.load_namespace_pruned_heights().await?;
+        for ns_id in cfg.namespace_retention().keys() {
+            let pruned_height = pruned_heights
+                .get(ns_id)
+                .map(|height| *height as i64)
+                .unwrap_or(-1);
+            let (retained,) = query_as::<(Option<i64>,)>(
+                "SELECT min(height) FROM block_namespace WHERE ns_id = $1 AND height > $2",
+            )
+            .bind(*ns_id as i64)
+            .bind(pruned_height)
+            .fetch_one(tx.as_mut())
+            .await?;
+            if let Some(retained) = retained {
+                let Some(limit) = (retained as u64).checked_sub(1) else {
+                    return Ok(None);
+                };
+                height = min(height, limit);
+            }
+        }
+        Ok(Some(height))
+    }
 }
 
 #[async_trait]
@@ -697,6 +828,12 @@ impl PruneStorage for SqlStorage {
         let max_usage = cfg.max_usage();
         let state_tables = cfg.state_tables();
 
+        // Apply namespace retention rules first. These only delete parts of blocks, and they
+        // determine how much of the chain the global retention policy below may delete.
+        if let Some(height) = self.prune_namespaces(&cfg, pruner).await? {
+            return Ok(Some(height));
+        }
+
         // If a pruner run was already in progress, some variables may already be set,
         // depending on whether a batch was deleted and which batch it was (target or minimum retention).
         // This enables us to resume the pruner run from the exact heights.
@@ -722,7 +859,7 @@ impl PruneStorage for SqlStorage {
                     Utc::now().timestamp() - (cfg.target_retention().as_secs()) as i64,
                 )
                 .await?;
-            target_height = th;
+            target_height = self.limit_by_namespace_retention(&cfg, th).await?;
             pruner.target_height = target_height;
         };
 
@@ -766,6 +903,9 @@ impl PruneStorage for SqlStorage {
                             Utc::now().timestamp() - (cfg.minimum_retention().as_secs()) as i64,
                         )
                         .await?;
+                    minimum_retention_height = self
+                        .limit_by_namespace_retention(&cfg, minimum_retention_height)
+                        .await?;
 
                     pruner.minimum_retention_height = minimum_retention_height;
                 }
@@ -811,11 +951,21 @@ impl VersionedDataSource for SqlStorage {
         Self: 'a;
 
     async fn write(&self) -> anyhow::Result<Transaction<Write>> {
-        Transaction::new(&self.pool, self.pool_metrics.clone()).await
+        Transaction::new(
+            &self.pool,
+            self.pool_metrics.clone(),
+            self.blob_store.clone(),
+        )
+        .await
     }
 
     async fn read(&self) -> anyhow::Result<Transaction<Read>> {
-        Transaction::new(&self.pool, self.pool_metrics.clone()).await
+        Transaction::new(
+            &self.pool,
+            self.pool_metrics.clone(),
+            self.blob_store.clone(),
+        )
+        .await
     }
 }
 
@@ -1308,7 +1458,7 @@ pub mod testing {
 // These tests run the `postgres` Docker image, which doesn't work on Windows.
 #[cfg(all(test, not(target_os = "windows")))]
 mod test {
-    use std::time::Duration;
+    use std::{num::NonZeroUsize, time::Duration};
 
     use committable::{Commitment, CommitmentBoundsArkless, Committable};
     use hotshot::traits::BlockPayload;
@@ -1335,13 +1485,27 @@ mod test {
 
     use super::{testing::TmpDb, *};
     use crate::{
-        availability::{LeafQueryData, QueryableHeader},
-        data_source::storage::{pruning::PrunedHeightStorage, UpdateAvailabilityStorage},
+        availability::{
+            BlockId, BlockQueryData, LeafQueryData, PayloadQueryData, QueryableHeader,
+            VidCommonQueryData,
+        },
+        data_source::storage::{
+            blob::{BlobKey, MemoryObjectStore, ObjectStoreBlobStore},
+            pruning::PrunedHeightStorage,
+            AvailabilityStorage, ExplorerStorage, NodeStorage, UpdateAvailabilityStorage,
+        },
+        explorer::{
+            GetNamespaceDetailError, GetNamespaceDetailRequest, GetNamespaceSummariesRequest,
+            NamespaceOrder, NamespaceSummary,
+        },
         merklized_state::{MerklizedState, UpdateStateData},
         testing::{
-            mocks::{MockHeader, MockMerkleTree, MockPayload, MockTypes, MockVersions},
+            mocks::{
+                mock_transaction, MockHeader, MockMerkleTree, MockPayload, MockTypes, MockVersions,
+            },
             setup_test,
         },
+        types::HeightIndexed,
     };
 
     #[tokio::test(flavor = "multi_thread")]
@@ -1717,6 +1881,256 @@ mod test {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_namespace_retention_pruning() {
+        setup_test();
+
+        let db = TmpDb::init().await;
+        let mut storage = SqlStorage::connect(db.config()).await.unwrap();
+
+        // Blocks 0-3 are old, blocks 4 and 5 are recent. The first byte of each mock transaction is
+        // its namespace.
+        let now = Utc::now().timestamp() as u64;
+        let namespaces: [&[u8]; 6] = [&[1], &[1], &[1, 2], &[3], &[1], &[2]];
+        let mut leaf = LeafQueryData::<MockTypes>::genesis::<TestVersions>(
+            &TestValidatedState::default(),
+            &TestInstanceState::default(),
+        )
+        .await;
+        let mut blocks = vec![];
+        for (i, nss) in namespaces.into_iter().enumerate() {
+            leaf.leaf.block_header_mut().block_number = i as u64;
+            leaf.leaf.block_header_mut().timestamp = if i < 4 { now - 1000 } else { now };
+            let payload = MockPayload {
+                transactions: nss
+                    .iter()
+                    .map(|ns_id| mock_transaction(vec![*ns_id, i as u8]))
+                    .collect(),
+            };
+            let block = BlockQueryData::new(leaf.header().clone(), payload);
+
+            let mut tx = storage.write().await.unwrap();
+            tx.insert_leaf(leaf.clone()).await.unwrap();
+            tx.insert_block(block.clone()).await.unwrap();
+            tx.commit().await.unwrap();
+            blocks.push(block);
+        }
+
+        // Drop namespace 1 after 100 seconds and keep namespace 2 forever. Namespace 3 has no rule.
+        storage.set_pruning_config(
+            PrunerCfg::new()
+                .with_namespace_retention(1, Some(Duration::from_secs(100)))
+                .with_namespace_retention(2, None),
+        );
+        let mut pruner = Default::default();
+        while storage.prune(&mut pruner).await.unwrap().is_some() {}
+
+        // Only the old transactions in namespace 1 are deleted.
+        let mut tx = storage.read().await.unwrap();
+        let transactions = query_as::<(i64, Option<i64>)>(
+            "SELECT block_height, ns_id FROM transactions ORDER BY block_height, ns_id",
+        )
+        .fetch_all(tx.as_mut())
+        .await
+        .unwrap();
+        assert_eq!(
+            transactions,
+            [(2, Some(2)), (3, Some(3)), (4, Some(1)), (5, Some(2))]
+        );
+
+        // Blocks 0 and 1 have lost their payloads, since they have no other namespaces. These gaps
+        // are reported separately from missing data.
+        assert_eq!(
+            AvailabilityStorage::<MockTypes>::get_pruned_payloads(&mut tx, 0..6)
+                .await
+                .unwrap(),
+            [0, 1]
+        );
+        tx.get_block(BlockId::<MockTypes>::Number(0))
+            .await
+            .unwrap_err();
+        tx.get_header(BlockId::<MockTypes>::Number(0))
+            .await
+            .unwrap();
+        for block in &blocks[2..] {
+            assert_eq!(
+                tx.get_block(BlockId::<MockTypes>::Number(block.height() as usize))
+                    .await
+                    .unwrap(),
+                *block
+            );
+        }
+        let sync_status = NodeStorage::<MockTypes>::sync_status(&mut tx)
+            .await
+            .unwrap();
+        assert_eq!(sync_status.missing_blocks, 0);
+        assert_eq!(sync_status.pruned_payloads, 2);
+        assert!(sync_status.is_fully_synced());
+        drop(tx);
+
+        // If the pruned block is fetched again, it is not restored.
+        let mut tx = storage.write().await.unwrap();
+        tx.insert_block(blocks[0].clone()).await.unwrap();
+        tx.commit().await.unwrap();
+        let mut tx = storage.read().await.unwrap();
+        tx.get_block(BlockId::<MockTypes>::Number(0))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            AvailabilityStorage::<MockTypes>::get_pruned_payloads(&mut tx, ..)
+                .await
+                .unwrap(),
+            [0, 1]
+        );
+        drop(tx);
+
+        // Global pruning cannot delete blocks with data that a namespace rule retains, so it stops
+        // below block 2, the first block with data in namespace 2.
+        storage.set_pruning_config(
+            PrunerCfg::new()
+                .with_target_retention(Duration::from_secs(10))
+                .with_namespace_retention(1, Some(Duration::from_secs(100)))
+                .with_namespace_retention(2, None),
+        );
+        let mut pruner = Default::default();
+        while storage.prune(&mut pruner).await.unwrap().is_some() {}
+        assert_eq!(storage.get_minimum_height().await.unwrap(), Some(2));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_namespace_analytics() {
+        setup_test();
+
+        let db = TmpDb::init().await;
+        let storage = SqlStorage::connect(db.config()).await.unwrap();
+
+        // Blocks 0-3 are old, blocks 4 and 5 are recent. The first byte of each mock transaction is
+        // its namespace.
+        let now = Utc::now().timestamp() as u64;
+        let namespaces: [&[u8]; 6] = [&[1], &[1], &[1, 2, 2], &[3], &[1], &[2]];
+        let mut leaf = LeafQueryData::<MockTypes>::genesis::<TestVersions>(
+            &TestValidatedState::default(),
+            &TestInstanceState::default(),
+        )
+        .await;
+        for (i, nss) in namespaces.into_iter().enumerate() {
+            leaf.leaf.block_header_mut().block_number = i as u64;
+            leaf.leaf.block_header_mut().timestamp = if i < 4 { now - 1000 } else { now };
+            let payload = MockPayload {
+                transactions: nss
+                    .iter()
+                    .enumerate()
+                    .map(|(j, ns_id)| mock_transaction(vec![*ns_id, i as u8, j as u8]))
+                    .collect(),
+            };
+            let block = BlockQueryData::new(leaf.header().clone(), payload);
+
+            let mut tx = storage.write().await.unwrap();
+            tx.insert_leaf(leaf.clone()).await.unwrap();
+            tx.insert_block(block).await.unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        let mut tx = storage.read().await.unwrap();
+        let summaries = |window, limit, order| GetNamespaceSummariesRequest {
+            window,
+            limit: NonZeroUsize::new(limit).unwrap(),
+            order,
+        };
+
+        // Only the recent blocks are in a short window.
+        assert_eq!(
+            ExplorerStorage::<MockTypes>::get_namespace_summaries(
+                &mut tx,
+                summaries(100, 10, NamespaceOrder::Transactions)
+            )
+            .await
+            .unwrap(),
+            [
+                NamespaceSummary {
+                    namespace: 1,
+                    blocks: 1,
+                    transactions: 1,
+                    bytes: 3,
+                },
+                NamespaceSummary {
+                    namespace: 2,
+                    blocks: 1,
+                    transactions: 1,
+                    bytes: 3,
+                },
+            ]
+        );
+
+        // Over a longer window, every namespace is included.
+        let all = ExplorerStorage::<MockTypes>::get_namespace_summaries(
+            &mut tx,
+            summaries(2000, 10, NamespaceOrder::Bytes),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            all.iter()
+                .map(|ns| (ns.namespace, ns.blocks, ns.transactions, ns.bytes))
+                .collect::<Vec<_>>(),
+            [(1, 4, 4, 12), (2, 2, 3, 9), (3, 1, 1, 3)]
+        );
+        assert_eq!(
+            ExplorerStorage::<MockTypes>::get_namespace_summaries(
+                &mut tx,
+                summaries(2000, 1, NamespaceOrder::Transactions)
+            )
+            .await
+            .unwrap(),
+            all[..1]
+        );
+
+        // The time series splits the window into an old and a recent bucket.
+        let detail = ExplorerStorage::<MockTypes>::get_namespace_detail(
+            &mut tx,
+            GetNamespaceDetailRequest {
+                namespace: 2,
+                window: 2000,
+                buckets: NonZeroUsize::new(2).unwrap(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(detail.first_height, 2);
+        assert_eq!(detail.last_height, 5);
+        assert_eq!(
+            detail
+                .series
+                .iter()
+                .map(|bucket| (
+                    bucket.blocks,
+                    bucket.namespace_blocks,
+                    bucket.transactions,
+                    bucket.bytes
+                ))
+                .collect::<Vec<_>>(),
+            [(4, 1, 2, 6), (2, 1, 1, 3)]
+        );
+        assert_eq!(detail.series[0].transactions_per_second, 2.0 / 1000.0);
+        assert_eq!(detail.series[0].bytes_per_block, 6.0);
+        assert_eq!(detail.series[0].end, detail.series[1].start);
+
+        let err = ExplorerStorage::<MockTypes>::get_namespace_detail(
+            &mut tx,
+            GetNamespaceDetailRequest {
+                namespace: 4,
+                window: 2000,
+                buckets: NonZeroUsize::new(2).unwrap(),
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            matches!(err, GetNamespaceDetailError::NamespaceNotFound(_)),
+            "{err:#?}"
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_types_migration() {
         setup_test();
@@ -1877,4 +2291,95 @@ mod test {
         assert_eq!(leaf_count as u64, num_rows, "not all leaves migrated");
         assert_eq!(vid_count as u64, num_rows, "not all vid migrated");
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_blob_store() {
+        setup_test();
+
+        let db = TmpDb::init().await;
+        let blob_store = Arc::new(ObjectStoreBlobStore::new(MemoryObjectStore::default(), ""));
+        let storage = SqlStorage::connect(db.config().blob_store(blob_store))
+            .await
+            .unwrap();
+
+        let leaf = LeafQueryData::<MockTypes>::genesis::<TestVersions>(
+            &TestValidatedState::default(),
+            &TestInstanceState::default(),
+        )
+        .await;
+        let block = BlockQueryData::<MockTypes>::genesis::<TestVersions>(
+            &TestValidatedState::default(),
+            &TestInstanceState::default(),
+        )
+        .await;
+        let common = VidCommonQueryData::<MockTypes>::genesis::<TestVersions>(
+            &TestValidatedState::default(),
+            &TestInstanceState::default(),
+        )
+        .await;
+
+        let mut tx = storage.write().await.unwrap();
+        tx.insert_leaf(leaf).await.unwrap();
+        tx.insert_block(block.clone()).await.unwrap();
+        tx.insert_vid(common.clone(), None).await.unwrap();
+        tx.commit().await.unwrap();
+
+        // The database only holds references to the payload and VID common data.
+        let mut tx = storage.read().await.unwrap();
+        let (data, data_blob) =
+            query_as::<(Vec<u8>, Option<String>)>("SELECT data, data_blob FROM payload")
+                .fetch_one(tx.as_mut())
+                .await
+                .unwrap();
+        assert!(data.is_empty());
+        assert_eq!(
+            data_blob.unwrap(),
+            BlobKey::of(&block.payload().encode()).to_string()
+        );
+        let (common_data, common_blob) =
+            query_as::<(Vec<u8>, Option<String>)>("SELECT common, common_blob FROM vid2")
+                .fetch_one(tx.as_mut())
+                .await
+                .unwrap();
+        assert!(common_data.is_empty());
+        assert_eq!(
+            common_blob.unwrap(),
+            BlobKey::of(&bincode::serialize(common.common()).unwrap()).to_string()
+        );
+
+        // The objects are loaded from the blob store.
+        assert_eq!(
+            tx.get_block(BlockId::<MockTypes>::Number(0)).await.unwrap(),
+            block
+        );
+        assert_eq!(
+            tx.get_payload(BlockId::<MockTypes>::Number(0))
+                .await
+                .unwrap(),
+            PayloadQueryData::from(block.clone())
+        );
+        assert_eq!(
+            tx.get_vid_common(BlockId::<MockTypes>::Number(0))
+                .await
+                .unwrap(),
+            common
+        );
+        assert_eq!(
+            AvailabilityStorage::<MockTypes>::get_block_range(&mut tx, 0..1)
+                .await
+                .unwrap()
+                .into_iter()
+                .collect::<QueryResult<Vec<_>>>()
+                .unwrap(),
+            [block]
+        );
+        drop(tx);
+
+        // Without the blob store, the objects cannot be loaded.
+        let storage = SqlStorage::connect(db.config()).await.unwrap();
+        let mut tx = storage.read().await.unwrap();
+        tx.get_block(BlockId::<MockTypes>::Number(0))
+            .await
+            .unwrap_err();
+    }
 }
@@ -35,7 +35,10 @@ use crate::{
         BlockId, BlockQueryData, LeafQueryData, PayloadQueryData, QueryablePayload,
         StateCertQueryData, VidCommonQueryData,
     },
-    data_source::storage::{PayloadMetadata, VidCommonMetadata},
+    data_source::storage::{
+        blob::{self, BlobStore},
+        PayloadMetadata, VidCommonMetadata,
+    },
     Header, Leaf2, Payload, QueryError, QueryResult,
 };
 
@@ -183,8 +186,7 @@ where
     }
 }
 
-const BLOCK_COLUMNS: &str =
-    "h.hash AS hash, h.data AS header_data, p.size AS payload_size, p.data AS payload_data";
+const BLOCK_COLUMNS: &str = "h.hash AS hash, h.data AS header_data, p.size AS payload_size, p.data AS payload_data, p.data_blob AS payload_blob";
 
 impl<'r, Types> FromRow<'r, <Db as Database>::Row> for BlockQueryData<Types>
 where
@@ -192,32 +194,73 @@ where
     Payload<Types>: QueryablePayload<Types>,
 {
     fn from_row(row: &'r <Db as Database>::Row) -> sqlx::Result<Self> {
-        // First, check if we have the payload for this block yet.
-        let size: Option<i32> = row.try_get("payload_size")?;
-        let payload_data: Option<Vec<u8>> = row.try_get("payload_data")?;
-        let (size, payload_data) = size.zip(payload_data).ok_or(sqlx::Error::RowNotFound)?;
-        let size = size as u64;
-
-        // Reconstruct the full header.
-        let header_data = row.try_get("header_data")?;
-        let header: Header<Types> =
-            serde_json::from_value(header_data).decode_error("malformed header")?;
+        // Payloads in a blob store can only be loaded asynchronously, using `load_block`.
+        if row.try_get::<Option<String>, _>("payload_blob")?.is_some() {
+            return Err(sqlx::Error::Decode(
+                "payload is stored in a blob store".into(),
+            ));
+        }
+        decode_block(row, row.try_get("payload_data")?)
+    }
+}
 
-        // Reconstruct the full block payload.
-        let payload = Payload::<Types>::from_bytes(&payload_data, header.metadata());
+/// Load a block from a row selected with [`BLOCK_COLUMNS`].
+///
+/// Unlike the [`FromRow`] implementation, this can load payloads which are stored in `blob_store`
+/// rather than inline in the database.
+pub(super) async fn load_block<Types>(
+    blob_store: Option<&dyn BlobStore>,
+    row: &<Db as Database>::Row,
+) -> QueryResult<BlockQueryData<Types>>
+where
+    Types: NodeType,
+    Payload<Types>: QueryablePayload<Types>,
+{
+    let payload_data = match row.try_get::<Option<Vec<u8>>, _>("payload_data")? {
+        Some(data) => Some(
+            blob::resolve(blob_store, data, row.try_get("payload_blob")?)
+                .await
+                .map_err(|err| QueryError::Error {
+                    message: format!("failed to load payload: {err:#}"),
+                })?,
+        ),
+        None => None,
+    };
+    Ok(decode_block(row, payload_data)?)
+}
 
-        // Reconstruct the query data by adding metadata.
-        let hash: String = row.try_get("hash")?;
-        let hash = hash.parse().decode_error("malformed block hash")?;
+fn decode_block<Types>(
+    row: &<Db as Database>::Row,
+    payload_data: Option<Vec<u8>>,
+) -> sqlx::Result<BlockQueryData<Types>>
+where
+    Types: NodeType,
+    Payload<Types>: QueryablePayload<Types>,
+{
+    // First, check if we have the payload for this block yet.
+    let size: Option<i32> = row.try_get("payload_size")?;
+    let (size, payload_data) = size.zip(payload_data).ok_or(sqlx::Error::RowNotFound)?;
+    let size = size as u64;
 
-        Ok(Self {
-            num_transactions: payload.len(header.metadata()) as u64,
-            header,
-            payload,
-            size,
-            hash,
-        })
-    }
+    // Reconstruct the full header.
+    let header_data = row.try_get("header_data")?;
+    let header: Header<Types> =
+        serde_json::from_value(header_data).decode_error("malformed header")?;
+
+    // Reconstruct the full block payload.
+    let payload = Payload::<Types>::from_bytes(&payload_data, header.metadata());
+
+    // Reconstruct the query data by adding metadata.
+    let hash: String = row.try_get("hash")?;
+    let hash = hash.parse().decode_error("malformed block hash")?;
+
+    Ok(BlockQueryData {
+        num_transactions: payload.len(header.metadata()) as u64,
+        header,
+        payload,
+        size,
+        hash,
+    })
 }
 
 const PAYLOAD_COLUMNS: &str = BLOCK_COLUMNS;
@@ -232,6 +275,20 @@ where
     }
 }
 
+/// Load a payload from a row selected with [`PAYLOAD_COLUMNS`].
+pub(super) async fn load_payload<Types>(
+    blob_store: Option<&dyn BlobStore>,
+    row: &<Db as Database>::Row,
+) -> QueryResult<PayloadQueryData<Types>>
+where
+    Types: NodeType,
+    Payload<Types>: QueryablePayload<Types>,
+{
+    load_block(blob_store, row)
+        .await
+        .map(PayloadQueryData::from)
+}
+
 const PAYLOAD_METADATA_COLUMNS: &str =
     "h.height AS height, h.hash AS hash, h.payload_hash AS payload_hash, p.size AS payload_size, p.num_transactions AS num_transactions";
 
@@ -260,7 +317,7 @@ where
     }
 }
 
-const VID_COMMON_COLUMNS: &str = "h.height AS height, h.hash AS block_hash, h.payload_hash AS payload_hash, v.common AS common_data";
+const VID_COMMON_COLUMNS: &str = "h.height AS height, h.hash AS block_hash, h.payload_hash AS payload_hash, v.common AS common_data, v.common_blob AS common_blob";
 
 impl<'r, Types> FromRow<'r, <Db as Database>::Row> for VidCommonQueryData<Types>
 where
@@ -268,25 +325,64 @@ where
     Payload<Types>: QueryablePayload<Types>,
 {
     fn from_row(row: &'r <Db as Database>::Row) -> sqlx::Result<Self> {
-        let height = row.try_get::<i64, _>("height")? as u64;
-        let block_hash: String = row.try_get("block_hash")?;
-        let block_hash = block_hash.parse().decode_error("malformed block hash")?;
-        let payload_hash: String = row.try_get("payload_hash")?;
-        let payload_hash = payload_hash
-            .parse()
-            .decode_error("malformed payload hash")?;
-        let common_data: Vec<u8> = row.try_get("common_data")?;
-        let common =
-            bincode::deserialize(&common_data).decode_error("malformed VID common data")?;
-        Ok(Self {
-            height,
-            block_hash,
-            payload_hash,
-            common,
-        })
+        // VID common data in a blob store can only be loaded asynchronously, using
+        // `load_vid_common`.
+        if row.try_get::<Option<String>, _>("common_blob")?.is_some() {
+            return Err(sqlx::Error::Decode(
+                "VID common data is stored in a blob store".into(),
+            ));
+        }
+        decode_vid_common(row, row.try_get("common_data")?)
     }
 }
 
+/// Load VID common data from a row selected with [`VID_COMMON_COLUMNS`].
+///
+/// Unlike the [`FromRow`] implementation, this can load data which is stored in `blob_store` rather
+/// than inline in the database.
+pub(super) async fn load_vid_common<Types>(
+    blob_store: Option<&dyn BlobStore>,
+    row: &<Db as Database>::Row,
+) -> QueryResult<VidCommonQueryData<Types>>
+where
+    Types: NodeType,
+    Payload<Types>: QueryablePayload<Types>,
+{
+    let common_data = blob::resolve(
+        blob_store,
+        row.try_get("common_data")?,
+        row.try_get("common_blob")?,
+    )
+    .await
+    .map_err(|err| QueryError::Error {
+        message: format!("failed to load VID common data: {err:#}"),
+    })?;
+    Ok(decode_vid_common(row, common_data)?)
+}
+
+fn decode_vid_common<Types>(
+    row: &<Db as Database>::Row,
+    common_data: Vec<u8>,
+) -> sqlx::Result<VidCommonQueryData<Types>>
+where
+    Types: NodeType,
+{
+    let height = row.try_get::<i64, _>("height")? as u64;
+    let block_hash: String = row.try_get("block_hash")?;
+    let block_hash = block_hash.parse().decode_error("malformed block hash")?;
+    let payload_hash: String = row.try_get("payload_hash")?;
+    let payload_hash = payload_hash
+        .parse()
+        .decode_error("malformed payload hash")?;
+    let common = bincode::deserialize(&common_data).decode_error("malformed VID common data")?;
+    Ok(VidCommonQueryData {
+        height,
+        block_hash,
+        payload_hash,
+        common,
+    })
+}
+
 const VID_COMMON_METADATA_COLUMNS: &str =
     "h.height AS height, h.hash AS block_hash, h.payload_hash AS payload_hash";
 
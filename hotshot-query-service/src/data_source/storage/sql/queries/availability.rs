@@ -23,8 +23,9 @@ use vec1::Vec1;
 
 use super::{
     super::transaction::{query, Transaction, TransactionMode},
-    QueryBuilder, BLOCK_COLUMNS, LEAF_COLUMNS, PAYLOAD_COLUMNS, PAYLOAD_METADATA_COLUMNS,
-    STATE_CERT_COLUMNS, VID_COMMON_COLUMNS, VID_COMMON_METADATA_COLUMNS,
+    load_block, load_payload, load_vid_common, QueryBuilder, BLOCK_COLUMNS, LEAF_COLUMNS,
+    PAYLOAD_COLUMNS, PAYLOAD_METADATA_COLUMNS, STATE_CERT_COLUMNS, VID_COMMON_COLUMNS,
+    VID_COMMON_METADATA_COLUMNS,
 };
 use crate::{
     availability::{
@@ -94,7 +95,7 @@ where
               LIMIT 1"
         );
         let row = query.query(&sql).fetch_one(self.as_mut()).await?;
-        let block = BlockQueryData::from_row(&row)?;
+        let block = load_block(self.blob_store(), &row).await?;
         Ok(block)
     }
 
@@ -116,7 +117,7 @@ where
               LIMIT 1"
         );
         let row = query.query(&sql).fetch_one(self.as_mut()).await?;
-        let payload = PayloadQueryData::from_row(&row)?;
+        let payload = load_payload(self.blob_store(), &row).await?;
         Ok(payload)
     }
 
@@ -162,7 +163,7 @@ where
               LIMIT 1"
         );
         let row = query.query(&sql).fetch_one(self.as_mut()).await?;
-        let common = VidCommonQueryData::from_row(&row)?;
+        let common = load_vid_common(self.blob_store(), &row).await?;
         Ok(common)
     }
 
@@ -243,11 +244,12 @@ where
               {where_clause}
               ORDER BY h.height"
         );
+        let store = self.blob_store.clone();
+        let blob_store = store.as_deref();
         Ok(query
             .query(&sql)
             .fetch(self.as_mut())
-            .map(|res| BlockQueryData::from_row(&res?))
-            .map_err(QueryError::from)
+            .then(move |res| async move { load_block(blob_store, &res?).await })
             .collect()
             .await)
     }
@@ -293,11 +295,12 @@ where
               {where_clause}
               ORDER BY h.height"
         );
+        let store = self.blob_store.clone();
+        let blob_store = store.as_deref();
         Ok(query
             .query(&sql)
             .fetch(self.as_mut())
-            .map(|res| PayloadQueryData::from_row(&res?))
-            .map_err(QueryError::from)
+            .then(move |res| async move { load_payload(blob_store, &res?).await })
             .collect()
             .await)
     }
@@ -343,11 +346,12 @@ where
               {where_clause}
               ORDER BY h.height"
         );
+        let store = self.blob_store.clone();
+        let blob_store = store.as_deref();
         Ok(query
             .query(&sql)
             .fetch(self.as_mut())
-            .map(|res| VidCommonQueryData::from_row(&res?))
-            .map_err(QueryError::from)
+            .then(move |res| async move { load_vid_common(blob_store, &res?).await })
             .collect()
             .await)
     }
@@ -398,7 +402,7 @@ where
         let row = query.query(&sql).fetch_one(self.as_mut()).await?;
 
         // Extract the block.
-        let block = BlockQueryData::from_row(&row)?;
+        let block = load_block(self.blob_store(), &row).await?;
 
         TransactionQueryData::with_hash(&block, hash).context(ErrorSnafu {
             message: format!(
@@ -428,4 +432,25 @@ where
         .await?;
         Ok(StateCertQueryData::from_row(&row)?)
     }
+
+    async fn get_pruned_payloads<R>(&mut self, range: R) -> QueryResult<Vec<u64>>
+    where
+        R: RangeBounds<usize> + Send + 'static,
+    {
+        let mut query = QueryBuilder::default();
+        let where_clause = query.bounds_to_where_clause(range, "height")?;
+        let sql = format!(
+            "SELECT height FROM payload {where_clause} {} retention_pruned ORDER BY height",
+            if where_clause.is_empty() {
+                "WHERE"
+            } else {
+                "AND"
+            }
+        );
+        let heights = query
+            .query_as::<(i64,)>(&sql)
+            .fetch_all(self.as_mut())
+            .await?;
+        Ok(heights.into_iter().map(|(height,)| height as u64).collect())
+    }
 }
@@ -19,16 +19,16 @@ use committable::{Commitment, Committable};
 use futures::stream::{self, StreamExt, TryStreamExt};
 use hotshot_types::traits::node_implementation::NodeType;
 use itertools::Itertools;
-use sqlx::{types::Json, FromRow, Row};
+use sqlx::{types::Json, Row};
 use tagged_base64::{Tagged, TaggedBase64};
 
 use super::{
-    super::transaction::{query, Transaction, TransactionMode},
-    Database, Db, DecodeError, BLOCK_COLUMNS,
+    super::transaction::{query, query_as, Transaction, TransactionMode},
+    load_block, Database, Db, DecodeError, BLOCK_COLUMNS,
 };
 use crate::{
-    availability::{BlockQueryData, QueryableHeader, QueryablePayload, TransactionIndex},
-    data_source::storage::{ExplorerStorage, NodeStorage},
+    availability::{QueryableHeader, QueryablePayload, TransactionIndex},
+    data_source::storage::{blob::BlobStore, ExplorerStorage, NodeStorage},
     explorer::{
         self,
         errors::{self, NotFound},
@@ -36,10 +36,12 @@ use crate::{
         traits::ExplorerHeader,
         BalanceAmount, BlockDetail, BlockIdentifier, BlockRange, BlockSummary, ExplorerHistograms,
         ExplorerSummary, GenesisOverview, GetBlockDetailError, GetBlockSummariesError,
-        GetBlockSummariesRequest, GetExplorerSummaryError, GetSearchResultsError,
-        GetTransactionDetailError, GetTransactionSummariesError, GetTransactionSummariesRequest,
-        MonetaryValue, SearchResult, TransactionIdentifier, TransactionRange, TransactionSummary,
-        TransactionSummaryFilter,
+        GetBlockSummariesRequest, GetExplorerSummaryError, GetNamespaceDetailError,
+        GetNamespaceDetailRequest, GetNamespaceSummariesError, GetNamespaceSummariesRequest,
+        GetSearchResultsError, GetTransactionDetailError, GetTransactionSummariesError,
+        GetTransactionSummariesRequest, MonetaryValue, NamespaceDetail, NamespaceOrder,
+        NamespaceStatsBucket, NamespaceSummary, SearchResult, Timestamp, TransactionIdentifier,
+        TransactionRange, TransactionSummary, TransactionSummaryFilter,
     },
     Header, Payload, QueryError, QueryResult, Transaction as HotshotTransaction,
 };
@@ -74,37 +76,55 @@ impl From<sqlx::Error> for GetBlockSummariesError {
     }
 }
 
+impl From<sqlx::Error> for GetNamespaceSummariesError {
+    fn from(err: sqlx::Error) -> Self {
+        Self::from(QueryError::from(err))
+    }
+}
+
+impl From<sqlx::Error> for GetNamespaceDetailError {
+    fn from(err: sqlx::Error) -> Self {
+        Self::from(QueryError::from(err))
+    }
+}
+
 impl From<sqlx::Error> for GetSearchResultsError {
     fn from(err: sqlx::Error) -> Self {
         Self::from(QueryError::from(err))
     }
 }
 
-impl<'r, Types> FromRow<'r, <Db as Database>::Row> for BlockSummary<Types>
+/// Load a block summary from a row selected with [`BLOCK_COLUMNS`].
+async fn load_block_summary<Types>(
+    blob_store: Option<&dyn BlobStore>,
+    row: &<Db as Database>::Row,
+) -> QueryResult<BlockSummary<Types>>
 where
     Types: NodeType,
     Header<Types>: QueryableHeader<Types> + ExplorerHeader<Types>,
     Payload<Types>: QueryablePayload<Types>,
 {
-    fn from_row(row: &'r <Db as Database>::Row) -> sqlx::Result<Self> {
-        BlockQueryData::<Types>::from_row(row)?
-            .try_into()
-            .decode_error("malformed block summary")
-    }
+    Ok(load_block::<Types>(blob_store, row)
+        .await?
+        .try_into()
+        .decode_error("malformed block summary")?)
 }
 
-impl<'r, Types> FromRow<'r, <Db as Database>::Row> for BlockDetail<Types>
+/// Load a block detail from a row selected with [`BLOCK_COLUMNS`].
+async fn load_block_detail<Types>(
+    blob_store: Option<&dyn BlobStore>,
+    row: &<Db as Database>::Row,
+) -> QueryResult<BlockDetail<Types>>
 where
     Types: NodeType,
     Header<Types>: QueryableHeader<Types> + ExplorerHeader<Types>,
     Payload<Types>: QueryablePayload<Types>,
     BalanceAmount<Types>: Into<MonetaryValue>,
 {
-    fn from_row(row: &'r <Db as Database>::Row) -> sqlx::Result<Self> {
-        BlockQueryData::<Types>::from_row(row)?
-            .try_into()
-            .decode_error("malformed block detail")
-    }
+    Ok(load_block::<Types>(blob_store, row)
+        .await?
+        .try_into()
+        .decode_error("malformed block detail")?)
 }
 
 lazy_static::lazy_static! {
@@ -265,6 +285,180 @@ const EXPLORER_SUMMARY_NUM_BLOCKS: usize = 10;
 /// to return in our explorer summary.
 const EXPLORER_SUMMARY_NUM_TRANSACTIONS: usize = 10;
 
+/// [EXPLORER_SUMMARY_NUM_NAMESPACES] is the number of most active namespaces we
+/// want to return in our explorer summary.
+const EXPLORER_SUMMARY_NUM_NAMESPACES: usize = 10;
+
+/// [EXPLORER_SUMMARY_NAMESPACE_WINDOW] is the window, in seconds, over which we
+/// rank the most active namespaces in our explorer summary.
+const EXPLORER_SUMMARY_NAMESPACE_WINDOW: u64 = 24 * 60 * 60;
+
+impl<Mode: TransactionMode> Transaction<Mode> {
+    /// Get the height of the first block in the window of the last `window`
+    /// seconds, along with the timestamp at which the window starts.
+    ///
+    /// Windows end just after the timestamp of the latest block, so that the
+    /// latest block is always included. Returns [`None`] if there are no blocks.
+    async fn namespace_window(&mut self, window: u64) -> sqlx::Result<Option<(i64, i64)>> {
+        let Some((latest_timestamp,)) =
+            query_as::<(i64,)>("SELECT timestamp FROM header ORDER BY height DESC LIMIT 1")
+                .fetch_optional(self.as_mut())
+                .await?
+        else {
+            return Ok(None);
+        };
+        let start = latest_timestamp
+            .saturating_add(1)
+            .saturating_sub(window as i64);
+        let (height,) =
+            query_as::<(Option<i64>,)>("SELECT min(height) FROM header WHERE timestamp >= $1")
+                .bind(start)
+                .fetch_one(self.as_mut())
+                .await?;
+        Ok(height.map(|height| (height, start)))
+    }
+
+    async fn namespace_summaries(
+        &mut self,
+        request: GetNamespaceSummariesRequest,
+    ) -> Result<Vec<NamespaceSummary>, GetNamespaceSummariesError> {
+        if request.window == 0 {
+            return Err(GetNamespaceSummariesError::InvalidQuery(
+                errors::BadQuery {},
+            ));
+        }
+        let Some((from, _)) = self.namespace_window(request.window).await? else {
+            return Ok(vec![]);
+        };
+
+        let order = match request.order {
+            NamespaceOrder::Transactions => "total_transactions",
+            NamespaceOrder::Bytes => "total_size",
+        };
+        let sql = format!(
+            "SELECT ns_id, count(*) AS blocks,
+                    CAST(sum(num_transactions) AS BIGINT) AS total_transactions,
+                    CAST(coalesce(sum(size), 0) AS BIGINT) AS total_size
+               FROM block_namespace
+              WHERE height >= $1
+              GROUP BY ns_id
+              ORDER BY {order} DESC, ns_id
+              LIMIT $2"
+        );
+        let rows = query_as::<(i64, i64, i64, i64)>(&sql)
+            .bind(from)
+            .bind(request.limit.get() as i64)
+            .fetch_all(self.as_mut())
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(
+                |(namespace, blocks, transactions, bytes)| NamespaceSummary {
+                    namespace: namespace as u64,
+                    blocks: blocks as u64,
+                    transactions: transactions as u64,
+                    bytes: bytes as u64,
+                },
+            )
+            .collect())
+    }
+
+    async fn namespace_detail(
+        &mut self,
+        request: GetNamespaceDetailRequest,
+    ) -> Result<NamespaceDetail, GetNamespaceDetailError> {
+        if request.window == 0 {
+            return Err(GetNamespaceDetailError::InvalidQuery(errors::BadQuery {}));
+        }
+
+        let (first_height, last_height) = query_as::<(Option<i64>, Option<i64>)>(
+            "SELECT min(height), max(height) FROM block_namespace WHERE ns_id = $1",
+        )
+        .bind(request.namespace as i64)
+        .fetch_one(self.as_mut())
+        .await?;
+        let (Some(first_height), Some(last_height)) = (first_height, last_height) else {
+            return Err(GetNamespaceDetailError::NamespaceNotFound(NotFound {
+                key: request.namespace.to_string(),
+            }));
+        };
+        let Some((from, start)) = self.namespace_window(request.window).await? else {
+            return Err(GetNamespaceDetailError::NamespaceNotFound(NotFound {
+                key: request.namespace.to_string(),
+            }));
+        };
+
+        // Aggregate every block in the window into its bucket, including blocks that don't
+        // contain the namespace, so we can tell how often the namespace appears.
+        let num_buckets = request.buckets.get() as i64;
+        let rows = query_as::<(i64, i64, i64, i64, i64)>(
+            "SELECT (h.timestamp - $1) * $2 / $3 AS bucket,
+                    count(*) AS blocks,
+                    count(b.ns_id) AS namespace_blocks,
+                    CAST(coalesce(sum(b.num_transactions), 0) AS BIGINT) AS total_transactions,
+                    CAST(coalesce(sum(b.size), 0) AS BIGINT) AS total_size
+               FROM header AS h
+               LEFT JOIN block_namespace AS b ON b.height = h.height AND b.ns_id = $4
+              WHERE h.height >= $5
+              GROUP BY bucket
+              ORDER BY bucket",
+        )
+        .bind(start)
+        .bind(num_buckets)
+        .bind(request.window as i64)
+        .bind(request.namespace as i64)
+        .bind(from)
+        .fetch_all(self.as_mut())
+        .await?;
+
+        let bucket_seconds = request.window as f64 / num_buckets as f64;
+        let bucket_time = |bucket: i64| -> Result<Timestamp, GetNamespaceDetailError> {
+            let seconds = start + bucket * request.window as i64 / num_buckets;
+            let time = time::OffsetDateTime::from_unix_timestamp(seconds).map_err(|err| {
+                QueryError::Error {
+                    message: format!("invalid timestamp {seconds}: {err}"),
+                }
+            })?;
+            Ok(Timestamp(time))
+        };
+        let mut rows = rows.into_iter().peekable();
+        let mut series = Vec::with_capacity(request.buckets.get());
+        for bucket in 0..num_buckets {
+            let (blocks, namespace_blocks, transactions, bytes) =
+                match rows.next_if(|(row_bucket, ..)| *row_bucket == bucket) {
+                    Some((_, blocks, namespace_blocks, transactions, bytes)) => (
+                        blocks as u64,
+                        namespace_blocks as u64,
+                        transactions as u64,
+                        bytes as u64,
+                    ),
+                    None => (0, 0, 0, 0),
+                };
+            series.push(NamespaceStatsBucket {
+                start: bucket_time(bucket)?,
+                end: bucket_time(bucket + 1)?,
+                blocks,
+                namespace_blocks,
+                transactions,
+                bytes,
+                transactions_per_second: transactions as f64 / bucket_seconds,
+                bytes_per_block: if namespace_blocks == 0 {
+                    0.0
+                } else {
+                    bytes as f64 / namespace_blocks as f64
+                },
+            });
+        }
+
+        Ok(NamespaceDetail {
+            namespace: request.namespace,
+            first_height: first_height as u64,
+            last_height: last_height as u64,
+            series,
+        })
+    }
+}
+
 #[async_trait]
 impl<Mode, Types> ExplorerStorage<Types> for Transaction<Mode>
 where
@@ -293,8 +487,11 @@ where
                 .bind(request.num_blocks.get() as i64),
         };
 
+        let store = self.blob_store.clone();
+        let blob_store = store.as_deref();
         let row_stream = query_stmt.fetch(self.as_mut());
-        let result = row_stream.map(|row| BlockSummary::from_row(&row?));
+        let result =
+            row_stream.then(move |row| async move { load_block_summary(blob_store, &row?).await });
 
         Ok(result.try_collect().await?)
     }
@@ -314,7 +511,7 @@ where
         };
 
         let query_result = query_stmt.fetch_one(self.as_mut()).await?;
-        let block = BlockDetail::from_row(&query_result)?;
+        let block = load_block_detail(self.blob_store(), &query_result).await?;
 
         Ok(block)
     }
@@ -380,9 +577,11 @@ where
             },
         };
 
+        let store = self.blob_store.clone();
+        let blob_store = store.as_deref();
         let block_stream = query_stmt
             .fetch(self.as_mut())
-            .map(|row| BlockQueryData::from_row(&row?));
+            .then(move |row| async move { load_block::<Types>(blob_store, &row?).await });
 
         let transaction_summary_stream = block_stream.flat_map(|row| match row {
             Ok(block) => stream::iter(
@@ -401,7 +600,7 @@ where
                     .rev()
                     .collect::<Vec<QueryResult<TransactionSummary<Types>>>>(),
             ),
-            Err(err) => stream::iter(vec![Err(err.into())]),
+            Err(err) => stream::iter(vec![Err(err)]),
         });
 
         let transaction_summary_vec = transaction_summary_stream
@@ -441,7 +640,7 @@ where
         };
 
         let query_row = query_stmt.fetch_one(self.as_mut()).await?;
-        let block = BlockQueryData::<Types>::from_row(&query_row)?;
+        let block = load_block::<Types>(self.blob_store(), &query_row).await?;
 
         let txns = block.enumerate().map(|(_, txn)| txn).collect::<Vec<_>>();
 
@@ -565,11 +764,20 @@ where
             })
             .await?;
 
+        let most_active_namespaces = self
+            .namespace_summaries(GetNamespaceSummariesRequest {
+                window: EXPLORER_SUMMARY_NAMESPACE_WINDOW,
+                limit: NonZeroUsize::new(EXPLORER_SUMMARY_NUM_NAMESPACES).unwrap(),
+                order: NamespaceOrder::Transactions,
+            })
+            .await?;
+
         Ok(ExplorerSummary {
             genesis_overview,
             latest_block,
             latest_transactions,
             latest_blocks,
+            most_active_namespaces,
             histograms,
         })
     }
@@ -601,7 +809,7 @@ where
                 .fetch_one(self.as_mut())
                 .await?;
 
-            let block = BlockSummary::from_row(&row)?;
+            let block = load_block_summary(self.blob_store(), &row).await?;
 
             Ok(SearchResult {
                 blocks: vec![block],
@@ -617,12 +825,15 @@ where
                     ORDER BY h.height DESC
                     LIMIT 5"
             );
+            let store = self.blob_store.clone();
+            let blob_store = store.as_deref();
             let transactions_query_rows = query(transactions_query.as_str())
                 .bind(&search_query_string)
                 .fetch(self.as_mut());
             let transactions_query_result: Vec<TransactionSummary<Types>> = transactions_query_rows
-                .map(|row| -> Result<Vec<TransactionSummary<Types>>, QueryError>{
-                    let block = BlockQueryData::<Types>::from_row(&row?)?;
+                .then(move |row| async move { load_block::<Types>(blob_store, &row?).await })
+                .map(|block| -> Result<Vec<TransactionSummary<Types>>, QueryError>{
+                    let block = block?;
                     let transactions = block
                         .enumerate()
                         .enumerate()
@@ -647,4 +858,18 @@ where
             })
         }
     }
+
+    async fn get_namespace_summaries(
+        &mut self,
+        request: GetNamespaceSummariesRequest,
+    ) -> Result<Vec<NamespaceSummary>, GetNamespaceSummariesError> {
+        self.namespace_summaries(request).await
+    }
+
+    async fn get_namespace_detail(
+        &mut self,
+        request: GetNamespaceDetailRequest,
+    ) -> Result<NamespaceDetail, GetNamespaceDetailError> {
+        self.namespace_detail(request).await
+    }
 }
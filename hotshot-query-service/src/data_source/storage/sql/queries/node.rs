@@ -159,9 +159,13 @@ where
         // missing in that case _or_ if the row is present but share data is NULL. Thus, we also
         // need to select the total number of VID rows and the number of present VID rows with a
         // NULL share.
-        let sql = "SELECT l.max_height, l.total_leaves, p.null_payloads, v.total_vid, vn.null_vid, pruned_height FROM
+        //
+        // Payloads deleted by namespace retention rules are also NULL, but they are deliberate gaps
+        // rather than missing data, so we count them separately.
+        let sql = "SELECT l.max_height, l.total_leaves, p.null_payloads, rp.pruned_payloads, v.total_vid, vn.null_vid, pruned_height FROM
                 (SELECT max(leaf2.height) AS max_height, count(*) AS total_leaves FROM leaf2) AS l,
-                (SELECT count(*) AS null_payloads FROM payload WHERE data IS NULL) AS p,
+                (SELECT count(*) AS null_payloads FROM payload WHERE data IS NULL AND NOT retention_pruned) AS p,
+                (SELECT count(*) AS pruned_payloads FROM payload WHERE retention_pruned) AS rp,
                 (SELECT count(*) AS total_vid FROM vid2) AS v,
                 (SELECT count(*) AS null_vid FROM vid2 WHERE share IS NULL) AS vn,
                 (SELECT(SELECT last_height FROM pruned_height ORDER BY id DESC LIMIT 1) as pruned_height)
@@ -184,6 +188,7 @@ where
         };
         let total_leaves = row.get::<i64, _>("total_leaves") as usize;
         let null_payloads = row.get::<i64, _>("null_payloads") as usize;
+        let pruned_payloads = row.get::<i64, _>("pruned_payloads") as usize;
         let total_vid = row.get::<i64, _>("total_vid") as usize;
         let null_vid = row.get::<i64, _>("null_vid") as usize;
         let pruned_height = row
@@ -201,6 +206,7 @@ where
             missing_vid_common,
             missing_vid_shares,
             pruned_height,
+            pruned_payloads,
         })
     }
 
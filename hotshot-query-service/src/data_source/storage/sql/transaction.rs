@@ -21,6 +21,7 @@
 use std::{
     collections::{HashMap, HashSet},
     marker::PhantomData,
+    sync::Arc,
     time::Instant,
 };
 
@@ -60,7 +61,11 @@ use crate::{
         VidCommonQueryData,
     },
     data_source::{
-        storage::{pruning::PrunedHeightStorage, UpdateAvailabilityStorage},
+        storage::{
+            blob::{self, BlobStore},
+            pruning::PrunedHeightStorage,
+            UpdateAvailabilityStorage,
+        },
         update,
     },
     merklized_state::{MerklizedState, UpdateStateData},
@@ -227,14 +232,28 @@ pub struct Transaction<Mode> {
     #[deref_mut]
     inner: sqlx::Transaction<'static, Db>,
     metrics: TransactionMetricsGuard<Mode>,
+    pub(super) blob_store: Option<Arc<dyn BlobStore>>,
 }
 
 impl<Mode: TransactionMode> Transaction<Mode> {
-    pub(super) async fn new(pool: &Pool<Db>, metrics: PoolMetrics) -> anyhow::Result<Self> {
+    pub(super) async fn new(
+        pool: &Pool<Db>,
+        metrics: PoolMetrics,
+        blob_store: Option<Arc<dyn BlobStore>>,
+    ) -> anyhow::Result<Self> {
         let mut inner = pool.begin().await?;
         let metrics = TransactionMetricsGuard::begin(metrics);
         Mode::begin(inner.as_mut()).await?;
-        Ok(Self { inner, metrics })
+        Ok(Self {
+            inner,
+            metrics,
+            blob_store,
+        })
+    }
+
+    /// The blob store used for large objects, if any.
+    pub fn blob_store(&self) -> Option<&dyn BlobStore> {
+        self.blob_store.as_deref()
     }
 }
 
@@ -458,6 +477,89 @@ impl Transaction<Write> {
         )
         .await
     }
+
+    /// Delete data for namespace `ns_id` in blocks from `from` to `to` (inclusive) according to a
+    /// namespace retention rule.
+    ///
+    /// The transaction index entries for the namespace are deleted. The payload of any block left
+    /// with no indexed transactions is deleted as well, and marked as a retention gap so it is not
+    /// reported as missing. Headers and leaves are kept.
+    pub(super) async fn prune_namespace(
+        &mut self,
+        ns_id: u64,
+        from: u64,
+        to: u64,
+    ) -> anyhow::Result<()> {
+        self.execute(
+            query(
+                "DELETE FROM transactions
+                  WHERE ns_id = $1 AND block_height >= $2 AND block_height <= $3",
+            )
+            .bind(ns_id as i64)
+            .bind(from as i64)
+            .bind(to as i64),
+        )
+        .await?;
+
+        // The payload of a block is only deleted when every namespace in it has been pruned, which
+        // is exactly when none of its transactions remain indexed.
+        self.execute(
+            query(
+                "UPDATE payload SET data = NULL, data_blob = NULL, retention_pruned = true
+                  WHERE height IN (
+                      SELECT height FROM block_namespace
+                       WHERE ns_id = $1 AND height >= $2 AND height <= $3)
+                    AND NOT EXISTS (
+                      SELECT 1 FROM transactions AS t WHERE t.block_height = payload.height)",
+            )
+            .bind(ns_id as i64)
+            .bind(from as i64)
+            .bind(to as i64),
+        )
+        .await?;
+
+        self.upsert(
+            "namespace_pruned_height",
+            ["ns_id", "last_height"],
+            ["ns_id"],
+            [(ns_id as i64, to as i64)],
+        )
+        .await
+    }
+}
+
+impl<Mode: TransactionMode> Transaction<Mode> {
+    /// Load the height of the last block pruned by each namespace retention rule.
+    pub(super) async fn load_namespace_pruned_heights(
+        &mut self,
+    ) -> anyhow::Result<HashMap<u64, u64>> {
+        let rows = query_as::<(i64, i64)>("SELECT ns_id, last_height FROM namespace_pruned_height")
+            .fetch_all(self.as_mut())
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(ns_id, height)| (ns_id as u64, height as u64))
+            .collect())
+    }
+}
+
+/// Statistics about the transactions of a single namespace in a block.
+#[derive(Clone, Copy, Debug, Default)]
+struct NamespaceBlockStats {
+    num_transactions: u64,
+    /// Total size of the namespace's transactions, if known for all of them.
+    size: Option<u64>,
+}
+
+impl NamespaceBlockStats {
+    fn add(&mut self, size: Option<u64>) {
+        self.size = if self.num_transactions == 0 {
+            size
+        } else {
+            self.size.zip(size).map(|(total, size)| total + size)
+        };
+        self.num_transactions += 1;
+    }
 }
 
 impl<Types> UpdateAvailabilityStorage<Types> for Transaction<Write>
@@ -550,40 +652,100 @@ where
             }
         }
 
-        // The header and payload tables should already have been initialized when we inserted the
-        // corresponding leaf. All we have to do is add the payload itself and its size.
-        let payload = block.payload.encode();
-
-        self.upsert(
-            "payload",
-            ["height", "data", "size", "num_transactions"],
-            ["height"],
-            [(
-                height as i64,
-                payload.as_ref().to_vec(),
-                block.size() as i32,
-                block.num_transactions() as i32,
-            )],
-        )
-        .await?;
-
-        // Index the transactions in the block.
+        // Index the transactions in the block, skipping namespaces which a retention rule has
+        // already pruned past this height. Again, this can happen if the fetcher is racing with
+        // the pruner.
+        let namespace_pruned_heights = self.load_namespace_pruned_heights().await?;
+        let mut namespaces = HashMap::<u64, NamespaceBlockStats>::new();
         let mut rows = vec![];
         for (txn_ix, txn) in block.enumerate() {
+            let ns_id = block
+                .payload()
+                .transaction_namespace(block.metadata(), &txn_ix);
+            if let Some(ns_id) = ns_id {
+                let size = block.payload().transaction_size(block.metadata(), &txn_ix);
+                namespaces.entry(ns_id).or_default().add(size);
+                if namespace_pruned_heights
+                    .get(&ns_id)
+                    .is_some_and(|pruned_height| height <= *pruned_height)
+                {
+                    continue;
+                }
+            }
             let txn_ix =
                 serde_json::to_value(&txn_ix).context("failed to serialize transaction index")?;
-            rows.push((txn.commit().to_string(), height as i64, txn_ix));
+            rows.push((
+                txn.commit().to_string(),
+                height as i64,
+                txn_ix,
+                ns_id.map(|ns_id| ns_id as i64),
+            ));
         }
+
+        // The header and payload tables should already have been initialized when we inserted the
+        // corresponding leaf. All we have to do is add the payload itself and its size, unless
+        // every namespace in the block has already been pruned, in which case we just record the
+        // retention gap.
+        if !namespaces.is_empty() && rows.is_empty() {
+            tracing::info!(
+                height,
+                "ignoring payload which is already pruned by namespace"
+            );
+            self.execute(
+                query(
+                    "UPDATE payload SET size = $1, num_transactions = $2, retention_pruned = true
+                      WHERE height = $3",
+                )
+                .bind(block.size() as i32)
+                .bind(block.num_transactions() as i32)
+                .bind(height as i64),
+            )
+            .await?;
+        } else {
+            let payload = block.payload.encode();
+            let (data, data_blob) =
+                blob::offload(self.blob_store(), payload.as_ref().to_vec()).await?;
+            self.upsert(
+                "payload",
+                ["height", "data", "size", "num_transactions", "data_blob"],
+                ["height"],
+                [(
+                    height as i64,
+                    data,
+                    block.size() as i32,
+                    block.num_transactions() as i32,
+                    data_blob,
+                )],
+            )
+            .await?;
+        }
+
         if !rows.is_empty() {
             self.upsert(
                 "transactions",
-                ["hash", "block_height", "idx"],
+                ["hash", "block_height", "idx", "ns_id"],
                 ["block_height", "idx"],
                 rows,
             )
             .await?;
         }
 
+        // Record which namespaces are in the block, so that retention rules can find it, along
+        // with the statistics used for namespace analytics.
+        if !namespaces.is_empty() {
+            let mut query_builder = QueryBuilder::new(
+                "INSERT INTO block_namespace (height, ns_id, num_transactions, size) ",
+            );
+            query_builder.push_values(namespaces, |mut b, (ns_id, stats)| {
+                b.push_bind(height as i64)
+                    .push_bind(ns_id as i64)
+                    .push_bind(stats.num_transactions as i64)
+                    .push_bind(stats.size.map(|size| size as i64));
+            });
+            query_builder.push(" ON CONFLICT DO NOTHING");
+            self.execute(query_builder.build()).await?;
+        }
+
         Ok(())
     }
 
@@ -609,13 +771,14 @@ where
 
         let common_data =
             bincode::serialize(common.common()).context("failed to serialize VID common data")?;
+        let (common_data, common_blob) = blob::offload(self.blob_store(), common_data).await?;
         if let Some(share) = share {
             let share_data = bincode::serialize(&share).context("failed to serialize VID share")?;
             self.upsert(
                 "vid2",
-                ["height", "common", "share"],
+                ["height", "common", "common_blob", "share"],
                 ["height"],
-                [(height as i64, common_data, share_data)],
+                [(height as i64, common_data, common_blob, share_data)],
             )
             .await
         } else {
@@ -624,9 +787,9 @@ where
             // in which case we don't want to overwrite the share with NULL.
             self.upsert(
                 "vid2",
-                ["height", "common"],
+                ["height", "common", "common_blob"],
                 ["height"],
-                [(height as i64, common_data)],
+                [(height as i64, common_data, common_blob)],
             )
             .await
         }
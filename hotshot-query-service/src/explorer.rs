@@ -17,7 +17,7 @@ pub(crate) mod monetary_value;
 pub(crate) mod query_data;
 pub(crate) mod traits;
 
-use std::{fmt::Display, num::NonZeroUsize, path::Path};
+use std::{fmt::Display, num::NonZeroUsize, path::PathBuf};
 
 pub use currency::*;
 pub use data_source::*;
@@ -26,6 +26,7 @@ use hotshot_types::traits::node_implementation::NodeType;
 pub use monetary_value::*;
 pub use query_data::*;
 use serde::{Deserialize, Serialize};
+use tagged_base64::TaggedBase64;
 use tide_disco::{api::ApiError, method::ReadState, Api, StatusCode};
 pub use traits::*;
 use vbs::version::StaticVersionType;
@@ -37,6 +38,34 @@ use crate::{
     Header, Payload, Transaction,
 };
 
+#[derive(Debug)]
+pub struct Options {
+    pub api_path: Option<PathBuf>,
+
+    /// Additional API specification files to merge with `explorer-api-path`.
+    ///
+    /// These optional files may contain route definitions for application-specific routes that have
+    /// been added as extensions to the basic explorer API.
+    pub extensions: Vec<toml::Value>,
+
+    /// Whether to register the generic handler for `search/:query`.
+    ///
+    /// Applications which match search queries against their own data can disable this and
+    /// register their own `get_search_result` handler, using [`parse_search_query`] and
+    /// [`ExplorerDataSource::get_search_results`] to keep matching blocks and transactions.
+    pub search: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            api_path: None,
+            extensions: vec![],
+            search: true,
+        }
+    }
+}
+
 /// [Error] is an enum that represents the various errors that can be returned
 /// from the Explorer API.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,9 +77,21 @@ pub enum Error {
     GetTransactionSummaries(GetTransactionSummariesError),
     GetExplorerSummary(GetExplorerSummaryError),
     GetSearchResults(GetSearchResultsError),
+    GetNamespaceSummaries(GetNamespaceSummariesError),
+    GetNamespaceDetail(GetNamespaceDetailError),
+    /// Errors from application-specific routes added as extensions to the
+    /// explorer API.
+    Custom {
+        message: String,
+        status: StatusCode,
+    },
 }
 
 impl Error {
+    pub fn catch_all(status: StatusCode, message: String) -> Self {
+        Self::Custom { message, status }
+    }
+
     pub fn status(&self) -> StatusCode {
         match self {
             Error::GetBlockDetail(e) => e.status(),
@@ -59,6 +100,9 @@ impl Error {
             Error::GetTransactionSummaries(e) => e.status(),
             Error::GetExplorerSummary(e) => e.status(),
             Error::GetSearchResults(e) => e.status(),
+            Error::GetNamespaceSummaries(e) => e.status(),
+            Error::GetNamespaceDetail(e) => e.status(),
+            Error::Custom { status, .. } => *status,
         }
     }
 }
@@ -72,6 +116,9 @@ impl Display for Error {
             Error::GetTransactionSummaries(e) => e.fmt(f),
             Error::GetExplorerSummary(e) => e.fmt(f),
             Error::GetSearchResults(e) => e.fmt(f),
+            Error::GetNamespaceSummaries(e) => e.fmt(f),
+            Error::GetNamespaceDetail(e) => e.fmt(f),
+            Error::Custom { message, status } => write!(f, "error {status}: {message}"),
         }
     }
 }
@@ -85,6 +132,9 @@ impl std::error::Error for Error {
             Error::GetTransactionSummaries(e) => Some(e),
             Error::GetExplorerSummary(e) => Some(e),
             Error::GetSearchResults(e) => Some(e),
+            Error::GetNamespaceSummaries(e) => Some(e),
+            Error::GetNamespaceDetail(e) => Some(e),
+            Error::Custom { .. } => None,
         }
     }
 }
@@ -213,6 +263,40 @@ where
     }
 }
 
+/// [NamespaceSummariesResponse] is a struct that represents the response from
+/// the `get_namespace_summaries` endpoint.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NamespaceSummariesResponse {
+    pub namespace_summaries: Vec<NamespaceSummary>,
+}
+
+impl From<Vec<NamespaceSummary>> for NamespaceSummariesResponse {
+    fn from(namespace_summaries: Vec<NamespaceSummary>) -> Self {
+        Self {
+            namespace_summaries,
+        }
+    }
+}
+
+/// [NamespaceDetailResponse] is a struct that represents the response from the
+/// `get_namespace_detail` endpoint.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NamespaceDetailResponse {
+    pub namespace_detail: NamespaceDetail,
+}
+
+impl From<NamespaceDetail> for NamespaceDetailResponse {
+    fn from(namespace_detail: NamespaceDetail) -> Self {
+        Self { namespace_detail }
+    }
+}
+
+/// The default window, in seconds, of the `get_namespace_detail` time series.
+const DEFAULT_NAMESPACE_WINDOW: u64 = 24 * 60 * 60;
+
+/// The default number of buckets in the `get_namespace_detail` time series.
+const DEFAULT_NAMESPACE_BUCKETS: usize = 24;
+
 fn validate_limit(
     limit: Result<usize, tide_disco::RequestError>,
 ) -> Result<NonZeroUsize, InvalidLimit> {
@@ -233,10 +317,21 @@ fn validate_limit(
     Ok(num_blocks)
 }
 
+/// `parse_search_query` parses the `:query` parameter of the `search/:query`
+/// endpoint into the [TaggedBase64] form understood by
+/// [ExplorerDataSource::get_search_results].
+pub fn parse_search_query(query: &str) -> Result<TaggedBase64, GetSearchResultsError> {
+    TaggedBase64::parse(query).map_err(|err| {
+        tracing::error!("query param error: {}", err);
+        GetSearchResultsError::InvalidQuery(errors::BadQuery {})
+    })
+}
+
 /// `define_api` is a function that defines the API endpoints for the Explorer
 /// module of the HotShot Query Service. It implements the specification
 /// defined in the `explorer.toml` file.
 pub fn define_api<State, Types: NodeType, Ver: StaticVersionType + 'static>(
+    options: &Options,
     _: Ver,
 ) -> Result<Api<State, Error, Ver>, ApiError>
 where
@@ -247,9 +342,9 @@ where
     <State as ReadState>::State: ExplorerDataSource<Types> + Send + Sync,
 {
     let mut api = load_api::<State, Error, Ver>(
-        Option::<Box<Path>>::None,
+        options.api_path.as_ref(),
         include_str!("../api/explorer.toml"),
-        None,
+        options.extensions.clone(),
     )?;
 
     api.with_version("0.0.1".parse().unwrap())
@@ -370,25 +465,94 @@ where
                     .map_err(Error::GetExplorerSummary)
             }
             .boxed()
-        })?
-        .get("get_search_result", move |req, state| {
+        })?;
+
+    api.get("get_namespace_summaries", move |req, state| {
+        async move {
+            let limit = validate_limit(req.integer_param("limit"))
+                .map_err(GetNamespaceSummariesError::InvalidLimit)
+                .map_err(Error::GetNamespaceSummaries)?;
+            let window = req.integer_param("window").map_err(|err| {
+                tracing::error!("window param error: {}", err);
+                Error::GetNamespaceSummaries(GetNamespaceSummariesError::InvalidQuery(
+                    errors::BadQuery {},
+                ))
+            })?;
+            let order = match req.opt_string_param("order") {
+                Ok(Some(order)) => order
+                    .parse()
+                    .map_err(GetNamespaceSummariesError::InvalidQuery)
+                    .map_err(Error::GetNamespaceSummaries)?,
+                _ => NamespaceOrder::default(),
+            };
+
+            state
+                .get_namespace_summaries(GetNamespaceSummariesRequest {
+                    window,
+                    limit,
+                    order,
+                })
+                .await
+                .map(NamespaceSummariesResponse::from)
+                .map_err(Error::GetNamespaceSummaries)
+        }
+        .boxed()
+    })?
+    .get("get_namespace_detail", move |req, state| {
+        async move {
+            let namespace = req.integer_param("namespace").map_err(|err| {
+                tracing::error!("namespace param error: {}", err);
+                Error::GetNamespaceDetail(GetNamespaceDetailError::InvalidQuery(
+                    errors::BadQuery {},
+                ))
+            })?;
+            let (window, buckets) = match req.opt_integer_param("window") {
+                Ok(Some(window)) => {
+                    let buckets = validate_limit(req.integer_param("buckets"))
+                        .map_err(GetNamespaceDetailError::InvalidLimit)
+                        .map_err(Error::GetNamespaceDetail)?;
+                    (window, buckets)
+                },
+                _ => (
+                    DEFAULT_NAMESPACE_WINDOW,
+                    NonZeroUsize::new(DEFAULT_NAMESPACE_BUCKETS).unwrap(),
+                ),
+            };
+
+            state
+                .get_namespace_detail(GetNamespaceDetailRequest {
+                    namespace,
+                    window,
+                    buckets,
+                })
+                .await
+                .map(NamespaceDetailResponse::from)
+                .map_err(Error::GetNamespaceDetail)
+        }
+        .boxed()
+    })?;
+
+    if options.search {
+        api.get("get_search_result", move |req, state| {
             async move {
                 let query = req
-                    .tagged_base64_param("query")
+                    .string_param("query")
                     .map_err(|err| {
                         tracing::error!("query param error: {}", err);
                         GetSearchResultsError::InvalidQuery(errors::BadQuery {})
                     })
+                    .and_then(parse_search_query)
                     .map_err(Error::GetSearchResults)?;
 
                 state
-                    .get_search_results(query.clone())
+                    .get_search_results(query)
                     .await
                     .map(SearchResultResponse::from)
                     .map_err(Error::GetSearchResults)
             }
             .boxed()
         })?;
+    }
     Ok(api)
 }
 
@@ -871,8 +1035,11 @@ mod test {
         // Start the web server.
         let port = pick_unused_port().unwrap();
         let mut app = App::<_, Error>::with_state(ApiState::from(network.data_source()));
-        app.register_module("explorer", define_api(MockBase::instance()).unwrap())
-            .unwrap();
+        app.register_module(
+            "explorer",
+            define_api(&Default::default(), MockBase::instance()).unwrap(),
+        )
+        .unwrap();
         app.register_module(
             "availability",
             availability::define_api(
@@ -18,9 +18,11 @@ use super::{
     query_data::{
         BlockDetail, BlockIdentifier, BlockSummary, ExplorerSummary, GetBlockDetailError,
         GetBlockSummariesError, GetBlockSummariesRequest, GetExplorerSummaryError,
-        GetSearchResultsError, GetTransactionDetailError, GetTransactionSummariesError,
-        GetTransactionSummariesRequest, SearchResult, TransactionDetailResponse,
-        TransactionIdentifier, TransactionSummary,
+        GetNamespaceDetailError, GetNamespaceDetailRequest, GetNamespaceSummariesError,
+        GetNamespaceSummariesRequest, GetSearchResultsError, GetTransactionDetailError,
+        GetTransactionSummariesError, GetTransactionSummariesRequest, NamespaceDetail,
+        NamespaceSummary, SearchResult, TransactionDetailResponse, TransactionIdentifier,
+        TransactionSummary,
     },
     traits::{ExplorerHeader, ExplorerTransaction},
 };
@@ -91,4 +93,19 @@ where
         &self,
         query: TaggedBase64,
     ) -> Result<SearchResult<Types>, GetSearchResultsError>;
+
+    /// `get_namespace_summaries` is a method that retrieves the most active
+    /// namespaces over a window of time, as specified by the given
+    /// [GetNamespaceSummariesRequest].
+    async fn get_namespace_summaries(
+        &self,
+        request: GetNamespaceSummariesRequest,
+    ) -> Result<Vec<NamespaceSummary>, GetNamespaceSummariesError>;
+
+    /// `get_namespace_detail` is a method that retrieves the history of a
+    /// single namespace, as specified by the given [GetNamespaceDetailRequest].
+    async fn get_namespace_detail(
+        &self,
+        request: GetNamespaceDetailRequest,
+    ) -> Result<NamespaceDetail, GetNamespaceDetailError>;
 }
@@ -14,6 +14,7 @@ use std::{
     collections::VecDeque,
     fmt::{Debug, Display},
     num::{NonZeroUsize, TryFromIntError},
+    str::FromStr,
 };
 
 use hotshot_types::traits::node_implementation::NodeType;
@@ -498,10 +499,101 @@ where
     pub genesis_overview: GenesisOverview,
     pub latest_blocks: Vec<BlockSummary<Types>>,
     pub latest_transactions: Vec<TransactionSummary<Types>>,
-    //  Most Active Rollups
+    /// The namespaces with the most transactions in the last day.
+    pub most_active_namespaces: Vec<NamespaceSummary>,
     pub histograms: ExplorerHistograms,
 }
 
+/// [NamespaceOrder] determines how namespaces are ranked in a list of
+/// [NamespaceSummary]s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NamespaceOrder {
+    /// Rank namespaces by the number of transactions they contain.
+    #[default]
+    Transactions,
+    /// Rank namespaces by the total size of their transactions.
+    Bytes,
+}
+
+impl FromStr for NamespaceOrder {
+    type Err = BadQuery;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "transactions" => Ok(Self::Transactions),
+            "bytes" => Ok(Self::Bytes),
+            _ => Err(BadQuery {}),
+        }
+    }
+}
+
+/// [GetNamespaceSummariesRequest] requests the most active namespaces over a
+/// window of time.
+///
+/// The window covers the last `window` seconds, up to and including the
+/// timestamp of the latest block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetNamespaceSummariesRequest {
+    pub window: u64,
+    pub limit: NonZeroUsize,
+    pub order: NamespaceOrder,
+}
+
+/// [NamespaceSummary] describes the activity of a single namespace over a
+/// window of time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamespaceSummary {
+    pub namespace: u64,
+    /// The number of blocks containing the namespace.
+    pub blocks: u64,
+    pub transactions: u64,
+    /// The total size of the namespace's transactions. This may undercount
+    /// blocks for which transaction sizes are not known.
+    pub bytes: u64,
+}
+
+/// [GetNamespaceDetailRequest] requests the history of a single namespace.
+///
+/// The time series covers the last `window` seconds, up to and including the
+/// timestamp of the latest block, split into `buckets` intervals of equal
+/// length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetNamespaceDetailRequest {
+    pub namespace: u64,
+    pub window: u64,
+    pub buckets: NonZeroUsize,
+}
+
+/// [NamespaceStatsBucket] is a single point in the time series of a
+/// namespace, covering the blocks with timestamps from `start` (inclusive) to
+/// `end` (exclusive).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamespaceStatsBucket {
+    pub start: Timestamp,
+    pub end: Timestamp,
+    /// The total number of blocks in the interval.
+    pub blocks: u64,
+    /// The number of blocks in the interval containing the namespace.
+    pub namespace_blocks: u64,
+    pub transactions: u64,
+    pub bytes: u64,
+    pub transactions_per_second: f64,
+    /// The average size of the namespace in blocks containing it.
+    pub bytes_per_block: f64,
+}
+
+/// [NamespaceDetail] describes the history of a single namespace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamespaceDetail {
+    pub namespace: u64,
+    /// The height of the first block containing the namespace.
+    pub first_height: u64,
+    /// The height of the last block containing the namespace.
+    pub last_height: u64,
+    pub series: Vec<NamespaceStatsBucket>,
+}
+
 /// [SearchResult] is a struct that represents the results of executing a
 /// search query against the chain.  It contains a list of blocks and
 /// transactions that match the search query.
@@ -770,6 +862,7 @@ pub enum GetExplorerSummaryError {
     GetBlockDetailError(GetBlockDetailError),
     GetBlockSummariesError(GetBlockSummariesError),
     GetTransactionSummariesError(GetTransactionSummariesError),
+    GetNamespaceSummariesError(GetNamespaceSummariesError),
 }
 
 impl GetExplorerSummaryError {
@@ -780,6 +873,7 @@ impl GetExplorerSummaryError {
             GetExplorerSummaryError::GetBlockDetailError(err) => err.status(),
             GetExplorerSummaryError::GetBlockSummariesError(err) => err.status(),
             GetExplorerSummaryError::GetTransactionSummariesError(err) => err.status(),
+            GetExplorerSummaryError::GetNamespaceSummariesError(err) => err.status(),
         }
     }
 }
@@ -792,6 +886,7 @@ impl Display for GetExplorerSummaryError {
             GetExplorerSummaryError::GetBlockDetailError(err) => write!(f, "{err}"),
             GetExplorerSummaryError::GetBlockSummariesError(err) => write!(f, "{err}"),
             GetExplorerSummaryError::GetTransactionSummariesError(err) => write!(f, "{err}"),
+            GetExplorerSummaryError::GetNamespaceSummariesError(err) => write!(f, "{err}"),
         }
     }
 }
@@ -804,6 +899,7 @@ impl ExplorerAPIError for GetExplorerSummaryError {
             GetExplorerSummaryError::GetBlockDetailError(err) => err.code(),
             GetExplorerSummaryError::GetBlockSummariesError(err) => err.code(),
             GetExplorerSummaryError::GetTransactionSummariesError(err) => err.code(),
+            GetExplorerSummaryError::GetNamespaceSummariesError(err) => err.code(),
         }
     }
 }
@@ -816,6 +912,7 @@ impl std::error::Error for GetExplorerSummaryError {
             GetExplorerSummaryError::GetBlockDetailError(err) => Some(err),
             GetExplorerSummaryError::GetBlockSummariesError(err) => Some(err),
             GetExplorerSummaryError::GetTransactionSummariesError(err) => Some(err),
+            GetExplorerSummaryError::GetNamespaceSummariesError(err) => Some(err),
         }
     }
 }
@@ -840,12 +937,145 @@ impl From<GetBlockSummariesError> for GetExplorerSummaryError {
     }
 }
 
+impl From<GetNamespaceSummariesError> for GetExplorerSummaryError {
+    fn from(value: GetNamespaceSummariesError) -> Self {
+        GetExplorerSummaryError::GetNamespaceSummariesError(value)
+    }
+}
+
 impl From<GetTransactionSummariesError> for GetExplorerSummaryError {
     fn from(value: GetTransactionSummariesError) -> Self {
         GetExplorerSummaryError::GetTransactionSummariesError(value)
     }
 }
 
+/// [GetNamespaceSummariesError] represents an error that has occurred in
+/// response to the [GetNamespaceSummariesRequest] request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GetNamespaceSummariesError {
+    Unimplemented(Unimplemented),
+    InvalidLimit(InvalidLimit),
+    InvalidQuery(BadQuery),
+    QueryError(QueryError),
+}
+
+impl GetNamespaceSummariesError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            GetNamespaceSummariesError::Unimplemented(err) => err.status(),
+            GetNamespaceSummariesError::InvalidLimit(err) => err.status(),
+            GetNamespaceSummariesError::InvalidQuery(err) => err.status(),
+            GetNamespaceSummariesError::QueryError(err) => err.status(),
+        }
+    }
+}
+
+impl Display for GetNamespaceSummariesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GetNamespaceSummariesError::Unimplemented(err) => write!(f, "{err}"),
+            GetNamespaceSummariesError::InvalidLimit(err) => write!(f, "{err}"),
+            GetNamespaceSummariesError::InvalidQuery(err) => write!(f, "{err}"),
+            GetNamespaceSummariesError::QueryError(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl ExplorerAPIError for GetNamespaceSummariesError {
+    fn code(&self) -> &str {
+        match self {
+            GetNamespaceSummariesError::Unimplemented(err) => err.code(),
+            GetNamespaceSummariesError::InvalidLimit(err) => err.code(),
+            GetNamespaceSummariesError::InvalidQuery(err) => err.code(),
+            GetNamespaceSummariesError::QueryError(err) => err.code(),
+        }
+    }
+}
+
+impl std::error::Error for GetNamespaceSummariesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GetNamespaceSummariesError::Unimplemented(err) => Some(err),
+            GetNamespaceSummariesError::InvalidLimit(err) => Some(err),
+            GetNamespaceSummariesError::InvalidQuery(err) => Some(err),
+            GetNamespaceSummariesError::QueryError(err) => Some(err),
+        }
+    }
+}
+
+impl From<crate::QueryError> for GetNamespaceSummariesError {
+    fn from(value: crate::QueryError) -> Self {
+        GetNamespaceSummariesError::QueryError(QueryError { error: value })
+    }
+}
+
+/// [GetNamespaceDetailError] represents an error that has occurred in response
+/// to the [GetNamespaceDetailRequest] request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GetNamespaceDetailError {
+    Unimplemented(Unimplemented),
+    InvalidLimit(InvalidLimit),
+    InvalidQuery(BadQuery),
+    NamespaceNotFound(NotFound),
+    QueryError(QueryError),
+}
+
+impl GetNamespaceDetailError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            GetNamespaceDetailError::Unimplemented(err) => err.status(),
+            GetNamespaceDetailError::InvalidLimit(err) => err.status(),
+            GetNamespaceDetailError::InvalidQuery(err) => err.status(),
+            GetNamespaceDetailError::NamespaceNotFound(err) => err.status(),
+            GetNamespaceDetailError::QueryError(err) => err.status(),
+        }
+    }
+}
+
+impl Display for GetNamespaceDetailError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GetNamespaceDetailError::Unimplemented(err) => write!(f, "{err}"),
+            GetNamespaceDetailError::InvalidLimit(err) => write!(f, "{err}"),
+            GetNamespaceDetailError::InvalidQuery(err) => write!(f, "{err}"),
+            GetNamespaceDetailError::NamespaceNotFound(err) => write!(f, "{err}"),
+            GetNamespaceDetailError::QueryError(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl ExplorerAPIError for GetNamespaceDetailError {
+    fn code(&self) -> &str {
+        match self {
+            GetNamespaceDetailError::Unimplemented(err) => err.code(),
+            GetNamespaceDetailError::InvalidLimit(err) => err.code(),
+            GetNamespaceDetailError::InvalidQuery(err) => err.code(),
+            GetNamespaceDetailError::NamespaceNotFound(err) => err.code(),
+            GetNamespaceDetailError::QueryError(err) => err.code(),
+        }
+    }
+}
+
+impl std::error::Error for GetNamespaceDetailError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GetNamespaceDetailError::Unimplemented(err) => Some(err),
+            GetNamespaceDetailError::InvalidLimit(err) => Some(err),
+            GetNamespaceDetailError::InvalidQuery(err) => Some(err),
+            GetNamespaceDetailError::NamespaceNotFound(err) => Some(err),
+            GetNamespaceDetailError::QueryError(err) => Some(err),
+        }
+    }
+}
+
+impl From<crate::QueryError> for GetNamespaceDetailError {
+    fn from(value: crate::QueryError) -> Self {
+        GetNamespaceDetailError::QueryError(QueryError { error: value })
+    }
+}
+
 /// [GetSearchResultsError] represents an error that has occurred in response
 /// to the `get_search_results` request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
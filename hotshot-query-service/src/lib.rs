@@ -783,6 +783,12 @@ mod test {
         async fn get_state_cert(&self, epoch: u64) -> Fetch<StateCertQueryData<MockTypes>> {
             self.hotshot_qs.get_state_cert(epoch).await
         }
+        async fn get_pruned_payloads<R>(&self, range: R) -> QueryResult<Vec<u64>>
+        where
+            R: RangeBounds<usize> + Send + 'static,
+        {
+            self.hotshot_qs.get_pruned_payloads(range).await
+        }
     }
 
     // Imiplement data source trait for node API.
@@ -956,7 +962,8 @@ mod test {
                 missing_leaves: 0,
                 missing_vid_common: 1,
                 missing_vid_shares: 1,
-                pruned_height: None
+                pruned_height: None,
+                pruned_payloads: 0
             }
         );
         assert_eq!(
@@ -23,6 +23,11 @@ pub struct SyncStatus {
     pub missing_vid_common: usize,
     pub missing_vid_shares: usize,
     pub pruned_height: Option<usize>,
+    /// Blocks whose payloads were deleted by namespace retention rules.
+    ///
+    /// These are deliberate gaps and are not counted in `missing_blocks`.
+    #[serde(default)]
+    pub pruned_payloads: usize,
 }
 
 impl SyncStatus {
@@ -33,11 +38,17 @@ impl SyncStatus {
             missing_vid_common: 0,
             missing_vid_shares: 0,
             pruned_height: None,
+            pruned_payloads: 0,
         }
     }
 
     pub fn is_fully_synced(&self) -> bool {
-        *self == Self::fully_synced()
+        // Payloads pruned by retention rules are not missing, so they do not count against being
+        // fully synced.
+        Self {
+            pruned_payloads: 0,
+            ..self.clone()
+        } == Self::fully_synced()
     }
 }
 
@@ -121,6 +121,25 @@ impl<Types: NodeType> QueryablePayload<Types> for MockPayload {
     ) -> Option<(Self::Transaction, Self::InclusionProof)> {
         self.transactions.get(*index).cloned().map(|tx| (tx, ()))
     }
+
+    fn transaction_namespace(
+        &self,
+        _meta: &Self::Metadata,
+        index: &Self::TransactionIndex,
+    ) -> Option<u64> {
+        // For testing namespace features, the first byte of a mock transaction is its namespace.
+        let tx = self.transactions.get(*index)?;
+        tx.bytes().first().map(|ns_id| *ns_id as u64)
+    }
+
+    fn transaction_size(
+        &self,
+        _meta: &Self::Metadata,
+        index: &Self::TransactionIndex,
+    ) -> Option<u64> {
+        let tx = self.transactions.get(*index)?;
+        Some(tx.bytes().len() as u64)
+    }
 }
 
 #[derive(
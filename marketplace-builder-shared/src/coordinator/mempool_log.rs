@@ -0,0 +1,401 @@
+//! Write-ahead log of transactions submitted to the builder's private mempool,
+//! allowing them to survive builder restarts.
+//!
+//! The log is a single append-only file of length-prefixed, bincode-encoded records.
+//! Each accepted transaction gets a record, and so does its final status once it's
+//! sequenced or rejected. The log is compacted on open and whenever finalized records
+//! significantly outnumber the pending transactions, so it only grows with the
+//! number of transactions the builder is actually holding.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::Context;
+use committable::{Commitment, Committable};
+use hotshot_builder_api::v0_1::builder::TransactionStatus;
+use hotshot_types::traits::node_implementation::NodeType;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Number of records the log may hold on top of the ones describing
+/// pending transactions before it gets compacted
+const COMPACTION_THRESHOLD: usize = 1024;
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+enum LogRecord<Types: NodeType> {
+    /// Transaction was accepted by the builder
+    Received(Types::Transaction),
+    /// Transaction left the mempool, either sequenced or rejected
+    Finalized {
+        commit: Commitment<Types::Transaction>,
+        status: TransactionStatus,
+    },
+}
+
+/// Mempool contents recovered from the log when it was opened
+#[derive(Debug)]
+pub struct RecoveredMempool<Types: NodeType> {
+    /// Transactions received but not finalized before the restart, in arrival order
+    pub pending: Vec<Types::Transaction>,
+    /// Final statuses of transactions finalized since the log was last compacted
+    pub finalized: Vec<(Commitment<Types::Transaction>, TransactionStatus)>,
+}
+
+struct Inner<Types: NodeType> {
+    file: File,
+    /// Pending transactions along with their arrival sequence number
+    pending: HashMap<Commitment<Types::Transaction>, (u64, Types::Transaction)>,
+    next_seq: u64,
+    num_records: usize,
+    recovered: Option<RecoveredMempool<Types>>,
+}
+
+/// Write-ahead log of received but not yet included transactions.
+///
+/// Attach it to a coordinator with
+/// [`BuilderStateCoordinator::with_mempool_log`](super::BuilderStateCoordinator::with_mempool_log)
+/// and replay its contents with
+/// [`BuilderStateCoordinator::replay_mempool_log`](super::BuilderStateCoordinator::replay_mempool_log).
+pub struct MempoolLog<Types: NodeType> {
+    path: PathBuf,
+    inner: Mutex<Inner<Types>>,
+}
+
+impl<Types: NodeType> std::fmt::Debug for MempoolLog<Types> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MempoolLog")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl<Types: NodeType> MempoolLog<Types> {
+    /// Open the log at `path`, creating it if it doesn't exist.
+    ///
+    /// Contents of an existing log are recovered and can be retrieved with [`Self::take_recovered`].
+    /// A partially written record at the end of the log, as left by a crash mid-write, is discarded.
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_owned();
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(err) => {
+                return Err(err).with_context(|| format!("reading mempool log {}", path.display()))
+            },
+        };
+
+        let mut pending = HashMap::new();
+        let mut finalized = HashMap::new();
+        let mut next_seq = 0;
+        for record in read_records::<Types>(&bytes) {
+            match record {
+                LogRecord::Received(transaction) => {
+                    let commit = transaction.commit();
+                    finalized.remove(&commit);
+                    pending.entry(commit).or_insert_with(|| {
+                        next_seq += 1;
+                        (next_seq, transaction)
+                    });
+                },
+                LogRecord::Finalized { commit, status } => {
+                    pending.remove(&commit);
+                    finalized.insert(commit, status);
+                },
+            }
+        }
+
+        let mut recovered_pending = pending.values().cloned().collect::<Vec<_>>();
+        recovered_pending.sort_by_key(|(seq, _)| *seq);
+        let recovered = RecoveredMempool {
+            pending: recovered_pending.into_iter().map(|(_, tx)| tx).collect(),
+            finalized: finalized.into_iter().collect(),
+        };
+
+        let file = compact::<Types>(&path, &pending)?;
+        Ok(Self {
+            path,
+            inner: Mutex::new(Inner {
+                file,
+                num_records: pending.len(),
+                pending,
+                next_seq,
+                recovered: Some(recovered),
+            }),
+        })
+    }
+
+    /// Take the mempool contents recovered when the log was opened.
+    /// Returns [`None`] if they've already been taken.
+    pub fn take_recovered(&self) -> Option<RecoveredMempool<Types>> {
+        self.lock().recovered.take()
+    }
+
+    /// Number of transactions currently pending in the log
+    pub fn len(&self) -> usize {
+        self.lock().pending.len()
+    }
+
+    /// Whether there are no pending transactions in the log
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Durably record that `transaction` was accepted by the builder.
+    /// Transactions already pending in the log are not recorded again.
+    ///
+    /// Blocks until the record is synced to disk, so shouldn't be called on an async runtime thread.
+    pub fn record_received(&self, transaction: &Types::Transaction) -> anyhow::Result<()> {
+        let commit = transaction.commit();
+        let file = {
+            let mut inner = self.lock();
+            if inner.pending.contains_key(&commit) {
+                return Ok(());
+            }
+
+            append::<Types>(&mut inner.file, &LogRecord::Received(transaction.clone()))?;
+
+            inner.next_seq += 1;
+            let seq = inner.next_seq;
+            inner.pending.insert(commit, (seq, transaction.clone()));
+            inner.num_records += 1;
+            inner
+                .file
+                .try_clone()
+                .context("duplicating mempool log handle")?
+        };
+
+        // Sync without holding the lock so that concurrent writers aren't held up by it,
+        // a single sync covers records appended by all of them
+        file.sync_data().context("syncing mempool log")
+    }
+
+    /// Record that the transaction with commitment `commit` left the mempool with `status`.
+    /// Does nothing if the transaction isn't pending in the log.
+    ///
+    /// This record isn't synced to disk: should it be lost, the transaction
+    /// is replayed after restart and deduplicated then.
+    pub fn record_finalized(
+        &self,
+        commit: &Commitment<Types::Transaction>,
+        status: &TransactionStatus,
+    ) -> anyhow::Result<()> {
+        let mut inner = self.lock();
+        if inner.pending.remove(commit).is_none() {
+            return Ok(());
+        }
+
+        append::<Types>(
+            &mut inner.file,
+            &LogRecord::Finalized {
+                commit: *commit,
+                status: status.clone(),
+            },
+        )?;
+        inner.num_records += 1;
+
+        if inner.num_records > inner.pending.len() + COMPACTION_THRESHOLD {
+            inner.file = compact::<Types>(&self.path, &inner.pending)?;
+            inner.num_records = inner.pending.len();
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner<Types>> {
+        self.inner.lock().expect("mempool log lock poisoned")
+    }
+}
+
+/// Decode records from the log's contents, stopping at the first incomplete or corrupt one
+fn read_records<Types: NodeType>(mut bytes: &[u8]) -> Vec<LogRecord<Types>> {
+    let mut records = Vec::new();
+    while !bytes.is_empty() {
+        let Some((len, rest)) = bytes.split_first_chunk::<4>() else {
+            warn!("Discarding truncated record at the end of mempool log");
+            break;
+        };
+        let len = u32::from_le_bytes(*len) as usize;
+        if rest.len() < len {
+            warn!("Discarding truncated record at the end of mempool log");
+            break;
+        }
+        let (record, rest) = rest.split_at(len);
+        match bincode::deserialize(record) {
+            Ok(record) => records.push(record),
+            Err(err) => {
+                warn!(%err, "Discarding corrupt record in mempool log and everything after it");
+                break;
+            },
+        }
+        bytes = rest;
+    }
+    records
+}
+
+fn append<Types: NodeType>(file: &mut File, record: &LogRecord<Types>) -> anyhow::Result<()> {
+    let record = bincode::serialize(record).context("serializing mempool log record")?;
+    let len = u32::try_from(record.len()).context("mempool log record too big")?;
+    let mut buf = Vec::with_capacity(4 + record.len());
+    buf.extend_from_slice(&len.to_le_bytes());
+    buf.extend_from_slice(&record);
+    file.write_all(&buf).context("writing mempool log")
+}
+
+/// Atomically replace the log at `path` with one containing only `pending` transactions,
+/// returning the new log opened for appending
+fn compact<Types: NodeType>(
+    path: &Path,
+    pending: &HashMap<Commitment<Types::Transaction>, (u64, Types::Transaction)>,
+) -> anyhow::Result<File> {
+    let mut transactions = pending.values().collect::<Vec<_>>();
+    transactions.sort_by_key(|(seq, _)| *seq);
+
+    let tmp_path = path.with_extension("tmp");
+    let mut tmp = File::create(&tmp_path)
+        .with_context(|| format!("creating mempool log {}", tmp_path.display()))?;
+    for (_, transaction) in transactions {
+        append::<Types>(&mut tmp, &LogRecord::Received(transaction.clone()))?;
+    }
+    tmp.sync_all().context("syncing compacted mempool log")?;
+    fs::rename(&tmp_path, path)
+        .with_context(|| format!("replacing mempool log {}", path.display()))?;
+
+    OpenOptions::new()
+        .append(true)
+        .open(path)
+        .with_context(|| format!("opening mempool log {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use hotshot_example_types::node_types::TestTypes;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::testing::mock;
+
+    type MempoolLog = super::MempoolLog<TestTypes>;
+
+    fn sequenced() -> TransactionStatus {
+        TransactionStatus::Sequenced { leaf: 1 }
+    }
+
+    #[test]
+    fn test_recovers_pending_transactions() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("mempool");
+
+        let transactions = (0..5).map(|_| mock::transaction()).collect::<Vec<_>>();
+        {
+            let log = MempoolLog::open(&path).unwrap();
+            assert!(log.take_recovered().unwrap().pending.is_empty());
+            for tx in &transactions {
+                log.record_received(tx).unwrap();
+            }
+            // Duplicates don't change arrival order
+            log.record_received(&transactions[0]).unwrap();
+            log.record_finalized(&transactions[1].commit(), &sequenced())
+                .unwrap();
+            assert_eq!(log.len(), 4);
+        }
+
+        let log = MempoolLog::open(&path).unwrap();
+        let recovered = log.take_recovered().unwrap();
+        assert_eq!(
+            recovered.pending,
+            vec![
+                transactions[0].clone(),
+                transactions[2].clone(),
+                transactions[3].clone(),
+                transactions[4].clone()
+            ]
+        );
+        assert_eq!(
+            recovered.finalized,
+            vec![(transactions[1].commit(), sequenced())]
+        );
+        assert!(log.take_recovered().is_none());
+
+        // Opening compacts the log, so finalized statuses are only recovered once
+        drop(log);
+        let recovered = MempoolLog::open(&path).unwrap().take_recovered().unwrap();
+        assert_eq!(recovered.pending.len(), 4);
+        assert!(recovered.finalized.is_empty());
+    }
+
+    #[test]
+    fn test_discards_truncated_record() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("mempool");
+
+        let transactions = (0..2).map(|_| mock::transaction()).collect::<Vec<_>>();
+        {
+            let log = MempoolLog::open(&path).unwrap();
+            for tx in &transactions {
+                log.record_received(tx).unwrap();
+            }
+        }
+
+        // Simulate a crash in the middle of writing the last record
+        let len = fs::metadata(&path).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(len - 3)
+            .unwrap();
+
+        let log = MempoolLog::open(&path).unwrap();
+        assert_eq!(
+            log.take_recovered().unwrap().pending,
+            vec![transactions[0].clone()]
+        );
+
+        // The log is usable after recovery
+        log.record_received(&transactions[1]).unwrap();
+        drop(log);
+        assert_eq!(
+            MempoolLog::open(&path)
+                .unwrap()
+                .take_recovered()
+                .unwrap()
+                .pending,
+            transactions
+        );
+    }
+
+    #[test]
+    fn test_compaction() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("mempool");
+
+        let log = MempoolLog::open(&path).unwrap();
+        let kept = mock::transaction();
+        log.record_received(&kept).unwrap();
+        let empty_size = fs::metadata(&path).unwrap().len();
+
+        // Every iteration adds two records, this is just enough to trigger compaction
+        for _ in 0..=COMPACTION_THRESHOLD / 2 {
+            let tx = mock::transaction();
+            log.record_received(&tx).unwrap();
+            log.record_finalized(&tx.commit(), &sequenced()).unwrap();
+        }
+
+        // Log was compacted down to the single pending transaction
+        assert_eq!(fs::metadata(&path).unwrap().len(), empty_size);
+        drop(log);
+        assert_eq!(
+            MempoolLog::open(&path)
+                .unwrap()
+                .take_recovered()
+                .unwrap()
+                .pending,
+            vec![kept]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     collections::{hash_map::Entry, HashMap},
+    future::Future,
     ops::Bound,
     sync::Arc,
     time::Duration,
@@ -7,7 +8,7 @@ use std::{
 
 use async_broadcast::Sender;
 use async_lock::{Mutex, RwLock};
-use committable::Commitment;
+use committable::{Commitment, Committable};
 use either::Either;
 use hotshot::traits::BlockPayload;
 use hotshot_builder_api::v0_1::builder::TransactionStatus;
@@ -19,17 +20,20 @@ use hotshot_types::{
         node_implementation::{ConsensusTime, NodeType},
     },
 };
+use mempool_log::MempoolLog;
 use quick_cache::sync::Cache;
 use tiered_view_map::TieredViewMap;
+use tokio::task::spawn_blocking;
 use tracing::{error, info, warn};
 
 use crate::{
-    block::{BuilderStateId, ParentBlockReferences, ReceivedTransaction},
+    block::{BuilderStateId, ParentBlockReferences, ReceivedTransaction, TransactionSource},
     error::Error,
     state::BuilderState,
     utils::ProposalId,
 };
 
+pub mod mempool_log;
 pub mod tiered_view_map;
 
 type ProposalMap<Types> =
@@ -79,6 +83,9 @@ where
 /// - [`Self::handle_quorum_proposal`]
 /// - [`Self::handle_da_proposal`]
 /// - [`Self::handle_transaction`]
+///
+/// Optionally, transactions submitted to the builder's private mempool can be persisted
+/// in a [`MempoolLog`] to survive restarts, see [`Self::with_mempool_log`].
 pub struct BuilderStateCoordinator<Types>
 where
     Types: NodeType,
@@ -87,6 +94,7 @@ where
     tx_status: quick_cache::sync::Cache<Commitment<Types::Transaction>, TransactionStatus>,
     transaction_sender: Sender<Arc<ReceivedTransaction<Types>>>,
     proposals: Mutex<ProposalMap<Types>>,
+    mempool_log: Option<Arc<MempoolLog<Types>>>,
 }
 
 impl<Types> BuilderStateCoordinator<Types>
@@ -119,7 +127,69 @@ where
             builder_states: RwLock::new(builder_states),
             proposals: Mutex::new(ProposalMap::new()),
             tx_status: Cache::new(tx_status_cache_capacity),
+            mempool_log: None,
+        }
+    }
+
+    /// Persist privately submitted transactions in `mempool_log` until they're
+    /// sequenced or rejected.
+    ///
+    /// Transactions recovered from the log are not enqueued until [`Self::replay_mempool_log`] is called.
+    pub fn with_mempool_log(mut self, mempool_log: Arc<MempoolLog<Types>>) -> Self {
+        self.mempool_log = Some(mempool_log);
+        self
+    }
+
+    /// Enqueue transactions recovered from the mempool log after a restart and restore
+    /// statuses of transactions finalized shortly before it.
+    ///
+    /// The builder misses decide events while it is down, so `sequenced_at` is asked about every
+    /// recovered transaction and should return the height of the block that includes it, if any.
+    /// Transactions already sequenced are marked as such instead of being enqueued again.
+    ///
+    /// Returns the number of transactions enqueued. Does nothing if there's no mempool log
+    /// or it has already been replayed.
+    #[tracing::instrument(skip_all)]
+    pub async fn replay_mempool_log<F, Fut>(&self, sequenced_at: F) -> usize
+    where
+        F: Fn(Commitment<Types::Transaction>) -> Fut,
+        Fut: Future<Output = Option<u64>>,
+    {
+        let Some(recovered) = self
+            .mempool_log
+            .as_ref()
+            .and_then(|log| log.take_recovered())
+        else {
+            return 0;
+        };
+
+        for (commit, status) in recovered.finalized {
+            self.update_txn_status(&commit, status);
+        }
+
+        let mut enqueued = 0;
+        for transaction in recovered.pending {
+            let commit = transaction.commit();
+            if let Some(leaf) = sequenced_at(commit).await {
+                tracing::debug!(%commit, leaf, "Recovered transaction was sequenced while down");
+                self.update_txn_status(&commit, TransactionStatus::Sequenced { leaf });
+                continue;
+            }
+
+            match self
+                .handle_transaction(ReceivedTransaction::new(
+                    transaction,
+                    TransactionSource::Private,
+                ))
+                .await
+            {
+                Ok(()) => enqueued += 1,
+                Err(err) => warn!(%commit, %err, "Failed to enqueue recovered transaction"),
+            }
         }
+
+        info!(enqueued, "Replayed mempool log");
+        enqueued
     }
 
     /// This function should be called whenever new decide events are received from HotShot.
@@ -184,7 +254,29 @@ where
     ) -> Result<(), Error<Types>> {
         let commit = transaction.commit;
 
-        let maybe_evicted = match self.transaction_sender.try_broadcast(Arc::new(transaction)) {
+        // Persist the transaction before builder states can include it, otherwise
+        // it could be finalized before it's in the log and never leave it.
+        // Don't persist transactions that were already finalized either,
+        // their status won't change anymore to remove them from the log.
+        if let Some(mempool_log) = &self.mempool_log {
+            if transaction.source == TransactionSource::Private
+                && !is_final(&self.tx_status(&commit))
+            {
+                let mempool_log = Arc::clone(mempool_log);
+                let tx = transaction.transaction.clone();
+                match spawn_blocking(move || mempool_log.record_received(&tx)).await {
+                    Ok(Ok(())) => {},
+                    Ok(Err(err)) => error!(%err, "Failed to persist transaction in mempool log"),
+                    Err(err) => error!(%err, "Mempool log task panicked"),
+                }
+            }
+        }
+
+        let transaction = Arc::new(transaction);
+        let maybe_evicted = match self
+            .transaction_sender
+            .try_broadcast(Arc::clone(&transaction))
+        {
             Ok(maybe_evicted) => maybe_evicted,
             Err(err) => {
                 warn!(?err, "Failed to broadcast txn");
@@ -491,18 +583,19 @@ where
         new_status: TransactionStatus,
     ) {
         if let Some(old_status) = self.tx_status.get(txn_hash) {
-            match old_status {
-                TransactionStatus::Rejected { .. } | TransactionStatus::Sequenced { .. } => {
-                    tracing::debug!(
-                        ?old_status,
-                        ?new_status,
-                        "Not changing status of rejected/sequenced transaction",
-                    );
-                    return;
-                },
-                _ => {
-                    tracing::debug!(?old_status, ?new_status, "Changing status of transaction",);
-                },
+            if is_final(&old_status) {
+                tracing::debug!(
+                    ?old_status,
+                    ?new_status,
+                    "Not changing status of rejected/sequenced transaction",
+                );
+                return;
+            }
+            tracing::debug!(?old_status, ?new_status, "Changing status of transaction",);
+        }
+        if let Some(mempool_log) = self.mempool_log.as_ref().filter(|_| is_final(&new_status)) {
+            if let Err(err) = mempool_log.record_finalized(txn_hash, &new_status) {
+                error!(%err, "Failed to record finalized transaction in mempool log");
             }
         }
         self.tx_status.insert(*txn_hash, new_status);
@@ -516,6 +609,14 @@ where
     }
 }
 
+/// Whether a transaction with this status has left the mempool for good
+fn is_final(status: &TransactionStatus) -> bool {
+    matches!(
+        status,
+        TransactionStatus::Rejected { .. } | TransactionStatus::Sequenced { .. }
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Instant;
@@ -880,4 +981,100 @@ mod tests {
                 .unwrap();
         }
     }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_mempool_log_replay() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("mempool");
+
+        let transactions = (0..4).map(|_| mock::transaction()).collect::<Vec<_>>();
+        let public_transaction = mock::transaction();
+        {
+            let coordinator = BuilderStateCoordinator::new(
+                TEST_CHANNEL_BUFFER_SIZE,
+                TEST_INCLUDED_TX_GC_PERIOD,
+                TEST_TX_STATUS_CACHE_CAPACITY,
+            )
+            .with_mempool_log(Arc::new(MempoolLog::open(&path).unwrap()));
+
+            for tx in &transactions {
+                coordinator
+                    .handle_transaction(ReceivedTransaction::new(
+                        tx.clone(),
+                        TransactionSource::Private,
+                    ))
+                    .await
+                    .unwrap();
+            }
+            coordinator
+                .handle_transaction(ReceivedTransaction::new(
+                    public_transaction.clone(),
+                    TransactionSource::Public,
+                ))
+                .await
+                .unwrap();
+
+            // First transaction is decided before the builder goes down
+            let leaf_chain = mock::decide_leaf_chain_with_transactions(
+                *ViewNumber::genesis(),
+                vec![transactions[0].clone()],
+            )
+            .await;
+            coordinator.handle_decide(leaf_chain).await;
+        }
+
+        let coordinator = BuilderStateCoordinator::new(
+            TEST_CHANNEL_BUFFER_SIZE,
+            TEST_INCLUDED_TX_GC_PERIOD,
+            TEST_TX_STATUS_CACHE_CAPACITY,
+        )
+        .with_mempool_log(Arc::new(MempoolLog::open(&path).unwrap()));
+
+        // Second transaction is decided while the builder is down
+        let decided_while_down = transactions[1].commit();
+        let enqueued = coordinator
+            .replay_mempool_log(|commit| async move { (commit == decided_while_down).then_some(7) })
+            .await;
+        assert_eq!(enqueued, 2);
+
+        assert!(matches!(
+            coordinator.tx_status(&transactions[0].commit()),
+            TransactionStatus::Sequenced { .. }
+        ));
+        assert_eq!(
+            coordinator.tx_status(&transactions[1].commit()),
+            TransactionStatus::Sequenced { leaf: 7 }
+        );
+        for tx in &transactions[2..] {
+            assert_eq!(
+                coordinator.tx_status(&tx.commit()),
+                TransactionStatus::Pending
+            );
+        }
+        // Public transactions aren't persisted
+        assert_eq!(
+            coordinator.tx_status(&public_transaction.commit()),
+            TransactionStatus::Unknown
+        );
+
+        // Only the pending transactions are enqueued
+        let builder_state = coordinator.highest_view_builder().await.unwrap();
+        builder_state
+            .collect_txns(Instant::now() + Duration::from_secs(10))
+            .await;
+        assert_eq!(
+            builder_state
+                .txn_queue
+                .read()
+                .await
+                .iter()
+                .map(|tx| tx.transaction.clone())
+                .collect::<Vec<_>>(),
+            transactions[2..]
+        );
+
+        // Replay only happens once
+        assert_eq!(coordinator.replay_mempool_log(|_| async { None }).await, 0);
+    }
 }
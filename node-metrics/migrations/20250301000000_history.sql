@@ -0,0 +1,33 @@
+-- Every block processed by the node validator service, so that the recent
+-- history can be restored after a restart.
+CREATE TABLE block_history (
+    height BIGINT PRIMARY KEY,
+    view BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    proposer TEXT NOT NULL,
+    -- bincode encoded `BlockDetail`
+    detail BYTEA NOT NULL,
+    -- bincode encoded voters `BitVec`, in node identity order
+    voters BYTEA NOT NULL
+);
+
+-- Participation counters for each validator, bucketed by epoch and by hour
+-- (seconds since the UNIX epoch divided by 3600) of the block timestamp.
+CREATE TABLE validator_uptime (
+    public_key TEXT NOT NULL,
+    epoch BIGINT NOT NULL,
+    hour BIGINT NOT NULL,
+    -- Number of views in which the validator was in the stake table.
+    views BIGINT NOT NULL DEFAULT 0,
+    -- Number of those views in which the validator signed the QC.
+    votes BIGINT NOT NULL DEFAULT 0,
+    -- Number of views in which the validator was the leader.
+    leader_views BIGINT NOT NULL DEFAULT 0,
+    -- Number of those views in which the validator's proposal was decided.
+    proposals BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (public_key, epoch, hour)
+);
+
+CREATE INDEX validator_uptime_hour_idx ON validator_uptime (hour);
+CREATE INDEX validator_uptime_epoch_idx ON validator_uptime (epoch);
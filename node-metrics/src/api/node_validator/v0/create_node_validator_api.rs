@@ -9,16 +9,22 @@ use futures::{
 use tokio::{spawn, task::JoinHandle};
 use url::Url;
 
-use super::{get_stake_table_from_sequencer, LeafAndBlock, ProcessNodeIdentityUrlStreamTask};
+use super::{
+    get_sequencer_config, stake_table_from_config, LeafAndBlock, ProcessNodeIdentityUrlStreamTask,
+};
 use crate::service::{
     client_id::ClientId,
     client_message::InternalClientMessage,
     client_state::{
         ClientThreadState, InternalClientMessageProcessingTask,
-        ProcessDistributeBlockDetailHandlingTask, ProcessDistributeNodeIdentityHandlingTask,
-        ProcessDistributeVotersHandlingTask,
+        ProcessDistributeBlockDetailHandlingTask, ProcessDistributeLeaderboardHandlingTask,
+        ProcessDistributeNodeIdentityHandlingTask, ProcessDistributeVotersHandlingTask,
+    },
+    data_state::{
+        DataState, ProcessLeafAndBlockPairStreamTask, ProcessNodeIdentityStreamTask,
+        MAX_VOTERS_HISTORY,
     },
-    data_state::{DataState, ProcessLeafAndBlockPairStreamTask, ProcessNodeIdentityStreamTask},
+    history::{HistoryStorage, HistoryStorageError, ProcessLeaderboardTask, SequencerLeaderLookup},
     server_message::ServerMessage,
 };
 
@@ -27,6 +33,8 @@ pub struct NodeValidatorAPI<K> {
     pub process_distribute_block_detail_handle: Option<ProcessDistributeBlockDetailHandlingTask>,
     pub process_distribute_node_identity_handle: Option<ProcessDistributeNodeIdentityHandlingTask>,
     pub process_distribute_voters_handle: Option<ProcessDistributeVotersHandlingTask>,
+    pub process_distribute_leaderboard_handle: Option<ProcessDistributeLeaderboardHandlingTask>,
+    pub process_leaderboard_handle: Option<ProcessLeaderboardTask>,
     pub process_leaf_stream_handle: Option<ProcessLeafAndBlockPairStreamTask>,
     pub process_node_identity_stream_handle: Option<ProcessNodeIdentityStreamTask>,
     pub process_url_stream_handle: Option<ProcessNodeIdentityUrlStreamTask>,
@@ -37,11 +45,13 @@ pub struct NodeValidatorAPI<K> {
 pub struct NodeValidatorConfig {
     pub stake_table_url_base: Url,
     pub initial_node_public_base_urls: Vec<Url>,
+    pub history: Option<HistoryStorage>,
 }
 
 #[derive(Debug)]
 pub enum CreateNodeValidatorProcessingError {
     FailedToGetStakeTable(hotshot_query_service::Error),
+    FailedToLoadHistory(HistoryStorageError),
 }
 
 /// [SubmitPublicUrlsToScrapeTask] is a task that is capable of submitting
@@ -108,16 +118,47 @@ pub async fn create_node_validator_processing(
         Default::default(),
         Default::default(),
         Default::default(),
+        Default::default(),
         ClientId::from_count(1),
     );
 
     let client_stake_table = surf_disco::Client::new(config.stake_table_url_base.clone());
 
-    let stake_table = get_stake_table_from_sequencer(client_stake_table)
+    let sequencer_config = get_sequencer_config(client_stake_table)
         .await
         .map_err(CreateNodeValidatorProcessingError::FailedToGetStakeTable)?;
+    let epoch_height = sequencer_config.config.epoch_height;
+    let stake_table = stake_table_from_config(sequencer_config.config);
+
+    let mut data_state = DataState::new(Default::default(), Default::default(), stake_table);
 
-    let data_state = DataState::new(Default::default(), Default::default(), stake_table);
+    // Restore the most recent history, so that it is available to clients
+    // without having to wait for the leaf stream to catch up.
+    let history = config
+        .history
+        .map(|history| history.with_epoch_height(epoch_height));
+    if let Some(history) = &history {
+        let last_block = history
+            .last_block()
+            .await
+            .map_err(CreateNodeValidatorProcessingError::FailedToLoadHistory)?;
+        let latest_blocks = history
+            .latest_blocks(MAX_VOTERS_HISTORY)
+            .await
+            .map_err(CreateNodeValidatorProcessingError::FailedToLoadHistory)?;
+
+        for (block, voters) in latest_blocks {
+            data_state.add_latest_block(block);
+            data_state.add_latest_voters(voters);
+        }
+        // The leaders of the views are taken from the membership of the
+        // same node that the stake table is retrieved from.
+        let leaders = SequencerLeaderLookup::new(
+            surf_disco::Client::new(config.stake_table_url_base.clone()),
+            epoch_height,
+        );
+        data_state.set_history(history.clone(), Arc::new(leaders), last_block);
+    }
 
     let data_state = Arc::new(RwLock::new(data_state));
     let client_thread_state = Arc::new(RwLock::new(client_thread_state));
@@ -146,6 +187,22 @@ pub async fn create_node_validator_processing(
     let process_distribute_voters_handle =
         ProcessDistributeVotersHandlingTask::new(client_thread_state.clone(), voters_receiver);
 
+    // The leaderboard can only be computed from the history, so without it
+    // subscribed clients simply never receive one.
+    let (process_leaderboard_handle, process_distribute_leaderboard_handle) = match history {
+        Some(history) => {
+            let (leaderboard_sender, leaderboard_receiver) = mpsc::channel(32);
+            (
+                Some(ProcessLeaderboardTask::new(history, leaderboard_sender)),
+                Some(ProcessDistributeLeaderboardHandlingTask::new(
+                    client_thread_state.clone(),
+                    leaderboard_receiver,
+                )),
+            )
+        },
+        None => (None, None),
+    };
+
     let process_leaf_stream_handle = ProcessLeafAndBlockPairStreamTask::new(
         leaf_and_block_pair_receiver,
         data_state.clone(),
@@ -174,6 +231,8 @@ pub async fn create_node_validator_processing(
         process_distribute_block_detail_handle: Some(process_distribute_block_detail_handle),
         process_distribute_node_identity_handle: Some(process_distribute_node_identity_handle),
         process_distribute_voters_handle: Some(process_distribute_voters_handle),
+        process_distribute_leaderboard_handle,
+        process_leaderboard_handle,
         process_leaf_stream_handle: Some(process_leaf_stream_handle),
         process_node_identity_stream_handle: Some(process_node_identity_stream_handle),
         process_url_stream_handle: Some(process_url_stream_handle),
@@ -216,6 +275,7 @@ mod test {
                     .unwrap(),
             ],
             port: 9000,
+            history: Default::default(),
         })
         .await;
     }
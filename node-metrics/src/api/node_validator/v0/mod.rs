@@ -21,7 +21,7 @@ use hotshot_types::{
 };
 use prometheus_parse::{Sample, Scrape};
 use serde::{Deserialize, Serialize};
-use tide_disco::{api::ApiError, socket::Connection, Api};
+use tide_disco::{api::ApiError, socket::Connection, Api, Error as _, RequestParams, StatusCode};
 use tokio::{spawn, task::JoinHandle};
 use url::Url;
 use vbs::version::{StaticVersion, StaticVersionType, Version};
@@ -29,6 +29,7 @@ use vbs::version::{StaticVersion, StaticVersionType, Version};
 use crate::service::{
     client_message::{ClientMessage, InternalClientMessage},
     data_state::{LocationDetails, NodeIdentity},
+    history::{HistoryStorage, HistoryStorageError, UptimeWindow},
     server_message::ServerMessage,
 };
 
@@ -80,7 +81,10 @@ impl tide_disco::Error for Error {
     }
 
     fn status(&self) -> tide_disco::StatusCode {
-        tide_disco::StatusCode::INTERNAL_SERVER_ERROR
+        match self {
+            Self::UnhandledTideDisco(status, _) => *status,
+            Self::UnhandledSurfDisco(..) => tide_disco::StatusCode::INTERNAL_SERVER_ERROR,
+        }
     }
 }
 
@@ -165,12 +169,55 @@ pub trait StateClientMessageSender<K> {
     fn sender(&self) -> Sender<InternalClientMessage<K>>;
 }
 
+/// [StateHistoryStorage] allows for the retrieval of the [HistoryStorage], if
+/// the service has been configured with one.
+pub trait StateHistoryStorage {
+    fn history(&self) -> Option<&HistoryStorage>;
+}
+
 #[derive(Debug)]
 pub enum EndpointError {}
 
+/// uptime_window determines the [UptimeWindow] that is requested by the given
+/// request, either as a specific `:epoch` or as a named `:window`.
+fn uptime_window(req: &RequestParams) -> Result<UptimeWindow, Error> {
+    let bad_request = |msg: String| Error::catch_all(StatusCode::BAD_REQUEST, msg);
+
+    if let Some(epoch) = req
+        .opt_integer_param("epoch")
+        .map_err(|err| bad_request(err.to_string()))?
+    {
+        return Ok(UptimeWindow::Epoch(epoch));
+    }
+
+    req.string_param("window")
+        .map_err(|err| bad_request(err.to_string()))?
+        .parse()
+        .map_err(bad_request)
+}
+
+/// history_storage retrieves the [HistoryStorage] from the given state, or
+/// fails the request if the service is not configured with one.
+fn history_storage<State: StateHistoryStorage>(state: &State) -> Result<&HistoryStorage, Error> {
+    state.history().ok_or_else(|| {
+        Error::catch_all(
+            StatusCode::NOT_FOUND,
+            "history storage is not configured for this service".to_string(),
+        )
+    })
+}
+
+fn history_storage_error(err: HistoryStorageError) -> Error {
+    Error::catch_all(StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+}
+
 pub fn define_api<State>() -> Result<Api<State, Error, Version01>, DefineApiError>
 where
-    State: StateClientMessageSender<Sender<ServerMessage>> + Send + Sync + 'static,
+    State: StateClientMessageSender<Sender<ServerMessage>>
+        + StateHistoryStorage
+        + Send
+        + Sync
+        + 'static,
 {
     let mut api = load_api::<State, Version01>(include_str!("./node_validator.toml"))?;
 
@@ -292,12 +339,49 @@ where
             .boxed()
         },
     )?;
+
+    api.at("validator_uptime", |req, state| {
+        async move {
+            let history = history_storage(state)?;
+            let public_key = req
+                .string_param("public_key")
+                .map_err(|err| Error::catch_all(StatusCode::BAD_REQUEST, err.to_string()))?;
+            let public_key = BLSPubKey::from_str(public_key).map_err(|err| {
+                Error::catch_all(
+                    StatusCode::BAD_REQUEST,
+                    format!("invalid public key {public_key}: {err}"),
+                )
+            })?;
+            let window = uptime_window(&req)?;
+
+            history
+                .validator_uptime(public_key, window)
+                .await
+                .map_err(history_storage_error)
+        }
+        .boxed()
+    })?
+    .at("leaderboard", |req, state| {
+        async move {
+            let history = history_storage(state)?;
+            let window = uptime_window(&req)?;
+
+            history
+                .leaderboard(window)
+                .await
+                .map_err(history_storage_error)
+        }
+        .boxed()
+    })?;
     Ok(api)
 }
 
 #[derive(Debug, Deserialize)]
 pub struct PublishHotShotConfig {
     pub known_nodes_with_stake: Vec<PeerConfig<SeqTypes>>,
+    /// The number of blocks per epoch, or 0 if epochs are not enabled.
+    #[serde(default)]
+    pub epoch_height: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -305,14 +389,12 @@ pub struct SequencerConfig {
     pub config: PublishHotShotConfig,
 }
 
-/// [get_stake_table_from_sequencer] retrieves the stake table from the
+/// [get_sequencer_config] retrieves the HotShot configuration from the
 /// Sequencer.  It expects a [surf_disco::Client] to be provided so that it can
-/// make the request to the Hotshot Query Service.  It will return a
-/// [StakeTable] that is populated with the data retrieved from the Hotshot
-/// Query Service.
-pub async fn get_stake_table_from_sequencer(
+/// make the request to the Hotshot Query Service.
+pub async fn get_sequencer_config(
     client: surf_disco::Client<hotshot_query_service::Error, Version01>,
-) -> Result<StakeTable<BLSPubKey, StateVerKey, CircuitField>, hotshot_query_service::Error> {
+) -> Result<SequencerConfig, hotshot_query_service::Error> {
     let request = client
         .get("config/hotshot")
         // We need to set the Accept header, otherwise the Content-Type
@@ -321,16 +403,20 @@ pub async fn get_stake_table_from_sequencer(
         .header("Accept", "application/json");
     let stake_table_result = request.send().await;
 
-    let sequencer_config: SequencerConfig = match stake_table_result {
-        Ok(public_hot_shot_config) => public_hot_shot_config,
+    match stake_table_result {
+        Ok(sequencer_config) => Ok(sequencer_config),
         Err(err) => {
             tracing::info!("retrieve stake table request failed: {}", err);
-            return Err(err);
+            Err(err)
         },
-    };
-
-    let public_hot_shot_config = sequencer_config.config;
+    }
+}
 
+/// [stake_table_from_config] creates a [StakeTable] that is populated with
+/// the known nodes of the given [PublishHotShotConfig].
+pub fn stake_table_from_config(
+    public_hot_shot_config: PublishHotShotConfig,
+) -> StakeTable<BLSPubKey, StateVerKey, CircuitField> {
     let mut stake_table = StakeTable::<BLSPubKey, StateVerKey, CircuitField>::new(
         public_hot_shot_config.known_nodes_with_stake.len(),
     );
@@ -348,7 +434,19 @@ pub async fn get_stake_table_from_sequencer(
     stake_table.advance();
     stake_table.advance();
 
-    Ok(stake_table)
+    stake_table
+}
+
+/// [get_stake_table_from_sequencer] retrieves the stake table from the
+/// Sequencer.  It expects a [surf_disco::Client] to be provided so that it can
+/// make the request to the Hotshot Query Service.  It will return a
+/// [StakeTable] that is populated with the data retrieved from the Hotshot
+/// Query Service.
+pub async fn get_stake_table_from_sequencer(
+    client: surf_disco::Client<hotshot_query_service::Error, Version01>,
+) -> Result<StakeTable<BLSPubKey, StateVerKey, CircuitField>, hotshot_query_service::Error> {
+    let sequencer_config = get_sequencer_config(client).await?;
+    Ok(stake_table_from_config(sequencer_config.config))
 }
 
 pub enum GetNodeIdentityFromUrlError {
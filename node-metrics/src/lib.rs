@@ -105,7 +105,10 @@ use futures::{
     channel::mpsc::{self, Sender},
     StreamExt,
 };
-use service::data_state::MAX_VOTERS_HISTORY;
+use service::{
+    data_state::MAX_VOTERS_HISTORY,
+    history::{HistoryOptions, HistoryStorage},
+};
 use tide_disco::App;
 use tokio::spawn;
 use url::Url;
@@ -113,7 +116,8 @@ use url::Url;
 use crate::{
     api::node_validator::v0::{
         create_node_validator_api::{create_node_validator_processing, NodeValidatorConfig},
-        BridgeLeafAndBlockStreamToSenderTask, StateClientMessageSender, STATIC_VER_0_1,
+        BridgeLeafAndBlockStreamToSenderTask, StateClientMessageSender, StateHistoryStorage,
+        STATIC_VER_0_1,
     },
     service::{client_message::InternalClientMessage, server_message::ServerMessage},
 };
@@ -172,6 +176,11 @@ pub struct Options {
         default_value = "9000"
     )]
     port: u16,
+
+    /// history configures the optional database that the history of the
+    /// network is persisted to.
+    #[clap(flatten)]
+    history: HistoryOptions,
 }
 
 impl Options {
@@ -190,12 +199,17 @@ impl Options {
     fn port(&self) -> u16 {
         self.port
     }
+
+    fn history(&self) -> &HistoryOptions {
+        &self.history
+    }
 }
 
 /// MainState represents the State of the application this is available to
 /// tide_disco.
 struct MainState {
     internal_client_message_sender: Sender<InternalClientMessage<Sender<ServerMessage>>>,
+    history: Option<HistoryStorage>,
 }
 
 impl StateClientMessageSender<Sender<ServerMessage>> for MainState {
@@ -204,15 +218,41 @@ impl StateClientMessageSender<Sender<ServerMessage>> for MainState {
     }
 }
 
+impl StateHistoryStorage for MainState {
+    fn history(&self) -> Option<&HistoryStorage> {
+        self.history.as_ref()
+    }
+}
+
 /// Run the service by itself.
 ///
 /// This function will run the node validator as its own service.  It has some
 /// options that allow it to be configured in order for it to operate
 /// effectively.
 pub async fn run_standalone_service(options: Options) {
+    let history = match options.history().connect().await {
+        Ok(history) => history,
+        Err(err) => {
+            panic!("error connecting to history storage: {}", err);
+        },
+    };
+
+    // If we have recorded history, we resume from the block after the last
+    // one recorded, so that there are no gaps in the history.
+    let last_recorded_block = match &history {
+        Some(history) => match history.last_block().await {
+            Ok(last_block) => last_block,
+            Err(err) => {
+                panic!("error loading last recorded block: {}", err);
+            },
+        },
+        None => None,
+    };
+
     let (internal_client_message_sender, internal_client_message_receiver) = mpsc::channel(32);
     let state = MainState {
         internal_client_message_sender,
+        history: history.clone(),
     };
 
     let mut app: App<_, api::node_validator::v0::Error> = App::with_state(state);
@@ -253,7 +293,9 @@ pub async fn run_standalone_service(options: Options) {
             }
         };
 
-        if let Some(block_height) = block_height {
+        if let Some((last_recorded_height, _)) = last_recorded_block {
+            last_recorded_height + 1
+        } else if let Some(block_height) = block_height {
             // We want to make sure that we have at least MAX_VOTERS_HISTORY blocks of
             // history that we are pulling
             block_height.saturating_sub(MAX_VOTERS_HISTORY as u64 + 1)
@@ -276,6 +318,7 @@ pub async fn run_standalone_service(options: Options) {
         NodeValidatorConfig {
             stake_table_url_base: options.stake_table_source_base_url().clone(),
             initial_node_public_base_urls: options.initial_node_public_base_urls().to_vec(),
+            history,
         },
         internal_client_message_receiver,
         leaf_and_block_pair_receiver,
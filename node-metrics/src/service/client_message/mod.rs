@@ -10,6 +10,7 @@ pub enum ClientMessage {
     SubscribeLatestBlock,
     SubscribeNodeIdentity,
     SubscribeVoters,
+    SubscribeLeaderboard,
 
     RequestBlocksSnapshot,
     RequestNodeIdentitySnapshot,
@@ -68,6 +69,7 @@ mod tests {
             ClientMessage::SubscribeLatestBlock,
             ClientMessage::SubscribeNodeIdentity,
             ClientMessage::SubscribeVoters,
+            ClientMessage::SubscribeLeaderboard,
             ClientMessage::RequestBlocksSnapshot,
             ClientMessage::RequestNodeIdentitySnapshot,
             ClientMessage::RequestHistogramSnapshot,
@@ -93,6 +95,7 @@ mod tests {
             ClientMessage::SubscribeLatestBlock,
             ClientMessage::SubscribeNodeIdentity,
             ClientMessage::SubscribeVoters,
+            ClientMessage::SubscribeLeaderboard,
             ClientMessage::RequestBlocksSnapshot,
             ClientMessage::RequestNodeIdentitySnapshot,
             ClientMessage::RequestHistogramSnapshot,
@@ -112,6 +115,7 @@ mod tests {
             ClientMessage::SubscribeLatestBlock,
             ClientMessage::SubscribeNodeIdentity,
             ClientMessage::SubscribeVoters,
+            ClientMessage::SubscribeLeaderboard,
             ClientMessage::RequestBlocksSnapshot,
             ClientMessage::RequestNodeIdentitySnapshot,
             ClientMessage::RequestHistogramSnapshot,
@@ -130,6 +134,7 @@ mod tests {
             ClientMessage::SubscribeLatestBlock,
             ClientMessage::SubscribeNodeIdentity,
             ClientMessage::SubscribeVoters,
+            ClientMessage::SubscribeLeaderboard,
             ClientMessage::RequestBlocksSnapshot,
             ClientMessage::RequestNodeIdentitySnapshot,
             ClientMessage::RequestHistogramSnapshot,
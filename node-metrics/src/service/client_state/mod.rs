@@ -14,6 +14,7 @@ use super::{
     client_id::ClientId,
     client_message::{ClientMessage, InternalClientMessage},
     data_state::{DataState, NodeIdentity},
+    history::ValidatorUptime,
     server_message::ServerMessage,
 };
 
@@ -49,6 +50,7 @@ pub struct ClientThreadState<K> {
     subscribed_latest_block: HashSet<ClientId>,
     subscribed_node_identity: HashSet<ClientId>,
     subscribed_voters: HashSet<ClientId>,
+    subscribed_leaderboard: HashSet<ClientId>,
     connection_id_counter: ClientId,
 }
 
@@ -58,6 +60,7 @@ impl<K> ClientThreadState<K> {
         subscribed_latest_block: HashSet<ClientId>,
         subscribed_node_identity: HashSet<ClientId>,
         subscribed_voters: HashSet<ClientId>,
+        subscribed_leaderboard: HashSet<ClientId>,
         connection_id_counter: ClientId,
     ) -> Self {
         Self {
@@ -65,6 +68,7 @@ impl<K> ClientThreadState<K> {
             subscribed_latest_block,
             subscribed_node_identity,
             subscribed_voters,
+            subscribed_leaderboard,
             connection_id_counter,
         }
     }
@@ -83,6 +87,9 @@ fn drop_client_client_thread_state_write_guard<K>(
     client_thread_state_write_guard
         .subscribed_node_identity
         .remove(client_id);
+    client_thread_state_write_guard
+        .subscribed_leaderboard
+        .remove(client_id);
 
     client
 }
@@ -220,6 +227,22 @@ pub async fn handle_client_message_subscribe_voters<K>(
     drop(client_thread_state_write_lock_guard);
 }
 
+/// [handle_client_message_subscribe_leaderboard] is a function that processes
+/// the client message to subscribe to the validator uptime leaderboard.
+pub async fn handle_client_message_subscribe_leaderboard<K>(
+    client_id: ClientId,
+    client_thread_state: Arc<RwLock<ClientThreadState<K>>>,
+) {
+    let mut client_thread_state_write_lock_guard = client_thread_state.write().await;
+
+    client_thread_state_write_lock_guard
+        .subscribed_leaderboard
+        .insert(client_id);
+
+    // Explicitly unlock
+    drop(client_thread_state_write_lock_guard);
+}
+
 /// [HandleRequestBlocksSnapshotsError] represents the scope of errors that can
 /// be returned from the [handle_client_message_request_blocks_snapshot] function.
 #[derive(Debug)]
@@ -638,6 +661,11 @@ where
             Ok(())
         },
 
+        InternalClientMessage::Request(client_id, ClientMessage::SubscribeLeaderboard) => {
+            handle_client_message_subscribe_leaderboard(client_id, client_thread_state).await;
+            Ok(())
+        },
+
         InternalClientMessage::Request(client_id, ClientMessage::RequestBlocksSnapshot) => {
             handle_client_message_request_blocks_snapshot(
                 client_id,
@@ -876,6 +904,65 @@ async fn handle_received_voters<K>(
     drop_failed_client_sends(client_thread_state, failed_client_sends).await;
 }
 
+/// [handle_received_leaderboard] is a function that processes a received
+/// leaderboard and will attempt to distribute the message to all of the
+/// clients that are subscribed to the leaderboard stream.
+async fn handle_received_leaderboard<K>(
+    client_thread_state: Arc<RwLock<ClientThreadState<K>>>,
+    leaderboard: Arc<Vec<ValidatorUptime>>,
+) where
+    K: Sink<ServerMessage, Error = SendError> + Clone + Unpin,
+{
+    let client_thread_state_read_lock_guard = client_thread_state.read().await;
+
+    // These are the clients who are subscribed to the leaderboard, that have
+    // an active ClientState within the system.
+    let leaderboard_subscribers = client_thread_state_read_lock_guard
+        .subscribed_leaderboard
+        .iter()
+        .map(|client_id| {
+            (
+                client_id,
+                client_thread_state_read_lock_guard.clients.get(client_id),
+            )
+        })
+        .filter(|(_, client)| client.is_some());
+
+    // We collect the results of sending the leaderboard to the clients.
+    let client_send_result_future = leaderboard_subscribers.map(|(client_id, client)| {
+        let leaderboard = leaderboard.clone();
+        async move {
+            // This is guaranteed to be a some now
+            let client = client.unwrap();
+            let mut sender = client.sender.clone();
+            let send_result = sender
+                .send(ServerMessage::LatestLeaderboard(leaderboard))
+                .await;
+
+            (client_id, send_result)
+        }
+    });
+
+    let client_send_results = futures::future::join_all(client_send_result_future).await;
+
+    // These are the clients we failed to send the message to.  We copy these
+    // here so we can drop our read lock.
+    let failed_client_sends = client_send_results
+        .into_iter()
+        .filter(|(_, send_result)| send_result.is_err())
+        .map(|(client_id, _)| *client_id)
+        .collect::<Vec<_>>();
+
+    // Explicitly Drop the read lock.
+    drop(client_thread_state_read_lock_guard);
+
+    if failed_client_sends.is_empty() {
+        return;
+    }
+
+    drop_failed_client_sends(client_thread_state, failed_client_sends).await;
+}
+
 /// InternalClientMessageProcessingTask represents an async task for
 /// InternalClientMessages, and making the appropriate updates to the
 /// [ClientThreadState] and [DataState].
@@ -1165,6 +1252,76 @@ impl Drop for ProcessDistributeVotersHandlingTask {
     }
 }
 
+/// [ProcessDistributeLeaderboardHandlingTask] represents an async task for
+/// processing the incoming leaderboards and distributing them to all
+/// subscribed clients.
+pub struct ProcessDistributeLeaderboardHandlingTask {
+    pub task_handle: Option<JoinHandle<()>>,
+}
+
+impl ProcessDistributeLeaderboardHandlingTask {
+    /// [new] creates a new [ProcessDistributeLeaderboardHandlingTask] with
+    /// the given client_thread_state and leaderboard_receiver.
+    ///
+    /// Calling this function will start an async task that will start
+    /// processing.  The handle for the async task is stored within the
+    /// returned state.
+    pub fn new<S, K>(
+        client_thread_state: Arc<RwLock<ClientThreadState<K>>>,
+        leaderboard_receiver: S,
+    ) -> Self
+    where
+        S: Stream<Item = Arc<Vec<ValidatorUptime>>> + Send + Sync + Unpin + 'static,
+        K: Sink<ServerMessage, Error = SendError> + Clone + Send + Sync + Unpin + 'static,
+    {
+        let task_handle = spawn(Self::process_distribute_leaderboard_handling_stream(
+            client_thread_state.clone(),
+            leaderboard_receiver,
+        ));
+
+        Self {
+            task_handle: Some(task_handle),
+        }
+    }
+
+    /// [process_distribute_leaderboard_handling_stream] is a function that
+    /// processes the [Stream] of incoming leaderboards and distributes them
+    /// to all subscribed clients.
+    async fn process_distribute_leaderboard_handling_stream<S, K>(
+        client_thread_state: Arc<RwLock<ClientThreadState<K>>>,
+        mut stream: S,
+    ) where
+        S: Stream<Item = Arc<Vec<ValidatorUptime>>> + Unpin,
+        K: Sink<ServerMessage, Error = SendError> + Clone + Unpin,
+    {
+        loop {
+            let leaderboard_result = stream.next().await;
+
+            let leaderboard = if let Some(leaderboard) = leaderboard_result {
+                leaderboard
+            } else {
+                tracing::error!(
+                    "leaderboard stream closed.  shutting down client handling stream.",
+                );
+                return;
+            };
+
+            handle_received_leaderboard(client_thread_state.clone(), leaderboard).await
+        }
+    }
+}
+
+/// [drop] implementation for [ProcessDistributeLeaderboardHandlingTask] that
+/// will cancel the task if it is still running.
+impl Drop for ProcessDistributeLeaderboardHandlingTask {
+    fn drop(&mut self) {
+        let task_handle = self.task_handle.take();
+        if let Some(task_handle) = task_handle {
+            task_handle.abort();
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use std::{sync::Arc, time::Duration};
@@ -1210,6 +1367,7 @@ pub mod tests {
             subscribed_latest_block: Default::default(),
             subscribed_node_identity: Default::default(),
             subscribed_voters: Default::default(),
+            subscribed_leaderboard: Default::default(),
             connection_id_counter: ClientId::from_count(1),
         }
     }
@@ -28,6 +28,7 @@ pub use node_identity::NodeIdentity;
 use time::OffsetDateTime;
 use tokio::{spawn, task::JoinHandle};
 
+use super::history::{BlockRecord, HistoryStorage, LeaderLookup, Participation};
 use crate::api::node_validator::v0::LeafAndBlock;
 
 /// MAX_HISTORY represents the last N records that are stored within the
@@ -47,6 +48,9 @@ pub struct DataState {
     stake_table: StakeTable<BLSPubKey, StateVerKey, CircuitField>,
     // Do we need any other data at the moment?
     node_identity: Vec<NodeIdentity>,
+    history: Option<(HistoryStorage, Arc<dyn LeaderLookup>)>,
+    // The height and view of the most recently decided block.
+    last_decided: Option<(u64, u64)>,
 }
 
 impl DataState {
@@ -70,9 +74,26 @@ impl DataState {
             latest_voters,
             stake_table,
             node_identity,
+            history: None,
+            last_decided: None,
         }
     }
 
+    /// set_history configures the [HistoryStorage] that every processed block
+    /// is recorded to, and the [LeaderLookup] that the leaders of the views
+    /// are attributed with.  `last_decided` is the height and view of the
+    /// most recently recorded block, if any, so that views that failed while
+    /// the service was not running can still be attributed to their leaders.
+    pub fn set_history(
+        &mut self,
+        history: HistoryStorage,
+        leaders: Arc<dyn LeaderLookup>,
+        last_decided: Option<(u64, u64)>,
+    ) {
+        self.history = Some((history, leaders));
+        self.last_decided = last_decided;
+    }
+
     pub fn latest_blocks(&self) -> impl Iterator<Item = &BlockDetail<SeqTypes>> {
         self.latest_blocks.iter()
     }
@@ -265,6 +286,10 @@ where
     let stable_table_entries_vec = stake_table
         .try_iter(SnapshotVersion::LastEpochStart)
         .map_or(vec![], |into_iter| into_iter.collect::<Vec<_>>());
+    let stake_table_keys = stable_table_entries_vec
+        .iter()
+        .map(|(key, ..)| *key)
+        .collect::<Vec<_>>();
 
     // We have a BitVec of voters who signed the QC.
     // We can use this to determine the weight of the QC
@@ -291,6 +316,35 @@ where
         },
     );
 
+    let height = block_detail.height;
+    let view = *leaf.leaf().view_number();
+    let history_record = data_state_write_lock_guard.history.clone().map(|history| {
+        // Any views between the previously decided block and this one
+        // failed, so their leaders missed their proposals.  We can only
+        // determine this when we have seen the directly preceding block.
+        let missed_views = match data_state_write_lock_guard.last_decided {
+            Some((last_height, last_view)) if last_height + 1 == height => last_view + 1..view,
+            _ => 0..0,
+        };
+
+        let record = BlockRecord {
+            detail: create_block_detail_from_block(&block),
+            view,
+            voters: voters_bitvec.clone(),
+            participation: Participation {
+                proposer: None,
+                participants: stake_table_keys
+                    .iter()
+                    .map(|key| (*key, voters_set.contains(key)))
+                    .collect(),
+                missed_leaders: vec![],
+            },
+        };
+
+        (history, record, missed_views)
+    });
+    data_state_write_lock_guard.last_decided = Some((height, view));
+
     data_state_write_lock_guard
         .latest_blocks
         .push_back(block_detail);
@@ -300,6 +354,23 @@ where
 
     drop(data_state_write_lock_guard);
 
+    if let Some(((history, leaders), mut record, missed_views)) = history_record {
+        // The leaders are looked up from the membership of the network once
+        // the lock is released, as this requires a round trip to a node.
+        record.participation.proposer = leaders.leader(view, height).await;
+        for view in missed_views {
+            if let Some(leader) = leaders.leader(view, height).await {
+                record.participation.missed_leaders.push(leader);
+            }
+        }
+
+        // A failure to record history should not stop the processing of the
+        // real-time data, so we only log it.
+        if let Err(err) = history.record_block(&record).await {
+            tracing::error!("failed to record block {} in history: {}", height, err);
+        }
+    }
+
     if let Err(err) = block_sender.send(block_detail_copy).await {
         // We have an error that prevents us from continuing
         return Err(ProcessLeafError::BlockSendError(err));
@@ -0,0 +1,854 @@
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use bitvec::vec::BitVec;
+use espresso_types::SeqTypes;
+use futures::{channel::mpsc::SendError, Sink, SinkExt};
+use hotshot_query_service::explorer::BlockDetail;
+use hotshot_types::{signature_key::BLSPubKey, utils::epoch_from_block_number};
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgPoolOptions, PgPool, Postgres, QueryBuilder, Row};
+use tokio::{spawn, task::JoinHandle, time::sleep};
+
+use crate::api::node_validator::v0::Version01;
+
+/// SECONDS_PER_HOUR is the width of the time buckets that the uptime
+/// counters are stored in.
+const SECONDS_PER_HOUR: i64 = 60 * 60;
+
+/// LEADERBOARD_UPDATE_INTERVAL is the interval at which the leaderboard is
+/// recomputed and distributed to subscribed clients.
+pub const LEADERBOARD_UPDATE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// [HistoryOptions] configures the optional Postgres database that the
+/// history of the network is persisted to.
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct HistoryOptions {
+    /// postgres_url is the connection url of the Postgres database that
+    /// block and voter history is persisted to.
+    ///
+    /// When this is not provided the history is only kept in memory, is lost
+    /// on restart, and uptime and leaderboard queries are unavailable.
+    #[clap(long, env = "ESPRESSO_NODE_VALIDATOR_POSTGRES_URL")]
+    pub postgres_url: Option<String>,
+
+    /// postgres_max_connections is the maximum number of connections kept
+    /// in the connection pool to the history database.
+    #[clap(
+        long,
+        env = "ESPRESSO_NODE_VALIDATOR_POSTGRES_MAX_CONNECTIONS",
+        default_value = "10"
+    )]
+    pub postgres_max_connections: u32,
+}
+
+impl HistoryOptions {
+    /// connect establishes a connection to the configured history database,
+    /// if any, and runs any outstanding migrations.
+    pub async fn connect(&self) -> Result<Option<HistoryStorage>, HistoryStorageError> {
+        let Some(url) = &self.postgres_url else {
+            return Ok(None);
+        };
+
+        HistoryStorage::connect(url, self.postgres_max_connections)
+            .await
+            .map(Some)
+    }
+}
+
+/// [HistoryStorageError] represents the errors that can occur when reading
+/// from or writing to the [HistoryStorage].
+#[derive(Debug)]
+pub enum HistoryStorageError {
+    Sqlx(sqlx::Error),
+    Migrate(sqlx::migrate::MigrateError),
+    Bincode(bincode::Error),
+    InvalidPublicKey(String),
+}
+
+impl std::fmt::Display for HistoryStorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryStorageError::Sqlx(err) => write!(f, "database error: {}", err),
+            HistoryStorageError::Migrate(err) => write!(f, "migration error: {}", err),
+            HistoryStorageError::Bincode(err) => write!(f, "encoding error: {}", err),
+            HistoryStorageError::InvalidPublicKey(key) => {
+                write!(f, "invalid public key stored in history: {}", key)
+            },
+        }
+    }
+}
+
+impl std::error::Error for HistoryStorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HistoryStorageError::Sqlx(err) => Some(err),
+            HistoryStorageError::Migrate(err) => Some(err),
+            HistoryStorageError::Bincode(err) => Some(err),
+            HistoryStorageError::InvalidPublicKey(_) => None,
+        }
+    }
+}
+
+impl From<sqlx::Error> for HistoryStorageError {
+    fn from(err: sqlx::Error) -> Self {
+        HistoryStorageError::Sqlx(err)
+    }
+}
+
+impl From<sqlx::migrate::MigrateError> for HistoryStorageError {
+    fn from(err: sqlx::migrate::MigrateError) -> Self {
+        HistoryStorageError::Migrate(err)
+    }
+}
+
+impl From<bincode::Error> for HistoryStorageError {
+    fn from(err: bincode::Error) -> Self {
+        HistoryStorageError::Bincode(err)
+    }
+}
+
+/// [UptimeWindow] is the range of history that uptime is computed over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UptimeWindow {
+    /// The last 24 hours.
+    Day,
+    /// The last 7 days.
+    Week,
+    /// The most recent epoch that has been recorded.
+    CurrentEpoch,
+    /// A specific epoch.
+    Epoch(u64),
+}
+
+impl FromStr for UptimeWindow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(UptimeWindow::Day),
+            "week" => Ok(UptimeWindow::Week),
+            "epoch" => Ok(UptimeWindow::CurrentEpoch),
+            _ => Err(format!(
+                "unknown uptime window {s}, expected one of day, week or epoch"
+            )),
+        }
+    }
+}
+
+/// [ValidatorUptime] summarizes the participation of a single validator over
+/// an [UptimeWindow].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorUptime {
+    pub public_key: BLSPubKey,
+    /// Number of views in which the validator was in the stake table.
+    pub views: u64,
+    /// Number of those views in which the validator signed the QC.
+    pub votes: u64,
+    /// Number of views in which the validator was the leader.
+    pub leader_views: u64,
+    /// Number of those views in which the validator's proposal was decided.
+    pub proposals: u64,
+    /// Number of views in which the validator was the leader but no
+    /// proposal was decided.
+    pub missed_proposals: u64,
+    /// Fraction of the validator's duties, votes and proposals, that it
+    /// performed.
+    pub uptime: f64,
+}
+
+impl ValidatorUptime {
+    pub fn new(
+        public_key: BLSPubKey,
+        views: u64,
+        votes: u64,
+        leader_views: u64,
+        proposals: u64,
+    ) -> Self {
+        let duties = views + leader_views;
+        let uptime = if duties == 0 {
+            0.0
+        } else {
+            (votes + proposals) as f64 / duties as f64
+        };
+
+        Self {
+            public_key,
+            views,
+            votes,
+            leader_views,
+            proposals,
+            missed_proposals: leader_views.saturating_sub(proposals),
+            uptime,
+        }
+    }
+}
+
+/// [BlockRecord] is everything that is recorded in the [HistoryStorage] for
+/// a single decided block.
+pub struct BlockRecord {
+    pub detail: BlockDetail<SeqTypes>,
+    pub view: u64,
+    /// The voters of the block, in node identity order.
+    pub voters: BitVec<u16>,
+    pub participation: Participation,
+}
+
+/// [Participation] describes which validators had a duty in deciding a
+/// block, and whether they performed it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Participation {
+    /// The leader of the view that the block was proposed in.
+    pub proposer: Option<BLSPubKey>,
+    /// Every validator in the stake table, and whether it signed the QC.
+    pub participants: Vec<(BLSPubKey, bool)>,
+    /// The leaders of the views that failed between the previously decided
+    /// block and this one.
+    pub missed_leaders: Vec<BLSPubKey>,
+}
+
+/// [UptimeCounters] are the increments to a validator's uptime counters
+/// from a single [BlockRecord].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct UptimeCounters {
+    views: i64,
+    votes: i64,
+    leader_views: i64,
+    proposals: i64,
+}
+
+impl Participation {
+    fn uptime_counters(&self) -> HashMap<BLSPubKey, UptimeCounters> {
+        let mut counters = HashMap::<BLSPubKey, UptimeCounters>::new();
+
+        for (key, voted) in &self.participants {
+            let entry = counters.entry(*key).or_default();
+            entry.views += 1;
+            entry.votes += *voted as i64;
+        }
+
+        if let Some(proposer) = self.proposer {
+            let entry = counters.entry(proposer).or_default();
+            entry.leader_views += 1;
+            entry.proposals += 1;
+        }
+
+        for key in &self.missed_leaders {
+            counters.entry(*key).or_default().leader_views += 1;
+        }
+
+        counters
+    }
+}
+
+/// [LeaderLookup] determines the leader of a view from the membership of
+/// the network.
+#[async_trait]
+pub trait LeaderLookup: Send + Sync {
+    /// leader returns the leader of the given view, which precedes or
+    /// decided the block at the given height, or [None] if it cannot be
+    /// determined.
+    async fn leader(&self, view: u64, height: u64) -> Option<BLSPubKey>;
+}
+
+/// [SequencerLeaderLookup] retrieves the leader of a view from the
+/// membership of a sequencer node, through its node API.
+pub struct SequencerLeaderLookup {
+    client: surf_disco::Client<hotshot_query_service::Error, Version01>,
+    epoch_height: u64,
+}
+
+impl SequencerLeaderLookup {
+    /// new creates a [SequencerLeaderLookup] that queries the sequencer
+    /// behind the given client.  `epoch_height` is the number of blocks per
+    /// epoch, or 0 if epochs are not enabled.
+    pub fn new(
+        client: surf_disco::Client<hotshot_query_service::Error, Version01>,
+        epoch_height: u64,
+    ) -> Self {
+        Self {
+            client,
+            epoch_height,
+        }
+    }
+}
+
+#[async_trait]
+impl LeaderLookup for SequencerLeaderLookup {
+    async fn leader(&self, view: u64, height: u64) -> Option<BLSPubKey> {
+        let path = if self.epoch_height == 0 {
+            format!("node/leader/{view}")
+        } else {
+            let epoch = epoch_from_block_number(height, self.epoch_height);
+            format!("node/leader/{view}/{epoch}")
+        };
+
+        match self.client.get::<BLSPubKey>(&path).send().await {
+            Ok(leader) => Some(leader),
+            Err(err) => {
+                tracing::warn!("failed to retrieve the leader of view {}: {}", view, err);
+                None
+            },
+        }
+    }
+}
+
+/// [HistoryStorage] persists the blocks and voters processed by the service
+/// and the per validator uptime counters derived from them.
+// PgPool is wrapped in an Arc internally so cloning here increments the reference count
+#[derive(Clone, Debug)]
+pub struct HistoryStorage {
+    pool: PgPool,
+    epoch_height: u64,
+}
+
+impl HistoryStorage {
+    pub async fn connect(url: &str, max_connections: u32) -> Result<Self, HistoryStorageError> {
+        let pool = PgPoolOptions::new()
+            .max_connections(max_connections)
+            .connect(url)
+            .await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        Ok(Self {
+            pool,
+            epoch_height: 0,
+        })
+    }
+
+    /// with_epoch_height sets the number of blocks per epoch that is used to
+    /// bucket the uptime counters by epoch.
+    pub fn with_epoch_height(mut self, epoch_height: u64) -> Self {
+        self.epoch_height = epoch_height;
+        self
+    }
+
+    /// record_block persists the given [BlockRecord] and updates the uptime
+    /// counters of every validator that had a duty in it.
+    ///
+    /// Recording the same block twice, as can happen when the leaf stream is
+    /// restarted, has no effect.
+    pub async fn record_block(&self, record: &BlockRecord) -> Result<(), HistoryStorageError> {
+        let height = record.detail.height;
+        let epoch = epoch_from_block_number(height, self.epoch_height);
+        let timestamp = record.detail.time.0.unix_timestamp();
+        let hour = timestamp.div_euclid(SECONDS_PER_HOUR);
+
+        let mut tx = self.pool.begin().await?;
+
+        let inserted = sqlx::query(
+            "INSERT INTO block_history (height, view, epoch, timestamp, proposer, detail, voters)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (height) DO NOTHING",
+        )
+        .bind(height as i64)
+        .bind(record.view as i64)
+        .bind(epoch as i64)
+        .bind(timestamp)
+        .bind(
+            record
+                .participation
+                .proposer
+                .map(|key| key.to_string())
+                .unwrap_or_default(),
+        )
+        .bind(bincode::serialize(&record.detail)?)
+        .bind(bincode::serialize(&record.voters)?)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if inserted == 0 {
+            // We have already recorded this block, so its counters have
+            // already been applied.
+            return Ok(());
+        }
+
+        let counters = record.participation.uptime_counters();
+        if !counters.is_empty() {
+            let mut query = QueryBuilder::<Postgres>::new(
+                "INSERT INTO validator_uptime (public_key, epoch, hour, views, votes, leader_views, proposals) ",
+            );
+            query.push_values(counters, |mut row, (key, counters)| {
+                row.push_bind(key.to_string())
+                    .push_bind(epoch as i64)
+                    .push_bind(hour)
+                    .push_bind(counters.views)
+                    .push_bind(counters.votes)
+                    .push_bind(counters.leader_views)
+                    .push_bind(counters.proposals);
+            });
+            query.push(
+                " ON CONFLICT (public_key, epoch, hour) DO UPDATE SET
+                    views = validator_uptime.views + excluded.views,
+                    votes = validator_uptime.votes + excluded.votes,
+                    leader_views = validator_uptime.leader_views + excluded.leader_views,
+                    proposals = validator_uptime.proposals + excluded.proposals",
+            );
+            query.build().execute(&mut *tx).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// last_block returns the height and view of the most recently recorded
+    /// block, if any.
+    pub async fn last_block(&self) -> Result<Option<(u64, u64)>, HistoryStorageError> {
+        let row: Option<(i64, i64)> =
+            sqlx::query_as("SELECT height, view FROM block_history ORDER BY height DESC LIMIT 1")
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(row.map(|(height, view)| (height as u64, view as u64)))
+    }
+
+    /// latest_blocks loads the most recent `limit` blocks and their voters,
+    /// in ascending order of height.
+    pub async fn latest_blocks(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<(BlockDetail<SeqTypes>, BitVec<u16>)>, HistoryStorageError> {
+        let rows: Vec<(Vec<u8>, Vec<u8>)> = sqlx::query_as(
+            "SELECT detail, voters FROM block_history ORDER BY height DESC LIMIT $1",
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .rev()
+            .map(|(detail, voters)| {
+                Ok((
+                    bincode::deserialize(&detail)?,
+                    bincode::deserialize(&voters)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// validator_uptime computes the uptime of a single validator over the
+    /// given window.
+    pub async fn validator_uptime(
+        &self,
+        public_key: BLSPubKey,
+        window: UptimeWindow,
+    ) -> Result<ValidatorUptime, HistoryStorageError> {
+        let uptime = self
+            .query_uptime(window, Some(public_key))
+            .await?
+            .pop()
+            .unwrap_or_else(|| ValidatorUptime::new(public_key, 0, 0, 0, 0));
+
+        Ok(uptime)
+    }
+
+    /// leaderboard computes the uptime of every validator over the given
+    /// window, ordered from the highest uptime to the lowest.
+    pub async fn leaderboard(
+        &self,
+        window: UptimeWindow,
+    ) -> Result<Vec<ValidatorUptime>, HistoryStorageError> {
+        let mut leaderboard = self.query_uptime(window, None).await?;
+        leaderboard.sort_by(|lhs, rhs| {
+            rhs.uptime
+                .total_cmp(&lhs.uptime)
+                .then_with(|| rhs.views.cmp(&lhs.views))
+        });
+
+        Ok(leaderboard)
+    }
+
+    async fn query_uptime(
+        &self,
+        window: UptimeWindow,
+        public_key: Option<BLSPubKey>,
+    ) -> Result<Vec<ValidatorUptime>, HistoryStorageError> {
+        let mut query = QueryBuilder::<Postgres>::new(
+            "SELECT public_key, sum(views)::BIGINT, sum(votes)::BIGINT,
+                sum(leader_views)::BIGINT, sum(proposals)::BIGINT
+                FROM validator_uptime WHERE ",
+        );
+
+        let now_hour = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_secs() as i64)
+            / SECONDS_PER_HOUR;
+        match window {
+            UptimeWindow::Day => {
+                query.push("hour >= ").push_bind(now_hour - 24);
+            },
+            UptimeWindow::Week => {
+                query.push("hour >= ").push_bind(now_hour - 24 * 7);
+            },
+            UptimeWindow::CurrentEpoch => {
+                query.push("epoch = (SELECT max(epoch) FROM block_history)");
+            },
+            UptimeWindow::Epoch(epoch) => {
+                query.push("epoch = ").push_bind(epoch as i64);
+            },
+        }
+
+        if let Some(public_key) = public_key {
+            query
+                .push(" AND public_key = ")
+                .push_bind(public_key.to_string());
+        }
+        query.push(" GROUP BY public_key");
+
+        let rows = query.build().fetch_all(&self.pool).await?;
+        rows.into_iter()
+            .map(|row| {
+                let public_key: String = row.try_get(0)?;
+                let public_key = BLSPubKey::from_str(&public_key)
+                    .map_err(|_| HistoryStorageError::InvalidPublicKey(public_key))?;
+                let views: i64 = row.try_get(1)?;
+                let votes: i64 = row.try_get(2)?;
+                let leader_views: i64 = row.try_get(3)?;
+                let proposals: i64 = row.try_get(4)?;
+
+                Ok(ValidatorUptime::new(
+                    public_key,
+                    views as u64,
+                    votes as u64,
+                    leader_views as u64,
+                    proposals as u64,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// [ProcessLeaderboardTask] represents the task that is responsible for
+/// periodically computing the leaderboard of the last day from the
+/// [HistoryStorage] and sending it to the given [Sink].
+pub struct ProcessLeaderboardTask {
+    pub task_handle: Option<JoinHandle<()>>,
+}
+
+impl ProcessLeaderboardTask {
+    /// [new] creates a new [ProcessLeaderboardTask] that will recompute the
+    /// leaderboard every [LEADERBOARD_UPDATE_INTERVAL].
+    ///
+    /// Calling this function will create an asynchronous task that will start
+    /// processing immediately. The handle for the task will be stored within
+    /// the returned structure.
+    pub fn new<K>(history: HistoryStorage, leaderboard_sender: K) -> Self
+    where
+        K: Sink<Arc<Vec<ValidatorUptime>>, Error = SendError> + Send + Unpin + 'static,
+    {
+        let task_handle = spawn(Self::process_leaderboard(history, leaderboard_sender));
+
+        Self {
+            task_handle: Some(task_handle),
+        }
+    }
+
+    async fn process_leaderboard<K>(history: HistoryStorage, mut leaderboard_sender: K)
+    where
+        K: Sink<Arc<Vec<ValidatorUptime>>, Error = SendError> + Unpin,
+    {
+        loop {
+            match history.leaderboard(UptimeWindow::Day).await {
+                Ok(leaderboard) => {
+                    if let Err(err) = leaderboard_sender.send(Arc::new(leaderboard)).await {
+                        tracing::error!("leaderboard sender closed: {}", err);
+                        return;
+                    }
+                },
+                Err(err) => {
+                    tracing::warn!("failed to compute leaderboard: {}", err);
+                },
+            }
+
+            sleep(LEADERBOARD_UPDATE_INTERVAL).await;
+        }
+    }
+}
+
+/// [Drop] implementation for [ProcessLeaderboardTask] that will cancel the
+/// task if it is still running.
+impl Drop for ProcessLeaderboardTask {
+    fn drop(&mut self) {
+        let task_handle = self.task_handle.take();
+        if let Some(task_handle) = task_handle {
+            task_handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use bitvec::vec::BitVec;
+    use espresso_types::{NodeState, SeqTypes, ValidatedState};
+    use hotshot_query_service::{
+        availability::BlockQueryData, data_source::storage::sql::testing::TmpDb,
+        explorer::Timestamp, testing::mocks::MockVersions,
+    };
+    use hotshot_types::{signature_key::BLSPubKey, traits::signature_key::SignatureKey};
+    use time::OffsetDateTime;
+
+    use super::{
+        BlockRecord, HistoryStorage, Participation, UptimeCounters, UptimeWindow, ValidatorUptime,
+    };
+    use crate::service::data_state::create_block_detail_from_block;
+
+    #[test]
+    fn test_uptime_window_from_str() {
+        assert_eq!("day".parse::<UptimeWindow>(), Ok(UptimeWindow::Day));
+        assert_eq!("week".parse::<UptimeWindow>(), Ok(UptimeWindow::Week));
+        assert_eq!(
+            "epoch".parse::<UptimeWindow>(),
+            Ok(UptimeWindow::CurrentEpoch)
+        );
+        assert!("month".parse::<UptimeWindow>().is_err());
+    }
+
+    #[test]
+    fn test_validator_uptime() {
+        let (public_key, _) = BLSPubKey::generated_from_seed_indexed([0; 32], 0);
+
+        let uptime = ValidatorUptime::new(public_key, 8, 6, 2, 1);
+        assert_eq!(uptime.missed_proposals, 1);
+        assert_eq!(uptime.uptime, 0.7);
+
+        let uptime = ValidatorUptime::new(public_key, 0, 0, 0, 0);
+        assert_eq!(uptime.uptime, 0.0);
+    }
+
+    #[test]
+    fn test_participation_uptime_counters() {
+        let keys = (0..3)
+            .map(|i| BLSPubKey::generated_from_seed_indexed([0; 32], i).0)
+            .collect::<Vec<_>>();
+
+        let participation = Participation {
+            proposer: Some(keys[0]),
+            participants: vec![(keys[0], true), (keys[1], false), (keys[2], true)],
+            missed_leaders: vec![keys[2]],
+        };
+        let counters = participation.uptime_counters();
+
+        assert_eq!(
+            counters[&keys[0]],
+            UptimeCounters {
+                views: 1,
+                votes: 1,
+                leader_views: 1,
+                proposals: 1,
+            }
+        );
+        assert_eq!(
+            counters[&keys[1]],
+            UptimeCounters {
+                views: 1,
+                votes: 0,
+                leader_views: 0,
+                proposals: 0,
+            }
+        );
+        assert_eq!(
+            counters[&keys[2]],
+            UptimeCounters {
+                views: 1,
+                votes: 1,
+                leader_views: 1,
+                proposals: 0,
+            }
+        );
+    }
+
+    async fn connect(db: &TmpDb) -> HistoryStorage {
+        let url = format!(
+            "postgres://postgres:password@{}:{}/postgres",
+            db.host(),
+            db.port()
+        );
+        HistoryStorage::connect(&url, 2)
+            .await
+            .unwrap()
+            .with_epoch_height(10)
+    }
+
+    async fn block_record(
+        height: u64,
+        timestamp: i64,
+        participation: Participation,
+    ) -> BlockRecord {
+        let block = BlockQueryData::<SeqTypes>::genesis::<MockVersions>(
+            &ValidatedState::default(),
+            &NodeState::mock(),
+        )
+        .await;
+        let mut detail = create_block_detail_from_block(&block);
+        detail.height = height;
+        detail.time = Timestamp(OffsetDateTime::from_unix_timestamp(timestamp).unwrap());
+
+        BlockRecord {
+            detail,
+            view: height + 1,
+            voters: BitVec::from_iter(participation.participants.iter().map(|(_, voted)| *voted)),
+            participation,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_record_block() {
+        let db = TmpDb::init().await;
+        let history = connect(&db).await;
+        let keys = (0..2)
+            .map(|i| BLSPubKey::generated_from_seed_indexed([0; 32], i).0)
+            .collect::<Vec<_>>();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        assert_eq!(history.last_block().await.unwrap(), None);
+
+        let mut records = vec![];
+        for height in 1..=3 {
+            let record = block_record(
+                height,
+                now,
+                Participation {
+                    proposer: Some(keys[0]),
+                    participants: vec![(keys[0], true), (keys[1], height != 2)],
+                    missed_leaders: vec![],
+                },
+            )
+            .await;
+            history.record_block(&record).await.unwrap();
+            records.push(record);
+        }
+        // Recording a block again has no effect.
+        history.record_block(&records[2]).await.unwrap();
+
+        assert_eq!(history.last_block().await.unwrap(), Some((3, 4)));
+        let latest = history.latest_blocks(2).await.unwrap();
+        assert_eq!(
+            latest
+                .iter()
+                .map(|(detail, _)| detail.height)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(latest[0].1, records[1].voters);
+        assert_eq!(latest[1].1, records[2].voters);
+
+        // The counters of the duplicate block are not applied twice.
+        let uptime = history
+            .validator_uptime(keys[1], UptimeWindow::Day)
+            .await
+            .unwrap();
+        assert_eq!(uptime, ValidatorUptime::new(keys[1], 3, 2, 0, 0));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_uptime_and_leaderboard() {
+        let db = TmpDb::init().await;
+        let history = connect(&db).await;
+        let keys = (0..3)
+            .map(|i| BLSPubKey::generated_from_seed_indexed([0; 32], i).0)
+            .collect::<Vec<_>>();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let two_days_ago = now - 2 * 24 * 60 * 60;
+
+        // A block in epoch 1, two days ago, which keys[2] did not vote for.
+        history
+            .record_block(
+                &block_record(
+                    9,
+                    two_days_ago,
+                    Participation {
+                        proposer: Some(keys[0]),
+                        participants: vec![(keys[0], true), (keys[1], true), (keys[2], false)],
+                        missed_leaders: vec![],
+                    },
+                )
+                .await,
+            )
+            .await
+            .unwrap();
+        // A block in epoch 2, now, after a view in which keys[1] failed to
+        // propose.
+        history
+            .record_block(
+                &block_record(
+                    12,
+                    now,
+                    Participation {
+                        proposer: Some(keys[2]),
+                        participants: vec![(keys[0], true), (keys[1], false), (keys[2], true)],
+                        missed_leaders: vec![keys[1]],
+                    },
+                )
+                .await,
+            )
+            .await
+            .unwrap();
+
+        // Only the recent block counts towards the last day.
+        assert_eq!(
+            history
+                .validator_uptime(keys[1], UptimeWindow::Day)
+                .await
+                .unwrap(),
+            ValidatorUptime::new(keys[1], 1, 0, 1, 0)
+        );
+        // Both blocks count towards the last week.
+        assert_eq!(
+            history
+                .validator_uptime(keys[0], UptimeWindow::Week)
+                .await
+                .unwrap(),
+            ValidatorUptime::new(keys[0], 2, 2, 1, 1)
+        );
+        // The windows for epochs only include the blocks in that epoch.
+        assert_eq!(
+            history
+                .validator_uptime(keys[2], UptimeWindow::CurrentEpoch)
+                .await
+                .unwrap(),
+            ValidatorUptime::new(keys[2], 1, 1, 1, 1)
+        );
+        assert_eq!(
+            history
+                .validator_uptime(keys[2], UptimeWindow::Epoch(1))
+                .await
+                .unwrap(),
+            ValidatorUptime::new(keys[2], 1, 0, 0, 0)
+        );
+        // A validator without history has no uptime.
+        let unknown = BLSPubKey::generated_from_seed_indexed([0; 32], 3).0;
+        assert_eq!(
+            history
+                .validator_uptime(unknown, UptimeWindow::Week)
+                .await
+                .unwrap(),
+            ValidatorUptime::new(unknown, 0, 0, 0, 0)
+        );
+
+        // The leaderboard is ordered from the highest uptime to the lowest.
+        let leaderboard = history.leaderboard(UptimeWindow::Week).await.unwrap();
+        assert_eq!(
+            leaderboard,
+            vec![
+                ValidatorUptime::new(keys[0], 2, 2, 1, 1),
+                ValidatorUptime::new(keys[2], 2, 1, 1, 1),
+                ValidatorUptime::new(keys[1], 2, 1, 1, 0),
+            ]
+        );
+    }
+}
@@ -2,5 +2,6 @@ pub mod client_id;
 pub mod client_message;
 pub mod client_state;
 pub mod data_state;
+pub mod history;
 pub mod node_type;
 pub mod server_message;
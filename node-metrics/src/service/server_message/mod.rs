@@ -5,7 +5,7 @@ use espresso_types::SeqTypes;
 use hotshot_query_service::explorer::{BlockDetail, ExplorerHistograms};
 use serde::{Deserialize, Serialize};
 
-use super::{client_id::ClientId, data_state::NodeIdentity};
+use super::{client_id::ClientId, data_state::NodeIdentity, history::ValidatorUptime};
 
 /// [ServerMessage] represents the messages that the server can send to the
 /// client for a response.
@@ -26,6 +26,11 @@ pub enum ServerMessage {
     /// voters that have arrived.
     LatestVoters(BitVec<u16>),
 
+    /// LatestLeaderboard is a message that is meant to show the most recent
+    /// validator uptime leaderboard, ordered from the highest uptime to the
+    /// lowest.
+    LatestLeaderboard(Arc<Vec<ValidatorUptime>>),
+
     /// BlocksSnapshot is a message that is sent in response to a request for
     /// the snapshot of block information that is available.
     BlocksSnapshot(Arc<Vec<BlockDetail<SeqTypes>>>),
@@ -50,6 +55,7 @@ impl PartialEq for ServerMessage {
             (Self::LatestBlock(lhs), Self::LatestBlock(rhs)) => lhs == rhs,
             (Self::LatestNodeIdentity(lhs), Self::LatestNodeIdentity(rhs)) => lhs == rhs,
             (Self::LatestVoters(lhs), Self::LatestVoters(rhs)) => lhs == rhs,
+            (Self::LatestLeaderboard(lhs), Self::LatestLeaderboard(rhs)) => lhs == rhs,
             (Self::BlocksSnapshot(lhs), Self::BlocksSnapshot(rhs)) => lhs == rhs,
             (Self::NodeIdentitySnapshot(lhs), Self::NodeIdentitySnapshot(rhs)) => lhs == rhs,
             (Self::HistogramSnapshot(_), Self::HistogramSnapshot(_)) => false,
@@ -0,0 +1,16 @@
+-- Every change to a fee account balance, derived from the L1 deposits credited and the builder fees
+-- charged by each decided block. `idx` orders the entries within a block, `kind` is one of
+-- 'deposit', 'fee_paid' or 'fee_received', and `amount` is a decimal string, since it may not fit in
+-- a native integer type. `data` is the serialized `FeeLedgerEntry`, including the L1 transaction
+-- for deposits and the counterparty for fees.
+CREATE TABLE fee_ledger (
+    height BIGINT NOT NULL,
+    idx BIGINT NOT NULL,
+    account TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    data BYTEA NOT NULL,
+    PRIMARY KEY (height, idx)
+);
+
+CREATE INDEX fee_ledger_account_idx ON fee_ledger (account, height, idx);
@@ -0,0 +1,10 @@
+-- Evidence that the leader of a view dispersed an incorrectly encoded block. `leader` is the
+-- incriminated leader's public key and `payload_hash` the incorrectly encoded VID commitment it
+-- signed. `data` is the serialized `VidEncodingEvidence`. Unlike consensus data, evidence is never
+-- garbage collected.
+CREATE TABLE vid_encoding_evidence (
+    view BIGINT PRIMARY KEY,
+    leader TEXT NOT NULL,
+    payload_hash TEXT NOT NULL,
+    data BYTEA NOT NULL
+);
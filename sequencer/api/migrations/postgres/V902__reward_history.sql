@@ -0,0 +1,24 @@
+-- The rewards paid out in each block, derived from decided leaves and the stake table of the
+-- block's epoch. `data` is the serialized `RewardDistribution`.
+CREATE TABLE reward_distribution (
+    height BIGINT PRIMARY KEY,
+    epoch BIGINT NOT NULL,
+    validator TEXT NOT NULL,
+    data BYTEA NOT NULL
+);
+
+CREATE INDEX reward_distribution_epoch_validator_idx ON reward_distribution (epoch, validator);
+
+-- Rewards accumulated by each account from each validator in each epoch. Commission kept by the
+-- validator is tracked separately from rewards it earns by delegating to itself. Amounts are
+-- decimal strings, since they may not fit in a native integer type.
+CREATE TABLE epoch_reward (
+    epoch BIGINT NOT NULL,
+    validator TEXT NOT NULL,
+    account TEXT NOT NULL,
+    commission BOOLEAN NOT NULL,
+    amount TEXT NOT NULL,
+    PRIMARY KEY (epoch, validator, account, commission)
+);
+
+CREATE INDEX epoch_reward_account_idx ON epoch_reward (account);
@@ -0,0 +1,10 @@
+-- The number of quorum certificates each validator's vote was included in during each epoch,
+-- counted from the certificate justifying each decided block. `last_height` is the last block
+-- counted for the validator, so that indexing the same block twice does not count its votes twice.
+CREATE TABLE epoch_votes (
+    epoch BIGINT NOT NULL,
+    validator TEXT NOT NULL,
+    votes BIGINT NOT NULL,
+    last_height BIGINT NOT NULL,
+    PRIMARY KEY (epoch, validator)
+);
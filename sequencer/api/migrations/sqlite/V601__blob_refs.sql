@@ -0,0 +1,8 @@
+-- DA proposals and VID shares may be stored in a separate blob store. In that
+-- case, the `data` column holds an empty value and the `blob` column holds the
+-- content address of the data in the blob store.
+ALTER TABLE da_proposal2
+    ADD COLUMN blob TEXT;
+
+ALTER TABLE vid_share2
+    ADD COLUMN blob TEXT;
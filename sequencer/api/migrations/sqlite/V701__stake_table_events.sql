@@ -0,0 +1,14 @@
+-- Stake table events fetched from L1, so that each new epoch only needs to fetch events emitted
+-- since the last L1 block processed. Only events from finalized L1 blocks are stored.
+CREATE TABLE stake_table_events (
+    l1_block BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    event BLOB NOT NULL,
+    PRIMARY KEY (l1_block, log_index)
+);
+
+-- The L1 block up to which all stake table events have been stored.
+CREATE TABLE stake_table_events_l1_block (
+    id bool PRIMARY KEY DEFAULT true,
+    last_l1_block BIGINT NOT NULL
+);
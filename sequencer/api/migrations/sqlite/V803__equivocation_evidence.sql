@@ -0,0 +1,11 @@
+-- Evidence that a node signed two conflicting proposals or votes in the same view. `offender` is
+-- the incriminated node's public key and `kind` the kind of message it equivocated on (e.g.
+-- `quorum_vote`). `data` is the serialized `EquivocationEvidence`. Unlike consensus data,
+-- evidence is never garbage collected.
+CREATE TABLE equivocation_evidence (
+    view BIGINT NOT NULL,
+    offender TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    data BLOB NOT NULL,
+    PRIMARY KEY (view, offender, kind)
+);
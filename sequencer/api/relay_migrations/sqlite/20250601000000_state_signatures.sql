@@ -0,0 +1,21 @@
+-- Light client state signatures collected by the state relay server. `state` is the signed light
+-- client state and `signer` the signer's state verification key, both tagged base64 encoded, and
+-- `data` is the serialized `StateSignatureRequestBody`. Several relay servers may share these
+-- tables, so that a signature posted to any of them counts towards the bundles served by all.
+CREATE TABLE state_signatures (
+    height BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    state TEXT NOT NULL,
+    signer TEXT NOT NULL,
+    data BLOB NOT NULL,
+    PRIMARY KEY (height, state, signer)
+);
+
+-- Signature bundles which reached the signing threshold, one per block height. `data` is the
+-- serialized `StateSignaturesBundle`. Unlike individual signatures, bundles are never pruned.
+CREATE TABLE state_signature_bundles (
+    height BIGINT PRIMARY KEY,
+    epoch BIGINT NOT NULL,
+    data BLOB NOT NULL
+);
+CREATE INDEX state_signature_bundles_epoch_idx ON state_signature_bundles (epoch);
@@ -1,17 +1,23 @@
-use std::{pin::Pin, sync::Arc};
+use std::{collections::HashMap, pin::Pin, sync::Arc};
 
+use alloy::primitives::Address;
 use anyhow::{bail, Context};
 use async_lock::RwLock;
 use async_once_cell::Lazy;
 use async_trait::async_trait;
-use committable::Commitment;
-use data_source::{CatchupDataSource, StakeTableDataSource, SubmitDataSource};
+use committable::{Commitment, Committable};
+use data_source::{CatchupDataSource, StakeTableDataSource, SubmitDataSource, TxStatusDataSource};
 use derivative::Derivative;
 use espresso_types::{
     config::PublicNetworkConfig,
-    retain_accounts,
+    retain_accounts, retain_reward_accounts,
+    submit::{SubmitError, TransactionStatus},
     v0::traits::SequencerPersistence,
-    v0_1::{RewardAccount, RewardAccountProof, RewardMerkleTree},
+    v0_1::{
+        EpochRewards, FeeLedgerPage, FeeStatement, RewardAccount, RewardAccountProof, RewardAmount,
+        RewardDistribution, RewardMerkleTree, RewardTotals,
+    },
+    v0_3::{Validator, ValidatorActivity},
     v0_99::ChainConfig,
     AccountQueryData, BlockMerkleTree, FeeAccount, FeeAccountProof, FeeMerkleTree, Leaf2,
     NodeState, PubKey, Transaction, ValidatedState,
@@ -25,29 +31,34 @@ use hotshot_events_service::events_source::{
 };
 use hotshot_query_service::data_source::ExtensibleDataSource;
 use hotshot_types::{
-    data::ViewNumber,
+    data::{EpochNumber, ViewNumber},
+    equivocation::EquivocationEvidence,
     event::Event,
     light_client::StateSignatureRequestBody,
     network::NetworkConfig,
     traits::{
+        election::Membership,
         network::ConnectedNetwork,
-        node_implementation::{NodeType, Versions},
+        node_implementation::{ConsensusTime, NodeType, Versions},
         ValidatedState as _,
     },
     utils::{View, ViewInner},
-    vote::HasViewNumber,
     PeerConfig,
 };
-use itertools::Itertools;
-use jf_merkle_tree::{
-    ForgetableMerkleTreeScheme, ForgetableUniversalMerkleTreeScheme, LookupResult,
-    MerkleTreeScheme, UniversalMerkleTreeScheme,
-};
+use indexmap::IndexMap;
+use jf_merkle_tree::MerkleTreeScheme;
 
-use self::data_source::{HotShotConfigDataSource, NodeStateDataSource, StateSignatureDataSource};
+use self::data_source::{
+    EvidenceDataSource, FeeLedgerDataSource, HotShotConfigDataSource, NodeStateDataSource,
+    RewardHistoryDataSource, StateSignatureDataSource, ValidatorActivityDataSource,
+    ValidatorSetDataSource,
+};
 use crate::{
-    catchup::CatchupStorage, context::Consensus, state_signature::StateSigner, SeqTypes,
-    SequencerApiVersion, SequencerContext,
+    catchup::{leaf_chain_from_undecided, CatchupStorage},
+    context::Consensus,
+    state_signature::StateSigner,
+    tx_status::TxStatusTracker,
+    SeqTypes, SequencerApiVersion, SequencerContext,
 };
 
 pub mod data_source;
@@ -68,6 +79,7 @@ type BoxLazy<T> = Pin<Arc<Lazy<T, BoxFuture<'static, T>>>>;
 struct ConsensusState<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> {
     state_signer: Arc<RwLock<StateSigner<SequencerApiVersion>>>,
     event_streamer: Arc<RwLock<EventsStreamer<SeqTypes>>>,
+    tx_status: Arc<TxStatusTracker>,
     node_state: NodeState,
     network_config: NetworkConfig<SeqTypes>,
 
@@ -82,6 +94,7 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions>
         Self {
             state_signer: ctx.state_signer(),
             event_streamer: ctx.event_streamer(),
+            tx_status: ctx.tx_status(),
             node_state: ctx.node_state(),
             network_config: ctx.network_config(),
             handle: ctx.consensus(),
@@ -115,6 +128,10 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> ApiState
         &self.consensus.as_ref().get().await.get_ref().event_streamer
     }
 
+    async fn tx_status(&self) -> &TxStatusTracker {
+        &self.consensus.as_ref().get().await.get_ref().tx_status
+    }
+
     async fn consensus(&self) -> Arc<RwLock<Consensus<N, P, V>>> {
         Arc::clone(&self.consensus.as_ref().get().await.get_ref().handle)
     }
@@ -165,6 +182,25 @@ impl<N: ConnectedNetwork<PubKey>, D: Send + Sync, V: Versions, P: SequencerPersi
     async fn submit(&self, tx: Transaction) -> anyhow::Result<()> {
         self.as_ref().submit(tx).await
     }
+
+    async fn submit_batch(&self, txs: Vec<Transaction>) -> Vec<Result<(), SubmitError>> {
+        self.as_ref().submit_batch(txs).await
+    }
+}
+
+impl<N: ConnectedNetwork<PubKey>, D: Send + Sync, V: Versions, P: SequencerPersistence>
+    TxStatusDataSource for StorageState<N, P, D, V>
+{
+    async fn get_tx_status(&self, hash: Commitment<Transaction>) -> Option<TransactionStatus> {
+        self.as_ref().get_tx_status(hash).await
+    }
+
+    async fn subscribe_tx_status(
+        &self,
+        hash: Commitment<Transaction>,
+    ) -> BoxStream<'static, TransactionStatus> {
+        self.as_ref().subscribe_tx_status(hash).await
+    }
 }
 
 impl<N: ConnectedNetwork<PubKey>, D: Sync, V: Versions, P: SequencerPersistence>
@@ -182,6 +218,15 @@ impl<N: ConnectedNetwork<PubKey>, D: Sync, V: Versions, P: SequencerPersistence>
     async fn get_stake_table_current(&self) -> Vec<PeerConfig<SeqTypes>> {
         self.as_ref().get_stake_table_current().await
     }
+
+    /// Get the leader of `view` in `epoch`
+    async fn get_leader(
+        &self,
+        view: <SeqTypes as NodeType>::View,
+        epoch: Option<<SeqTypes as NodeType>::Epoch>,
+    ) -> anyhow::Result<PubKey> {
+        self.as_ref().get_leader(view, epoch).await
+    }
 }
 impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence>
     StakeTableDataSource<SeqTypes> for ApiState<N, P, V>
@@ -211,12 +256,80 @@ impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence>
 
         self.get_stake_table(epoch).await
     }
+
+    /// Get the leader of `view` in `epoch`
+    async fn get_leader(
+        &self,
+        view: <SeqTypes as NodeType>::View,
+        epoch: Option<<SeqTypes as NodeType>::Epoch>,
+    ) -> anyhow::Result<PubKey> {
+        let leader = self
+            .node_state()
+            .await
+            .coordinator
+            .membership()
+            .read()
+            .await
+            .leader(view, epoch)
+            .context(format!("leader for view {view} is not available"))?;
+        Ok(leader)
+    }
+}
+
+impl<N: ConnectedNetwork<PubKey>, D: Sync, V: Versions, P: SequencerPersistence>
+    ValidatorSetDataSource for StorageState<N, P, D, V>
+{
+    async fn get_validators(
+        &self,
+        epoch: Option<EpochNumber>,
+    ) -> anyhow::Result<(EpochNumber, IndexMap<Address, Validator<PubKey>>)> {
+        self.as_ref().get_validators(epoch).await
+    }
+}
+
+impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence> ValidatorSetDataSource
+    for ApiState<N, P, V>
+{
+    async fn get_validators(
+        &self,
+        epoch: Option<EpochNumber>,
+    ) -> anyhow::Result<(EpochNumber, IndexMap<Address, Validator<PubKey>>)> {
+        let epoch = match epoch {
+            Some(epoch) => epoch,
+            None => self
+                .consensus()
+                .await
+                .read()
+                .await
+                .cur_epoch()
+                .await
+                .context("epochs are not enabled")?,
+        };
+        let validators = self
+            .node_state()
+            .await
+            .coordinator
+            .membership()
+            .read()
+            .await
+            .validators(&epoch)
+            .context(format!("validators for epoch {epoch} are not available"))?;
+        Ok((epoch, validators))
+    }
 }
 
 impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence> SubmitDataSource<N, P>
     for ApiState<N, P, V>
 {
     async fn submit(&self, tx: Transaction) -> anyhow::Result<()> {
+        self.submit_batch(vec![tx])
+            .await
+            .pop()
+            .context("missing submission result")??;
+        Ok(())
+    }
+
+    async fn submit_batch(&self, txs: Vec<Transaction>) -> Vec<Result<(), SubmitError>> {
         let handle = self.consensus().await;
 
         let consensus_read_lock = handle.read().await;
@@ -239,15 +352,45 @@ impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence> SubmitDa
         };
 
         let max_block_size: u64 = cf.max_block_size.into();
-        let txn_size = tx.payload().len() as u64;
+        let mut results = Vec::with_capacity(txs.len());
+        for tx in txs {
+            let size = tx.payload().len() as u64;
+
+            // reject transaction bigger than block size
+            if size > max_block_size {
+                results.push(Err(SubmitError::TooLarge {
+                    size,
+                    max_block_size,
+                }));
+                continue;
+            }
 
-        // reject transaction bigger than block size
-        if txn_size > max_block_size {
-            bail!("transaction size ({txn_size}) is greater than max_block_size ({max_block_size})")
+            let hash = tx.commit();
+            let res = consensus_read_lock
+                .submit_transaction(tx)
+                .await
+                .map_err(|err| SubmitError::MempoolRejected(err.to_string()));
+            if res.is_ok() {
+                self.tx_status().await.submitted(hash).await;
+            }
+            results.push(res);
         }
+        results
+    }
+}
 
-        consensus_read_lock.submit_transaction(tx).await?;
-        Ok(())
+impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence> TxStatusDataSource
+    for ApiState<N, P, V>
+{
+    async fn get_tx_status(&self, hash: Commitment<Transaction>) -> Option<TransactionStatus> {
+        self.tx_status().await.status(hash).await
+    }
+
+    async fn subscribe_tx_status(
+        &self,
+        hash: Commitment<Transaction>,
+    ) -> BoxStream<'static, TransactionStatus> {
+        self.tx_status().await.subscribe(hash).await
     }
 }
 
@@ -555,7 +698,7 @@ impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence> CatchupD
     }
 
     async fn get_leaf_chain(&self, height: u64) -> anyhow::Result<Vec<Leaf2>> {
-        let mut leaves = self
+        let leaves = self
             .consensus()
             .await
             .read()
@@ -564,36 +707,7 @@ impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence> CatchupD
             .read()
             .await
             .undecided_leaves();
-        leaves.sort_by_key(|l| l.view_number());
-        let (position, mut last_leaf) = leaves
-            .iter()
-            .find_position(|l| l.height() == height)
-            .context(format!("leaf chain not available for {height}"))?;
-        let mut chain = vec![last_leaf.clone()];
-        for leaf in leaves.iter().skip(position + 1) {
-            if leaf.justify_qc().view_number() == last_leaf.view_number() {
-                chain.push(leaf.clone());
-            } else {
-                continue;
-            }
-            if leaf.view_number() == last_leaf.view_number() + 1 {
-                // one away from decide
-                last_leaf = leaf;
-                break;
-            }
-            last_leaf = leaf;
-        }
-        // Make sure we got one more leaf to confirm the decide
-        for leaf in leaves
-            .iter()
-            .skip_while(|l| l.view_number() <= last_leaf.view_number())
-        {
-            if leaf.justify_qc().view_number() == last_leaf.view_number() {
-                chain.push(leaf.clone());
-                return Ok(chain);
-            }
-        }
-        bail!(format!("leaf chain not available for {height}"))
+        leaf_chain_from_undecided(leaves, height)
     }
 
     #[tracing::instrument(skip(self, _instance))]
@@ -615,25 +729,83 @@ impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence> CatchupD
                 "state not available for height {height}, view {view:?}"
             ))?;
 
-        let mut snapshot = RewardMerkleTree::from_commitment(state.reward_merkle_tree.commitment());
-        for account in accounts {
-            match state.reward_merkle_tree.universal_lookup(account) {
-                LookupResult::Ok(elem, proof) => {
-                    // This remember cannot fail, since we just constructed a valid proof, and are
-                    // remembering into a tree with the same commitment.
-                    snapshot.remember(account, *elem, proof).unwrap();
-                },
-                LookupResult::NotFound(proof) => {
-                    // Likewise this cannot fail.
-                    snapshot.non_membership_remember(*account, proof).unwrap()
-                },
-                LookupResult::NotInMemory => {
-                    bail!("missing account {account}");
-                },
-            }
-        }
+        retain_reward_accounts(&state.reward_merkle_tree, accounts.iter().copied())
+    }
+}
+
+impl<N, P, D, V> ValidatorActivityDataSource for StorageState<N, P, D, V>
+where
+    N: ConnectedNetwork<PubKey>,
+    V: Versions,
+    P: SequencerPersistence,
+    D: ValidatorActivityDataSource + Sync,
+{
+    async fn get_validator_activity(
+        &self,
+        epoch: u64,
+    ) -> anyhow::Result<HashMap<Address, ValidatorActivity>> {
+        self.inner().get_validator_activity(epoch).await
+    }
+}
+
+impl<N, P, D, V> RewardHistoryDataSource for StorageState<N, P, D, V>
+where
+    N: ConnectedNetwork<PubKey>,
+    V: Versions,
+    P: SequencerPersistence,
+    D: RewardHistoryDataSource + Sync,
+{
+    async fn get_block_rewards(&self, height: u64) -> anyhow::Result<Option<RewardDistribution>> {
+        self.inner().get_block_rewards(height).await
+    }
+
+    async fn get_epoch_rewards(
+        &self,
+        epoch: u64,
+        validator: RewardAccount,
+    ) -> anyhow::Result<EpochRewards> {
+        self.inner().get_epoch_rewards(epoch, validator).await
+    }
+
+    async fn get_delegator_epoch_reward(
+        &self,
+        epoch: u64,
+        validator: RewardAccount,
+        delegator: RewardAccount,
+    ) -> anyhow::Result<RewardAmount> {
+        self.inner()
+            .get_delegator_epoch_reward(epoch, validator, delegator)
+            .await
+    }
 
-        Ok(snapshot)
+    async fn get_reward_totals(&self, account: RewardAccount) -> anyhow::Result<RewardTotals> {
+        self.inner().get_reward_totals(account).await
+    }
+}
+
+impl<N, P, D, V> FeeLedgerDataSource for StorageState<N, P, D, V>
+where
+    N: ConnectedNetwork<PubKey>,
+    V: Versions,
+    P: SequencerPersistence,
+    D: FeeLedgerDataSource + Sync,
+{
+    async fn get_fee_statement(
+        &self,
+        account: FeeAccount,
+        from: u64,
+        to: u64,
+    ) -> anyhow::Result<FeeStatement> {
+        self.inner().get_fee_statement(account, from, to).await
+    }
+
+    async fn get_fee_history(
+        &self,
+        account: FeeAccount,
+        before: Option<(u64, u64)>,
+        limit: usize,
+    ) -> anyhow::Result<FeeLedgerPage> {
+        self.inner().get_fee_history(account, before, limit).await
     }
 }
 
@@ -676,6 +848,39 @@ impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence> StateSig
     }
 }
 
+impl<N: ConnectedNetwork<PubKey>, D: Sync, V: Versions, P: SequencerPersistence> EvidenceDataSource
+    for StorageState<N, P, D, V>
+{
+    async fn get_equivocation_evidence(
+        &self,
+        from: u64,
+        to: u64,
+    ) -> anyhow::Result<Vec<EquivocationEvidence<SeqTypes>>> {
+        self.as_ref().get_equivocation_evidence(from, to).await
+    }
+}
+
+impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence> EvidenceDataSource
+    for ApiState<N, P, V>
+{
+    async fn get_equivocation_evidence(
+        &self,
+        from: u64,
+        to: u64,
+    ) -> anyhow::Result<Vec<EquivocationEvidence<SeqTypes>>> {
+        let storage = self.consensus().await.read().await.storage();
+        let evidence = storage
+            .read()
+            .await
+            .load_equivocation_evidence(ViewNumber::new(from), ViewNumber::new(to))
+            .await?
+            .into_values()
+            .flatten()
+            .collect();
+        Ok(evidence)
+    }
+}
+
 #[cfg(any(test, feature = "testing"))]
 pub mod test_helpers {
     use std::time::Duration;
@@ -1170,7 +1375,7 @@ pub mod test_helpers {
         client.connect(None).await;
 
         let hash = client
-            .post("submit/submit")
+            .post::<Commitment<Transaction>>("submit/submit")
             .body_json(&txn)
             .unwrap()
             .send()
@@ -1178,8 +1383,33 @@ pub mod test_helpers {
             .unwrap();
         assert_eq!(txn.commit(), hash);
 
+        // Subscribe to status updates for the transaction.
+        let mut status_updates = client
+            .socket(&format!("submit/status/stream/{hash}"))
+            .subscribe::<TransactionStatus>()
+            .await
+            .unwrap();
+
         // Wait for a Decide event containing transaction matching the one we sent
-        wait_for_decide_on_handle(&mut events, &txn).await;
+        let height = wait_for_decide_on_handle(&mut events, &txn).await;
+
+        // The status stream should end with the transaction being decided.
+        let mut last_status = None;
+        while let Some(status) = status_updates.next().await {
+            last_status = Some(status.unwrap());
+        }
+        let Some(TransactionStatus::Decided { height: h, .. }) = last_status else {
+            panic!("expected transaction to be decided, got {last_status:?}");
+        };
+        assert_eq!(h, height);
+
+        // Once decided, the status is also available directly.
+        let status = client
+            .get::<TransactionStatus>(&format!("submit/status/{hash}"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(status, last_status.unwrap());
     }
 
     /// Test the state signature API.
@@ -1392,7 +1622,10 @@ mod api_tests {
         traits::{EventConsumer, PersistenceOptions},
         Header, Leaf2, MockSequencerVersions, NamespaceId,
     };
-    use futures::{future, stream::StreamExt};
+    use futures::{
+        future,
+        stream::{StreamExt, TryStreamExt},
+    };
     use hotshot_example_types::node_types::{EpochsTestVersions, TestVersions};
     use hotshot_query_service::availability::{
         AvailabilityDataSource, BlockQueryData, StateCertQueryData, VidCommonQueryData,
@@ -1421,7 +1654,7 @@ mod api_tests {
 
     use super::{update::ApiEventConsumer, *};
     use crate::{
-        api::endpoints::NamespaceProofQueryData,
+        api::endpoints::{NamespaceBlockQueryData, NamespaceProofQueryData},
         network,
         persistence::no_storage::NoStorage,
         testing::{wait_for_decide_on_handle, TestConfigBuilder},
@@ -1495,6 +1728,26 @@ mod api_tests {
             .unwrap()
             .unwrap();
 
+        // The same data is available as a range and as a stream.
+        let range: Vec<NamespaceBlockQueryData> = client
+            .get(&format!(
+                "availability/block/0/{}/namespace/{ns_id}",
+                block_height + 1
+            ))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(range.len(), block_height + 1);
+        let stream: Vec<NamespaceBlockQueryData> = client
+            .socket(&format!("availability/stream/namespace/{ns_id}/0"))
+            .subscribe::<NamespaceBlockQueryData>()
+            .await
+            .unwrap()
+            .take(block_height + 1)
+            .try_collect()
+            .await
+            .unwrap();
+
         let mut found_txn = false;
         let mut found_empty_block = false;
         for block_num in 0..=block_height {
@@ -1529,6 +1782,12 @@ mod api_tests {
                 assert!(ns_query_res.transactions.is_empty());
             }
 
+            for ns_block in [&range[block_num], &stream[block_num]] {
+                assert_eq!(ns_block.header, header);
+                assert_eq!(ns_block.transactions, ns_query_res.transactions);
+                assert_eq!(ns_block.proof.is_some(), ns_query_res.proof.is_some());
+            }
+
             found_empty_block = found_empty_block || ns_query_res.transactions.is_empty();
 
             for txn in ns_query_res.transactions {
@@ -1919,6 +2178,48 @@ mod api_tests {
             );
         }
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    pub(crate) async fn test_leader_query<D: TestableSequencerDataSource>() {
+        setup_test();
+
+        let port = pick_unused_port().expect("No ports free");
+        let storage = D::create_storage().await;
+        let anvil = Anvil::new().spawn();
+        let l1 = anvil.endpoint().parse().unwrap();
+        let network_config = TestConfigBuilder::default().l1_url(l1).build();
+        let config = TestNetworkConfigBuilder::default()
+            .api_config(D::options(&storage, Options::with_port(port)))
+            .network_config(network_config)
+            .build();
+        let network = TestNetwork::new(config, MockSequencerVersions::new()).await;
+        let client: Client<ServerError, StaticVersion<0, 1>> =
+            Client::new(format!("http://localhost:{port}").parse().unwrap());
+        client.connect(None).await;
+
+        // The leader served by the API is the one chosen by the membership.
+        let membership = network.server.node_state().coordinator.membership().clone();
+        for view in 0..10 {
+            let expected = membership
+                .read()
+                .await
+                .leader(ViewNumber::new(view), None)
+                .unwrap();
+            let leader = client
+                .get::<PubKey>(&format!("node/leader/{view}"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(leader, expected);
+        }
+
+        // Epochs are not enabled, so there is no leader for any epoch.
+        client
+            .get::<PubKey>("node/leader/1/1")
+            .send()
+            .await
+            .unwrap_err();
+    }
 }
 
 #[cfg(test)]
@@ -1929,21 +2230,29 @@ mod test {
     };
 
     use alloy::{node_bindings::Anvil, primitives::U256, signers::local::LocalSigner};
+    use client::SequencerClient;
     use committable::{Commitment, Committable};
     use espresso_types::{
-        config::PublicHotShotConfig, traits::NullEventConsumer, BackoffParams, EpochVersion,
-        FeeAmount, FeeVersion, Header, MarketplaceVersion, MockSequencerVersions,
+        config::PublicHotShotConfig,
+        submit::SubmitResult,
+        traits::{
+            MembershipPersistence, NullEventConsumer, PersistenceOptions, SequencerPersistence,
+            StateCatchup,
+        },
+        v0::snapshot::StateSnapshot,
+        BackoffParams, BlockMerkleTree, EpochVersion, FeeAmount, FeeMerkleTree, FeeVersion, Header,
+        MarketplaceVersion, MockSequencerVersions, NamespaceId, NamespaceProofQueryData,
         SequencerVersions, TimeBasedUpgrade, Timestamp, Upgrade, UpgradeMode, UpgradeType,
-        ValidatedState, ViewBasedUpgrade, V0_1,
+        ValidatedState, ViewBasedUpgrade, FEE_MERKLE_TREE_HEIGHT, V0_1,
     };
     use futures::{
-        future::{self, join_all},
+        future::{self, join_all, TryFutureExt},
         stream::{StreamExt, TryStreamExt},
     };
     use hotshot::types::EventType;
     use hotshot_example_types::node_types::EpochsTestVersions;
     use hotshot_query_service::{
-        availability::{BlockQueryData, LeafQueryData, VidCommonQueryData},
+        availability::{self, BlockQueryData, LeafQueryData, VidCommonQueryData},
         types::HeightIndexed,
     };
     use hotshot_types::{
@@ -1952,7 +2261,10 @@ mod test {
         utils::epoch_from_block_number,
         ValidatorConfig,
     };
-    use jf_merkle_tree::prelude::{MerkleProof, Sha3Node};
+    use jf_merkle_tree::{
+        prelude::{MerkleProof, Sha3Node},
+        ForgetableMerkleTreeScheme,
+    };
     use portpicker::pick_unused_port;
     use sequencer_utils::{ser::FromStringOrInteger, test_utils::setup_test};
     use surf_disco::Client;
@@ -1960,9 +2272,13 @@ mod test {
         catchup_test_helper, spawn_dishonest_peer_catchup_api, state_signature_test_helper,
         status_test_helper, submit_test_helper, TestNetwork, TestNetworkConfigBuilder,
     };
-    use tide_disco::{app::AppHealth, error::ServerError, healthcheck::HealthStatus};
+    use tide_disco::{
+        app::AppHealth, error::ServerError, healthcheck::HealthStatus, Api, App, Error as _,
+        StatusCode,
+    };
     use time::OffsetDateTime;
-    use tokio::time::sleep;
+    use tokio::{spawn, time::sleep};
+    use url::Url;
     use vbs::version::{StaticVersion, StaticVersionType, Version};
 
     use self::{
@@ -1973,7 +2289,7 @@ mod test {
     use crate::{
         catchup::{NullStateCatchup, StatePeers},
         persistence::no_storage,
-        testing::{TestConfig, TestConfigBuilder},
+        testing::{wait_for_decide_on_handle, TestConfig, TestConfigBuilder},
     };
 
     #[tokio::test(flavor = "multi_thread")]
@@ -2008,6 +2324,81 @@ mod test {
         submit_test_helper(|opt| opt).await
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_submit_batch() {
+        setup_test();
+
+        let port = pick_unused_port().expect("No ports free");
+        let url = format!("http://localhost:{port}").parse().unwrap();
+        let client: Client<ServerError, StaticVersion<0, 1>> = Client::new(url);
+
+        let options = Options::with_port(port).submit(options::Submit {
+            allowed_namespaces: vec![1],
+            max_batch_size: 2,
+        });
+        let anvil = Anvil::new().spawn();
+        let l1 = anvil.endpoint_url();
+        let network_config = TestConfigBuilder::default().l1_url(l1).build();
+        let config = TestNetworkConfigBuilder::default()
+            .api_config(options)
+            .network_config(network_config)
+            .build();
+        let network = TestNetwork::new(config, MockSequencerVersions::new()).await;
+        let mut events = network.server.event_stream().await;
+
+        client.connect(None).await;
+
+        // Submit one transaction in an allowed namespace and one in a disallowed namespace.
+        let allowed = Transaction::new(NamespaceId::from(1_u32), vec![1, 2, 3, 4]);
+        let disallowed = Transaction::new(NamespaceId::from(2_u32), vec![5, 6, 7, 8]);
+        let results = client
+            .post::<Vec<SubmitResult>>("submit/batch")
+            .body_json(&vec![allowed.clone(), disallowed.clone()])
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            results,
+            vec![
+                Ok(allowed.commit()),
+                Err(SubmitError::NamespaceNotAllowed(disallowed.namespace()))
+            ]
+        );
+
+        // The single transaction endpoint enforces the same restriction.
+        let err = client
+            .post::<Commitment<Transaction>>("submit/submit")
+            .body_json(&disallowed)
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            client
+                .post::<Commitment<Transaction>>("submit/submit")
+                .body_json(&allowed)
+                .unwrap()
+                .send()
+                .await
+                .unwrap(),
+            allowed.commit()
+        );
+
+        // Batches which are too large are rejected entirely.
+        client
+            .post::<Vec<SubmitResult>>("submit/batch")
+            .body_json(&vec![allowed.clone(); 3])
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+
+        // The allowed transaction from the first batch gets sequenced.
+        wait_for_decide_on_handle(&mut events, &allowed).await;
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn state_signature_test_without_query_module() {
         state_signature_test_helper(|opt| opt).await
@@ -2966,6 +3357,325 @@ mod test {
         assert_eq!(chain, new_chain);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_sequencer_client() {
+        setup_test();
+
+        let ns_id = NamespaceId::from(42_u32);
+        let txn = Transaction::new(ns_id, vec![1, 2, 3, 4]);
+
+        let port = pick_unused_port().expect("No ports free");
+        let anvil = Anvil::new().spawn();
+        let l1 = anvil.endpoint_url();
+        let network_config = TestConfigBuilder::default().l1_url(l1).build();
+        let config = TestNetworkConfigBuilder::default()
+            .api_config(Options::with_port(port).submit(Default::default()))
+            .network_config(network_config)
+            .build();
+        let network = TestNetwork::new(config, MockSequencerVersions::new()).await;
+        let mut events = network.server.event_stream().await;
+
+        // A URL where nothing is listening, which the client must fail over from.
+        let dead: Url = format!("http://localhost:{}", pick_unused_port().unwrap())
+            .parse()
+            .unwrap();
+        let live: Url = format!("http://localhost:{port}").parse().unwrap();
+        SequencerClient::new(dead.clone())
+            .get_height()
+            .await
+            .unwrap_err();
+        let client = SequencerClient::with_failover([dead, live]).unwrap();
+
+        assert_eq!(client.submit(&txn).await.unwrap(), txn.commit());
+        let block_height = wait_for_decide_on_handle(&mut events, &txn).await;
+        tracing::info!(block_height, "transaction sequenced");
+
+        // Wait for the query service to catch up to the block with our transaction.
+        let header = client.stream_headers(block_height).next().await.unwrap();
+        assert_eq!(header.height(), block_height);
+        assert!(client.get_height().await.unwrap() > block_height);
+
+        // The namespace proof verifies against the header, and proves our transaction.
+        let ns = client
+            .get_verified_namespace(block_height, ns_id)
+            .await
+            .unwrap();
+        assert_eq!(ns.header, header);
+        assert_eq!(ns.transactions, vec![txn.clone()]);
+
+        // A namespace which is absent from the block verifies as empty.
+        let other_ns_id = NamespaceId::from(43_u32);
+        let empty = client
+            .get_verified_namespace(block_height, other_ns_id)
+            .await
+            .unwrap();
+        assert!(empty.transactions.is_empty());
+        assert!(empty.proof.is_none());
+
+        // Tampering with the response is detected.
+        let common = client.get_vid_common(block_height).await.unwrap();
+        let honest = client.get_namespace(block_height, ns_id).await.unwrap();
+        honest.verify(&header, common.common(), ns_id).unwrap();
+        honest
+            .verify(&header, common.common(), other_ns_id)
+            .unwrap_err();
+        let mut extra = honest.clone();
+        extra
+            .transactions
+            .push(Transaction::new(ns_id, vec![5, 6, 7, 8]));
+        extra.verify(&header, common.common(), ns_id).unwrap_err();
+        let omitted = NamespaceProofQueryData {
+            proof: None,
+            transactions: vec![],
+        };
+        omitted.verify(&header, common.common(), ns_id).unwrap_err();
+
+        // The namespace stream agrees with the range endpoint, and every block in it verifies.
+        let range = client
+            .get_namespace_range(0, block_height + 1, ns_id)
+            .await
+            .unwrap();
+        let stream = client
+            .stream_namespace(0, ns_id)
+            .take(block_height as usize + 1)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(range, stream);
+        for block in &stream {
+            let common = client.get_vid_common(block.header.height()).await.unwrap();
+            block.verify(common.common(), ns_id).unwrap();
+        }
+
+        // Serve the same blocks from a server which drops the connection after every block, so
+        // that the client has to reconnect and resume from the next height each time.
+        let toml = toml::from_str::<toml::Value>(include_str!("../api/availability.toml")).unwrap();
+        let mut api = Api::<(), availability::Error, SequencerApiVersion>::new(toml).unwrap();
+        let blocks = Arc::new(stream.clone());
+        api.stream("stream_namespace", move |req, _state| {
+            let blocks = blocks.clone();
+            async move {
+                let height: usize = req.integer_param("height")?;
+                Ok(futures::stream::iter(blocks.get(height).cloned().map(Ok)))
+            }
+            .try_flatten_stream()
+            .boxed()
+        })
+        .unwrap();
+        let mut app = App::<_, availability::Error>::with_state(());
+        app.register_module("availability", api).unwrap();
+        let flaky_port = pick_unused_port().expect("No ports free");
+        let flaky: Url = format!("http://localhost:{flaky_port}").parse().unwrap();
+        let server = spawn({
+            let flaky = flaky.clone();
+            async move {
+                let _ = app.serve(flaky, SequencerApiVersion::instance()).await;
+            }
+        });
+
+        let reconnected = SequencerClient::new(flaky)
+            .stream_namespace(0, ns_id)
+            .take(stream.len())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(reconnected, stream);
+        server.abort();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_state_snapshot() {
+        setup_test();
+
+        const NUM_NODES: usize = 5;
+        let storage = join_all((0..NUM_NODES).map(|_| SqlDataSource::create_storage())).await;
+        let persistence: [_; NUM_NODES] = storage
+            .iter()
+            .map(<SqlDataSource as TestableSequencerDataSource>::persistence_options)
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        let port = pick_unused_port().unwrap();
+        let anvil = Anvil::new().spawn();
+        let l1 = anvil.endpoint_url();
+
+        let config = TestNetworkConfigBuilder::default()
+            .api_config(SqlDataSource::options(
+                &storage[0],
+                Options::with_port(port),
+            ))
+            .persistences(persistence.clone())
+            .network_config(TestConfigBuilder::default().l1_url(l1).build())
+            .build();
+        let mut network = TestNetwork::new(config, MockSequencerVersions::new()).await;
+        let instance = network.server.node_state();
+        let network_config = network.server.network_config();
+        let stake_table = network_config.config.known_nodes_with_stake.clone();
+
+        // Decide enough blocks that the snapshot height has a chain of descendants proving it was
+        // decided, and wait for its state to be stored.
+        let client: Client<ServerError, SequencerApiVersion> =
+            Client::new(format!("http://localhost:{port}").parse().unwrap());
+        client.connect(None).await;
+        client
+            .socket("availability/stream/blocks/0")
+            .subscribe::<BlockQueryData<SeqTypes>>()
+            .await
+            .unwrap()
+            .take(8)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        sleep(Duration::from_secs(5)).await;
+        network.stop_consensus().await;
+
+        // Export from the node running the archival query service.
+        let height = 3;
+        let source = persistence[0].clone().create().await.unwrap();
+        source.save_config(&network_config).await.unwrap();
+        let snapshot = source.export_snapshot(height).await.unwrap();
+        assert_eq!(snapshot.height(), height);
+        assert!(snapshot.leaf_chain.len() > 1);
+        let builder = TestConfig::<NUM_NODES>::builder_key().fee_account();
+        let (_, proof) = snapshot
+            .state
+            .fee_merkle_tree
+            .lookup(builder)
+            .expect_ok()
+            .unwrap();
+        let balance = *proof.elem().unwrap();
+        assert!(balance > 0.into());
+
+        // The snapshot survives a round trip through a file, and verifies against the real stake
+        // table.
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot");
+        snapshot.save(&path).unwrap();
+        let snapshot = StateSnapshot::load(&path).unwrap();
+        snapshot.verify(stake_table.clone()).await.unwrap();
+
+        // It does not verify against a different stake table, or with state which does not match
+        // the header.
+        snapshot
+            .verify(stake_table[..1].to_vec())
+            .await
+            .unwrap_err();
+        let mut forged = snapshot.clone();
+        forged.state.fee_merkle_tree = FeeMerkleTree::new(FEE_MERKLE_TREE_HEIGHT);
+        forged.verify(stake_table.clone()).await.unwrap_err();
+
+        // Import into fresh SQL storage. Consensus starts from the snapshotted leaf, and the full
+        // state is available as of the snapshot height.
+        let tmp = SqlDataSource::create_storage().await;
+        let opt = SqlDataSource::persistence_options(&tmp);
+        let imported = opt.clone().create().await.unwrap();
+        imported.import_snapshot(&snapshot).await.unwrap();
+        let (anchor, _) = imported.load_anchor_leaf().await.unwrap().unwrap();
+        assert_eq!(anchor.commit(), snapshot.leaf().commit());
+        let ds = SqlDataSource::create(opt, Default::default(), false)
+            .await
+            .unwrap();
+        let (state, leaf) = sql::load_full_state(&mut ds.read().await.unwrap(), height)
+            .await
+            .unwrap();
+        assert_eq!(leaf.commit(), snapshot.leaf().commit());
+        assert_eq!(
+            state.fee_merkle_tree.commitment(),
+            snapshot.state.fee_merkle_tree.commitment()
+        );
+        assert_eq!(
+            state.fee_merkle_tree.num_leaves(),
+            snapshot.state.fee_merkle_tree.num_leaves()
+        );
+        assert_eq!(
+            state.block_merkle_tree.commitment(),
+            snapshot.state.block_merkle_tree.commitment()
+        );
+
+        // Import into file system storage, which serves catchup for the snapshotted state.
+        let fs_dir = tempfile::tempdir().unwrap();
+        let fs = crate::persistence::fs::Options::new(fs_dir.path().to_path_buf())
+            .create()
+            .await
+            .unwrap();
+        fs.import_snapshot(&snapshot).await.unwrap();
+        let (anchor, _) = fs.load_anchor_leaf().await.unwrap().unwrap();
+        assert_eq!(anchor.commit(), snapshot.leaf().commit());
+
+        // A snapshot with a tampered stake table is rejected: it is not anchored to the epoch root
+        // of the snapshotted leaf, so it cannot be checked against L1, and storage which already
+        // has the stake table for its epoch refuses to import it.
+        let epoch = EpochNumber::new(1);
+        let honest = Validator::mock();
+        let honest = IndexMap::from([(honest.account, honest)]);
+        let forged_validator = Validator::mock();
+        let mut tampered = snapshot.clone();
+        tampered.stake_table = Some((
+            epoch,
+            IndexMap::from([(forged_validator.account, forged_validator)]),
+        ));
+        tampered
+            .epoch_root(network_config.config.epoch_height)
+            .unwrap_err();
+        let tampered_dir = tempfile::tempdir().unwrap();
+        let tampered_fs = crate::persistence::fs::Options::new(tampered_dir.path().to_path_buf())
+            .create()
+            .await
+            .unwrap();
+        tampered_fs
+            .store_stake(epoch, honest.clone())
+            .await
+            .unwrap();
+        tampered_fs.import_snapshot(&tampered).await.unwrap_err();
+        assert!(tampered_fs.load_anchor_leaf().await.unwrap().is_none());
+        assert_eq!(tampered_fs.load_stake(epoch).await.unwrap(), Some(honest));
+
+        let catchup = fs.into_catchup_provider(Default::default()).unwrap();
+        let header = snapshot.leaf().block_header();
+        let view = snapshot.leaf().view_number();
+        assert_eq!(
+            catchup.try_fetch_leaves(0, height).await.unwrap(),
+            snapshot.leaf_chain
+        );
+        catchup.try_fetch_leaves(0, height + 1).await.unwrap_err();
+
+        let fees = catchup
+            .try_fetch_accounts(
+                0,
+                &instance,
+                height,
+                view,
+                header.fee_merkle_tree_root(),
+                &[builder],
+            )
+            .await
+            .unwrap();
+        let (_, proof) = fees.lookup(builder).expect_ok().unwrap();
+        assert_eq!(*proof.elem().unwrap(), balance);
+        catchup
+            .try_fetch_accounts(
+                0,
+                &instance,
+                height + 1,
+                view,
+                header.fee_merkle_tree_root(),
+                &[builder],
+            )
+            .await
+            .unwrap_err();
+
+        let mut blocks = BlockMerkleTree::from_commitment(header.block_merkle_tree_root());
+        catchup
+            .try_remember_blocks_merkle_tree(0, &instance, height, view, &mut blocks)
+            .await
+            .unwrap();
+        blocks.lookup(height - 1).expect_ok().unwrap();
+
+        let chain_config = catchup
+            .try_fetch_chain_config(0, header.chain_config().commit())
+            .await
+            .unwrap();
+        assert_eq!(chain_config.commit(), header.chain_config().commit());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_fetch_config() {
         setup_test();
@@ -1,14 +1,22 @@
+use std::collections::HashMap;
+
+use alloy::primitives::Address;
 use anyhow::Context;
 use async_trait::async_trait;
 use committable::Commitment;
 use espresso_types::{
     config::PublicNetworkConfig,
+    submit::{SubmitError, TransactionStatus},
     v0::traits::{PersistenceOptions, SequencerPersistence},
-    v0_1::{RewardAccount, RewardAccountProof, RewardAccountQueryData, RewardMerkleTree},
+    v0_1::{
+        EpochRewards, FeeLedgerPage, FeeStatement, RewardAccount, RewardAccountProof,
+        RewardAccountQueryData, RewardAmount, RewardDistribution, RewardMerkleTree, RewardTotals,
+    },
+    v0_3::{Validator, ValidatorActivity},
     v0_99::ChainConfig,
     FeeAccount, FeeAccountProof, FeeMerkleTree, Leaf2, NodeState, PubKey, Transaction,
 };
-use futures::future::Future;
+use futures::{future::Future, stream::BoxStream};
 use hotshot_query_service::{
     availability::AvailabilityDataSource,
     data_source::{UpdateDataSource, VersionedDataSource},
@@ -17,7 +25,8 @@ use hotshot_query_service::{
     status::StatusDataSource,
 };
 use hotshot_types::{
-    data::ViewNumber,
+    data::{EpochNumber, ViewNumber},
+    equivocation::EquivocationEvidence,
     light_client::StateSignatureRequestBody,
     traits::{
         network::ConnectedNetwork,
@@ -25,6 +34,7 @@ use hotshot_types::{
     },
     PeerConfig,
 };
+use indexmap::IndexMap;
 use tide_disco::Url;
 
 use super::{
@@ -96,6 +106,29 @@ pub fn provider<V: Versions>(
 
 pub(crate) trait SubmitDataSource<N: ConnectedNetwork<PubKey>, P: SequencerPersistence> {
     fn submit(&self, tx: Transaction) -> impl Send + Future<Output = anyhow::Result<()>>;
+
+    /// Submit a batch of transactions, returning the outcome for each one.
+    ///
+    /// Each transaction is validated and submitted independently, so the failure of one transaction
+    /// does not affect the others. The results are in the same order as `txs`.
+    fn submit_batch(
+        &self,
+        txs: Vec<Transaction>,
+    ) -> impl Send + Future<Output = Vec<Result<(), SubmitError>>>;
+}
+
+pub(crate) trait TxStatusDataSource {
+    /// Get the status of a recently seen transaction.
+    fn get_tx_status(
+        &self,
+        hash: Commitment<Transaction>,
+    ) -> impl Send + Future<Output = Option<TransactionStatus>>;
+
+    /// Stream status transitions for a transaction, ending once it reaches a final status.
+    fn subscribe_tx_status(
+        &self,
+        hash: Commitment<Transaction>,
+    ) -> impl Send + Future<Output = BoxStream<'static, TransactionStatus>>;
 }
 
 pub(crate) trait HotShotConfigDataSource {
@@ -120,6 +153,102 @@ pub(crate) trait StakeTableDataSource<T: NodeType> {
 
     /// Get the stake table for  the current epoch if not provided
     fn get_stake_table_current(&self) -> impl Send + Future<Output = Vec<PeerConfig<T>>>;
+
+    /// Get the leader of `view` in `epoch`
+    fn get_leader(
+        &self,
+        view: <T as NodeType>::View,
+        epoch: Option<<T as NodeType>::Epoch>,
+    ) -> impl Send + Future<Output = anyhow::Result<T::SignatureKey>>;
+}
+
+/// The full validator set of each epoch, including commission and delegations.
+pub(crate) trait ValidatorSetDataSource {
+    /// Get the validators for `epoch`, or for the current epoch if not provided.
+    ///
+    /// Returns the epoch the validators belong to along with the validators, keyed by account.
+    fn get_validators(
+        &self,
+        epoch: Option<EpochNumber>,
+    ) -> impl Send + Future<Output = anyhow::Result<(EpochNumber, IndexMap<Address, Validator<PubKey>>)>>;
+}
+
+/// Participation of validators in consensus, indexed from decided blocks.
+pub(crate) trait ValidatorActivityDataSource {
+    /// Get the number of blocks proposed and votes cast by each validator during `epoch`.
+    ///
+    /// Validators which did not participate in the epoch are omitted.
+    fn get_validator_activity(
+        &self,
+        epoch: u64,
+    ) -> impl Send + Future<Output = anyhow::Result<HashMap<Address, ValidatorActivity>>>;
+}
+
+/// Historical reward distributions, indexed from decided blocks.
+pub(crate) trait RewardHistoryDataSource {
+    /// Get the rewards paid out in the block at `height`.
+    ///
+    /// Returns [`None`] if no rewards were paid out in this block, or if the block has not been
+    /// indexed yet.
+    fn get_block_rewards(
+        &self,
+        height: u64,
+    ) -> impl Send + Future<Output = anyhow::Result<Option<RewardDistribution>>>;
+
+    /// Get the rewards paid out by `validator` over the course of `epoch`.
+    fn get_epoch_rewards(
+        &self,
+        epoch: u64,
+        validator: RewardAccount,
+    ) -> impl Send + Future<Output = anyhow::Result<EpochRewards>>;
+
+    /// Get the reward earned by `delegator` from `validator` in `epoch`.
+    ///
+    /// This does not include any commission earned by `delegator` when it is the validator.
+    fn get_delegator_epoch_reward(
+        &self,
+        epoch: u64,
+        validator: RewardAccount,
+        delegator: RewardAccount,
+    ) -> impl Send + Future<Output = anyhow::Result<RewardAmount>>;
+
+    /// Get the rewards earned by `account` over all indexed epochs.
+    fn get_reward_totals(
+        &self,
+        account: RewardAccount,
+    ) -> impl Send + Future<Output = anyhow::Result<RewardTotals>>;
+}
+
+/// Historical changes to fee account balances, indexed from decided blocks.
+pub(crate) trait FeeLedgerDataSource {
+    /// Get the changes to the balance of `account` made by the blocks in `from..to`.
+    fn get_fee_statement(
+        &self,
+        account: FeeAccount,
+        from: u64,
+        to: u64,
+    ) -> impl Send + Future<Output = anyhow::Result<FeeStatement>>;
+
+    /// Get up to `limit` ledger entries for `account`, newest first.
+    ///
+    /// If `before` is given, only entries strictly older than the entry at that `(height, index)`
+    /// are returned.
+    fn get_fee_history(
+        &self,
+        account: FeeAccount,
+        before: Option<(u64, u64)>,
+        limit: usize,
+    ) -> impl Send + Future<Output = anyhow::Result<FeeLedgerPage>>;
+}
+
+/// Evidence of misbehavior recorded by this node's consensus.
+pub(crate) trait EvidenceDataSource {
+    /// Get the evidence of equivocation recorded for views `from..to`.
+    fn get_equivocation_evidence(
+        &self,
+        from: u64,
+        to: u64,
+    ) -> impl Send + Future<Output = anyhow::Result<Vec<EquivocationEvidence<SeqTypes>>>>;
 }
 
 pub(crate) trait CatchupDataSource: Sync {
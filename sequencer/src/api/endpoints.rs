@@ -1,19 +1,27 @@
 //! Sequencer-specific API endpoint handlers.
 
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeSet, HashMap, HashSet},
     env,
+    str::FromStr,
+    sync::Arc,
 };
 
+use alloy::primitives::Address;
 use anyhow::Result;
-use committable::Committable;
+use committable::{Commitment, Committable};
 use espresso_types::{
+    submit::{SubmitError, SubmitResult},
     v0_1::{ADVZNsProof, RewardAccount, RewardMerkleTree},
-    FeeAccount, FeeMerkleTree, NamespaceId, NsProof, PubKey, Transaction,
+    v0_3::{DelegatorDetail, Validator, ValidatorActivity, ValidatorDetail, ValidatorSummary},
+    FeeAccount, FeeMerkleTree, NamespaceId, PubKey, Transaction,
 };
-use futures::{try_join, FutureExt};
+use futures::{try_join, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use hotshot_query_service::{
-    availability::{self, AvailabilityDataSource, CustomSnafu, FetchBlockSnafu},
+    availability::{
+        self, AvailabilityDataSource, BlockQueryData, CustomSnafu, FetchBlockSnafu,
+        VidCommonQueryData,
+    },
     explorer::{self, ExplorerDataSource},
     merklized_state::{
         self, MerklizedState, MerklizedStateDataSource, MerklizedStateHeightPersistence, Snapshot,
@@ -28,32 +36,53 @@ use hotshot_types::{
         node_implementation::{ConsensusTime, Versions},
     },
 };
+use indexmap::IndexMap;
 use jf_merkle_tree::MerkleTreeScheme;
 use serde::{de::Error as _, Deserialize, Serialize};
 use snafu::OptionExt;
 use tagged_base64::TaggedBase64;
-use tide_disco::{method::ReadState, Api, Error as _, StatusCode};
+use tide_disco::{method::ReadState, Api, Error as _, RequestError, RequestParams, StatusCode};
 use vbs::version::{StaticVersion, StaticVersionType};
 
 use super::{
     data_source::{
-        CatchupDataSource, HotShotConfigDataSource, NodeStateDataSource, SequencerDataSource,
-        StakeTableDataSource, StateSignatureDataSource, SubmitDataSource,
+        CatchupDataSource, EvidenceDataSource, FeeLedgerDataSource, HotShotConfigDataSource,
+        NodeStateDataSource, RewardHistoryDataSource, SequencerDataSource, StakeTableDataSource,
+        StateSignatureDataSource, SubmitDataSource, TxStatusDataSource,
+        ValidatorActivityDataSource, ValidatorSetDataSource,
     },
-    StorageState,
+    options, StorageState,
 };
 use crate::{SeqTypes, SequencerApiVersion, SequencerPersistence};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct NamespaceProofQueryData {
-    pub proof: Option<NsProof>,
-    pub transactions: Vec<Transaction>,
+pub use espresso_types::{
+    v0_3::{DelegatorDetailResponse, ValidatorDetailResponse, ValidatorsResponse},
+    ADVZNamespaceProofQueryData, NamespaceBlockQueryData, NamespaceProofQueryData,
+};
+
+/// Get the transactions in namespace `ns_id` of `block`, along with a proof.
+fn namespace_proof(
+    block: &BlockQueryData<SeqTypes>,
+    common: &VidCommonQueryData<SeqTypes>,
+    ns_id: NamespaceId,
+) -> Result<NamespaceProofQueryData, availability::Error> {
+    NamespaceProofQueryData::new(block.payload(), common.common(), ns_id).context(CustomSnafu {
+        message: format!("failed to make proof for namespace {ns_id}"),
+        status: StatusCode::NOT_FOUND,
+    })
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct ADVZNamespaceProofQueryData {
-    pub proof: Option<ADVZNsProof>,
-    pub transactions: Vec<Transaction>,
+/// Get the part of `block` belonging to namespace `ns_id`.
+fn namespace_block(
+    block: &BlockQueryData<SeqTypes>,
+    common: &VidCommonQueryData<SeqTypes>,
+    ns_id: NamespaceId,
+) -> Result<NamespaceBlockQueryData, availability::Error> {
+    let ns_data = namespace_proof(block, common, ns_id)?;
+    Ok(NamespaceBlockQueryData::new(
+        block.header().clone(),
+        ns_data,
+    ))
 }
 
 pub(super) fn fee<State, Ver>() -> Result<Api<State, merklized_state::Error, Ver>>
@@ -131,6 +160,166 @@ where
     Ok(api)
 }
 
+/// Parse the reward account in the URL parameter `name`.
+fn account_param<A>(req: &RequestParams, name: &str) -> Result<A, Error>
+where
+    A: FromStr<Err = anyhow::Error>,
+{
+    let account = req.string_param(name).map_err(Error::from_request_error)?;
+    account.parse().map_err(|err| {
+        Error::catch_all(
+            StatusCode::BAD_REQUEST,
+            format!("malformed {name} {account}: {err}"),
+        )
+    })
+}
+
+pub(super) fn reward_history<S, ApiVer: StaticVersionType + 'static>(
+    _: ApiVer,
+) -> Result<Api<S, Error, ApiVer>>
+where
+    S: 'static + Send + Sync + ReadState,
+    S::State: Send + Sync + RewardHistoryDataSource,
+{
+    let toml = toml::from_str::<toml::Value>(include_str!("../../api/reward_history.toml"))?;
+    let mut api = Api::<S, Error, ApiVer>::new(toml)?;
+
+    api.get("block", |req, state| {
+        async move {
+            let height = req
+                .integer_param("height")
+                .map_err(Error::from_request_error)?;
+            state
+                .get_block_rewards(height)
+                .await
+                .map_err(|err| {
+                    Error::catch_all(StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}"))
+                })?
+                .ok_or_else(|| {
+                    Error::catch_all(
+                        StatusCode::NOT_FOUND,
+                        format!("no rewards indexed for block {height}"),
+                    )
+                })
+        }
+        .boxed()
+    })?
+    .get("epoch", |req, state| {
+        async move {
+            let epoch = req
+                .integer_param("epoch")
+                .map_err(Error::from_request_error)?;
+            let validator = account_param(&req, "validator")?;
+            state
+                .get_epoch_rewards(epoch, validator)
+                .await
+                .map_err(|err| {
+                    Error::catch_all(StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}"))
+                })
+        }
+        .boxed()
+    })?
+    .get("delegator", |req, state| {
+        async move {
+            let epoch = req
+                .integer_param("epoch")
+                .map_err(Error::from_request_error)?;
+            let validator = account_param(&req, "validator")?;
+            let delegator = account_param(&req, "delegator")?;
+            state
+                .get_delegator_epoch_reward(epoch, validator, delegator)
+                .await
+                .map_err(|err| {
+                    Error::catch_all(StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}"))
+                })
+        }
+        .boxed()
+    })?
+    .get("totals", |req, state| {
+        async move {
+            let account = account_param(&req, "address")?;
+            state.get_reward_totals(account).await.map_err(|err| {
+                Error::catch_all(StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}"))
+            })
+        }
+        .boxed()
+    })?;
+
+    Ok(api)
+}
+
+/// The maximum number of entries which can be requested from the fee history endpoint at once.
+const MAX_FEE_HISTORY_LIMIT: usize = 1000;
+
+pub(super) fn fee_ledger<S, ApiVer: StaticVersionType + 'static>(
+    _: ApiVer,
+) -> Result<Api<S, Error, ApiVer>>
+where
+    S: 'static + Send + Sync + ReadState,
+    S::State: Send + Sync + FeeLedgerDataSource,
+{
+    let toml = toml::from_str::<toml::Value>(include_str!("../../api/fee_ledger.toml"))?;
+    let mut api = Api::<S, Error, ApiVer>::new(toml)?;
+
+    api.get("statement", |req, state| {
+        async move {
+            let account = account_param(&req, "address")?;
+            let from = req
+                .integer_param("from")
+                .map_err(Error::from_request_error)?;
+            let to = req.integer_param("to").map_err(Error::from_request_error)?;
+            if from > to {
+                return Err(Error::catch_all(
+                    StatusCode::BAD_REQUEST,
+                    format!("invalid block range {from}..{to}"),
+                ));
+            }
+            state
+                .get_fee_statement(account, from, to)
+                .await
+                .map_err(|err| {
+                    Error::catch_all(StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}"))
+                })
+        }
+        .boxed()
+    })?
+    .get("history", |req, state| {
+        async move {
+            let account = account_param(&req, "address")?;
+            let limit = req
+                .integer_param("limit")
+                .map_err(Error::from_request_error)?;
+            if limit > MAX_FEE_HISTORY_LIMIT {
+                return Err(Error::catch_all(
+                    StatusCode::BAD_REQUEST,
+                    format!("limit {limit} exceeds maximum of {MAX_FEE_HISTORY_LIMIT}"),
+                ));
+            }
+            let before = match req
+                .opt_integer_param("height")
+                .map_err(Error::from_request_error)?
+            {
+                Some(height) => {
+                    let index = req
+                        .integer_param("index")
+                        .map_err(Error::from_request_error)?;
+                    Some((height, index))
+                },
+                None => None,
+            };
+            state
+                .get_fee_history(account, before, limit)
+                .await
+                .map_err(|err| {
+                    Error::catch_all(StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}"))
+                })
+        }
+        .boxed()
+    })?;
+
+    Ok(api)
+}
+
 pub(super) type AvailState<N, P, D, ApiVer> = ApiState<StorageState<N, P, D, ApiVer>>;
 
 type AvailabilityApi<N, P, D, V, ApiVer> = Api<AvailState<N, P, D, V>, availability::Error, ApiVer>;
@@ -150,6 +339,7 @@ where
     let extension = toml::from_str(include_str!("../../api/availability.toml"))?;
     options.extensions.push(extension);
     let timeout = options.fetch_timeout;
+    let large_object_range_limit = options.large_object_range_limit;
 
     let mut api = availability::define_api::<AvailState<N, P, D, _>, SeqTypes, _>(
         &options,
@@ -185,26 +375,61 @@ where
                     }
                 )?;
 
-                if let Some(ns_index) = block.payload().ns_table().find_ns_id(&ns_id) {
-                    let proof = NsProof::new(block.payload(), &ns_index, common.common()).context(
-                        CustomSnafu {
-                            message: format!("failed to make proof for namespace {ns_id}"),
-                            status: StatusCode::NOT_FOUND,
-                        },
-                    )?;
+                namespace_proof(&block, &common, ns_id)
+            }
+            .boxed()
+        })?
+        .get("getnamespaceproof_range", move |req, state| {
+            async move {
+                let from: usize = req.integer_param("from")?;
+                let until: usize = req.integer_param("until")?;
+                let ns_id = NamespaceId::from(req.integer_param::<_, u32>("namespace")?);
+                availability::enforce_range_limit(from, until, large_object_range_limit)?;
 
-                    Ok(NamespaceProofQueryData {
-                        transactions: proof.export_all_txs(&ns_id),
-                        proof: Some(proof),
+                let blocks = state.get_block_range(from..until).await;
+                let common = state.get_vid_common_range(from..until).await;
+                blocks
+                    .zip(common)
+                    .enumerate()
+                    .then(|(index, (block, common))| async move {
+                        let height = from + index;
+                        let (block, common) = try_join!(
+                            async move {
+                                block.with_timeout(timeout).await.context(FetchBlockSnafu {
+                                    resource: height.to_string(),
+                                })
+                            },
+                            async move {
+                                common.with_timeout(timeout).await.context(FetchBlockSnafu {
+                                    resource: height.to_string(),
+                                })
+                            }
+                        )?;
+                        namespace_block(&block, &common, ns_id)
                     })
-                } else {
-                    // ns_id not found in ns_table
-                    Ok(NamespaceProofQueryData {
-                        proof: None,
-                        transactions: Vec::new(),
+                    .try_collect::<Vec<_>>()
+                    .await
+            }
+            .boxed()
+        })?
+        .stream("stream_namespace", move |req, state| {
+            async move {
+                let height = req.integer_param("height")?;
+                let ns_id = NamespaceId::from(req.integer_param::<_, u32>("namespace")?);
+                state
+                    .read(|state| {
+                        async move {
+                            let blocks = state.subscribe_blocks(height).await;
+                            let common = state.subscribe_vid_common(height).await;
+                            Ok(blocks.zip(common).map(move |(block, common)| {
+                                namespace_block(&block, &common, ns_id)
+                            }))
+                        }
+                        .boxed()
                     })
-                }
+                    .await
             }
+            .try_flatten_stream()
             .boxed()
         })?;
     } else {
@@ -271,19 +496,231 @@ where
 
 type ExplorerApi<N, P, D, V, ApiVer> = Api<AvailState<N, P, D, V>, explorer::Error, ApiVer>;
 
+/// Explorer search results, extended with the validators and delegators matching the query.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExplorerSearchResult {
+    #[serde(flatten)]
+    pub chain: explorer::SearchResult<SeqTypes>,
+    pub validators: Vec<ValidatorSummary>,
+    pub delegators: Vec<DelegatorDetail>,
+}
+
+/// Response of the `get_search_result` explorer endpoint.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExplorerSearchResultResponse {
+    pub search_results: ExplorerSearchResult,
+}
+
+fn explorer_request_error(err: RequestError) -> explorer::Error {
+    explorer::Error::catch_all(StatusCode::BAD_REQUEST, err.to_string())
+}
+
+fn explorer_not_found(err: anyhow::Error) -> explorer::Error {
+    explorer::Error::catch_all(StatusCode::NOT_FOUND, format!("{err:#}"))
+}
+
+/// Get the validators of `epoch` (default current epoch) along with their activity.
+async fn validators_with_activity<S>(
+    state: &S,
+    epoch: Option<EpochNumber>,
+) -> Result<
+    (
+        EpochNumber,
+        IndexMap<Address, Validator<PubKey>>,
+        HashMap<Address, ValidatorActivity>,
+    ),
+    explorer::Error,
+>
+where
+    S: ValidatorSetDataSource + ValidatorActivityDataSource + Sync,
+{
+    let (epoch, validators) = state
+        .get_validators(epoch)
+        .await
+        .map_err(explorer_not_found)?;
+    let activity = state
+        .get_validator_activity(epoch.u64())
+        .await
+        .map_err(|err| {
+            explorer::Error::catch_all(StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}"))
+        })?;
+    Ok((epoch, validators, activity))
+}
+
 pub(super) fn explorer<N, P, D, V: Versions>(
 ) -> Result<ExplorerApi<N, P, D, V, SequencerApiVersion>>
 where
     N: ConnectedNetwork<PubKey>,
-    D: ExplorerDataSource<SeqTypes> + Send + Sync + 'static,
+    D: ExplorerDataSource<SeqTypes> + ValidatorActivityDataSource + Send + Sync + 'static,
     P: SequencerPersistence,
 {
-    let api = explorer::define_api::<AvailState<N, P, D, V>, SeqTypes, _>(
+    // Extend the base API with staking data, which we also match search queries against
+    let options = explorer::Options {
+        extensions: vec![toml::from_str(include_str!("../../api/explorer.toml"))?],
+        search: false,
+        ..Default::default()
+    };
+    let mut api = explorer::define_api::<AvailState<N, P, D, V>, SeqTypes, _>(
+        &options,
         SequencerApiVersion::instance(),
     )?;
+
+    api.get("get_validators", |req, state| {
+        async move {
+            let epoch = req
+                .opt_integer_param("epoch")
+                .map_err(explorer_request_error)?
+                .map(EpochNumber::new);
+            let (epoch, validators, activity) = validators_with_activity(state, epoch).await?;
+            let validators = validators
+                .values()
+                .map(|validator| {
+                    ValidatorSummary::new(
+                        validator,
+                        activity
+                            .get(&validator.account)
+                            .copied()
+                            .unwrap_or_default(),
+                    )
+                })
+                .collect();
+            Ok(ValidatorsResponse { epoch, validators })
+        }
+        .boxed()
+    })?
+    .get("get_validator_detail", |req, state| {
+        async move {
+            let epoch = req
+                .opt_integer_param("epoch")
+                .map_err(explorer_request_error)?
+                .map(EpochNumber::new);
+            let query = req
+                .string_param("validator")
+                .map_err(explorer_request_error)?;
+            let (epoch, validators, activity) = validators_with_activity(state, epoch).await?;
+            let validator = find_validator(&validators, query).ok_or_else(|| {
+                explorer::Error::catch_all(
+                    StatusCode::NOT_FOUND,
+                    format!("no validator {query} in epoch {epoch}"),
+                )
+            })?;
+            Ok(ValidatorDetailResponse {
+                validator_detail: ValidatorDetail {
+                    epoch,
+                    activity: activity
+                        .get(&validator.account)
+                        .copied()
+                        .unwrap_or_default(),
+                    validator: validator.clone(),
+                },
+            })
+        }
+        .boxed()
+    })?
+    .get("get_delegator_detail", |req, state| {
+        async move {
+            let epoch = req
+                .opt_integer_param("epoch")
+                .map_err(explorer_request_error)?
+                .map(EpochNumber::new);
+            let delegator = req
+                .string_param("delegator")
+                .map_err(explorer_request_error)?;
+            let address = delegator.parse::<Address>().map_err(|err| {
+                explorer::Error::catch_all(
+                    StatusCode::BAD_REQUEST,
+                    format!("malformed delegator {delegator}: {err}"),
+                )
+            })?;
+            let (epoch, validators) = state
+                .get_validators(epoch)
+                .await
+                .map_err(explorer_not_found)?;
+            Ok(DelegatorDetailResponse {
+                delegator_detail: DelegatorDetail::new(epoch, address, validators.values()),
+            })
+        }
+        .boxed()
+    })?
+    .get("get_search_result", |req, state| {
+        async move {
+            let query = req.string_param("query").map_err(explorer_request_error)?;
+
+            // Addresses and stake table keys identify validators and delegators in the current
+            // epoch, anything else is matched against blocks and transactions.
+            let staking_query = query.parse::<Address>().is_ok()
+                || TaggedBase64::parse(query)
+                    .ok()
+                    .and_then(|tb64| PubKey::try_from(tb64).ok())
+                    .is_some();
+            if !staking_query {
+                let query = explorer::parse_search_query(query)
+                    .map_err(explorer::Error::GetSearchResults)?;
+                let chain = state
+                    .get_search_results(query)
+                    .await
+                    .map_err(explorer::Error::GetSearchResults)?;
+                return Ok(ExplorerSearchResultResponse {
+                    search_results: ExplorerSearchResult {
+                        chain,
+                        validators: vec![],
+                        delegators: vec![],
+                    },
+                });
+            }
+
+            let (epoch, validators, activity) = validators_with_activity(state, None).await?;
+            let summary = |validator: &Validator<PubKey>| {
+                ValidatorSummary::new(
+                    validator,
+                    activity
+                        .get(&validator.account)
+                        .copied()
+                        .unwrap_or_default(),
+                )
+            };
+            let matching_validators = find_validator(&validators, query)
+                .map(summary)
+                .into_iter()
+                .collect();
+            let delegators = query
+                .parse::<Address>()
+                .ok()
+                .map(|address| DelegatorDetail::new(epoch, address, validators.values()))
+                .filter(|delegator| !delegator.positions.is_empty())
+                .into_iter()
+                .collect();
+            Ok(ExplorerSearchResultResponse {
+                search_results: ExplorerSearchResult {
+                    chain: explorer::SearchResult {
+                        blocks: vec![],
+                        transactions: vec![],
+                    },
+                    validators: matching_validators,
+                    delegators,
+                },
+            })
+        }
+        .boxed()
+    })?;
+
     Ok(api)
 }
 
+/// Look up a validator by either its account address or its stake table key.
+fn find_validator<'a>(
+    validators: &'a IndexMap<Address, Validator<PubKey>>,
+    query: &str,
+) -> Option<&'a Validator<PubKey>> {
+    if let Ok(address) = query.parse::<Address>() {
+        return validators.get(&address);
+    }
+    let key = PubKey::try_from(TaggedBase64::parse(query).ok()?).ok()?;
+    validators
+        .values()
+        .find(|validator| validator.stake_table_key == key)
+}
+
 pub(super) fn node<S>() -> Result<Api<S, node::Error, StaticVersion<0, 1>>>
 where
     S: 'static + Send + Sync + ReadState,
@@ -324,33 +761,139 @@ where
                 .await)
         }
         .boxed()
+    })?
+    .at("leader", |req, state| {
+        async move {
+            let view = ViewNumber::new(req.integer_param("view")?);
+            let epoch = req.opt_integer_param("epoch_number")?.map(EpochNumber::new);
+
+            state
+                .read(|state| state.get_leader(view, epoch).boxed())
+                .await
+                .map_err(|err| hotshot_query_service::node::Error::Custom {
+                    message: format!("{err:#}"),
+                    status: StatusCode::NOT_FOUND,
+                })
+        }
+        .boxed()
     })?;
 
     Ok(api)
 }
-pub(super) fn submit<N, P, S, ApiVer: StaticVersionType + 'static>() -> Result<Api<S, Error, ApiVer>>
+pub(super) fn submit<N, P, S, ApiVer: StaticVersionType + 'static>(
+    opt: options::Submit,
+) -> Result<Api<S, Error, ApiVer>>
 where
     N: ConnectedNetwork<PubKey>,
     S: 'static + Send + Sync + ReadState,
     P: SequencerPersistence,
-    S::State: Send + Sync + SubmitDataSource<N, P>,
+    S::State: Send + Sync + SubmitDataSource<N, P> + TxStatusDataSource,
 {
     let toml = toml::from_str::<toml::Value>(include_str!("../../api/submit.toml"))?;
     let mut api = Api::<S, Error, ApiVer>::new(toml)?;
 
-    api.at("submit", |req, state| {
+    // An empty allow-list means all namespaces are allowed.
+    let allowed_namespaces: Arc<HashSet<NamespaceId>> = Arc::new(
+        opt.allowed_namespaces
+            .into_iter()
+            .map(NamespaceId::from)
+            .collect(),
+    );
+    let check_namespace = move |tx: &Transaction| -> Result<(), SubmitError> {
+        if allowed_namespaces.is_empty() || allowed_namespaces.contains(&tx.namespace()) {
+            Ok(())
+        } else {
+            Err(SubmitError::NamespaceNotAllowed(tx.namespace()))
+        }
+    };
+    let max_batch_size = opt.max_batch_size;
+
+    api.at("submit", {
+        let check_namespace = check_namespace.clone();
+        move |req, state| {
+            let check_namespace = check_namespace.clone();
+            async move {
+                let tx = req
+                    .body_auto::<Transaction, ApiVer>(ApiVer::instance())
+                    .map_err(Error::from_request_error)?;
+                check_namespace(&tx)
+                    .map_err(|err| Error::catch_all(StatusCode::BAD_REQUEST, err.to_string()))?;
+
+                let hash = tx.commit();
+                state
+                    .read(|state| state.submit(tx).boxed())
+                    .await
+                    .map_err(|err| Error::internal(err.to_string()))?;
+                Ok(hash)
+            }
+            .boxed()
+        }
+    })?
+    .at("submit_batch", move |req, state| {
+        let check_namespace = check_namespace.clone();
         async move {
-            let tx = req
-                .body_auto::<Transaction, ApiVer>(ApiVer::instance())
+            let txs = req
+                .body_auto::<Vec<Transaction>, ApiVer>(ApiVer::instance())
                 .map_err(Error::from_request_error)?;
+            if txs.len() > max_batch_size {
+                return Err(Error::catch_all(
+                    StatusCode::BAD_REQUEST,
+                    format!(
+                        "batch size ({}) is greater than max_batch_size ({max_batch_size})",
+                        txs.len()
+                    ),
+                ));
+            }
 
-            let hash = tx.commit();
+            // Reject transactions in disallowed namespaces up front, and submit the rest together.
+            let mut results: Vec<SubmitResult> = Vec::with_capacity(txs.len());
+            let mut to_submit = vec![];
+            for tx in txs {
+                match check_namespace(&tx) {
+                    Ok(()) => {
+                        results.push(Ok(tx.commit()));
+                        to_submit.push((results.len() - 1, tx));
+                    },
+                    Err(err) => results.push(Err(err)),
+                }
+            }
+            let (indices, to_submit): (Vec<_>, Vec<_>) = to_submit.into_iter().unzip();
+            let outcomes = state
+                .read(|state| state.submit_batch(to_submit).boxed())
+                .await;
+            for (i, outcome) in indices.into_iter().zip(outcomes) {
+                if let Err(err) = outcome {
+                    results[i] = Err(err);
+                }
+            }
+            Ok(results)
+        }
+        .boxed()
+    })?
+    .at("get_transaction_status", |req, state| {
+        async move {
+            let hash: Commitment<Transaction> =
+                req.blob_param("hash").map_err(Error::from_request_error)?;
             state
-                .read(|state| state.submit(tx).boxed())
+                .read(|state| state.get_tx_status(hash).boxed())
+                .await
+                .ok_or(Error::catch_all(
+                    StatusCode::NOT_FOUND,
+                    format!("status of transaction {hash} is not known"),
+                ))
+        }
+        .boxed()
+    })?
+    .stream("stream_transaction_status", |req, state| {
+        async move {
+            let hash: Commitment<Transaction> =
+                req.blob_param("hash").map_err(Error::from_request_error)?;
+            Ok(state
+                .read(|state| state.subscribe_tx_status(hash).boxed())
                 .await
-                .map_err(|err| Error::internal(err.to_string()))?;
-            Ok(hash)
+                .map(Ok))
         }
+        .try_flatten_stream()
         .boxed()
     })?;
 
@@ -387,6 +930,53 @@ where
     Ok(api)
 }
 
+/// The maximum number of views which can be requested from the equivocation evidence endpoint at
+/// once.
+const MAX_EVIDENCE_VIEW_RANGE: u64 = 10000;
+
+pub(super) fn evidence<S, ApiVer: StaticVersionType + 'static>(
+    _: ApiVer,
+) -> Result<Api<S, Error, ApiVer>>
+where
+    S: 'static + Send + Sync + ReadState,
+    S::State: Send + Sync + EvidenceDataSource,
+{
+    let toml = toml::from_str::<toml::Value>(include_str!("../../api/evidence.toml"))?;
+    let mut api = Api::<S, Error, ApiVer>::new(toml)?;
+
+    api.get("equivocation", |req, state| {
+        async move {
+            let from = req
+                .integer_param("from")
+                .map_err(Error::from_request_error)?;
+            let to = req.integer_param("to").map_err(Error::from_request_error)?;
+            if from > to {
+                return Err(Error::catch_all(
+                    StatusCode::BAD_REQUEST,
+                    format!("invalid view range {from}..{to}"),
+                ));
+            }
+            if to - from > MAX_EVIDENCE_VIEW_RANGE {
+                return Err(Error::catch_all(
+                    StatusCode::BAD_REQUEST,
+                    format!(
+                        "view range {from}..{to} exceeds maximum of {MAX_EVIDENCE_VIEW_RANGE} views"
+                    ),
+                ));
+            }
+            state
+                .get_equivocation_evidence(from, to)
+                .await
+                .map_err(|err| {
+                    Error::catch_all(StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}"))
+                })
+        }
+        .boxed()
+    })?;
+
+    Ok(api)
+}
+
 pub(super) fn catchup<S, ApiVer: StaticVersionType + 'static>(
     _: ApiVer,
 ) -> Result<Api<S, Error, ApiVer>>
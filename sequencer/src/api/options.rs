@@ -29,8 +29,9 @@ use vbs::version::StaticVersionType;
 
 use super::{
     data_source::{
-        provider, CatchupDataSource, HotShotConfigDataSource, NodeStateDataSource, Provider,
-        SequencerDataSource, StateSignatureDataSource, SubmitDataSource,
+        provider, CatchupDataSource, EvidenceDataSource, HotShotConfigDataSource,
+        NodeStateDataSource, Provider, SequencerDataSource, StateSignatureDataSource,
+        SubmitDataSource,
     },
     endpoints, fs, sql,
     update::ApiEventConsumer,
@@ -297,10 +298,10 @@ impl Options {
         app.register_module("node", endpoints::node()?)?;
 
         // Initialize submit API
-        if self.submit.is_some() {
+        if let Some(opt) = self.submit.clone() {
             app.register_module(
                 "submit",
-                endpoints::submit::<_, _, _, SequencerApiVersion>()?,
+                endpoints::submit::<_, _, _, SequencerApiVersion>(opt)?,
             )?;
         }
 
@@ -308,6 +309,7 @@ impl Options {
         app.register_module("catchup", endpoints::catchup(bind_version)?)?;
 
         app.register_module("state-signature", endpoints::state_signature(bind_version)?)?;
+        app.register_module("evidence", endpoints::evidence(bind_version)?)?;
 
         if self.config.is_some() {
             app.register_module("config", endpoints::config(bind_version)?)?;
@@ -390,6 +392,8 @@ impl Options {
             "reward-state",
             endpoints::reward::<_, SequencerApiVersion>()?,
         )?;
+        app.register_module("reward-history", endpoints::reward_history(bind_version)?)?;
+        app.register_module("fee-ledger", endpoints::fee_ledger(bind_version)?)?;
 
         let get_node_state = {
             let state = state.clone();
@@ -425,13 +429,14 @@ impl Options {
             + StateSignatureDataSource<N>
             + NodeStateDataSource
             + CatchupDataSource
-            + HotShotConfigDataSource,
+            + HotShotConfigDataSource
+            + EvidenceDataSource,
         N: ConnectedNetwork<PubKey>,
     {
         let bind_version = SequencerApiVersion::instance();
         // Initialize submit API
-        if self.submit.is_some() {
-            let submit_api = endpoints::submit::<_, _, _, SequencerApiVersion>()?;
+        if let Some(opt) = self.submit.clone() {
+            let submit_api = endpoints::submit::<_, _, _, SequencerApiVersion>(opt)?;
             app.register_module("submit", submit_api)?;
         }
 
@@ -445,6 +450,8 @@ impl Options {
         let state_signature_api = endpoints::state_signature(bind_version)?;
         app.register_module("state-signature", state_signature_api)?;
 
+        app.register_module("evidence", endpoints::evidence(bind_version)?)?;
+
         if self.config.is_some() {
             app.register_module("config", endpoints::config(bind_version)?)?;
         }
@@ -546,8 +553,36 @@ impl Http {
 }
 
 /// Options for the submission API module.
-#[derive(Parser, Clone, Copy, Debug, Default)]
-pub struct Submit;
+#[derive(Parser, Clone, Debug)]
+pub struct Submit {
+    /// Namespaces for which this node will accept transactions.
+    ///
+    /// Transactions in any other namespace are rejected, by both the single transaction and the batch
+    /// submission endpoints. Leave unset to accept transactions in all namespaces.
+    #[clap(
+        long,
+        env = "ESPRESSO_SEQUENCER_SUBMIT_ALLOWED_NAMESPACES",
+        value_delimiter = ','
+    )]
+    pub allowed_namespaces: Vec<u32>,
+
+    /// Maximum number of transactions that can be submitted in a single batch.
+    #[clap(
+        long,
+        env = "ESPRESSO_SEQUENCER_SUBMIT_MAX_BATCH_SIZE",
+        default_value = "1000"
+    )]
+    pub max_batch_size: usize,
+}
+
+impl Default for Submit {
+    fn default() -> Self {
+        Self {
+            allowed_namespaces: vec![],
+            max_batch_size: 1000,
+        }
+    }
+}
 
 /// Options for the status API module.
 #[derive(Parser, Clone, Copy, Debug, Default)]
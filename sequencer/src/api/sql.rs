@@ -1,13 +1,20 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
+use alloy::primitives::{Address, U256};
 use anyhow::{bail, ensure, Context};
 use async_trait::async_trait;
 use committable::{Commitment, Committable};
 use espresso_types::{
-    get_l1_deposits,
-    v0_1::{RewardAccount, RewardMerkleTree, REWARD_MERKLE_TREE_HEIGHT},
+    get_l1_deposits, get_l1_withdrawal_requests,
+    v0_1::{
+        EpochRewards, FeeLedgerEntry, FeeLedgerEntryKind, FeeLedgerPage, FeeStatement,
+        RewardAccount, RewardAmount, RewardDistribution, RewardMerkleTree, RewardTotals,
+        REWARD_MERKLE_TREE_HEIGHT,
+    },
+    v0_3::ValidatorActivity,
     v0_99::{ChainConfig, IterableFeeInfo},
-    BlockMerkleTree, EpochVersion, FeeAccount, FeeMerkleTree, Leaf2, NodeState, ValidatedState,
+    withdrawal_accounts, BlockMerkleTree, EpochVersion, FeeAccount, FeeAmount, FeeMerkleTree,
+    Leaf2, NodeState, ValidatedState, FEE_MERKLE_TREE_HEIGHT,
 };
 use hotshot::traits::ValidatedState as _;
 use hotshot_query_service::{
@@ -20,7 +27,7 @@ use hotshot_query_service::{
         },
         VersionedDataSource,
     },
-    merklized_state::Snapshot,
+    merklized_state::{MerklizedState, Snapshot},
     Resolvable,
 };
 use hotshot_types::{
@@ -32,18 +39,25 @@ use hotshot_types::{
 };
 use jf_merkle_tree::{
     prelude::MerkleNode, ForgetableMerkleTreeScheme, ForgetableUniversalMerkleTreeScheme,
-    LookupResult, MerkleTreeScheme,
+    LookupResult, MerkleTreeScheme, UniversalMerkleTreeScheme,
 };
-use sqlx::{Encode, Type};
+use serde::de::DeserializeOwned;
+use sqlx::{types::JsonValue, Encode, Type};
 use vbs::version::StaticVersionType;
 
 use super::{
-    data_source::{Provider, SequencerDataSource},
+    data_source::{
+        FeeLedgerDataSource, Provider, RewardHistoryDataSource, SequencerDataSource,
+        ValidatorActivityDataSource,
+    },
     BlocksFrontier,
 };
 use crate::{
     catchup::{CatchupStorage, NullStateCatchup},
-    persistence::{sql::Options, ChainConfigPersistence},
+    persistence::{
+        sql::Options, ChainConfigPersistence, FeeLedgerPersistence, RewardHistoryPersistence,
+        VoteHistoryPersistence,
+    },
     state::compute_state_update,
     SeqTypes,
 };
@@ -315,12 +329,513 @@ impl ChainConfigPersistence for Transaction<Write> {
     }
 }
 
+#[async_trait]
+impl RewardHistoryPersistence for Transaction<Write> {
+    async fn insert_reward_distribution(
+        &mut self,
+        height: u64,
+        epoch: u64,
+        distribution: RewardDistribution,
+    ) -> anyhow::Result<()> {
+        // The epoch totals are updated incrementally, so we must make sure not to count the same
+        // block twice.
+        let existing =
+            query_as::<(i64,)>("SELECT height FROM reward_distribution WHERE height = $1")
+                .bind(height as i64)
+                .fetch_optional(self.as_mut())
+                .await?;
+        if existing.is_some() {
+            tracing::debug!(height, "reward distribution already indexed");
+            return Ok(());
+        }
+
+        let validator = distribution.validator.to_string();
+        self.upsert(
+            "reward_distribution",
+            ["height", "epoch", "validator", "data"],
+            ["height"],
+            [(
+                height as i64,
+                epoch as i64,
+                validator.clone(),
+                bincode::serialize(&distribution)?,
+            )],
+        )
+        .await?;
+
+        let payments = distribution
+            .delegators
+            .iter()
+            .map(|(account, amount)| (*account, *amount, false))
+            .chain([(distribution.validator, distribution.commission, true)]);
+        for (account, amount, commission) in payments {
+            let account = account.to_string();
+            let (total,) = query_as::<(String,)>(
+                "SELECT amount FROM epoch_reward
+                  WHERE epoch = $1 AND validator = $2 AND account = $3 AND commission = $4",
+            )
+            .bind(epoch as i64)
+            .bind(&validator)
+            .bind(&account)
+            .bind(commission)
+            .fetch_optional(self.as_mut())
+            .await?
+            .unwrap_or_else(|| ("0".to_string(),));
+            let total = parse_amount(&total)?
+                .checked_add(amount.0)
+                .context(format!("overflowed epoch {epoch} reward for {account}"))?;
+
+            self.upsert(
+                "epoch_reward",
+                ["epoch", "validator", "account", "commission", "amount"],
+                ["epoch", "validator", "account", "commission"],
+                [(
+                    epoch as i64,
+                    validator.clone(),
+                    account,
+                    commission,
+                    total.to_string(),
+                )],
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl VoteHistoryPersistence for Transaction<Write> {
+    async fn insert_votes(
+        &mut self,
+        height: u64,
+        epoch: u64,
+        voters: Vec<RewardAccount>,
+    ) -> anyhow::Result<()> {
+        let counted = query_as::<(String, i64, i64)>(
+            "SELECT validator, votes, last_height FROM epoch_votes WHERE epoch = $1",
+        )
+        .bind(epoch as i64)
+        .fetch_all(self.as_mut())
+        .await?
+        .into_iter()
+        .map(|(validator, votes, last_height)| (validator, (votes, last_height)))
+        .collect::<HashMap<_, _>>();
+
+        // The counts are updated incrementally, so we must make sure not to count the same block
+        // twice.
+        let rows = voters
+            .into_iter()
+            .filter_map(|voter| {
+                let validator = voter.to_string();
+                let (votes, last_height) = counted.get(&validator).copied().unwrap_or((0, -1));
+                (last_height < height as i64).then_some((
+                    epoch as i64,
+                    validator,
+                    votes + 1,
+                    height as i64,
+                ))
+            })
+            .collect::<Vec<_>>();
+        if rows.is_empty() {
+            tracing::debug!(height, "votes already indexed");
+            return Ok(());
+        }
+
+        self.upsert(
+            "epoch_votes",
+            ["epoch", "validator", "votes", "last_height"],
+            ["epoch", "validator"],
+            rows,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl FeeLedgerPersistence for Transaction<Write> {
+    async fn insert_fee_ledger(
+        &mut self,
+        height: u64,
+        entries: Vec<FeeLedgerEntry>,
+    ) -> anyhow::Result<()> {
+        // Entries are keyed by their position in the block, so inserting the same block twice
+        // overwrites the existing entries with identical ones.
+        let rows = entries
+            .iter()
+            .map(|entry| {
+                Ok((
+                    height as i64,
+                    entry.index as i64,
+                    entry.account.to_string(),
+                    fee_ledger_kind(&entry.kind),
+                    entry.amount.0.to_string(),
+                    bincode::serialize(entry)?,
+                ))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        self.upsert(
+            "fee_ledger",
+            ["height", "idx", "account", "kind", "amount", "data"],
+            ["height", "idx"],
+            rows,
+        )
+        .await
+    }
+}
+
+impl ValidatorActivityDataSource for SqlStorage {
+    async fn get_validator_activity(
+        &self,
+        epoch: u64,
+    ) -> anyhow::Result<HashMap<Address, ValidatorActivity>> {
+        let mut tx = self.read().await.context(format!(
+            "opening transaction to fetch validator activity for epoch {epoch}"
+        ))?;
+
+        let proposals = query_as::<(String, i64)>(
+            "SELECT validator, count(*) FROM reward_distribution WHERE epoch = $1
+              GROUP BY validator",
+        )
+        .bind(epoch as i64)
+        .fetch_all(tx.as_mut())
+        .await?;
+        let votes =
+            query_as::<(String, i64)>("SELECT validator, votes FROM epoch_votes WHERE epoch = $1")
+                .bind(epoch as i64)
+                .fetch_all(tx.as_mut())
+                .await?;
+
+        let mut activity = HashMap::<Address, ValidatorActivity>::new();
+        for (validator, count) in proposals {
+            let RewardAccount(validator) = validator.parse()?;
+            activity.entry(validator).or_default().proposals = count as u64;
+        }
+        for (validator, count) in votes {
+            let RewardAccount(validator) = validator.parse()?;
+            activity.entry(validator).or_default().votes = count as u64;
+        }
+        Ok(activity)
+    }
+}
+
+impl ValidatorActivityDataSource for DataSource {
+    async fn get_validator_activity(
+        &self,
+        epoch: u64,
+    ) -> anyhow::Result<HashMap<Address, ValidatorActivity>> {
+        self.as_ref().get_validator_activity(epoch).await
+    }
+}
+
+impl RewardHistoryDataSource for SqlStorage {
+    async fn get_block_rewards(&self, height: u64) -> anyhow::Result<Option<RewardDistribution>> {
+        let mut tx = self.read().await.context(format!(
+            "opening transaction to fetch rewards for block {height}"
+        ))?;
+        let Some((data,)) =
+            query_as::<(Vec<u8>,)>("SELECT data FROM reward_distribution WHERE height = $1")
+                .bind(height as i64)
+                .fetch_optional(tx.as_mut())
+                .await?
+        else {
+            return Ok(None);
+        };
+        let distribution =
+            bincode::deserialize(&data).context("failed to deserialize reward distribution")?;
+        Ok(Some(distribution))
+    }
+
+    async fn get_epoch_rewards(
+        &self,
+        epoch: u64,
+        validator: RewardAccount,
+    ) -> anyhow::Result<EpochRewards> {
+        let mut tx = self.read().await.context(format!(
+            "opening transaction to fetch epoch {epoch} rewards for {validator}"
+        ))?;
+
+        let (blocks,) = query_as::<(i64,)>(
+            "SELECT count(*) FROM reward_distribution WHERE epoch = $1 AND validator = $2",
+        )
+        .bind(epoch as i64)
+        .bind(validator.to_string())
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        let rows = query_as::<(String, bool, String)>(
+            "SELECT account, commission, amount FROM epoch_reward
+              WHERE epoch = $1 AND validator = $2
+              ORDER BY account",
+        )
+        .bind(epoch as i64)
+        .bind(validator.to_string())
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        let mut rewards = EpochRewards {
+            epoch,
+            validator,
+            blocks: blocks as u64,
+            commission: Default::default(),
+            delegators: vec![],
+        };
+        for (account, commission, amount) in rows {
+            let amount = RewardAmount(parse_amount(&amount)?);
+            if commission {
+                rewards.commission = amount;
+            } else {
+                rewards.delegators.push((account.parse()?, amount));
+            }
+        }
+        Ok(rewards)
+    }
+
+    async fn get_delegator_epoch_reward(
+        &self,
+        epoch: u64,
+        validator: RewardAccount,
+        delegator: RewardAccount,
+    ) -> anyhow::Result<RewardAmount> {
+        let mut tx = self.read().await.context(format!(
+            "opening transaction to fetch epoch {epoch} reward for {delegator}"
+        ))?;
+        let amount = query_as::<(String,)>(
+            "SELECT amount FROM epoch_reward
+              WHERE epoch = $1 AND validator = $2 AND account = $3 AND NOT commission",
+        )
+        .bind(epoch as i64)
+        .bind(validator.to_string())
+        .bind(delegator.to_string())
+        .fetch_optional(tx.as_mut())
+        .await?;
+        match amount {
+            Some((amount,)) => Ok(RewardAmount(parse_amount(&amount)?)),
+            None => Ok(RewardAmount::default()),
+        }
+    }
+
+    async fn get_reward_totals(&self, account: RewardAccount) -> anyhow::Result<RewardTotals> {
+        let mut tx = self.read().await.context(format!(
+            "opening transaction to fetch reward totals for {account}"
+        ))?;
+        let rows = query_as::<(String, bool, String)>(
+            "SELECT validator, commission, amount FROM epoch_reward WHERE account = $1",
+        )
+        .bind(account.to_string())
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        let mut commission = U256::ZERO;
+        let mut delegated = BTreeMap::<RewardAccount, U256>::new();
+        for (validator, is_commission, amount) in rows {
+            let amount = parse_amount(&amount)?;
+            let total = if is_commission {
+                &mut commission
+            } else {
+                delegated.entry(validator.parse()?).or_default()
+            };
+            *total = total
+                .checked_add(amount)
+                .context(format!("overflowed total reward for {account}"))?;
+        }
+
+        let total = delegated
+            .values()
+            .try_fold(commission, |total, amount| total.checked_add(*amount))
+            .context(format!("overflowed total reward for {account}"))?;
+        Ok(RewardTotals {
+            commission: commission.into(),
+            delegated: delegated
+                .into_iter()
+                .map(|(validator, amount)| (validator, amount.into()))
+                .collect(),
+            total: total.into(),
+        })
+    }
+}
+
+impl RewardHistoryDataSource for DataSource {
+    async fn get_block_rewards(&self, height: u64) -> anyhow::Result<Option<RewardDistribution>> {
+        self.as_ref().get_block_rewards(height).await
+    }
+
+    async fn get_epoch_rewards(
+        &self,
+        epoch: u64,
+        validator: RewardAccount,
+    ) -> anyhow::Result<EpochRewards> {
+        self.as_ref().get_epoch_rewards(epoch, validator).await
+    }
+
+    async fn get_delegator_epoch_reward(
+        &self,
+        epoch: u64,
+        validator: RewardAccount,
+        delegator: RewardAccount,
+    ) -> anyhow::Result<RewardAmount> {
+        self.as_ref()
+            .get_delegator_epoch_reward(epoch, validator, delegator)
+            .await
+    }
+
+    async fn get_reward_totals(&self, account: RewardAccount) -> anyhow::Result<RewardTotals> {
+        self.as_ref().get_reward_totals(account).await
+    }
+}
+
+impl FeeLedgerDataSource for SqlStorage {
+    async fn get_fee_statement(
+        &self,
+        account: FeeAccount,
+        from: u64,
+        to: u64,
+    ) -> anyhow::Result<FeeStatement> {
+        let mut tx = self.read().await.context(format!(
+            "opening transaction to fetch fee statement for {account}"
+        ))?;
+        let rows = query_as::<(String, String, Vec<u8>)>(
+            "SELECT kind, amount, data FROM fee_ledger
+              WHERE account = $1 AND height >= $2 AND height < $3
+              ORDER BY height, idx",
+        )
+        .bind(account.to_string())
+        .bind(from as i64)
+        .bind(to as i64)
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        let mut deposited = U256::ZERO;
+        let mut paid = U256::ZERO;
+        let mut received = U256::ZERO;
+        let mut withdrawn = U256::ZERO;
+        let mut deposits = vec![];
+        let mut withdrawals = vec![];
+        for (kind, amount, data) in rows {
+            let amount = parse_amount(&amount)?;
+            let total = match kind.as_str() {
+                "deposit" => {
+                    deposits.push(
+                        bincode::deserialize(&data)
+                            .context("failed to deserialize fee ledger entry")?,
+                    );
+                    &mut deposited
+                },
+                "fee_paid" => &mut paid,
+                "fee_received" => &mut received,
+                "withdrawal" => {
+                    withdrawals.push(
+                        bincode::deserialize(&data)
+                            .context("failed to deserialize fee ledger entry")?,
+                    );
+                    &mut withdrawn
+                },
+                _ => bail!("unknown fee ledger entry kind {kind}"),
+            };
+            *total = total
+                .checked_add(amount)
+                .context(format!("overflowed fee statement for {account}"))?;
+        }
+
+        Ok(FeeStatement {
+            account,
+            from,
+            to,
+            deposited: deposited.into(),
+            paid: paid.into(),
+            received: received.into(),
+            withdrawn: withdrawn.into(),
+            deposits,
+            withdrawals,
+        })
+    }
+
+    async fn get_fee_history(
+        &self,
+        account: FeeAccount,
+        before: Option<(u64, u64)>,
+        limit: usize,
+    ) -> anyhow::Result<FeeLedgerPage> {
+        let mut tx = self.read().await.context(format!(
+            "opening transaction to fetch fee history for {account}"
+        ))?;
+        let (height, index) = before
+            .map(|(height, index)| (height as i64, index as i64))
+            .unwrap_or((i64::MAX, i64::MAX));
+
+        // Fetch one extra entry to find out whether there is another page after this one.
+        let rows = query_as::<(Vec<u8>,)>(
+            "SELECT data FROM fee_ledger
+              WHERE account = $1 AND (height < $2 OR (height = $2 AND idx < $3))
+              ORDER BY height DESC, idx DESC
+              LIMIT $4",
+        )
+        .bind(account.to_string())
+        .bind(height)
+        .bind(index)
+        .bind((limit + 1) as i64)
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        let mut entries = rows
+            .into_iter()
+            .map(|(data,)| {
+                bincode::deserialize::<FeeLedgerEntry>(&data)
+                    .context("failed to deserialize fee ledger entry")
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let next = if entries.len() > limit {
+            entries.truncate(limit);
+            entries.last().map(|entry| (entry.height, entry.index))
+        } else {
+            None
+        };
+        Ok(FeeLedgerPage { entries, next })
+    }
+}
+
+impl FeeLedgerDataSource for DataSource {
+    async fn get_fee_statement(
+        &self,
+        account: FeeAccount,
+        from: u64,
+        to: u64,
+    ) -> anyhow::Result<FeeStatement> {
+        self.as_ref().get_fee_statement(account, from, to).await
+    }
+
+    async fn get_fee_history(
+        &self,
+        account: FeeAccount,
+        before: Option<(u64, u64)>,
+        limit: usize,
+    ) -> anyhow::Result<FeeLedgerPage> {
+        self.as_ref().get_fee_history(account, before, limit).await
+    }
+}
+
+/// Parse a reward or fee amount stored as a decimal string.
+fn parse_amount(amount: &str) -> anyhow::Result<U256> {
+    U256::from_str_radix(amount, 10).context(format!("malformed amount {amount}"))
+}
+
+/// The value of the `kind` column for a fee ledger entry.
+fn fee_ledger_kind(kind: &FeeLedgerEntryKind) -> &'static str {
+    match kind {
+        FeeLedgerEntryKind::Deposit { .. } => "deposit",
+        FeeLedgerEntryKind::FeePaid { .. } => "fee_paid",
+        FeeLedgerEntryKind::FeeReceived { .. } => "fee_received",
+        FeeLedgerEntryKind::Withdrawal { .. } => "withdrawal",
+    }
+}
+
 async fn load_frontier<Mode: TransactionMode>(
     tx: &mut Transaction<Mode>,
     height: u64,
 ) -> anyhow::Result<BlocksFrontier> {
-    tx.get_path(
-        Snapshot::<SeqTypes, BlockMerkleTree, { BlockMerkleTree::ARITY }>::Index(height),
+    load_block_proof(
+        tx,
+        height,
         height
             .checked_sub(1)
             .ok_or(anyhow::anyhow!("Subtract with overflow ({height})!"))?,
@@ -329,6 +844,22 @@ async fn load_frontier<Mode: TransactionMode>(
     .context(format!("fetching frontier at height {height}"))
 }
 
+/// Load a proof of the header of block `index` in the block Merkle tree as of block `height`.
+pub(crate) async fn load_block_proof<Mode: TransactionMode>(
+    tx: &mut Transaction<Mode>,
+    height: u64,
+    index: u64,
+) -> anyhow::Result<BlocksFrontier> {
+    tx.get_path(
+        Snapshot::<SeqTypes, BlockMerkleTree, { BlockMerkleTree::ARITY }>::Index(height),
+        index,
+    )
+    .await
+    .context(format!(
+        "fetching proof of block {index} at height {height}"
+    ))
+}
+
 async fn load_reward_accounts<Mode: TransactionMode>(
     tx: &mut Transaction<Mode>,
     height: u64,
@@ -438,6 +969,110 @@ async fn load_chain_config<Mode: TransactionMode>(
     bincode::deserialize(&data[..]).context("failed to deserialize")
 }
 
+/// Load the complete state after the block at `height`.
+///
+/// Unlike [`reconstruct_state`], this does not replay any headers. Instead it loads every account
+/// present in the merklized state tables as of `height`, so the state at `height` must not have
+/// been pruned.
+pub(crate) async fn load_full_state<Mode: TransactionMode>(
+    tx: &mut Transaction<Mode>,
+    height: u64,
+) -> anyhow::Result<(ValidatedState, Leaf2)> {
+    let leaf = tx
+        .get_leaf(LeafId::<SeqTypes>::from(height as usize))
+        .await
+        .context(format!("leaf {height} not available"))?;
+    let leaf = leaf.leaf().clone();
+    let header = leaf.block_header();
+    let mut state = ValidatedState::from_header(header);
+
+    let fee_accounts =
+        load_merkle_entries::<_, FeeAccount, FeeAmount>(tx, FeeMerkleTree::state_type(), height)
+            .await?;
+    tracing::info!(height, count = fee_accounts.len(), "loaded fee accounts");
+    state.fee_merkle_tree = FeeMerkleTree::from_kv_set(FEE_MERKLE_TREE_HEIGHT, fee_accounts)?;
+    ensure!(
+        state.fee_merkle_tree.commitment() == header.fee_merkle_tree_root(),
+        "loaded fee state does not match header {height}"
+    );
+
+    state.reward_merkle_tree = if header.version() >= EpochVersion::version() {
+        let reward_accounts = load_merkle_entries::<_, RewardAccount, RewardAmount>(
+            tx,
+            RewardMerkleTree::state_type(),
+            height,
+        )
+        .await?;
+        tracing::info!(
+            height,
+            count = reward_accounts.len(),
+            "loaded reward accounts"
+        );
+        RewardMerkleTree::from_kv_set(REWARD_MERKLE_TREE_HEIGHT, reward_accounts)?
+    } else {
+        RewardMerkleTree::new(REWARD_MERKLE_TREE_HEIGHT)
+    };
+    ensure!(
+        state.reward_merkle_tree.commitment() == header.reward_merkle_tree_root(),
+        "loaded reward state does not match header {height}"
+    );
+
+    // The genesis block tree is empty, so there is no frontier to load.
+    if height > 0 {
+        let frontier = load_frontier(tx, height).await?;
+        match frontier.proof.first().context("empty proof for frontier")? {
+            MerkleNode::Leaf { pos, elem, .. } => state
+                .block_merkle_tree
+                .remember(*pos, *elem, frontier)
+                .context("failed to remember frontier")?,
+            _ => bail!("invalid frontier proof"),
+        }
+    }
+
+    let chain_config = match header.chain_config().resolve() {
+        Some(chain_config) => chain_config,
+        None => load_chain_config(tx, header.chain_config().commit()).await?,
+    };
+    state.chain_config = chain_config.into();
+
+    Ok((state, leaf))
+}
+
+/// Load every key-value pair stored in a merklized state table as of block `height`.
+async fn load_merkle_entries<Mode, K, V>(
+    tx: &mut Transaction<Mode>,
+    table: &str,
+    height: u64,
+) -> anyhow::Result<Vec<(K, V)>>
+where
+    Mode: TransactionMode,
+    K: DeserializeOwned,
+    V: DeserializeOwned,
+{
+    // Find the latest version of each node as of `height`, and keep only the leaves. A leaf which
+    // was later removed from the tree is superseded by an empty node at the same path.
+    let rows = query_as::<(JsonValue, JsonValue)>(&format!(
+        "SELECT t.idx, t.entry FROM {table} AS t
+           JOIN (SELECT path, max(created) AS created FROM {table}
+                  WHERE created <= $1 GROUP BY path) AS latest
+             ON t.path = latest.path AND t.created = latest.created
+          WHERE t.idx IS NOT NULL AND t.entry IS NOT NULL"
+    ))
+    .bind(height as i64)
+    .fetch_all(tx.as_mut())
+    .await
+    .context(format!("loading {table} entries at height {height}"))?;
+
+    rows.into_iter()
+        .map(|(idx, entry)| {
+            Ok((
+                serde_json::from_value(idx).context(format!("malformed {table} index"))?,
+                serde_json::from_value(entry).context(format!("malformed {table} entry"))?,
+            ))
+        })
+        .collect()
+}
+
 #[tracing::instrument(skip(instance, tx))]
 async fn reconstruct_state<Mode: TransactionMode>(
     instance: &NodeState,
@@ -620,8 +1255,14 @@ async fn fee_header_dependencies<Mode: TransactionMode>(
             get_l1_deposits(instance, header, parent, chain_config.fee_contract)
                 .await
                 .into_iter()
-                .map(|fee| fee.account()),
+                .map(|deposit| deposit.fee_info.account()),
         );
+        if header.version() >= EpochVersion::version() {
+            let withdrawals =
+                get_l1_withdrawal_requests(instance, header, parent, chain_config.fee_contract)
+                    .await;
+            accounts.extend(withdrawal_accounts(&withdrawals));
+        }
         accounts.extend(header.fee_info().accounts());
         parent = proposal;
     }
@@ -763,3 +1404,287 @@ mod generic_tests {
 
     instantiate_api_tests!(DataSource);
 }
+
+#[cfg(test)]
+mod test {
+    use alloy::primitives::Address;
+    use espresso_types::{
+        v0_1::{FeeTransfer, L1Deposit, L1WithdrawalRequest},
+        FeeInfo,
+    };
+    use hotshot_query_service::data_source::Transaction as _;
+    use sequencer_utils::test_utils::setup_test;
+
+    use super::*;
+    use crate::api::data_source::testing::TestableSequencerDataSource;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reward_history() {
+        setup_test();
+
+        let storage = DataSource::create_storage().await;
+        let ds = DataSource::create(
+            DataSource::persistence_options(&storage),
+            Default::default(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        let validator = RewardAccount(Address::random());
+        let delegator = RewardAccount(Address::random());
+        let distribution =
+            |commission: u64, delegated: u64, self_delegated: u64| RewardDistribution {
+                validator,
+                commission: commission.into(),
+                delegators: vec![
+                    (delegator, delegated.into()),
+                    (validator, self_delegated.into()),
+                ],
+            };
+
+        let mut tx = ds.write().await.unwrap();
+        tx.insert_reward_distribution(1, 3, distribution(10, 20, 5))
+            .await
+            .unwrap();
+        // Indexing the same block again must not count its rewards twice.
+        tx.insert_reward_distribution(1, 3, distribution(10, 20, 5))
+            .await
+            .unwrap();
+        tx.insert_reward_distribution(2, 3, distribution(1, 2, 3))
+            .await
+            .unwrap();
+        tx.insert_reward_distribution(3, 4, distribution(100, 200, 300))
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(
+            ds.get_block_rewards(1).await.unwrap(),
+            Some(distribution(10, 20, 5))
+        );
+        assert_eq!(ds.get_block_rewards(4).await.unwrap(), None);
+
+        let mut rewards = ds.get_epoch_rewards(3, validator).await.unwrap();
+        rewards.delegators.sort();
+        let mut expected = vec![(delegator, 22.into()), (validator, 8.into())];
+        expected.sort();
+        assert_eq!(
+            rewards,
+            EpochRewards {
+                epoch: 3,
+                validator,
+                blocks: 2,
+                commission: 11.into(),
+                delegators: expected,
+            }
+        );
+        assert_eq!(
+            ds.get_epoch_rewards(3, delegator).await.unwrap(),
+            EpochRewards {
+                epoch: 3,
+                validator: delegator,
+                blocks: 0,
+                commission: 0.into(),
+                delegators: vec![],
+            }
+        );
+
+        assert_eq!(
+            ds.get_delegator_epoch_reward(3, validator, delegator)
+                .await
+                .unwrap(),
+            22.into()
+        );
+        // The validator's own delegation is reported separately from its commission.
+        assert_eq!(
+            ds.get_delegator_epoch_reward(3, validator, validator)
+                .await
+                .unwrap(),
+            8.into()
+        );
+        assert_eq!(
+            ds.get_delegator_epoch_reward(5, validator, delegator)
+                .await
+                .unwrap(),
+            0.into()
+        );
+
+        assert_eq!(
+            ds.get_reward_totals(validator).await.unwrap(),
+            RewardTotals {
+                commission: 111.into(),
+                delegated: vec![(validator, 308.into())],
+                total: 419.into(),
+            }
+        );
+        assert_eq!(
+            ds.get_reward_totals(delegator).await.unwrap(),
+            RewardTotals {
+                commission: 0.into(),
+                delegated: vec![(validator, 222.into())],
+                total: 222.into(),
+            }
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_fee_ledger() {
+        setup_test();
+
+        let storage = DataSource::create_storage().await;
+        let ds = DataSource::create(
+            DataSource::persistence_options(&storage),
+            Default::default(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        let builder = FeeAccount::from(Address::random());
+        let recipient = FeeAccount::from(Address::random());
+        let deposit = |amount: u64, l1_block: u64| L1Deposit {
+            fee_info: FeeInfo::new(builder, amount),
+            l1_block: Some(l1_block),
+            l1_tx_hash: Some(Default::default()),
+            l1_log_index: Some(0),
+        };
+        let fee = |amount: u64| FeeTransfer {
+            from: builder,
+            to: recipient,
+            amount: amount.into(),
+        };
+        let withdrawal = |amount: u64, l1_block: u64| L1WithdrawalRequest {
+            fee_info: FeeInfo::new(builder, amount),
+            l1_block: Some(l1_block),
+            l1_tx_hash: Some(Default::default()),
+            l1_log_index: Some(1),
+        };
+        let blocks = [
+            FeeLedgerEntry::from_block(1, &[deposit(100, 10)], &[fee(5)], &[]),
+            FeeLedgerEntry::from_block(2, &[], &[fee(7)], &[]),
+            FeeLedgerEntry::from_block(3, &[deposit(50, 12)], &[], &[withdrawal(20, 12)]),
+        ];
+
+        let mut tx = ds.write().await.unwrap();
+        for entries in &blocks {
+            tx.insert_fee_ledger(entries[0].height, entries.clone())
+                .await
+                .unwrap();
+        }
+        // Indexing the same block again must not duplicate its entries.
+        tx.insert_fee_ledger(1, blocks[0].clone()).await.unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(
+            ds.get_fee_statement(builder, 0, 4).await.unwrap(),
+            FeeStatement {
+                account: builder,
+                from: 0,
+                to: 4,
+                deposited: 150.into(),
+                paid: 12.into(),
+                received: 0.into(),
+                withdrawn: 20.into(),
+                deposits: vec![blocks[0][0], blocks[2][0]],
+                withdrawals: vec![blocks[2][1]],
+            }
+        );
+        assert_eq!(
+            ds.get_fee_statement(recipient, 2, 3).await.unwrap(),
+            FeeStatement {
+                account: recipient,
+                from: 2,
+                to: 3,
+                deposited: 0.into(),
+                paid: 0.into(),
+                received: 7.into(),
+                withdrawn: 0.into(),
+                deposits: vec![],
+                withdrawals: vec![],
+            }
+        );
+
+        // Page through the builder's history, newest first.
+        let page = ds.get_fee_history(builder, None, 2).await.unwrap();
+        assert_eq!(page.entries, [blocks[2][1], blocks[2][0]]);
+        assert_eq!(page.next, Some((3, 0)));
+        let page = ds.get_fee_history(builder, page.next, 2).await.unwrap();
+        assert_eq!(page.entries, [blocks[1][0], blocks[0][1]]);
+        assert_eq!(page.next, Some((1, 1)));
+        let page = ds.get_fee_history(builder, page.next, 2).await.unwrap();
+        assert_eq!(page.entries, [blocks[0][0]]);
+        assert_eq!(page.next, None);
+
+        let page = ds.get_fee_history(recipient, None, 10).await.unwrap();
+        assert_eq!(page.entries, [blocks[1][1], blocks[0][2]]);
+        assert_eq!(page.next, None);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_validator_activity() {
+        setup_test();
+
+        let storage = DataSource::create_storage().await;
+        let ds = DataSource::create(
+            DataSource::persistence_options(&storage),
+            Default::default(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        let leader = RewardAccount(Address::random());
+        let voter = RewardAccount(Address::random());
+        let distribution = RewardDistribution {
+            validator: leader,
+            commission: 1.into(),
+            delegators: vec![],
+        };
+
+        let mut tx = ds.write().await.unwrap();
+        tx.insert_reward_distribution(1, 3, distribution.clone())
+            .await
+            .unwrap();
+        tx.insert_votes(1, 3, vec![leader, voter]).await.unwrap();
+        // Indexing the same block again must not count its votes twice.
+        tx.insert_votes(1, 3, vec![leader, voter]).await.unwrap();
+        tx.insert_reward_distribution(2, 3, distribution.clone())
+            .await
+            .unwrap();
+        tx.insert_votes(2, 3, vec![voter]).await.unwrap();
+        tx.insert_votes(3, 4, vec![leader]).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let activity = ds.get_validator_activity(3).await.unwrap();
+        assert_eq!(activity.len(), 2);
+        assert_eq!(
+            activity[&leader.0],
+            ValidatorActivity {
+                proposals: 2,
+                votes: 1,
+            }
+        );
+        assert_eq!(
+            activity[&voter.0],
+            ValidatorActivity {
+                proposals: 0,
+                votes: 2,
+            }
+        );
+
+        assert_eq!(
+            ds.get_validator_activity(4).await.unwrap(),
+            [(
+                leader.0,
+                ValidatorActivity {
+                    proposals: 0,
+                    votes: 1,
+                }
+            )]
+            .into_iter()
+            .collect()
+        );
+        assert!(ds.get_validator_activity(5).await.unwrap().is_empty());
+    }
+}
@@ -1,4 +1,10 @@
-use std::{fs::File, io::stdout, path::PathBuf, thread::sleep, time::Duration};
+use std::{
+    fs::File,
+    io::stdout,
+    path::{Path, PathBuf},
+    thread::sleep,
+    time::Duration,
+};
 
 use alloy::{
     network::EthereumWallet,
@@ -10,9 +16,13 @@ use anyhow::Context;
 use clap::Parser;
 use espresso_types::{config::PublicNetworkConfig, parse_duration, SeqTypes};
 use hotshot_stake_table::config::STAKE_TABLE_CAPACITY;
-use hotshot_state_prover::service::light_client_genesis;
+use hotshot_state_prover::service::{light_client_genesis, light_client_genesis_from_stake_table};
 use sequencer_utils::{
-    deployer::{self, transfer_ownership, Contract, Contracts, DeployedContracts},
+    deployer::{
+        self, transfer_ownership,
+        verify::{verify_deployment, DeploymentExpectations},
+        Contract, Contracts, DeployedContracts,
+    },
     logging,
     stake_table::PermissionedStakeTableConfig,
 };
@@ -37,6 +47,9 @@ use vbs::version::StaticVersion;
 /// addresses will be used in place of deploying a new contract wherever that contract is required
 /// in the deployment process. The generated .env file will include all the addresses passed in as
 /// well as those newly deployed.
+///
+/// With --verify, nothing is deployed. Instead, the deployment recorded in a .env file generated
+/// by this script is audited, and a JSON drift report is written in place of the .env file.
 #[derive(Clone, Debug, Parser)]
 struct Options {
     /// A JSON-RPC endpoint for the L1 to deploy to.
@@ -119,6 +132,24 @@ struct Options {
     #[clap(long, default_value = "false")]
     deploy_stake_table: bool,
 
+    /// Verify the deployment recorded in DEPLOYMENT_ENV_FILE instead of deploying.
+    ///
+    /// The file has the format of the .env file written by this script. Each proxy's
+    /// implementation code is compared with the compiled contracts, and owners, the permissioned
+    /// prover and initializer values with the ones this script would use given the other options.
+    /// The fee contract must use the recorded fee withdrawal verifier, if any, or else have
+    /// withdrawals disabled.
+    /// Genesis and epoch values are only checked if the sequencer at SEQUENCER_URL is reachable,
+    /// and epoch values only with --upgrade-light-client-v2.
+    ///
+    /// A JSON drift report is written to OUT, and the script fails if any drift is found.
+    #[clap(
+        long,
+        name = "DEPLOYMENT_ENV_FILE",
+        env = "ESPRESSO_DEPLOYER_VERIFY_PATH"
+    )]
+    verify: Option<PathBuf>,
+
     /// Write deployment results to OUT as a .env file.
     ///
     /// If not provided, the results will be written to stdout.
@@ -147,6 +178,12 @@ struct Options {
     #[clap(long, env = "ESPRESSO_SEQUENCER_PERMISSIONED_PROVER")]
     permissioned_prover: Option<Address>,
 
+    /// Attester expected to sign fee withdrawals, checked by --verify.
+    ///
+    /// If unset, the attester of the fee withdrawal verifier is reported but not checked.
+    #[clap(long, env = "ESPRESSO_DEPLOYER_FEE_WITHDRAWAL_ATTESTER")]
+    fee_withdrawal_attester: Option<Address>,
+
     /// A toml file with the initial stake table.
     ///
     /// Schema:
@@ -186,6 +223,10 @@ async fn main() -> anyhow::Result<()> {
     let opt = Options::parse();
     opt.logging.init();
 
+    if let Some(path) = &opt.verify {
+        return verify(&opt, path).await;
+    }
+
     let mut contracts = Contracts::from(opt.contracts);
 
     let signer = MnemonicBuilder::<English>::default()
@@ -367,3 +408,80 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Audit the deployment recorded in the .env file at `path`, see [`Options::verify`].
+async fn verify(opt: &Options, path: &Path) -> anyhow::Result<()> {
+    dotenvy::from_path_override(path)
+        .with_context(|| format!("failed to load deployment from {}", path.display()))?;
+    let contracts = Contracts::from(DeployedContracts::try_parse_from(["deploy"])?);
+
+    let deployer = MnemonicBuilder::<English>::default()
+        .phrase(opt.mnemonic.clone())
+        .index(opt.account_index)
+        .expect("wrong mnemonic or index")
+        .build()
+        .expect("fail to build signer")
+        .address();
+    let mut expected = DeploymentExpectations {
+        owner: Some(opt.multisig_address.unwrap_or(deployer)),
+        permissioned_prover: opt.permissioned_prover,
+        exit_escrow_period: opt
+            .exit_escrow_period
+            .map(|period| U256::from(period.as_secs())),
+        attester: opt.fee_withdrawal_attester,
+        use_mock: opt.use_mock,
+        ..Default::default()
+    };
+
+    // Unlike when deploying, don't wait for the sequencer, so the rest can be audited without it.
+    match surf_disco::Client::<ServerError, StaticVersion<0, 1>>::new(opt.sequencer_url.clone())
+        .get::<PublicNetworkConfig>("config/hotshot")
+        .send()
+        .await
+    {
+        Ok(resp) => {
+            let config = resp.hotshot_config();
+            let (genesis_state, genesis_stake) = light_client_genesis_from_stake_table(
+                &config.known_nodes_with_stake(),
+                opt.stake_table_capacity,
+            )?;
+            expected.genesis_state = Some(genesis_state);
+            expected.genesis_stake = Some(genesis_stake);
+
+            // Only a light client upgraded to V2 is configured with epochs.
+            if opt.upgrade_light_client_v2 {
+                let mut blocks_per_epoch = config.blocks_per_epoch();
+                // Same TEST-ONLY default as when upgrading to LightClientV2.
+                if opt.use_mock && blocks_per_epoch == 0 {
+                    blocks_per_epoch = u64::MAX;
+                }
+                expected.blocks_per_epoch = Some(blocks_per_epoch);
+                expected.epoch_start_block = Some(config.epoch_start_block());
+            }
+        },
+        Err(e) => {
+            tracing::warn!(
+                "Failed to fetch the network config, skipping genesis and epoch checks: {e}"
+            );
+        },
+    }
+
+    let provider = ProviderBuilder::new().on_http(opt.rpc_url.clone());
+    let report = verify_deployment(&provider, &contracts, &expected).await;
+
+    if let Some(out) = &opt.out {
+        let file = File::options()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(out)?;
+        serde_json::to_writer_pretty(file, &report)?;
+    } else {
+        serde_json::to_writer_pretty(stdout(), &report)?;
+        println!();
+    }
+
+    let drift = report.drift().len();
+    anyhow::ensure!(drift == 0, "deployment drift detected in {drift} checks");
+    Ok(())
+}
@@ -3,15 +3,21 @@ use std::time::Duration;
 use alloy::{
     eips::{BlockId, BlockNumberOrTag},
     network::EthereumWallet,
-    primitives::{Address, U256},
+    primitives::{Address, Bytes, U256},
     providers::{Provider, ProviderBuilder},
+    signers::Signer,
+    sol_types::SolValue,
 };
 use anyhow::{bail, ensure, Context};
 use clap::{Parser, Subcommand};
 use client::SequencerClient;
-use espresso_types::{eth_signature_key::EthKeyPair, parse_duration, Header};
+use espresso_types::{eth_signature_key::EthKeyPair, parse_duration, FeeAccount, Header};
 use futures::stream::StreamExt;
-use hotshot_contract_adapter::sol_types::FeeContract;
+use hotshot_contract_adapter::sol_types::{
+    FeeContract, FeeContractWithdrawals, FeeWithdrawalAttestationSol, FeeWithdrawalVerifier,
+    LightClientStateSol, LightClientV2,
+};
+use hotshot_types::light_client::LightClientState;
 use sequencer_utils::logging;
 use surf_disco::Url;
 
@@ -28,6 +34,9 @@ struct Options {
 #[derive(Debug, Subcommand)]
 enum Command {
     Deposit(Deposit),
+    Withdraw(Withdraw),
+    Attest(Attest),
+    Claim(Claim),
     Balance(Balance),
     L1Balance(L1Balance),
 }
@@ -79,6 +88,121 @@ struct Deposit {
     confirmations: usize,
 }
 
+/// Withdraw ETH from Espresso back to the L1.
+///
+/// The withdrawal is requested on the L1, and takes effect on Espresso once the request is
+/// finalized, by moving the amount into the withdrawal escrow of the account. Once the light client
+/// contract has caught up, the withdrawal can be attested to using the `attest` command, and the
+/// funds paid out on the L1 using the `claim` command.
+#[derive(Debug, Parser)]
+struct Withdraw {
+    /// L1 JSON-RPC provider.
+    #[clap(short, long, env = "L1_PROVIDER")]
+    rpc_url: Url,
+
+    /// Espresso query service provider.
+    ///
+    /// This must point to an Espresso node running the /availability, /node and Merklized state
+    /// (/fee-state and /block-state) APIs.
+    #[clap(short, long, env = "ESPRESSO_PROVIDER")]
+    espresso_provider: Url,
+
+    /// The address of the Espresso fee contract on the L1.
+    #[clap(short, long, env = "CONTRACT_ADDRESS")]
+    contract_address: Address,
+
+    /// Mnemonic to generate the account from which to withdraw.
+    #[clap(short, long, env = "MNEMONIC")]
+    mnemonic: String,
+
+    /// Account index when deriving an account from MNEMONIC.
+    #[clap(short = 'i', long, env = "ACCOUNT_INDEX", default_value = "0")]
+    account_index: u32,
+
+    /// Amount of WEI to withdraw.
+    #[clap(short, long, env = "AMOUNT")]
+    amount: u64,
+
+    /// Number of confirmations to wait for before considering an L1 transaction mined.
+    #[clap(long, env = "CONFIRMATIONS", default_value = "6")]
+    confirmations: usize,
+}
+
+/// Attest to the amount withdrawn from an Espresso account, so that it can be claimed on the L1.
+///
+/// This is run by the holder of the attester key of the fee withdrawal verifier contract. It proves
+/// the amount withdrawn from the account as of the last Espresso block finalized by the light
+/// client contract, checks the proof, and signs the amount. The attestation is written to standard
+/// out, to be passed to the `claim` command by the owner of the account.
+#[derive(Debug, Parser)]
+struct Attest {
+    /// L1 JSON-RPC provider.
+    #[clap(short, long, env = "L1_PROVIDER")]
+    rpc_url: Url,
+
+    /// Espresso query service provider.
+    ///
+    /// This must point to an Espresso node running the /availability and Merklized state
+    /// (/fee-state and /block-state) APIs.
+    #[clap(short, long, env = "ESPRESSO_PROVIDER")]
+    espresso_provider: Url,
+
+    /// The address of the Espresso light client contract on the L1.
+    #[clap(long, env = "LIGHT_CLIENT_ADDRESS")]
+    light_client_address: Address,
+
+    /// The address of the fee withdrawal verifier contract on the L1.
+    #[clap(long, env = "VERIFIER_ADDRESS")]
+    verifier_address: Address,
+
+    /// Mnemonic to generate the attester key.
+    #[clap(short, long, env = "MNEMONIC")]
+    mnemonic: String,
+
+    /// Account index when deriving the attester key from MNEMONIC.
+    #[clap(short = 'i', long, env = "ACCOUNT_INDEX", default_value = "0")]
+    account_index: u32,
+
+    /// The account which withdrew.
+    #[clap(short, long, env = "ADDRESS")]
+    address: Address,
+}
+
+/// Claim ETH withdrawn from Espresso on the L1.
+///
+/// This pays out everything withdrawn from the account as of the attested Espresso block, which
+/// has not already been claimed.
+#[derive(Debug, Parser)]
+struct Claim {
+    /// L1 JSON-RPC provider.
+    #[clap(short, long, env = "L1_PROVIDER")]
+    rpc_url: Url,
+
+    /// The address of the Espresso fee contract on the L1.
+    #[clap(short, long, env = "CONTRACT_ADDRESS")]
+    contract_address: Address,
+
+    /// Mnemonic to generate the account which withdrew.
+    #[clap(short, long, env = "MNEMONIC")]
+    mnemonic: String,
+
+    /// Account index when deriving an account from MNEMONIC.
+    #[clap(short = 'i', long, env = "ACCOUNT_INDEX", default_value = "0")]
+    account_index: u32,
+
+    /// Attestation of the amount withdrawn, as output by the `attest` command.
+    #[clap(long, env = "ATTESTATION")]
+    attestation: Bytes,
+
+    /// L1 account to pay the withdrawn funds to (default: the withdrawing account).
+    #[clap(long, env = "RECIPIENT")]
+    recipient: Option<Address>,
+
+    /// Number of confirmations to wait for before considering an L1 transaction mined.
+    #[clap(long, env = "CONFIRMATIONS", default_value = "6")]
+    confirmations: usize,
+}
+
 /// Check the balance (in ETH) of an Espresso account.
 #[derive(Debug, Parser)]
 struct Balance {
@@ -251,6 +375,248 @@ async fn deposit(opt: Deposit) -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn withdraw(opt: Withdraw) -> anyhow::Result<()> {
+    // Derive the account to withdraw from.
+    let key_pair = EthKeyPair::from_mnemonic(opt.mnemonic, opt.account_index)?;
+    let signer = key_pair.signer();
+    let escrow = FeeAccount::from(signer.address())
+        .withdrawal_escrow()
+        .address();
+
+    // Connect to L1.
+    let l1 = ProviderBuilder::new()
+        .wallet(EthereumWallet::from(signer.clone()))
+        .on_http(opt.rpc_url);
+    let contract = FeeContractWithdrawals::new(opt.contract_address, &l1);
+
+    // Connect to Espresso.
+    let espresso = SequencerClient::new(opt.espresso_provider);
+
+    // Validate withdrawal. A request for more than the balance is ignored by Espresso, so catch it
+    // before spending gas on it.
+    let amount = U256::from(opt.amount);
+    let balance = espresso
+        .get_espresso_balance(signer.address(), None)
+        .await
+        .context("getting Espresso balance")?;
+    ensure!(
+        balance >= amount.into(),
+        "amount is too large (Espresso balance: {balance})",
+    );
+
+    // Record the initial escrow balance on Espresso.
+    let initial_escrow = espresso
+        .get_espresso_balance(escrow, None)
+        .await
+        .context("getting Espresso escrow balance")?;
+    tracing::debug!(%initial_escrow, "initial escrow balance");
+
+    // Send the withdrawal request.
+    tracing::info!(address = %signer.address(), %amount, "sending withdrawal request");
+    let tx = contract
+        .requestWithdrawal(amount)
+        .send()
+        .await
+        .context("sending withdrawal request")?;
+    tracing::info!(hash = %tx.tx_hash(), "withdrawal request sent to L1");
+
+    // Wait for the transaction to finalize on L1.
+    let receipt = tx
+        .with_required_confirmations(opt.confirmations as u64)
+        .get_receipt()
+        .await
+        .context("waiting for withdrawal request")?;
+    let l1_block = receipt
+        .block_number
+        .context("withdrawal request not mined")?;
+    ensure!(receipt.inner.is_success(), "withdrawal request reverted");
+    tracing::info!(l1_block, "withdrawal request mined on L1");
+
+    // Wait for Espresso to catch up to the L1.
+    let espresso_height = espresso.get_height().await?;
+    let mut headers = espresso.subscribe_headers(espresso_height).await?;
+    let espresso_block = loop {
+        let header: Header = match headers.next().await.context("header stream ended")? {
+            Ok(header) => header,
+            Err(err) => {
+                tracing::warn!("error in header stream: {err:#}");
+                continue;
+            },
+        };
+        let Some(l1_finalized) = header.l1_finalized() else {
+            continue;
+        };
+        if l1_finalized.number() >= l1_block {
+            tracing::info!(block = header.height(), "withdrawal finalized on Espresso");
+            break header.height();
+        } else {
+            tracing::debug!(
+                block = header.height(),
+                l1_block,
+                ?l1_finalized,
+                "waiting for withdrawal on Espresso"
+            )
+        }
+    };
+
+    // Confirm that the escrow balance has increased.
+    let final_escrow = espresso
+        .get_espresso_balance(escrow, Some(espresso_block))
+        .await?;
+    if final_escrow >= initial_escrow + amount.into() {
+        tracing::info!(
+            %final_escrow,
+            "withdrawal successful, funds can be claimed once the light client reaches block \
+             {espresso_block}"
+        );
+    } else {
+        // As with deposits, this could be a race with some other operation on the account, but the
+        // most likely cause is that the balance was spent before the request was finalized.
+        tracing::warn!(%initial_escrow, %final_escrow, "escrow balance did not increase as expected");
+    }
+
+    Ok(())
+}
+
+async fn attest(opt: Attest) -> anyhow::Result<()> {
+    // Derive the attester key.
+    let key_pair = EthKeyPair::from_mnemonic(opt.mnemonic, opt.account_index)?;
+    let signer = key_pair.signer();
+    let account = FeeAccount::from(opt.address);
+
+    // Connect to L1.
+    let l1 = ProviderBuilder::new().on_http(opt.rpc_url);
+    let light_client = LightClientV2::new(opt.light_client_address, &l1);
+    let verifier = FeeWithdrawalVerifier::new(opt.verifier_address, &l1);
+    let attester = verifier
+        .attester()
+        .call()
+        .await
+        .context("getting attester")?
+        ._0;
+    ensure!(
+        attester == signer.address(),
+        "{} is not the attester of the verifier ({attester})",
+        signer.address()
+    );
+
+    // Connect to Espresso.
+    let espresso = SequencerClient::new(opt.espresso_provider);
+
+    // Prove the amount withdrawn as of the light client's finalized state, and check the proof.
+    let sol_state: LightClientStateSol = light_client
+        .finalizedState()
+        .call()
+        .await
+        .context("getting light client state")?
+        .into();
+    let state: LightClientState = sol_state.clone().into();
+    tracing::debug!(?state, "light client state");
+    let proof = espresso
+        .get_fee_withdrawal_proof(state.block_height, account)
+        .await
+        .context("getting withdrawal proof")?;
+    let withdrawn = proof
+        .verify(&state, account)
+        .context("verifying withdrawal proof")?;
+    tracing::info!(%account, %withdrawn, block = state.block_height, "verified withdrawal");
+
+    // Sign the amount along with the state it was proven against.
+    let digest = verifier
+        .attestationDigest(
+            opt.address,
+            withdrawn,
+            sol_state.blockHeight,
+            sol_state.blockCommRoot,
+        )
+        .call()
+        .await
+        .context("getting attestation digest")?
+        ._0;
+    let signature = signer
+        .sign_message(digest.as_slice())
+        .await
+        .context("signing attestation")?;
+    let attestation = FeeWithdrawalAttestationSol {
+        blockHeight: sol_state.blockHeight,
+        blockCommRoot: sol_state.blockCommRoot,
+        signature: signature.as_bytes().into(),
+    };
+
+    // Output on regular standard out, rather than as a log message, to make scripting easier.
+    println!(
+        "{}",
+        Bytes::from((withdrawn, Bytes::from(attestation.abi_encode())).abi_encode_params())
+    );
+
+    Ok(())
+}
+
+async fn claim(opt: Claim) -> anyhow::Result<()> {
+    // Derive the account which withdrew.
+    let key_pair = EthKeyPair::from_mnemonic(opt.mnemonic, opt.account_index)?;
+    let signer = key_pair.signer();
+    let recipient = opt.recipient.unwrap_or(signer.address());
+    let (withdrawn, proof) = <(U256, Bytes)>::abi_decode_params(&opt.attestation, true)
+        .context("malformed attestation")?;
+
+    // Connect to L1.
+    let l1 = ProviderBuilder::new()
+        .wallet(EthereumWallet::from(signer.clone()))
+        .on_http(opt.rpc_url);
+    let contract = FeeContractWithdrawals::new(opt.contract_address, &l1);
+
+    let claimed = contract
+        .claimed(signer.address())
+        .call()
+        .await
+        .context("getting claimed amount")?
+        ._0;
+    if withdrawn <= claimed {
+        // Output on regular standard out, rather than as a log message, to make scripting easier.
+        println!("0");
+        tracing::info!(%withdrawn, %claimed, "nothing left to claim");
+        return Ok(());
+    }
+    let amount = withdrawn - claimed;
+
+    // Check the attestation before spending gas on it.
+    let verifier = contract
+        .withdrawalVerifier()
+        .call()
+        .await
+        .context("getting withdrawal verifier")?
+        ._0;
+    ensure!(!verifier.is_zero(), "withdrawals are not enabled");
+    ensure!(
+        FeeWithdrawalVerifier::new(verifier, &l1)
+            .verifyWithdrawn(signer.address(), withdrawn, proof.clone())
+            .call()
+            .await
+            .context("verifying attestation")?
+            ._0,
+        "attestation was rejected by the withdrawal verifier"
+    );
+
+    tracing::info!(%recipient, %amount, "sending claim transaction");
+    let tx = contract
+        .claimWithdrawal(recipient, withdrawn, proof)
+        .send()
+        .await
+        .context("sending claim transaction")?;
+    tracing::info!(hash = %tx.tx_hash(), "claim transaction sent to L1");
+    let receipt = tx
+        .with_required_confirmations(opt.confirmations as u64)
+        .get_receipt()
+        .await
+        .context("waiting for claim transaction")?;
+    ensure!(receipt.inner.is_success(), "claim transaction reverted");
+    tracing::info!(l1_block = ?receipt.block_number, %amount, "claim successful");
+    println!("{amount}");
+
+    Ok(())
+}
+
 async fn balance(opt: Balance) -> anyhow::Result<()> {
     // Derive the address to look up.
     let address = if let Some(address) = opt.address {
@@ -310,6 +676,9 @@ async fn main() -> anyhow::Result<()> {
 
     match opt.command {
         Command::Deposit(opt) => deposit(opt).await,
+        Command::Withdraw(opt) => withdraw(opt).await,
+        Command::Attest(opt) => attest(opt).await,
+        Command::Claim(opt) => claim(opt).await,
         Command::Balance(opt) => balance(opt).await,
         Command::L1Balance(opt) => l1_balance(opt).await,
     }
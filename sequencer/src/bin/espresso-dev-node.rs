@@ -176,6 +176,13 @@ struct Args {
     #[clap(long, env = "ESPRESSO_DEV_NODE_EPOCH_HEIGHT", default_value_t = 300)]
     epoch_height: u64,
 
+    /// Espresso block height from which fee withdrawals are honored.
+    ///
+    /// If set, the fee contract is registered in the chain config and a withdrawal verifier is
+    /// deployed, with the deployer account as the attester. If not set, withdrawals are disabled.
+    #[clap(long, env = "ESPRESSO_DEV_NODE_FEE_WITHDRAWAL_START_BLOCK")]
+    fee_withdrawal_start_block: Option<u64>,
+
     #[clap(flatten)]
     sql: persistence::sql::Options,
 
@@ -210,6 +217,7 @@ async fn main() -> anyhow::Result<()> {
         l1_interval: _,
         max_block_size,
         epoch_height,
+        fee_withdrawal_start_block,
     } = cli_params;
 
     logging.init();
@@ -229,12 +237,15 @@ async fn main() -> anyhow::Result<()> {
         .parse()
         .unwrap();
 
-    let network_config = TestConfigBuilder::default()
+    let mut network_config = TestConfigBuilder::default()
         .epoch_height(epoch_height)
         .builder_port(builder_port)
         .state_relay_url(relay_server_url.clone())
-        .l1_url(l1_url.clone())
-        .build();
+        .l1_url(l1_url.clone());
+    if let Some(start_block) = fee_withdrawal_start_block {
+        network_config = network_config.fee_withdrawal_start_block(start_block);
+    }
+    let network_config = network_config.build();
     let blocks_per_epoch = network_config.hotshot_config().epoch_height;
     let epoch_start_block = network_config.hotshot_config().epoch_start_block;
 
@@ -370,6 +381,26 @@ async fn main() -> anyhow::Result<()> {
             // deploy fee contract (and proxy)
             let fee_proxy_addr =
                 deployer::deploy_fee_contract_proxy(&provider, contracts, admin).await?;
+            if fee_withdrawal_start_block.is_some() {
+                // deploy the withdrawal verifier, while the fee contract is still owned by `admin`
+                let verifier_addr = deployer::deploy_fee_withdrawal_verifier(
+                    &provider,
+                    contracts,
+                    lc_proxy_addr,
+                    admin,
+                    admin,
+                )
+                .await?;
+                if let Some(multisig) = multisig_address {
+                    deployer::transfer_ownership(
+                        &provider,
+                        Contract::FeeWithdrawalVerifier,
+                        verifier_addr,
+                        multisig,
+                    )
+                    .await?;
+                }
+            }
             if let Some(multisig) = multisig_address {
                 deployer::transfer_ownership(
                     &provider,
@@ -438,11 +469,16 @@ async fn main() -> anyhow::Result<()> {
     let stake_table_address = l1_contracts
         .address(Contract::StakeTableProxy)
         .expect("stake table deployed");
+    let fee_contract_address = l1_contracts
+        .address(Contract::FeeContractProxy)
+        .expect("fee contract deployed");
     let chain_config = ChainConfig {
         max_block_size: max_block_size.into(),
         // TODO: MA: the builder has block fee `123` hardcoded so we have to set this to zero for now.
         base_fee: 0.into(),
         stake_table_contract: Some(stake_table_address),
+        // withdrawals are requested through the fee contract, so Espresso must follow it
+        fee_contract: fee_withdrawal_start_block.map(|_| fee_contract_address),
         ..Default::default()
     };
     tracing::info!("Chain config: {chain_config:?}");
@@ -530,6 +566,8 @@ async fn main() -> anyhow::Result<()> {
         l1_prover_port,
         l1_url,
         l1_light_client_address: l1_lc,
+        l1_fee_contract_address: fee_contract_address,
+        l1_fee_withdrawal_verifier_address: l1_contracts.address(Contract::FeeWithdrawalVerifier),
         alt_chains: alt_chain_providers
             .into_iter()
             .zip(light_client_addresses)
@@ -717,6 +755,8 @@ struct DevInfo {
     pub l1_prover_port: u16,
     pub l1_url: Url,
     pub l1_light_client_address: Address,
+    pub l1_fee_contract_address: Address,
+    pub l1_fee_withdrawal_verifier_address: Option<Address>,
     pub alt_chains: Vec<AltChainInfo>,
 }
 
@@ -746,13 +786,14 @@ mod tests {
 
     use alloy::{
         node_bindings::{Anvil, AnvilInstance},
-        primitives::U256,
+        primitives::{Bytes, U256},
+        sol_types::SolValue,
     };
     use committable::{Commitment, Committable};
     use escargot::CargoBuild;
     use espresso_types::{BlockMerkleTree, Header, SeqTypes, Transaction};
     use futures::{StreamExt, TryStreamExt};
-    use hotshot_contract_adapter::sol_types::LightClientV2Mock;
+    use hotshot_contract_adapter::sol_types::{FeeContractWithdrawals, LightClientV2Mock};
     use hotshot_query_service::availability::{
         BlockQueryData, TransactionQueryData, VidCommonQueryData,
     };
@@ -1268,4 +1309,224 @@ mod tests {
         drop(process);
         drop(alt_providers);
     }
+
+    /// Run an `espresso-bridge` command to completion, returning its standard output if it succeeds.
+    fn run_bridge(args: &[&str]) -> Option<String> {
+        let output = CargoBuild::new()
+            .bin("espresso-bridge")
+            .current_target()
+            .run()
+            .unwrap()
+            .command()
+            .args(args)
+            .output()
+            .unwrap();
+        if output.status.success() {
+            Some(String::from_utf8(output.stdout).unwrap().trim().to_string())
+        } else {
+            tracing::warn!(
+                ?args,
+                stderr = %String::from_utf8_lossy(&output.stderr),
+                "bridge command failed"
+            );
+            None
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn slow_dev_node_fee_withdrawal_test() {
+        setup_test();
+
+        let builder_port = pick_unused_port().unwrap();
+        let api_port = pick_unused_port().unwrap();
+        let dev_node_port = pick_unused_port().unwrap();
+        // Finalize L1 blocks immediately, so deposits and withdrawals are picked up right away.
+        let instance = Anvil::new().args(["--slots-in-an-epoch", "0"]).spawn();
+        let l1_url = instance.endpoint_url();
+
+        let tmp_dir = tempfile::tempdir().unwrap();
+
+        let process = CargoBuild::new()
+            .bin("espresso-dev-node")
+            .features("testing embedded-db")
+            .current_target()
+            .run()
+            .unwrap()
+            .command()
+            .env("ESPRESSO_SEQUENCER_L1_PROVIDER", l1_url.to_string())
+            .env("ESPRESSO_BUILDER_PORT", builder_port.to_string())
+            .env("ESPRESSO_SEQUENCER_API_PORT", api_port.to_string())
+            .env("ESPRESSO_SEQUENCER_ETH_MNEMONIC", TEST_MNEMONIC)
+            .env("ESPRESSO_DEPLOYER_ACCOUNT_INDEX", "0")
+            .env("ESPRESSO_DEV_NODE_PORT", dev_node_port.to_string())
+            .env("ESPRESSO_DEV_NODE_FEE_WITHDRAWAL_START_BLOCK", "0")
+            .env("ESPRESSO_STATE_PROVER_UPDATE_INTERVAL", "2s")
+            .env(
+                "ESPRESSO_SEQUENCER_STORAGE_PATH",
+                tmp_dir.path().as_os_str(),
+            )
+            .env("ESPRESSO_SEQUENCER_DATABASE_MAX_CONNECTIONS", "25")
+            .spawn()
+            .unwrap();
+
+        let process = BackgroundProcess(process);
+
+        let api_client: Client<ServerError, SequencerApiVersion> =
+            Client::new(format!("http://localhost:{api_port}").parse().unwrap());
+        api_client.connect(None).await;
+
+        tracing::info!("waiting for blocks");
+        let _ = api_client
+            .socket("availability/stream/blocks/0")
+            .subscribe::<BlockQueryData<SeqTypes>>()
+            .await
+            .unwrap()
+            .take(5)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        let dev_node_client: Client<ServerError, SequencerApiVersion> =
+            Client::new(format!("http://localhost:{dev_node_port}").parse().unwrap());
+        dev_node_client.connect(None).await;
+        let dev_info = dev_node_client
+            .get::<DevInfo>("api/dev-info")
+            .send()
+            .await
+            .unwrap();
+        let verifier_address = dev_info
+            .l1_fee_withdrawal_verifier_address
+            .expect("withdrawal verifier deployed");
+
+        // The deployer is the attester; the withdrawing user is another account.
+        let user = MnemonicBuilder::<English>::default()
+            .phrase(TEST_MNEMONIC)
+            .index(1)
+            .unwrap()
+            .build()
+            .unwrap()
+            .address();
+        let recipient = Address::random();
+        let provider = ProviderBuilder::new().on_http(l1_url.clone());
+        let fee_contract = FeeContractWithdrawals::new(dev_info.l1_fee_contract_address, &provider);
+
+        let l1 = l1_url.to_string();
+        let espresso = format!("http://localhost:{api_port}");
+        let fee_contract_address = dev_info.l1_fee_contract_address.to_string();
+        let light_client_address = dev_info.l1_light_client_address.to_string();
+        let verifier_address = verifier_address.to_string();
+        let user_address = user.to_string();
+        let recipient_address = recipient.to_string();
+        let deposit = 10_000_000_000_000_000u64;
+        let withdrawal = 4_000_000_000_000_000u64;
+
+        tracing::info!("depositing");
+        run_bridge(&[
+            "deposit",
+            "--rpc-url",
+            &l1,
+            "--espresso-provider",
+            &espresso,
+            "--contract-address",
+            &fee_contract_address,
+            "--mnemonic",
+            TEST_MNEMONIC,
+            "--account-index",
+            "1",
+            "--amount",
+            &deposit.to_string(),
+            "--confirmations",
+            "1",
+        ])
+        .expect("deposit failed");
+
+        tracing::info!("withdrawing");
+        run_bridge(&[
+            "withdraw",
+            "--rpc-url",
+            &l1,
+            "--espresso-provider",
+            &espresso,
+            "--contract-address",
+            &fee_contract_address,
+            "--mnemonic",
+            TEST_MNEMONIC,
+            "--account-index",
+            "1",
+            "--amount",
+            &withdrawal.to_string(),
+            "--confirmations",
+            "1",
+        ])
+        .expect("withdrawal failed");
+
+        // Wait for the light client to catch up to the withdrawal, at which point the attester will
+        // attest to the full amount.
+        let attestation = loop {
+            let attestation = run_bridge(&[
+                "attest",
+                "--rpc-url",
+                &l1,
+                "--espresso-provider",
+                &espresso,
+                "--light-client-address",
+                &light_client_address,
+                "--verifier-address",
+                &verifier_address,
+                "--mnemonic",
+                TEST_MNEMONIC,
+                "--account-index",
+                "0",
+                "--address",
+                &user_address,
+            ]);
+            if let Some(attestation) = attestation {
+                let bytes: Bytes = attestation.parse().unwrap();
+                let (withdrawn, _) = <(U256, Bytes)>::abi_decode_params(&bytes, true).unwrap();
+                if withdrawn == U256::from(withdrawal) {
+                    break attestation;
+                }
+                tracing::info!(%withdrawn, "waiting for light client to finalize withdrawal");
+            }
+            sleep(Duration::from_secs(3)).await;
+        };
+
+        tracing::info!("claiming");
+        let claim = [
+            "claim",
+            "--rpc-url",
+            &l1,
+            "--contract-address",
+            &fee_contract_address,
+            "--mnemonic",
+            TEST_MNEMONIC,
+            "--account-index",
+            "1",
+            "--attestation",
+            &attestation,
+            "--recipient",
+            &recipient_address,
+            "--confirmations",
+            "1",
+        ];
+        let claimed = run_bridge(&claim).expect("claim failed");
+        assert_eq!(claimed, withdrawal.to_string());
+        assert_eq!(
+            provider.get_balance(recipient).await.unwrap(),
+            U256::from(withdrawal)
+        );
+        assert_eq!(
+            fee_contract.claimed(user).call().await.unwrap()._0,
+            U256::from(withdrawal)
+        );
+
+        // The same withdrawal cannot be claimed twice.
+        assert_eq!(run_bridge(&claim).expect("claim failed"), "0");
+        assert_eq!(
+            provider.get_balance(recipient).await.unwrap(),
+            U256::from(withdrawal)
+        );
+
+        drop(process);
+    }
 }
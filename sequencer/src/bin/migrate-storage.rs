@@ -0,0 +1,195 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+#[allow(unused_imports)]
+use espresso_types::{
+    traits::{MembershipPersistence, PersistenceOptions, SequencerPersistence},
+    FeeVersion, MarketplaceVersion, NodeState, SequencerVersions, ValidatedState, V0_0,
+};
+use hotshot_types::traits::node_implementation::Versions;
+use sequencer::{
+    genesis::L1Finalized,
+    persistence::{
+        self,
+        backend_migration::{migrate_backend, verify_backend_migration},
+    },
+    Genesis,
+};
+use sequencer_utils::logging;
+use vbs::version::StaticVersionType;
+
+/// Move the consensus storage of a sequencer to a different persistence backend.
+///
+/// All data needed to resume consensus is copied from the source storage to the destination
+/// storage, which must be empty. The migration succeeds only if both storages then produce the same
+/// consensus state. If a migration fails part way through, running it again with the same storages
+/// resumes it. The source storage is not modified. Do not run this program while the sequencer is
+/// running.
+#[derive(Clone, Debug, Parser)]
+struct Options {
+    /// Path to the genesis file of the network the sequencer belongs to.
+    ///
+    /// This determines the protocol versions and genesis state used to check that both storages
+    /// resume consensus from the same state.
+    #[clap(
+        long,
+        name = "GENESIS_FILE",
+        env = "ESPRESSO_SEQUENCER_GENESIS_FILE",
+        default_value = "/genesis/demo.toml"
+    )]
+    genesis_file: PathBuf,
+
+    #[clap(flatten)]
+    logging: logging::Config,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+enum Command {
+    /// Migrate from file system storage to SQL storage.
+    FsToSql {
+        /// Storage path of the file system storage.
+        #[clap(long)]
+        fs_path: PathBuf,
+
+        #[clap(flatten)]
+        sql: Box<persistence::sql::Options>,
+    },
+    /// Migrate from SQL storage to file system storage.
+    SqlToFs {
+        /// Storage path of the file system storage.
+        #[clap(long)]
+        fs_path: PathBuf,
+
+        #[clap(flatten)]
+        sql: Box<persistence::sql::Options>,
+    },
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Options::parse();
+    opt.logging.init();
+
+    let genesis = Genesis::from_file(&opt.genesis_file)?;
+    tracing::info!(?genesis, "genesis");
+
+    let base = genesis.base_version;
+    let upgrade = genesis.upgrade_version;
+
+    match (base, upgrade) {
+        #[cfg(all(feature = "fee", feature = "pos"))]
+        (FeeVersion::VERSION, espresso_types::EpochVersion::VERSION) => {
+            run(
+                opt.command,
+                genesis,
+                SequencerVersions::<espresso_types::FeeVersion, espresso_types::EpochVersion>::new(
+                ),
+            )
+            .await
+        },
+        #[cfg(feature = "pos")]
+        (espresso_types::EpochVersion::VERSION, _) => {
+            run(
+                opt.command,
+                genesis,
+                SequencerVersions::<espresso_types::EpochVersion, V0_0>::new(),
+            )
+            .await
+        },
+        #[cfg(all(feature = "fee", feature = "marketplace"))]
+        (FeeVersion::VERSION, espresso_types::MarketplaceVersion::VERSION) => {
+            run(
+                opt.command,
+                genesis,
+                SequencerVersions::<FeeVersion, MarketplaceVersion>::new(),
+            )
+            .await
+        },
+        #[cfg(feature = "fee")]
+        (FeeVersion::VERSION, _) => {
+            run(
+                opt.command,
+                genesis,
+                SequencerVersions::<FeeVersion, V0_0>::new(),
+            )
+            .await
+        },
+        #[cfg(feature = "marketplace")]
+        (espresso_types::MarketplaceVersion::VERSION, _) => {
+            run(
+                opt.command,
+                genesis,
+                SequencerVersions::<espresso_types::MarketplaceVersion, V0_0>::new(),
+            )
+            .await
+        },
+        _ => panic!(
+            "Invalid base ({base}) and upgrade ({upgrade}) versions specified in the toml file."
+        ),
+    }
+}
+
+async fn run<V: Versions>(command: Command, genesis: Genesis, _: V) -> anyhow::Result<()> {
+    let instance = node_state::<V>(genesis);
+    match command {
+        Command::FsToSql { fs_path, sql } => {
+            let fs = persistence::fs::Options::new(fs_path);
+            tracing::warn!("migrating file system storage {fs:?} to SQL storage {sql:?}");
+            migrate::<V, _, _>(fs, *sql, instance).await
+        },
+        Command::SqlToFs { fs_path, sql } => {
+            let fs = persistence::fs::Options::new(fs_path);
+            tracing::warn!("migrating SQL storage {sql:?} to file system storage {fs:?}");
+            migrate::<V, _, _>(*sql, fs, instance).await
+        },
+    }
+}
+
+/// The parts of the node state which determine the consensus state loaded from storage.
+fn node_state<V: Versions>(genesis: Genesis) -> NodeState {
+    let mut genesis_state = ValidatedState {
+        chain_config: genesis.chain_config.into(),
+        ..Default::default()
+    };
+    for (address, amount) in genesis.accounts {
+        genesis_state.prefund_account(address, amount);
+    }
+
+    let mut instance = NodeState::default()
+        .with_chain_config(genesis.chain_config)
+        .with_genesis(genesis_state)
+        .with_upgrades(genesis.upgrades)
+        .with_current_version(V::Base::VERSION)
+        .with_epoch_height(genesis.epoch_height.unwrap_or_default())
+        .with_fee_withdrawal_start_block(genesis.fee_withdrawal_start_block);
+    instance.genesis_header = genesis.header;
+    // Finding the L1 genesis block by number or timestamp requires an L1 client. It is only used to
+    // build the genesis leaf, which storage holding a decided leaf never falls back to, and which
+    // is the same for both storages otherwise.
+    instance.l1_genesis = match genesis.l1_finalized {
+        L1Finalized::Block(block) => Some(block),
+        _ => None,
+    };
+    instance
+}
+
+async fn migrate<V, S, D>(mut src: S, mut dst: D, instance: NodeState) -> anyhow::Result<()>
+where
+    V: Versions,
+    S: PersistenceOptions,
+    D: PersistenceOptions,
+    S::Persistence: MembershipPersistence + SequencerPersistence,
+    D::Persistence: MembershipPersistence + SequencerPersistence,
+{
+    let src = src.create().await?;
+    let dst = dst.create().await?;
+
+    migrate_backend(&src, &dst).await?;
+    verify_backend_migration::<V, _, _>(&src, &dst, instance).await?;
+
+    tracing::info!("migration complete");
+    Ok(())
+}
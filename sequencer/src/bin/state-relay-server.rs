@@ -1,6 +1,9 @@
 use clap::Parser;
 use hotshot_stake_table::config::STAKE_TABLE_CAPACITY;
-use sequencer::{state_signature::relay_server::run_relay_server, SequencerApiVersion};
+use sequencer::{
+    state_signature::relay_server::{run_relay_server, Options},
+    SequencerApiVersion,
+};
 use sequencer_utils::logging;
 use url::Url;
 use vbs::version::StaticVersionType;
@@ -29,6 +32,9 @@ struct Args {
     #[clap(short, long, env = "ESPRESSO_SEQUENCER_STAKE_TABLE_CAPACITY", default_value_t = STAKE_TABLE_CAPACITY)]
     pub stake_table_capacity: usize,
 
+    #[clap(flatten)]
+    options: Options,
+
     #[clap(flatten)]
     logging: logging::Config,
 }
@@ -44,6 +50,7 @@ async fn main() {
         None,
         args.sequencer_url,
         format!("http://0.0.0.0:{}", args.port).parse().unwrap(),
+        &args.options,
         SequencerApiVersion::instance(),
     )
     .await
@@ -0,0 +1,189 @@
+use std::path::PathBuf;
+
+use anyhow::{ensure, Context};
+use clap::{Parser, Subcommand};
+use committable::Committable;
+use espresso_types::{
+    traits::{MembershipPersistence, PersistenceOptions, SequencerPersistence},
+    v0::snapshot::StateSnapshot,
+    L1Client,
+};
+use sequencer::persistence;
+use sequencer_utils::logging;
+use url::Url;
+
+/// Export or import a snapshot of decided state.
+///
+/// A snapshot contains a decided leaf, the QCs proving it was decided, the full state after that
+/// leaf, and the stake table for its epoch. Importing a snapshot into fresh storage lets a node
+/// start consensus from the snapshotted leaf without replaying the history of the chain. Since the
+/// snapshot comes from an untrusted source, importing requires the commitment of the snapshotted
+/// leaf from a trusted source, and the stake table is checked against the stake table contract on
+/// L1. Do not run this program while the sequencer is running.
+#[derive(Clone, Debug, Parser)]
+struct Options {
+    #[clap(flatten)]
+    logging: logging::Config,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+enum Command {
+    /// Export a snapshot from SQL storage.
+    ///
+    /// The storage must contain the merklized state and decided leaves for the snapshot height,
+    /// so it should belong to an archival node or a node which has not yet pruned that height.
+    Export {
+        /// Block height of the decided leaf to snapshot.
+        #[clap(long)]
+        height: u64,
+
+        /// File to write the snapshot to.
+        #[clap(long)]
+        output: PathBuf,
+
+        #[clap(flatten)]
+        storage: Box<persistence::sql::Options>,
+    },
+    /// Verify a snapshot and import it into storage.
+    Import {
+        #[clap(flatten)]
+        import: ImportOptions,
+
+        #[command(subcommand)]
+        storage: Storage,
+    },
+}
+
+#[derive(Clone, Debug, Parser)]
+struct ImportOptions {
+    /// File to read the snapshot from.
+    #[clap(long)]
+    input: PathBuf,
+
+    /// Commitment of the snapshotted leaf, obtained from a trusted source.
+    ///
+    /// The snapshot itself is untrusted: its QCs can only be checked against the stake table it
+    /// carries, which anyone can forge along with the QCs. The leaf commitment anchors the snapshot
+    /// to the right chain, and the snapshot is rejected if its leaf does not match it.
+    #[clap(long, env = "ESPRESSO_STATE_SNAPSHOT_LEAF_COMMIT")]
+    leaf_commit: String,
+
+    /// URLs of layer 1 Ethereum JSON-RPC providers, used to check the snapshot stake table.
+    ///
+    /// The stake table carried by the snapshot is what its QCs are checked against, so it must be
+    /// checked itself before it is used. Required if the snapshot has a stake table (that is, if
+    /// epochs are enabled at the snapshot height) and storage does not already have the stake
+    /// table for its epoch.
+    #[clap(long, env = "ESPRESSO_SEQUENCER_L1_PROVIDER", value_delimiter = ',')]
+    l1_provider_url: Vec<Url>,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+enum Storage {
+    /// Import into file system storage.
+    Fs(persistence::fs::Options),
+    /// Import into SQL storage.
+    Sql(Box<persistence::sql::Options>),
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Options::parse();
+    opt.logging.init();
+
+    match opt.command {
+        Command::Export {
+            height,
+            output,
+            mut storage,
+        } => {
+            let persistence = storage.create().await?;
+            let snapshot = persistence.export_snapshot(height).await?;
+            snapshot.save(&output)?;
+            tracing::info!(height, "exported snapshot to {}", output.display());
+            Ok(())
+        },
+        Command::Import { import, storage } => match storage {
+            Storage::Fs(opt) => import_snapshot(import, opt).await,
+            Storage::Sql(opt) => import_snapshot(import, *opt).await,
+        },
+    }
+}
+
+async fn import_snapshot(
+    opt: ImportOptions,
+    mut storage: impl PersistenceOptions,
+) -> anyhow::Result<()> {
+    let snapshot = StateSnapshot::load(&opt.input)?;
+    let height = snapshot.height();
+    tracing::info!(height, "loaded snapshot from {}", opt.input.display());
+
+    let persistence = storage.create().await?;
+    if let Some((leaf, _)) = persistence.load_anchor_leaf().await? {
+        ensure!(
+            leaf.height() < height,
+            "storage already contains decided leaf {}, which is not older than the snapshot",
+            leaf.height()
+        );
+    }
+
+    // Everything else in the snapshot is only checked against the snapshot itself, so first check
+    // that it is anchored to the trusted leaf.
+    let commit = snapshot.leaf().commit().to_string();
+    ensure!(
+        opt.leaf_commit == commit,
+        "snapshot leaf {commit} does not match trusted leaf {}",
+        opt.leaf_commit
+    );
+
+    // Verify against the snapshot's own stake table if it has one (that is, if epochs are enabled
+    // at the snapshot height) and otherwise the static stake table from the network config. Either
+    // way, the state is checked against the header of the trusted leaf.
+    let config = persistence
+        .load_config()
+        .await?
+        .context("storage has no network config")?;
+    let stake_table = match &snapshot.stake_table {
+        Some((epoch, stake)) => {
+            // The snapshot stake table is untrusted, so check it against the one we already have
+            // for its epoch, or else against the L1.
+            ensure!(
+                snapshot.leaf().epoch(config.config.epoch_height) == Some(*epoch),
+                "snapshot stake table is for epoch {epoch}, not the epoch of the snapshotted leaf"
+            );
+            match persistence.load_stake(*epoch).await? {
+                Some(stored) => ensure!(
+                    stored == *stake,
+                    "snapshot stake table for epoch {epoch} does not match the stored one"
+                ),
+                None => {
+                    ensure!(
+                        !opt.l1_provider_url.is_empty(),
+                        "an L1 provider is required to check the snapshot stake table for epoch \
+                         {epoch}"
+                    );
+                    let l1 = L1Client::new(opt.l1_provider_url.clone())?;
+                    snapshot
+                        .verify_stake_table(config.config.epoch_height, &l1)
+                        .await
+                        .context("invalid snapshot stake table")?;
+                },
+            }
+            tracing::info!(height, %epoch, "verified snapshot stake table");
+            snapshot.peers().context("snapshot has no stake table")?
+        },
+        None => config.config.known_nodes_with_stake,
+    };
+    snapshot
+        .verify(stake_table)
+        .await
+        .context("invalid snapshot")?;
+    tracing::info!(height, "verified snapshot");
+
+    persistence.import_snapshot(&snapshot).await?;
+    tracing::info!(height, "imported snapshot");
+    Ok(())
+}
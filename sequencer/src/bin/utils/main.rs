@@ -5,6 +5,7 @@ use sequencer_utils::logging;
 mod keygen;
 mod pubkey;
 mod reset_storage;
+mod verify_stake_table_events;
 
 #[derive(Debug, Parser)]
 struct Options {
@@ -21,6 +22,7 @@ enum Command {
     Pubkey(pubkey::Options),
     #[command(subcommand)]
     ResetStorage(reset_storage::Commands),
+    VerifyStakeTableEvents(verify_stake_table_events::Options),
 }
 
 #[tokio::main]
@@ -35,5 +37,6 @@ async fn main() -> anyhow::Result<()> {
             Ok(())
         },
         Command::ResetStorage(opt) => reset_storage::run(opt).await,
+        Command::VerifyStakeTableEvents(opt) => verify_stake_table_events::run(opt).await,
     }
 }
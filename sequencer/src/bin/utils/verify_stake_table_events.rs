@@ -0,0 +1,48 @@
+use alloy::primitives::Address;
+use clap::{Parser, Subcommand};
+use espresso_types::{traits::PersistenceOptions, verify_stake_table_events, L1Client};
+use sequencer::persistence;
+use url::Url;
+
+/// Check the stake table events cached in sequencer storage against the L1.
+///
+/// All stake table events up to the last cached L1 block are fetched from the L1 and compared with
+/// the cached events. If they differ, the sequencer storage should be reset.
+#[derive(Clone, Debug, Parser)]
+pub struct Options {
+    /// RPC URL for the L1 provider.
+    #[clap(long, env = "ESPRESSO_SEQUENCER_L1_PROVIDER")]
+    l1_provider_url: Url,
+
+    /// Address of the stake table contract.
+    #[clap(long, env = "ESPRESSO_SEQUENCER_STAKE_TABLE_PROXY_ADDRESS")]
+    stake_table_address: Address,
+
+    #[command(subcommand)]
+    storage: Storage,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+enum Storage {
+    /// Verify file system storage.
+    Fs(persistence::fs::Options),
+    /// Verify SQL storage.
+    Sql(Box<persistence::sql::Options>),
+}
+
+pub async fn run(opt: Options) -> anyhow::Result<()> {
+    let l1_client = L1Client::new(vec![opt.l1_provider_url])?;
+    match opt.storage {
+        Storage::Fs(storage) => verify(&l1_client, storage, opt.stake_table_address).await,
+        Storage::Sql(storage) => verify(&l1_client, *storage, opt.stake_table_address).await,
+    }
+}
+
+async fn verify(
+    l1_client: &L1Client,
+    mut storage: impl PersistenceOptions,
+    stake_table_address: Address,
+) -> anyhow::Result<()> {
+    let persistence = storage.create().await?;
+    verify_stake_table_events(l1_client, &persistence, stake_table_address).await
+}
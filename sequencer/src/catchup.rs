@@ -6,8 +6,9 @@ use async_trait::async_trait;
 use committable::{Commitment, Committable};
 use espresso_types::{
     config::PublicNetworkConfig,
+    retain_accounts, retain_reward_accounts,
     traits::SequencerPersistence,
-    v0::traits::StateCatchup,
+    v0::{snapshot::StateSnapshot, traits::StateCatchup},
     v0_1::{RewardAccount, RewardAccountProof, RewardMerkleCommitment, RewardMerkleTree},
     v0_99::ChainConfig,
     BackoffParams, BlockMerkleTree, FeeAccount, FeeAccountProof, FeeMerkleCommitment,
@@ -21,6 +22,7 @@ use hotshot_types::{
         metrics::{Counter, CounterFamily, Metrics},
         node_implementation::ConsensusTime as _,
     },
+    vote::HasViewNumber,
     ValidatorConfig,
 };
 use itertools::Itertools;
@@ -467,6 +469,46 @@ pub(crate) trait CatchupStorage: Sync {
     }
 }
 
+/// Extract, from a set of undecided leaves, the chain proving that the leaf at `height` was decided.
+///
+/// The chain starts at the leaf at `height` and is followed by enough descendants to form a
+/// decide, so that it can be checked with [`verify_leaf_chain`](hotshot_types::utils::verify_leaf_chain).
+pub(crate) fn leaf_chain_from_undecided(
+    mut leaves: Vec<Leaf2>,
+    height: u64,
+) -> anyhow::Result<Vec<Leaf2>> {
+    leaves.sort_by_key(|l| l.view_number());
+    let (position, mut last_leaf) = leaves
+        .iter()
+        .find_position(|l| l.height() == height)
+        .context(format!("leaf chain not available for {height}"))?;
+    let mut chain = vec![last_leaf.clone()];
+    for leaf in leaves.iter().skip(position + 1) {
+        if leaf.justify_qc().view_number() == last_leaf.view_number() {
+            chain.push(leaf.clone());
+        } else {
+            continue;
+        }
+        if leaf.view_number() == last_leaf.view_number() + 1 {
+            // one away from decide
+            last_leaf = leaf;
+            break;
+        }
+        last_leaf = leaf;
+    }
+    // Make sure we got one more leaf to confirm the decide
+    for leaf in leaves
+        .iter()
+        .skip_while(|l| l.view_number() <= last_leaf.view_number())
+    {
+        if leaf.justify_qc().view_number() == last_leaf.view_number() {
+            chain.push(leaf.clone());
+            return Ok(chain);
+        }
+    }
+    bail!(format!("leaf chain not available for {height}"))
+}
+
 impl CatchupStorage for hotshot_query_service::data_source::MetricsDataSource {}
 
 impl<T, S> CatchupStorage for hotshot_query_service::data_source::ExtensibleDataSource<T, S>
@@ -723,6 +765,130 @@ impl StateCatchup for NullStateCatchup {
     }
 }
 
+/// Serve catchup requests from an imported [`StateSnapshot`].
+///
+/// This allows a node bootstrapped from a snapshot to supply the state it needs to resume
+/// consensus, without relying on peers, for storage backends which do not otherwise support local
+/// catchup. Only requests for the snapshotted state are served.
+#[derive(Clone, Debug)]
+pub struct SnapshotStateCatchup {
+    snapshot: Arc<StateSnapshot>,
+    backoff: BackoffParams,
+}
+
+impl SnapshotStateCatchup {
+    pub fn new(snapshot: StateSnapshot, backoff: BackoffParams) -> anyhow::Result<Self> {
+        ensure!(
+            snapshot.state.chain_config.resolve().is_some(),
+            "snapshot chain config is not resolved"
+        );
+        Ok(Self {
+            snapshot: Arc::new(snapshot),
+            backoff,
+        })
+    }
+
+    fn ensure_snapshot_height(&self, height: u64) -> anyhow::Result<()> {
+        ensure!(
+            height == self.snapshot.height(),
+            "state at height {height} is not available (snapshot height {})",
+            self.snapshot.height()
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StateCatchup for SnapshotStateCatchup {
+    async fn try_fetch_leaves(&self, _retry: usize, height: u64) -> anyhow::Result<Vec<Leaf2>> {
+        self.ensure_snapshot_height(height)?;
+        Ok(self.snapshot.leaf_chain.clone())
+    }
+
+    async fn try_fetch_accounts(
+        &self,
+        _retry: usize,
+        _instance: &NodeState,
+        height: u64,
+        _view: ViewNumber,
+        fee_merkle_tree_root: FeeMerkleCommitment,
+        accounts: &[FeeAccount],
+    ) -> anyhow::Result<FeeMerkleTree> {
+        self.ensure_snapshot_height(height)?;
+        let tree = &self.snapshot.state.fee_merkle_tree;
+        ensure!(
+            tree.commitment() == fee_merkle_tree_root,
+            "snapshot fee state does not match requested root"
+        );
+        retain_accounts(tree, accounts.iter().copied())
+    }
+
+    async fn try_fetch_reward_accounts(
+        &self,
+        _retry: usize,
+        _instance: &NodeState,
+        height: u64,
+        _view: ViewNumber,
+        reward_merkle_tree_root: RewardMerkleCommitment,
+        accounts: &[RewardAccount],
+    ) -> anyhow::Result<RewardMerkleTree> {
+        self.ensure_snapshot_height(height)?;
+        let tree = &self.snapshot.state.reward_merkle_tree;
+        ensure!(
+            tree.commitment() == reward_merkle_tree_root,
+            "snapshot reward state does not match requested root"
+        );
+        retain_reward_accounts(tree, accounts.iter().copied())
+    }
+
+    async fn try_remember_blocks_merkle_tree(
+        &self,
+        _retry: usize,
+        _instance: &NodeState,
+        height: u64,
+        _view: ViewNumber,
+        mt: &mut BlockMerkleTree,
+    ) -> anyhow::Result<()> {
+        self.ensure_snapshot_height(height)?;
+        if height == 0 {
+            return Ok(());
+        }
+
+        let tree = &self.snapshot.state.block_merkle_tree;
+        ensure!(
+            tree.commitment() == mt.commitment(),
+            "snapshot block state does not match requested root"
+        );
+        let (elem, proof) = tree
+            .lookup(height - 1)
+            .expect_ok()
+            .context("snapshot is missing blocks frontier")?;
+        mt.remember(height - 1, elem, proof)
+            .context("failed to remember proof")
+    }
+
+    async fn try_fetch_chain_config(
+        &self,
+        _retry: usize,
+        commitment: Commitment<ChainConfig>,
+    ) -> anyhow::Result<ChainConfig> {
+        let chain_config = self.snapshot.chain_config()?;
+        ensure!(
+            chain_config.commit() == commitment,
+            "chain config {commitment} not available"
+        );
+        Ok(chain_config)
+    }
+
+    fn backoff(&self) -> &BackoffParams {
+        &self.backoff
+    }
+
+    fn name(&self) -> String {
+        format!("SnapshotStateCatchup({})", self.snapshot.height())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
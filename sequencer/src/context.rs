@@ -9,7 +9,7 @@ use async_lock::RwLock;
 use derivative::Derivative;
 use espresso_types::{
     v0::traits::{EventConsumer as PersistenceEventConsumer, SequencerPersistence},
-    NodeState, PubKey, Transaction, ValidatedState,
+    BackoffParams, NodeState, PubKey, Transaction, ValidatedState,
 };
 use futures::{
     future::{join_all, Future},
@@ -31,12 +31,8 @@ use hotshot_types::{
     PeerConfig, ValidatorConfig,
 };
 use parking_lot::Mutex;
-use request_response::{network::Bytes, RequestResponse, RequestResponseConfig};
-use tokio::{
-    spawn,
-    sync::mpsc::{channel, Receiver},
-    task::JoinHandle,
-};
+use request_response::{RequestResponse, RequestResponseConfig};
+use tokio::{spawn, sync::mpsc::channel, task::JoinHandle};
 use tracing::{Instrument, Level};
 use url::Url;
 
@@ -45,9 +41,10 @@ use crate::{
     proposal_fetcher::ProposalFetcherConfig,
     request_response::{
         data_source::DataSource, network::Sender as RequestResponseSender,
-        recipient_source::RecipientSource, request::Request,
+        recipient_source::RecipientSource, RequestResponseProtocol,
     },
     state_signature::StateSigner,
+    tx_status::{TxStatusConfig, TxStatusTracker},
     Node, SeqTypes, SequencerApiVersion,
 };
 
@@ -64,15 +61,7 @@ pub struct SequencerContext<N: ConnectedNetwork<PubKey>, P: SequencerPersistence
 
     /// The request-response protocol
     #[derivative(Debug = "ignore")]
-    #[allow(dead_code)]
-    request_response_protocol: RequestResponse<
-        RequestResponseSender,
-        Receiver<Bytes>,
-        Request,
-        RecipientSource,
-        DataSource,
-        PubKey,
-    >,
+    request_response_protocol: RequestResponseProtocol,
 
     /// Context for generating state signatures.
     state_signer: Arc<RwLock<StateSigner<SequencerApiVersion>>>,
@@ -87,6 +76,9 @@ pub struct SequencerContext<N: ConnectedNetwork<PubKey>, P: SequencerPersistence
     /// events streamer to stream hotshot events to external clients
     events_streamer: Arc<RwLock<EventsStreamer<SeqTypes>>>,
 
+    /// Status of recently seen transactions.
+    tx_status: Arc<TxStatusTracker>,
+
     detached: bool,
 
     node_state: NodeState,
@@ -114,6 +106,7 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
         _: V,
         marketplace_config: MarketplaceConfig<SeqTypes, Node<N, P>>,
         proposal_fetcher_cfg: ProposalFetcherConfig,
+        tx_status_cfg: TxStatusConfig,
     ) -> anyhow::Result<Self> {
         let config = &network_config.config;
         let pub_key = validator_config.public_key;
@@ -145,6 +138,18 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
             0,
         )));
 
+        // Serve request-response catchup requests from local storage, if supported.
+        let local_catchup = match persistence
+            .clone()
+            .into_catchup_provider(BackoffParams::disabled())
+        {
+            Ok(local) => Some(local),
+            Err(err) => {
+                tracing::info!("not serving catchup requests from local storage: {err:#}");
+                None
+            },
+        };
+
         let persistence = Arc::new(persistence);
         let membership = coordinator.membership().clone();
 
@@ -197,8 +202,13 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
             request_response_receiver,
             RecipientSource {
                 memberships: membership,
+                epoch_height: config.epoch_height,
+            },
+            DataSource {
+                consensus: handle.consensus(),
+                node_state: instance_state.clone(),
+                storage: local_catchup,
             },
-            DataSource {},
         );
 
         // Create the external event handler
@@ -227,6 +237,7 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
             event_consumer,
             anchor_view,
             proposal_fetcher_cfg,
+            tx_status_cfg,
             metrics,
         )
         .with_task_list(tasks))
@@ -239,14 +250,7 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
         persistence: Arc<P>,
         state_signer: StateSigner<SequencerApiVersion>,
         external_event_handler: ExternalEventHandler<V>,
-        request_response_protocol: RequestResponse<
-            RequestResponseSender,
-            Receiver<Bytes>,
-            Request,
-            RecipientSource,
-            DataSource,
-            PubKey,
-        >,
+        request_response_protocol: RequestResponseProtocol,
         event_streamer: Arc<RwLock<EventsStreamer<SeqTypes>>>,
         node_state: NodeState,
         network_config: NetworkConfig<SeqTypes>,
@@ -254,6 +258,7 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
         event_consumer: impl PersistenceEventConsumer + 'static,
         anchor_view: Option<ViewNumber>,
         proposal_fetcher_cfg: ProposalFetcherConfig,
+        tx_status_cfg: TxStatusConfig,
         metrics: &dyn Metrics,
     ) -> Self {
         let events = handle.event_stream();
@@ -267,6 +272,7 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
             detached: false,
             wait_for_orchestrator: None,
             events_streamer: event_streamer.clone(),
+            tx_status: Arc::new(TxStatusTracker::new(tx_status_cfg)),
             node_state,
             network_config,
             validator_config,
@@ -291,6 +297,7 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
                 ctx.state_signer.clone(),
                 external_event_handler,
                 Some(event_streamer.clone()),
+                ctx.tx_status.clone(),
                 event_consumer,
                 anchor_view,
             ),
@@ -331,6 +338,11 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
         self.events_streamer.clone()
     }
 
+    /// Get the transaction status tracker.
+    pub fn tx_status(&self) -> Arc<TxStatusTracker> {
+        self.tx_status.clone()
+    }
+
     /// Return a reference to the underlying consensus handle.
     pub fn consensus(&self) -> Arc<RwLock<Consensus<N, P, V>>> {
         Arc::clone(&self.handle)
@@ -360,6 +372,11 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
         self.node_state.clone()
     }
 
+    /// Get the request-response protocol used to communicate with other consensus nodes.
+    pub(crate) fn request_response_protocol(&self) -> RequestResponseProtocol {
+        self.request_response_protocol.clone()
+    }
+
     /// Start participating in consensus.
     pub async fn start_consensus(&self) {
         if let Some(orchestrator_client) = &self.wait_for_orchestrator {
@@ -463,6 +480,7 @@ async fn handle_events<N, P, V>(
     state_signer: Arc<RwLock<StateSigner<SequencerApiVersion>>>,
     external_event_handler: ExternalEventHandler<V>,
     events_streamer: Option<Arc<RwLock<EventsStreamer<SeqTypes>>>>,
+    tx_status: Arc<TxStatusTracker>,
     event_consumer: impl PersistenceEventConsumer + 'static,
     anchor_view: Option<ViewNumber>,
 ) where
@@ -503,6 +521,9 @@ async fn handle_events<N, P, V>(
             };
         }
 
+        // Track the status of transactions.
+        tx_status.handle_event(&event).await;
+
         // Send the event via the event streaming service
         if let Some(events_streamer) = events_streamer.as_ref() {
             events_streamer.write().await.handle_event(event).await;
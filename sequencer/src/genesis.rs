@@ -53,6 +53,11 @@ pub struct Genesis {
     pub upgrade_version: Version,
     pub epoch_height: Option<u64>,
     pub epoch_start_block: Option<u64>,
+    /// Block height from which L1 fee withdrawal requests are honored.
+    ///
+    /// Requests made on L1 before this height are ignored. If not set, withdrawals are disabled.
+    #[serde(default)]
+    pub fee_withdrawal_start_block: Option<u64>,
     pub chain_config: ChainConfig,
     pub stake_table: StakeTableConfig,
     #[serde(default)]
@@ -342,6 +347,7 @@ mod test {
         let toml = toml! {
             base_version = "0.1"
             upgrade_version = "0.2"
+            fee_withdrawal_start_block = 100
 
             [stake_table]
             capacity = 10
@@ -369,6 +375,7 @@ mod test {
 
         let genesis: Genesis = toml::from_str(&toml).unwrap_or_else(|err| panic!("{err:#}"));
         assert_eq!(genesis.stake_table, StakeTableConfig { capacity: 10 });
+        assert_eq!(genesis.fee_withdrawal_start_block, Some(100));
         assert_eq!(
             genesis.chain_config,
             ChainConfig {
@@ -462,6 +469,7 @@ mod test {
         );
         assert_eq!(genesis.accounts, HashMap::default());
         assert_eq!(genesis.l1_finalized, L1Finalized::Number { number: 0 });
+        assert_eq!(genesis.fee_withdrawal_start_block, None);
     }
 
     #[test]
@@ -8,6 +8,7 @@ mod request_response;
 mod external_event_handler;
 pub mod options;
 pub mod state_signature;
+pub mod tx_status;
 
 mod restart_tests;
 
@@ -21,7 +22,7 @@ use async_lock::RwLock;
 use catchup::StatePeers;
 use context::SequencerContext;
 use espresso_types::{
-    traits::{EventConsumer, MembershipPersistence},
+    traits::{EventConsumer, MembershipPersistence, StateCatchup},
     BackoffParams, EpochCommittees, L1ClientOptions, NodeState, PubKey, SeqTypes,
     SolverAuctionResultsProvider, ValidatedState,
 };
@@ -34,7 +35,10 @@ use options::Identity;
 use proposal_fetcher::ProposalFetcherConfig;
 use tokio::select;
 use tracing::info;
+use tx_status::TxStatusConfig;
 use url::Url;
+
+use crate::request_response::catchup::RequestResponseStateCatchup;
 pub mod persistence;
 pub mod state;
 use std::{fmt::Debug, marker::PhantomData, time::Duration};
@@ -114,6 +118,8 @@ pub struct NetworkParams {
     pub state_peers: Vec<Url>,
     pub config_peers: Option<Vec<Url>>,
     pub catchup_backoff: BackoffParams,
+    /// The timeout for catchup requests to other consensus nodes
+    pub catchup_request_timeout: Duration,
     /// The address to advertise as our public API's URL
     pub public_api_url: Option<Url>,
 
@@ -203,6 +209,7 @@ pub async fn init_node<P: SequencerPersistence + MembershipPersistence, V: Versi
     identity: Identity,
     marketplace_config: MarketplaceConfig<SeqTypes, Node<network::Production, P>>,
     proposal_fetcher_config: ProposalFetcherConfig,
+    tx_status_config: TxStatusConfig,
 ) -> anyhow::Result<SequencerContext<network::Production, P, V>> {
     // Expose git information via status API.
     metrics
@@ -470,15 +477,25 @@ pub async fn init_node<P: SequencerPersistence + MembershipPersistence, V: Versi
         genesis_state.prefund_account(address, amount);
     }
 
-    let peers = catchup::local_and_remote(
-        persistence.clone(),
-        StatePeers::<SequencerApiVersion>::from_urls(
+    // Catch up from other consensus nodes over the request-response protocol, and from any
+    // configured HTTP peers. The request-response protocol only becomes available once consensus
+    // is initialized, below.
+    let request_response_catchup = RequestResponseStateCatchup::new(
+        validator_config.public_key,
+        validator_config.private_key.clone(),
+        network_params.catchup_request_timeout,
+        network_params.catchup_backoff,
+    );
+    let mut remote: Vec<Arc<dyn StateCatchup>> = vec![];
+    if !network_params.state_peers.is_empty() {
+        remote.push(Arc::new(StatePeers::<SequencerApiVersion>::from_urls(
             network_params.state_peers,
             network_params.catchup_backoff,
             metrics,
-        ),
-    )
-    .await;
+        )));
+    }
+    remote.push(Arc::new(request_response_catchup.clone()));
+    let peers = catchup::local_and_remote(persistence.clone(), remote).await;
     // Create the HotShot membership
     let mut membership = EpochCommittees::new_stake(
         network_config.config.known_nodes_with_stake.clone(),
@@ -504,6 +521,7 @@ pub async fn init_node<P: SequencerPersistence + MembershipPersistence, V: Versi
         upgrades: genesis.upgrades,
         current_version: V::Base::VERSION,
         epoch_height: Some(epoch_height),
+        fee_withdrawal_start_block: genesis.fee_withdrawal_start_block,
         peers,
         coordinator: coordinator.clone(),
     };
@@ -563,8 +581,10 @@ pub async fn init_node<P: SequencerPersistence + MembershipPersistence, V: Versi
         seq_versions,
         marketplace_config,
         proposal_fetcher_config,
+        tx_status_config,
     )
     .await?;
+    request_response_catchup.set_protocol(ctx.request_response_protocol());
     if wait_for_orchestrator {
         ctx = ctx.wait_for_orchestrator(orchestrator_client);
     }
@@ -609,8 +629,9 @@ pub mod testing {
         },
         types::EventType::Decide,
     };
-    use hotshot_builder_core_refactored::service::{
-        BuilderConfig as LegacyBuilderConfig, GlobalState as LegacyGlobalState,
+    use hotshot_builder_core_refactored::{
+        selector::FifoSelector,
+        service::{BuilderConfig as LegacyBuilderConfig, GlobalState as LegacyGlobalState},
     };
     use hotshot_stake_table::vec_based::StakeTable;
     use hotshot_testing::block_builder::{
@@ -689,6 +710,8 @@ pub mod testing {
                 txn_channel_capacity: BUILDER_CHANNEL_CAPACITY_FOR_TEST,
                 tx_status_cache_capacity: 81920,
                 base_fee: 10,
+                transaction_selector: Arc::new(FifoSelector),
+                mempool_log: None,
             },
             NodeState::default(),
             max_block_size.unwrap_or(300),
@@ -816,6 +839,7 @@ pub mod testing {
         builder_port: Option<u16>,
         marketplace_builder_port: Option<u16>,
         upgrades: BTreeMap<Version, Upgrade>,
+        fee_withdrawal_start_block: Option<u64>,
     }
 
     impl<const NUM_NODES: usize> TestConfigBuilder<NUM_NODES> {
@@ -856,6 +880,11 @@ pub mod testing {
             self
         }
 
+        pub fn fee_withdrawal_start_block(mut self, start_block: u64) -> Self {
+            self.fee_withdrawal_start_block = Some(start_block);
+            self
+        }
+
         pub fn build(self) -> TestConfig<NUM_NODES> {
             TestConfig {
                 config: self.config,
@@ -868,6 +897,7 @@ pub mod testing {
                 marketplace_builder_port: self.marketplace_builder_port,
                 builder_port: self.builder_port,
                 upgrades: self.upgrades,
+                fee_withdrawal_start_block: self.fee_withdrawal_start_block,
             }
         }
     }
@@ -938,6 +968,7 @@ pub mod testing {
                 builder_port: None,
                 marketplace_builder_port: None,
                 upgrades: Default::default(),
+                fee_withdrawal_start_block: None,
             }
         }
     }
@@ -954,6 +985,7 @@ pub mod testing {
         builder_port: Option<u16>,
         marketplace_builder_port: Option<u16>,
         upgrades: BTreeMap<Version, Upgrade>,
+        fee_withdrawal_start_block: Option<u64>,
     }
 
     impl<const NUM_NODES: usize> TestConfig<NUM_NODES> {
@@ -1124,6 +1156,7 @@ pub mod testing {
             .with_current_version(V::Base::version())
             .with_genesis(state)
             .with_epoch_height(config.epoch_height)
+            .with_fee_withdrawal_start_block(self.fee_withdrawal_start_block)
             .with_upgrades(upgrades);
 
             tracing::info!(
@@ -1156,6 +1189,7 @@ pub mod testing {
                     fallback_builder_url: marketplace_builder_url,
                 },
                 Default::default(),
+                Default::default(),
             )
             .await
             .unwrap()
@@ -21,7 +21,7 @@ use sequencer_utils::logging;
 use tagged_base64::TaggedBase64;
 use url::Url;
 
-use crate::{api, persistence, proposal_fetcher::ProposalFetcherConfig};
+use crate::{api, persistence, proposal_fetcher::ProposalFetcherConfig, tx_status::TxStatusConfig};
 
 // This options struct is a bit unconventional. The sequencer has multiple optional modules which
 // can be added, in any combination, to the service. These include, for example, the API server.
@@ -374,6 +374,18 @@ pub struct Options {
     #[clap(flatten)]
     pub catchup_backoff: BackoffParams,
 
+    /// Timeout for fetching missing state from other consensus nodes over the request-response
+    /// protocol.
+    ///
+    /// This is the timeout of the first attempt. It is relaxed on each successive retry.
+    #[clap(
+        long,
+        env = "ESPRESSO_SEQUENCER_CATCHUP_REQUEST_TIMEOUT",
+        default_value = "10s",
+        value_parser = parse_duration
+    )]
+    pub catchup_request_timeout: Duration,
+
     #[clap(flatten)]
     pub logging: logging::Config,
 
@@ -382,6 +394,9 @@ pub struct Options {
 
     #[clap(flatten)]
     pub proposal_fetcher_config: ProposalFetcherConfig,
+
+    #[clap(flatten)]
+    pub tx_status_config: TxStatusConfig,
 }
 
 impl Options {
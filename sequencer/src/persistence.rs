@@ -9,8 +9,12 @@
 //! persistence which is _required_ to run a node.
 
 use async_trait::async_trait;
-use espresso_types::v0_99::ChainConfig;
+use espresso_types::{
+    v0_1::{FeeLedgerEntry, RewardAccount, RewardDistribution},
+    v0_99::ChainConfig,
+};
 
+pub mod backend_migration;
 pub mod fs;
 pub mod no_storage;
 pub mod sql;
@@ -20,6 +24,45 @@ pub trait ChainConfigPersistence: Sized + Send + Sync {
     async fn insert_chain_config(&mut self, chain_config: ChainConfig) -> anyhow::Result<()>;
 }
 
+#[async_trait]
+pub trait RewardHistoryPersistence: Sized + Send + Sync {
+    /// Record the rewards paid out in the block at `height`, which belongs to `epoch`.
+    ///
+    /// Inserting the same block more than once has no effect.
+    async fn insert_reward_distribution(
+        &mut self,
+        height: u64,
+        epoch: u64,
+        distribution: RewardDistribution,
+    ) -> anyhow::Result<()>;
+}
+
+#[async_trait]
+pub trait FeeLedgerPersistence: Sized + Send + Sync {
+    /// Record the changes to fee account balances made by the block at `height`.
+    ///
+    /// Inserting the same block more than once has no effect.
+    async fn insert_fee_ledger(
+        &mut self,
+        height: u64,
+        entries: Vec<FeeLedgerEntry>,
+    ) -> anyhow::Result<()>;
+}
+
+#[async_trait]
+pub trait VoteHistoryPersistence: Sized + Send + Sync {
+    /// Record that `voters` voted in the quorum certificate justifying the block at `height`,
+    /// which was formed in `epoch`.
+    ///
+    /// Inserting the same block more than once has no effect.
+    async fn insert_votes(
+        &mut self,
+        height: u64,
+        epoch: u64,
+        voters: Vec<RewardAccount>,
+    ) -> anyhow::Result<()>;
+}
+
 #[cfg(any(test, feature = "testing"))]
 mod testing {
 
@@ -45,31 +88,39 @@ mod testing {
 mod persistence_tests {
     use std::{collections::BTreeMap, marker::PhantomData, sync::Arc};
 
+    use alloy::primitives::{Address, LogData, U256};
     use anyhow::bail;
     use async_lock::RwLock;
     use committable::{Commitment, Committable};
     use espresso_types::{
-        traits::{EventConsumer, NullEventConsumer, PersistenceOptions},
-        Event, Leaf, Leaf2, NodeState, PubKey, SeqTypes, ValidatedState,
+        traits::{EventConsumer, MembershipPersistence, NullEventConsumer, PersistenceOptions},
+        Event, IndexedStakeTableEvent, Leaf, Leaf2, NodeState, PubKey, SeqTypes, StakeTableEvent,
+        ValidatedState,
     };
     use hotshot::{
         types::{BLSPubKey, SignatureKey},
         InitializerEpochInfo,
     };
+    use hotshot_contract_adapter::sol_types::StakeTable::Delegated;
     use hotshot_example_types::node_types::TestVersions;
     use hotshot_query_service::testing::mocks::MockVersions;
     use hotshot_types::{
         data::{
-            ns_table::parse_ns_table, vid_commitment, vid_disperse::VidDisperseShare2, DaProposal2,
-            EpochNumber, QuorumProposal2, QuorumProposalWrapper, VidCommitment, VidDisperseShare,
-            ViewNumber,
+            ns_table::parse_ns_table,
+            vid_commitment,
+            vid_disperse::{VidDisperseShare2, VidEncodingEvidence},
+            DaProposal2, EpochNumber, QuorumProposal2, QuorumProposalWrapper, VidCommitment,
+            VidDisperseShare, ViewNumber,
         },
+        equivocation::EquivocationEvidence,
         event::{EventType, HotShotAction, LeafInfo},
         message::{convert_proposal, Proposal, UpgradeLock},
         simple_certificate::{
             NextEpochQuorumCertificate2, QuorumCertificate, QuorumCertificate2, UpgradeCertificate,
         },
-        simple_vote::{NextEpochQuorumData2, QuorumData2, UpgradeProposalData, VersionedVoteData},
+        simple_vote::{
+            NextEpochQuorumData2, QuorumData2, QuorumVote2, UpgradeProposalData, VersionedVoteData,
+        },
         traits::{
             block_contents::BlockHeader,
             node_implementation::{ConsensusTime, Versions},
@@ -672,6 +723,158 @@ mod persistence_tests {
         assert_eq!(view_number, new_view_number_for_certificate);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    pub async fn test_vid_encoding_evidence<P: TestablePersistence>() {
+        setup_test();
+
+        let tmp = P::tmp_storage().await;
+        let storage = P::connect(&tmp).await;
+        assert!(storage
+            .load_vid_encoding_evidence()
+            .await
+            .unwrap()
+            .is_empty());
+
+        // A leader disperses an incorrectly encoded payload to 6 nodes.
+        let total_weight = 6;
+        let avidm_param = init_avidm_param(total_weight).unwrap();
+        let weights = vec![1u32; total_weight];
+        let (payload_commitment, shares) = AvidMScheme::ns_disperse_incorrectly(
+            &avidm_param,
+            &weights,
+            &[1u8; 64],
+            std::iter::once(0..64),
+        )
+        .unwrap();
+        let mut evidence = BTreeMap::new();
+        for view in [1, 3] {
+            let (leader, leader_key) = BLSPubKey::generated_from_seed_indexed([0; 32], view);
+            let shares = shares
+                .iter()
+                .enumerate()
+                .map(|(i, share)| {
+                    VidDisperseShare2::<SeqTypes> {
+                        view_number: ViewNumber::new(view),
+                        payload_commitment,
+                        share: share.clone(),
+                        recipient_key: BLSPubKey::generated_from_seed_indexed([0; 32], i as u64).0,
+                        epoch: Some(EpochNumber::new(1)),
+                        target_epoch: Some(EpochNumber::new(1)),
+                        common: avidm_param.clone(),
+                    }
+                    .to_proposal(&leader_key)
+                    .unwrap()
+                })
+                .collect::<Vec<_>>();
+            let view_evidence =
+                VidEncodingEvidence::from_shares(leader, &shares, total_weight).unwrap();
+            view_evidence.verify(total_weight).unwrap();
+
+            storage
+                .append_vid_encoding_evidence(&view_evidence)
+                .await
+                .unwrap();
+            evidence.insert(ViewNumber::new(view), view_evidence);
+        }
+
+        assert_eq!(
+            storage.load_vid_encoding_evidence().await.unwrap(),
+            evidence
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    pub async fn test_equivocation_evidence<P: TestablePersistence>() {
+        setup_test();
+
+        let tmp = P::tmp_storage().await;
+        let storage = P::connect(&tmp).await;
+        assert!(storage
+            .load_equivocation_evidence(ViewNumber::genesis(), ViewNumber::new(10))
+            .await
+            .unwrap()
+            .is_empty());
+
+        let upgrade_lock = UpgradeLock::<SeqTypes, TestVersions>::new();
+        let vote = |view: u64, signer: u64, leaf_byte: u8| {
+            let upgrade_lock = upgrade_lock.clone();
+            async move {
+                let (pub_key, priv_key) = BLSPubKey::generated_from_seed_indexed([0; 32], signer);
+                QuorumVote2::<SeqTypes>::create_signed_vote(
+                    QuorumData2 {
+                        leaf_commit: Commitment::from_raw([leaf_byte; 32]),
+                        epoch: None,
+                        block_number: None,
+                    },
+                    ViewNumber::new(view),
+                    &pub_key,
+                    &priv_key,
+                    &upgrade_lock,
+                )
+                .await
+                .unwrap()
+            }
+        };
+
+        // Two nodes equivocate in view 1 and one in view 2.
+        let mut expected = BTreeMap::<ViewNumber, Vec<_>>::new();
+        for (view, signer) in [(1, 0), (1, 1), (2, 0)] {
+            let evidence = EquivocationEvidence::QuorumVote {
+                first: vote(view, signer, 1).await,
+                second: vote(view, signer, 2).await,
+            };
+            evidence.verify(&upgrade_lock).await.unwrap();
+
+            storage
+                .append_equivocation_evidence(&evidence)
+                .await
+                .unwrap();
+            // Appending the same offense again is a no-op.
+            storage
+                .append_equivocation_evidence(&evidence)
+                .await
+                .unwrap();
+            expected
+                .entry(ViewNumber::new(view))
+                .or_default()
+                .push(evidence);
+        }
+
+        let mut loaded = storage
+            .load_equivocation_evidence(ViewNumber::genesis(), ViewNumber::new(10))
+            .await
+            .unwrap();
+        for evidence in loaded.values_mut() {
+            evidence.sort_by_key(|evidence| evidence.offender());
+        }
+        for evidence in expected.values_mut() {
+            evidence.sort_by_key(|evidence| evidence.offender());
+        }
+        assert_eq!(loaded, expected);
+
+        // Only the evidence for the requested views is loaded.
+        let loaded = storage
+            .load_equivocation_evidence(ViewNumber::new(2), ViewNumber::new(3))
+            .await
+            .unwrap();
+        assert_eq!(
+            loaded.keys().copied().collect::<Vec<_>>(),
+            [ViewNumber::new(2)]
+        );
+        assert!(storage
+            .load_equivocation_evidence(ViewNumber::new(1), ViewNumber::new(1))
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Votes for the same leaf are not evidence of anything.
+        let same = EquivocationEvidence::QuorumVote {
+            first: vote(3, 0, 1).await,
+            second: vote(3, 0, 1).await,
+        };
+        same.verify(&upgrade_lock).await.unwrap_err();
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     pub async fn test_next_epoch_quorum_certificate<P: TestablePersistence>() {
         setup_test();
@@ -734,6 +937,55 @@ mod persistence_tests {
         assert_eq!(view_number, new_view_number_for_qc);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    pub async fn test_stake_table_events<P: TestablePersistence + MembershipPersistence>() {
+        setup_test();
+
+        let tmp = P::tmp_storage().await;
+        let storage = P::connect(&tmp).await;
+        assert!(storage.load_events().await.unwrap().is_none());
+
+        let validator = Address::random();
+        let delegated = |amount: u64| -> StakeTableEvent {
+            Delegated {
+                delegator: Address::random(),
+                validator,
+                amount: U256::from(amount),
+            }
+            .into()
+        };
+        // Stake table events are compared as the L1 logs they encode.
+        let logs = |events: Vec<IndexedStakeTableEvent>| {
+            events
+                .into_iter()
+                .map(|(key, event)| (key, LogData::from(event)))
+                .collect::<Vec<_>>()
+        };
+
+        let first = vec![((10, 0), delegated(1)), ((10, 1), delegated(2))];
+        storage.store_events(20, first.clone()).await.unwrap();
+        let (l1_block, events) = storage.load_events().await.unwrap().unwrap();
+        assert_eq!(l1_block, 20);
+        assert_eq!(logs(events), logs(first.clone()));
+
+        // Storing new events appends to the stored events. Storing an event which is already
+        // stored has no effect.
+        let second = vec![first[1].clone(), ((25, 0), delegated(3))];
+        storage.store_events(30, second.clone()).await.unwrap();
+        let (l1_block, events) = storage.load_events().await.unwrap().unwrap();
+        assert_eq!(l1_block, 30);
+        assert_eq!(
+            logs(events),
+            logs(vec![first[0].clone(), first[1].clone(), second[1].clone()])
+        );
+
+        // The stored L1 block never decreases.
+        storage.store_events(15, vec![]).await.unwrap();
+        let (l1_block, events) = storage.load_events().await.unwrap().unwrap();
+        assert_eq!(l1_block, 30);
+        assert_eq!(events.len(), 3);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     pub async fn test_decide_with_failing_event_consumer<P: TestablePersistence>() {
         #[derive(Clone, Copy, Debug)]
@@ -0,0 +1,539 @@
+//! Moving consensus storage from one persistence backend to another.
+//!
+//! Everything a node needs to resume consensus is read from the source backend through the
+//! [`SequencerPersistence`] and [`MembershipPersistence`] interfaces and written to the destination
+//! backend through the same interfaces, so that a node can switch between `storage-fs` and
+//! `storage-sql` without resetting its storage and resyncing.
+
+use std::fmt::Debug;
+
+use anyhow::{ensure, Context};
+use committable::Committable;
+use espresso_types::{
+    traits::{MembershipPersistence, NullEventConsumer, SequencerPersistence},
+    Leaf2, NodeState,
+};
+use hotshot_types::{
+    data::{VidDisperseShare, ViewNumber},
+    event::{HotShotAction, LeafInfo},
+    message::Proposal,
+    traits::node_implementation::{ConsensusTime, Versions},
+};
+use serde::Serialize;
+
+/// The maximum number of stake tables to copy.
+const STAKE_TABLE_LIMIT: u64 = u32::MAX as u64;
+
+/// Copy all consensus storage from `src` to `dst`.
+///
+/// `dst` must be empty, or else contain a partial migration from `src` left by an earlier call
+/// which failed part way through. Every write to `dst` overwrites whatever is already stored for
+/// the same key, so calling this again after a failure resumes and completes the migration.
+///
+/// The anchor leaf is copied as a leaf which has already been decided and processed, so any
+/// decided leaves which `src` has not yet passed to its event consumer (for example, leaves not yet
+/// moved to archival storage) will not be passed to the event consumer of `dst`.
+pub async fn migrate_backend<S, D>(src: &S, dst: &D) -> anyhow::Result<()>
+where
+    S: SequencerPersistence + MembershipPersistence,
+    D: SequencerPersistence + MembershipPersistence,
+{
+    let anchor = src
+        .load_anchor_leaf()
+        .await
+        .context("loading anchor leaf")?;
+    let voted_view = src
+        .load_latest_acted_view()
+        .await
+        .context("loading latest acted view")?;
+    ensure_resumable(dst, anchor.as_ref().map(|(leaf, _)| leaf), voted_view).await?;
+
+    if let Some(config) = src.load_config().await.context("loading config")? {
+        tracing::info!("migrating network config");
+        dst.save_config(&config).await?;
+    }
+
+    // The anchor leaf goes first, since storing a decided leaf may garbage collect older data.
+    if let Some((leaf, qc)) = &anchor {
+        let view = leaf.view_number();
+        tracing::info!(?view, height = leaf.height(), "migrating anchor leaf");
+        let info = LeafInfo {
+            leaf: leaf.clone(),
+            vid_share: None,
+            state: Default::default(),
+            delta: None,
+            state_cert: None,
+        };
+        dst.append_decided_leaves(view, [(&info, qc.clone())], &NullEventConsumer)
+            .await?;
+    }
+
+    if let Some(view) = voted_view {
+        tracing::info!(?view, "migrating latest acted view");
+        dst.record_action(view, None, HotShotAction::Vote).await?;
+    }
+
+    let proposals = src
+        .load_quorum_proposals()
+        .await
+        .context("loading quorum proposals")?;
+    tracing::info!(count = proposals.len(), "migrating quorum proposals");
+    for proposal in proposals.values() {
+        dst.append_quorum_proposal2(proposal).await?;
+    }
+
+    // DA proposals and VID shares can only be loaded by view, so check every view which could
+    // still have some, from the anchor leaf up to the latest view we have acted in.
+    let views = proposals
+        .keys()
+        .copied()
+        .chain(anchor.as_ref().map(|(leaf, _)| leaf.view_number()))
+        .chain(voted_view);
+    if let (Some(first), Some(last)) = (views.clone().min(), views.max()) {
+        tracing::info!(?first, ?last, "migrating DA proposals and VID shares");
+        for view in first.u64()..=last.u64() {
+            let view = ViewNumber::new(view);
+
+            let vid_share = src
+                .load_vid_share(view)
+                .await
+                .context(format!("loading VID share for view {view:?}"))?;
+            if let Some(proposal) = &vid_share {
+                match &proposal.data {
+                    VidDisperseShare::V0(share) => {
+                        dst.append_vid(&Proposal {
+                            data: share.clone(),
+                            signature: proposal.signature.clone(),
+                            _pd: proposal._pd,
+                        })
+                        .await?
+                    },
+                    VidDisperseShare::V1(share) => {
+                        dst.append_vid2(&Proposal {
+                            data: share.clone(),
+                            signature: proposal.signature.clone(),
+                            _pd: proposal._pd,
+                        })
+                        .await?
+                    },
+                }
+            }
+
+            let Some(da_proposal) = src
+                .load_da_proposal(view)
+                .await
+                .context(format!("loading DA proposal for view {view:?}"))?
+            else {
+                continue;
+            };
+            // DA proposals are stored with their payload commitment, which we take from the VID
+            // share or the quorum proposal for the same view.
+            let vid_commit = vid_share
+                .as_ref()
+                .map(|proposal| proposal.data.payload_commitment())
+                .or_else(|| {
+                    proposals
+                        .get(&view)
+                        .map(|proposal| proposal.data.block_header().payload_commitment())
+                });
+            let vid_commit = vid_commit.with_context(|| {
+                format!(
+                    "cannot migrate DA proposal for view {view:?}: no VID share or quorum proposal \
+                     to take its payload commitment from"
+                )
+            })?;
+            dst.append_da2(&da_proposal, vid_commit).await?;
+        }
+    }
+
+    if let Some(cert) = src
+        .load_upgrade_certificate()
+        .await
+        .context("loading upgrade certificate")?
+    {
+        tracing::info!("migrating upgrade certificate");
+        dst.store_upgrade_certificate(Some(cert)).await?;
+    }
+
+    if let Some(qc) = src
+        .load_next_epoch_quorum_certificate()
+        .await
+        .context("loading next epoch QC")?
+    {
+        tracing::info!("migrating next epoch QC");
+        dst.store_next_epoch_quorum_certificate(qc).await?;
+    }
+
+    let epoch_info = src
+        .load_start_epoch_info()
+        .await
+        .context("loading epoch info")?;
+    tracing::info!(count = epoch_info.len(), "migrating epoch info");
+    for info in epoch_info {
+        dst.add_drb_result(info.epoch, info.drb_result).await?;
+        if let Some(header) = info.block_header {
+            dst.add_epoch_root(info.epoch, header).await?;
+        }
+    }
+
+    let stake_tables = src
+        .load_latest_stake(STAKE_TABLE_LIMIT)
+        .await
+        .context("loading stake tables")?
+        .unwrap_or_default();
+    tracing::info!(count = stake_tables.len(), "migrating stake tables");
+    for (epoch, stake) in stake_tables {
+        dst.store_stake(epoch, stake).await?;
+    }
+
+    if let Some((l1_block, events)) = src
+        .load_events()
+        .await
+        .context("loading stake table events")?
+    {
+        tracing::info!(
+            l1_block,
+            count = events.len(),
+            "migrating stake table events"
+        );
+        dst.store_events(l1_block, events).await?;
+    }
+
+    if let Some(cert) = src.load_state_cert().await.context("loading state cert")? {
+        tracing::info!("migrating light client state update certificate");
+        dst.add_state_cert(cert).await?;
+    }
+
+    Ok(())
+}
+
+/// Check that `dst` is either empty or a partial migration from a source with the given anchor
+/// leaf and latest acted view.
+///
+/// Since the anchor leaf is the first thing migrated, a partial migration has either no anchor
+/// leaf or the same anchor leaf as the source. A destination which has decided a different leaf,
+/// or acted in a later view than the source, has been used to run consensus and must not be
+/// overwritten.
+async fn ensure_resumable(
+    dst: &impl SequencerPersistence,
+    anchor: Option<&Leaf2>,
+    voted_view: Option<ViewNumber>,
+) -> anyhow::Result<()> {
+    let dst_anchor = dst.load_anchor_leaf().await?;
+    let dst_voted_view = dst.load_latest_acted_view().await?;
+    if dst_anchor.is_none() && dst_voted_view.is_none() {
+        return Ok(());
+    }
+
+    if let Some((dst_leaf, _)) = &dst_anchor {
+        ensure!(
+            Some(dst_leaf.commit()) == anchor.map(Committable::commit),
+            "destination storage is not empty: it has decided a different leaf than the source"
+        );
+    }
+    ensure!(
+        dst_voted_view <= voted_view,
+        "destination storage is not empty: it has acted in a later view than the source"
+    );
+    tracing::warn!(
+        ?dst_voted_view,
+        "destination contains a partial migration, resuming"
+    );
+    Ok(())
+}
+
+/// Check that `src` and `dst` would resume consensus from the same state.
+pub async fn verify_backend_migration<V, S, D>(
+    src: &S,
+    dst: &D,
+    instance: NodeState,
+) -> anyhow::Result<()>
+where
+    V: Versions,
+    S: SequencerPersistence,
+    D: SequencerPersistence,
+{
+    let (src_state, src_anchor_view) = src
+        .load_consensus_state::<V>(instance.clone())
+        .await
+        .context("loading source consensus state")?;
+    let (dst_state, dst_anchor_view) = dst
+        .load_consensus_state::<V>(instance)
+        .await
+        .context("loading destination consensus state")?;
+
+    ensure_same("anchor view", &src_anchor_view, &dst_anchor_view)?;
+    // Backends differ in whether they keep the payload of the anchor leaf, which is not needed to
+    // resume consensus.
+    ensure_same(
+        "anchor leaf",
+        &without_payload(&src_state.anchor_leaf),
+        &without_payload(&dst_state.anchor_leaf),
+    )?;
+    ensure_same(
+        "anchor state",
+        &*src_state.anchor_state,
+        &*dst_state.anchor_state,
+    )?;
+    ensure_same("start view", &src_state.start_view, &dst_state.start_view)?;
+    ensure_same(
+        "last actioned view",
+        &src_state.last_actioned_view,
+        &dst_state.last_actioned_view,
+    )?;
+    ensure_same(
+        "start epoch",
+        &src_state.start_epoch,
+        &dst_state.start_epoch,
+    )?;
+    ensure_same(
+        "epoch height",
+        &src_state.epoch_height,
+        &dst_state.epoch_height,
+    )?;
+    ensure_same(
+        "epoch start block",
+        &src_state.epoch_start_block,
+        &dst_state.epoch_start_block,
+    )?;
+    ensure_same("high QC", &src_state.high_qc, &dst_state.high_qc)?;
+    ensure_same(
+        "next epoch high QC",
+        &src_state.next_epoch_high_qc,
+        &dst_state.next_epoch_high_qc,
+    )?;
+    ensure_same(
+        "saved proposals",
+        &src_state.saved_proposals,
+        &dst_state.saved_proposals,
+    )?;
+    ensure_same(
+        "upgrade certificate",
+        &src_state.decided_upgrade_certificate,
+        &dst_state.decided_upgrade_certificate,
+    )?;
+    ensure_same("state cert", &src_state.state_cert, &dst_state.state_cert)?;
+    ensure!(
+        src_state.start_epoch_info == dst_state.start_epoch_info,
+        "epoch info differs after migration: {:?} != {:?}",
+        src_state.start_epoch_info,
+        dst_state.start_epoch_info
+    );
+
+    Ok(())
+}
+
+fn without_payload(leaf: &Leaf2) -> Leaf2 {
+    let mut leaf = leaf.clone();
+    leaf.unfill_block_payload();
+    leaf
+}
+
+fn ensure_same<T: Debug + Serialize>(what: &str, src: &T, dst: &T) -> anyhow::Result<()> {
+    ensure!(
+        bincode::serialize(src)? == bincode::serialize(dst)?,
+        "{what} differs after migration: {src:?} != {dst:?}"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use espresso_types::{PubKey, SeqTypes, ValidatedState};
+    use hotshot::types::{BLSPubKey, SignatureKey};
+    use hotshot_example_types::node_types::TestVersions;
+    use hotshot_types::{
+        data::{DaProposal2, EpochNumber, QuorumProposal2, QuorumProposalWrapper},
+        simple_certificate::QuorumCertificate2,
+        traits::EncodeBytes,
+        utils::EpochTransitionIndicator,
+    };
+    use sequencer_utils::test_utils::setup_test;
+
+    use super::*;
+    use crate::persistence::{fs, sql, testing::TestablePersistence};
+
+    async fn populate(storage: &(impl SequencerPersistence + MembershipPersistence)) {
+        let instance = NodeState::mock();
+        let state = ValidatedState::default();
+        let leaf = Leaf2::genesis::<TestVersions>(&state, &instance).await;
+        let qc = QuorumCertificate2::genesis::<TestVersions>(&state, &instance).await;
+        let info = LeafInfo {
+            leaf: leaf.clone(),
+            vid_share: None,
+            state: Default::default(),
+            delta: None,
+            state_cert: None,
+        };
+        storage
+            .append_decided_leaves(
+                ViewNumber::genesis(),
+                [(&info, qc.clone())],
+                &NullEventConsumer,
+            )
+            .await
+            .unwrap();
+        storage
+            .record_action(ViewNumber::new(2), None, HotShotAction::Vote)
+            .await
+            .unwrap();
+
+        let (_, privkey) = BLSPubKey::generated_from_seed_indexed([0; 32], 1);
+        let proposal = Proposal {
+            data: QuorumProposalWrapper {
+                proposal: QuorumProposal2 {
+                    epoch: None,
+                    block_header: leaf.block_header().clone(),
+                    view_number: ViewNumber::new(1),
+                    justify_qc: qc,
+                    upgrade_certificate: None,
+                    view_change_evidence: None,
+                    next_drb_result: None,
+                    next_epoch_justify_qc: None,
+                    state_cert: None,
+                },
+            },
+            signature: PubKey::sign(&privkey, &[]).unwrap(),
+            _pd: Default::default(),
+        };
+        storage.append_quorum_proposal2(&proposal).await.unwrap();
+
+        storage
+            .add_drb_result(EpochNumber::new(1), [1; 32])
+            .await
+            .unwrap();
+        storage
+            .add_epoch_root(EpochNumber::new(1), leaf.block_header().clone())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_migrate_backend() {
+        setup_test();
+
+        let fs_tmp = fs::Persistence::tmp_storage().await;
+        let fs_storage = fs::Persistence::connect(&fs_tmp).await;
+        let sql_tmp = sql::Persistence::tmp_storage().await;
+        let sql_storage = sql::Persistence::connect(&sql_tmp).await;
+        populate(&fs_storage).await;
+
+        // Migrate from file system to SQL storage.
+        migrate_backend(&fs_storage, &sql_storage).await.unwrap();
+        verify_backend_migration::<TestVersions, _, _>(
+            &fs_storage,
+            &sql_storage,
+            NodeState::mock(),
+        )
+        .await
+        .unwrap();
+
+        // Migrate back to fresh file system storage.
+        let fs_tmp2 = fs::Persistence::tmp_storage().await;
+        let fs_storage2 = fs::Persistence::connect(&fs_tmp2).await;
+        migrate_backend(&sql_storage, &fs_storage2).await.unwrap();
+        verify_backend_migration::<TestVersions, _, _>(
+            &fs_storage,
+            &fs_storage2,
+            NodeState::mock(),
+        )
+        .await
+        .unwrap();
+
+        // Migrating again into the same storage is a no-op.
+        migrate_backend(&fs_storage, &sql_storage).await.unwrap();
+        verify_backend_migration::<TestVersions, _, _>(
+            &fs_storage,
+            &sql_storage,
+            NodeState::mock(),
+        )
+        .await
+        .unwrap();
+
+        // Storage which is already in use cannot be the destination of a migration.
+        let used_tmp = sql::Persistence::tmp_storage().await;
+        let used = sql::Persistence::connect(&used_tmp).await;
+        used.record_action(ViewNumber::new(10), None, HotShotAction::Vote)
+            .await
+            .unwrap();
+        migrate_backend(&fs_storage, &used).await.unwrap_err();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_migrate_da_proposal_without_payload_commitment() {
+        setup_test();
+
+        let fs_tmp = fs::Persistence::tmp_storage().await;
+        let fs_storage = fs::Persistence::connect(&fs_tmp).await;
+        populate(&fs_storage).await;
+
+        // A DA proposal for a view with neither a VID share nor a quorum proposal, so there is
+        // nothing to take its payload commitment from.
+        let leaf = fs_storage.load_anchor_leaf().await.unwrap().unwrap().0;
+        let payload = leaf.block_payload().unwrap();
+        let (_, privkey) = BLSPubKey::generated_from_seed_indexed([0; 32], 1);
+        let proposal = Proposal {
+            data: DaProposal2::<SeqTypes> {
+                encoded_transactions: payload.encode(),
+                metadata: payload.ns_table().clone(),
+                view_number: ViewNumber::new(2),
+                epoch: None,
+                epoch_transition_indicator: EpochTransitionIndicator::NotInTransition,
+            },
+            signature: PubKey::sign(&privkey, &[]).unwrap(),
+            _pd: Default::default(),
+        };
+        fs_storage
+            .append_da2(&proposal, leaf.block_header().payload_commitment())
+            .await
+            .unwrap();
+
+        let sql_tmp = sql::Persistence::tmp_storage().await;
+        let sql_storage = sql::Persistence::connect(&sql_tmp).await;
+        migrate_backend(&fs_storage, &sql_storage)
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_resume_migration() {
+        setup_test();
+
+        let fs_tmp = fs::Persistence::tmp_storage().await;
+        let fs_storage = fs::Persistence::connect(&fs_tmp).await;
+        populate(&fs_storage).await;
+
+        // Simulate a migration which failed right after copying the anchor leaf.
+        let sql_tmp = sql::Persistence::tmp_storage().await;
+        let sql_storage = sql::Persistence::connect(&sql_tmp).await;
+        let (leaf, qc) = fs_storage.load_anchor_leaf().await.unwrap().unwrap();
+        let info = LeafInfo {
+            leaf: leaf.clone(),
+            vid_share: None,
+            state: Default::default(),
+            delta: None,
+            state_cert: None,
+        };
+        sql_storage
+            .append_decided_leaves(leaf.view_number(), [(&info, qc)], &NullEventConsumer)
+            .await
+            .unwrap();
+        verify_backend_migration::<TestVersions, _, _>(
+            &fs_storage,
+            &sql_storage,
+            NodeState::mock(),
+        )
+        .await
+        .unwrap_err();
+
+        // Running the migration again completes it.
+        migrate_backend(&fs_storage, &sql_storage).await.unwrap();
+        verify_backend_migration::<TestVersions, _, _>(
+            &fs_storage,
+            &sql_storage,
+            NodeState::mock(),
+        )
+        .await
+        .unwrap();
+    }
+}
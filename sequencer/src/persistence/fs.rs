@@ -7,24 +7,28 @@ use std::{
     sync::Arc,
 };
 
-use anyhow::{anyhow, Context};
+use anyhow::{anyhow, ensure, Context};
 use async_lock::RwLock;
 use async_trait::async_trait;
 use clap::Parser;
 use espresso_types::{
     traits::MembershipPersistence,
-    v0::traits::{EventConsumer, PersistenceOptions, SequencerPersistence},
+    v0::{
+        snapshot::StateSnapshot,
+        traits::{EventConsumer, PersistenceOptions, SequencerPersistence, StateCatchup},
+    },
     v0_3::{IndexedStake, Validator},
-    Leaf, Leaf2, NetworkConfig, Payload, SeqTypes,
+    BackoffParams, IndexedStakeTableEvent, Leaf, Leaf2, NetworkConfig, Payload, SeqTypes,
 };
 use hotshot::{types::BLSPubKey, InitializerEpochInfo};
 use hotshot_types::{
     data::{
-        vid_disperse::{ADVZDisperseShare, VidDisperseShare2},
+        vid_disperse::{ADVZDisperseShare, VidDisperseShare2, VidEncodingEvidence},
         DaProposal, DaProposal2, EpochNumber, QuorumProposal, QuorumProposal2,
         QuorumProposalWrapper, VidCommitment, VidDisperseShare,
     },
     drb::DrbResult,
+    equivocation::EquivocationEvidence,
     event::{Event, EventType, HotShotAction, LeafInfo},
     message::{convert_proposal, Proposal},
     simple_certificate::{
@@ -40,7 +44,7 @@ use hotshot_types::{
 use indexmap::IndexMap;
 use itertools::Itertools;
 
-use crate::ViewNumber;
+use crate::{catchup::SnapshotStateCatchup, ViewNumber};
 
 /// Options for file system backed persistence.
 #[derive(Parser, Clone, Debug)]
@@ -199,6 +203,10 @@ impl Inner {
         self.path.join("stake_table")
     }
 
+    fn stake_table_events_path(&self) -> PathBuf {
+        self.path.join("stake_table_events")
+    }
+
     fn next_epoch_qc(&self) -> PathBuf {
         self.path.join("next_epoch_quorum_certificate")
     }
@@ -219,6 +227,19 @@ impl Inner {
         self.path.join("state_cert")
     }
 
+    fn vid_encoding_evidence_dir_path(&self) -> PathBuf {
+        self.path.join("vid_encoding_evidence")
+    }
+
+    fn equivocation_evidence_dir_path(&self) -> PathBuf {
+        self.path.join("equivocation_evidence")
+    }
+
+    /// Path to a state snapshot this storage was bootstrapped from, if any.
+    fn snapshot_path(&self) -> PathBuf {
+        self.path.join("snapshot")
+    }
+
     fn update_migration(&mut self) -> anyhow::Result<()> {
         let path = self.migration();
         let bytes = bincode::serialize(&self.migrated)?;
@@ -549,10 +570,95 @@ impl Inner {
 
         Ok(None)
     }
+
+    fn load_stake_table_events(
+        &self,
+    ) -> anyhow::Result<Option<(u64, Vec<IndexedStakeTableEvent>)>> {
+        let path = self.stake_table_events_path();
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let bytes = fs::read(&path).context("read")?;
+        Ok(Some(
+            bincode::deserialize(&bytes).context("deserialize stake table events")?,
+        ))
+    }
 }
 
 #[async_trait]
 impl SequencerPersistence for Persistence {
+    fn into_catchup_provider(
+        self,
+        backoff: BackoffParams,
+    ) -> anyhow::Result<Arc<dyn StateCatchup>> {
+        let path = self.inner.read_blocking().snapshot_path();
+        ensure!(
+            path.is_file(),
+            "file system storage supports catchup only from an imported snapshot"
+        );
+        let snapshot = StateSnapshot::load(path)?;
+        Ok(Arc::new(SnapshotStateCatchup::new(snapshot, backoff)?))
+    }
+
+    async fn import_snapshot(&self, snapshot: &StateSnapshot) -> anyhow::Result<()> {
+        let height = snapshot.height();
+        ensure!(height > 0, "importing a genesis snapshot is not supported");
+        // Never replace a stake table we already have with one from an untrusted snapshot.
+        if let Some((epoch, stake)) = &snapshot.stake_table {
+            if let Some(stored) = self.load_stake(*epoch).await? {
+                ensure!(
+                    stored == *stake,
+                    "snapshot stake table for epoch {epoch} does not match the stored one"
+                );
+            }
+        }
+        let qc = snapshot
+            .leaf_chain
+            .get(1)
+            .context("snapshot leaf chain is too short")?
+            .justify_qc();
+        let mut leaf = snapshot.leaf().clone();
+        leaf.unfill_block_payload();
+        let view = leaf.view_number().u64();
+        tracing::info!(height, view, "importing state snapshot");
+
+        {
+            let mut inner = self.inner.write().await;
+
+            // Keep the full snapshot, so that it can serve catchup requests for the initial state.
+            let snapshot_path = inner.snapshot_path();
+            inner.replace(
+                &snapshot_path,
+                |_| Ok(true),
+                |mut file| {
+                    let bytes = bincode::serialize(snapshot)?;
+                    file.write_all(&bytes)?;
+                    Ok(())
+                },
+            )?;
+
+            // Start consensus from the snapshotted leaf. As the oldest decided leaf in storage, it
+            // will be treated as already processed.
+            let dir_path = inner.decided_leaf2_path();
+            fs::create_dir_all(&dir_path).context("creating anchor leaf directory")?;
+            let file_path = dir_path.join(view.to_string()).with_extension("txt");
+            inner.replace(
+                &file_path,
+                |_| Ok(true),
+                |mut file| {
+                    let bytes = bincode::serialize(&(&leaf, qc))?;
+                    file.write_all(&bytes)?;
+                    Ok(())
+                },
+            )?;
+        }
+
+        if let Some((epoch, stake)) = &snapshot.stake_table {
+            self.store_stake(*epoch, stake.clone()).await?;
+        }
+        Ok(())
+    }
+
     async fn load_config(&self) -> anyhow::Result<Option<NetworkConfig>> {
         let inner = self.inner.read().await;
         let path = inner.config_path();
@@ -737,6 +843,83 @@ impl SequencerPersistence for Persistence {
             },
         )
     }
+    async fn append_vid_encoding_evidence(
+        &self,
+        evidence: &VidEncodingEvidence<SeqTypes>,
+    ) -> anyhow::Result<()> {
+        let mut inner = self.inner.write().await;
+        let view_number = evidence.view_number.u64();
+
+        let dir_path = inner.vid_encoding_evidence_dir_path();
+
+        fs::create_dir_all(dir_path.clone())
+            .context("failed to create vid encoding evidence dir")?;
+
+        let file_path = dir_path.join(view_number.to_string()).with_extension("txt");
+
+        inner.replace(
+            &file_path,
+            |_| {
+                // One piece of evidence per view is enough to incriminate the leader.
+                tracing::debug!(view_number, "duplicate VID encoding evidence");
+                Ok(false)
+            },
+            |mut file| {
+                let evidence_bytes =
+                    bincode::serialize(evidence).context("serialize VID encoding evidence")?;
+                file.write_all(&evidence_bytes)?;
+                Ok(())
+            },
+        )
+    }
+    async fn append_equivocation_evidence(
+        &self,
+        evidence: &EquivocationEvidence<SeqTypes>,
+    ) -> anyhow::Result<()> {
+        let mut inner = self.inner.write().await;
+        let view_number = evidence.view_number().u64();
+
+        let dir_path = inner.equivocation_evidence_dir_path();
+
+        fs::create_dir_all(dir_path.clone())
+            .context("failed to create equivocation evidence dir")?;
+
+        let file_path = dir_path.join(view_number.to_string()).with_extension("txt");
+
+        // Several nodes may equivocate in the same view, so each file holds all the evidence for
+        // its view.
+        let mut view_evidence: Vec<EquivocationEvidence<SeqTypes>> = if file_path.is_file() {
+            let bytes = fs::read(&file_path).context(format!(
+                "reading equivocation evidence {}",
+                file_path.display()
+            ))?;
+            bincode::deserialize(&bytes).context(format!(
+                "parsing equivocation evidence {}",
+                file_path.display()
+            ))?
+        } else {
+            vec![]
+        };
+        if view_evidence.iter().any(|existing| {
+            existing.offender() == evidence.offender() && existing.kind() == evidence.kind()
+        }) {
+            // One piece of evidence per offense is enough to incriminate the offender.
+            tracing::debug!(view_number, "duplicate equivocation evidence");
+            return Ok(());
+        }
+        view_evidence.push(evidence.clone());
+
+        inner.replace(
+            &file_path,
+            |_| Ok(true),
+            |mut file| {
+                let evidence_bytes = bincode::serialize(&view_evidence)
+                    .context("serialize equivocation evidence")?;
+                file.write_all(&evidence_bytes)?;
+                Ok(())
+            },
+        )
+    }
     async fn append_da(
         &self,
         proposal: &Proposal<SeqTypes, DaProposal<SeqTypes>>,
@@ -860,6 +1043,55 @@ impl SequencerPersistence for Persistence {
         Ok(map)
     }
 
+    async fn load_vid_encoding_evidence(
+        &self,
+    ) -> anyhow::Result<BTreeMap<ViewNumber, VidEncodingEvidence<SeqTypes>>> {
+        let inner = self.inner.read().await;
+
+        let dir_path = inner.vid_encoding_evidence_dir_path();
+        if !dir_path.is_dir() {
+            return Ok(Default::default());
+        }
+
+        let mut map = BTreeMap::new();
+        for (view, path) in view_files(&dir_path)? {
+            let bytes = fs::read(&path)
+                .context(format!("reading VID encoding evidence {}", path.display()))?;
+            let evidence = bincode::deserialize(&bytes)
+                .context(format!("parsing VID encoding evidence {}", path.display()))?;
+            map.insert(view, evidence);
+        }
+
+        Ok(map)
+    }
+
+    async fn load_equivocation_evidence(
+        &self,
+        from: ViewNumber,
+        to: ViewNumber,
+    ) -> anyhow::Result<BTreeMap<ViewNumber, Vec<EquivocationEvidence<SeqTypes>>>> {
+        let inner = self.inner.read().await;
+
+        let dir_path = inner.equivocation_evidence_dir_path();
+        if !dir_path.is_dir() {
+            return Ok(Default::default());
+        }
+
+        let mut map = BTreeMap::new();
+        for (view, path) in view_files(&dir_path)? {
+            if view < from || view >= to {
+                continue;
+            }
+            let bytes = fs::read(&path)
+                .context(format!("reading equivocation evidence {}", path.display()))?;
+            let evidence = bincode::deserialize(&bytes)
+                .context(format!("parsing equivocation evidence {}", path.display()))?;
+            map.insert(view, evidence);
+        }
+
+        Ok(map)
+    }
+
     async fn load_quorum_proposal(
         &self,
         view: ViewNumber,
@@ -1448,6 +1680,44 @@ impl MembershipPersistence for Persistence {
             },
         )
     }
+
+    async fn load_events(&self) -> anyhow::Result<Option<(u64, Vec<IndexedStakeTableEvent>)>> {
+        let inner = self.inner.read().await;
+        inner.load_stake_table_events()
+    }
+
+    async fn store_events(
+        &self,
+        l1_block: u64,
+        events: Vec<IndexedStakeTableEvent>,
+    ) -> anyhow::Result<()> {
+        let mut inner = self.inner.write().await;
+
+        // The events are stored in a single file, so merge the new events with the stored ones.
+        let (l1_block, events) = match inner.load_stake_table_events()? {
+            Some((stored_l1_block, stored_events)) => {
+                let mut merged: BTreeMap<_, _> = stored_events.into_iter().collect();
+                merged.extend(events);
+                (l1_block.max(stored_l1_block), merged.into_iter().collect())
+            },
+            None => (l1_block, events),
+        };
+
+        let path = inner.stake_table_events_path();
+        inner.replace(
+            &path,
+            |_| {
+                // Always overwrite the previous file.
+                Ok(true)
+            },
+            |mut file| {
+                let bytes = bincode::serialize(&(l1_block, events))
+                    .context("serializing stake table events")?;
+                file.write_all(&bytes)?;
+                Ok(())
+            },
+        )
+    }
 }
 
 /// Update a `NetworkConfig` that may have originally been persisted with an old version.
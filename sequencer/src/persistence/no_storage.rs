@@ -9,16 +9,17 @@ use espresso_types::{
     traits::MembershipPersistence,
     v0::traits::{EventConsumer, PersistenceOptions, SequencerPersistence},
     v0_3::{IndexedStake, Validator},
-    Leaf2, NetworkConfig,
+    IndexedStakeTableEvent, Leaf2, NetworkConfig,
 };
 use hotshot::{types::BLSPubKey, InitializerEpochInfo};
 use hotshot_types::{
     data::{
-        vid_disperse::{ADVZDisperseShare, VidDisperseShare2},
+        vid_disperse::{ADVZDisperseShare, VidDisperseShare2, VidEncodingEvidence},
         DaProposal, DaProposal2, EpochNumber, QuorumProposalWrapper, VidCommitment,
         VidDisperseShare,
     },
     drb::DrbResult,
+    equivocation::EquivocationEvidence,
     event::{Event, EventType, HotShotAction, LeafInfo},
     message::Proposal,
     simple_certificate::{
@@ -140,6 +141,18 @@ impl SequencerPersistence for NoStorage {
     ) -> anyhow::Result<()> {
         Ok(())
     }
+    async fn append_vid_encoding_evidence(
+        &self,
+        _evidence: &VidEncodingEvidence<SeqTypes>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+    async fn append_equivocation_evidence(
+        &self,
+        _evidence: &EquivocationEvidence<SeqTypes>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
     async fn append_da(
         &self,
         _proposal: &Proposal<SeqTypes, DaProposal<SeqTypes>>,
@@ -244,6 +257,20 @@ impl SequencerPersistence for NoStorage {
     ) -> anyhow::Result<Option<LightClientStateUpdateCertificate<SeqTypes>>> {
         Ok(None)
     }
+
+    async fn load_vid_encoding_evidence(
+        &self,
+    ) -> anyhow::Result<BTreeMap<ViewNumber, VidEncodingEvidence<SeqTypes>>> {
+        Ok(Default::default())
+    }
+
+    async fn load_equivocation_evidence(
+        &self,
+        _from: ViewNumber,
+        _to: ViewNumber,
+    ) -> anyhow::Result<BTreeMap<ViewNumber, Vec<EquivocationEvidence<SeqTypes>>>> {
+        Ok(Default::default())
+    }
 }
 
 #[async_trait]
@@ -266,4 +293,16 @@ impl MembershipPersistence for NoStorage {
     ) -> anyhow::Result<()> {
         Ok(())
     }
+
+    async fn load_events(&self) -> anyhow::Result<Option<(u64, Vec<IndexedStakeTableEvent>)>> {
+        Ok(None)
+    }
+
+    async fn store_events(
+        &self,
+        _l1_block: u64,
+        _events: Vec<IndexedStakeTableEvent>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
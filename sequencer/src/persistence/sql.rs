@@ -1,6 +1,6 @@
 use std::{collections::BTreeMap, path::PathBuf, str::FromStr, sync::Arc, time::Duration};
 
-use anyhow::{bail, Context};
+use anyhow::{bail, ensure, Context};
 use async_trait::async_trait;
 use clap::Parser;
 use committable::Committable;
@@ -9,21 +9,27 @@ use derive_more::derive::{From, Into};
 use espresso_types::{
     parse_duration, parse_size,
     traits::MembershipPersistence,
-    v0::traits::{EventConsumer, PersistenceOptions, SequencerPersistence, StateCatchup},
+    v0::{
+        snapshot::StateSnapshot,
+        traits::{EventConsumer, PersistenceOptions, SequencerPersistence, StateCatchup},
+    },
     v0_3::{IndexedStake, Validator},
-    BackoffParams, BlockMerkleTree, FeeMerkleTree, Leaf, Leaf2, NetworkConfig, Payload,
+    BackoffParams, BlockMerkleTree, Delta, FeeMerkleTree, IndexedStakeTableEvent, Leaf, Leaf2,
+    NetworkConfig, Payload,
 };
 use futures::stream::StreamExt;
 use hotshot::{types::BLSPubKey, InitializerEpochInfo};
 use hotshot_query_service::{
-    availability::LeafQueryData,
+    availability::{LeafId, LeafQueryData},
     data_source::{
         storage::{
+            blob::{self, FileSystemBlobStore},
             pruning::PrunerCfg,
             sql::{
                 include_migrations, query_as, syntax_helpers::MAX_FN, Config, Db, SqlStorage,
                 Transaction, TransactionMode, Write,
             },
+            AvailabilityStorage, UpdateAvailabilityStorage,
         },
         Transaction as _, VersionedDataSource,
     },
@@ -36,11 +42,12 @@ use hotshot_query_service::{
 };
 use hotshot_types::{
     data::{
-        vid_disperse::{ADVZDisperseShare, VidDisperseShare2},
+        vid_disperse::{ADVZDisperseShare, VidDisperseShare2, VidEncodingEvidence},
         DaProposal, DaProposal2, EpochNumber, QuorumProposal, QuorumProposalWrapper, VidCommitment,
         VidDisperseShare,
     },
     drb::DrbResult,
+    equivocation::EquivocationEvidence,
     event::{Event, EventType, HotShotAction, LeafInfo},
     message::{convert_proposal, Proposal},
     simple_certificate::{
@@ -51,13 +58,20 @@ use hotshot_types::{
         block_contents::{BlockHeader, BlockPayload},
         node_implementation::ConsensusTime,
     },
+    utils::root_block_in_epoch,
     vote::HasViewNumber,
 };
 use indexmap::IndexMap;
 use itertools::Itertools;
-use sqlx::{query, Executor, Row};
-
-use crate::{catchup::SqlStateCatchup, NodeType, SeqTypes, ViewNumber};
+use sqlx::{query, Database, Executor, Row};
+
+use crate::{
+    api::sql::{load_block_proof, load_full_state},
+    catchup::{CatchupStorage, SqlStateCatchup},
+    persistence::ChainConfigPersistence,
+    state::store_state_update,
+    NodeType, SeqTypes, ViewNumber,
+};
 
 /// Options for Postgres-backed persistence.
 #[derive(Parser, Clone, Derivative)]
@@ -188,6 +202,16 @@ pub struct Options {
     #[clap(long, env = "ESPRESSO_SEQUENCER_ARCHIVE", conflicts_with = "prune")]
     pub(crate) archive: bool,
 
+    /// Directory in which to store large objects outside of the database.
+    ///
+    /// If set, block payloads, VID common data, DA proposals and VID shares are kept in a
+    /// content-addressed blob store in this directory, and the database stores only references to
+    /// them. Once set, this must keep pointing at the same blob store, or objects referenced by the
+    /// database cannot be loaded. Blobs are not deleted when the data referencing them is pruned,
+    /// so this is best suited to archival nodes.
+    #[clap(long, env = "ESPRESSO_SEQUENCER_BLOB_STORE_PATH")]
+    pub(crate) blob_store_path: Option<PathBuf>,
+
     /// Turns on leaf only data storage
     #[clap(
         long,
@@ -409,18 +433,21 @@ impl TryFrom<&Options> for Config {
         }
 
         if opt.prune {
-            cfg = cfg.pruner_cfg(PrunerCfg::from(opt.pruning))?;
+            cfg = cfg.pruner_cfg(PrunerCfg::from(opt.pruning.clone()))?;
         }
         if opt.archive {
             cfg = cfg.archive();
         }
+        if let Some(path) = &opt.blob_store_path {
+            cfg = cfg.blob_store(Arc::new(FileSystemBlobStore::new(path)?));
+        }
 
         Ok(cfg)
     }
 }
 
 /// Pruning parameters.
-#[derive(Parser, Clone, Copy, Debug)]
+#[derive(Parser, Clone, Debug)]
 pub struct PruningOptions {
     /// Threshold for pruning, specified in bytes.
     /// If the disk usage surpasses this threshold, pruning is initiated for data older than the specified minimum retention period.
@@ -466,6 +493,35 @@ pub struct PruningOptions {
         value_parser = parse_duration,
     )]
     interval: Option<Duration>,
+
+    /// Retention rules for individual namespaces.
+    ///
+    /// Each rule has the form `NAMESPACE=RETENTION`, where `RETENTION` is a duration or `forever`.
+    /// Payload data and transaction indexes for a namespace are deleted once they are older than its
+    /// retention period, while headers and leaves are kept. Blocks containing data which a rule
+    /// says to retain are never pruned, regardless of the global retention settings.
+    #[clap(
+        long,
+        env = "ESPRESSO_SEQUENCER_PRUNER_NAMESPACE_RETENTION",
+        value_parser = parse_namespace_retention,
+        value_delimiter = ',',
+    )]
+    namespace_retention: Vec<(u64, Option<Duration>)>,
+}
+
+fn parse_namespace_retention(s: &str) -> anyhow::Result<(u64, Option<Duration>)> {
+    let (ns_id, retention) = s.split_once('=').context(format!(
+        "malformed namespace retention rule {s}, expected NAMESPACE=RETENTION"
+    ))?;
+    let ns_id = ns_id
+        .trim()
+        .parse()
+        .context(format!("malformed namespace {ns_id}"))?;
+    let retention = match retention.trim() {
+        "forever" => None,
+        retention => Some(parse_duration(retention)?),
+    };
+    Ok((ns_id, retention))
 }
 
 impl From<PruningOptions> for PrunerCfg {
@@ -489,6 +545,9 @@ impl From<PruningOptions> for PrunerCfg {
         if let Some(interval) = opt.interval {
             cfg = cfg.with_interval(interval);
         }
+        for (ns_id, retention) in opt.namespace_retention {
+            cfg = cfg.with_namespace_retention(ns_id, retention);
+        }
 
         cfg = cfg.with_state_tables(vec![
             BlockMerkleTree::state_type().to_string(),
@@ -706,41 +765,42 @@ impl Persistence {
             let to_view = leaves[leaves.len() - 1].view_number();
 
             // Collect VID shares for the decide event.
-            let mut vid_shares = tx
+            let rows = tx
                 .fetch_all(
-                    query("SELECT view, data FROM vid_share2 where view >= $1 AND view <= $2")
-                        .bind(from_view.u64() as i64)
-                        .bind(to_view.u64() as i64),
+                    query(
+                        "SELECT view, data, blob FROM vid_share2 where view >= $1 AND view <= $2",
+                    )
+                    .bind(from_view.u64() as i64)
+                    .bind(to_view.u64() as i64),
                 )
-                .await?
-                .into_iter()
-                .map(|row| {
-                    let view: i64 = row.get("view");
-                    let data: Vec<u8> = row.get("data");
-                    let vid_proposal = bincode::deserialize::<
-                        Proposal<SeqTypes, VidDisperseShare<SeqTypes>>,
-                    >(&data)?;
-                    Ok((view as u64, vid_proposal.data))
-                })
-                .collect::<anyhow::Result<BTreeMap<_, _>>>()?;
+                .await?;
+            let mut vid_shares = BTreeMap::new();
+            for row in rows {
+                let view: i64 = row.get("view");
+                let data = load_blob_data(&tx, &row).await?;
+                let vid_proposal =
+                    bincode::deserialize::<Proposal<SeqTypes, VidDisperseShare<SeqTypes>>>(&data)?;
+                vid_shares.insert(view as u64, vid_proposal.data);
+            }
 
             // Collect DA proposals for the decide event.
-            let mut da_proposals = tx
+            let rows = tx
                 .fetch_all(
-                    query("SELECT view, data FROM da_proposal2 where view >= $1 AND view <= $2")
-                        .bind(from_view.u64() as i64)
-                        .bind(to_view.u64() as i64),
+                    query(
+                        "SELECT view, data, blob FROM da_proposal2 where view >= $1 AND view <= $2",
+                    )
+                    .bind(from_view.u64() as i64)
+                    .bind(to_view.u64() as i64),
                 )
-                .await?
-                .into_iter()
-                .map(|row| {
-                    let view: i64 = row.get("view");
-                    let data: Vec<u8> = row.get("data");
-                    let da_proposal =
-                        bincode::deserialize::<Proposal<SeqTypes, DaProposal2<SeqTypes>>>(&data)?;
-                    Ok((view as u64, da_proposal.data))
-                })
-                .collect::<anyhow::Result<BTreeMap<_, _>>>()?;
+                .await?;
+            let mut da_proposals = BTreeMap::new();
+            for row in rows {
+                let view: i64 = row.get("view");
+                let data = load_blob_data(&tx, &row).await?;
+                let da_proposal =
+                    bincode::deserialize::<Proposal<SeqTypes, DaProposal2<SeqTypes>>>(&data)?;
+                da_proposals.insert(view as u64, da_proposal.data);
+            }
 
             // Collect state certs for the decide event.
             let state_certs = tx
@@ -984,6 +1044,132 @@ impl SequencerPersistence for Persistence {
         Ok(Arc::new(SqlStateCatchup::new(Arc::new(self.db), backoff)))
     }
 
+    async fn export_snapshot(&self, height: u64) -> anyhow::Result<StateSnapshot> {
+        tracing::info!(height, "exporting state snapshot");
+        let (state, leaf) = load_full_state(&mut self.db.read().await?, height).await?;
+        let leaf_chain = self
+            .db
+            .get_leaf_chain(height)
+            .await
+            .context(format!("loading leaf chain for height {height}"))?;
+        ensure!(
+            leaf_chain.first().map(Committable::commit) == Some(leaf.commit()),
+            "leaf chain does not start at leaf {height}"
+        );
+
+        // The stake table for the first epochs comes from the network config, and is not stored.
+        let epoch_height = self
+            .load_config()
+            .await?
+            .context("network config not found")?
+            .config
+            .epoch_height;
+        let stake_table = match leaf.epoch(epoch_height) {
+            Some(epoch) => self.load_stake(epoch).await?.map(|stake| (epoch, stake)),
+            None => None,
+        };
+
+        // Include the epoch root the stake table was computed from, so it can be checked against
+        // L1 on import.
+        let epoch_root = match &stake_table {
+            Some((epoch, _)) => {
+                let root_height = root_block_in_epoch(epoch.u64().saturating_sub(2), epoch_height);
+                let mut tx = self.db.read().await?;
+                let root = tx
+                    .get_leaf(LeafId::<SeqTypes>::from(root_height as usize))
+                    .await
+                    .context(format!("loading epoch root {root_height}"))?;
+                let proof = load_block_proof(&mut tx, height, root_height).await?;
+                Some((root.header().clone(), proof))
+            },
+            None => None,
+        };
+
+        Ok(StateSnapshot {
+            leaf_chain,
+            state,
+            stake_table,
+            epoch_root,
+        })
+    }
+
+    async fn import_snapshot(&self, snapshot: &StateSnapshot) -> anyhow::Result<()> {
+        let height = snapshot.height();
+        ensure!(height > 0, "importing a genesis snapshot is not supported");
+        // Never replace a stake table we already have with one from an untrusted snapshot.
+        if let Some((epoch, stake)) = &snapshot.stake_table {
+            if let Some(stored) = self.load_stake(*epoch).await? {
+                ensure!(
+                    stored == *stake,
+                    "snapshot stake table for epoch {epoch} does not match the stored one"
+                );
+            }
+        }
+        let qc = snapshot
+            .leaf_chain
+            .get(1)
+            .context("snapshot leaf chain is too short")?
+            .justify_qc();
+        let mut leaf = snapshot.leaf().clone();
+        leaf.unfill_block_payload();
+        let view = leaf.view_number().u64() as i64;
+        tracing::info!(height, view, "importing state snapshot");
+
+        let mut tx = self.db.write().await?;
+
+        // Make the leaves available to the query service, so they can be used for catchup.
+        for (leaf, child) in snapshot.leaf_chain.iter().tuple_windows() {
+            tx.insert_leaf(LeafQueryData::new(leaf.clone(), child.justify_qc())?)
+                .await?;
+        }
+
+        // Store the full state, as if every account was modified by the snapshotted block.
+        let delta = Delta {
+            fees_delta: snapshot
+                .state
+                .fee_merkle_tree
+                .iter()
+                .map(|(account, _)| *account)
+                .collect(),
+            rewards_delta: snapshot
+                .state
+                .reward_merkle_tree
+                .iter()
+                .map(|(account, _)| *account)
+                .collect(),
+            // The snapshot does not include the history of rewards or fees leading up to it.
+            reward_distribution: None,
+            l1_deposits: vec![],
+            fee_transfers: vec![],
+            fee_withdrawals: vec![],
+        };
+        store_state_update(&mut tx, height, &snapshot.state, delta).await?;
+        tx.insert_chain_config(snapshot.chain_config()?).await?;
+
+        // Start consensus from the snapshotted leaf. It is marked as processed, since we do not
+        // have the payload or VID data needed to generate a decide event for it.
+        tx.upsert(
+            "anchor_leaf2",
+            ["view", "leaf", "qc"],
+            ["view"],
+            [(view, bincode::serialize(&leaf)?, bincode::serialize(&qc)?)],
+        )
+        .await?;
+        tx.upsert(
+            "event_stream",
+            ["id", "last_processed_view"],
+            ["id"],
+            [(1i32, view)],
+        )
+        .await?;
+        tx.commit().await?;
+
+        if let Some((epoch, stake)) = &snapshot.stake_table {
+            self.store_stake(*epoch, stake.clone()).await?;
+        }
+        Ok(())
+    }
+
     async fn load_config(&self) -> anyhow::Result<Option<NetworkConfig>> {
         tracing::info!("loading config from Postgres");
 
@@ -1109,42 +1295,35 @@ impl SequencerPersistence for Persistence {
         &self,
         view: ViewNumber,
     ) -> anyhow::Result<Option<Proposal<SeqTypes, DaProposal2<SeqTypes>>>> {
-        let result = self
-            .db
-            .read()
-            .await?
+        let mut tx = self.db.read().await?;
+        let Some(row) = tx
             .fetch_optional(
-                query("SELECT data FROM da_proposal2 where view = $1").bind(view.u64() as i64),
+                query("SELECT data, blob FROM da_proposal2 where view = $1")
+                    .bind(view.u64() as i64),
             )
-            .await?;
-
-        result
-            .map(|row| {
-                let bytes: Vec<u8> = row.get("data");
-                anyhow::Result::<_>::Ok(bincode::deserialize(&bytes)?)
-            })
-            .transpose()
+            .await?
+        else {
+            return Ok(None);
+        };
+        let bytes = load_blob_data(&tx, &row).await?;
+        Ok(Some(bincode::deserialize(&bytes)?))
     }
 
     async fn load_vid_share(
         &self,
         view: ViewNumber,
     ) -> anyhow::Result<Option<Proposal<SeqTypes, VidDisperseShare<SeqTypes>>>> {
-        let result = self
-            .db
-            .read()
-            .await?
+        let mut tx = self.db.read().await?;
+        let Some(row) = tx
             .fetch_optional(
-                query("SELECT data FROM vid_share2 where view = $1").bind(view.u64() as i64),
+                query("SELECT data, blob FROM vid_share2 where view = $1").bind(view.u64() as i64),
             )
-            .await?;
-
-        result
-            .map(|row| {
-                let bytes: Vec<u8> = row.get("data");
-                anyhow::Result::<_>::Ok(bincode::deserialize(&bytes)?)
-            })
-            .transpose()
+            .await?
+        else {
+            return Ok(None);
+        };
+        let bytes = load_blob_data(&tx, &row).await?;
+        Ok(Some(bincode::deserialize(&bytes)?))
     }
 
     async fn load_quorum_proposals(
@@ -1171,6 +1350,59 @@ impl SequencerPersistence for Persistence {
         ))
     }
 
+    async fn load_vid_encoding_evidence(
+        &self,
+    ) -> anyhow::Result<BTreeMap<ViewNumber, VidEncodingEvidence<SeqTypes>>> {
+        let rows = self
+            .db
+            .read()
+            .await?
+            .fetch_all("SELECT view, data FROM vid_encoding_evidence")
+            .await?;
+
+        Ok(BTreeMap::from_iter(
+            rows.into_iter()
+                .map(|row| {
+                    let view: i64 = row.get("view");
+                    let view_number: ViewNumber = ViewNumber::new(view.try_into()?);
+                    let bytes: Vec<u8> = row.get("data");
+                    let evidence = bincode::deserialize(&bytes)?;
+                    Ok((view_number, evidence))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        ))
+    }
+
+    async fn load_equivocation_evidence(
+        &self,
+        from: ViewNumber,
+        to: ViewNumber,
+    ) -> anyhow::Result<BTreeMap<ViewNumber, Vec<EquivocationEvidence<SeqTypes>>>> {
+        let rows = self
+            .db
+            .read()
+            .await?
+            .fetch_all(
+                query(
+                    "SELECT view, data FROM equivocation_evidence WHERE view >= $1 AND view < $2 \
+                     ORDER BY view, offender, kind",
+                )
+                .bind(from.u64() as i64)
+                .bind(to.u64() as i64),
+            )
+            .await?;
+
+        let mut map: BTreeMap<ViewNumber, Vec<_>> = BTreeMap::new();
+        for row in rows {
+            let view: i64 = row.get("view");
+            let view_number = ViewNumber::new(view.try_into()?);
+            let bytes: Vec<u8> = row.get("data");
+            let evidence = bincode::deserialize(&bytes)?;
+            map.entry(view_number).or_default().push(evidence);
+        }
+        Ok(map)
+    }
+
     async fn load_quorum_proposal(
         &self,
         view: ViewNumber,
@@ -1197,11 +1429,12 @@ impl SequencerPersistence for Persistence {
         let data_bytes = bincode::serialize(&proposal).unwrap();
 
         let mut tx = self.db.write().await?;
+        let (data, blob) = blob::offload(tx.blob_store(), data_bytes).await?;
         tx.upsert(
             "vid_share2",
-            ["view", "data", "payload_hash"],
+            ["view", "data", "payload_hash", "blob"],
             ["view"],
-            [(view as i64, data_bytes, payload_hash.to_string())],
+            [(view as i64, data, payload_hash.to_string(), blob)],
         )
         .await?;
         tx.commit().await
@@ -1217,11 +1450,60 @@ impl SequencerPersistence for Persistence {
         let data_bytes = bincode::serialize(&proposal).unwrap();
 
         let mut tx = self.db.write().await?;
+        let (data, blob) = blob::offload(tx.blob_store(), data_bytes).await?;
         tx.upsert(
             "vid_share2",
-            ["view", "data", "payload_hash"],
+            ["view", "data", "payload_hash", "blob"],
             ["view"],
-            [(view as i64, data_bytes, payload_hash.to_string())],
+            [(view as i64, data, payload_hash.to_string(), blob)],
+        )
+        .await?;
+        tx.commit().await
+    }
+
+    async fn append_vid_encoding_evidence(
+        &self,
+        evidence: &VidEncodingEvidence<SeqTypes>,
+    ) -> anyhow::Result<()> {
+        let view = evidence.view_number.u64();
+        let data_bytes =
+            bincode::serialize(evidence).context("serializing VID encoding evidence")?;
+
+        let mut tx = self.db.write().await?;
+        tx.upsert(
+            "vid_encoding_evidence",
+            ["view", "leader", "payload_hash", "data"],
+            ["view"],
+            [(
+                view as i64,
+                evidence.leader.to_string(),
+                evidence.payload_commitment.to_string(),
+                data_bytes,
+            )],
+        )
+        .await?;
+        tx.commit().await
+    }
+
+    async fn append_equivocation_evidence(
+        &self,
+        evidence: &EquivocationEvidence<SeqTypes>,
+    ) -> anyhow::Result<()> {
+        let view = evidence.view_number().u64();
+        let data_bytes =
+            bincode::serialize(evidence).context("serializing equivocation evidence")?;
+
+        let mut tx = self.db.write().await?;
+        tx.upsert(
+            "equivocation_evidence",
+            ["view", "offender", "kind", "data"],
+            ["view", "offender", "kind"],
+            [(
+                view as i64,
+                evidence.offender().to_string(),
+                evidence.kind().to_string(),
+                data_bytes,
+            )],
         )
         .await?;
         tx.commit().await
@@ -1884,11 +2166,12 @@ impl SequencerPersistence for Persistence {
         let data_bytes = bincode::serialize(proposal).unwrap();
 
         let mut tx = self.db.write().await?;
+        let (data, blob) = blob::offload(tx.blob_store(), data_bytes).await?;
         tx.upsert(
             "da_proposal2",
-            ["view", "data", "payload_hash"],
+            ["view", "data", "payload_hash", "blob"],
             ["view"],
-            [(view as i64, data_bytes, vid_commit.to_string())],
+            [(view as i64, data, vid_commit.to_string(), blob)],
         )
         .await?;
         tx.commit().await
@@ -2078,6 +2361,80 @@ impl MembershipPersistence for Persistence {
         .await?;
         tx.commit().await
     }
+
+    async fn load_events(&self) -> anyhow::Result<Option<(u64, Vec<IndexedStakeTableEvent>)>> {
+        let mut tx = self.db.read().await?;
+
+        let Some((l1_block,)) = query_as::<(i64,)>(
+            "SELECT last_l1_block FROM stake_table_events_l1_block WHERE id = true",
+        )
+        .fetch_optional(tx.as_mut())
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let rows = query_as::<(i64, i64, Vec<u8>)>(
+            "SELECT l1_block, log_index, event FROM stake_table_events
+              ORDER BY l1_block, log_index",
+        )
+        .fetch_all(tx.as_mut())
+        .await?;
+        let events = rows
+            .into_iter()
+            .map(|(block, log_index, bytes)| -> anyhow::Result<_> {
+                let event =
+                    bincode::deserialize(&bytes).context("deserializing stake table event")?;
+                Ok(((block as u64, log_index as u64), event))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Some((l1_block as u64, events)))
+    }
+
+    async fn store_events(
+        &self,
+        l1_block: u64,
+        events: Vec<IndexedStakeTableEvent>,
+    ) -> anyhow::Result<()> {
+        let mut tx = self.db.write().await?;
+
+        let rows = events
+            .into_iter()
+            .map(|((block, log_index), event)| -> anyhow::Result<_> {
+                let bytes = bincode::serialize(&event).context("serializing stake table event")?;
+                Ok((block as i64, log_index as i64, bytes))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        // Insert in batches to stay within the database's limit on query parameters.
+        for chunk in &rows.into_iter().chunks(1000) {
+            tx.upsert(
+                "stake_table_events",
+                ["l1_block", "log_index", "event"],
+                ["l1_block", "log_index"],
+                chunk,
+            )
+            .await?;
+        }
+
+        // Never move the last processed L1 block backwards.
+        let stored = query_as::<(i64,)>(
+            "SELECT last_l1_block FROM stake_table_events_l1_block WHERE id = true",
+        )
+        .fetch_optional(tx.as_mut())
+        .await?;
+        let l1_block = match stored {
+            Some((stored,)) => l1_block.max(stored as u64),
+            None => l1_block,
+        };
+        tx.upsert(
+            "stake_table_events_l1_block",
+            ["id", "last_l1_block"],
+            ["id"],
+            [(true, l1_block as i64)],
+        )
+        .await?;
+        tx.commit().await
+    }
 }
 
 #[async_trait]
@@ -2092,20 +2449,27 @@ impl Provider<SeqTypes, VidCommonRequest> for Persistence {
             },
         };
 
-        let bytes = match query_as::<(Vec<u8>,)>(
-            "SELECT data FROM vid_share2 WHERE payload_hash = $1 LIMIT 1",
-        )
-        .bind(req.0.to_string())
-        .fetch_optional(tx.as_mut())
-        .await
+        let row = match tx
+            .fetch_optional(
+                query("SELECT data, blob FROM vid_share2 WHERE payload_hash = $1 LIMIT 1")
+                    .bind(req.0.to_string()),
+            )
+            .await
         {
-            Ok(Some((bytes,))) => bytes,
+            Ok(Some(row)) => row,
             Ok(None) => return None,
             Err(err) => {
                 tracing::error!("error loading VID share: {err:#}");
                 return None;
             },
         };
+        let bytes = match load_blob_data(&tx, &row).await {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                tracing::warn!("error loading VID share from blob store: {err:#}");
+                return None;
+            },
+        };
 
         let share: Proposal<SeqTypes, VidDisperseShare<SeqTypes>> =
             match bincode::deserialize(&bytes) {
@@ -2135,20 +2499,27 @@ impl Provider<SeqTypes, PayloadRequest> for Persistence {
             },
         };
 
-        let bytes = match query_as::<(Vec<u8>,)>(
-            "SELECT data FROM da_proposal2 WHERE payload_hash = $1 LIMIT 1",
-        )
-        .bind(req.0.to_string())
-        .fetch_optional(tx.as_mut())
-        .await
+        let row = match tx
+            .fetch_optional(
+                query("SELECT data, blob FROM da_proposal2 WHERE payload_hash = $1 LIMIT 1")
+                    .bind(req.0.to_string()),
+            )
+            .await
         {
-            Ok(Some((bytes,))) => bytes,
+            Ok(Some(row)) => row,
             Ok(None) => return None,
             Err(err) => {
                 tracing::warn!("error loading DA proposal: {err:#}");
                 return None;
             },
         };
+        let bytes = match load_blob_data(&tx, &row).await {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                tracing::warn!("error loading DA proposal from blob store: {err:#}");
+                return None;
+            },
+        };
 
         let proposal: Proposal<SeqTypes, DaProposal2<SeqTypes>> = match bincode::deserialize(&bytes)
         {
@@ -2231,6 +2602,17 @@ async fn fetch_leaf_from_proposals<Mode: TransactionMode>(
     Ok(Some((leaf, qc)))
 }
 
+/// Load the `data` column of a DA proposal or VID share row.
+///
+/// If the data was moved to the blob store when it was inserted, it is loaded from there, using
+/// the reference in the `blob` column.
+async fn load_blob_data<Mode: TransactionMode>(
+    tx: &Transaction<Mode>,
+    row: &<Db as Database>::Row,
+) -> anyhow::Result<Vec<u8>> {
+    blob::resolve(tx.blob_store(), row.get("data"), row.get("blob")).await
+}
+
 #[cfg(test)]
 mod testing {
     use hotshot_query_service::data_source::storage::sql::testing::TmpDb;
@@ -2307,6 +2689,7 @@ mod test {
     };
     use jf_vid::VidScheme;
     use sequencer_utils::test_utils::setup_test;
+    use tempfile::TempDir;
     use vbs::version::StaticVersionType;
 
     use super::*;
@@ -2398,7 +2781,22 @@ mod test {
         setup_test();
 
         let tmp = Persistence::tmp_storage().await;
-        let storage = Persistence::connect(&tmp).await;
+        test_fetching_providers_helper(Persistence::options(&tmp)).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_fetching_providers_blob_store() {
+        setup_test();
+
+        let tmp = Persistence::tmp_storage().await;
+        let blob_dir = TempDir::new().unwrap();
+        let mut opt = Persistence::options(&tmp);
+        opt.blob_store_path = Some(blob_dir.path().into());
+        test_fetching_providers_helper(opt).await;
+    }
+
+    async fn test_fetching_providers_helper(mut opt: Options) {
+        let storage = opt.create().await.unwrap();
 
         // Mock up some data.
         let leaf =
@@ -2497,6 +2895,40 @@ mod test {
             .await
             .unwrap();
 
+        // The DA proposal and VID share are stored inline unless there is a blob store.
+        let mut tx = storage.db.read().await.unwrap();
+        for table in ["da_proposal2", "vid_share2"] {
+            let (data, blob) =
+                query_as::<(Vec<u8>, Option<String>)>(&format!("SELECT data, blob FROM {table}"))
+                    .fetch_one(tx.as_mut())
+                    .await
+                    .unwrap();
+            if opt.blob_store_path.is_some() {
+                assert!(data.is_empty(), "{table} stored inline");
+                assert!(blob.is_some(), "{table} missing blob reference");
+            } else {
+                assert!(!data.is_empty(), "{table} not stored inline");
+                assert_eq!(blob, None);
+            }
+        }
+        drop(tx);
+        assert_eq!(
+            storage
+                .load_da_proposal(ViewNumber::new(0))
+                .await
+                .unwrap()
+                .unwrap(),
+            da_proposal
+        );
+        assert_eq!(
+            storage
+                .load_vid_share(ViewNumber::new(0))
+                .await
+                .unwrap()
+                .unwrap(),
+            convert_proposal(vid_share.clone())
+        );
+
         // Fetch it as if we were rebuilding an archive.
         assert_eq!(
             Some(VidCommon::V1(avidm_param)),
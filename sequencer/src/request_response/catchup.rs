@@ -0,0 +1,183 @@
+//! This file contains a [`StateCatchup`] implementation backed by the request-response protocol.
+//! It lets a node catch up from the other members of the consensus network, without needing any
+//! public HTTP peers.
+
+use std::{
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use committable::Commitment;
+use espresso_types::{
+    v0::traits::StateCatchup,
+    v0_1::{RewardAccount, RewardMerkleCommitment, RewardMerkleTree},
+    v0_99::ChainConfig,
+    BackoffParams, BlockMerkleTree, FeeAccount, FeeMerkleCommitment, FeeMerkleTree, Leaf2,
+    NodeState, PubKey,
+};
+use hotshot_types::{data::ViewNumber, traits::signature_key::SignatureKey};
+use jf_merkle_tree::{ForgetableMerkleTreeScheme, MerkleTreeScheme};
+use request_response::message::RequestMessage;
+
+use super::{
+    request::{Request, Response},
+    RequestResponseProtocol,
+};
+
+/// A catchup provider which fetches state from other consensus nodes using the request-response
+/// protocol.
+///
+/// The protocol is only available once consensus has been initialized, which happens after the
+/// catchup providers are constructed. Until [`set_protocol`](Self::set_protocol) is called, all
+/// requests fail immediately, so that other providers can be tried instead.
+#[derive(Clone)]
+pub struct RequestResponseStateCatchup {
+    protocol: Arc<OnceLock<RequestResponseProtocol>>,
+    public_key: PubKey,
+    private_key: <PubKey as SignatureKey>::PrivateKey,
+    request_timeout: Duration,
+    backoff: BackoffParams,
+}
+
+impl RequestResponseStateCatchup {
+    /// Create a catchup provider.
+    ///
+    /// `request_timeout` is the timeout for the first attempt at each request, which is relaxed on
+    /// each successive retry.
+    pub fn new(
+        public_key: PubKey,
+        private_key: <PubKey as SignatureKey>::PrivateKey,
+        request_timeout: Duration,
+        backoff: BackoffParams,
+    ) -> Self {
+        Self {
+            protocol: Default::default(),
+            public_key,
+            private_key,
+            request_timeout,
+            backoff,
+        }
+    }
+
+    /// Start using the given request-response protocol to make requests.
+    ///
+    /// This can only be done once. Subsequent calls are ignored.
+    pub fn set_protocol(&self, protocol: RequestResponseProtocol) {
+        if self.protocol.set(protocol).is_err() {
+            tracing::warn!("request-response catchup protocol already set");
+        }
+    }
+
+    /// Sign and send a request, waiting for a valid response
+    async fn request(&self, retry: usize, request: Request) -> Result<Response> {
+        let protocol = self
+            .protocol
+            .get()
+            .context("request-response protocol has not been started yet")?;
+
+        // Sign the request
+        let request_message =
+            RequestMessage::new_signed(&self.public_key, &self.private_key, &request)
+                .with_context(|| "failed to sign request")?;
+
+        // Make the request. The response has already been validated against the request by the
+        // time it gets back to us. As with HTTP catchup, we start with a fairly aggressive timeout
+        // and relax it on each successive retry.
+        let timeout = self.request_timeout * (retry as u32 + 1);
+        protocol
+            .request(request_message, timeout)
+            .await
+            .map_err(|err| anyhow!("request {request:?} failed: {err:#}"))
+    }
+}
+
+#[async_trait]
+impl StateCatchup for RequestResponseStateCatchup {
+    async fn try_fetch_leaves(&self, retry: usize, height: u64) -> Result<Vec<Leaf2>> {
+        match self.request(retry, Request::LeafChain(height)).await? {
+            Response::LeafChain(leaves) => Ok(leaves),
+            _ => bail!("unexpected response type for leaf chain request"),
+        }
+    }
+
+    #[tracing::instrument(skip(self, _instance))]
+    async fn try_fetch_accounts(
+        &self,
+        retry: usize,
+        _instance: &NodeState,
+        height: u64,
+        view: ViewNumber,
+        fee_merkle_tree_root: FeeMerkleCommitment,
+        accounts: &[FeeAccount],
+    ) -> Result<FeeMerkleTree> {
+        let request = Request::Accounts(height, view, fee_merkle_tree_root, accounts.to_vec());
+        match self.request(retry, request).await? {
+            Response::Accounts(tree) => Ok(tree),
+            _ => bail!("unexpected response type for accounts request"),
+        }
+    }
+
+    #[tracing::instrument(skip(self, _instance, mt))]
+    async fn try_remember_blocks_merkle_tree(
+        &self,
+        retry: usize,
+        _instance: &NodeState,
+        height: u64,
+        view: ViewNumber,
+        mt: &mut BlockMerkleTree,
+    ) -> Result<()> {
+        let request = Request::BlocksFrontier(height, view, mt.commitment());
+        let frontier = match self.request(retry, request).await? {
+            Response::BlocksFrontier(frontier) => frontier,
+            _ => bail!("unexpected response type for blocks frontier request"),
+        };
+        let elem = frontier
+            .elem()
+            .context("provided frontier is missing leaf element")?;
+        mt.remember(mt.num_leaves() - 1, *elem, &frontier)
+            .context("verifying block proof")?;
+        Ok(())
+    }
+
+    async fn try_fetch_chain_config(
+        &self,
+        retry: usize,
+        commitment: Commitment<ChainConfig>,
+    ) -> Result<ChainConfig> {
+        match self
+            .request(retry, Request::ChainConfig(commitment))
+            .await?
+        {
+            Response::ChainConfig(cf) => Ok(cf),
+            _ => bail!("unexpected response type for chain config request"),
+        }
+    }
+
+    #[tracing::instrument(skip(self, _instance))]
+    async fn try_fetch_reward_accounts(
+        &self,
+        retry: usize,
+        _instance: &NodeState,
+        height: u64,
+        view: ViewNumber,
+        reward_merkle_tree_root: RewardMerkleCommitment,
+        accounts: &[RewardAccount],
+    ) -> Result<RewardMerkleTree> {
+        let request =
+            Request::RewardAccounts(height, view, reward_merkle_tree_root, accounts.to_vec());
+        match self.request(retry, request).await? {
+            Response::RewardAccounts(tree) => Ok(tree),
+            _ => bail!("unexpected response type for reward accounts request"),
+        }
+    }
+
+    fn backoff(&self) -> &BackoffParams {
+        &self.backoff
+    }
+
+    fn name(&self) -> String {
+        "RequestResponseStateCatchup".into()
+    }
+}
@@ -2,21 +2,178 @@
 //! to calculate/derive a response for a specific request. In the confirmation layer the implementer
 //! would be something like a [`FeeMerkleTree`] for fee catchup
 
-use anyhow::Result;
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
+use committable::{Commitment, Committable};
+use espresso_types::{
+    retain_accounts, retain_reward_accounts,
+    v0::traits::StateCatchup,
+    v0_1::{RewardAccount, RewardMerkleCommitment, RewardMerkleTree},
+    v0_99::ChainConfig,
+    BlockMerkleCommitment, BlockMerkleTree, FeeAccount, FeeMerkleCommitment, FeeMerkleTree, Leaf2,
+    NodeState, SeqTypes, ValidatedState,
+};
+use hotshot_types::{consensus::LockedConsensusState, data::ViewNumber};
+use jf_merkle_tree::{ForgetableMerkleTreeScheme, MerkleTreeScheme};
 use request_response::data_source::DataSource as DataSourceTrait;
 
 use super::request::{Request, Response};
+use crate::{api::BlocksFrontier, catchup::leaf_chain_from_undecided};
 
-#[derive(Clone, Debug)]
-pub struct DataSource {}
+/// The data source for the request-response protocol. Responses are derived from the in-memory
+/// consensus state if possible, falling back to local storage.
+#[derive(Clone)]
+pub struct DataSource {
+    /// The consensus state, which holds recent (possibly undecided) validated states and leaves
+    pub consensus: LockedConsensusState<SeqTypes>,
+    /// Our own node state, required by storage lookups
+    pub node_state: NodeState,
+    /// Catchup from local storage, if supported by our persistence backend
+    pub storage: Option<Arc<dyn StateCatchup>>,
+}
 
 /// Implement the trait that allows the [`RequestResponseProtocol`] to calculate/derive a response for a specific request
 #[async_trait]
 impl DataSourceTrait<Request> for DataSource {
     async fn derive_response_for(&self, request: &Request) -> Result<Response> {
         match request {
-            Request::Example => Ok(Response::Example),
+            Request::Accounts(height, view, root, accounts) => self
+                .get_accounts(*height, *view, *root, accounts)
+                .await
+                .map(Response::Accounts),
+            Request::RewardAccounts(height, view, root, accounts) => self
+                .get_reward_accounts(*height, *view, *root, accounts)
+                .await
+                .map(Response::RewardAccounts),
+            Request::BlocksFrontier(height, view, root) => self
+                .get_frontier(*height, *view, *root)
+                .await
+                .map(Response::BlocksFrontier),
+            Request::ChainConfig(commitment) => self
+                .get_chain_config(*commitment)
+                .await
+                .map(Response::ChainConfig),
+            Request::LeafChain(height) => {
+                self.get_leaf_chain(*height).await.map(Response::LeafChain)
+            },
+        }
+    }
+}
+
+impl DataSource {
+    /// Get the validated state for `view` from memory, if we have it
+    async fn state(&self, view: ViewNumber) -> Option<Arc<ValidatedState>> {
+        self.consensus.read().await.state(view).cloned()
+    }
+
+    /// Get the local storage catchup provider, failing if there is none
+    fn storage(&self) -> Result<&Arc<dyn StateCatchup>> {
+        self.storage
+            .as_ref()
+            .context("state is not in memory, and local storage does not support catchup")
+    }
+
+    async fn get_accounts(
+        &self,
+        height: u64,
+        view: ViewNumber,
+        root: FeeMerkleCommitment,
+        accounts: &[FeeAccount],
+    ) -> Result<FeeMerkleTree> {
+        // Check if we have the desired state in memory.
+        if let Some(state) = self.state(view).await {
+            match retain_accounts(&state.fee_merkle_tree, accounts.iter().copied()) {
+                Ok(tree) => return Ok(tree),
+                Err(err) => {
+                    tracing::info!("accounts not in memory, trying storage: {err:#}");
+                },
+            }
+        }
+
+        // Try storage.
+        self.storage()?
+            .try_fetch_accounts(0, &self.node_state, height, view, root, accounts)
+            .await
+    }
+
+    async fn get_reward_accounts(
+        &self,
+        height: u64,
+        view: ViewNumber,
+        root: RewardMerkleCommitment,
+        accounts: &[RewardAccount],
+    ) -> Result<RewardMerkleTree> {
+        // Check if we have the desired state in memory.
+        if let Some(state) = self.state(view).await {
+            match retain_reward_accounts(&state.reward_merkle_tree, accounts.iter().copied()) {
+                Ok(tree) => return Ok(tree),
+                Err(err) => {
+                    tracing::info!("reward accounts not in memory, trying storage: {err:#}");
+                },
+            }
+        }
+
+        // Try storage.
+        self.storage()?
+            .try_fetch_reward_accounts(0, &self.node_state, height, view, root, accounts)
+            .await
+    }
+
+    async fn get_frontier(
+        &self,
+        height: u64,
+        view: ViewNumber,
+        root: BlockMerkleCommitment,
+    ) -> Result<BlocksFrontier> {
+        // Check if we have the desired state in memory.
+        let tree = match self.state(view).await {
+            Some(state) => state.block_merkle_tree.clone(),
+            None => {
+                // Try storage. Storage remembers the frontier in a tree, from which we can then
+                // read it back out.
+                let mut tree = BlockMerkleTree::from_commitment(root);
+                self.storage()?
+                    .try_remember_blocks_merkle_tree(0, &self.node_state, height, view, &mut tree)
+                    .await?;
+                tree
+            },
+        };
+        let frontier = tree.lookup(tree.num_leaves() - 1).expect_ok()?.1;
+        Ok(frontier)
+    }
+
+    async fn get_chain_config(&self, commitment: Commitment<ChainConfig>) -> Result<ChainConfig> {
+        // Check our own chain config and the decided state first.
+        if self.node_state.chain_config.commit() == commitment {
+            return Ok(self.node_state.chain_config);
+        }
+        let state = self.consensus.read().await.decided_state();
+        if state.chain_config.commit() == commitment {
+            if let Some(cf) = state.chain_config.resolve() {
+                return Ok(cf);
+            }
+        }
+
+        // Try storage.
+        self.storage()?.try_fetch_chain_config(0, commitment).await
+    }
+
+    async fn get_leaf_chain(&self, height: u64) -> Result<Vec<Leaf2>> {
+        // Check if we have the desired leaves in memory.
+        let leaves = self.consensus.read().await.undecided_leaves();
+        match leaf_chain_from_undecided(leaves, height) {
+            Ok(chain) => return Ok(chain),
+            Err(err) => {
+                tracing::info!("leaf chain is not in memory, trying storage: {err:#}");
+            },
+        }
+
+        // Try storage.
+        match &self.storage {
+            Some(storage) => storage.try_fetch_leaves(0, height).await,
+            None => bail!("leaf chain not available for {height}"),
         }
     }
 }
@@ -1,4 +1,19 @@
+use espresso_types::PubKey;
+use request_response::{network::Bytes, RequestResponse};
+use tokio::sync::mpsc::Receiver;
+
+pub mod catchup;
 pub mod data_source;
 pub mod network;
 pub mod recipient_source;
 pub mod request;
+
+/// The request-response protocol, as instantiated by the sequencer
+pub type RequestResponseProtocol = RequestResponse<
+    network::Sender,
+    Receiver<Bytes>,
+    request::Request,
+    recipient_source::RecipientSource,
+    data_source::DataSource,
+    PubKey,
+>;
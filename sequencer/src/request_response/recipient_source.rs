@@ -9,6 +9,7 @@ use hotshot_types::{
         election::Membership,
         node_implementation::{ConsensusTime, NodeType},
     },
+    utils::epoch_from_block_number,
 };
 use request_response::recipient_source::RecipientSource as RecipientSourceTrait;
 
@@ -17,6 +18,23 @@ use super::request::Request;
 #[derive(Clone, Debug)]
 pub struct RecipientSource {
     pub memberships: Arc<RwLock<<SeqTypes as NodeType>::Membership>>,
+    pub epoch_height: u64,
+}
+
+impl RecipientSource {
+    /// Get the epoch whose stake table should be asked about the given block height, if we know
+    /// the stake table for that epoch.
+    fn epoch_for_height(
+        &self,
+        memberships: &<SeqTypes as NodeType>::Membership,
+        height: u64,
+    ) -> Option<EpochNumber> {
+        if self.epoch_height == 0 {
+            return None;
+        }
+        let epoch = EpochNumber::new(epoch_from_block_number(height, self.epoch_height));
+        memberships.has_stake_table(epoch).then_some(epoch)
+    }
 }
 
 /// Implement the RecipientSourceTrait, which allows the request-response protocol to derive the
@@ -24,21 +42,25 @@ pub struct RecipientSource {
 #[async_trait]
 impl RecipientSourceTrait<Request, PubKey> for RecipientSource {
     async fn get_expected_responders(&self, request: &Request) -> Vec<PubKey> {
-        match request {
-            Request::Example => {
-                // Get the memberships
-                let memberships = self.memberships.read().await;
+        // Get the memberships
+        let memberships = self.memberships.read().await;
 
-                // Get everyone in the stake table
+        // Requests for state at a given height are best answered by the stake table of the epoch
+        // containing that height. If we don't know that stake table (which is likely, if we are
+        // catching up), fall back to the genesis stake table.
+        let epoch = match request {
+            Request::Accounts(height, ..)
+            | Request::RewardAccounts(height, ..)
+            | Request::BlocksFrontier(height, ..)
+            | Request::LeafChain(height) => self.epoch_for_height(&memberships, *height),
+            Request::ChainConfig(_) => None,
+        };
 
-                // NOTE: This is just an example request, therefore this is hardcoded to get members of epoch 0.
-                // When doing a real request, please choose the appropriate epoch to get members from.
-                memberships
-                    .stake_table(Some(EpochNumber::new(0)))
-                    .iter()
-                    .map(|entry| entry.stake_table_entry.stake_key)
-                    .collect()
-            },
-        }
+        // Get everyone in the stake table
+        memberships
+            .stake_table(epoch)
+            .iter()
+            .map(|entry| entry.stake_table_entry.stake_key)
+            .collect()
     }
 }
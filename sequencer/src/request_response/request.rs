@@ -1,18 +1,46 @@
 use std::io::Cursor;
 
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 use async_trait::async_trait;
 use byteorder::ReadBytesExt;
+use committable::{Commitment, Committable};
+use espresso_types::{
+    v0_1::{RewardAccount, RewardAccountProof, RewardMerkleCommitment, RewardMerkleTree},
+    v0_99::ChainConfig,
+    BlockMerkleCommitment, BlockMerkleTree, FeeAccount, FeeAccountProof, FeeMerkleCommitment,
+    FeeMerkleTree, Leaf2,
+};
+use hotshot_types::{data::ViewNumber, traits::node_implementation::ConsensusTime as _};
+use jf_merkle_tree::{ForgetableMerkleTreeScheme, MerkleTreeScheme};
 use request_response::{
     request::{Request as RequestTrait, Response as ResponseTrait},
     Serializable,
 };
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::api::BlocksFrontier;
+
+/// The maximum number of accounts that can be requested in a single fee or reward account request.
+/// This bounds the amount of work a responder has to do for a single request.
+pub const MAX_ACCOUNTS_PER_REQUEST: usize = 1000;
 
 /// The outermost request type. This an enum that contains all the possible requests that the
 /// sequencer can make.
 #[derive(Debug, Clone)]
 pub enum Request {
-    Example,
+    /// Request the fee accounts at the given height and view, proven against the given fee Merkle
+    /// tree root
+    Accounts(u64, ViewNumber, FeeMerkleCommitment, Vec<FeeAccount>),
+    /// Request the reward accounts at the given height and view, proven against the given reward
+    /// Merkle tree root
+    RewardAccounts(u64, ViewNumber, RewardMerkleCommitment, Vec<RewardAccount>),
+    /// Request the blocks frontier at the given height and view, proven against the given block
+    /// Merkle tree root
+    BlocksFrontier(u64, ViewNumber, BlockMerkleCommitment),
+    /// Request the chain config with the given commitment
+    ChainConfig(Commitment<ChainConfig>),
+    /// Request the chain of leaves needed to prove that the leaf at the given height was decided
+    LeafChain(u64),
 }
 
 /// Implement the `RequestTrait` trait for the `Request` type. This tells the request response
@@ -22,6 +50,28 @@ impl RequestTrait for Request {
     type Response = Response;
 
     async fn validate(&self) -> Result<()> {
+        match self {
+            Request::Accounts(_, _, _, accounts) => {
+                ensure!(!accounts.is_empty(), "no accounts requested");
+                ensure!(
+                    accounts.len() <= MAX_ACCOUNTS_PER_REQUEST,
+                    "too many accounts requested: {}",
+                    accounts.len()
+                );
+            },
+            Request::RewardAccounts(_, _, _, accounts) => {
+                ensure!(!accounts.is_empty(), "no reward accounts requested");
+                ensure!(
+                    accounts.len() <= MAX_ACCOUNTS_PER_REQUEST,
+                    "too many reward accounts requested: {}",
+                    accounts.len()
+                );
+            },
+            Request::BlocksFrontier(height, _, _) => {
+                ensure!(*height > 0, "there is no blocks frontier at height 0");
+            },
+            Request::ChainConfig(_) | Request::LeafChain(_) => {},
+        }
         Ok(())
     }
 }
@@ -30,7 +80,16 @@ impl RequestTrait for Request {
 /// sequencer can make.
 #[derive(Debug, Clone)]
 pub enum Response {
-    Example,
+    /// A fee Merkle tree snapshot containing (at least) the requested accounts
+    Accounts(FeeMerkleTree),
+    /// A reward Merkle tree snapshot containing (at least) the requested accounts
+    RewardAccounts(RewardMerkleTree),
+    /// A proof of the last leaf in the block Merkle tree
+    BlocksFrontier(BlocksFrontier),
+    /// The requested chain config
+    ChainConfig(ChainConfig),
+    /// A chain of leaves starting at the requested height
+    LeafChain(Vec<Leaf2>),
 }
 
 /// Implement the `ResponseTrait` trait for the `Response` type. This tells the request response
@@ -40,17 +99,108 @@ impl ResponseTrait<Request> for Response {
     async fn validate(&self, request: &Request) -> Result<()> {
         // Match the type of the response and request
         match (self, request) {
-            (Response::Example, Request::Example) => Ok(()),
+            (Response::Accounts(tree), Request::Accounts(_, _, root, accounts)) => {
+                ensure!(
+                    tree.commitment() == *root,
+                    "fee merkle tree root mismatch: expected {root}, got {}",
+                    tree.commitment()
+                );
+                for account in accounts {
+                    let (proof, _) = FeeAccountProof::prove(tree, (*account).into())
+                        .context(format!("response missing account {account}"))?;
+                    proof
+                        .verify(root)
+                        .context(format!("invalid proof for account {account}"))?;
+                }
+                Ok(())
+            },
+            (Response::RewardAccounts(tree), Request::RewardAccounts(_, _, root, accounts)) => {
+                ensure!(
+                    tree.commitment() == *root,
+                    "reward merkle tree root mismatch: expected {root}, got {}",
+                    tree.commitment()
+                );
+                for account in accounts {
+                    let (proof, _) = RewardAccountProof::prove(tree, (*account).into())
+                        .context(format!("response missing reward account {account}"))?;
+                    proof
+                        .verify(root)
+                        .context(format!("invalid proof for reward account {account}"))?;
+                }
+                Ok(())
+            },
+            (Response::BlocksFrontier(frontier), Request::BlocksFrontier(_, _, root)) => {
+                let mut mt = BlockMerkleTree::from_commitment(root);
+                let elem = frontier
+                    .elem()
+                    .context("provided frontier is missing leaf element")?;
+                mt.remember(mt.num_leaves() - 1, *elem, frontier)
+                    .context("verifying block proof")?;
+                Ok(())
+            },
+            (Response::ChainConfig(cf), Request::ChainConfig(commitment)) => {
+                ensure!(
+                    cf.commit() == *commitment,
+                    "received chain config with mismatched commitment: expected {commitment}, got {}",
+                    cf.commit()
+                );
+                Ok(())
+            },
+            (Response::LeafChain(leaves), Request::LeafChain(height)) => {
+                // The decide at the end of the chain is verified against the stake table by the
+                // requester (see `StateCatchup::fetch_leaf`), which only holds for the requested
+                // leaf if every leaf extends the one before it.
+                let first = leaves.first().context("received empty leaf chain")?;
+                ensure!(
+                    first.height() == *height,
+                    "leaf chain starts at height {} instead of {height}",
+                    first.height()
+                );
+                for (parent, leaf) in leaves.iter().zip(leaves.iter().skip(1)) {
+                    ensure!(
+                        leaf.parent_commitment() == parent.commit(),
+                        "leaf at view {:?} does not extend the leaf at view {:?}",
+                        leaf.view_number(),
+                        parent.view_number()
+                    );
+                }
+                Ok(())
+            },
+            _ => Err(anyhow::anyhow!("response type does not match request type")),
         }
     }
 }
 
+/// Serialize `payload` prefixed with a single byte identifying its type
+fn to_bytes_with_type<T: Serialize>(type_id: u8, payload: &T) -> Result<Vec<u8>> {
+    let mut bytes = vec![type_id];
+    bytes.extend(bincode::serialize(payload).with_context(|| "failed to serialize payload")?);
+    Ok(bytes)
+}
+
+/// Deserialize the payload that remains in `cursor` after its type byte has been read
+fn payload_from_cursor<T: DeserializeOwned>(cursor: Cursor<&[u8]>) -> Result<T> {
+    let position = cursor.position() as usize;
+    bincode::deserialize(&cursor.into_inner()[position..])
+        .with_context(|| "failed to deserialize payload")
+}
+
 /// Implement the `Serializable` trait for the `Request` type. This tells the request response
 /// protocol how to serialize and deserialize the request
 impl Serializable for Request {
     fn to_bytes(&self) -> Result<Vec<u8>> {
         match self {
-            Request::Example => Ok(vec![0]),
+            Request::Accounts(height, view, root, accounts) => {
+                to_bytes_with_type(0, &(height, view.u64(), root, accounts))
+            },
+            Request::RewardAccounts(height, view, root, accounts) => {
+                to_bytes_with_type(1, &(height, view.u64(), root, accounts))
+            },
+            Request::BlocksFrontier(height, view, root) => {
+                to_bytes_with_type(2, &(height, view.u64(), root))
+            },
+            Request::ChainConfig(commitment) => to_bytes_with_type(3, commitment),
+            Request::LeafChain(height) => to_bytes_with_type(4, height),
         }
     }
 
@@ -63,7 +213,30 @@ impl Serializable for Request {
 
         // Deserialize the request based on the type
         match request_type {
-            0 => Ok(Request::Example),
+            0 => {
+                let (height, view, root, accounts) = payload_from_cursor(cursor)?;
+                Ok(Request::Accounts(
+                    height,
+                    ViewNumber::new(view),
+                    root,
+                    accounts,
+                ))
+            },
+            1 => {
+                let (height, view, root, accounts) = payload_from_cursor(cursor)?;
+                Ok(Request::RewardAccounts(
+                    height,
+                    ViewNumber::new(view),
+                    root,
+                    accounts,
+                ))
+            },
+            2 => {
+                let (height, view, root) = payload_from_cursor(cursor)?;
+                Ok(Request::BlocksFrontier(height, ViewNumber::new(view), root))
+            },
+            3 => Ok(Request::ChainConfig(payload_from_cursor(cursor)?)),
+            4 => Ok(Request::LeafChain(payload_from_cursor(cursor)?)),
             _ => Err(anyhow::anyhow!("invalid request type")),
         }
     }
@@ -74,7 +247,11 @@ impl Serializable for Request {
 impl Serializable for Response {
     fn to_bytes(&self) -> Result<Vec<u8>> {
         match self {
-            Response::Example => Ok(vec![0]),
+            Response::Accounts(tree) => to_bytes_with_type(0, tree),
+            Response::RewardAccounts(tree) => to_bytes_with_type(1, tree),
+            Response::BlocksFrontier(frontier) => to_bytes_with_type(2, frontier),
+            Response::ChainConfig(cf) => to_bytes_with_type(3, cf),
+            Response::LeafChain(leaves) => to_bytes_with_type(4, leaves),
         }
     }
 
@@ -87,8 +264,141 @@ impl Serializable for Response {
 
         // Deserialize the response based on the type
         match response_type {
-            0 => Ok(Response::Example),
+            0 => Ok(Response::Accounts(payload_from_cursor(cursor)?)),
+            1 => Ok(Response::RewardAccounts(payload_from_cursor(cursor)?)),
+            2 => Ok(Response::BlocksFrontier(payload_from_cursor(cursor)?)),
+            3 => Ok(Response::ChainConfig(payload_from_cursor(cursor)?)),
+            4 => Ok(Response::LeafChain(payload_from_cursor(cursor)?)),
             _ => Err(anyhow::anyhow!("invalid response type")),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use espresso_types::{
+        FeeAmount, MockSequencerVersions, NodeState, SeqTypes, ValidatedState,
+        BLOCK_MERKLE_TREE_HEIGHT, FEE_MERKLE_TREE_HEIGHT,
+    };
+    use hotshot_types::{
+        data::{QuorumProposal2, QuorumProposalWrapper},
+        simple_certificate::QuorumCertificate2,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_request_serialization_round_trip() {
+        let account = FeeAccount::default();
+        let tree =
+            FeeMerkleTree::from_kv_set(FEE_MERKLE_TREE_HEIGHT, [(account, FeeAmount::from(1))])
+                .unwrap();
+        let chain_config = ChainConfig::default();
+
+        let requests = [
+            Request::Accounts(1, ViewNumber::new(2), tree.commitment(), vec![account]),
+            Request::BlocksFrontier(
+                1,
+                ViewNumber::new(2),
+                BlockMerkleTree::new(BLOCK_MERKLE_TREE_HEIGHT).commitment(),
+            ),
+            Request::ChainConfig(chain_config.commit()),
+            Request::LeafChain(10),
+        ];
+        for request in requests {
+            let bytes = request.to_bytes().unwrap();
+            let decoded = Request::from_bytes(&bytes).unwrap();
+            assert_eq!(bytes, decoded.to_bytes().unwrap());
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_response_validation() {
+        let account = FeeAccount::default();
+        let tree =
+            FeeMerkleTree::from_kv_set(FEE_MERKLE_TREE_HEIGHT, [(account, FeeAmount::from(1))])
+                .unwrap();
+        let request = Request::Accounts(1, ViewNumber::new(2), tree.commitment(), vec![account]);
+
+        // A snapshot of the right tree is valid.
+        let response = Response::Accounts(tree.clone());
+        response.validate(&request).await.unwrap();
+
+        // A snapshot of a different tree is rejected.
+        let other =
+            FeeMerkleTree::from_kv_set(FEE_MERKLE_TREE_HEIGHT, [(account, FeeAmount::from(2))])
+                .unwrap();
+        Response::Accounts(other)
+            .validate(&request)
+            .await
+            .unwrap_err();
+
+        // A chain config with the wrong commitment is rejected.
+        let chain_config = ChainConfig::default();
+        let other_config = ChainConfig {
+            max_block_size: 1.into(),
+            ..chain_config
+        };
+        Response::ChainConfig(other_config)
+            .validate(&Request::ChainConfig(chain_config.commit()))
+            .await
+            .unwrap_err();
+
+        // A leaf chain must start at the requested height and link each leaf to the previous one.
+        let genesis =
+            Leaf2::genesis::<MockSequencerVersions>(&ValidatedState::default(), &NodeState::mock())
+                .await;
+        let mut justify_qc = QuorumCertificate2::genesis::<MockSequencerVersions>(
+            &ValidatedState::default(),
+            &NodeState::mock(),
+        )
+        .await;
+        let mut quorum_proposal = QuorumProposalWrapper::<SeqTypes> {
+            proposal: QuorumProposal2::<SeqTypes> {
+                block_header: genesis.block_header().clone(),
+                view_number: ViewNumber::genesis(),
+                justify_qc: justify_qc.clone(),
+                upgrade_certificate: None,
+                view_change_evidence: None,
+                next_drb_result: None,
+                next_epoch_justify_qc: None,
+                epoch: None,
+                state_cert: None,
+            },
+        };
+        let mut chain = vec![];
+        for i in 0..4 {
+            *quorum_proposal.proposal.block_header.height_mut() = i;
+            quorum_proposal.proposal.view_number = ViewNumber::new(i);
+            quorum_proposal.proposal.justify_qc = justify_qc.clone();
+            let leaf = Leaf2::from_quorum_proposal(&quorum_proposal);
+            justify_qc.view_number = leaf.view_number();
+            justify_qc.data.leaf_commit = leaf.commit();
+            chain.push(leaf);
+        }
+        Response::LeafChain(chain.clone())
+            .validate(&Request::LeafChain(0))
+            .await
+            .unwrap();
+        Response::LeafChain(chain[1..].to_vec())
+            .validate(&Request::LeafChain(0))
+            .await
+            .unwrap_err();
+        Response::LeafChain(vec![])
+            .validate(&Request::LeafChain(0))
+            .await
+            .unwrap_err();
+        let mut broken = chain;
+        broken.remove(2);
+        Response::LeafChain(broken)
+            .validate(&Request::LeafChain(0))
+            .await
+            .unwrap_err();
+
+        // Mismatched response types are rejected.
+        Response::LeafChain(vec![])
+            .validate(&request)
+            .await
+            .unwrap_err();
+    }
+}
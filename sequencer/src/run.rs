@@ -170,6 +170,7 @@ where
         state_peers: opt.state_peers,
         config_peers: opt.config_peers,
         catchup_backoff: opt.catchup_backoff,
+        catchup_request_timeout: opt.catchup_request_timeout,
         libp2p_history_gossip: opt.libp2p_history_gossip,
         libp2p_history_length: opt.libp2p_history_length,
         libp2p_max_ihave_length: opt.libp2p_max_ihave_length,
@@ -202,6 +203,7 @@ where
         fallback_builder_url: opt.fallback_builder_url,
     };
     let proposal_fetcher_config = opt.proposal_fetcher_config;
+    let tx_status_config = opt.tx_status_config;
 
     let persistence = storage_opt.create().await?;
     persistence
@@ -254,6 +256,7 @@ where
                             opt.identity,
                             marketplace_config,
                             proposal_fetcher_config,
+                            tx_status_config,
                         )
                         .await
                     }
@@ -274,6 +277,7 @@ where
                 opt.identity,
                 marketplace_config,
                 proposal_fetcher_config,
+                tx_status_config,
             )
             .await?
         },
@@ -325,6 +329,7 @@ mod test {
             upgrade_version: Version { major: 0, minor: 2 },
             epoch_height: None,
             epoch_start_block: None,
+            fee_withdrawal_start_block: None,
         };
         genesis.to_file(&genesis_file).unwrap();
 
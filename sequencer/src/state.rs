@@ -4,7 +4,7 @@ use std::{cmp::max, sync::Arc, time::Duration};
 use anyhow::{bail, ensure, Context};
 use espresso_types::{
     traits::StateCatchup,
-    v0_1::{RewardAccount, RewardMerkleTree},
+    v0_1::{FeeLedgerEntry, RewardAccount, RewardMerkleTree},
     v0_99::ChainConfig,
     BlockMerkleTree, Delta, FeeAccount, FeeMerkleTree, Leaf2, ValidatedState,
 };
@@ -17,12 +17,16 @@ use hotshot_query_service::{
     status::StatusDataSource,
     types::HeightIndexed,
 };
+use hotshot_types::{traits::signature_key::StakeTableEntryType, utils::epoch_from_block_number};
 use jf_merkle_tree::{LookupResult, MerkleTreeScheme, ToTraversalPath, UniversalMerkleTreeScheme};
 use tokio::time::sleep;
 
 use crate::{
     catchup::{CatchupStorage, SqlStateCatchup},
-    persistence::ChainConfigPersistence,
+    persistence::{
+        ChainConfigPersistence, FeeLedgerPersistence, RewardHistoryPersistence,
+        VoteHistoryPersistence,
+    },
     NodeState, SeqTypes,
 };
 
@@ -75,7 +79,7 @@ pub(crate) async fn compute_state_update(
         .await
 }
 
-async fn store_state_update(
+pub(crate) async fn store_state_update(
     tx: &mut impl SequencerStateUpdate,
     block_number: u64,
     state: &ValidatedState,
@@ -90,6 +94,7 @@ async fn store_state_update(
     let Delta {
         fees_delta,
         rewards_delta,
+        ..
     } = delta;
 
     // Insert fee merkle tree nodes
@@ -194,7 +199,7 @@ where
 {
     let parent_chain_config = parent_state.chain_config;
 
-    let (state, delta) = compute_state_update(
+    let (state, mut delta) = compute_state_update(
         parent_state,
         instance,
         peers,
@@ -203,6 +208,22 @@ where
     )
     .await
     .context("computing state update")?;
+    let reward_distribution = delta.reward_distribution.take();
+    let fee_ledger = FeeLedgerEntry::from_block(
+        proposed_leaf.height(),
+        &delta.l1_deposits,
+        &delta.fee_transfers,
+        &delta.fee_withdrawals,
+    );
+    // Voter participation is informational only, failing to compute it
+    // mustn't hold up storing the state
+    let votes = match qc_voters(instance, proposed_leaf.leaf()).await {
+        Ok(votes) => votes,
+        Err(err) => {
+            tracing::warn!("failed to compute voters, not storing votes: {err:#}");
+            None
+        },
+    };
 
     tracing::debug!("storing state update");
     let mut tx = storage
@@ -212,6 +233,31 @@ where
 
     store_state_update(&mut tx, proposed_leaf.height(), &state, delta).await?;
 
+    if let Some(distribution) = reward_distribution {
+        let epoch_height = instance
+            .epoch_height
+            .context("rewards distributed without epoch height")?;
+        let epoch = epoch_from_block_number(proposed_leaf.height(), epoch_height);
+        tracing::debug!(epoch, validator = %distribution.validator, "storing reward distribution");
+        tx.insert_reward_distribution(proposed_leaf.height(), epoch, distribution)
+            .await
+            .context("storing reward distribution")?;
+    }
+
+    if !fee_ledger.is_empty() {
+        tracing::debug!(entries = fee_ledger.len(), "storing fee ledger");
+        tx.insert_fee_ledger(proposed_leaf.height(), fee_ledger)
+            .await
+            .context("storing fee ledger")?;
+    }
+
+    if let Some((epoch, voters)) = votes {
+        tracing::debug!(epoch, num_voters = voters.len(), "storing votes");
+        tx.insert_votes(proposed_leaf.height(), epoch, voters)
+            .await
+            .context("storing votes")?;
+    }
+
     if parent_chain_config != state.chain_config {
         let cf = state
             .chain_config
@@ -225,6 +271,40 @@ where
     Ok(state)
 }
 
+/// The validators whose votes make up the quorum certificate justifying `leaf`, along with the
+/// epoch in which the certificate was formed.
+///
+/// Returns [`None`] for certificates formed before epochs were enabled, and for the genesis
+/// certificate, which carries no signatures.
+async fn qc_voters(
+    instance: &NodeState,
+    leaf: &Leaf2,
+) -> anyhow::Result<Option<(u64, Vec<RewardAccount>)>> {
+    let qc = leaf.justify_qc();
+    let (Some(epoch), Some((_, signers))) = (qc.data.epoch, qc.signatures.as_ref()) else {
+        return Ok(None);
+    };
+
+    // Signers are identified by their position in the stake table of the certificate's epoch.
+    let stake_table = instance
+        .coordinator
+        .stake_table_for_epoch(Some(epoch))
+        .await?
+        .stake_table()
+        .await;
+    let membership = instance.coordinator.membership().read().await;
+    let voters = stake_table
+        .iter()
+        .zip(signers.iter())
+        .filter(|(_, signed)| **signed)
+        .map(|(peer, _)| {
+            let address = membership.address(&epoch, peer.stake_table_entry.public_key())?;
+            Ok(RewardAccount(address))
+        })
+        .collect::<anyhow::Result<_>>()?;
+    Ok(Some((*epoch, voters)))
+}
+
 async fn store_genesis_state<T>(
     mut tx: T,
     chain_config: ChainConfig,
@@ -386,6 +466,9 @@ pub(crate) trait SequencerStateUpdate:
     + UpdateStateData<SeqTypes, BlockMerkleTree, { BlockMerkleTree::ARITY }>
     + UpdateStateData<SeqTypes, RewardMerkleTree, { RewardMerkleTree::ARITY }>
     + ChainConfigPersistence
+    + FeeLedgerPersistence
+    + RewardHistoryPersistence
+    + VoteHistoryPersistence
 {
 }
 
@@ -395,5 +478,8 @@ impl<T> SequencerStateUpdate for T where
         + UpdateStateData<SeqTypes, BlockMerkleTree, { BlockMerkleTree::ARITY }>
         + UpdateStateData<SeqTypes, RewardMerkleTree, { RewardMerkleTree::ARITY }>
         + ChainConfigPersistence
+        + FeeLedgerPersistence
+        + RewardHistoryPersistence
+        + VoteHistoryPersistence
 {
 }
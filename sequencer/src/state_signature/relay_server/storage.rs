@@ -0,0 +1,273 @@
+//! SQL persistence for the state relay server.
+
+use anyhow::Context;
+use hotshot_query_service::data_source::storage::sql::Db;
+use hotshot_types::light_client::{
+    LightClientState, StateSignatureRequestBody, StateSignaturesBundle,
+};
+use sqlx::{migrate::Migrator, pool::PoolOptions, query, query_as, Pool};
+
+/// The relay server only needs its own tables, not the sequencer or query service schema.
+#[cfg(not(feature = "embedded-db"))]
+static MIGRATOR: Migrator = sqlx::migrate!("./api/relay_migrations/postgres");
+#[cfg(feature = "embedded-db")]
+static MIGRATOR: Migrator = sqlx::migrate!("./api/relay_migrations/sqlite");
+
+/// Maximum number of connections kept in the pool.
+const MAX_CONNECTIONS: u32 = 10;
+
+/// Signatures and signature bundles collected by the state relay server, stored in a SQL database.
+///
+/// The same database can be shared by several relay servers, in which case every server sees the
+/// signatures posted to any of them.
+#[derive(Clone, Debug)]
+pub struct RelayStorage {
+    pool: Pool<Db>,
+}
+
+impl RelayStorage {
+    /// Connect to the database at `uri`, running any outstanding migrations.
+    pub async fn connect(uri: &str) -> anyhow::Result<Self> {
+        let pool = PoolOptions::<Db>::new()
+            .max_connections(MAX_CONNECTIONS)
+            .connect(uri)
+            .await
+            .context("connecting to relay server database")?;
+        MIGRATOR
+            .run(&pool)
+            .await
+            .context("running relay server database migrations")?;
+        Ok(Self { pool })
+    }
+
+    /// Store a signature posted for a state in `epoch`.
+    ///
+    /// Returns `false` without changing anything if the same key already signed this state.
+    pub async fn insert_signature(
+        &self,
+        epoch: u64,
+        req: &StateSignatureRequestBody,
+    ) -> anyhow::Result<bool> {
+        let data = bincode::serialize(req).context("serializing state signature")?;
+
+        let res = query(
+            "INSERT INTO state_signatures (height, epoch, state, signer, data)
+                VALUES ($1, $2, $3, $4, $5) ON CONFLICT DO NOTHING",
+        )
+        .bind(req.state.block_height as i64)
+        .bind(epoch as i64)
+        .bind(req.state.to_string())
+        .bind(req.key.to_string())
+        .bind(data)
+        .execute(&self.pool)
+        .await
+        .context("inserting state signature")?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    /// All the signatures posted for `state`.
+    pub async fn signatures(
+        &self,
+        state: &LightClientState,
+    ) -> anyhow::Result<Vec<StateSignatureRequestBody>> {
+        let rows = query_as::<_, (Vec<u8>,)>(
+            "SELECT data FROM state_signatures WHERE height = $1 AND state = $2 ORDER BY signer",
+        )
+        .bind(state.block_height as i64)
+        .bind(state.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|(data,)| bincode::deserialize(&data).context("deserializing state signature"))
+            .collect()
+    }
+
+    /// Store a bundle which reached the signing threshold.
+    pub async fn insert_bundle(
+        &self,
+        epoch: u64,
+        bundle: &StateSignaturesBundle,
+    ) -> anyhow::Result<()> {
+        let height = bundle.state.block_height;
+        let data = bincode::serialize(bundle).context("serializing state signatures bundle")?;
+
+        query(
+            "INSERT INTO state_signature_bundles (height, epoch, data) VALUES ($1, $2, $3)
+                ON CONFLICT (height) DO UPDATE SET epoch = excluded.epoch, data = excluded.data",
+        )
+        .bind(height as i64)
+        .bind(epoch as i64)
+        .bind(data)
+        .execute(&self.pool)
+        .await
+        .context("inserting state signatures bundle")?;
+        Ok(())
+    }
+
+    /// The bundle which reached the signing threshold at `height`, if any.
+    pub async fn bundle(&self, height: u64) -> anyhow::Result<Option<StateSignaturesBundle>> {
+        let row = query_as::<_, (Vec<u8>,)>(
+            "SELECT data FROM state_signature_bundles WHERE height = $1 LIMIT 1",
+        )
+        .bind(height as i64)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|(data,)| {
+            bincode::deserialize(&data).context("deserializing state signatures bundle")
+        })
+        .transpose()
+    }
+
+    /// The bundle at the greatest height which reached the signing threshold, if any.
+    pub async fn latest_bundle(&self) -> anyhow::Result<Option<StateSignaturesBundle>> {
+        let row = query_as::<_, (Vec<u8>,)>(
+            "SELECT data FROM state_signature_bundles ORDER BY height DESC LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|(data,)| {
+            bincode::deserialize(&data).context("deserializing state signatures bundle")
+        })
+        .transpose()
+    }
+
+    /// Delete the individual signatures strictly below `until_height`.
+    ///
+    /// The signatures at `until_height` itself are kept, so that a relay server sharing this
+    /// database which has not completed the bundle at that height yet can still do so. Bundles
+    /// are kept, so that historical states can still be served.
+    pub async fn prune(&self, until_height: u64) -> anyhow::Result<()> {
+        let res = query("DELETE FROM state_signatures WHERE height < $1")
+            .bind(until_height as i64)
+            .execute(&self.pool)
+            .await
+            .context("pruning state signatures")?;
+
+        if res.rows_affected() > 0 {
+            tracing::debug!(
+                until_height,
+                "garbage collected {} state signatures",
+                res.rows_affected()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use hotshot::types::SchnorrPubKey;
+    use hotshot_query_service::data_source::storage::sql::testing::TmpDb;
+    use hotshot_types::{
+        light_client::{StakeTableState, StateKeyPair},
+        traits::signature_key::StateSignatureKey,
+    };
+    use sequencer_utils::test_utils::setup_test;
+
+    use super::*;
+
+    pub(crate) fn uri(db: &TmpDb) -> String {
+        #[cfg(not(feature = "embedded-db"))]
+        {
+            format!("postgres://postgres:password@{}:{}", db.host(), db.port())
+        }
+
+        #[cfg(feature = "embedded-db")]
+        {
+            format!("sqlite://{}?mode=rwc", db.path().display())
+        }
+    }
+
+    fn sign(key_pair: &StateKeyPair, state: LightClientState) -> StateSignatureRequestBody {
+        let next_stake = StakeTableState::default();
+        StateSignatureRequestBody {
+            key: key_pair.ver_key(),
+            state,
+            next_stake,
+            signature: <SchnorrPubKey as StateSignatureKey>::sign_state(
+                &key_pair.sign_key(),
+                &state,
+                &next_stake,
+            )
+            .unwrap(),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_relay_storage() {
+        setup_test();
+
+        let tmp = TmpDb::init().await;
+        let storage = RelayStorage::connect(&uri(&tmp)).await.unwrap();
+        // A second relay server sharing the same database.
+        let peer = RelayStorage::connect(&uri(&tmp)).await.unwrap();
+
+        let state = LightClientState {
+            view_number: 10,
+            block_height: 5,
+            ..Default::default()
+        };
+        let other_state = LightClientState {
+            view_number: 11,
+            ..state
+        };
+        let keys = (0..2)
+            .map(|i| StateKeyPair::generate_from_seed_indexed([0; 32], i))
+            .collect::<Vec<_>>();
+
+        assert!(storage
+            .insert_signature(1, &sign(&keys[0], state))
+            .await
+            .unwrap());
+        assert!(peer
+            .insert_signature(1, &sign(&keys[1], state))
+            .await
+            .unwrap());
+        assert!(peer
+            .insert_signature(1, &sign(&keys[0], other_state))
+            .await
+            .unwrap());
+        // A signature is only stored once, whichever server it is posted to.
+        assert!(!peer
+            .insert_signature(1, &sign(&keys[0], state))
+            .await
+            .unwrap());
+
+        let signatures = storage.signatures(&state).await.unwrap();
+        assert_eq!(signatures.len(), 2);
+        assert!(signatures.iter().all(|sig| sig.state == state));
+        assert_eq!(storage.signatures(&other_state).await.unwrap().len(), 1);
+
+        assert!(storage.latest_bundle().await.unwrap().is_none());
+        let bundle = StateSignaturesBundle {
+            state,
+            next_stake: StakeTableState::default(),
+            signatures: signatures
+                .into_iter()
+                .map(|sig| (sig.key, sig.signature))
+                .collect(),
+            accumulated_weight: Default::default(),
+        };
+        storage.insert_bundle(1, &bundle).await.unwrap();
+        let latest = peer.latest_bundle().await.unwrap().unwrap();
+        assert_eq!(latest.state, state);
+        assert_eq!(latest.signatures, bundle.signatures);
+        assert_eq!(peer.bundle(5).await.unwrap().unwrap().state, state);
+        assert!(peer.bundle(4).await.unwrap().is_none());
+
+        // Pruning keeps the signatures at the pruned height, for peers still completing it.
+        storage.prune(5).await.unwrap();
+        assert_eq!(storage.signatures(&state).await.unwrap().len(), 2);
+        assert_eq!(storage.signatures(&other_state).await.unwrap().len(), 1);
+
+        // Pruning past a height removes its signatures, but not the bundle.
+        storage.prune(6).await.unwrap();
+        assert!(storage.signatures(&state).await.unwrap().is_empty());
+        assert!(storage.signatures(&other_state).await.unwrap().is_empty());
+        assert_eq!(storage.bundle(5).await.unwrap().unwrap().state, state);
+    }
+}
@@ -0,0 +1,377 @@
+//! Tracking of the lifecycle of individual transactions, derived from consensus events.
+//!
+//! This lets clients find out what happened to a transaction after submitting it, without polling
+//! the availability API until the transaction shows up in a block.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+use async_lock::RwLock;
+use clap::Parser;
+use committable::{Commitment, Committable};
+use espresso_types::{submit::TransactionStatus, Payload, SeqTypes, Transaction};
+use futures::stream::{self, BoxStream, StreamExt};
+use hotshot::types::{Event, EventType};
+use hotshot_types::{
+    data::ViewNumber,
+    event::LeafInfo,
+    traits::{
+        block_contents::{BlockHeader, BlockPayload},
+        node_implementation::ConsensusTime,
+    },
+};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Capacity of the channel used to notify subscribers of status transitions.
+const TX_STATUS_CHANNEL_CAPACITY: usize = 1_000;
+
+#[derive(Clone, Copy, Debug, Parser)]
+pub struct TxStatusConfig {
+    /// Maximum number of transactions whose status is kept in memory.
+    #[clap(
+        long = "tx-status-capacity",
+        env = "ESPRESSO_SEQUENCER_TX_STATUS_CAPACITY",
+        default_value = "10000"
+    )]
+    pub capacity: usize,
+
+    /// Number of views after which a transaction which has not been decided is considered dropped.
+    #[clap(
+        long = "tx-status-expiry-views",
+        env = "ESPRESSO_SEQUENCER_TX_STATUS_EXPIRY_VIEWS",
+        default_value = "100"
+    )]
+    pub expiry_views: u64,
+}
+
+impl Default for TxStatusConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            expiry_views: 100,
+        }
+    }
+}
+
+/// Tracks the status of recently seen transactions.
+#[derive(Debug)]
+pub struct TxStatusTracker {
+    statuses: Arc<RwLock<TxStatusMemStorage>>,
+    updates: broadcast::Sender<(Commitment<Transaction>, TransactionStatus)>,
+}
+
+impl Default for TxStatusTracker {
+    fn default() -> Self {
+        Self::new(TxStatusConfig::default())
+    }
+}
+
+impl TxStatusTracker {
+    pub fn new(cfg: TxStatusConfig) -> Self {
+        Self {
+            statuses: Arc::new(RwLock::new(TxStatusMemStorage::new(cfg))),
+            updates: broadcast::channel(TX_STATUS_CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// Get the current status of a transaction, if it is known.
+    pub async fn status(&self, hash: Commitment<Transaction>) -> Option<TransactionStatus> {
+        self.statuses.read().await.get(hash)
+    }
+
+    /// Stream status transitions for a transaction.
+    ///
+    /// The stream starts with the current status of the transaction, if it is known, and ends after
+    /// the transaction reaches a final status. If the subscriber falls behind and misses some
+    /// transitions, it skips ahead to the current status.
+    pub async fn subscribe(
+        &self,
+        hash: Commitment<Transaction>,
+    ) -> BoxStream<'static, TransactionStatus> {
+        // Subscribe before reading the current status, so we can't miss a transition in between.
+        let updates = self.updates.subscribe();
+        let current = self.status(hash).await;
+        let statuses = self.statuses.clone();
+
+        let transitions = stream::unfold((updates, current), move |(mut updates, last)| {
+            let statuses = statuses.clone();
+            async move {
+                if last.is_some_and(|status| status.is_final()) {
+                    return None;
+                }
+                loop {
+                    let status = match updates.recv().await {
+                        Ok((tx, status)) if tx == hash => status,
+                        Ok(_) => continue,
+                        Err(RecvError::Lagged(n)) => {
+                            tracing::warn!(%hash, n, "transaction status subscriber lagged");
+                            // We may have missed a transition of this transaction, so catch up
+                            // with its current status.
+                            match statuses.read().await.get(hash) {
+                                Some(status) if Some(status) != last => status,
+                                _ => continue,
+                            }
+                        },
+                        Err(RecvError::Closed) => return None,
+                    };
+                    return Some((status, (updates, Some(status))));
+                }
+            }
+        });
+        stream::iter(current).chain(transitions).boxed()
+    }
+
+    /// Record that a transaction was submitted to this node.
+    pub async fn submitted(&self, hash: Commitment<Transaction>) {
+        let mut statuses = self.statuses.write().await;
+        let view = statuses.view;
+        self.update(&mut statuses, view, hash, TransactionStatus::Pending);
+    }
+
+    /// Update transaction statuses based on a consensus event.
+    pub async fn handle_event(&self, event: &Event<SeqTypes>) {
+        let mut statuses = self.statuses.write().await;
+        let view = event.view_number;
+        statuses.view = statuses.view.max(view);
+
+        match &event.event {
+            EventType::Transactions { transactions } => {
+                for tx in transactions {
+                    self.update(&mut statuses, view, tx.commit(), TransactionStatus::Pending);
+                }
+            },
+            EventType::DaProposal { proposal, .. } => {
+                let proposal = &proposal.data;
+                let payload =
+                    Payload::from_bytes(&proposal.encoded_transactions, &proposal.metadata);
+                let status = TransactionStatus::Proposed {
+                    view: proposal.view_number.u64(),
+                };
+                for tx in payload.transactions(&proposal.metadata) {
+                    self.update(&mut statuses, view, tx.commit(), status);
+                }
+            },
+            EventType::Decide { leaf_chain, .. } => {
+                // The leaf chain is in reverse chronological order.
+                for LeafInfo { leaf, .. } in leaf_chain.iter().rev() {
+                    let Some(payload) = leaf.block_payload() else {
+                        tracing::debug!(
+                            height = leaf.height(),
+                            "decided leaf is missing payload, cannot update transaction statuses"
+                        );
+                        continue;
+                    };
+                    let height = leaf.height();
+                    let metadata = leaf.block_header().metadata();
+                    for (index, tx) in payload.transactions(metadata).enumerate() {
+                        let status = TransactionStatus::Decided {
+                            height,
+                            index: index as u64,
+                        };
+                        self.update(&mut statuses, view, tx.commit(), status);
+                    }
+                }
+
+                // Anything which has been waiting too long is not going to be sequenced.
+                for hash in statuses.expired(view) {
+                    self.update(&mut statuses, view, hash, TransactionStatus::Dropped);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn update(
+        &self,
+        statuses: &mut TxStatusMemStorage,
+        view: ViewNumber,
+        hash: Commitment<Transaction>,
+        status: TransactionStatus,
+    ) {
+        if statuses.update(view, hash, status) {
+            // An error here just means there are no subscribers.
+            self.updates.send((hash, status)).ok();
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct TrackedTx {
+    status: TransactionStatus,
+    /// The view in which the transaction was first seen, used for expiry.
+    first_seen: ViewNumber,
+}
+
+#[derive(Debug)]
+struct TxStatusMemStorage {
+    pool: HashMap<Commitment<Transaction>, TrackedTx>,
+    deque: VecDeque<Commitment<Transaction>>,
+    /// The latest view we have seen an event for.
+    view: ViewNumber,
+    cfg: TxStatusConfig,
+}
+
+impl TxStatusMemStorage {
+    fn new(cfg: TxStatusConfig) -> Self {
+        Self {
+            pool: Default::default(),
+            deque: Default::default(),
+            view: ViewNumber::genesis(),
+            cfg,
+        }
+    }
+
+    fn get(&self, hash: Commitment<Transaction>) -> Option<TransactionStatus> {
+        self.pool.get(&hash).map(|tx| tx.status)
+    }
+
+    /// Apply a status transition, returning whether the status changed.
+    fn update(
+        &mut self,
+        view: ViewNumber,
+        hash: Commitment<Transaction>,
+        status: TransactionStatus,
+    ) -> bool {
+        let Some(tx) = self.pool.get_mut(&hash) else {
+            self.pool.insert(
+                hash,
+                TrackedTx {
+                    status,
+                    first_seen: view,
+                },
+            );
+            self.deque.push_back(hash);
+            if self.pool.len() > self.cfg.capacity {
+                self.pool.remove(&self.deque.pop_front().unwrap());
+            }
+            return true;
+        };
+
+        if !is_transition(tx.status, status) {
+            return false;
+        }
+        if tx.status == TransactionStatus::Dropped {
+            // The transaction has been resubmitted, restart the expiry window.
+            tx.first_seen = view;
+        }
+        tx.status = status;
+        true
+    }
+
+    /// Transactions which have been waiting to be decided for too long, as of `view`.
+    fn expired(&self, view: ViewNumber) -> Vec<Commitment<Transaction>> {
+        self.pool
+            .iter()
+            .filter(|(_, tx)| {
+                matches!(
+                    tx.status,
+                    TransactionStatus::Pending | TransactionStatus::Proposed { .. }
+                ) && view.u64().saturating_sub(tx.first_seen.u64()) > self.cfg.expiry_views
+            })
+            .map(|(hash, _)| *hash)
+            .collect()
+    }
+}
+
+/// Whether a transaction with status `old` can move to status `new`.
+///
+/// Events can arrive out of order (e.g. a transaction may be gossiped to us after we have already
+/// seen it in a proposal), so we never move a transaction backwards in its lifecycle. The exception
+/// is a dropped transaction, which may reappear if it is resubmitted. Dropped is therefore not a
+/// final status, and subscribers keep listening for transitions after it.
+fn is_transition(old: TransactionStatus, new: TransactionStatus) -> bool {
+    use TransactionStatus::*;
+    match (old, new) {
+        (Decided { .. }, _) => false,
+        (Dropped, Dropped) => false,
+        (Dropped, _) => true,
+        (Pending, Pending) => false,
+        (Pending, _) => true,
+        (Proposed { view: old }, Proposed { view: new }) => new > old,
+        (Proposed { .. }, Pending) => false,
+        (Proposed { .. }, _) => true,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tx_status_transitions() {
+        let hash = Transaction::new(0u32.into(), vec![1, 2, 3]).commit();
+        let mut statuses = TxStatusMemStorage::new(TxStatusConfig::default());
+        let view = ViewNumber::new(1);
+
+        assert!(statuses.update(view, hash, TransactionStatus::Pending));
+        assert!(!statuses.update(view, hash, TransactionStatus::Pending));
+        assert!(statuses.update(view, hash, TransactionStatus::Proposed { view: 2 }));
+
+        // Statuses never move backwards.
+        assert!(!statuses.update(view, hash, TransactionStatus::Pending));
+        assert!(!statuses.update(view, hash, TransactionStatus::Proposed { view: 1 }));
+        assert!(statuses.update(view, hash, TransactionStatus::Proposed { view: 3 }));
+
+        let decided = TransactionStatus::Decided {
+            height: 1,
+            index: 0,
+        };
+        assert!(statuses.update(view, hash, decided));
+        assert!(!statuses.update(view, hash, TransactionStatus::Dropped));
+        assert_eq!(statuses.get(hash), Some(decided));
+    }
+
+    #[test]
+    fn test_tx_status_expiry() {
+        let expiry = TxStatusConfig::default().expiry_views;
+        let hash = Transaction::new(0u32.into(), vec![1, 2, 3]).commit();
+        let mut statuses = TxStatusMemStorage::new(TxStatusConfig::default());
+        statuses.update(ViewNumber::new(1), hash, TransactionStatus::Pending);
+
+        assert!(statuses.expired(ViewNumber::new(1 + expiry)).is_empty());
+        assert_eq!(statuses.expired(ViewNumber::new(2 + expiry)), vec![hash]);
+
+        // A dropped transaction can be resubmitted, which restarts the expiry window.
+        let view = ViewNumber::new(2 + expiry);
+        statuses.update(view, hash, TransactionStatus::Dropped);
+        assert!(statuses.expired(view).is_empty());
+        statuses.update(view, hash, TransactionStatus::Pending);
+        assert!(statuses.expired(view + expiry).is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_tx_status_subscribe_lagged() {
+        let tracker = TxStatusTracker::default();
+        let hash = Transaction::new(0u32.into(), vec![1, 2, 3]).commit();
+        let view = ViewNumber::new(1);
+        tracker.submitted(hash).await;
+
+        let mut stream = tracker.subscribe(hash).await;
+        assert_eq!(stream.next().await, Some(TransactionStatus::Pending));
+
+        // Overflow the subscriber's channel, so it misses the transaction being dropped.
+        {
+            let mut statuses = tracker.statuses.write().await;
+            tracker.update(&mut statuses, view, hash, TransactionStatus::Dropped);
+            for i in 0..TX_STATUS_CHANNEL_CAPACITY {
+                let other = Transaction::new(1u32.into(), i.to_le_bytes().to_vec()).commit();
+                tracker.update(&mut statuses, view, other, TransactionStatus::Pending);
+            }
+        }
+        assert_eq!(stream.next().await, Some(TransactionStatus::Dropped));
+
+        // A dropped transaction can still be decided, after which the stream ends.
+        let decided = TransactionStatus::Decided {
+            height: 1,
+            index: 0,
+        };
+        {
+            let mut statuses = tracker.statuses.write().await;
+            tracker.update(&mut statuses, view, hash, decided);
+        }
+        assert_eq!(stream.next().await, Some(decided));
+        assert_eq!(stream.next().await, None);
+    }
+}
@@ -247,6 +247,23 @@ impl QueryablePayload<SeqTypes> for Payload {
     ) -> Option<Self::Transaction> {
         self.transaction(index)
     }
+
+    fn transaction_namespace(
+        &self,
+        meta: &Self::Metadata,
+        index: &Self::TransactionIndex,
+    ) -> Option<u64> {
+        let ns_id = meta.read_ns_id(index.ns())?;
+        Some(u32::from(ns_id).into())
+    }
+
+    fn transaction_size(
+        &self,
+        _meta: &Self::Metadata,
+        index: &Self::TransactionIndex,
+    ) -> Option<u64> {
+        Some(self.transaction(index)?.payload().len() as u64)
+    }
 }
 
 impl std::fmt::Display for Payload {
@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 use alloy::primitives::{
+    keccak256,
     utils::{parse_units, ParseUnits},
     Address, U256,
 };
@@ -25,11 +26,16 @@ use sequencer_utils::{
 use thiserror::Error;
 
 use crate::{
-    eth_signature_key::EthKeyPair, v0_99::IterableFeeInfo, AccountQueryData, FeeAccount,
-    FeeAccountProof, FeeAmount, FeeInfo, FeeMerkleCommitment, FeeMerkleProof, FeeMerkleTree,
-    SeqTypes,
+    eth_signature_key::EthKeyPair,
+    v0_1::{FeeLedgerEntry, FeeLedgerEntryKind, FeeTransfer, L1Deposit, L1WithdrawalRequest},
+    v0_99::IterableFeeInfo,
+    AccountQueryData, FeeAccount, FeeAccountProof, FeeAmount, FeeInfo, FeeMerkleCommitment,
+    FeeMerkleProof, FeeMerkleTree, SeqTypes,
 };
 
+/// Domain separator for deriving [`FeeAccount::withdrawal_escrow`].
+const WITHDRAWAL_ESCROW_DOMAIN: &[u8] = b"ESPRESSO_FEE_WITHDRAWAL";
+
 /// Possible charge fee failures
 #[derive(Error, Debug, Eq, PartialEq)]
 pub enum FeeError {
@@ -79,6 +85,72 @@ impl FeeInfo {
     }
 }
 
+impl FeeLedgerEntry {
+    /// The ledger entries for the block at `height`, in the order they were applied.
+    ///
+    /// Deposits are credited before fees are charged, and withdrawals are debited last. Each fee
+    /// results in two entries: a debit of the paying account and a credit of the recipient.
+    pub fn from_block(
+        height: u64,
+        deposits: &[L1Deposit],
+        transfers: &[FeeTransfer],
+        withdrawals: &[L1WithdrawalRequest],
+    ) -> Vec<Self> {
+        let deposits = deposits.iter().map(|deposit| {
+            (
+                deposit.fee_info.account,
+                deposit.fee_info.amount,
+                FeeLedgerEntryKind::Deposit {
+                    l1_block: deposit.l1_block,
+                    l1_tx_hash: deposit.l1_tx_hash,
+                    l1_log_index: deposit.l1_log_index,
+                },
+            )
+        });
+        let transfers = transfers.iter().flat_map(|transfer| {
+            [
+                (
+                    transfer.from,
+                    transfer.amount,
+                    FeeLedgerEntryKind::FeePaid {
+                        recipient: transfer.to,
+                    },
+                ),
+                (
+                    transfer.to,
+                    transfer.amount,
+                    FeeLedgerEntryKind::FeeReceived {
+                        payer: transfer.from,
+                    },
+                ),
+            ]
+        });
+        let withdrawals = withdrawals.iter().map(|withdrawal| {
+            (
+                withdrawal.fee_info.account,
+                withdrawal.fee_info.amount,
+                FeeLedgerEntryKind::Withdrawal {
+                    l1_block: withdrawal.l1_block,
+                    l1_tx_hash: withdrawal.l1_tx_hash,
+                    l1_log_index: withdrawal.l1_log_index,
+                },
+            )
+        });
+        deposits
+            .chain(transfers)
+            .chain(withdrawals)
+            .enumerate()
+            .map(|(index, (account, amount, kind))| Self {
+                height,
+                index: index as u64,
+                account,
+                amount,
+                kind,
+            })
+            .collect()
+    }
+}
+
 impl IterableFeeInfo for Vec<FeeInfo> {
     /// Get sum of fee amounts
     fn amount(&self) -> Option<FeeAmount> {
@@ -236,6 +308,17 @@ impl FeeAccount {
     pub fn to_fixed_bytes(self) -> [u8; 20] {
         self.0.into_array()
     }
+    /// The account holding the funds this account has withdrawn to L1.
+    ///
+    /// Withdrawals move funds from an account to its escrow account, whose balance is the
+    /// cumulative amount withdrawn, which the fee contract pays out on L1. The escrow address is the
+    /// last 20 bytes of `keccak256("ESPRESSO_FEE_WITHDRAWAL" ++ address)`, so no one holds a key for
+    /// it and its balance can never be spent on Espresso.
+    pub fn withdrawal_escrow(&self) -> FeeAccount {
+        let hash = keccak256([WITHDRAWAL_ESCROW_DOMAIN, self.as_bytes()].concat());
+        Self(Address::from_slice(&hash[12..]))
+    }
+
     pub fn test_key_pair() -> EthKeyPair {
         EthKeyPair::from_mnemonic(
             "test test test test test test test test test test test junk",
@@ -449,7 +532,10 @@ pub fn retain_accounts(
 #[cfg(test)]
 mod test {
     use super::{Address, IterableFeeInfo};
-    use crate::{FeeAccount, FeeAmount, FeeInfo};
+    use crate::{
+        v0_1::{FeeLedgerEntry, FeeLedgerEntryKind, FeeTransfer, L1Deposit, L1WithdrawalRequest},
+        FeeAccount, FeeAmount, FeeInfo,
+    };
 
     #[test]
     fn test_iterable_fee_info() {
@@ -464,4 +550,71 @@ mod test {
         let accounts = fees.accounts();
         assert_eq!(vec![FeeAccount::from(Address::default())], accounts);
     }
+
+    #[test]
+    fn test_fee_ledger_entries() {
+        let builder = FeeAccount::from(Address::repeat_byte(1));
+        let recipient = FeeAccount::from(Address::repeat_byte(2));
+        let deposit = L1Deposit {
+            fee_info: FeeInfo::new(builder, 10),
+            l1_block: Some(5),
+            l1_tx_hash: Some(Default::default()),
+            l1_log_index: Some(0),
+        };
+        let transfer = FeeTransfer {
+            from: builder,
+            to: recipient,
+            amount: 3.into(),
+        };
+
+        let withdrawal = L1WithdrawalRequest {
+            fee_info: FeeInfo::new(builder, 4),
+            l1_block: Some(6),
+            l1_tx_hash: Some(Default::default()),
+            l1_log_index: Some(1),
+        };
+
+        let entries = FeeLedgerEntry::from_block(7, &[deposit], &[transfer], &[withdrawal]);
+        assert_eq!(
+            entries,
+            [
+                FeeLedgerEntry {
+                    height: 7,
+                    index: 0,
+                    account: builder,
+                    amount: 10.into(),
+                    kind: FeeLedgerEntryKind::Deposit {
+                        l1_block: Some(5),
+                        l1_tx_hash: Some(Default::default()),
+                        l1_log_index: Some(0),
+                    },
+                },
+                FeeLedgerEntry {
+                    height: 7,
+                    index: 1,
+                    account: builder,
+                    amount: 3.into(),
+                    kind: FeeLedgerEntryKind::FeePaid { recipient },
+                },
+                FeeLedgerEntry {
+                    height: 7,
+                    index: 2,
+                    account: recipient,
+                    amount: 3.into(),
+                    kind: FeeLedgerEntryKind::FeeReceived { payer: builder },
+                },
+                FeeLedgerEntry {
+                    height: 7,
+                    index: 3,
+                    account: builder,
+                    amount: 4.into(),
+                    kind: FeeLedgerEntryKind::Withdrawal {
+                        l1_block: Some(6),
+                        l1_tx_hash: Some(Default::default()),
+                        l1_log_index: Some(1),
+                    },
+                },
+            ]
+        );
+    }
 }
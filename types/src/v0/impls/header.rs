@@ -28,8 +28,10 @@ use vbs::version::{StaticVersionType, Version};
 
 use super::{
     instance_state::NodeState,
-    state::ValidatedState,
-    v0_1::{RewardMerkleCommitment, RewardMerkleTree, REWARD_MERKLE_TREE_HEIGHT},
+    state::{withdrawal_accounts, ValidatedState},
+    v0_1::{
+        L1WithdrawalRequest, RewardMerkleCommitment, RewardMerkleTree, REWARD_MERKLE_TREE_HEIGHT,
+    },
     v0_3::Validator,
 };
 use crate::{
@@ -410,6 +412,7 @@ impl Header {
         parent_leaf: &Leaf2,
         mut l1: L1Snapshot,
         l1_deposits: &[FeeInfo],
+        l1_withdrawals: &[L1WithdrawalRequest],
         builder_fee: Vec<BuilderFee<SeqTypes>>,
         view_number: u64,
         mut timestamp: u64,
@@ -517,6 +520,11 @@ impl Header {
                 .context(format!("invalid builder fee {fee_info:?}"))?;
         }
 
+        // Honor withdrawal requests.
+        state
+            .apply_withdrawals(&mut Default::default(), l1_withdrawals.to_vec())
+            .context("applying withdrawals")?;
+
         let fee_info = FeeInfo::from_builder_fees(builder_fee.clone());
 
         let builder_signature: Vec<BuilderSignature> =
@@ -905,15 +913,38 @@ impl BlockHeader<SeqTypes> for Header {
         } else {
             vec![]
         };
+        // Fetch the new L1 withdrawal requests between parent and current finalized L1 block.
+        // Withdrawals are honored starting with the epoch version, once activated in genesis.
+        let l1_withdrawals = if let (true, Some(addr), Some(block_info)) = (
+            version >= EpochVersion::version()
+                && instance_state.fee_withdrawals_enabled(height + 1),
+            chain_config.fee_contract,
+            l1_snapshot.finalized,
+        ) {
+            instance_state
+                .l1_client
+                .get_finalized_withdrawal_requests(
+                    addr,
+                    parent_leaf
+                        .block_header()
+                        .l1_finalized()
+                        .map(|block_info| block_info.number),
+                    block_info.number,
+                )
+                .await
+        } else {
+            vec![]
+        };
         // Find missing fee state entries. We will need to use the builder account which is paying a
         // fee and the recipient account which is receiving it, plus any counts receiving deposits
-        // in this block.
+        // or withdrawing in this block.
 
         let missing_accounts = parent_state.forgotten_accounts(
             [chain_config.fee_recipient]
                 .into_iter()
                 .chain(builder_fee.accounts())
-                .chain(l1_deposits.accounts()),
+                .chain(l1_deposits.accounts())
+                .chain(withdrawal_accounts(&l1_withdrawals)),
         );
 
         if !missing_accounts.is_empty() {
@@ -968,6 +999,7 @@ impl BlockHeader<SeqTypes> for Header {
             parent_leaf,
             l1_snapshot,
             &l1_deposits,
+            &l1_withdrawals,
             builder_fee,
             view_number,
             OffsetDateTime::now_utc().unix_timestamp() as u64,
@@ -1041,13 +1073,36 @@ impl BlockHeader<SeqTypes> for Header {
         } else {
             vec![]
         };
+        // Fetch the new L1 withdrawal requests between parent and current finalized L1 block.
+        // Withdrawals are honored starting with the epoch version, once activated in genesis.
+        let l1_withdrawals = if let (true, Some(addr), Some(block_info)) = (
+            version >= EpochVersion::version()
+                && instance_state.fee_withdrawals_enabled(height + 1),
+            chain_config.fee_contract,
+            l1_snapshot.finalized,
+        ) {
+            instance_state
+                .l1_client
+                .get_finalized_withdrawal_requests(
+                    addr,
+                    parent_leaf
+                        .block_header()
+                        .l1_finalized()
+                        .map(|block_info| block_info.number),
+                    block_info.number,
+                )
+                .await
+        } else {
+            vec![]
+        };
         // Find missing fee state entries. We will need to use the builder account which is paying a
         // fee and the recipient account which is receiving it, plus any counts receiving deposits
-        // in this block.
+        // or withdrawing in this block.
         let missing_accounts = parent_state.forgotten_accounts(
             [builder_fee.fee_account, chain_config.fee_recipient]
                 .into_iter()
-                .chain(l1_deposits.iter().map(|info| info.account())),
+                .chain(l1_deposits.iter().map(|info| info.account()))
+                .chain(withdrawal_accounts(&l1_withdrawals)),
         );
         if !missing_accounts.is_empty() {
             tracing::warn!(
@@ -1123,6 +1178,7 @@ impl BlockHeader<SeqTypes> for Header {
             parent_leaf,
             l1_snapshot,
             &l1_deposits,
+            &l1_withdrawals,
             vec![builder_fee],
             // View number is 0 for legacy headers
             0,
@@ -1355,6 +1411,7 @@ mod test_headers {
                     finalized: self.l1_finalized,
                 },
                 &self.l1_deposits,
+                &[],
                 vec![BuilderFee {
                     fee_account,
                     fee_amount,
@@ -18,7 +18,7 @@ use super::{
     traits::MembershipPersistence,
     v0_1::NoStorage,
     v0_3::{IndexedStake, Validator},
-    SeqTypes,
+    IndexedStakeTableEvent, SeqTypes,
 };
 use crate::v0::{
     traits::StateCatchup, v0_99::ChainConfig, GenesisHeader, L1BlockInfo, L1Client, Timestamp,
@@ -43,6 +43,11 @@ pub struct NodeState {
     #[debug(skip)]
     pub coordinator: EpochMembershipCoordinator<SeqTypes>,
     pub epoch_height: Option<u64>,
+    /// Block height from which L1 fee withdrawal requests are honored.
+    ///
+    /// Withdrawals change the state transition function, so they must be activated at the same
+    /// height by every node. If this is `None`, withdrawal requests are ignored.
+    pub fee_withdrawal_start_block: Option<u64>,
 
     /// Map containing all planned and executed upgrades.
     ///
@@ -82,6 +87,18 @@ impl MembershipPersistence for NoStorage {
     ) -> anyhow::Result<()> {
         Ok(())
     }
+
+    async fn load_events(&self) -> anyhow::Result<Option<(u64, Vec<IndexedStakeTableEvent>)>> {
+        Ok(None)
+    }
+
+    async fn store_events(
+        &self,
+        _l1_block: u64,
+        _events: Vec<IndexedStakeTableEvent>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
 
 impl NodeState {
@@ -107,6 +124,7 @@ impl NodeState {
             upgrades: Default::default(),
             current_version,
             epoch_height: None,
+            fee_withdrawal_start_block: None,
             coordinator,
         }
     }
@@ -251,6 +269,17 @@ impl NodeState {
         self.epoch_height = Some(epoch_height);
         self
     }
+
+    pub fn with_fee_withdrawal_start_block(mut self, start_block: Option<u64>) -> Self {
+        self.fee_withdrawal_start_block = start_block;
+        self
+    }
+
+    /// Whether L1 fee withdrawal requests are honored in the block at `height`.
+    pub fn fee_withdrawals_enabled(&self, height: u64) -> bool {
+        self.fee_withdrawal_start_block
+            .is_some_and(|start_block| height >= start_block)
+    }
 }
 
 // This allows us to turn on `Default` on InstanceState trait
@@ -9,6 +9,7 @@ use std::{
 };
 
 use alloy::{
+    contract::Event,
     eips::BlockId,
     hex,
     primitives::{Address, B256, U256},
@@ -16,8 +17,9 @@ use alloy::{
     rpc::{
         client::RpcClient,
         json_rpc::{RequestPacket, ResponsePacket},
-        types::Block,
+        types::{Block, Log},
     },
+    sol_types::SolEvent,
     transports::{http::Http, RpcError, TransportErrorKind},
 };
 use anyhow::Context;
@@ -29,7 +31,9 @@ use futures::{
     stream::{self, StreamExt},
 };
 use hotshot::types::BLSPubKey;
-use hotshot_contract_adapter::sol_types::{FeeContract, StakeTable};
+use hotshot_contract_adapter::sol_types::{
+    Deposit, FeeContractWithdrawals::WithdrawalRequested, StakeTable, StakeTableSelectionPolicy,
+};
 use hotshot_types::traits::metrics::Metrics;
 use indexmap::IndexMap;
 use lru::LruCache;
@@ -45,7 +49,10 @@ use url::Url;
 
 use super::{
     active_validator_set_from_l1_events,
-    v0_1::{L1BlockInfoWithParent, SingleTransport, SingleTransportStatus, SwitchingTransport},
+    v0_1::{
+        L1BlockInfoWithParent, L1Deposit, L1WithdrawalRequest, SingleTransport,
+        SingleTransportStatus, SwitchingTransport,
+    },
     v0_3::Validator,
     validators_from_l1_events, L1BlockInfo, L1ClientMetrics, L1State, L1UpdateTask,
     StakeTableEvent,
@@ -833,8 +840,71 @@ impl L1Client {
         prev_finalized: Option<u64>,
         new_finalized: u64,
     ) -> Vec<FeeInfo> {
+        self.get_finalized_deposit_events(fee_contract_address, prev_finalized, new_finalized)
+            .await
+            .into_iter()
+            .map(|deposit| deposit.fee_info)
+            .collect()
+    }
+
+    /// Get each `Deposit` occurring between `prev` and `new`, along with
+    /// the L1 event which made it.
+    pub async fn get_finalized_deposit_events(
+        &self,
+        fee_contract_address: Address,
+        prev_finalized: Option<u64>,
+        new_finalized: u64,
+    ) -> Vec<L1Deposit> {
+        self.get_finalized_fee_contract_events::<Deposit>(
+            fee_contract_address,
+            prev_finalized,
+            new_finalized,
+        )
+        .await
+        .into_iter()
+        .map(|(deposit, log)| L1Deposit {
+            fee_info: FeeInfo::from(deposit),
+            l1_block: log.block_number,
+            l1_tx_hash: log.transaction_hash,
+            l1_log_index: log.log_index,
+        })
+        .collect()
+    }
+
+    /// Get each withdrawal request occurring between `prev` and `new`,
+    /// along with the L1 event which made it.
+    pub async fn get_finalized_withdrawal_requests(
+        &self,
+        fee_contract_address: Address,
+        prev_finalized: Option<u64>,
+        new_finalized: u64,
+    ) -> Vec<L1WithdrawalRequest> {
+        self.get_finalized_fee_contract_events::<WithdrawalRequested>(
+            fee_contract_address,
+            prev_finalized,
+            new_finalized,
+        )
+        .await
+        .into_iter()
+        .map(|(request, log)| L1WithdrawalRequest {
+            fee_info: FeeInfo::new(request.user, request.amount),
+            l1_block: log.block_number,
+            l1_tx_hash: log.transaction_hash,
+            l1_log_index: log.log_index,
+        })
+        .collect()
+    }
+
+    /// Get each fee contract event of type `E` occurring between `prev`
+    /// and `new`.
+    async fn get_finalized_fee_contract_events<E: SolEvent>(
+        &self,
+        fee_contract_address: Address,
+        prev_finalized: Option<u64>,
+        new_finalized: u64,
+    ) -> Vec<(E, Log)> {
         // No new blocks have been finalized, therefore there are no
-        // new deposits.
+        // new events.
         if prev_finalized >= Some(new_finalized) {
             return vec![];
         }
@@ -864,20 +934,13 @@ impl L1Client {
         // Fetch events for each chunk.
         let events = stream::iter(chunks).then(|(from, to)| {
             let retry_delay = opt.l1_retry_delay;
-            let fee_contract = FeeContract::new(fee_contract_address, self.provider.clone());
+            let filter = Event::<_, E>::new_sol(self.provider.clone(), &fee_contract_address);
             async move {
-                tracing::debug!(from, to, "fetch events in range");
+                tracing::debug!(from, to, event = E::SIGNATURE, "fetch events in range");
 
-                // query for deposit events, loop until successful.
+                // query for events, loop until successful.
                 loop {
-                    match fee_contract
-                        .Deposit_filter()
-                        .address(*fee_contract.address())
-                        .from_block(from)
-                        .to_block(to)
-                        .query()
-                        .await
-                    {
+                    match filter.clone().from_block(from).to_block(to).query().await {
                         Ok(events) => break stream::iter(events),
                         Err(err) => {
                             tracing::warn!(from, to, %err, "Fee L1Event Error");
@@ -887,11 +950,7 @@ impl L1Client {
                 }
             }
         });
-        events
-            .flatten()
-            .map(|(deposit, _)| FeeInfo::from(deposit))
-            .collect()
-            .await
+        events.flatten().collect().await
     }
 
     /// Fetch all stake table events from L1
@@ -917,6 +976,19 @@ impl L1Client {
             }
         };
 
+        self.fetch_stake_table_events_in_range(contract, from_block, to_block)
+            .await
+    }
+
+    /// Fetch stake table events emitted in the L1 blocks `from_block..=to_block`.
+    pub async fn fetch_stake_table_events_in_range(
+        &self,
+        contract: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> anyhow::Result<BTreeMap<(u64, u64), StakeTableEvent>> {
+        let stake_table_contract = StakeTable::new(contract, self.provider.clone());
+
         // To avoid making large RPC calls, divide the range into smaller chunks.
         // chunk size is from env "ESPRESSO_SEQUENCER_L1_EVENTS_MAX_BLOCK_RANGE
         // default value  is `10000` if env variable is not set
@@ -946,7 +1018,7 @@ impl L1Client {
                         .clone()
                         .ValidatorRegistered_filter()
                         .from_block(from)
-                        .to_block(to_block)
+                        .to_block(to)
                         .query()
                         .await
                     {
@@ -970,7 +1042,7 @@ impl L1Client {
                     match stake_table_contract
                         .ValidatorExit_filter()
                         .from_block(from)
-                        .to_block(to_block)
+                        .to_block(to)
                         .query()
                         .await
                     {
@@ -994,7 +1066,7 @@ impl L1Client {
                     match stake_table_contract
                         .Delegated_filter()
                         .from_block(from)
-                        .to_block(to_block)
+                        .to_block(to)
                         .query()
                         .await
                     {
@@ -1017,7 +1089,7 @@ impl L1Client {
                     match stake_table_contract
                         .Undelegated_filter()
                         .from_block(from)
-                        .to_block(to_block)
+                        .to_block(to)
                         .query()
                         .await
                     {
@@ -1032,7 +1104,7 @@ impl L1Client {
         });
 
         // fetch consensus keys updated events
-        let keys_update_events = stream::iter(chunks).then(|(from, to)| {
+        let keys_update_events = stream::iter(chunks.clone()).then(|(from, to)| {
             let retry_delay = self.options().l1_retry_delay;
             let stake_table_contract = stake_table_contract.clone();
             async move {
@@ -1041,7 +1113,7 @@ impl L1Client {
                     match stake_table_contract
                         .ConsensusKeysUpdated_filter()
                         .from_block(from)
-                        .to_block(to_block)
+                        .to_block(to)
                         .query()
                         .await
                     {
@@ -1055,11 +1127,40 @@ impl L1Client {
             }
         });
 
+        // fetch validator selection policy updated events
+        let policy_update_events = stream::iter(chunks).then(|(from, to)| {
+            let retry_delay = self.options().l1_retry_delay;
+            let policy_contract = StakeTableSelectionPolicy::new(contract, self.provider.clone());
+            async move {
+                tracing::debug!(
+                    from,
+                    to,
+                    "fetch ValidatorSelectionPolicyUpdated events in range"
+                );
+                loop {
+                    match policy_contract
+                        .ValidatorSelectionPolicyUpdated_filter()
+                        .from_block(from)
+                        .to_block(to)
+                        .query()
+                        .await
+                    {
+                        Ok(events) => break stream::iter(events),
+                        Err(err) => {
+                            tracing::warn!(from, to, %err, "ValidatorSelectionPolicyUpdated Error");
+                            sleep(retry_delay).await;
+                        },
+                    }
+                }
+            }
+        });
+
         let registered = registered_events.flatten().collect().await;
         let deregistered = deregistered_events.flatten().collect().await;
         let delegated = delegated_events.flatten().collect().await;
         let undelegated = undelegated_events.flatten().collect().await;
         let keys_update = keys_update_events.flatten().collect().await;
+        let policy_updates = policy_update_events.flatten().collect().await;
 
         // Sort all events by log index and log block number for correct order.
         StakeTableEvent::sort_events(
@@ -1068,12 +1169,14 @@ impl L1Client {
             delegated,
             undelegated,
             keys_update,
+            policy_updates,
         )
     }
 
     /// Get `StakeTable` at specific l1 block height.
     /// This function fetches and processes various events (ValidatorRegistered, ValidatorExit,
-    /// Delegated, Undelegated, and ConsensusKeysUpdated) within the block range from the
+    /// Delegated, Undelegated, ConsensusKeysUpdated and ValidatorSelectionPolicyUpdated) within
+    /// the block range from the
     /// contract's initialization block to the provided `to_block` value.
     /// Events are fetched in chunks to and retries are implemented for failed requests.
     pub async fn fetch_stake_table(
@@ -1204,6 +1307,7 @@ mod test {
         primitives::utils::parse_ether,
         providers::layers::AnvilProvider,
     };
+    use hotshot_contract_adapter::sol_types::FeeContract;
     use portpicker::pick_unused_port;
     use sequencer_utils::{
         deployer::{deploy_fee_contract_proxy, Contracts},
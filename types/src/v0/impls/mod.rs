@@ -18,8 +18,9 @@ pub use fee_info::{retain_accounts, FeeError};
 #[cfg(any(test, feature = "testing"))]
 pub use instance_state::mock;
 pub use instance_state::NodeState;
+pub use reward::retain_reward_accounts;
 pub use stake_table::*;
 pub use state::{
-    get_l1_deposits, BuilderValidationError, ProposalValidationError, StateValidationError,
-    ValidatedState,
+    get_l1_deposits, get_l1_withdrawal_requests, withdrawal_accounts, BuilderValidationError,
+    ProposalValidationError, StateValidationError, ValidatedState,
 };
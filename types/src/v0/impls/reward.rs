@@ -28,8 +28,8 @@ use sequencer_utils::{
 use super::{
     v0_1::{
         block_reward, RewardAccount, RewardAccountProof, RewardAccountQueryData, RewardAmount,
-        RewardInfo, RewardMerkleCommitment, RewardMerkleProof, RewardMerkleTree,
-        COMMISSION_BASIS_POINTS,
+        RewardDistribution, RewardInfo, RewardMerkleCommitment, RewardMerkleProof,
+        RewardMerkleTree, COMMISSION_BASIS_POINTS,
     },
     v0_3::Validator,
     Leaf2, NodeState, ValidatedState,
@@ -314,9 +314,46 @@ impl From<(RewardAccountProof, U256)> for RewardAccountQueryData {
     }
 }
 
+/// Get a partial snapshot of the given reward state, which contains only the specified accounts.
+///
+/// Fails if one of the requested accounts is not represented in the original `state`.
+pub fn retain_reward_accounts(
+    state: &RewardMerkleTree,
+    accounts: impl IntoIterator<Item = RewardAccount>,
+) -> anyhow::Result<RewardMerkleTree> {
+    let mut snapshot = RewardMerkleTree::from_commitment(state.commitment());
+    for account in accounts {
+        match state.universal_lookup(account) {
+            LookupResult::Ok(elem, proof) => {
+                // This remember cannot fail, since we just constructed a valid proof, and are
+                // remembering into a tree with the same commitment.
+                snapshot.remember(account, *elem, proof).unwrap();
+            },
+            LookupResult::NotFound(proof) => {
+                // Likewise this cannot fail.
+                snapshot.non_membership_remember(account, proof).unwrap()
+            },
+            LookupResult::NotInMemory => {
+                bail!("missing account {account}");
+            },
+        }
+    }
+
+    Ok(snapshot)
+}
+
 pub fn apply_rewards(
-    mut reward_state: RewardMerkleTree,
+    reward_state: RewardMerkleTree,
     validator: Validator<BLSPubKey>,
+) -> anyhow::Result<RewardMerkleTree> {
+    let distribution = compute_reward_distribution(&validator)?;
+    apply_reward_distribution(reward_state, &distribution)
+}
+
+/// Credit each account in `distribution` with its share of the block reward.
+pub fn apply_reward_distribution(
+    mut reward_state: RewardMerkleTree,
+    distribution: &RewardDistribution,
 ) -> anyhow::Result<RewardMerkleTree> {
     let mut update_balance = |account: &RewardAccount, amount: RewardAmount| {
         let mut err = None;
@@ -338,10 +375,9 @@ pub fn apply_rewards(
         Ok::<(), anyhow::Error>(())
     };
 
-    let computed_rewards = compute_rewards(validator)?;
-    for (address, reward) in computed_rewards {
-        update_balance(&RewardAccount(address), reward)?;
-        tracing::debug!("applied rewards address={address} reward={reward}",);
+    for (account, reward) in distribution.rewards() {
+        update_balance(&account, reward)?;
+        tracing::debug!("applied rewards address={account} reward={reward}",);
     }
     Ok(reward_state)
 }
@@ -349,12 +385,22 @@ pub fn apply_rewards(
 pub fn compute_rewards(
     validator: Validator<BLSPubKey>,
 ) -> anyhow::Result<Vec<(alloy::primitives::Address, RewardAmount)>> {
+    Ok(compute_reward_distribution(&validator)?
+        .rewards()
+        .map(|(account, reward)| (account.address(), reward))
+        .collect())
+}
+
+/// Split the block reward between `validator` and its delegators.
+pub fn compute_reward_distribution(
+    validator: &Validator<BLSPubKey>,
+) -> anyhow::Result<RewardDistribution> {
     ensure!(
         validator.commission <= COMMISSION_BASIS_POINTS,
         "commission must not exceed {COMMISSION_BASIS_POINTS}"
     );
 
-    let mut rewards = Vec::new();
+    let mut delegators = Vec::new();
 
     let total_reward = block_reward().0;
     let delegators_ratio_basis_points = U256::from(COMMISSION_BASIS_POINTS)
@@ -380,16 +426,38 @@ pub fn compute_rewards(
 
         delegators_rewards_distributed += delegator_reward.0;
 
-        rewards.push((*delegator_address, delegator_reward));
+        delegators.push((RewardAccount(*delegator_address), delegator_reward));
     }
 
     let leader_reward = total_reward
         .checked_sub(delegators_rewards_distributed)
         .context("overflow")?;
-    rewards.push((validator.account, leader_reward.into()));
 
-    Ok(rewards)
+    Ok(RewardDistribution {
+        validator: RewardAccount(validator.account),
+        commission: leader_reward.into(),
+        delegators,
+    })
+}
+
+impl RewardDistribution {
+    /// All the payments made by this distribution, delegators first and then the validator.
+    ///
+    /// An account which is both the validator and one of its delegators appears twice.
+    pub fn rewards(&self) -> impl '_ + Iterator<Item = (RewardAccount, RewardAmount)> {
+        self.delegators
+            .iter()
+            .copied()
+            .chain([(self.validator, self.commission)])
+    }
+
+    /// The total amount paid out by this distribution.
+    pub fn total(&self) -> RewardAmount {
+        self.rewards()
+            .fold(RewardAmount::default(), |total, (_, reward)| total + reward)
+    }
 }
+
 /// Checks whether the given height belongs to the first or second epoch. or
 /// the Genesis epoch (EpochNumber::new(0))
 ///
@@ -528,4 +596,36 @@ pub mod tests {
             .to_string()
             .contains("must not exceed"));
     }
+
+    #[test]
+    fn test_reward_distribution() {
+        let mut validator = Validator::mock();
+        validator.commission = 500;
+        let distribution = compute_reward_distribution(&validator).unwrap();
+
+        assert_eq!(distribution.validator, RewardAccount(validator.account));
+        assert_eq!(distribution.total(), block_reward());
+        assert_eq!(
+            distribution
+                .delegators
+                .iter()
+                .map(|(account, _)| account.address())
+                .collect::<Vec<_>>(),
+            validator.delegators.keys().copied().collect::<Vec<_>>()
+        );
+
+        // The commission is at least the validator's share, plus any remainder from rounding.
+        let commission_share = block_reward().0 * U256::from(validator.commission)
+            / U256::from(COMMISSION_BASIS_POINTS);
+        assert!(distribution.commission.0 >= commission_share);
+
+        // The distribution agrees with the flat list of rewards.
+        assert_eq!(
+            compute_rewards(validator).unwrap(),
+            distribution
+                .rewards()
+                .map(|(account, reward)| (account.address(), reward))
+                .collect::<Vec<_>>()
+        );
+    }
 }
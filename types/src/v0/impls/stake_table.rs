@@ -5,15 +5,19 @@ use std::{
 };
 
 use alloy::{
-    primitives::{Address, U256},
+    primitives::{Address, LogData, U256},
     rpc::types::Log,
+    sol_types::SolEvent,
 };
 use anyhow::{bail, Context};
 use async_lock::RwLock;
 use committable::Committable;
 use hotshot::types::{BLSPubKey, SchnorrPubKey, SignatureKey as _};
-use hotshot_contract_adapter::sol_types::StakeTable::{
-    ConsensusKeysUpdated, Delegated, Undelegated, ValidatorExit, ValidatorRegistered,
+use hotshot_contract_adapter::sol_types::{
+    StakeTable::{
+        ConsensusKeysUpdated, Delegated, Undelegated, ValidatorExit, ValidatorRegistered,
+    },
+    StakeTableSelectionPolicy::ValidatorSelectionPolicyUpdated,
 };
 use hotshot_types::{
     data::{vid_disperse::VID_TARGET_TOTAL_STAKE, EpochNumber},
@@ -166,17 +170,74 @@ pub(crate) fn validators_from_l1_events<I: Iterator<Item = StakeTableEvent>>(
                 validator.stake_table_key = bls;
                 validator.state_ver_key = state_ver_key;
             },
+            // The selection policy does not affect which validators are registered.
+            StakeTableEvent::PolicyUpdate(_) => {},
         }
     }
 
     Ok(validators)
 }
 
+/// Rules for selecting the active validators of an epoch from all registered validators.
+///
+/// The policy is set by the owner of the stake table contract, and the most recent
+/// `ValidatorSelectionPolicyUpdated` event applies. Until the owner sets a policy, the default
+/// policy is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidatorSelectionPolicy {
+    /// The maximum number of validators in the active set.
+    ///
+    /// If more validators are eligible, the ones with the most stake are selected.
+    pub max_validators: u64,
+
+    /// Validators with less than `1 / min_stake_ratio` of the stake of the largest validator are
+    /// not selected. If this is zero, there is no minimum stake.
+    pub min_stake_ratio: u64,
+
+    /// The minimum amount a validator must delegate to itself to be selected.
+    pub min_self_stake: U256,
+}
+
+impl Default for ValidatorSelectionPolicy {
+    fn default() -> Self {
+        Self {
+            max_validators: 100,
+            min_stake_ratio: VID_TARGET_TOTAL_STAKE.into(),
+            min_self_stake: U256::ZERO,
+        }
+    }
+}
+
+impl From<ValidatorSelectionPolicyUpdated> for ValidatorSelectionPolicy {
+    fn from(event: ValidatorSelectionPolicyUpdated) -> Self {
+        Self {
+            max_validators: event.maxValidators,
+            min_stake_ratio: event.minStakeRatio,
+            min_self_stake: event.minSelfStake,
+        }
+    }
+}
+
+/// Extract the validator selection policy in effect after the L1 stake table events.
+pub(crate) fn selection_policy_from_l1_events<'a, I: Iterator<Item = &'a StakeTableEvent>>(
+    events: I,
+) -> ValidatorSelectionPolicy {
+    events
+        .filter_map(|event| match event {
+            StakeTableEvent::PolicyUpdate(update) => Some(update.clone().into()),
+            _ => None,
+        })
+        .last()
+        .unwrap_or_default()
+}
+
 /// Select active validators
 ///
-/// Removes the validators without stake and selects the top 100 staked validators.
+/// Removes the validators without stake or without enough self stake, and selects the validators
+/// with the most stake, according to `policy`.
 pub(crate) fn select_active_validator_set(
     validators: &mut IndexMap<Address, Validator<BLSPubKey>>,
+    policy: &ValidatorSelectionPolicy,
 ) -> anyhow::Result<()> {
     // Remove invalid validators first
     validators.retain(|address, validator| {
@@ -190,6 +251,19 @@ pub(crate) fn select_active_validator_set(
             return false;
         }
 
+        let self_stake = validator
+            .delegators
+            .get(address)
+            .copied()
+            .unwrap_or_default();
+        if self_stake < policy.min_self_stake {
+            tracing::info!(
+                "Validator {address:?} self stake {self_stake} is below the minimum {}",
+                policy.min_self_stake
+            );
+            return false;
+        }
+
         true
     });
 
@@ -204,9 +278,10 @@ pub(crate) fn select_active_validator_set(
         .max()
         .context("Failed to determine max stake")?;
 
+    // A ratio of zero means there is no minimum stake.
     let minimum_stake = maximum_stake
-        .checked_div(U256::from(VID_TARGET_TOTAL_STAKE))
-        .context("div err")?;
+        .checked_div(U256::from(policy.min_stake_ratio))
+        .unwrap_or_default();
 
     // Collect validators that meet the minimum stake criteria
     let mut valid_stakers: Vec<_> = validators
@@ -218,9 +293,10 @@ pub(crate) fn select_active_validator_set(
     // Sort by stake (descending order)
     valid_stakers.sort_by_key(|(_, stake)| std::cmp::Reverse(*stake));
 
-    // Keep only the top 100 stakers
-    if valid_stakers.len() > 100 {
-        valid_stakers.truncate(100);
+    // Keep only the top stakers
+    let max_validators = usize::try_from(policy.max_validators).unwrap_or(usize::MAX);
+    if valid_stakers.len() > max_validators {
+        valid_stakers.truncate(max_validators);
     }
 
     // Retain only the selected validators
@@ -234,20 +310,43 @@ pub(crate) fn select_active_validator_set(
 pub(crate) fn active_validator_set_from_l1_events<I: Iterator<Item = StakeTableEvent>>(
     events: I,
 ) -> anyhow::Result<IndexMap<Address, Validator<BLSPubKey>>> {
-    let mut validators = validators_from_l1_events(events)?;
-    select_active_validator_set(&mut validators)?;
+    let events = events.collect::<Vec<_>>();
+    let policy = selection_policy_from_l1_events(events.iter());
+    let mut validators = validators_from_l1_events(events.into_iter())?;
+    select_active_validator_set(&mut validators, &policy)?;
     Ok(validators)
 }
 
-#[derive(Clone, derive_more::From)]
+/// The stake required for a QC to be valid, given the total stake of the committee.
+pub(crate) fn success_threshold(total_stake: U256) -> U256 {
+    let one = U256::ONE;
+    let two = U256::from(2);
+    let three = U256::from(3);
+    if total_stake < U256::MAX / two {
+        ((total_stake * two) / three) + one
+    } else {
+        ((total_stake / three) * two) + two
+    }
+}
+
+/// An event emitted by the stake table contract.
+///
+/// Events are serialized as the L1 log they were decoded from, so that they can be persisted and
+/// compared with freshly fetched events.
+#[derive(Clone, derive_more::From, serde::Serialize, serde::Deserialize)]
+#[serde(into = "LogData", try_from = "LogData")]
 pub enum StakeTableEvent {
     Register(ValidatorRegistered),
     Deregister(ValidatorExit),
     Delegate(Delegated),
     Undelegate(Undelegated),
     KeyUpdate(ConsensusKeysUpdated),
+    PolicyUpdate(ValidatorSelectionPolicyUpdated),
 }
 
+/// A stake table event, keyed by the L1 block number and log index at which it was emitted.
+pub type IndexedStakeTableEvent = ((u64, u64), StakeTableEvent);
+
 impl std::fmt::Debug for StakeTableEvent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -256,6 +355,50 @@ impl std::fmt::Debug for StakeTableEvent {
             StakeTableEvent::Delegate(event) => write!(f, "Delegate({:?})", event.delegator),
             StakeTableEvent::Undelegate(event) => write!(f, "Undelegate({:?})", event.delegator),
             StakeTableEvent::KeyUpdate(event) => write!(f, "KeyUpdate({:?})", event.account),
+            StakeTableEvent::PolicyUpdate(event) => write!(
+                f,
+                "PolicyUpdate({}, {}, {})",
+                event.maxValidators, event.minStakeRatio, event.minSelfStake
+            ),
+        }
+    }
+}
+
+impl From<StakeTableEvent> for LogData {
+    fn from(event: StakeTableEvent) -> Self {
+        match event {
+            StakeTableEvent::Register(event) => event.encode_log_data(),
+            StakeTableEvent::Deregister(event) => event.encode_log_data(),
+            StakeTableEvent::Delegate(event) => event.encode_log_data(),
+            StakeTableEvent::Undelegate(event) => event.encode_log_data(),
+            StakeTableEvent::KeyUpdate(event) => event.encode_log_data(),
+            StakeTableEvent::PolicyUpdate(event) => event.encode_log_data(),
+        }
+    }
+}
+
+impl TryFrom<LogData> for StakeTableEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(log: LogData) -> anyhow::Result<Self> {
+        let topic = *log
+            .topics()
+            .first()
+            .context("stake table event has no topics")?;
+        if topic == ValidatorRegistered::SIGNATURE_HASH {
+            Ok(ValidatorRegistered::decode_log_data(&log, true)?.into())
+        } else if topic == ValidatorExit::SIGNATURE_HASH {
+            Ok(ValidatorExit::decode_log_data(&log, true)?.into())
+        } else if topic == Delegated::SIGNATURE_HASH {
+            Ok(Delegated::decode_log_data(&log, true)?.into())
+        } else if topic == Undelegated::SIGNATURE_HASH {
+            Ok(Undelegated::decode_log_data(&log, true)?.into())
+        } else if topic == ConsensusKeysUpdated::SIGNATURE_HASH {
+            Ok(ConsensusKeysUpdated::decode_log_data(&log, true)?.into())
+        } else if topic == ValidatorSelectionPolicyUpdated::SIGNATURE_HASH {
+            Ok(ValidatorSelectionPolicyUpdated::decode_log_data(&log, true)?.into())
+        } else {
+            bail!("unknown stake table event {topic}")
         }
     }
 }
@@ -267,6 +410,7 @@ impl StakeTableEvent {
         delegations: Vec<(Delegated, Log)>,
         undelegated_events: Vec<(Undelegated, Log)>,
         keys_update: Vec<(ConsensusKeysUpdated, Log)>,
+        policy_updates: Vec<(ValidatorSelectionPolicyUpdated, Log)>,
     ) -> anyhow::Result<BTreeMap<(u64, u64), StakeTableEvent>> {
         let mut map = BTreeMap::new();
         for (registration, log) in registrations {
@@ -315,6 +459,16 @@ impl StakeTableEvent {
                 update.into(),
             );
         }
+
+        for (update, log) in policy_updates {
+            map.insert(
+                (
+                    log.block_number.context("block number")?,
+                    log.log_index.context("log index")?,
+                ),
+                update.into(),
+            );
+        }
         Ok(map)
     }
 }
@@ -572,25 +726,159 @@ impl EpochCommittees {
         }
     }
 
-    /// Get the stake table by epoch. Try to load from DB and fall back to fetching from l1.
+    /// Get the stake table at `l1_block` from the stake table events on L1.
     async fn get_stake_table_from_l1(
         &self,
         contract_address: Address,
         l1_block: u64,
     ) -> Result<IndexMap<alloy::primitives::Address, Validator<BLSPubKey>>, GetStakeTablesError>
     {
-        self.l1_client
-            .fetch_stake_table(contract_address, l1_block)
+        let events = self
+            .get_stake_table_events(contract_address, l1_block)
             .await
-            .map_err(GetStakeTablesError::L1ClientFetchError)
+            .map_err(GetStakeTablesError::L1ClientFetchError)?;
+        active_validator_set_from_l1_events(events.into_values())
+            .map_err(GetStakeTablesError::InvalidEvents)
+    }
+
+    /// Get all stake table events up to `l1_block`.
+    ///
+    /// Events are cached in persistence, so only events after the last cached L1 block are fetched
+    /// from L1. To avoid caching events which could be reorged out, newly fetched events are only
+    /// cached if our L1 client agrees that `l1_block` is finalized.
+    async fn get_stake_table_events(
+        &self,
+        contract_address: Address,
+        l1_block: u64,
+    ) -> anyhow::Result<BTreeMap<(u64, u64), StakeTableEvent>> {
+        let cached = self.persistence.load_events().await.unwrap_or_else(|err| {
+            tracing::warn!("failed to load cached stake table events: {err:#}");
+            None
+        });
+        let Some((cached_l1_block, cached_events)) = cached else {
+            let events = self
+                .l1_client
+                .fetch_stake_table_events(contract_address, l1_block)
+                .await?;
+            self.cache_stake_table_events(l1_block, &events).await;
+            return Ok(events);
+        };
+
+        let mut events: BTreeMap<_, _> = cached_events.into_iter().collect();
+        if l1_block <= cached_l1_block {
+            tracing::debug!(l1_block, cached_l1_block, "using cached stake table events");
+            events.retain(|(block, _), _| *block <= l1_block);
+            return Ok(events);
+        }
+
+        tracing::debug!(
+            from = cached_l1_block + 1,
+            to = l1_block,
+            "fetching new stake table events"
+        );
+        let new_events = self
+            .l1_client
+            .fetch_stake_table_events_in_range(contract_address, cached_l1_block + 1, l1_block)
+            .await?;
+        self.cache_stake_table_events(l1_block, &new_events).await;
+        events.extend(new_events);
+        Ok(events)
+    }
+
+    /// Cache stake table events fetched up to `l1_block`, if `l1_block` is finalized.
+    async fn cache_stake_table_events(
+        &self,
+        l1_block: u64,
+        events: &BTreeMap<(u64, u64), StakeTableEvent>,
+    ) {
+        let finalized = self.l1_client.snapshot().await.finalized;
+        if finalized.is_none_or(|finalized| finalized.number() < l1_block) {
+            tracing::info!(
+                l1_block,
+                ?finalized,
+                "not caching stake table events from unfinalized L1 blocks"
+            );
+            return;
+        }
+        let events = events
+            .iter()
+            .map(|(key, event)| (*key, event.clone()))
+            .collect();
+        if let Err(err) = self.persistence.store_events(l1_block, events).await {
+            tracing::warn!(l1_block, "failed to cache stake table events: {err:#}");
+        }
     }
 }
 
+/// Check the stake table events cached in `persistence` against a fresh scan of the L1.
+///
+/// All events emitted by the stake table `contract` up to the last cached L1 block are fetched and
+/// compared with the cached events. Fails if the events differ, in which case the cache should be
+/// cleared.
+pub async fn verify_stake_table_events(
+    l1_client: &L1Client,
+    persistence: &impl MembershipPersistence,
+    contract: Address,
+) -> anyhow::Result<()> {
+    let Some((l1_block, cached)) = persistence.load_events().await? else {
+        tracing::info!("no cached stake table events to verify");
+        return Ok(());
+    };
+    tracing::info!(
+        l1_block,
+        count = cached.len(),
+        "verifying cached stake table events"
+    );
+
+    let cached: BTreeMap<_, _> = cached
+        .into_iter()
+        .map(|(key, event)| (key, LogData::from(event)))
+        .collect();
+    let fetched: BTreeMap<_, _> = l1_client
+        .fetch_stake_table_events(contract, l1_block)
+        .await?
+        .into_iter()
+        .map(|(key, event)| (key, LogData::from(event)))
+        .collect();
+
+    for (key, event) in &fetched {
+        match cached.get(key) {
+            Some(cached_event) if cached_event == event => {},
+            Some(cached_event) => bail!(
+                "cached stake table event at L1 block {}, log {} does not match L1: {cached_event:?} != {event:?}",
+                key.0,
+                key.1
+            ),
+            None => bail!(
+                "stake table event at L1 block {}, log {} is missing from the cache",
+                key.0,
+                key.1
+            ),
+        }
+    }
+    if let Some((key, _)) = cached.iter().find(|(key, _)| !fetched.contains_key(key)) {
+        bail!(
+            "cached stake table event at L1 block {}, log {} does not exist on L1",
+            key.0,
+            key.1
+        );
+    }
+
+    tracing::info!(
+        l1_block,
+        count = fetched.len(),
+        "cached stake table events are valid"
+    );
+    Ok(())
+}
+
 #[derive(Error, Debug)]
 /// Error representing fail cases for retrieving the stake table.
 enum GetStakeTablesError {
     #[error("Error fetching from L1: {0}")]
     L1ClientFetchError(anyhow::Error),
+    #[error("Invalid stake table events: {0}")]
+    InvalidEvents(anyhow::Error),
 }
 
 #[derive(Error, Debug)]
@@ -724,15 +1012,7 @@ impl Membership<SeqTypes> for EpochCommittees {
 
     /// Get the voting success threshold for the committee
     fn success_threshold(&self, epoch: Option<Epoch>) -> U256 {
-        let total_stake = self.total_stake(epoch);
-        let one = U256::ONE;
-        let two = U256::from(2);
-        let three = U256::from(3);
-        if total_stake < U256::MAX / two {
-            ((total_stake * two) / three) + one
-        } else {
-            ((total_stake / three) * two) + two
-        }
+        success_threshold(self.total_stake(epoch))
     }
 
     /// Get the voting success threshold for the committee
@@ -1169,6 +1449,165 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_from_l1_events_with_selection_policy() -> anyhow::Result<()> {
+        setup_test();
+
+        // Register three validators. Each delegates to itself, and one also has an external
+        // delegator.
+        let vals = [
+            TestValidator::random(),
+            TestValidator::random(),
+            TestValidator::random(),
+        ];
+        let delegator = Address::random();
+        let mut events: Vec<StakeTableEvent> = vec![];
+        for (val, self_stake) in vals.iter().zip([100, 50, 10]) {
+            events.push(
+                ValidatorRegistered {
+                    account: val.account,
+                    blsVk: val.bls_vk.clone().into(),
+                    schnorrVk: val.schnorr_vk.clone().into(),
+                    commission: val.commission,
+                }
+                .into(),
+            );
+            events.push(
+                Delegated {
+                    delegator: val.account,
+                    validator: val.account,
+                    amount: U256::from(self_stake),
+                }
+                .into(),
+            );
+        }
+        events.push(
+            Delegated {
+                delegator,
+                validator: vals[2].account,
+                amount: U256::from(60),
+            }
+            .into(),
+        );
+
+        // Append a policy update by the contract owner to the events.
+        let with_policy = |max_validators: u64, min_stake_ratio: u64, min_self_stake: u64| {
+            let mut events = events.clone();
+            events.push(
+                ValidatorSelectionPolicyUpdated {
+                    maxValidators: max_validators,
+                    minStakeRatio: min_stake_ratio,
+                    minSelfStake: U256::from(min_self_stake),
+                }
+                .into(),
+            );
+            events
+        };
+
+        // Without a policy update, the default policy selects all validators.
+        assert_eq!(
+            selection_policy_from_l1_events(events.iter()),
+            ValidatorSelectionPolicy::default()
+        );
+        let st = active_validator_set_from_l1_events(events.iter().cloned())?;
+        assert_eq!(st.len(), 3);
+
+        // Cap the number of validators: the validators with the most stake are selected.
+        let st = active_validator_set_from_l1_events(with_policy(2, 0, 0).into_iter())?;
+        assert_eq!(
+            st.keys().collect::<HashSet<_>>(),
+            HashSet::from([&vals[0].account, &vals[2].account])
+        );
+
+        // Require a minimum self stake: delegated stake does not count.
+        let st = active_validator_set_from_l1_events(with_policy(100, 0, 50).into_iter())?;
+        assert_eq!(
+            st.keys().collect::<HashSet<_>>(),
+            HashSet::from([&vals[0].account, &vals[1].account])
+        );
+
+        // Require a minimum fraction of the largest stake.
+        let st = active_validator_set_from_l1_events(with_policy(100, 1, 0).into_iter())?;
+        assert_eq!(st.keys().collect::<Vec<_>>(), vec![&vals[0].account]);
+
+        // If no validator satisfies the policy, there is no valid stake table.
+        assert!(
+            active_validator_set_from_l1_events(with_policy(100, 0, 1000).into_iter()).is_err()
+        );
+
+        // Only the most recent policy applies, wherever it appears among the other events.
+        let mut events = with_policy(1, 0, 0);
+        events.insert(
+            0,
+            ValidatorSelectionPolicyUpdated {
+                maxValidators: 100,
+                minStakeRatio: 0,
+                minSelfStake: U256::from(1000),
+            }
+            .into(),
+        );
+        let st = active_validator_set_from_l1_events(events.into_iter())?;
+        assert_eq!(st.keys().collect::<Vec<_>>(), vec![&vals[0].account]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stake_table_event_serde() {
+        let val = TestValidator::random();
+        let delegator = Address::random();
+        let events: Vec<StakeTableEvent> = vec![
+            ValidatorRegistered {
+                account: val.account,
+                blsVk: val.bls_vk.clone().into(),
+                schnorrVk: val.schnorr_vk.clone().into(),
+                commission: val.commission,
+            }
+            .into(),
+            Delegated {
+                delegator,
+                validator: val.account,
+                amount: U256::from(10),
+            }
+            .into(),
+            Undelegated {
+                delegator,
+                validator: val.account,
+                amount: U256::from(7),
+            }
+            .into(),
+            ConsensusKeysUpdated {
+                account: val.account,
+                blsVK: val.bls_vk.clone().into(),
+                schnorrVK: val.schnorr_vk.clone().into(),
+            }
+            .into(),
+            ValidatorExit {
+                validator: val.account,
+            }
+            .into(),
+            ValidatorSelectionPolicyUpdated {
+                maxValidators: 50,
+                minStakeRatio: 10,
+                minSelfStake: U256::from(1),
+            }
+            .into(),
+        ];
+
+        for event in events {
+            let bytes = bincode::serialize(&event).unwrap();
+            let decoded: StakeTableEvent = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(LogData::from(decoded), LogData::from(event));
+        }
+
+        // Logs which are not stake table events are rejected.
+        StakeTableEvent::try_from(LogData::new_unchecked(
+            vec![Default::default()],
+            vec![].into(),
+        ))
+        .unwrap_err();
+    }
+
     #[test]
     fn test_validators_selection() {
         let mut validators = IndexMap::new();
@@ -1185,7 +1624,8 @@ mod tests {
 
         let minimum_stake = highest_stake / U256::from(VID_TARGET_TOTAL_STAKE);
 
-        select_active_validator_set(&mut validators).expect("Failed to select validators");
+        select_active_validator_set(&mut validators, &Default::default())
+            .expect("Failed to select validators");
         assert!(
             validators.len() <= 100,
             "validators len is {}, expected at most 100",
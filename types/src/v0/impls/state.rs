@@ -29,10 +29,13 @@ use super::{
     auction::ExecutionError,
     fee_info::FeeError,
     instance_state::NodeState,
-    reward::{apply_rewards, find_validator_info, first_two_epochs},
+    reward::{
+        apply_reward_distribution, compute_reward_distribution, find_validator_info,
+        first_two_epochs,
+    },
     v0_1::{
-        RewardAccount, RewardAmount, RewardMerkleCommitment, RewardMerkleTree,
-        REWARD_MERKLE_TREE_HEIGHT,
+        FeeTransfer, L1Deposit, L1WithdrawalRequest, RewardAccount, RewardAmount,
+        RewardMerkleCommitment, RewardMerkleTree, REWARD_MERKLE_TREE_HEIGHT,
     },
     v0_3::Validator,
     BlockMerkleCommitment, BlockSize, EpochVersion, FeeMerkleCommitment, L1Client,
@@ -292,6 +295,48 @@ impl ValidatedState {
         for fee_info in fee_info {
             self.charge_fee(fee_info, recipient)?;
             delta.fees_delta.extend([fee_info.account, recipient]);
+            if fee_info.amount != FeeAmount::default() {
+                delta.fee_transfers.push(FeeTransfer {
+                    from: fee_info.account,
+                    to: recipient,
+                    amount: fee_info.amount,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Honor withdrawal requests made on L1.
+    ///
+    /// Each requested amount is moved from the requesting account to its
+    /// [withdrawal escrow](FeeAccount::withdrawal_escrow), from which it can be claimed on L1.
+    /// Requests for more than the balance of the account are ignored.
+    pub fn apply_withdrawals(
+        &mut self,
+        delta: &mut Delta,
+        requests: Vec<L1WithdrawalRequest>,
+    ) -> Result<(), FeeError> {
+        for request in requests {
+            let FeeInfo { account, amount } = request.fee_info;
+            if amount == FeeAmount::default() {
+                continue;
+            }
+            let escrow = account.withdrawal_escrow();
+            match self.charge_fee(request.fee_info, escrow) {
+                Ok(()) => {},
+                Err(FeeError::InsufficientFunds { balance, amount }) => {
+                    tracing::info!(
+                        %account,
+                        ?balance,
+                        %amount,
+                        "ignoring withdrawal request with insufficient funds"
+                    );
+                    continue;
+                },
+                Err(err) => return Err(err),
+            }
+            delta.fees_delta.extend([account, escrow]);
+            delta.fee_withdrawals.push(request);
         }
         Ok(())
     }
@@ -301,14 +346,16 @@ impl ValidatedState {
         delta: &mut Delta,
         validator: Validator<BLSPubKey>,
     ) -> anyhow::Result<()> {
-        let reward_state = apply_rewards(self.reward_merkle_tree.clone(), validator.clone())?;
+        let distribution = compute_reward_distribution(&validator)?;
+        let reward_state =
+            apply_reward_distribution(self.reward_merkle_tree.clone(), &distribution)?;
         self.reward_merkle_tree = reward_state;
 
         // Update delta rewards
-        delta.rewards_delta.insert(RewardAccount(validator.account));
         delta
             .rewards_delta
-            .extend(validator.delegators.keys().map(|d| RewardAccount(*d)));
+            .extend(distribution.rewards().map(|(account, _)| account));
+        delta.reward_distribution = Some(distribution);
 
         Ok(())
     }
@@ -813,15 +860,30 @@ impl ValidatedState {
             chain_config.fee_contract,
         )
         .await;
+        // Withdrawals are honored starting with the epoch version, once activated in genesis.
+        let l1_withdrawals = if version >= EpochVersion::version()
+            && instance.fee_withdrawals_enabled(proposed_header.height())
+        {
+            get_l1_withdrawal_requests(
+                instance,
+                proposed_header,
+                parent_leaf,
+                chain_config.fee_contract,
+            )
+            .await
+        } else {
+            vec![]
+        };
 
         // Find missing fee state entries. We will need to use the builder account which is paying a
         // fee and the recipient account which is receiving it, plus any counts receiving deposits
-        // in this block.
+        // or withdrawing in this block.
         let missing_accounts = self.forgotten_accounts(
             [chain_config.fee_recipient]
                 .into_iter()
                 .chain(proposed_header.fee_info().accounts())
-                .chain(l1_deposits.accounts()),
+                .chain(l1_deposits.iter().map(|deposit| deposit.fee_info.account))
+                .chain(withdrawal_accounts(&l1_withdrawals)),
         );
 
         let parent_height = parent_leaf.height();
@@ -872,13 +934,19 @@ impl ValidatedState {
         }
 
         let mut delta = Delta::default();
-        validated_state.apply_proposal(&mut delta, parent_leaf, l1_deposits);
+        validated_state.apply_proposal(
+            &mut delta,
+            parent_leaf,
+            l1_deposits.iter().map(|deposit| deposit.fee_info).collect(),
+        );
+        delta.l1_deposits = l1_deposits;
 
         validated_state.charge_fees(
             &mut delta,
             proposed_header.fee_info(),
             chain_config.fee_recipient,
         )?;
+        validated_state.apply_withdrawals(&mut delta, l1_withdrawals)?;
 
         // TODO(abdul): Change this to version >= EpochVersion::version()
         // when we deploy the permissionless contract in native demo
@@ -963,11 +1031,11 @@ pub async fn get_l1_deposits(
     header: &Header,
     parent_leaf: &Leaf2,
     fee_contract_address: Option<Address>,
-) -> Vec<FeeInfo> {
+) -> Vec<L1Deposit> {
     if let (Some(addr), Some(block_info)) = (fee_contract_address, header.l1_finalized()) {
         instance
             .l1_client
-            .get_finalized_deposits(
+            .get_finalized_deposit_events(
                 addr,
                 parent_leaf
                     .block_header()
@@ -981,6 +1049,40 @@ pub async fn get_l1_deposits(
     }
 }
 
+pub async fn get_l1_withdrawal_requests(
+    instance: &NodeState,
+    header: &Header,
+    parent_leaf: &Leaf2,
+    fee_contract_address: Option<Address>,
+) -> Vec<L1WithdrawalRequest> {
+    if let (Some(addr), Some(block_info)) = (fee_contract_address, header.l1_finalized()) {
+        instance
+            .l1_client
+            .get_finalized_withdrawal_requests(
+                addr,
+                parent_leaf
+                    .block_header()
+                    .l1_finalized()
+                    .map(|block_info| block_info.number),
+                block_info.number,
+            )
+            .await
+    } else {
+        vec![]
+    }
+}
+
+/// The fee accounts touched by honoring `requests`: each requesting account and its withdrawal
+/// escrow.
+pub fn withdrawal_accounts(
+    requests: &[L1WithdrawalRequest],
+) -> impl '_ + Iterator<Item = FeeAccount> {
+    requests.iter().flat_map(|request| {
+        let account = request.fee_info.account;
+        [account, account.withdrawal_escrow()]
+    })
+}
+
 impl HotShotState<SeqTypes> for ValidatedState {
     type Error = BlockError;
     type Instance = NodeState;
@@ -1827,6 +1929,40 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_apply_withdrawals() {
+        initialize_logging();
+        let account = FeeAccount::generated_from_seed_indexed([0; 32], 0).0;
+        let escrow = account.withdrawal_escrow();
+        let request = |amount: u64| L1WithdrawalRequest {
+            fee_info: FeeInfo::new(account, amount),
+            l1_block: Some(1),
+            l1_tx_hash: None,
+            l1_log_index: Some(0),
+        };
+
+        let mut state = ValidatedState::default();
+        state.prefund_account(account, 10.into());
+
+        // A request for more than the balance is ignored, while the rest are honored in order.
+        let mut delta = Delta::default();
+        state
+            .apply_withdrawals(&mut delta, vec![request(4), request(7), request(6)])
+            .unwrap();
+        assert_eq!(state.balance(account), Some(0.into()));
+        assert_eq!(state.balance(escrow), Some(10.into()));
+        assert_eq!(delta.fee_withdrawals, [request(4), request(6)]);
+        assert!(delta.fees_delta.contains(&account));
+        assert!(delta.fees_delta.contains(&escrow));
+
+        // The escrow balance accumulates over subsequent withdrawals.
+        state.prefund_account(account, 5.into());
+        state
+            .apply_withdrawals(&mut Delta::default(), vec![request(5)])
+            .unwrap();
+        assert_eq!(state.balance(escrow), Some(15.into()));
+    }
+
     #[test]
     fn test_fee_amount_serde_json_as_decimal() {
         let amt = FeeAmount::from(123);
@@ -0,0 +1,366 @@
+//! Verification of namespace contents against the finalized state of the light client contract.
+//!
+//! The light client contract stores a commitment to the root of the block Merkle tree as of its
+//! finalized block height. Starting from that state, the contents of a namespace in an earlier block
+//! are verified in three steps:
+//! 1. the block Merkle tree root hashes to the light client's `block_comm_root`, and a Merkle proof
+//!    shows that a header is in the tree at its claimed height,
+//! 2. the header commits to a namespace table and a payload commitment,
+//! 3. a namespace proof (ADVZ or AvidM) shows that a list of transactions is exactly the contents of
+//!    the namespace in that payload.
+//!
+//! Fee withdrawals are proven the same way, except that the last step is a fee Merkle proof of the
+//! balance of an account's withdrawal escrow, against the fee Merkle root in the header.
+
+use alloy::primitives::U256;
+use anyhow::{ensure, Context};
+use ark_serialize::CanonicalSerialize;
+use committable::Committable;
+use hotshot_query_service::VidCommon;
+use hotshot_types::light_client::{hash_bytes_to_field, CircuitField, LightClientState};
+use jf_merkle_tree::{MerkleCommitment, MerkleTreeScheme};
+use serde::{Deserialize, Serialize};
+
+use super::nsproof::verify_ns_proof;
+use crate::{
+    v0_1::{BlockMerkleCommitment, BlockMerkleTree},
+    FeeAccount, FeeAccountProof, Header, NamespaceId, NsProof, Transaction,
+};
+
+/// Everything needed to prove the contents of a namespace against the light client state.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NamespaceInclusionProof {
+    /// The root of the block Merkle tree committed to by the light client state.
+    ///
+    /// This can be taken from the header at the light client's finalized block height.
+    pub block_merkle_root: BlockMerkleCommitment,
+    /// A proof that `header` is in the block Merkle tree.
+    pub block_proof: <BlockMerkleTree as MerkleTreeScheme>::MembershipProof,
+    /// The header of the block containing the namespace.
+    pub header: Header,
+    /// VID common data for the block, needed to check the namespace proof.
+    pub vid_common: VidCommon,
+    /// A proof of the contents of the namespace, or [`None`] if the namespace is not in the block.
+    pub ns_proof: Option<NsProof>,
+}
+
+/// The contents of a namespace, verified against the light client state.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifiedNamespace {
+    pub height: u64,
+    pub ns_id: NamespaceId,
+    pub transactions: Vec<Transaction>,
+}
+
+impl NamespaceInclusionProof {
+    /// Decode a proof from its binary serialization.
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        bincode::deserialize(bytes).context("malformed namespace inclusion proof")
+    }
+
+    /// Encode this proof in the format accepted by [`from_bytes`](Self::from_bytes).
+    pub fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        bincode::serialize(self).context("serializing namespace inclusion proof")
+    }
+
+    /// Verify the contents of namespace `ns_id` against the light client state `state`.
+    pub fn verify(
+        &self,
+        state: &LightClientState,
+        ns_id: NamespaceId,
+    ) -> anyhow::Result<VerifiedNamespace> {
+        verify_header(
+            state,
+            &self.block_merkle_root,
+            &self.block_proof,
+            &self.header,
+        )?;
+        let height = self.header.height();
+
+        // The header commits to the namespace table and payload, which the namespace proof is
+        // checked against.
+        let transactions = verify_ns_proof(
+            self.ns_proof.as_ref(),
+            &self.header,
+            &self.vid_common,
+            ns_id,
+        )?;
+
+        Ok(VerifiedNamespace {
+            height,
+            ns_id,
+            transactions,
+        })
+    }
+}
+
+/// Everything needed to prove the total amount withdrawn from a fee account against the light
+/// client state.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FeeWithdrawalProof {
+    /// The root of the block Merkle tree committed to by the light client state.
+    pub block_merkle_root: BlockMerkleCommitment,
+    /// A proof that `header` is in the block Merkle tree.
+    pub block_proof: <BlockMerkleTree as MerkleTreeScheme>::MembershipProof,
+    /// A finalized header, whose fee Merkle root the escrow balance is checked against.
+    pub header: Header,
+    /// A proof of the balance of the withdrawal escrow of the account in the fee state after
+    /// `header`.
+    pub escrow_proof: FeeAccountProof,
+}
+
+impl FeeWithdrawalProof {
+    /// Decode a proof from its binary serialization.
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        bincode::deserialize(bytes).context("malformed fee withdrawal proof")
+    }
+
+    /// Encode this proof in the format accepted by [`from_bytes`](Self::from_bytes).
+    pub fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        bincode::serialize(self).context("serializing fee withdrawal proof")
+    }
+
+    /// Verify the total amount withdrawn from `account` against the light client state `state`.
+    pub fn verify(&self, state: &LightClientState, account: FeeAccount) -> anyhow::Result<U256> {
+        verify_header(
+            state,
+            &self.block_merkle_root,
+            &self.block_proof,
+            &self.header,
+        )?;
+        ensure!(
+            self.escrow_proof.account == account.withdrawal_escrow().address(),
+            "fee proof is not for the withdrawal escrow of {account}"
+        );
+        self.escrow_proof
+            .verify(&self.header.fee_merkle_tree_root())
+            .context("invalid withdrawal escrow proof")
+    }
+}
+
+/// Check that `header` is in the block Merkle tree committed to by the light client state `state`.
+fn verify_header(
+    state: &LightClientState,
+    block_merkle_root: &BlockMerkleCommitment,
+    block_proof: &<BlockMerkleTree as MerkleTreeScheme>::MembershipProof,
+    header: &Header,
+) -> anyhow::Result<()> {
+    // The light client state commits to the block Merkle tree root.
+    let mut root_bytes = vec![];
+    block_merkle_root
+        .serialize_compressed(&mut root_bytes)
+        .context("serializing block Merkle root")?;
+    let root: CircuitField =
+        hash_bytes_to_field(&root_bytes).context("hashing block Merkle root")?;
+    ensure!(
+        root == state.block_comm_root,
+        "block Merkle root does not match light client state"
+    );
+
+    // The block Merkle tree contains the header. The tree committed to by the light client at
+    // height `h` contains only the blocks before `h`.
+    let height = header.height();
+    ensure!(
+        height < state.block_height,
+        "block {height} is not finalized by the light client (finalized height {})",
+        state.block_height
+    );
+    ensure!(
+        BlockMerkleTree::verify(block_merkle_root.digest(), height, block_proof)?.is_ok(),
+        "invalid block Merkle proof"
+    );
+    let leaf = block_proof
+        .elem()
+        .context("block Merkle proof is missing header commitment")?;
+    ensure!(
+        *leaf == header.commit(),
+        "block Merkle proof is for a different header"
+    );
+    Ok(())
+}
+
+/// Verify the contents of namespace `ns_id` against the light client state `state`, given a proof
+/// encoded with [`NamespaceInclusionProof::to_bytes`].
+pub fn verify_namespace_inclusion(
+    state: &LightClientState,
+    ns_id: NamespaceId,
+    proof: &[u8],
+) -> anyhow::Result<VerifiedNamespace> {
+    NamespaceInclusionProof::from_bytes(proof)?.verify(state, ns_id)
+}
+
+#[cfg(test)]
+mod test {
+    use alloy::primitives::Address;
+    use hotshot_types::{
+        data::VidCommitment,
+        traits::{block_contents::BlockHeader, BlockPayload, EncodeBytes},
+        vid::avidm::{AvidMParam, AvidMScheme},
+    };
+    use jf_merkle_tree::{AppendableMerkleTreeScheme, LookupResult, UniversalMerkleTreeScheme};
+
+    use super::*;
+    use crate::{
+        v0_1::{BLOCK_MERKLE_TREE_HEIGHT, FEE_MERKLE_TREE_HEIGHT},
+        FeeAmount, FeeMerkleTree, NodeState, Payload,
+    };
+
+    async fn mock_proof(txs: Vec<Transaction>, ns_id: NamespaceId) -> NamespaceInclusionProof {
+        let instance = NodeState::mock();
+        let (payload, ns_table) =
+            Payload::from_transactions(txs, &instance.genesis_state, &instance)
+                .await
+                .unwrap();
+
+        let param = AvidMParam::new(5, 10).unwrap();
+        let payload_byte_len = payload.byte_len();
+        let ns_ranges = ns_table
+            .iter()
+            .map(|index| ns_table.ns_range(&index, &payload_byte_len).0)
+            .collect::<Vec<_>>();
+        let payload_commitment = AvidMScheme::commit(&param, &payload.encode(), ns_ranges).unwrap();
+        let vid_common = VidCommon::V1(param);
+
+        let ns_proof = ns_table
+            .find_ns_id(&ns_id)
+            .map(|index| NsProof::new(&payload, &index, &vid_common).unwrap());
+        let header = Header::genesis(
+            &instance,
+            VidCommitment::V1(payload_commitment),
+            payload.builder_commitment(&ns_table),
+            ns_table,
+        );
+
+        let mut tree = BlockMerkleTree::new(BLOCK_MERKLE_TREE_HEIGHT);
+        tree.push(header.commit()).unwrap();
+        let LookupResult::Ok(_, block_proof) = tree.lookup(0) else {
+            panic!("header missing from block Merkle tree");
+        };
+
+        NamespaceInclusionProof {
+            block_merkle_root: tree.commitment(),
+            block_proof,
+            header,
+            vid_common,
+            ns_proof,
+        }
+    }
+
+    fn light_client_state(proof: &NamespaceInclusionProof, block_height: u64) -> LightClientState {
+        let mut root_bytes = vec![];
+        proof
+            .block_merkle_root
+            .serialize_compressed(&mut root_bytes)
+            .unwrap();
+        LightClientState {
+            view_number: 1,
+            block_height,
+            block_comm_root: hash_bytes_to_field(&root_bytes).unwrap(),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_namespace_inclusion() {
+        let ns_id = NamespaceId::from(1u32);
+        let txs = vec![
+            Transaction::new(ns_id, vec![1, 2, 3]),
+            Transaction::new(ns_id, vec![4, 5]),
+            Transaction::new(2u32.into(), vec![6]),
+        ];
+        let proof = mock_proof(txs.clone(), ns_id).await;
+        let state = light_client_state(&proof, 1);
+
+        // Round trip through bytes.
+        let verified =
+            verify_namespace_inclusion(&state, ns_id, &proof.to_bytes().unwrap()).unwrap();
+        assert_eq!(verified.height, 0);
+        assert_eq!(verified.ns_id, ns_id);
+        assert_eq!(verified.transactions, txs[..2]);
+
+        // Wrong namespace.
+        proof.verify(&state, 2u32.into()).unwrap_err();
+
+        // A namespace which is not in the block has no transactions.
+        let absent = mock_proof(txs.clone(), 3u32.into()).await;
+        let state = light_client_state(&absent, 1);
+        assert_eq!(
+            absent.verify(&state, 3u32.into()).unwrap().transactions,
+            vec![]
+        );
+
+        // Wrong light client state.
+        let mut state = light_client_state(&proof, 1);
+        state.block_comm_root = Default::default();
+        proof.verify(&state, ns_id).unwrap_err();
+
+        // Block not yet finalized by the light client.
+        let state = light_client_state(&proof, 0);
+        proof.verify(&state, ns_id).unwrap_err();
+
+        // Header does not match block Merkle proof.
+        let state = light_client_state(&proof, 1);
+        let mut bad = proof.clone();
+        *bad.header.timestamp_mut() += 1;
+        bad.verify(&state, ns_id).unwrap_err();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_fee_withdrawal_proof() {
+        let account = FeeAccount::from(Address::random());
+        let escrow = account.withdrawal_escrow();
+        let mut fee_tree = FeeMerkleTree::new(FEE_MERKLE_TREE_HEIGHT);
+        fee_tree.update(escrow, FeeAmount::from(42)).unwrap();
+        let (escrow_proof, _) = FeeAccountProof::prove(&fee_tree, escrow.address()).unwrap();
+
+        let mut header = mock_proof(vec![], 1u32.into()).await.header;
+        *header.fee_merkle_tree_root_mut() = fee_tree.commitment();
+        let mut tree = BlockMerkleTree::new(BLOCK_MERKLE_TREE_HEIGHT);
+        tree.push(header.commit()).unwrap();
+        let LookupResult::Ok(_, block_proof) = tree.lookup(0) else {
+            panic!("header missing from block Merkle tree");
+        };
+        let proof = FeeWithdrawalProof {
+            block_merkle_root: tree.commitment(),
+            block_proof,
+            header,
+            escrow_proof,
+        };
+
+        let mut root_bytes = vec![];
+        tree.commitment()
+            .serialize_compressed(&mut root_bytes)
+            .unwrap();
+        let state = LightClientState {
+            view_number: 1,
+            block_height: 1,
+            block_comm_root: hash_bytes_to_field(&root_bytes).unwrap(),
+        };
+
+        // Round trip through bytes.
+        let proof = FeeWithdrawalProof::from_bytes(&proof.to_bytes().unwrap()).unwrap();
+        assert_eq!(proof.verify(&state, account).unwrap(), U256::from(42));
+
+        // The proof is only valid for the account whose escrow it proves.
+        proof
+            .verify(&state, FeeAccount::from(Address::random()))
+            .unwrap_err();
+
+        // An account which never withdrew has an empty escrow.
+        let other = FeeAccount::from(Address::random());
+        let mut absent = proof.clone();
+        absent.escrow_proof =
+            FeeAccountProof::prove(&fee_tree, other.withdrawal_escrow().address())
+                .unwrap()
+                .0;
+        assert_eq!(absent.verify(&state, other).unwrap(), U256::ZERO);
+
+        // Escrow proof from a different fee state.
+        let mut other_tree = FeeMerkleTree::new(FEE_MERKLE_TREE_HEIGHT);
+        other_tree.update(escrow, FeeAmount::from(1000)).unwrap();
+        let mut bad = proof.clone();
+        bad.escrow_proof = FeeAccountProof::prove(&other_tree, escrow.address())
+            .unwrap()
+            .0;
+        bad.verify(&state, account).unwrap_err();
+    }
+}
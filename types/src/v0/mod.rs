@@ -13,17 +13,24 @@ use serde::{Deserialize, Serialize};
 pub mod config;
 mod header;
 mod impls;
+pub mod light_client;
 mod nsproof;
+pub mod snapshot;
+pub mod submit;
 pub mod traits;
 mod utils;
 pub use header::Header;
 #[cfg(any(test, feature = "testing"))]
 pub use impls::mock;
 pub use impls::{
-    get_l1_deposits, retain_accounts, BuilderValidationError, EpochCommittees, FeeError,
-    ProposalValidationError, StateValidationError,
+    get_l1_deposits, get_l1_withdrawal_requests, retain_accounts, retain_reward_accounts,
+    verify_stake_table_events, withdrawal_accounts, BuilderValidationError, EpochCommittees,
+    FeeError, IndexedStakeTableEvent, ProposalValidationError, StakeTableEvent,
+    StateValidationError,
+};
+pub use nsproof::{
+    ADVZNamespaceProofQueryData, NamespaceBlockQueryData, NamespaceProofQueryData, NsProof,
 };
-pub use nsproof::NsProof;
 pub use utils::*;
 use vbs::version::{StaticVersion, StaticVersionType};
 
@@ -1,9 +1,10 @@
+use anyhow::{ensure, Context};
 use hotshot_query_service::VidCommon;
 use hotshot_types::data::VidCommitment;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    v0::{NamespaceId, NsIndex, NsPayload, NsTable, Payload, Transaction},
+    v0::{Header, NamespaceId, NsIndex, NsPayload, NsTable, Payload, Transaction},
     v0_1::ADVZNsProof,
     v0_3::AvidMNsProof,
 };
@@ -48,3 +49,126 @@ impl NsProof {
         }
     }
 }
+
+/// The transactions in a single namespace of a block, along with a proof.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamespaceProofQueryData {
+    pub proof: Option<NsProof>,
+    pub transactions: Vec<Transaction>,
+}
+
+impl NamespaceProofQueryData {
+    /// Extract the transactions in namespace `ns_id` from `payload`, along with a proof.
+    ///
+    /// If the namespace is not present in the payload, the result has no transactions and no proof.
+    /// Returns [`None`] if a proof cannot be constructed.
+    pub fn new(payload: &Payload, common: &VidCommon, ns_id: NamespaceId) -> Option<Self> {
+        let Some(ns_index) = payload.ns_table().find_ns_id(&ns_id) else {
+            // ns_id not found in ns_table
+            return Some(Self {
+                proof: None,
+                transactions: Vec::new(),
+            });
+        };
+        let proof = NsProof::new(payload, &ns_index, common)?;
+        Some(Self {
+            transactions: proof.export_all_txs(&ns_id),
+            proof: Some(proof),
+        })
+    }
+
+    /// Check that these are exactly the transactions in namespace `ns_id` of the block with the
+    /// given `header`.
+    pub fn verify(
+        &self,
+        header: &Header,
+        common: &VidCommon,
+        ns_id: NamespaceId,
+    ) -> anyhow::Result<()> {
+        verify_namespace(
+            self.proof.as_ref(),
+            &self.transactions,
+            header,
+            common,
+            ns_id,
+        )
+    }
+}
+
+/// The transactions in a single namespace of a block, along with a legacy ADVZ proof.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ADVZNamespaceProofQueryData {
+    pub proof: Option<ADVZNsProof>,
+    pub transactions: Vec<Transaction>,
+}
+
+/// The part of a block belonging to a single namespace.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamespaceBlockQueryData {
+    pub header: Header,
+    pub proof: Option<NsProof>,
+    pub transactions: Vec<Transaction>,
+}
+
+impl NamespaceBlockQueryData {
+    pub fn new(header: Header, ns_data: NamespaceProofQueryData) -> Self {
+        Self {
+            header,
+            proof: ns_data.proof,
+            transactions: ns_data.transactions,
+        }
+    }
+
+    /// Check that these are exactly the transactions in namespace `ns_id` of this block.
+    pub fn verify(&self, common: &VidCommon, ns_id: NamespaceId) -> anyhow::Result<()> {
+        verify_namespace(
+            self.proof.as_ref(),
+            &self.transactions,
+            &self.header,
+            common,
+            ns_id,
+        )
+    }
+}
+
+fn verify_namespace(
+    proof: Option<&NsProof>,
+    transactions: &[Transaction],
+    header: &Header,
+    common: &VidCommon,
+    ns_id: NamespaceId,
+) -> anyhow::Result<()> {
+    let proven = verify_ns_proof(proof, header, common, ns_id)?;
+    ensure!(
+        proven == transactions,
+        "transactions do not match namespace proof"
+    );
+    Ok(())
+}
+
+/// Verify an optional namespace proof against a header, returning the proven transactions.
+///
+/// If there is no proof, the namespace must be absent from the block, in which case there are no
+/// transactions.
+pub(crate) fn verify_ns_proof(
+    proof: Option<&NsProof>,
+    header: &Header,
+    common: &VidCommon,
+    ns_id: NamespaceId,
+) -> anyhow::Result<Vec<Transaction>> {
+    let Some(proof) = proof else {
+        ensure!(
+            header.ns_table().find_ns_id(&ns_id).is_none(),
+            "missing proof for namespace {ns_id}"
+        );
+        return Ok(vec![]);
+    };
+    let (transactions, proven_ns_id) = proof
+        .verify(header.ns_table(), &header.payload_commitment(), common)
+        .context("invalid namespace proof")?;
+    ensure!(
+        proven_ns_id == ns_id,
+        "proof is for namespace {proven_ns_id}, expected {ns_id}"
+    );
+    Ok(transactions)
+}
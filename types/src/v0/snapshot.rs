@@ -0,0 +1,217 @@
+//! Portable snapshots of decided state, used to bootstrap a node without replaying history.
+//!
+//! A snapshot contains everything a node needs to start participating in consensus from a decided
+//! leaf: the leaf itself, a chain of descendants whose QCs prove the leaf was decided, the full
+//! [`ValidatedState`] after the leaf, and the stake table for the epoch containing the leaf along
+//! with the epoch root header it was computed from.
+
+use std::path::Path;
+
+use alloy::primitives::{Address, U256};
+use anyhow::{ensure, Context};
+use committable::Committable;
+use hotshot_types::{
+    data::EpochNumber,
+    message::UpgradeLock,
+    signature_key::BLSPubKey,
+    traits::signature_key::{SignatureKey, StakeTableEntryType},
+    utils::{root_block_in_epoch, verify_leaf_chain},
+    PeerConfig,
+};
+use indexmap::IndexMap;
+use jf_merkle_tree::{MerkleCommitment, MerkleTreeScheme};
+use serde::{Deserialize, Serialize};
+
+use super::{impls::success_threshold, v0_3::Validator, v0_99::ChainConfig};
+use crate::{
+    BlockMerkleTree, EpochVersion, Header, L1Client, Leaf2, SeqTypes, SequencerVersions,
+    ValidatedState,
+};
+
+/// A snapshot of the state after a decided leaf.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    /// The snapshotted leaf, followed by descendants proving that it was decided.
+    pub leaf_chain: Vec<Leaf2>,
+    /// The full state after the snapshotted leaf.
+    ///
+    /// The fee and reward Merkle trees are complete, the block Merkle tree contains the frontier,
+    /// and the chain config is resolved.
+    pub state: ValidatedState,
+    /// The stake table for the epoch containing the snapshotted leaf, if epochs are enabled.
+    pub stake_table: Option<(EpochNumber, IndexMap<Address, Validator<BLSPubKey>>)>,
+    /// The epoch root header the stake table was computed from, with a proof that it is in the
+    /// block Merkle tree of the snapshotted leaf.
+    ///
+    /// Present whenever [`stake_table`](Self::stake_table) is.
+    pub epoch_root: Option<(
+        Header,
+        <BlockMerkleTree as MerkleTreeScheme>::MembershipProof,
+    )>,
+}
+
+impl StateSnapshot {
+    /// The snapshotted leaf.
+    pub fn leaf(&self) -> &Leaf2 {
+        &self.leaf_chain[0]
+    }
+
+    /// The block height of the snapshotted leaf.
+    pub fn height(&self) -> u64 {
+        self.leaf().height()
+    }
+
+    /// The resolved chain config in effect after the snapshotted leaf.
+    pub fn chain_config(&self) -> anyhow::Result<ChainConfig> {
+        self.state
+            .chain_config
+            .resolve()
+            .context("snapshot chain config is not resolved")
+    }
+
+    /// Read a snapshot from a file written by [`save`](Self::save).
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).context(format!("reading snapshot {}", path.display()))?;
+        bincode::deserialize(&bytes).context(format!("malformed snapshot {}", path.display()))
+    }
+
+    /// Write this snapshot to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let bytes = bincode::serialize(self).context("serializing snapshot")?;
+        std::fs::write(path, bytes).context(format!("writing snapshot {}", path.display()))
+    }
+
+    /// The stake table included in this snapshot, in the form used to verify QCs.
+    pub fn peers(&self) -> Option<Vec<PeerConfig<SeqTypes>>> {
+        let (_, validators) = self.stake_table.as_ref()?;
+        Some(
+            validators
+                .values()
+                .map(|v| PeerConfig {
+                    stake_table_entry: BLSPubKey::stake_table_entry(&v.stake_table_key, v.stake),
+                    state_ver_key: v.state_ver_key.clone(),
+                })
+                .collect(),
+        )
+    }
+
+    /// The epoch root header the stake table was computed from, checked against the snapshotted
+    /// leaf.
+    ///
+    /// Returns `None` if the snapshot has no stake table. The stake table itself is not checked,
+    /// see [`verify_stake_table`](Self::verify_stake_table).
+    pub fn epoch_root(&self, epoch_height: u64) -> anyhow::Result<Option<&Header>> {
+        let Some((epoch, _)) = &self.stake_table else {
+            return Ok(None);
+        };
+        ensure!(
+            self.leaf().epoch(epoch_height) == Some(*epoch),
+            "snapshot stake table is for epoch {epoch}, not the epoch of the snapshotted leaf"
+        );
+        let (header, proof) = self
+            .epoch_root
+            .as_ref()
+            .context("snapshot has a stake table but no epoch root")?;
+
+        // The stake table for an epoch is computed from the root block two epochs earlier.
+        let root_height = root_block_in_epoch((**epoch).saturating_sub(2), epoch_height);
+        ensure!(
+            header.height() == root_height,
+            "snapshot epoch root is block {}, not {root_height}",
+            header.height()
+        );
+        let block_merkle_root = self.leaf().block_header().block_merkle_tree_root();
+        ensure!(
+            BlockMerkleTree::verify(block_merkle_root.digest(), root_height, proof)?.is_ok(),
+            "invalid block Merkle proof for snapshot epoch root"
+        );
+        ensure!(
+            proof.elem() == Some(&header.commit()),
+            "block Merkle proof is for a different epoch root header"
+        );
+        Ok(Some(header))
+    }
+
+    /// Check the stake table against the stake table contract on L1.
+    ///
+    /// The stake table is recomputed from the contract events up to the L1 block finalized by the
+    /// epoch root, which is itself checked against the snapshotted leaf. Succeeds trivially if the
+    /// snapshot has no stake table.
+    pub async fn verify_stake_table(&self, epoch_height: u64, l1: &L1Client) -> anyhow::Result<()> {
+        let (Some((epoch, stake_table)), Some(root)) =
+            (&self.stake_table, self.epoch_root(epoch_height)?)
+        else {
+            return Ok(());
+        };
+
+        // The epoch root only commits to its chain config, which is usually the same as ours.
+        let chain_config = match root.chain_config().resolve() {
+            Some(chain_config) => chain_config,
+            None => {
+                let chain_config = self.chain_config()?;
+                ensure!(
+                    chain_config.commit() == root.chain_config().commit(),
+                    "chain config of snapshot epoch root is not available"
+                );
+                chain_config
+            },
+        };
+        let contract = chain_config
+            .stake_table_contract
+            .context("snapshot epoch root has no stake table contract")?;
+        let l1_block = root
+            .l1_finalized()
+            .context("snapshot epoch root has no finalized L1 block")?
+            .number();
+
+        let expected = l1
+            .fetch_stake_table(contract, l1_block)
+            .await
+            .context(format!("fetching stake table for epoch {epoch} from L1"))?;
+        ensure!(
+            *stake_table == expected,
+            "snapshot stake table for epoch {epoch} does not match L1 block {l1_block}"
+        );
+        Ok(())
+    }
+
+    /// Check that the snapshotted leaf was decided by `stake_table` and that the state matches it.
+    pub async fn verify(&self, stake_table: Vec<PeerConfig<SeqTypes>>) -> anyhow::Result<()> {
+        ensure!(!self.leaf_chain.is_empty(), "snapshot has no leaves");
+        let total_stake = stake_table
+            .iter()
+            .map(|peer| peer.stake_table_entry.stake())
+            .fold(U256::ZERO, |acc, stake| acc + stake);
+        let threshold = success_threshold(total_stake);
+        verify_leaf_chain(
+            self.leaf_chain.iter().rev().cloned().collect(),
+            stake_table,
+            threshold,
+            self.height(),
+            &UpgradeLock::<SeqTypes, SequencerVersions<EpochVersion, EpochVersion>>::new(),
+        )
+        .await
+        .context("snapshot leaf is not decided")?;
+
+        let header = self.leaf().block_header();
+        ensure!(
+            self.state.fee_merkle_tree.commitment() == header.fee_merkle_tree_root(),
+            "snapshot fee state does not match header"
+        );
+        ensure!(
+            self.state.reward_merkle_tree.commitment() == header.reward_merkle_tree_root(),
+            "snapshot reward state does not match header"
+        );
+        ensure!(
+            self.state.block_merkle_tree.commitment() == header.block_merkle_tree_root(),
+            "snapshot block state does not match header"
+        );
+        ensure!(
+            self.chain_config()?.commit() == header.chain_config().commit(),
+            "snapshot chain config does not match header"
+        );
+        Ok(())
+    }
+}
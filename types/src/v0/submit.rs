@@ -0,0 +1,51 @@
+use committable::Commitment;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{NamespaceId, Transaction};
+
+/// The reason a submitted transaction was not accepted.
+#[derive(Clone, Debug, PartialEq, Eq, Error, Serialize, Deserialize)]
+pub enum SubmitError {
+    /// The transaction cannot fit in a block under the current `ChainConfig::max_block_size`.
+    #[error("transaction size ({size}) is greater than max_block_size ({max_block_size})")]
+    TooLarge { size: u64, max_block_size: u64 },
+    /// The node is not configured to accept transactions in this namespace.
+    #[error("namespace {0} is not allowed")]
+    NamespaceNotAllowed(NamespaceId),
+    /// The transaction was valid, but the node failed to add it to its mempool.
+    #[error("transaction rejected by mempool: {0}")]
+    MempoolRejected(String),
+}
+
+/// The result of submitting one transaction as part of a batch.
+///
+/// On success, this is the commitment of the submitted transaction, which can be used to look it up
+/// once it has been sequenced.
+pub type SubmitResult = Result<Commitment<Transaction>, SubmitError>;
+
+/// The lifecycle status of a transaction, as observed by a sequencer node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionStatus {
+    /// The transaction has been submitted to or gossiped to this node, but has not yet been seen in
+    /// a proposal.
+    Pending,
+    /// The transaction has been included in a DA proposal for the given view, which has not yet been
+    /// decided.
+    Proposed { view: u64 },
+    /// The transaction has been sequenced in the block at `height`, at position `index` within the
+    /// block.
+    Decided { height: u64, index: u64 },
+    /// The transaction was not decided within the expiry window, and is no longer expected to be
+    /// sequenced. It may still be sequenced if it is resubmitted.
+    Dropped,
+}
+
+impl TransactionStatus {
+    /// Whether this status is final, meaning the node will not report any further transitions.
+    ///
+    /// [`Dropped`](Self::Dropped) is not final, since a dropped transaction can still be decided.
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Decided { .. })
+    }
+}
@@ -13,11 +13,12 @@ use hotshot::{
 };
 use hotshot_types::{
     data::{
-        vid_disperse::{ADVZDisperseShare, VidDisperseShare2},
+        vid_disperse::{ADVZDisperseShare, VidDisperseShare2, VidEncodingEvidence},
         DaProposal, DaProposal2, EpochNumber, QuorumProposal, QuorumProposal2,
         QuorumProposalWrapper, VidCommitment, VidDisperseShare, ViewNumber,
     },
     drb::DrbResult,
+    equivocation::EquivocationEvidence,
     event::{HotShotAction, LeafInfo},
     message::{convert_proposal, Proposal, UpgradeLock},
     simple_certificate::{
@@ -37,7 +38,8 @@ use itertools::Itertools;
 use serde::{de::DeserializeOwned, Serialize};
 
 use super::{
-    impls::NodeState,
+    impls::{IndexedStakeTableEvent, NodeState},
+    snapshot::StateSnapshot,
     utils::BackoffParams,
     v0_1::{RewardAccount, RewardAccountProof, RewardMerkleCommitment, RewardMerkleTree},
     v0_3::{IndexedStake, Validator},
@@ -675,6 +677,23 @@ pub trait MembershipPersistence: Send + Sync + 'static {
         epoch: EpochNumber,
         stake: IndexMap<alloy::primitives::Address, Validator<BLSPubKey>>,
     ) -> anyhow::Result<()>;
+
+    /// Load stake table events cached from L1.
+    ///
+    /// Returns the L1 block up to which events have been stored, along with all the stored events.
+    /// Returns `None` if no events have been stored.
+    async fn load_events(&self) -> anyhow::Result<Option<(u64, Vec<IndexedStakeTableEvent>)>>;
+
+    /// Store stake table events fetched from L1, up to and including `l1_block`.
+    ///
+    /// `events` should contain all events emitted after the previously stored L1 block, up to
+    /// `l1_block`, which must be finalized. Storing an event which is already stored is a no-op,
+    /// and the stored L1 block never decreases.
+    async fn store_events(
+        &self,
+        l1_block: u64,
+        events: Vec<IndexedStakeTableEvent>,
+    ) -> anyhow::Result<()>;
 }
 
 #[async_trait]
@@ -687,6 +706,21 @@ pub trait SequencerPersistence: Sized + Send + Sync + Clone + 'static {
         bail!("state catchup is not implemented for this persistence type");
     }
 
+    /// Export a snapshot of the decided state after the block at `height`, if supported.
+    async fn export_snapshot(&self, _height: u64) -> anyhow::Result<StateSnapshot> {
+        bail!("snapshot export is not implemented for this persistence type");
+    }
+
+    /// Seed this storage with the state in `snapshot`, if supported.
+    ///
+    /// The snapshot should be verified with [`StateSnapshot::verify`], and its stake table with
+    /// [`StateSnapshot::verify_stake_table`], before it is imported. The import fails if the
+    /// snapshot stake table differs from one already stored for the same epoch. After importing,
+    /// consensus will start from the snapshotted leaf.
+    async fn import_snapshot(&self, _snapshot: &StateSnapshot) -> anyhow::Result<()> {
+        bail!("snapshot import is not implemented for this persistence type");
+    }
+
     /// Load the orchestrator config from storage.
     ///
     /// Returns `None` if no config exists (we are joining a network for the first time). Fails with
@@ -724,6 +758,16 @@ pub trait SequencerPersistence: Sized + Send + Sync + Clone + 'static {
     async fn load_state_cert(
         &self,
     ) -> anyhow::Result<Option<LightClientStateUpdateCertificate<SeqTypes>>>;
+    /// Load all recorded evidence of incorrectly encoded VID dispersals, by view.
+    async fn load_vid_encoding_evidence(
+        &self,
+    ) -> anyhow::Result<BTreeMap<ViewNumber, VidEncodingEvidence<SeqTypes>>>;
+    /// Load the recorded evidence of equivocating proposals and votes for views `from..to`, by view.
+    async fn load_equivocation_evidence(
+        &self,
+        from: ViewNumber,
+        to: ViewNumber,
+    ) -> anyhow::Result<BTreeMap<ViewNumber, Vec<EquivocationEvidence<SeqTypes>>>>;
 
     /// Load the latest known consensus state.
     ///
@@ -939,6 +983,14 @@ pub trait SequencerPersistence: Sized + Send + Sync + Clone + 'static {
         &self,
         proposal: &Proposal<SeqTypes, VidDisperseShare2<SeqTypes>>,
     ) -> anyhow::Result<()>;
+    async fn append_vid_encoding_evidence(
+        &self,
+        evidence: &VidEncodingEvidence<SeqTypes>,
+    ) -> anyhow::Result<()>;
+    async fn append_equivocation_evidence(
+        &self,
+        evidence: &EquivocationEvidence<SeqTypes>,
+    ) -> anyhow::Result<()>;
     async fn append_da(
         &self,
         proposal: &Proposal<SeqTypes, DaProposal<SeqTypes>>,
@@ -1065,6 +1117,20 @@ impl<P: SequencerPersistence> Storage<SeqTypes> for Arc<P> {
         (**self).append_vid2(proposal).await
     }
 
+    async fn append_vid_encoding_evidence(
+        &self,
+        evidence: &VidEncodingEvidence<SeqTypes>,
+    ) -> anyhow::Result<()> {
+        (**self).append_vid_encoding_evidence(evidence).await
+    }
+
+    async fn append_equivocation_evidence(
+        &self,
+        evidence: &EquivocationEvidence<SeqTypes>,
+    ) -> anyhow::Result<()> {
+        (**self).append_equivocation_evidence(evidence).await
+    }
+
     async fn append_da(
         &self,
         proposal: &Proposal<SeqTypes, DaProposal<SeqTypes>>,
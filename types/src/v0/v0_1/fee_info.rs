@@ -1,4 +1,4 @@
-use alloy::primitives::{Address, U256};
+use alloy::primitives::{Address, TxHash, U256};
 use alloy_compat::ethers_serde;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use derive_more::{Add, Display, From, Into, Mul, Sub};
@@ -69,6 +69,106 @@ pub struct FeeInfo {
     pub amount: FeeAmount,
 }
 
+/// A deposit into the fee contract, along with the L1 event which made it.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct L1Deposit {
+    pub fee_info: FeeInfo,
+    /// The L1 block containing the deposit.
+    pub l1_block: Option<u64>,
+    /// The L1 transaction which made the deposit.
+    pub l1_tx_hash: Option<TxHash>,
+    /// The index of the `Deposit` event within the L1 block.
+    pub l1_log_index: Option<u64>,
+}
+
+/// A request, made on L1, to withdraw from a fee account.
+///
+/// The request is honored when the L1 block containing it is finalized, by moving `amount` from
+/// the account to its withdrawal escrow account. If the account has insufficient funds at that
+/// point, the request is ignored.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct L1WithdrawalRequest {
+    pub fee_info: FeeInfo,
+    /// The L1 block containing the request.
+    pub l1_block: Option<u64>,
+    /// The L1 transaction which made the request.
+    pub l1_tx_hash: Option<TxHash>,
+    /// The index of the `WithdrawalRequested` event within the L1 block.
+    pub l1_log_index: Option<u64>,
+}
+
+/// A builder fee moved from the paying account to the fee recipient.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct FeeTransfer {
+    pub from: FeeAccount,
+    pub to: FeeAccount,
+    pub amount: FeeAmount,
+}
+
+/// A single change to the balance of a fee account.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct FeeLedgerEntry {
+    /// The block in which the balance changed.
+    pub height: u64,
+    /// The position of this entry among the ledger entries of its block.
+    pub index: u64,
+    pub account: FeeAccount,
+    pub amount: FeeAmount,
+    pub kind: FeeLedgerEntryKind,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum FeeLedgerEntryKind {
+    /// The account was credited by a deposit into the fee contract on L1.
+    Deposit {
+        l1_block: Option<u64>,
+        l1_tx_hash: Option<TxHash>,
+        l1_log_index: Option<u64>,
+    },
+    /// The account was debited for a builder fee paid to `recipient`.
+    FeePaid { recipient: FeeAccount },
+    /// The account was credited with a builder fee paid by `payer`.
+    FeeReceived { payer: FeeAccount },
+    /// The account was debited by a withdrawal requested on L1.
+    Withdrawal {
+        l1_block: Option<u64>,
+        l1_tx_hash: Option<TxHash>,
+        l1_log_index: Option<u64>,
+    },
+}
+
+/// The changes to the balance of a fee account over a range of blocks.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct FeeStatement {
+    pub account: FeeAccount,
+    /// The first block covered by the statement.
+    pub from: u64,
+    /// The block after the last block covered by the statement.
+    pub to: u64,
+    /// The total amount deposited from L1.
+    pub deposited: FeeAmount,
+    /// The total builder fees paid by the account.
+    pub paid: FeeAmount,
+    /// The total builder fees received by the account.
+    pub received: FeeAmount,
+    /// The total amount withdrawn to L1.
+    #[serde(default)]
+    pub withdrawn: FeeAmount,
+    /// Each deposit, with a reference to the L1 event which made it.
+    pub deposits: Vec<FeeLedgerEntry>,
+    /// Each withdrawal, with a reference to the L1 event which requested it.
+    #[serde(default)]
+    pub withdrawals: Vec<FeeLedgerEntry>,
+}
+
+/// A page of the ledger entries for a fee account, newest first.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct FeeLedgerPage {
+    pub entries: Vec<FeeLedgerEntry>,
+    /// The `(height, index)` of the last entry, if there are older entries to fetch.
+    pub next: Option<(u64, u64)>,
+}
+
 /// A proof of the balance of an account in the fee ledger.
 ///
 /// If the account of interest does not exist in the fee state, this is a Merkle non-membership
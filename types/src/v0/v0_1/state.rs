@@ -10,13 +10,25 @@ use jf_merkle_tree::{
 };
 use serde::{Deserialize, Serialize};
 
-use super::{FeeAccount, FeeAmount};
+use super::{FeeAccount, FeeAmount, FeeTransfer, L1Deposit, L1WithdrawalRequest};
 use crate::Header;
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 pub struct Delta {
     pub fees_delta: HashSet<FeeAccount>,
     pub rewards_delta: HashSet<RewardAccount>,
+    /// The block reward paid out by this state transition, if any.
+    #[serde(default)]
+    pub reward_distribution: Option<RewardDistribution>,
+    /// L1 deposits credited by this state transition.
+    #[serde(default)]
+    pub l1_deposits: Vec<L1Deposit>,
+    /// Builder fees charged by this state transition.
+    #[serde(default)]
+    pub fee_transfers: Vec<FeeTransfer>,
+    /// Withdrawal requests honored by this state transition.
+    #[serde(default)]
+    pub fee_withdrawals: Vec<L1WithdrawalRequest>,
 }
 
 pub const BLOCK_MERKLE_TREE_HEIGHT: usize = 32;
@@ -112,6 +124,43 @@ pub struct RewardInfo {
     pub amount: RewardAmount,
 }
 
+/// How the reward for a single block was split between a validator and its delegators.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RewardDistribution {
+    /// The validator which led the view in which the block was proposed.
+    pub validator: RewardAccount,
+    /// The part of the block reward kept by the validator as commission.
+    ///
+    /// This includes any remainder left over from rounding down the delegator rewards.
+    pub commission: RewardAmount,
+    /// The reward paid to each delegator, in proportion to its stake.
+    pub delegators: Vec<(RewardAccount, RewardAmount)>,
+}
+
+/// The rewards paid out by a single validator over the course of an epoch.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct EpochRewards {
+    pub epoch: u64,
+    pub validator: RewardAccount,
+    /// The number of blocks in this epoch for which the validator was rewarded.
+    pub blocks: u64,
+    /// The total commission kept by the validator.
+    pub commission: RewardAmount,
+    /// The total reward paid to each delegator.
+    pub delegators: Vec<(RewardAccount, RewardAmount)>,
+}
+
+/// The cumulative rewards earned by an account, over all epochs.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RewardTotals {
+    /// The total commission earned while acting as a validator.
+    pub commission: RewardAmount,
+    /// The total reward earned as a delegator of each validator.
+    pub delegated: Vec<(RewardAccount, RewardAmount)>,
+    /// The sum of all commission and delegator rewards.
+    pub total: RewardAmount,
+}
+
 /// A proof of the balance of an account in the fee ledger.
 ///
 /// If the account of interest does not exist in the fee state, this is a Merkle non-membership
@@ -56,3 +56,102 @@ pub type IndexedStake = (
     EpochNumber,
     IndexMap<alloy::primitives::Address, Validator<BLSPubKey>>,
 );
+
+/// Participation of a validator in consensus over the course of an epoch, indexed from decided
+/// blocks.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ValidatorActivity {
+    /// The number of blocks proposed by the validator.
+    pub proposals: u64,
+    /// The number of quorum certificates the validator's vote was included in.
+    pub votes: u64,
+}
+
+/// Overview of a validator in an epoch, as listed by the explorer.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ValidatorSummary {
+    pub account: Address,
+    pub stake_table_key: BLSPubKey,
+    pub stake: U256,
+    pub commission: u16,
+    /// The number of accounts delegating to the validator.
+    pub delegators: usize,
+    pub activity: ValidatorActivity,
+}
+
+impl ValidatorSummary {
+    pub fn new(validator: &Validator<BLSPubKey>, activity: ValidatorActivity) -> Self {
+        Self {
+            account: validator.account,
+            stake_table_key: validator.stake_table_key,
+            stake: validator.stake,
+            commission: validator.commission,
+            delegators: validator.delegators.len(),
+            activity,
+        }
+    }
+}
+
+/// A validator's full stake table entry in an epoch, along with its activity in that epoch.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ValidatorDetail {
+    pub epoch: EpochNumber,
+    pub validator: Validator<BLSPubKey>,
+    pub activity: ValidatorActivity,
+}
+
+/// The stake delegated by an account in an epoch.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct DelegatorDetail {
+    pub epoch: EpochNumber,
+    pub address: Address,
+    /// The stake delegated to each validator, in stake table order.
+    pub positions: Vec<Delegator>,
+    pub total_stake: U256,
+}
+
+impl DelegatorDetail {
+    /// Collect the positions of `address` from the validator set of `epoch`.
+    pub fn new<'a>(
+        epoch: EpochNumber,
+        address: Address,
+        validators: impl IntoIterator<Item = &'a Validator<BLSPubKey>>,
+    ) -> Self {
+        let positions = validators
+            .into_iter()
+            .filter_map(|validator| {
+                Some(Delegator {
+                    address,
+                    validator: validator.account,
+                    stake: *validator.delegators.get(&address)?,
+                })
+            })
+            .collect::<Vec<_>>();
+        let total_stake = positions.iter().map(|position| position.stake).sum();
+        Self {
+            epoch,
+            address,
+            positions,
+            total_stake,
+        }
+    }
+}
+
+/// Response of the `get_validators` explorer endpoint.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ValidatorsResponse {
+    pub epoch: EpochNumber,
+    pub validators: Vec<ValidatorSummary>,
+}
+
+/// Response of the `get_validator_detail` explorer endpoint.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ValidatorDetailResponse {
+    pub validator_detail: ValidatorDetail,
+}
+
+/// Response of the `get_delegator_detail` explorer endpoint.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DelegatorDetailResponse {
+    pub delegator_detail: DelegatorDetail,
+}
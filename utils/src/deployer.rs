@@ -13,6 +13,8 @@ use clap::{builder::OsStr, Parser};
 use derive_more::Display;
 use hotshot_contract_adapter::sol_types::*;
 
+pub mod verify;
+
 // We pass this during `forge bind --libraries` as a placeholder for the actual deployed library address
 const LIBRARY_PLACEHOLDER_ADDRESS: &str = "ffffffffffffffffffffffffffffffffffffffff";
 /// `stateHistoryRetentionPeriod` in LightClient.sol as the maximum retention period in seconds
@@ -47,6 +49,10 @@ pub struct DeployedContracts {
     #[clap(long, env = Contract::FeeContractProxy)]
     fee_contract_proxy: Option<Address>,
 
+    /// Use an already-deployed FeeWithdrawalVerifier.sol instead of deploying a new one.
+    #[clap(long, env = Contract::FeeWithdrawalVerifier)]
+    fee_withdrawal_verifier: Option<Address>,
+
     /// Use an already-deployed PermissonedStakeTable.sol proxy instead of deploying a new one.
     #[clap(long, env = Contract::PermissonedStakeTable)]
     permissioned_stake_table: Option<Address>,
@@ -85,6 +91,8 @@ pub enum Contract {
     FeeContract,
     #[display("ESPRESSO_SEQUENCER_FEE_CONTRACT_PROXY_ADDRESS")]
     FeeContractProxy,
+    #[display("ESPRESSO_SEQUENCER_FEE_WITHDRAWAL_VERIFIER_ADDRESS")]
+    FeeWithdrawalVerifier,
     #[display("ESPRESSO_SEQUENCER_PERMISSIONED_STAKE_TABLE_ADDRESS")]
     PermissonedStakeTable,
     #[display("ESPRESSO_SEQUENCER_ESP_TOKEN_ADDRESS")]
@@ -131,6 +139,9 @@ impl From<DeployedContracts> for Contracts {
         if let Some(addr) = deployed.fee_contract_proxy {
             m.insert(Contract::FeeContractProxy, addr);
         }
+        if let Some(addr) = deployed.fee_withdrawal_verifier {
+            m.insert(Contract::FeeWithdrawalVerifier, addr);
+        }
         if let Some(addr) = deployed.permissioned_stake_table {
             m.insert(Contract::PermissonedStakeTable, addr);
         }
@@ -443,6 +454,43 @@ pub async fn deploy_fee_contract_proxy(
     Ok(fee_proxy_addr)
 }
 
+/// Deploy the verifier of fee withdrawals, checking attestations signed by `attester` against
+/// the light client at `light_client`, and enable withdrawals on the fee contract with it.
+///
+/// The fee contract must already be deployed, and `provider` must be its owner.
+/// Return the verifier address.
+pub async fn deploy_fee_withdrawal_verifier(
+    provider: impl Provider,
+    contracts: &mut Contracts,
+    light_client: Address,
+    attester: Address,
+    owner: Address,
+) -> Result<Address> {
+    let fee_proxy_addr = contracts
+        .address(Contract::FeeContractProxy)
+        .ok_or_else(|| anyhow!("FeeContractProxy not found, can't enable withdrawals"))?;
+    let verifier_addr = contracts
+        .deploy(
+            Contract::FeeWithdrawalVerifier,
+            FeeWithdrawalVerifier::deploy_builder(&provider, light_client, attester, owner),
+        )
+        .await?;
+
+    let fee = FeeContract::new(fee_proxy_addr, &provider);
+    let receipt = fee
+        .setWithdrawalVerifier(verifier_addr)
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+    if !receipt.inner.is_success() {
+        return Err(anyhow!("failed to set the fee withdrawal verifier"));
+    }
+    tracing::info!(%fee_proxy_addr, %verifier_addr, "fee withdrawals enabled");
+
+    Ok(verifier_addr)
+}
+
 /// The primary logic for deploying permissioned stake table contract.
 /// Return the contract address.
 pub async fn deploy_permissioned_stake_table(
@@ -547,6 +595,16 @@ pub async fn transfer_ownership(
                 .get_receipt()
                 .await?
         },
+        Contract::FeeWithdrawalVerifier => {
+            tracing::info!(%addr, %new_owner, "Transfer FeeWithdrawalVerifier ownership");
+            let verifier = FeeWithdrawalVerifier::new(addr, &provider);
+            verifier
+                .transferOwnership(new_owner)
+                .send()
+                .await?
+                .get_receipt()
+                .await?
+        },
         Contract::PermissonedStakeTable => {
             tracing::info!(%addr, %new_owner, "Transfer PermissionedStakeTable ownership");
             let st = PermissionedStakeTable::new(addr, &provider);
@@ -583,7 +641,15 @@ pub async fn transfer_ownership(
 
 /// helper function to decide if the contract at given address `addr` is a proxy contract
 pub async fn is_proxy_contract(provider: impl Provider, addr: Address) -> Result<bool> {
-    // confirm that the proxy_address is a proxy
+    Ok(implementation_address(provider, addr).await?.is_some())
+}
+
+/// helper function to read the implementation address of the proxy contract at `addr`, or `None`
+/// if `addr` is not a proxy contract
+pub async fn implementation_address(
+    provider: impl Provider,
+    addr: Address,
+) -> Result<Option<Address>> {
     // using the implementation slot, 0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc, which is the keccak-256 hash of "eip1967.proxy.implementation" subtracted by 1
     let impl_slot = U256::from_str_radix(
         "360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc",
@@ -593,7 +659,7 @@ pub async fn is_proxy_contract(provider: impl Provider, addr: Address) -> Result
     let impl_address = Address::from_slice(&storage.to_be_bytes_vec()[12..]);
 
     // when the implementation address is not equal to zero, it's a proxy
-    Ok(impl_address != Address::default())
+    Ok((impl_address != Address::default()).then_some(impl_address))
 }
 
 #[cfg(test)]
@@ -770,6 +836,57 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_deploy_fee_withdrawal_verifier() -> Result<()> {
+        let provider = ProviderBuilder::new().on_anvil_with_wallet();
+        let mut contracts = Contracts::new();
+        let admin = provider.get_accounts().await?[0];
+        let light_client = Address::random();
+        let attester = Address::random();
+
+        // the fee contract must be deployed first
+        assert!(deploy_fee_withdrawal_verifier(
+            &provider,
+            &mut contracts,
+            light_client,
+            attester,
+            admin
+        )
+        .await
+        .is_err());
+
+        let fee_proxy_addr = deploy_fee_contract_proxy(&provider, &mut contracts, admin).await?;
+        let verifier_addr = deploy_fee_withdrawal_verifier(
+            &provider,
+            &mut contracts,
+            light_client,
+            attester,
+            admin,
+        )
+        .await?;
+
+        // check the verifier is configured and enabled on the fee contract
+        let verifier = FeeWithdrawalVerifier::new(verifier_addr, &provider);
+        assert_eq!(verifier.lightClient().call().await?._0, light_client);
+        assert_eq!(verifier.attester().call().await?._0, attester);
+        assert_eq!(verifier.owner().call().await?._0, admin);
+        let fee = FeeContract::new(fee_proxy_addr, &provider);
+        assert_eq!(fee.withdrawalVerifier().call().await?._0, verifier_addr);
+
+        // test transfer ownership to multisig
+        let multisig = Address::random();
+        let _receipt = transfer_ownership(
+            &provider,
+            Contract::FeeWithdrawalVerifier,
+            verifier_addr,
+            multisig,
+        )
+        .await?;
+        assert_eq!(verifier.owner().call().await?._0, multisig);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_deploy_permissioned_stake_table() -> Result<()> {
         let provider = ProviderBuilder::new().on_anvil_with_wallet();
@@ -0,0 +1,919 @@
+//! Verification of an existing deployment against the expected contracts and configuration.
+//!
+//! Nothing here sends transactions: every check is a read of on-chain code, storage or a view
+//! function, and its outcome is recorded in a [`DriftReport`] rather than aborting the audit, so
+//! that a single run reports every discrepancy at once.
+
+use std::fmt::Display;
+
+use alloy::{
+    primitives::{Address, Bytes, U256},
+    providers::Provider,
+};
+use anyhow::Result;
+use hotshot_contract_adapter::sol_types::*;
+use serde::{Deserialize, Serialize};
+
+use super::{implementation_address, Contract, Contracts, MAX_HISTORY_RETENTION_SECONDS};
+
+/// The configuration a deployment is expected to have.
+///
+/// Any expectation left unset is not checked, and the corresponding check is reported as
+/// [`CheckStatus::Skipped`] along with the actual on-chain value.
+#[derive(Clone, Debug, Default)]
+pub struct DeploymentExpectations {
+    /// Owner of every ownable contract, i.e. the multisig or else the deployer account.
+    pub owner: Option<Address>,
+    /// Permissioned prover of the light client, or `None` if proving should be permissionless.
+    pub permissioned_prover: Option<Address>,
+    /// Genesis light client state passed to `LightClient.initialize()`.
+    pub genesis_state: Option<LightClientStateSol>,
+    /// Genesis stake table state passed to `LightClient.initialize()`, which commits to the stake
+    /// table capacity of the prover circuit.
+    pub genesis_stake: Option<StakeTableStateSol>,
+    /// Epoch length passed to `LightClientV2.initializeV2()`.
+    pub blocks_per_epoch: Option<u64>,
+    /// First block of epoch 1 passed to `LightClientV2.initializeV2()`.
+    pub epoch_start_block: Option<u64>,
+    /// Exit escrow period passed to `StakeTable.initialize()`.
+    pub exit_escrow_period: Option<U256>,
+    /// Attester whose signatures the fee withdrawal verifier accepts.
+    pub attester: Option<Address>,
+    /// Whether the light client is expected to be the mock contract used in tests.
+    pub use_mock: bool,
+}
+
+/// Outcome of a single check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// The on-chain value is the expected one.
+    Match,
+    /// The on-chain value differs from the expected one.
+    Drift,
+    /// Nothing was expected, so only the on-chain value is reported.
+    Skipped,
+    /// The on-chain value could not be read.
+    Error,
+}
+
+/// A single comparison between an expected and an on-chain value.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Check {
+    /// The contract checked, e.g. `LightClientProxy`.
+    pub contract: String,
+    /// The property checked, e.g. `owner`.
+    pub check: String,
+    /// Whether the on-chain value is the expected one.
+    pub status: CheckStatus,
+    /// The expected value, if any.
+    pub expected: Option<String>,
+    /// The on-chain value, unless it could not be read.
+    pub actual: Option<String>,
+    /// The error reading the on-chain value, for [`CheckStatus::Error`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Machine-readable result of [`verify_deployment`].
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DriftReport {
+    /// Every check performed, in order.
+    pub checks: Vec<Check>,
+}
+
+impl DriftReport {
+    /// Whether any check found a discrepancy or could not be completed.
+    pub fn has_drift(&self) -> bool {
+        !self.drift().is_empty()
+    }
+
+    /// The checks which found a discrepancy or could not be completed.
+    pub fn drift(&self) -> Vec<&Check> {
+        self.checks
+            .iter()
+            .filter(|check| matches!(check.status, CheckStatus::Drift | CheckStatus::Error))
+            .collect()
+    }
+
+    fn push(
+        &mut self,
+        contract: Contract,
+        check: &str,
+        status: CheckStatus,
+        expected: Option<String>,
+        actual: Option<String>,
+        error: Option<String>,
+    ) {
+        if status == CheckStatus::Drift {
+            tracing::warn!(?contract, check, ?expected, ?actual, "deployment drift");
+        }
+        self.checks.push(Check {
+            contract: format!("{contract:?}"),
+            check: check.to_string(),
+            status,
+            expected,
+            actual,
+            error,
+        });
+    }
+
+    /// Record a comparison between `expected`, if any, and the on-chain value `actual`.
+    fn compare<T: PartialEq + Display>(
+        &mut self,
+        contract: Contract,
+        check: &str,
+        expected: Option<T>,
+        actual: Result<T>,
+    ) {
+        match actual {
+            Ok(actual) => {
+                let status = match &expected {
+                    None => CheckStatus::Skipped,
+                    Some(expected) if *expected == actual => CheckStatus::Match,
+                    Some(_) => CheckStatus::Drift,
+                };
+                self.push(
+                    contract,
+                    check,
+                    status,
+                    expected.map(|v| v.to_string()),
+                    Some(actual.to_string()),
+                    None,
+                );
+            },
+            Err(err) => self.push(
+                contract,
+                check,
+                CheckStatus::Error,
+                expected.map(|v| v.to_string()),
+                None,
+                Some(format!("{err:#}")),
+            ),
+        }
+    }
+}
+
+/// Compiled runtime code of a contract, and the library linked into it, if any.
+struct Artifact {
+    name: &'static str,
+    code: &'static Bytes,
+    library: Option<(Contract, &'static Bytes)>,
+}
+
+fn light_client_artifacts(use_mock: bool) -> Vec<Artifact> {
+    let v1 = Some((Contract::PlonkVerifier, &PlonkVerifier::DEPLOYED_BYTECODE));
+    let v2 = Some((
+        Contract::PlonkVerifierV2,
+        &PlonkVerifierV2::DEPLOYED_BYTECODE,
+    ));
+    if use_mock {
+        vec![
+            Artifact {
+                name: "LightClientMock",
+                code: &LightClientMock::DEPLOYED_BYTECODE,
+                library: v1,
+            },
+            Artifact {
+                name: "LightClientV2Mock",
+                code: &LightClientV2Mock::DEPLOYED_BYTECODE,
+                library: v2,
+            },
+        ]
+    } else {
+        vec![
+            Artifact {
+                name: "LightClient",
+                code: &LightClient::DEPLOYED_BYTECODE,
+                library: v1,
+            },
+            Artifact {
+                name: "LightClientV2",
+                code: &LightClientV2::DEPLOYED_BYTECODE,
+                library: v2,
+            },
+        ]
+    }
+}
+
+/// Whether `actual`, the code deployed at `addr`, is the compiled runtime code `expected`.
+///
+/// Two parts of the deployed code legitimately differ from the compiled artifact, and are
+/// normalized before comparing:
+/// * library addresses, which are linked in place of the placeholder address passed to
+///   `forge bind --libraries`. The linked address is returned, so it can be checked separately.
+/// * address immutables, which the artifact has as zeros. These are the contract's own address,
+///   which libraries and UUPS implementations embed as the `__self` immutable, and any other
+///   addresses in `immutables` the contract was constructed with.
+///
+/// Returns `None` if the code does not match, or if it links different addresses in place of the
+/// same placeholder.
+fn match_bytecode(
+    expected: &[u8],
+    actual: &[u8],
+    addr: Address,
+    immutables: &[Address],
+) -> Option<Option<Address>> {
+    if expected.len() != actual.len() {
+        return None;
+    }
+    let placeholder = [0xff; 20];
+    let mut normalized = actual.to_vec();
+    let mut library = None;
+
+    for i in 0..=expected.len().saturating_sub(20) {
+        let window = i..i + 20;
+        if expected[window.clone()] == placeholder {
+            let linked = Address::from_slice(&actual[window.clone()]);
+            if library.is_some_and(|lib| lib != linked) {
+                return None;
+            }
+            library = Some(linked);
+            normalized[window].copy_from_slice(&placeholder);
+        } else if expected[window.clone()] == [0; 20]
+            && (actual[window.clone()] == addr[..]
+                || immutables
+                    .iter()
+                    .any(|immutable| actual[window.clone()] == immutable[..]))
+        {
+            normalized[window].fill(0);
+        }
+    }
+
+    (normalized == expected).then_some(library)
+}
+
+/// Check the deployed code at `addr` against the candidate `artifacts`, and any library it links
+/// against the compiled library and the address recorded in `contracts`.
+///
+/// `immutables` are the addresses the contract is expected to have been constructed with.
+///
+/// Returns the name of the matching artifact, if any.
+async fn check_bytecode(
+    provider: impl Provider,
+    report: &mut DriftReport,
+    contracts: &Contracts,
+    contract: Contract,
+    addr: Address,
+    immutables: &[Address],
+    artifacts: &[Artifact],
+) -> Option<&'static str> {
+    let expected = artifacts
+        .iter()
+        .map(|artifact| artifact.name)
+        .collect::<Vec<_>>()
+        .join(" | ");
+    let code = match provider.get_code_at(addr).await {
+        Ok(code) => code,
+        Err(err) => {
+            report.compare::<String>(
+                contract,
+                "implementation_bytecode",
+                Some(expected),
+                Err(err.into()),
+            );
+            return None;
+        },
+    };
+
+    let Some((artifact, library)) = artifacts.iter().find_map(|artifact| {
+        match_bytecode(artifact.code, &code, addr, immutables).map(|library| (artifact, library))
+    }) else {
+        report.push(
+            contract,
+            "implementation_bytecode",
+            CheckStatus::Drift,
+            Some(expected),
+            Some(format!("unknown code of {} bytes at {addr:#x}", code.len())),
+            None,
+        );
+        return None;
+    };
+    report.push(
+        contract,
+        "implementation_bytecode",
+        CheckStatus::Match,
+        Some(expected),
+        Some(artifact.name.to_string()),
+        None,
+    );
+
+    if let (Some((library_contract, library_code)), Some(library_addr)) =
+        (artifact.library, library)
+    {
+        report.compare(
+            library_contract,
+            "linked_address",
+            contracts.address(library_contract),
+            Ok(library_addr),
+        );
+        let matches = provider
+            .get_code_at(library_addr)
+            .await
+            .map(|code| match_bytecode(library_code, &code, library_addr, &[]).is_some());
+        report.compare(
+            library_contract,
+            "bytecode",
+            Some(true),
+            matches.map_err(Into::into),
+        );
+    }
+
+    Some(artifact.name)
+}
+
+/// Check that `proxy` is an ERC1967 proxy whose implementation is the one recorded in
+/// `contracts`, and runs one of `artifacts`.
+///
+/// Returns the name of the matching artifact, if any.
+async fn check_proxy(
+    provider: impl Provider,
+    report: &mut DriftReport,
+    contracts: &Contracts,
+    proxy: Contract,
+    implementation: &[Contract],
+    artifacts: &[Artifact],
+) -> Option<&'static str> {
+    let proxy_addr = contracts.address(proxy)?;
+    let impl_addr = match implementation_address(&provider, proxy_addr).await {
+        Ok(Some(addr)) => addr,
+        Ok(None) => {
+            report.push(
+                proxy,
+                "proxy",
+                CheckStatus::Drift,
+                Some("ERC1967 proxy".to_string()),
+                Some("no implementation".to_string()),
+                None,
+            );
+            return None;
+        },
+        Err(err) => {
+            report.compare::<Address>(proxy, "implementation_address", None, Err(err));
+            return None;
+        },
+    };
+
+    // The most recently deployed implementation recorded, e.g. LightClientV2 over LightClient.
+    let expected = implementation
+        .iter()
+        .find_map(|contract| contracts.address(*contract));
+    report.compare(proxy, "implementation_address", expected, Ok(impl_addr));
+
+    check_bytecode(
+        &provider,
+        report,
+        contracts,
+        proxy,
+        impl_addr,
+        &[],
+        artifacts,
+    )
+    .await
+}
+
+/// Check every contract recorded in `contracts` against its compiled bindings and `expected`.
+pub async fn verify_deployment(
+    provider: impl Provider,
+    contracts: &Contracts,
+    expected: &DeploymentExpectations,
+) -> DriftReport {
+    let mut report = DriftReport::default();
+
+    verify_light_client(&provider, &mut report, contracts, expected).await;
+    verify_fee_contract(&provider, &mut report, contracts, expected).await;
+    verify_fee_withdrawal_verifier(&provider, &mut report, contracts, expected).await;
+    verify_permissioned_stake_table(&provider, &mut report, contracts, expected).await;
+    verify_token(&provider, &mut report, contracts, expected).await;
+    verify_stake_table(&provider, &mut report, contracts, expected).await;
+
+    report
+}
+
+async fn verify_light_client(
+    provider: impl Provider,
+    report: &mut DriftReport,
+    contracts: &Contracts,
+    expected: &DeploymentExpectations,
+) {
+    let contract = Contract::LightClientProxy;
+    let Some(addr) = contracts.address(contract) else {
+        return;
+    };
+    let artifact = check_proxy(
+        &provider,
+        report,
+        contracts,
+        contract,
+        &[Contract::LightClientV2, Contract::LightClient],
+        &light_client_artifacts(expected.use_mock),
+    )
+    .await;
+
+    // All versions share the V1 storage layout and view functions.
+    let lc = LightClient::new(addr, &provider);
+    report.compare(
+        contract,
+        "owner",
+        expected.owner,
+        lc.owner().call().await.map(|r| r._0).map_err(Into::into),
+    );
+    report.compare(
+        contract,
+        "permissioned_prover",
+        Some(expected.permissioned_prover.unwrap_or_default()),
+        lc.permissionedProver()
+            .call()
+            .await
+            .map(|r| r._0)
+            .map_err(Into::into),
+    );
+    report.compare(
+        contract,
+        "state_history_retention_period",
+        Some(MAX_HISTORY_RETENTION_SECONDS),
+        lc.stateHistoryRetentionPeriod()
+            .call()
+            .await
+            .map(|r| r._0)
+            .map_err(Into::into),
+    );
+    report.compare(
+        contract,
+        "genesis_state",
+        expected
+            .genesis_state
+            .as_ref()
+            .map(|s| format_state(s.viewNum, s.blockHeight, s.blockCommRoot)),
+        lc.genesisState()
+            .call()
+            .await
+            .map(|s| format_state(s.viewNum, s.blockHeight, s.blockCommRoot))
+            .map_err(Into::into),
+    );
+    report.compare(
+        contract,
+        "genesis_stake_table_state",
+        expected
+            .genesis_stake
+            .as_ref()
+            .map(|s| format_stake_table(s.threshold, s.blsKeyComm, s.schnorrKeyComm, s.amountComm)),
+        lc.genesisStakeTableState()
+            .call()
+            .await
+            .map(|s| format_stake_table(s.threshold, s.blsKeyComm, s.schnorrKeyComm, s.amountComm))
+            .map_err(Into::into),
+    );
+
+    let is_v2 = matches!(artifact, Some("LightClientV2" | "LightClientV2Mock"));
+    if is_v2 {
+        let lcv2 = LightClientV2::new(addr, &provider);
+        report.compare(
+            contract,
+            "blocks_per_epoch",
+            expected.blocks_per_epoch,
+            lcv2.blocksPerEpoch()
+                .call()
+                .await
+                .map(|r| r._0)
+                .map_err(Into::into),
+        );
+        report.compare(
+            contract,
+            "epoch_start_block",
+            expected.epoch_start_block,
+            lcv2.epochStartBlock()
+                .call()
+                .await
+                .map(|r| r._0)
+                .map_err(Into::into),
+        );
+    } else if expected.blocks_per_epoch.is_some() || expected.epoch_start_block.is_some() {
+        report.push(
+            contract,
+            "version",
+            CheckStatus::Drift,
+            Some("LightClientV2".to_string()),
+            artifact.map(str::to_string),
+            None,
+        );
+    }
+}
+
+async fn verify_fee_contract(
+    provider: impl Provider,
+    report: &mut DriftReport,
+    contracts: &Contracts,
+    expected: &DeploymentExpectations,
+) {
+    let contract = Contract::FeeContractProxy;
+    let Some(addr) = contracts.address(contract) else {
+        return;
+    };
+    check_proxy(
+        &provider,
+        report,
+        contracts,
+        contract,
+        &[Contract::FeeContract],
+        &[Artifact {
+            name: "FeeContract",
+            code: &FeeContract::DEPLOYED_BYTECODE,
+            library: None,
+        }],
+    )
+    .await;
+
+    let fee = FeeContract::new(addr, &provider);
+    report.compare(
+        contract,
+        "owner",
+        expected.owner,
+        fee.owner().call().await.map(|r| r._0).map_err(Into::into),
+    );
+    // Withdrawals are expected to be disabled unless a verifier was deployed.
+    report.compare(
+        contract,
+        "withdrawal_verifier",
+        Some(
+            contracts
+                .address(Contract::FeeWithdrawalVerifier)
+                .unwrap_or_default(),
+        ),
+        fee.withdrawalVerifier()
+            .call()
+            .await
+            .map(|r| r._0)
+            .map_err(Into::into),
+    );
+}
+
+async fn verify_fee_withdrawal_verifier(
+    provider: impl Provider,
+    report: &mut DriftReport,
+    contracts: &Contracts,
+    expected: &DeploymentExpectations,
+) {
+    let contract = Contract::FeeWithdrawalVerifier;
+    let Some(addr) = contracts.address(contract) else {
+        return;
+    };
+    let light_client = contracts.address(Contract::LightClientProxy);
+    check_bytecode(
+        &provider,
+        report,
+        contracts,
+        contract,
+        addr,
+        // The light client is an immutable of the verifier.
+        light_client.as_slice(),
+        &[Artifact {
+            name: "FeeWithdrawalVerifier",
+            code: &FeeWithdrawalVerifier::DEPLOYED_BYTECODE,
+            library: None,
+        }],
+    )
+    .await;
+
+    let verifier = FeeWithdrawalVerifier::new(addr, &provider);
+    report.compare(
+        contract,
+        "owner",
+        expected.owner,
+        verifier
+            .owner()
+            .call()
+            .await
+            .map(|r| r._0)
+            .map_err(Into::into),
+    );
+    report.compare(
+        contract,
+        "light_client",
+        light_client,
+        verifier
+            .lightClient()
+            .call()
+            .await
+            .map(|r| r._0)
+            .map_err(Into::into),
+    );
+    report.compare(
+        contract,
+        "attester",
+        expected.attester,
+        verifier
+            .attester()
+            .call()
+            .await
+            .map(|r| r._0)
+            .map_err(Into::into),
+    );
+}
+
+async fn verify_permissioned_stake_table(
+    provider: impl Provider,
+    report: &mut DriftReport,
+    contracts: &Contracts,
+    expected: &DeploymentExpectations,
+) {
+    let contract = Contract::PermissonedStakeTable;
+    let Some(addr) = contracts.address(contract) else {
+        return;
+    };
+    check_bytecode(
+        &provider,
+        report,
+        contracts,
+        contract,
+        addr,
+        &[],
+        &[Artifact {
+            name: "PermissionedStakeTable",
+            code: &PermissionedStakeTable::DEPLOYED_BYTECODE,
+            library: None,
+        }],
+    )
+    .await;
+
+    let st = PermissionedStakeTable::new(addr, &provider);
+    report.compare(
+        contract,
+        "owner",
+        expected.owner,
+        st.owner().call().await.map(|r| r._0).map_err(Into::into),
+    );
+}
+
+async fn verify_token(
+    provider: impl Provider,
+    report: &mut DriftReport,
+    contracts: &Contracts,
+    expected: &DeploymentExpectations,
+) {
+    let contract = Contract::EspTokenProxy;
+    let Some(addr) = contracts.address(contract) else {
+        return;
+    };
+    check_proxy(
+        &provider,
+        report,
+        contracts,
+        contract,
+        &[Contract::EspToken],
+        &[Artifact {
+            name: "EspToken",
+            code: &EspToken::DEPLOYED_BYTECODE,
+            library: None,
+        }],
+    )
+    .await;
+
+    let token = EspToken::new(addr, &provider);
+    report.compare(
+        contract,
+        "owner",
+        expected.owner,
+        token.owner().call().await.map(|r| r._0).map_err(Into::into),
+    );
+}
+
+async fn verify_stake_table(
+    provider: impl Provider,
+    report: &mut DriftReport,
+    contracts: &Contracts,
+    expected: &DeploymentExpectations,
+) {
+    let contract = Contract::StakeTableProxy;
+    let Some(addr) = contracts.address(contract) else {
+        return;
+    };
+    check_proxy(
+        &provider,
+        report,
+        contracts,
+        contract,
+        &[Contract::StakeTable],
+        &[Artifact {
+            name: "StakeTable",
+            code: &StakeTable::DEPLOYED_BYTECODE,
+            library: None,
+        }],
+    )
+    .await;
+
+    let stake_table = StakeTable::new(addr, &provider);
+    report.compare(
+        contract,
+        "owner",
+        expected.owner,
+        stake_table
+            .owner()
+            .call()
+            .await
+            .map(|r| r._0)
+            .map_err(Into::into),
+    );
+    report.compare(
+        contract,
+        "token",
+        contracts.address(Contract::EspTokenProxy),
+        stake_table
+            .token()
+            .call()
+            .await
+            .map(|r| r._0)
+            .map_err(Into::into),
+    );
+    report.compare(
+        contract,
+        "light_client",
+        contracts.address(Contract::LightClientProxy),
+        stake_table
+            .lightClient()
+            .call()
+            .await
+            .map(|r| r._0)
+            .map_err(Into::into),
+    );
+    report.compare(
+        contract,
+        "exit_escrow_period",
+        expected.exit_escrow_period,
+        stake_table
+            .exitEscrowPeriod()
+            .call()
+            .await
+            .map(|r| r._0)
+            .map_err(Into::into),
+    );
+}
+
+fn format_state(view_num: u64, block_height: u64, block_comm_root: U256) -> String {
+    format!("viewNum={view_num} blockHeight={block_height} blockCommRoot={block_comm_root}")
+}
+
+fn format_stake_table(threshold: U256, bls: U256, schnorr: U256, amount: U256) -> String {
+    format!("threshold={threshold} blsKeyComm={bls} schnorrKeyComm={schnorr} amountComm={amount}")
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::providers::ProviderBuilder;
+
+    use super::*;
+    use crate::{
+        deployer::{
+            deploy_fee_contract_proxy, deploy_fee_withdrawal_verifier, deploy_light_client_proxy,
+            transfer_ownership,
+        },
+        test_utils::setup_test,
+    };
+
+    fn status(report: &DriftReport, contract: Contract, check: &str) -> CheckStatus {
+        report
+            .checks
+            .iter()
+            .find(|c| c.contract == format!("{contract:?}") && c.check == check)
+            .unwrap_or_else(|| panic!("no {check} check for {contract:?}"))
+            .status
+    }
+
+    #[tokio::test]
+    async fn test_verify_deployment() -> Result<()> {
+        setup_test();
+        let provider = ProviderBuilder::new().on_anvil_with_wallet();
+        let mut contracts = Contracts::new();
+
+        let genesis_state = LightClientStateSol::dummy_genesis();
+        let genesis_stake = StakeTableStateSol::dummy_genesis();
+        let admin = provider.get_accounts().await?[0];
+        let prover = Address::random();
+
+        deploy_light_client_proxy(
+            &provider,
+            &mut contracts,
+            false,
+            genesis_state.clone(),
+            genesis_stake.clone(),
+            admin,
+            Some(prover),
+        )
+        .await?;
+        let fee_proxy = deploy_fee_contract_proxy(&provider, &mut contracts, admin).await?;
+
+        let mut expected = DeploymentExpectations {
+            owner: Some(admin),
+            permissioned_prover: Some(prover),
+            genesis_state: Some(genesis_state),
+            genesis_stake: Some(genesis_stake),
+            ..Default::default()
+        };
+        let report = verify_deployment(&provider, &contracts, &expected).await;
+        assert!(!report.has_drift(), "{report:#?}");
+        for contract in [Contract::LightClientProxy, Contract::FeeContractProxy] {
+            assert_eq!(
+                status(&report, contract, "implementation_bytecode"),
+                CheckStatus::Match
+            );
+        }
+
+        // Enabling withdrawals without recording the verifier is drift.
+        let light_client = contracts.address(Contract::LightClientProxy).unwrap();
+        let attester = Address::random();
+        let unrecorded = contracts.clone();
+        deploy_fee_withdrawal_verifier(&provider, &mut contracts, light_client, attester, admin)
+            .await?;
+        let report = verify_deployment(&provider, &unrecorded, &expected).await;
+        assert_eq!(
+            status(&report, Contract::FeeContractProxy, "withdrawal_verifier"),
+            CheckStatus::Drift
+        );
+
+        // Once recorded, the verifier and its wiring are checked.
+        expected.attester = Some(attester);
+        let report = verify_deployment(&provider, &contracts, &expected).await;
+        assert!(!report.has_drift(), "{report:#?}");
+        assert_eq!(
+            status(&report, Contract::FeeContractProxy, "withdrawal_verifier"),
+            CheckStatus::Match
+        );
+        for check in [
+            "implementation_bytecode",
+            "owner",
+            "light_client",
+            "attester",
+        ] {
+            assert_eq!(
+                status(&report, Contract::FeeWithdrawalVerifier, check),
+                CheckStatus::Match
+            );
+        }
+        assert_eq!(
+            status(&report, Contract::PlonkVerifier, "linked_address"),
+            CheckStatus::Match
+        );
+
+        // Handing the fee contract to a multisig, and expecting a different prover, are drift.
+        let multisig = Address::random();
+        transfer_ownership(&provider, Contract::FeeContractProxy, fee_proxy, multisig).await?;
+        expected.permissioned_prover = None;
+        let report = verify_deployment(&provider, &contracts, &expected).await;
+        assert!(report.has_drift());
+        assert_eq!(
+            status(&report, Contract::FeeContractProxy, "owner"),
+            CheckStatus::Drift
+        );
+        assert_eq!(
+            status(&report, Contract::LightClientProxy, "permissioned_prover"),
+            CheckStatus::Drift
+        );
+        assert_eq!(report.drift().len(), 2);
+
+        // Expecting the light client to run the mock contract is drift, too.
+        expected.use_mock = true;
+        let report = verify_deployment(&provider, &contracts, &expected).await;
+        assert_eq!(
+            status(
+                &report,
+                Contract::LightClientProxy,
+                "implementation_bytecode"
+            ),
+            CheckStatus::Drift
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_match_bytecode() {
+        let addr = Address::random();
+        let library = Address::random();
+
+        // PUSH20 <placeholder> ... PUSH32 <immutable self address>
+        let mut expected = vec![0x73];
+        expected.extend([0xff; 20]);
+        expected.push(0x7f);
+        expected.extend([0; 32]);
+
+        let mut actual = vec![0x73];
+        actual.extend(library.as_slice());
+        actual.push(0x7f);
+        actual.extend([0; 12]);
+        actual.extend(addr.as_slice());
+
+        assert_eq!(
+            match_bytecode(&expected, &actual, addr, &[]),
+            Some(Some(library))
+        );
+        // Any other immutable value is a mismatch, unless the contract is expected to have been
+        // constructed with it.
+        let other = Address::random();
+        assert_eq!(match_bytecode(&expected, &actual, other, &[]), None);
+        assert_eq!(
+            match_bytecode(&expected, &actual, other, &[addr]),
+            Some(Some(library))
+        );
+        assert_eq!(match_bytecode(&expected, &actual[1..], addr, &[]), None);
+    }
+}
@@ -33,7 +33,7 @@ pub struct NsAvidMShare {
 }
 
 impl NsAvidMShare {
-    fn inner_ns_share(&self, ns_id: usize) -> AvidMShare {
+    pub(crate) fn inner_ns_share(&self, ns_id: usize) -> AvidMShare {
         AvidMShare {
             index: self.index,
             payload_byte_len: self.ns_lens[ns_id],
@@ -45,6 +45,18 @@ impl NsAvidMShare {
     pub fn payload_byte_len(&self) -> usize {
         self.ns_lens.iter().sum()
     }
+
+    /// Return the list of all namespace commitments
+    pub fn ns_commits(&self) -> &[AvidMCommit] {
+        &self.ns_commits
+    }
+
+    /// Return the weight of this share, i.e. the number of raw shares it holds
+    pub fn weight(&self) -> usize {
+        self.content
+            .first()
+            .map_or(0, |content| content.range.len())
+    }
 }
 
 impl NsAvidMScheme {
@@ -81,13 +93,58 @@ impl NsAvidMScheme {
         distribution: &[u32],
         payload: &[u8],
         ns_table: impl IntoIterator<Item = Range<usize>>,
+    ) -> VidResult<(NsAvidMCommit, Vec<NsAvidMShare>)> {
+        Self::ns_disperse_with(payload, ns_table, |ns_payload| {
+            AvidMScheme::disperse(param, distribution, ns_payload)
+        })
+    }
+
+    /// Disperse a payload like [`Self::ns_disperse`], but with an incorrect
+    /// encoding for every namespace: the last raw share of each namespace is
+    /// tampered with before being committed to. Every resulting share still
+    /// verifies against the returned commitment, so the misbehavior can only
+    /// be caught with [`NsAvidMScheme::proof_of_incorrect_encoding`].
+    ///
+    /// This is only meant for testing the handling of malicious dispersers.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn ns_disperse_incorrectly(
+        param: &NsAvidMParam,
+        distribution: &[u32],
+        payload: &[u8],
+        ns_table: impl IntoIterator<Item = Range<usize>>,
+    ) -> VidResult<(NsAvidMCommit, Vec<NsAvidMShare>)> {
+        use crate::avid_m::{config::AvidMConfig, Config, F};
+
+        Self::ns_disperse_with(payload, ns_table, |ns_payload| {
+            let (_, mut raw_shares) = AvidMScheme::pad_and_encode(param, ns_payload)?;
+            if let Some(raw_share) = raw_shares.last_mut() {
+                raw_share[0] += F::from(1u64);
+            }
+            let mt = MerkleTree::from_elems(
+                None,
+                raw_shares
+                    .iter()
+                    .map(|raw_share| Config::raw_share_digest(raw_share))
+                    .collect::<Result<Vec<_>, _>>()?,
+            )?;
+            AvidMScheme::distribute_shares(param, distribution, mt, raw_shares, ns_payload.len())
+        })
+    }
+
+    /// Helper function.
+    /// Disperse each namespace of the payload with `ns_disperse` and assemble
+    /// the namespaced shares and commitment.
+    fn ns_disperse_with(
+        payload: &[u8],
+        ns_table: impl IntoIterator<Item = Range<usize>>,
+        ns_disperse: impl Fn(&[u8]) -> VidResult<(AvidMCommit, Vec<AvidMShare>)>,
     ) -> VidResult<(NsAvidMCommit, Vec<NsAvidMShare>)> {
         let mut ns_commits = vec![];
         let mut disperses = vec![];
         let mut ns_lens = vec![];
         for ns_range in ns_table {
             ns_lens.push(ns_range.len());
-            let (commit, shares) = AvidMScheme::disperse(param, distribution, &payload[ns_range])?;
+            let (commit, shares) = ns_disperse(&payload[ns_range])?;
             ns_commits.push(commit.commit);
             disperses.push(shares);
         }
@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     avid_m::{
         config::AvidMConfig,
-        namespaced::{NsAvidMCommit, NsAvidMScheme},
+        namespaced::{NsAvidMCommit, NsAvidMScheme, NsAvidMShare},
         AvidMCommit, AvidMParam, AvidMScheme, AvidMShare, Config, MerkleProof, MerkleTree, F,
     },
     VerificationResult, VidError, VidResult, VidScheme,
@@ -127,6 +127,91 @@ impl MalEncodingProof {
     }
 }
 
+/// A proof of incorrect encoding for a namespaced VID commitment.
+/// It pins down the namespace whose encoding is incorrect with a merkle proof of its commitment against the
+/// namespaced VID commitment, and carries a [`MalEncodingProof`] against that namespace commitment.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct NsMalEncodingProof {
+    /// The index of the incorrectly encoded namespace.
+    pub ns_index: usize,
+    /// The commitment of the incorrectly encoded namespace.
+    pub ns_commit: AvidMCommit,
+    /// The merkle proof of the namespace commitment against the namespaced VID commitment.
+    pub ns_commit_proof: MerkleProof,
+    /// The proof of incorrect encoding against the namespace commitment.
+    pub proof: MalEncodingProof,
+}
+
+impl NsAvidMScheme {
+    /// Generate a proof of incorrect encoding for a namespaced VID commitment.
+    /// Returns `VidError::Argument` if all namespaces are correctly encoded.
+    /// See [`NsMalEncodingProof`] for details.
+    pub fn proof_of_incorrect_encoding(
+        param: &AvidMParam,
+        commit: &NsAvidMCommit,
+        shares: &[NsAvidMShare],
+    ) -> VidResult<NsMalEncodingProof> {
+        if shares.is_empty() {
+            return Err(VidError::InsufficientShares);
+        }
+        // First verify all the shares, they will then share the same namespace commitments.
+        for share in shares.iter() {
+            if NsAvidMScheme::verify_share(param, commit, share)?.is_err() {
+                return Err(VidError::InvalidShare);
+            }
+        }
+        let ns_commits = shares[0].ns_commits();
+        let mt = MerkleTree::from_elems(None, ns_commits.iter().map(|ns_commit| ns_commit.commit))?;
+        for (ns_index, ns_commit) in ns_commits.iter().enumerate() {
+            let ns_shares: Vec<_> = shares
+                .iter()
+                .map(|share| share.inner_ns_share(ns_index))
+                .collect();
+            match AvidMScheme::proof_of_incorrect_encoding(param, ns_commit, &ns_shares) {
+                Ok(proof) => {
+                    return Ok(NsMalEncodingProof {
+                        ns_index,
+                        ns_commit: *ns_commit,
+                        ns_commit_proof: mt
+                            .lookup(ns_index as u64)
+                            .expect_ok()
+                            .expect("MT lookup shouldn't fail")
+                            .1,
+                        proof,
+                    });
+                },
+                // This namespace is correctly encoded, try the next one.
+                Err(VidError::Argument(_)) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Err(VidError::Argument(
+            "Cannot generate the proof of incorrect encoding: encoding is good.".to_string(),
+        ))
+    }
+}
+
+impl NsMalEncodingProof {
+    /// Verify a proof of incorrect encoding against a namespaced VID commitment.
+    pub fn verify(
+        &self,
+        param: &AvidMParam,
+        commit: &NsAvidMCommit,
+    ) -> VidResult<VerificationResult> {
+        if MerkleTree::verify(
+            &commit.commit,
+            self.ns_index as u64,
+            &self.ns_commit.commit,
+            &self.ns_commit_proof,
+        )?
+        .is_err()
+        {
+            return Ok(Err(()));
+        }
+        self.proof.verify(param, &self.ns_commit)
+    }
+}
+
 /// A proof of a namespace payload.
 /// It consists of the index of the namespace, the namespace payload, and a merkle proof
 /// of the namespace payload against the namespaced VID commitment.
@@ -262,6 +347,58 @@ mod tests {
         assert!(bad_proof2.verify(&param, &commit).is_err());
     }
 
+    #[test]
+    fn test_ns_proof_of_incorrect_encoding() {
+        let mut rng = jf_utils::test_rng();
+        let param = AvidMScheme::setup(4usize, 10usize).unwrap();
+        let weights = [2u32, 1, 3, 1, 1, 2];
+        let payload = vec![7u8; 100];
+        let ns_table = vec![(0..30), (30..100)];
+
+        // proof generation shall not work on a correct dispersal
+        let (commit, shares) =
+            NsAvidMScheme::ns_disperse(&param, &weights, &payload, ns_table.clone()).unwrap();
+        assert!(NsAvidMScheme::proof_of_incorrect_encoding(&param, &commit, &shares).is_err());
+
+        let (commit, mut shares) =
+            NsAvidMScheme::ns_disperse_incorrectly(&param, &weights, &payload, ns_table.clone())
+                .unwrap();
+        assert_ne!(
+            commit,
+            NsAvidMScheme::commit(&param, &payload, ns_table).unwrap()
+        );
+        shares.iter().for_each(|share| {
+            assert!(NsAvidMScheme::verify_share(&param, &commit, share).is_ok_and(|r| r.is_ok()))
+        });
+
+        // not enough shares
+        assert!(NsAvidMScheme::proof_of_incorrect_encoding(&param, &commit, &[]).is_err());
+        assert!(NsAvidMScheme::proof_of_incorrect_encoding(&param, &commit, &shares[..1]).is_err());
+
+        // successful proof generation once the shares reach the recovery threshold
+        shares.shuffle(&mut rng);
+        let mut weight = 0;
+        let mut cut_index = 0;
+        while weight < param.recovery_threshold {
+            weight += shares[cut_index].weight();
+            cut_index += 1;
+        }
+        let proof =
+            NsAvidMScheme::proof_of_incorrect_encoding(&param, &commit, &shares[..cut_index])
+                .unwrap();
+        assert!(proof.verify(&param, &commit).unwrap().is_ok());
+
+        // the proof is bound to the namespace it was generated for
+        let mut bad_proof = proof.clone();
+        bad_proof.ns_index = 1 - proof.ns_index;
+        assert!(bad_proof.verify(&param, &commit).unwrap().is_err());
+
+        // the proof doesn't verify against a different commitment
+        let (other_commit, _) =
+            NsAvidMScheme::ns_disperse(&param, &weights, &[1u8; 100], [0..30, 30..100]).unwrap();
+        assert!(proof.verify(&param, &other_commit).unwrap().is_err());
+    }
+
     #[test]
     fn test_ns_proof() {
         let param = AvidMScheme::setup(5usize, 10usize).unwrap();